@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: MIT
+
+//! Decode-time and allocation benchmarks for large CoRIMs.
+//!
+//! Run with `cargo bench --bench decode`. Each case decodes a CoRIM built by repeating the tags
+//! of the test fixtures and reports the median time per decode together with the number of
+//! allocations and bytes allocated by one decode. Cases decode with `cbor::from_slice`, which
+//! borrows text and byte strings from the input, apart from one `cbor::from_reader` case that
+//! copies them. The baseline cases first decode the input into a `ciborium::Value` and encode it
+//! again, as the deserializers of earlier releases did before decoding each choice type. They
+//! do so once for the whole input, so they understate the cost of nested choices.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use corim_rs::{
+    cbor::{from_reader, from_slice},
+    core::{
+        Bytes, Digest, ExtensionValue, HashAlgorithm, PkixBase64KeyType, TaggedUuidType, UeidType,
+        UuidType,
+    },
+    corim::{ConciseRimTypeChoice, ConciseTagTypeChoice, TaggedUnsignedCorimMap},
+    fixed_bytes::FixedBytes,
+    triples::{ClassIdTypeChoice, CryptoKeyTypeChoice, InstanceIdTypeChoice},
+};
+
+/// Counts the allocations made through the system allocator
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const UNSIGNED: &[u8] = include_bytes!("../testdata/corim/unsigned.cbor");
+const SIGNED: &[u8] = include_bytes!("../testdata/corim/signed.cbor");
+
+/// The number of copies of the fixture tags in the generated CoRIM
+const TAG_COPIES: usize = 500;
+
+/// The number of timed runs of each case
+const RUNS: usize = 25;
+
+/// Repeats the tags of the unsigned fixture and returns the encoded CoRIM
+fn large_corim() -> Vec<u8> {
+    let corim: ConciseRimTypeChoice = from_slice(UNSIGNED).expect("fixture decodes");
+    let mut map = corim.as_unsigned_corim_map().expect("fixture is unsigned");
+    let tags = std::mem::take(&mut map.tags);
+    map.tags = tags
+        .iter()
+        .cycle()
+        .take(tags.len() * TAG_COPIES)
+        .cloned()
+        .collect();
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&TaggedUnsignedCorimMap::new(map), &mut cbor).expect("CoRIM encodes");
+    cbor
+}
+
+/// Returns instance identifiers of each kind, repeated `count` times
+fn instance_ids(count: usize) -> Vec<InstanceIdTypeChoice<'static>> {
+    let ids = [
+        InstanceIdTypeChoice::Ueid(UeidType::try_from([0x01; 17].as_slice()).unwrap().into()),
+        InstanceIdTypeChoice::Uuid(TaggedUuidType::from(UuidType::from(FixedBytes([0x22; 16])))),
+        InstanceIdTypeChoice::Bytes(Bytes::from(vec![0x33; 32]).into()),
+        InstanceIdTypeChoice::CryptoKey(CryptoKeyTypeChoice::Thumbprint(
+            Digest {
+                alg: HashAlgorithm::Sha256,
+                val: Bytes::from(vec![0x44; 32]),
+            }
+            .into(),
+        )),
+        InstanceIdTypeChoice::CryptoKey(CryptoKeyTypeChoice::PkixBase64Key(
+            PkixBase64KeyType::from(std::borrow::Cow::Borrowed(
+                "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE",
+            )),
+        )),
+    ];
+    ids.iter()
+        .cycle()
+        .take(ids.len() * count)
+        .cloned()
+        .collect()
+}
+
+/// Returns untagged class identifiers, which are decoded as extension values, repeated `count`
+/// times
+fn extension_class_ids(count: usize) -> Vec<ClassIdTypeChoice<'static>> {
+    let id = ClassIdTypeChoice::Extension(ExtensionValue::Array(vec![
+        ExtensionValue::Uint(1.into()),
+        ExtensionValue::Text("acme-roadrunner".into()),
+        ExtensionValue::Bytes(vec![0x55; 16].into()),
+    ]));
+    vec![id; count]
+}
+
+/// Runs `decode` over `input` and prints the median time and the allocations of one run
+fn bench<'a, T>(name: &str, input: &'a [u8], decode: impl Fn(&'a [u8]) -> T) {
+    // Warm up, so that one-off allocations are not counted
+    black_box(decode(input));
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    black_box(decode(input));
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes;
+
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(decode(black_box(input)));
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+
+    println!(
+        "{name:<28} {:>8} bytes {:>12.3?}/decode {allocations:>9} allocations {bytes:>11} bytes allocated",
+        input.len(),
+        times[RUNS / 2],
+    );
+}
+
+/// Encodes `value` as CBOR
+fn encode<T: serde::Serialize>(value: &T) -> Vec<u8> {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(value, &mut cbor).expect("value encodes");
+    cbor
+}
+
+/// Decodes `cbor` through a `ciborium::Value`, as the deserializers of earlier releases did
+fn through_value<T: serde::Deserialize<'static>>(cbor: &[u8]) -> T {
+    let value: ciborium::Value = ciborium::from_reader(cbor).expect("value decodes");
+    from_reader(encode(&value).as_slice()).unwrap()
+}
+
+fn main() {
+    let large = large_corim();
+    let corim: ConciseRimTypeChoice = from_slice(&large).unwrap();
+    let map = corim.as_unsigned_corim_map().unwrap();
+
+    // The class identifiers of the generated CoRIM
+    let comids = map
+        .tags
+        .iter()
+        .filter_map(ConciseTagTypeChoice::as_ref_comid);
+    let triples = comids.filter_map(|comid| comid.triples.reference_triples.as_ref());
+    let class_ids: Vec<&ClassIdTypeChoice> = triples
+        .flatten()
+        .filter_map(|triple| triple.ref_env.class.as_ref()?.class_id.as_ref())
+        .collect();
+
+    bench("unsigned CoRIM", &large, |cbor| {
+        from_slice::<ConciseRimTypeChoice>(cbor).unwrap()
+    });
+    bench("unsigned CoRIM (from_reader)", &large, |cbor| {
+        from_reader::<ConciseRimTypeChoice, _>(cbor).unwrap()
+    });
+    bench(
+        "unsigned CoRIM (baseline)",
+        &large,
+        through_value::<ConciseRimTypeChoice>,
+    );
+    bench("signed CoRIM (fixture)", SIGNED, |cbor| {
+        from_slice::<ConciseRimTypeChoice>(cbor).unwrap()
+    });
+    bench("class identifiers", &encode(&class_ids), |cbor| {
+        from_slice::<Vec<ClassIdTypeChoice>>(cbor).unwrap()
+    });
+    bench(
+        "class identifiers (baseline)",
+        &encode(&class_ids),
+        through_value::<Vec<ClassIdTypeChoice>>,
+    );
+    bench(
+        "extension class identifiers",
+        &encode(&extension_class_ids(TAG_COPIES)),
+        |cbor| from_slice::<Vec<ClassIdTypeChoice>>(cbor).unwrap(),
+    );
+    bench(
+        "instance identifiers",
+        &encode(&instance_ids(TAG_COPIES)),
+        |cbor| from_slice::<Vec<InstanceIdTypeChoice>>(cbor).unwrap(),
+    );
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+
+//! Generates `include/corim.h` from the sources of the crate
+
+use std::{env, path::PathBuf};
+
+const SOURCES: &[&str] = &[
+    "src/lib.rs",
+    "src/corim.rs",
+    "src/verify.rs",
+    "src/appraisal.rs",
+];
+
+fn main() {
+    let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml")).unwrap();
+
+    let mut builder = cbindgen::Builder::new().with_config(config);
+    for source in SOURCES {
+        println!("cargo:rerun-if-changed={source}");
+        builder = builder.with_src(dir.join(source));
+    }
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    builder
+        .generate()
+        .expect("failed to generate the C header")
+        .write_to_file(dir.join("include/corim.h"));
+}
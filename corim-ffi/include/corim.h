@@ -0,0 +1,503 @@
+/* SPDX-License-Identifier: MIT */
+
+#ifndef CORIM_FFI_H
+#define CORIM_FFI_H
+
+/* Generated by cbindgen from the corim-ffi sources; do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Result of a call to the C API
+typedef enum CorimStatus {
+  // The call succeeded
+  CORIM_STATUS_OK = 0,
+  // A required pointer argument was null
+  CORIM_STATUS_NULL_POINTER = 1,
+  // An argument was not valid, e.g. a string that is not UTF-8
+  CORIM_STATUS_INVALID_ARGUMENT = 2,
+  // The output buffer was null or too small; the required length was stored
+  CORIM_STATUS_BUFFER_TOO_SMALL = 3,
+  // An index was past the end of the sequence
+  CORIM_STATUS_OUT_OF_RANGE = 4,
+  // The value does not hold what was asked for, e.g. the tags of a detached signed CoRIM
+  CORIM_STATUS_NOT_PRESENT = 5,
+  // A CBOR input could not be decoded
+  CORIM_STATUS_DECODE = 6,
+  // A value could not be encoded
+  CORIM_STATUS_ENCODE = 7,
+  // The library panicked; the handles passed to the call should not be used again
+  CORIM_STATUS_PANIC = 8,
+  // Appraisal error
+  CORIM_STATUS_APPRAISAL = 16,
+  // CoMID error
+  CORIM_STATUS_COMID = 17,
+  // Core type error
+  CORIM_STATUS_CORE = 18,
+  // CoRIM error
+  CORIM_STATUS_CORIM = 19,
+  // COSE signing or verification error, including failed signatures
+  CORIM_STATUS_COSE = 20,
+  // CoSWID error
+  CORIM_STATUS_COSWID = 21,
+  // CoTL error
+  CORIM_STATUS_COTL = 22,
+  // EDN error
+  CORIM_STATUS_EDN = 23,
+  // Triples error
+  CORIM_STATUS_TRIPLES = 24,
+  // Numbers error
+  CORIM_STATUS_NUMBERS = 25,
+  // SBOM error
+  CORIM_STATUS_SBOM = 26,
+  // Streaming error
+  CORIM_STATUS_STREAM = 27,
+  // SWID error
+  CORIM_STATUS_SWID = 28,
+  // Veraison template error
+  CORIM_STATUS_VERAISON = 29,
+  // X.509 certificate chain error
+  CORIM_STATUS_X509 = 30,
+  // Any other library error
+  CORIM_STATUS_UNKNOWN = 31,
+  // A CoRIM or tag broke a rule of the builders or the CDDL
+  CORIM_STATUS_VALIDATION = 32,
+} CorimStatus;
+
+// The form a CoRIM takes
+typedef enum CorimKind {
+  // An unsigned CoRIM (CBOR tag 501)
+  CORIM_KIND_UNSIGNED = 0,
+  // A COSE_Sign1 signed CoRIM (CBOR tag 18)
+  CORIM_KIND_SIGNED = 1,
+  // A COSE_Sign1 signed CoRIM whose payload is distributed separately
+  CORIM_KIND_DETACHED_SIGNED = 2,
+  // A COSE_Sign signed CoRIM with one or more signers (CBOR tag 98)
+  CORIM_KIND_MULTI_SIGNED = 3,
+} CorimKind;
+
+// The kind of a tag
+typedef enum CorimTagType {
+  // A CoMID (CBOR tag 506)
+  CORIM_TAG_TYPE_COMID = 0,
+  // A CoSWID (CBOR tag 505)
+  CORIM_TAG_TYPE_COSWID = 1,
+  // A CoTL (CBOR tag 508)
+  CORIM_TAG_TYPE_COTL = 2,
+} CorimTagType;
+
+// The kinds of triple a CoMID carries
+typedef enum CorimTripleType {
+  // `reference-triples`
+  CORIM_TRIPLE_TYPE_REFERENCE = 0,
+  // `endorsed-triples`
+  CORIM_TRIPLE_TYPE_ENDORSED = 1,
+  // `identity-triples`
+  CORIM_TRIPLE_TYPE_IDENTITY = 2,
+  // `attest-key-triples`
+  CORIM_TRIPLE_TYPE_ATTEST_KEY = 3,
+  // `dependency-triples`
+  CORIM_TRIPLE_TYPE_DEPENDENCY = 4,
+  // `membership-triples`
+  CORIM_TRIPLE_TYPE_MEMBERSHIP = 5,
+  // `coswid-triples`
+  CORIM_TRIPLE_TYPE_COSWID = 6,
+  // `conditional-endorsement-series-triples`
+  CORIM_TRIPLE_TYPE_CONDITIONAL_ENDORSEMENT_SERIES = 7,
+  // `conditional-endorsement-triples`
+  CORIM_TRIPLE_TYPE_CONDITIONAL_ENDORSEMENT = 8,
+} CorimTripleType;
+
+// Whether any or all signers of a multi-signed CoRIM must verify
+typedef enum CorimSignerPolicy {
+  // At least one signer must verify
+  CORIM_SIGNER_POLICY_ANY = 0,
+  // Every signer must verify
+  CORIM_SIGNER_POLICY_ALL = 1,
+} CorimSignerPolicy;
+
+// A decoded CoRIM, unsigned or signed
+typedef struct Corim Corim;
+
+// The reference triples returned by [`corim_store_reference_values`], borrowed from the store
+typedef struct CorimReferenceList CorimReferenceList;
+
+// The reference and endorsed values of a set of CoRIMs
+typedef struct CorimReferenceStore CorimReferenceStore;
+
+// A reference triple, borrowed from the [`CorimTag`] or [`crate::CorimReferenceList`] it was
+// obtained from
+typedef struct CorimReferenceTriple CorimReferenceTriple;
+
+// The outcome of [`corim_store_appraise`]
+typedef struct CorimReport CorimReport;
+
+// A tag of a CoRIM, borrowed from the [`Corim`] it was obtained from
+typedef struct CorimTag CorimTag;
+
+// Public keys or trust anchors that signatures are verified against
+typedef struct CorimVerifier CorimVerifier;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns a description of the last failed call on this thread, or null if the last call
+// succeeded. The string is valid until the next call on this thread.
+const char *corim_last_error_message(void);
+
+// Returns a static, NUL terminated name for `status`, e.g. `"buffer too small"`
+const char *corim_status_name(enum CorimStatus status);
+
+// Creates an empty reference store, released with [`corim_store_free`]
+enum CorimStatus corim_store_new(struct CorimReferenceStore **out);
+
+// Releases a reference store
+void corim_store_free(struct CorimReferenceStore *store);
+
+// Adds the reference and endorsed triples of every CoMID in `corim` to `store`. The triples
+// are copied, so `corim` may be freed afterwards. `name` identifies the CoRIM in reports.
+//
+// The signature of a signed CoRIM is not checked here; verify it first with
+// [`corim_verify`](crate::corim_verify).
+enum CorimStatus corim_store_add(struct CorimReferenceStore *store,
+                                 const char *name,
+                                 const struct Corim *corim);
+
+// Stores a list of the reference triples of `store` whose environment matches the CBOR
+// encoded environment-map of `len` bytes at `environment` in `*out`. The list is released
+// with [`corim_reference_list_free`], and its triples are valid until `store` is modified or
+// freed.
+enum CorimStatus corim_store_reference_values(const struct CorimReferenceStore *store,
+                                              const uint8_t *environment,
+                                              size_t len,
+                                              struct CorimReferenceList **out);
+
+// Stores the number of reference triples in `list` in `*out`
+enum CorimStatus corim_reference_list_count(const struct CorimReferenceList *list, size_t *out);
+
+// Stores the reference triple at `index` of `list` in `*out`
+enum CorimStatus corim_reference_list_get(const struct CorimReferenceList *list,
+                                          size_t index,
+                                          const struct CorimReferenceTriple **out);
+
+// Releases a list of reference triples
+void corim_reference_list_free(struct CorimReferenceList *list);
+
+// Appraises evidence against the reference values of `store`, storing the report in `*out`.
+// The report is released with [`corim_report_free`].
+//
+// `evidence` is a JSON object with `environment` and `measurements` entries, in the JSON form
+// of the corresponding CoMID types, or an array of such objects. A failed appraisal is not an
+// error; it is reported by [`corim_report_pass`].
+enum CorimStatus corim_store_appraise(const struct CorimReferenceStore *store,
+                                      const char *evidence,
+                                      struct CorimReport **out);
+
+// Stores whether every environment of the appraised evidence passed in `*out`
+enum CorimStatus corim_report_pass(const struct CorimReport *report, bool *out);
+
+// Writes `report` as NUL terminated JSON, with the outcome of every check
+enum CorimStatus corim_report_json(const struct CorimReport *report, char *buf, size_t *len);
+
+// Releases an appraisal report
+void corim_report_free(struct CorimReport *report);
+
+// Decodes an unsigned or signed CoRIM from `len` bytes of CBOR at `data`, storing a handle in
+// `*out` that is released with [`corim_free`]
+enum CorimStatus corim_decode(const uint8_t *data, size_t len, struct Corim **out);
+
+// Releases a CoRIM. Tags and triples borrowed from it must no longer be used.
+void corim_free(struct Corim *corim);
+
+// Stores the form of `corim` in `*out`
+enum CorimStatus corim_kind(const struct Corim *corim, enum CorimKind *out);
+
+// Writes the identifier of `corim` as a NUL terminated string. UUID identifiers are written
+// in their hyphenated form.
+enum CorimStatus corim_id(const struct Corim *corim, char *buf, size_t *len);
+
+// Stores the number of tags of `corim` in `*out`. Detached signed CoRIMs carry no tags and
+// return [`CorimStatus::NotPresent`].
+enum CorimStatus corim_tag_count(const struct Corim *corim, size_t *out);
+
+// Stores the tag of `corim` at `index` in `*out`
+enum CorimStatus corim_tag_get(const struct Corim *corim,
+                               size_t index,
+                               const struct CorimTag **out);
+
+// Stores the kind of `tag` in `*out`
+enum CorimStatus corim_tag_type(const struct CorimTag *tag, enum CorimTagType *out);
+
+// Writes the tag identifier of `tag` as a NUL terminated string
+enum CorimStatus corim_tag_id(const struct CorimTag *tag, char *buf, size_t *len);
+
+// Writes the CBOR encoding of `tag`, including its CBOR tag
+enum CorimStatus corim_tag_encode(const struct CorimTag *tag, uint8_t *buf, size_t *len);
+
+// Stores the number of triples of kind `kind` carried by the CoMID `tag` in `*out`
+enum CorimStatus corim_tag_triple_count(const struct CorimTag *tag,
+                                        enum CorimTripleType kind,
+                                        size_t *out);
+
+// Writes the CBOR encoding of the triple of kind `kind` at `index` of the CoMID `tag`
+enum CorimStatus corim_tag_triple_encode(const struct CorimTag *tag,
+                                         enum CorimTripleType kind,
+                                         size_t index,
+                                         uint8_t *buf,
+                                         size_t *len);
+
+// Stores the reference triple at `index` of the CoMID `tag` in `*out`
+enum CorimStatus corim_tag_reference_triple(const struct CorimTag *tag,
+                                            size_t index,
+                                            const struct CorimReferenceTriple **out);
+
+// Writes the CBOR encoding of the environment-map of `triple`
+enum CorimStatus corim_reference_triple_environment(const struct CorimReferenceTriple *triple,
+                                                    uint8_t *buf,
+                                                    size_t *len);
+
+// Stores the number of reference measurements of `triple` in `*out`
+enum CorimStatus corim_reference_triple_measurement_count(const struct CorimReferenceTriple *triple,
+                                                          size_t *out);
+
+// Writes the CBOR encoding of the measurement-map at `index` of `triple`
+enum CorimStatus corim_reference_triple_measurement(const struct CorimReferenceTriple *triple,
+                                                    size_t index,
+                                                    uint8_t *buf,
+                                                    size_t *len);
+
+// Writes the CBOR encoding of `triple`
+enum CorimStatus corim_reference_triple_encode(const struct CorimReferenceTriple *triple,
+                                               uint8_t *buf,
+                                               size_t *len);
+
+// Creates a verifier that checks signatures with public keys, added with the
+// `corim_verifier_add_*key` functions. The handle is released with [`corim_verifier_free`].
+enum CorimStatus corim_verifier_new_keys(struct CorimVerifier **out);
+
+// Creates a verifier that validates the x5chain of each signer against trust anchors, added
+// with [`corim_verifier_add_trust_anchors_pem`]. The handle is released with
+// [`corim_verifier_free`].
+enum CorimStatus corim_verifier_new_trust_anchors(struct CorimVerifier **out);
+
+// Releases a verifier
+void corim_verifier_free(struct CorimVerifier *verifier);
+
+// Adds a PEM encoded public key to a key verifier
+enum CorimStatus corim_verifier_add_public_key_pem(struct CorimVerifier *verifier, const char *pem);
+
+// Adds a DER encoded SubjectPublicKeyInfo of `len` bytes to a key verifier
+enum CorimStatus corim_verifier_add_public_key_der(struct CorimVerifier *verifier,
+                                                   const uint8_t *der,
+                                                   size_t len);
+
+// Adds the subject public key of a DER encoded X.509 certificate of `len` bytes to a key
+// verifier
+enum CorimStatus corim_verifier_add_certificate_der(struct CorimVerifier *verifier,
+                                                    const uint8_t *der,
+                                                    size_t len);
+
+// Adds a CBOR encoded COSE_Key of `len` bytes to a key verifier
+enum CorimStatus corim_verifier_add_cose_key(struct CorimVerifier *verifier,
+                                             const uint8_t *cbor,
+                                             size_t len);
+
+// Adds every certificate of a PEM file to a trust anchor verifier
+enum CorimStatus corim_verifier_add_trust_anchors_pem(struct CorimVerifier *verifier,
+                                                      const char *pem);
+
+// Verifies the signatures of `corim` at `time`, in seconds since the Unix epoch, and checks
+// that the signature validity period of each verified signer contains `time`.
+//
+// `policy` applies to multi-signed CoRIMs. `payload` holds the `payload_len` bytes of the
+// payload of a detached signed CoRIM and may be null otherwise. On success the number of
+// signers that verified is stored in `*signers`, which may be null. A signature that does not
+// verify returns [`CorimStatus::Cose`].
+enum CorimStatus corim_verify(const struct Corim *corim,
+                              const struct CorimVerifier *verifier,
+                              enum CorimSignerPolicy policy,
+                              uint64_t time,
+                              const uint8_t *payload,
+                              size_t payload_len,
+                              size_t *signers);
+
+// Decodes an unsigned or signed CoRIM from `len` bytes of CBOR at `data`, storing a handle in
+// `*out` that is released with [`corim_free`]
+enum CorimStatus corim_decode(const uint8_t *data, size_t len, struct Corim **out);
+
+// Releases a CoRIM. Tags and triples borrowed from it must no longer be used.
+void corim_free(struct Corim *corim);
+
+// Stores the form of `corim` in `*out`
+enum CorimStatus corim_kind(const struct Corim *corim, enum CorimKind *out);
+
+// Writes the identifier of `corim` as a NUL terminated string. UUID identifiers are written
+// in their hyphenated form.
+enum CorimStatus corim_id(const struct Corim *corim, char *buf, size_t *len);
+
+// Stores the number of tags of `corim` in `*out`. Detached signed CoRIMs carry no tags and
+// return [`CorimStatus::NotPresent`].
+enum CorimStatus corim_tag_count(const struct Corim *corim, size_t *out);
+
+// Stores the tag of `corim` at `index` in `*out`
+enum CorimStatus corim_tag_get(const struct Corim *corim,
+                               size_t index,
+                               const struct CorimTag **out);
+
+// Stores the kind of `tag` in `*out`
+enum CorimStatus corim_tag_type(const struct CorimTag *tag, enum CorimTagType *out);
+
+// Writes the tag identifier of `tag` as a NUL terminated string
+enum CorimStatus corim_tag_id(const struct CorimTag *tag, char *buf, size_t *len);
+
+// Writes the CBOR encoding of `tag`, including its CBOR tag
+enum CorimStatus corim_tag_encode(const struct CorimTag *tag, uint8_t *buf, size_t *len);
+
+// Stores the number of triples of kind `kind` carried by the CoMID `tag` in `*out`
+enum CorimStatus corim_tag_triple_count(const struct CorimTag *tag,
+                                        enum CorimTripleType kind,
+                                        size_t *out);
+
+// Writes the CBOR encoding of the triple of kind `kind` at `index` of the CoMID `tag`
+enum CorimStatus corim_tag_triple_encode(const struct CorimTag *tag,
+                                         enum CorimTripleType kind,
+                                         size_t index,
+                                         uint8_t *buf,
+                                         size_t *len);
+
+// Stores the reference triple at `index` of the CoMID `tag` in `*out`
+enum CorimStatus corim_tag_reference_triple(const struct CorimTag *tag,
+                                            size_t index,
+                                            const struct CorimReferenceTriple **out);
+
+// Writes the CBOR encoding of the environment-map of `triple`
+enum CorimStatus corim_reference_triple_environment(const struct CorimReferenceTriple *triple,
+                                                    uint8_t *buf,
+                                                    size_t *len);
+
+// Stores the number of reference measurements of `triple` in `*out`
+enum CorimStatus corim_reference_triple_measurement_count(const struct CorimReferenceTriple *triple,
+                                                          size_t *out);
+
+// Writes the CBOR encoding of the measurement-map at `index` of `triple`
+enum CorimStatus corim_reference_triple_measurement(const struct CorimReferenceTriple *triple,
+                                                    size_t index,
+                                                    uint8_t *buf,
+                                                    size_t *len);
+
+// Writes the CBOR encoding of `triple`
+enum CorimStatus corim_reference_triple_encode(const struct CorimReferenceTriple *triple,
+                                               uint8_t *buf,
+                                               size_t *len);
+
+// Creates a verifier that checks signatures with public keys, added with the
+// `corim_verifier_add_*key` functions. The handle is released with [`corim_verifier_free`].
+enum CorimStatus corim_verifier_new_keys(struct CorimVerifier **out);
+
+// Creates a verifier that validates the x5chain of each signer against trust anchors, added
+// with [`corim_verifier_add_trust_anchors_pem`]. The handle is released with
+// [`corim_verifier_free`].
+enum CorimStatus corim_verifier_new_trust_anchors(struct CorimVerifier **out);
+
+// Releases a verifier
+void corim_verifier_free(struct CorimVerifier *verifier);
+
+// Adds a PEM encoded public key to a key verifier
+enum CorimStatus corim_verifier_add_public_key_pem(struct CorimVerifier *verifier, const char *pem);
+
+// Adds a DER encoded SubjectPublicKeyInfo of `len` bytes to a key verifier
+enum CorimStatus corim_verifier_add_public_key_der(struct CorimVerifier *verifier,
+                                                   const uint8_t *der,
+                                                   size_t len);
+
+// Adds the subject public key of a DER encoded X.509 certificate of `len` bytes to a key
+// verifier
+enum CorimStatus corim_verifier_add_certificate_der(struct CorimVerifier *verifier,
+                                                    const uint8_t *der,
+                                                    size_t len);
+
+// Adds a CBOR encoded COSE_Key of `len` bytes to a key verifier
+enum CorimStatus corim_verifier_add_cose_key(struct CorimVerifier *verifier,
+                                             const uint8_t *cbor,
+                                             size_t len);
+
+// Adds every certificate of a PEM file to a trust anchor verifier
+enum CorimStatus corim_verifier_add_trust_anchors_pem(struct CorimVerifier *verifier,
+                                                      const char *pem);
+
+// Verifies the signatures of `corim` at `time`, in seconds since the Unix epoch, and checks
+// that the signature validity period of each verified signer contains `time`.
+//
+// `policy` applies to multi-signed CoRIMs. `payload` holds the `payload_len` bytes of the
+// payload of a detached signed CoRIM and may be null otherwise. On success the number of
+// signers that verified is stored in `*signers`, which may be null. A signature that does not
+// verify returns [`CorimStatus::Cose`].
+enum CorimStatus corim_verify(const struct Corim *corim,
+                              const struct CorimVerifier *verifier,
+                              enum CorimSignerPolicy policy,
+                              uint64_t time,
+                              const uint8_t *payload,
+                              size_t payload_len,
+                              size_t *signers);
+
+// Creates an empty reference store, released with [`corim_store_free`]
+enum CorimStatus corim_store_new(struct CorimReferenceStore **out);
+
+// Releases a reference store
+void corim_store_free(struct CorimReferenceStore *store);
+
+// Adds the reference and endorsed triples of every CoMID in `corim` to `store`. The triples
+// are copied, so `corim` may be freed afterwards. `name` identifies the CoRIM in reports.
+//
+// The signature of a signed CoRIM is not checked here; verify it first with
+// [`corim_verify`](crate::corim_verify).
+enum CorimStatus corim_store_add(struct CorimReferenceStore *store,
+                                 const char *name,
+                                 const struct Corim *corim);
+
+// Stores a list of the reference triples of `store` whose environment matches the CBOR
+// encoded environment-map of `len` bytes at `environment` in `*out`. The list is released
+// with [`corim_reference_list_free`], and its triples are valid until `store` is modified or
+// freed.
+enum CorimStatus corim_store_reference_values(const struct CorimReferenceStore *store,
+                                              const uint8_t *environment,
+                                              size_t len,
+                                              struct CorimReferenceList **out);
+
+// Stores the number of reference triples in `list` in `*out`
+enum CorimStatus corim_reference_list_count(const struct CorimReferenceList *list, size_t *out);
+
+// Stores the reference triple at `index` of `list` in `*out`
+enum CorimStatus corim_reference_list_get(const struct CorimReferenceList *list,
+                                          size_t index,
+                                          const struct CorimReferenceTriple **out);
+
+// Releases a list of reference triples
+void corim_reference_list_free(struct CorimReferenceList *list);
+
+// Appraises evidence against the reference values of `store`, storing the report in `*out`.
+// The report is released with [`corim_report_free`].
+//
+// `evidence` is a JSON object with `environment` and `measurements` entries, in the JSON form
+// of the corresponding CoMID types, or an array of such objects. A failed appraisal is not an
+// error; it is reported by [`corim_report_pass`].
+enum CorimStatus corim_store_appraise(const struct CorimReferenceStore *store,
+                                      const char *evidence,
+                                      struct CorimReport **out);
+
+// Stores whether every environment of the appraised evidence passed in `*out`
+enum CorimStatus corim_report_pass(const struct CorimReport *report, bool *out);
+
+// Writes `report` as NUL terminated JSON, with the outcome of every check
+enum CorimStatus corim_report_json(const struct CorimReport *report, char *buf, size_t *len);
+
+// Releases an appraisal report
+void corim_report_free(struct CorimReport *report);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CORIM_FFI_H */
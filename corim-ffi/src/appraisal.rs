@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: MIT
+
+//! Reference value queries and appraisal of evidence
+
+use std::ffi::c_char;
+
+use corim_rs::{
+    appraisal::{EvidenceClaims, ReferenceStore, Report},
+    triples::EnvironmentMap,
+};
+
+use crate::{
+    arg, arg_mut, bytes, call, corim::element, decode, free_handle, output, output_handle, text,
+    write_text, Corim, CorimReferenceTriple, CorimStatus, Failure,
+};
+
+/// The reference and endorsed values of a set of CoRIMs
+#[derive(Debug, Default)]
+pub struct CorimReferenceStore(ReferenceStore);
+
+/// The reference triples returned by [`corim_store_reference_values`], borrowed from the store
+#[derive(Debug)]
+pub struct CorimReferenceList(Vec<*const CorimReferenceTriple>);
+
+/// The outcome of [`corim_store_appraise`]
+#[derive(Debug)]
+pub struct CorimReport(Report);
+
+/// Creates an empty reference store, released with [`corim_store_free`]
+#[no_mangle]
+pub unsafe extern "C" fn corim_store_new(out: *mut *mut CorimReferenceStore) -> CorimStatus {
+    call(|| output_handle(out, "out", CorimReferenceStore::default()))
+}
+
+/// Releases a reference store
+#[no_mangle]
+pub unsafe extern "C" fn corim_store_free(store: *mut CorimReferenceStore) {
+    free_handle(store)
+}
+
+/// Adds the reference and endorsed triples of every CoMID in `corim` to `store`. The triples
+/// are copied, so `corim` may be freed afterwards. `name` identifies the CoRIM in reports.
+///
+/// The signature of a signed CoRIM is not checked here; verify it first with
+/// [`corim_verify`](crate::corim_verify).
+#[no_mangle]
+pub unsafe extern "C" fn corim_store_add(
+    store: *mut CorimReferenceStore,
+    name: *const c_char,
+    corim: *const Corim,
+) -> CorimStatus {
+    call(|| {
+        let name = text(name, "name")?;
+        let corim = &arg(corim, "corim")?.0;
+        Ok(arg_mut(store, "store")?.0.add(name, corim)?)
+    })
+}
+
+/// Stores a list of the reference triples of `store` whose environment matches the CBOR
+/// encoded environment-map of `len` bytes at `environment` in `*out`. The list is released
+/// with [`corim_reference_list_free`], and its triples are valid until `store` is modified or
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn corim_store_reference_values(
+    store: *const CorimReferenceStore,
+    environment: *const u8,
+    len: usize,
+    out: *mut *mut CorimReferenceList,
+) -> CorimStatus {
+    call(|| {
+        let store = &arg(store, "store")?.0;
+        let environment: EnvironmentMap<'static> =
+            decode(bytes(environment, len, "environment")?, "environment-map")?;
+        let triples = store
+            .reference_values(&environment)
+            .map(CorimReferenceTriple::handle)
+            .collect();
+        output_handle(out, "out", CorimReferenceList(triples))
+    })
+}
+
+/// Stores the number of reference triples in `list` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_reference_list_count(
+    list: *const CorimReferenceList,
+    out: *mut usize,
+) -> CorimStatus {
+    call(|| output(out, "out", arg(list, "list")?.0.len()))
+}
+
+/// Stores the reference triple at `index` of `list` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_reference_list_get(
+    list: *const CorimReferenceList,
+    index: usize,
+    out: *mut *const CorimReferenceTriple,
+) -> CorimStatus {
+    call(|| {
+        let triple = *element(&arg(list, "list")?.0, index)?;
+        output(out, "out", triple)
+    })
+}
+
+/// Releases a list of reference triples
+#[no_mangle]
+pub unsafe extern "C" fn corim_reference_list_free(list: *mut CorimReferenceList) {
+    free_handle(list)
+}
+
+/// Appraises evidence against the reference values of `store`, storing the report in `*out`.
+/// The report is released with [`corim_report_free`].
+///
+/// `evidence` is a JSON object with `environment` and `measurements` entries, in the JSON form
+/// of the corresponding CoMID types, or an array of such objects. A failed appraisal is not an
+/// error; it is reported by [`corim_report_pass`].
+#[no_mangle]
+pub unsafe extern "C" fn corim_store_appraise(
+    store: *const CorimReferenceStore,
+    evidence: *const c_char,
+    out: *mut *mut CorimReport,
+) -> CorimStatus {
+    call(|| {
+        let store = &arg(store, "store")?.0;
+        let evidence = EvidenceClaims::from_json(text(evidence, "evidence")?)?;
+        output_handle(out, "out", CorimReport(store.appraise(&evidence)))
+    })
+}
+
+/// Stores whether every environment of the appraised evidence passed in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_report_pass(
+    report: *const CorimReport,
+    out: *mut bool,
+) -> CorimStatus {
+    call(|| output(out, "out", arg(report, "report")?.0.pass))
+}
+
+/// Writes `report` as NUL terminated JSON, with the outcome of every check
+#[no_mangle]
+pub unsafe extern "C" fn corim_report_json(
+    report: *const CorimReport,
+    buf: *mut c_char,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let json = serde_json::to_string(&arg(report, "report")?.0)
+            .map_err(|e| Failure::new(CorimStatus::Encode, e.to_string()))?;
+        write_text(&json, buf, len)
+    })
+}
+
+/// Releases an appraisal report
+#[no_mangle]
+pub unsafe extern "C" fn corim_report_free(report: *mut CorimReport) {
+    free_handle(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        corim::tests::{read_buffer, UNSIGNED},
+        corim_decode, corim_free, corim_reference_triple_encode, corim_tag_get,
+        corim_tag_reference_triple, encode,
+    };
+    use corim_rs::triples::ReferenceTripleRecord;
+    use std::{ffi::CString, ptr};
+
+    const EVIDENCE: &str = r#"{
+        "environment": {"class": {
+            "class-id": {"type": "oid", "value": "2.16.840.1.113741.1.2.3"},
+            "vendor": "ACME Inc.",
+            "model": "RoadRunner"
+        }},
+        "measurements": [{"mkey": "firmware", "mval": {
+            "svn": 3,
+            "digests": ["sha-256;XP_FZ_Dl8qdF9zaFHbFVlEHm8Tsj7aHpBm7izNZOxag"]
+        }}]
+    }"#;
+
+    #[test]
+    fn test_store() {
+        unsafe {
+            let mut corim = ptr::null_mut();
+            assert_eq!(
+                corim_decode(UNSIGNED.as_ptr(), UNSIGNED.len(), &mut corim),
+                CorimStatus::Ok
+            );
+            let mut tag = ptr::null();
+            assert_eq!(corim_tag_get(corim, 0, &mut tag), CorimStatus::Ok);
+            let mut triple = ptr::null();
+            assert_eq!(
+                corim_tag_reference_triple(tag, 0, &mut triple),
+                CorimStatus::Ok
+            );
+            let expected = read_buffer(|buf, len| corim_reference_triple_encode(triple, buf, len));
+            let environment = encode(&(*triple.cast::<ReferenceTripleRecord>()).ref_env).unwrap();
+
+            let mut store = ptr::null_mut();
+            assert_eq!(corim_store_new(&mut store), CorimStatus::Ok);
+            let name = CString::new("unsigned.cbor").unwrap();
+            assert_eq!(
+                corim_store_add(store, name.as_ptr(), corim),
+                CorimStatus::Ok
+            );
+            corim_free(corim);
+
+            let mut list = ptr::null_mut();
+            assert_eq!(
+                corim_store_reference_values(
+                    store,
+                    environment.as_ptr(),
+                    environment.len(),
+                    &mut list
+                ),
+                CorimStatus::Ok
+            );
+            let mut count = 0;
+            assert_eq!(
+                corim_reference_list_count(list, &mut count),
+                CorimStatus::Ok
+            );
+            assert_eq!(count, 1);
+            assert_eq!(
+                corim_reference_list_get(list, 0, &mut triple),
+                CorimStatus::Ok
+            );
+            assert_eq!(
+                read_buffer(|buf, len| corim_reference_triple_encode(triple, buf, len)),
+                expected
+            );
+            assert_eq!(
+                corim_reference_list_get(list, 1, &mut triple),
+                CorimStatus::OutOfRange
+            );
+            corim_reference_list_free(list);
+
+            let evidence = CString::new(EVIDENCE).unwrap();
+            let mut report = ptr::null_mut();
+            assert_eq!(
+                corim_store_appraise(store, evidence.as_ptr(), &mut report),
+                CorimStatus::Ok
+            );
+            let mut pass = false;
+            assert_eq!(corim_report_pass(report, &mut pass), CorimStatus::Ok);
+            assert!(pass);
+            let json = read_buffer(|buf, len| corim_report_json(report, buf.cast(), len));
+            let json: serde_json::Value = serde_json::from_slice(&json[..json.len() - 1]).unwrap();
+            assert_eq!(json["pass"], true);
+            corim_report_free(report);
+
+            let evidence = CString::new("{}").unwrap();
+            assert_eq!(
+                corim_store_appraise(store, evidence.as_ptr(), &mut report),
+                CorimStatus::Appraisal
+            );
+            corim_store_free(store);
+        }
+    }
+}
@@ -0,0 +1,519 @@
+// SPDX-License-Identifier: MIT
+
+//! Decoding of CoRIMs and access to their tags and triples
+
+use std::ffi::c_char;
+
+use corim_rs::{
+    comid::ConciseMidTag,
+    corim::{ConciseRimTypeChoice, ConciseTagTypeChoice, CorimIdTypeChoice, CorimMap},
+    describe::{describe, describe_tag_id},
+    triples::ReferenceTripleRecord,
+};
+
+use crate::{
+    arg, bytes, call, decode, encode, free_handle, output, output_handle, write_buffer, write_text,
+    CorimStatus, Failure, FfiResult,
+};
+
+/// A decoded CoRIM, unsigned or signed
+#[derive(Debug)]
+pub struct Corim(pub(crate) ConciseRimTypeChoice<'static>);
+
+/// A tag of a CoRIM, borrowed from the [`Corim`] it was obtained from
+#[derive(Debug)]
+pub struct CorimTag {
+    _private: [u8; 0],
+}
+
+/// A reference triple, borrowed from the [`CorimTag`] or [`crate::CorimReferenceList`] it was
+/// obtained from
+#[derive(Debug)]
+pub struct CorimReferenceTriple {
+    _private: [u8; 0],
+}
+
+/// The form a CoRIM takes
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorimKind {
+    /// An unsigned CoRIM (CBOR tag 501)
+    Unsigned = 0,
+    /// A COSE_Sign1 signed CoRIM (CBOR tag 18)
+    Signed = 1,
+    /// A COSE_Sign1 signed CoRIM whose payload is distributed separately
+    DetachedSigned = 2,
+    /// A COSE_Sign signed CoRIM with one or more signers (CBOR tag 98)
+    MultiSigned = 3,
+}
+
+/// The kind of a tag
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorimTagType {
+    /// A CoMID (CBOR tag 506)
+    Comid = 0,
+    /// A CoSWID (CBOR tag 505)
+    Coswid = 1,
+    /// A CoTL (CBOR tag 508)
+    Cotl = 2,
+}
+
+/// The kinds of triple a CoMID carries
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorimTripleType {
+    /// `reference-triples`
+    Reference = 0,
+    /// `endorsed-triples`
+    Endorsed = 1,
+    /// `identity-triples`
+    Identity = 2,
+    /// `attest-key-triples`
+    AttestKey = 3,
+    /// `dependency-triples`
+    Dependency = 4,
+    /// `membership-triples`
+    Membership = 5,
+    /// `coswid-triples`
+    Coswid = 6,
+    /// `conditional-endorsement-series-triples`
+    ConditionalEndorsementSeries = 7,
+    /// `conditional-endorsement-triples`
+    ConditionalEndorsement = 8,
+}
+
+impl Corim {
+    /// Returns the CoRIM map, which detached signed CoRIMs do not carry
+    pub(crate) fn map(&self) -> FfiResult<&CorimMap<'static>> {
+        match &self.0 {
+            ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) => Ok(map.as_ref()),
+            ConciseRimTypeChoice::SignedCorim(signed) => Ok(signed.payload.as_ref()),
+            ConciseRimTypeChoice::MultiSignedCorim(signed) => Ok(signed.payload.as_ref()),
+            ConciseRimTypeChoice::DetachedSignedCorim(_) => Err(Failure::new(
+                CorimStatus::NotPresent,
+                "a detached signed CoRIM carries no payload",
+            )),
+        }
+    }
+}
+
+impl CorimTag {
+    unsafe fn get<'a>(tag: *const CorimTag) -> FfiResult<&'a ConciseTagTypeChoice<'static>> {
+        arg(tag.cast::<ConciseTagTypeChoice<'static>>(), "tag")
+    }
+
+    unsafe fn comid<'a>(tag: *const CorimTag) -> FfiResult<&'a ConciseMidTag<'static>> {
+        match Self::get(tag)? {
+            ConciseTagTypeChoice::Mid(comid) => Ok(comid.as_ref()),
+            _ => Err(Failure::new(
+                CorimStatus::NotPresent,
+                "the tag is not a CoMID",
+            )),
+        }
+    }
+}
+
+impl CorimReferenceTriple {
+    pub(crate) fn handle(triple: &ReferenceTripleRecord<'static>) -> *const Self {
+        (triple as *const ReferenceTripleRecord<'static>).cast()
+    }
+
+    unsafe fn get<'a>(triple: *const Self) -> FfiResult<&'a ReferenceTripleRecord<'static>> {
+        arg(triple.cast::<ReferenceTripleRecord<'static>>(), "triple")
+    }
+}
+
+/// Returns the element at `index`, or [`CorimStatus::OutOfRange`]
+pub(crate) fn element<T>(items: &[T], index: usize) -> FfiResult<&T> {
+    items.get(index).ok_or_else(|| {
+        Failure::new(
+            CorimStatus::OutOfRange,
+            format!("index {index} is out of range for {} elements", items.len()),
+        )
+    })
+}
+
+/// Decodes an unsigned or signed CoRIM from `len` bytes of CBOR at `data`, storing a handle in
+/// `*out` that is released with [`corim_free`]
+#[no_mangle]
+pub unsafe extern "C" fn corim_decode(
+    data: *const u8,
+    len: usize,
+    out: *mut *mut Corim,
+) -> CorimStatus {
+    call(|| {
+        let corim = decode(bytes(data, len, "data")?, "CoRIM")?;
+        output_handle(out, "out", Corim(corim))
+    })
+}
+
+/// Releases a CoRIM. Tags and triples borrowed from it must no longer be used.
+#[no_mangle]
+pub unsafe extern "C" fn corim_free(corim: *mut Corim) {
+    free_handle(corim)
+}
+
+/// Stores the form of `corim` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_kind(corim: *const Corim, out: *mut CorimKind) -> CorimStatus {
+    call(|| {
+        let kind = match &arg(corim, "corim")?.0 {
+            ConciseRimTypeChoice::TaggedUnsignedCorimMap(_) => CorimKind::Unsigned,
+            ConciseRimTypeChoice::SignedCorim(_) => CorimKind::Signed,
+            ConciseRimTypeChoice::DetachedSignedCorim(_) => CorimKind::DetachedSigned,
+            ConciseRimTypeChoice::MultiSignedCorim(_) => CorimKind::MultiSigned,
+        };
+        output(out, "out", kind)
+    })
+}
+
+/// Writes the identifier of `corim` as a NUL terminated string. UUID identifiers are written
+/// in their hyphenated form.
+#[no_mangle]
+pub unsafe extern "C" fn corim_id(
+    corim: *const Corim,
+    buf: *mut c_char,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let map = arg(corim, "corim")?.map()?;
+        let id = match &map.id {
+            CorimIdTypeChoice::Tstr(id) => id.to_string(),
+            CorimIdTypeChoice::Uuid(id) => id.to_string(),
+        };
+        write_text(&id, buf, len)
+    })
+}
+
+/// Stores the number of tags of `corim` in `*out`. Detached signed CoRIMs carry no tags and
+/// return [`CorimStatus::NotPresent`].
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_count(corim: *const Corim, out: *mut usize) -> CorimStatus {
+    call(|| output(out, "out", arg(corim, "corim")?.map()?.tags.len()))
+}
+
+/// Stores the tag of `corim` at `index` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_get(
+    corim: *const Corim,
+    index: usize,
+    out: *mut *const CorimTag,
+) -> CorimStatus {
+    call(|| {
+        let tag = element(&arg(corim, "corim")?.map()?.tags, index)?;
+        output(
+            out,
+            "out",
+            (tag as *const ConciseTagTypeChoice<'static>).cast(),
+        )
+    })
+}
+
+/// Stores the kind of `tag` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_type(
+    tag: *const CorimTag,
+    out: *mut CorimTagType,
+) -> CorimStatus {
+    call(|| {
+        let kind = match CorimTag::get(tag)? {
+            ConciseTagTypeChoice::Mid(_) => CorimTagType::Comid,
+            ConciseTagTypeChoice::Swid(_) => CorimTagType::Coswid,
+            ConciseTagTypeChoice::Tl(_) => CorimTagType::Cotl,
+        };
+        output(out, "out", kind)
+    })
+}
+
+/// Writes the tag identifier of `tag` as a NUL terminated string
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_id(
+    tag: *const CorimTag,
+    buf: *mut c_char,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let id = match CorimTag::get(tag)? {
+            ConciseTagTypeChoice::Mid(comid) => describe_tag_id(&comid.tag_identity.tag_id),
+            ConciseTagTypeChoice::Swid(coswid) => describe(&coswid.tag_id),
+            ConciseTagTypeChoice::Tl(cotl) => describe_tag_id(&cotl.tag_identity.tag_id),
+        };
+        write_text(&id, buf, len)
+    })
+}
+
+/// Writes the CBOR encoding of `tag`, including its CBOR tag
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_encode(
+    tag: *const CorimTag,
+    buf: *mut u8,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| write_buffer(&encode(CorimTag::get(tag)?)?, buf, len))
+}
+
+/// Calls `$body` with the triples of `$kind` carried by `$comid`, as a slice
+macro_rules! with_triples {
+    ($comid:expr, $kind:expr, |$triples:ident| $body:expr) => {{
+        let triples = &$comid.triples;
+        match $kind {
+            CorimTripleType::Reference => {
+                let $triples = triples.reference_triples.as_deref().unwrap_or_default();
+                $body
+            }
+            CorimTripleType::Endorsed => {
+                let $triples = triples.endorsed_triples.as_deref().unwrap_or_default();
+                $body
+            }
+            CorimTripleType::Identity => {
+                let $triples = triples.identity_triples.as_deref().unwrap_or_default();
+                $body
+            }
+            CorimTripleType::AttestKey => {
+                let $triples = triples.attest_key_triples.as_deref().unwrap_or_default();
+                $body
+            }
+            CorimTripleType::Dependency => {
+                let $triples = triples.dependency_triples.as_deref().unwrap_or_default();
+                $body
+            }
+            CorimTripleType::Membership => {
+                let $triples = triples.membership_triples.as_deref().unwrap_or_default();
+                $body
+            }
+            CorimTripleType::Coswid => {
+                let $triples = triples.coswid_triples.as_deref().unwrap_or_default();
+                $body
+            }
+            CorimTripleType::ConditionalEndorsementSeries => {
+                let $triples = triples
+                    .conditional_endorsement_series_triples
+                    .as_deref()
+                    .unwrap_or_default();
+                $body
+            }
+            CorimTripleType::ConditionalEndorsement => {
+                let $triples = triples
+                    .conditional_endorsement_triples
+                    .as_deref()
+                    .unwrap_or_default();
+                $body
+            }
+        }
+    }};
+}
+
+/// Stores the number of triples of kind `kind` carried by the CoMID `tag` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_triple_count(
+    tag: *const CorimTag,
+    kind: CorimTripleType,
+    out: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let comid = CorimTag::comid(tag)?;
+        output(
+            out,
+            "out",
+            with_triples!(comid, kind, |triples| triples.len()),
+        )
+    })
+}
+
+/// Writes the CBOR encoding of the triple of kind `kind` at `index` of the CoMID `tag`
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_triple_encode(
+    tag: *const CorimTag,
+    kind: CorimTripleType,
+    index: usize,
+    buf: *mut u8,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let comid = CorimTag::comid(tag)?;
+        let cbor = with_triples!(comid, kind, |triples| encode(element(triples, index)?)?);
+        write_buffer(&cbor, buf, len)
+    })
+}
+
+/// Stores the reference triple at `index` of the CoMID `tag` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_tag_reference_triple(
+    tag: *const CorimTag,
+    index: usize,
+    out: *mut *const CorimReferenceTriple,
+) -> CorimStatus {
+    call(|| {
+        let comid = CorimTag::comid(tag)?;
+        let triples = comid
+            .triples
+            .reference_triples
+            .as_deref()
+            .unwrap_or_default();
+        output(
+            out,
+            "out",
+            CorimReferenceTriple::handle(element(triples, index)?),
+        )
+    })
+}
+
+/// Writes the CBOR encoding of the environment-map of `triple`
+#[no_mangle]
+pub unsafe extern "C" fn corim_reference_triple_environment(
+    triple: *const CorimReferenceTriple,
+    buf: *mut u8,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let triple = CorimReferenceTriple::get(triple)?;
+        write_buffer(&encode(&triple.ref_env)?, buf, len)
+    })
+}
+
+/// Stores the number of reference measurements of `triple` in `*out`
+#[no_mangle]
+pub unsafe extern "C" fn corim_reference_triple_measurement_count(
+    triple: *const CorimReferenceTriple,
+    out: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        output(
+            out,
+            "out",
+            CorimReferenceTriple::get(triple)?.ref_claims.len(),
+        )
+    })
+}
+
+/// Writes the CBOR encoding of the measurement-map at `index` of `triple`
+#[no_mangle]
+pub unsafe extern "C" fn corim_reference_triple_measurement(
+    triple: *const CorimReferenceTriple,
+    index: usize,
+    buf: *mut u8,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let triple = CorimReferenceTriple::get(triple)?;
+        let measurement = element(&triple.ref_claims, index)?;
+        write_buffer(&encode(measurement)?, buf, len)
+    })
+}
+
+/// Writes the CBOR encoding of `triple`
+#[no_mangle]
+pub unsafe extern "C" fn corim_reference_triple_encode(
+    triple: *const CorimReferenceTriple,
+    buf: *mut u8,
+    len: *mut usize,
+) -> CorimStatus {
+    call(|| write_buffer(&encode(CorimReferenceTriple::get(triple)?)?, buf, len))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::ptr;
+
+    pub(crate) const UNSIGNED: &[u8] = include_bytes!("../../testdata/corim/unsigned.cbor");
+    pub(crate) const SIGNED: &[u8] = include_bytes!("../../testdata/corim/signed.cbor");
+
+    /// Calls a function writing to a buffer twice: once to size the buffer, then to fill it
+    pub(crate) fn read_buffer(
+        mut write: impl FnMut(*mut u8, *mut usize) -> CorimStatus,
+    ) -> Vec<u8> {
+        let mut len = 0;
+        assert_eq!(
+            write(ptr::null_mut(), &mut len),
+            CorimStatus::BufferTooSmall
+        );
+        let mut buf = vec![0; len];
+        assert_eq!(write(buf.as_mut_ptr(), &mut len), CorimStatus::Ok);
+        buf.truncate(len);
+        buf
+    }
+
+    #[test]
+    fn test_decode_and_iterate() {
+        unsafe {
+            let mut corim = ptr::null_mut();
+            assert_eq!(
+                corim_decode(UNSIGNED.as_ptr(), UNSIGNED.len(), &mut corim),
+                CorimStatus::Ok
+            );
+
+            let mut kind = CorimKind::Signed;
+            assert_eq!(corim_kind(corim, &mut kind), CorimStatus::Ok);
+            assert_eq!(kind, CorimKind::Unsigned);
+
+            let id = read_buffer(|buf, len| corim_id(corim, buf.cast(), len));
+            assert_eq!(id, b"acme-roadrunner-corim\0");
+
+            let mut count = 0;
+            assert_eq!(corim_tag_count(corim, &mut count), CorimStatus::Ok);
+            assert_eq!(count, 2);
+
+            let mut tag = ptr::null();
+            assert_eq!(corim_tag_get(corim, 0, &mut tag), CorimStatus::Ok);
+            let mut tag_type = CorimTagType::Cotl;
+            assert_eq!(corim_tag_type(tag, &mut tag_type), CorimStatus::Ok);
+            assert_eq!(tag_type, CorimTagType::Comid);
+            let id = read_buffer(|buf, len| corim_tag_id(tag, buf.cast(), len));
+            assert_eq!(id, b"acme-roadrunner-comid\0");
+
+            assert_eq!(
+                corim_tag_triple_count(tag, CorimTripleType::Reference, &mut count),
+                CorimStatus::Ok
+            );
+            assert_eq!(count, 1);
+            let cbor = read_buffer(|buf, len| {
+                corim_tag_triple_encode(tag, CorimTripleType::Reference, 0, buf, len)
+            });
+            let mut triple = ptr::null();
+            assert_eq!(
+                corim_tag_reference_triple(tag, 0, &mut triple),
+                CorimStatus::Ok
+            );
+            assert_eq!(
+                read_buffer(|buf, len| corim_reference_triple_encode(triple, buf, len)),
+                cbor
+            );
+            assert_eq!(
+                corim_reference_triple_measurement_count(triple, &mut count),
+                CorimStatus::Ok
+            );
+            assert_eq!(count, 1);
+
+            assert_eq!(corim_tag_get(corim, 2, &mut tag), CorimStatus::OutOfRange);
+            assert_eq!(corim_tag_get(corim, 1, &mut tag), CorimStatus::Ok);
+            assert_eq!(
+                corim_tag_triple_count(tag, CorimTripleType::Reference, &mut count),
+                CorimStatus::NotPresent
+            );
+
+            corim_free(corim);
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        unsafe {
+            let mut corim = ptr::null_mut();
+            assert_eq!(
+                corim_decode(UNSIGNED.as_ptr(), 10, &mut corim),
+                CorimStatus::Decode
+            );
+            assert!(corim.is_null());
+            assert_eq!(
+                corim_decode(ptr::null(), 1, &mut corim),
+                CorimStatus::NullPointer
+            );
+            assert_eq!(
+                corim_decode(SIGNED.as_ptr(), SIGNED.len(), ptr::null_mut()),
+                CorimStatus::NullPointer
+            );
+        }
+    }
+}
@@ -0,0 +1,358 @@
+// SPDX-License-Identifier: MIT
+
+//! # corim-ffi
+//!
+//! A C ABI for corim-rs, for verifiers written in C. The header `include/corim.h` is generated
+//! by cbindgen when the crate is built.
+//!
+//! The API works on opaque handles:
+//!
+//! * [`Corim`] - A decoded CoRIM, unsigned or signed, created by [`corim_decode`]
+//! * [`CorimTag`] and [`CorimReferenceTriple`] - A tag or reference triple borrowed from the
+//!   CoRIM or store it was obtained from
+//! * [`CorimVerifier`] - Public keys or trust anchors signatures are verified against
+//! * [`CorimReferenceStore`] - The reference values of a set of CoRIMs, which are queried by
+//!   environment and against which evidence is appraised
+//! * [`CorimReferenceList`] and [`CorimReport`] - The results of a query and of an appraisal
+//!
+//! Handles created by a `*_new`, `corim_decode` or `*_appraise` function are released with the
+//! matching `*_free` function. Borrowed handles are valid until the handle they were obtained
+//! from is modified or freed.
+//!
+//! # Errors
+//!
+//! Every function other than the `*_free` functions returns a [`CorimStatus`]. Errors of the
+//! Rust library map to the status of the module that raised them, and a description of the
+//! last failure on the calling thread is returned by [`corim_last_error_message`].
+//!
+//! Variable length results are written to a caller supplied buffer. The function takes the
+//! buffer and a pointer to its length; if the buffer is null or too small the required length
+//! is stored and [`CorimStatus::BufferTooSmall`] is returned, otherwise the length written.
+//!
+//! # Safety
+//!
+//! Pointers passed to the API must be null or valid for the access the function documents:
+//! input buffers must be readable for the given length, strings must be NUL terminated UTF-8,
+//! and handles must come from this API and not have been freed. Enumeration arguments must hold
+//! one of their enumerators.
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use corim_rs::error::Error;
+
+mod appraisal;
+mod corim;
+mod verify;
+
+pub use appraisal::*;
+pub use corim::*;
+pub use verify::*;
+
+/// Result of a call to the C API
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorimStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// An argument was not valid, e.g. a string that is not UTF-8
+    InvalidArgument = 2,
+    /// The output buffer was null or too small; the required length was stored
+    BufferTooSmall = 3,
+    /// An index was past the end of the sequence
+    OutOfRange = 4,
+    /// The value does not hold what was asked for, e.g. the tags of a detached signed CoRIM
+    NotPresent = 5,
+    /// A CBOR input could not be decoded
+    Decode = 6,
+    /// A value could not be encoded
+    Encode = 7,
+    /// The library panicked; the handles passed to the call should not be used again
+    Panic = 8,
+    /// Appraisal error
+    Appraisal = 16,
+    /// CoMID error
+    Comid = 17,
+    /// Core type error
+    Core = 18,
+    /// CoRIM error
+    Corim = 19,
+    /// COSE signing or verification error, including failed signatures
+    Cose = 20,
+    /// CoSWID error
+    Coswid = 21,
+    /// CoTL error
+    Cotl = 22,
+    /// EDN error
+    Edn = 23,
+    /// Triples error
+    Triples = 24,
+    /// Numbers error
+    Numbers = 25,
+    /// SBOM error
+    Sbom = 26,
+    /// Streaming error
+    Stream = 27,
+    /// SWID error
+    Swid = 28,
+    /// Veraison template error
+    Veraison = 29,
+    /// X.509 certificate chain error
+    X509 = 30,
+    /// Any other library error
+    Unknown = 31,
+    /// A CoRIM or tag broke a rule of the builders or the CDDL
+    Validation = 32,
+}
+
+impl From<&Error> for CorimStatus {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::Appraisal(_) => Self::Appraisal,
+            Error::Cbor(_) => Self::Decode,
+            Error::Comid(_) => Self::Comid,
+            Error::Core(_) => Self::Core,
+            Error::Corim(_) => Self::Corim,
+            Error::Cose(_) => Self::Cose,
+            Error::Coswid(_) => Self::Coswid,
+            Error::Cotl(_) => Self::Cotl,
+            Error::Edn(_) => Self::Edn,
+            Error::Triples(_) => Self::Triples,
+            Error::Numbers(_) => Self::Numbers,
+            Error::Sbom(_) => Self::Sbom,
+            Error::Stream(_) => Self::Stream,
+            Error::Swid(_) => Self::Swid,
+            Error::Veraison(_) => Self::Veraison,
+            Error::X509(_) => Self::X509,
+            Error::Validation(_) => Self::Validation,
+            Error::Custom(..) | Error::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// A failed call: the status returned and the message kept for [`corim_last_error_message`]
+#[derive(Debug)]
+pub(crate) struct Failure {
+    status: CorimStatus,
+    message: String,
+}
+
+impl Failure {
+    pub(crate) fn new(status: CorimStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        Self::new(CorimStatus::from(&error), error.to_string())
+    }
+}
+
+pub(crate) type FfiResult<T> = Result<T, Failure>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: Option<String>) {
+    let message = message.map(|message| {
+        CString::new(message.replace('\0', " ")).expect("interior NUL bytes were replaced")
+    });
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// Runs the body of an API function, recording its failure, or a panic, for
+/// [`corim_last_error_message`]
+pub(crate) fn call(body: impl FnOnce() -> FfiResult<()>) -> CorimStatus {
+    let result = panic::catch_unwind(AssertUnwindSafe(body))
+        .unwrap_or_else(|_| Err(Failure::new(CorimStatus::Panic, "corim-ffi panicked")));
+
+    match result {
+        Ok(()) => {
+            set_last_error(None);
+            CorimStatus::Ok
+        }
+        Err(failure) => {
+            set_last_error(Some(failure.message));
+            failure.status
+        }
+    }
+}
+
+/// Borrows the value behind a pointer argument
+pub(crate) unsafe fn arg<'a, T>(ptr: *const T, name: &str) -> FfiResult<&'a T> {
+    ptr.as_ref()
+        .ok_or_else(|| Failure::new(CorimStatus::NullPointer, format!("{name} is null")))
+}
+
+/// Borrows the value behind a mutable pointer argument
+pub(crate) unsafe fn arg_mut<'a, T>(ptr: *mut T, name: &str) -> FfiResult<&'a mut T> {
+    ptr.as_mut()
+        .ok_or_else(|| Failure::new(CorimStatus::NullPointer, format!("{name} is null")))
+}
+
+/// Borrows an input buffer, which may be null if `len` is 0
+pub(crate) unsafe fn bytes<'a>(data: *const u8, len: usize, name: &str) -> FfiResult<&'a [u8]> {
+    match (data.is_null(), len) {
+        (true, 0) => Ok(&[]),
+        (true, _) => Err(Failure::new(
+            CorimStatus::NullPointer,
+            format!("{name} is null"),
+        )),
+        (false, _) => Ok(slice::from_raw_parts(data, len)),
+    }
+}
+
+/// Borrows a NUL terminated UTF-8 string argument
+pub(crate) unsafe fn text<'a>(ptr: *const c_char, name: &str) -> FfiResult<&'a str> {
+    let text = arg(ptr, name)?;
+    CStr::from_ptr(text)
+        .to_str()
+        .map_err(|_| Failure::new(CorimStatus::InvalidArgument, format!("{name} is not UTF-8")))
+}
+
+/// Stores `value` in an output argument
+pub(crate) unsafe fn output<T>(out: *mut T, name: &str, value: T) -> FfiResult<()> {
+    *arg_mut(out, name)? = value;
+    Ok(())
+}
+
+/// Writes `data` to a caller supplied buffer of `*len` bytes, storing the length of `data` in
+/// `*len`
+pub(crate) unsafe fn write_buffer(data: &[u8], buf: *mut u8, len: *mut usize) -> FfiResult<()> {
+    let len = arg_mut(len, "len")?;
+    let capacity = std::mem::replace(len, data.len());
+
+    if buf.is_null() || capacity < data.len() {
+        return Err(Failure::new(
+            CorimStatus::BufferTooSmall,
+            format!("the result needs {} bytes", data.len()),
+        ));
+    }
+
+    ptr::copy_nonoverlapping(data.as_ptr(), buf, data.len());
+    Ok(())
+}
+
+/// Writes `text` and a terminating NUL to a caller supplied buffer, as [`write_buffer`] does
+pub(crate) unsafe fn write_text(text: &str, buf: *mut c_char, len: *mut usize) -> FfiResult<()> {
+    let text = CString::new(text.replace('\0', " ")).expect("interior NUL bytes were replaced");
+    write_buffer(text.as_bytes_with_nul(), buf.cast(), len)
+}
+
+/// Encodes `value` as CBOR
+pub(crate) fn encode<T: serde::Serialize>(value: &T) -> FfiResult<Vec<u8>> {
+    let mut cbor = vec![];
+    ciborium::into_writer(value, &mut cbor)
+        .map_err(|e| Failure::new(CorimStatus::Encode, e.to_string()))?;
+    Ok(cbor)
+}
+
+/// Decodes a CBOR input buffer
+pub(crate) fn decode<T: serde::Deserialize<'static>>(cbor: &[u8], what: &str) -> FfiResult<T> {
+    corim_rs::cbor::from_reader(cbor)
+        .map_err(|e| Failure::new(CorimStatus::Decode, format!("invalid {what}: {e}")))
+}
+
+/// Moves `value` to the heap and stores the resulting handle in `out`
+pub(crate) unsafe fn output_handle<T>(out: *mut *mut T, name: &str, value: T) -> FfiResult<()> {
+    let out = arg_mut(out, name)?;
+    *out = Box::into_raw(Box::new(value));
+    Ok(())
+}
+
+/// Releases a handle created by [`output_handle`]
+pub(crate) unsafe fn free_handle<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Returns a description of the last failed call on this thread, or null if the last call
+/// succeeded. The string is valid until the next call on this thread.
+#[no_mangle]
+pub extern "C" fn corim_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Returns a static, NUL terminated name for `status`, e.g. `"buffer too small"`
+#[no_mangle]
+pub extern "C" fn corim_status_name(status: CorimStatus) -> *const c_char {
+    let name: &'static CStr = match status {
+        CorimStatus::Ok => c"ok",
+        CorimStatus::NullPointer => c"null pointer",
+        CorimStatus::InvalidArgument => c"invalid argument",
+        CorimStatus::BufferTooSmall => c"buffer too small",
+        CorimStatus::OutOfRange => c"index out of range",
+        CorimStatus::NotPresent => c"not present",
+        CorimStatus::Decode => c"decoding error",
+        CorimStatus::Encode => c"encoding error",
+        CorimStatus::Panic => c"panic",
+        CorimStatus::Appraisal => c"appraisal error",
+        CorimStatus::Comid => c"CoMID error",
+        CorimStatus::Core => c"core error",
+        CorimStatus::Corim => c"CoRIM error",
+        CorimStatus::Cose => c"COSE error",
+        CorimStatus::Coswid => c"CoSWID error",
+        CorimStatus::Cotl => c"CoTL error",
+        CorimStatus::Edn => c"EDN error",
+        CorimStatus::Triples => c"triples error",
+        CorimStatus::Numbers => c"numbers error",
+        CorimStatus::Sbom => c"SBOM error",
+        CorimStatus::Stream => c"stream error",
+        CorimStatus::Swid => c"SWID error",
+        CorimStatus::Veraison => c"Veraison error",
+        CorimStatus::X509 => c"X.509 error",
+        CorimStatus::Unknown => c"unknown error",
+        CorimStatus::Validation => c"validation error",
+    };
+    name.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_buffer() {
+        let mut len = 0;
+        let status = call(|| unsafe { write_buffer(b"abc", ptr::null_mut(), &mut len) });
+        assert_eq!(status, CorimStatus::BufferTooSmall);
+        assert_eq!(len, 3);
+        let message = unsafe { CStr::from_ptr(corim_last_error_message()) };
+        assert_eq!(message.to_str().unwrap(), "the result needs 3 bytes");
+
+        let mut buf = [0u8; 4];
+        len = buf.len();
+        let status = call(|| unsafe { write_buffer(b"abc", buf.as_mut_ptr(), &mut len) });
+        assert_eq!(status, CorimStatus::Ok);
+        assert_eq!((&buf[..len], len), (&b"abc"[..], 3));
+        assert!(corim_last_error_message().is_null());
+    }
+
+    #[test]
+    fn test_panic_status() {
+        let status = call(|| panic!("boom"));
+        assert_eq!(status, CorimStatus::Panic);
+        assert_eq!(
+            unsafe { CStr::from_ptr(corim_status_name(status)) },
+            c"panic"
+        );
+    }
+}
@@ -0,0 +1,341 @@
+// SPDX-License-Identifier: MIT
+
+//! Verification of signed CoRIMs
+
+use std::ffi::c_char;
+
+use corim_rs::{
+    cose::{CoseVerifyingKey, SignerPolicy, Verifier},
+    x509::TrustAnchorStore,
+};
+
+use crate::{
+    arg, arg_mut, bytes, call, decode, free_handle, output, output_handle, text, Corim,
+    CorimStatus, Failure, FfiResult,
+};
+
+/// Public keys or trust anchors that signatures are verified against
+#[derive(Debug)]
+pub struct CorimVerifier(Verifier);
+
+/// Whether any or all signers of a multi-signed CoRIM must verify
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorimSignerPolicy {
+    /// At least one signer must verify
+    Any = 0,
+    /// Every signer must verify
+    All = 1,
+}
+
+impl From<CorimSignerPolicy> for SignerPolicy {
+    fn from(policy: CorimSignerPolicy) -> Self {
+        match policy {
+            CorimSignerPolicy::Any => SignerPolicy::AnyOf,
+            CorimSignerPolicy::All => SignerPolicy::AllOf,
+        }
+    }
+}
+
+impl CorimVerifier {
+    unsafe fn keys<'a>(verifier: *mut Self) -> FfiResult<&'a mut Vec<CoseVerifyingKey>> {
+        match &mut arg_mut(verifier, "verifier")?.0 {
+            Verifier::Keys(keys) => Ok(keys),
+            Verifier::TrustAnchors(_) => Err(Failure::new(
+                CorimStatus::InvalidArgument,
+                "the verifier holds trust anchors, not keys",
+            )),
+        }
+    }
+}
+
+/// Creates a verifier that checks signatures with public keys, added with the
+/// `corim_verifier_add_*key` functions. The handle is released with [`corim_verifier_free`].
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_new_keys(out: *mut *mut CorimVerifier) -> CorimStatus {
+    call(|| output_handle(out, "out", CorimVerifier(Verifier::Keys(vec![]))))
+}
+
+/// Creates a verifier that validates the x5chain of each signer against trust anchors, added
+/// with [`corim_verifier_add_trust_anchors_pem`]. The handle is released with
+/// [`corim_verifier_free`].
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_new_trust_anchors(
+    out: *mut *mut CorimVerifier,
+) -> CorimStatus {
+    call(|| {
+        let anchors = TrustAnchorStore::new();
+        output_handle(out, "out", CorimVerifier(Verifier::TrustAnchors(anchors)))
+    })
+}
+
+/// Releases a verifier
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_free(verifier: *mut CorimVerifier) {
+    free_handle(verifier)
+}
+
+/// Adds a PEM encoded public key to a key verifier
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_add_public_key_pem(
+    verifier: *mut CorimVerifier,
+    pem: *const c_char,
+) -> CorimStatus {
+    call(|| {
+        let key = CoseVerifyingKey::from_public_key_pem(text(pem, "pem")?)?;
+        CorimVerifier::keys(verifier)?.push(key);
+        Ok(())
+    })
+}
+
+/// Adds a DER encoded SubjectPublicKeyInfo of `len` bytes to a key verifier
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_add_public_key_der(
+    verifier: *mut CorimVerifier,
+    der: *const u8,
+    len: usize,
+) -> CorimStatus {
+    call(|| {
+        let key = CoseVerifyingKey::from_public_key_der(bytes(der, len, "der")?)?;
+        CorimVerifier::keys(verifier)?.push(key);
+        Ok(())
+    })
+}
+
+/// Adds the subject public key of a DER encoded X.509 certificate of `len` bytes to a key
+/// verifier
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_add_certificate_der(
+    verifier: *mut CorimVerifier,
+    der: *const u8,
+    len: usize,
+) -> CorimStatus {
+    call(|| {
+        let key = CoseVerifyingKey::from_certificate_der(bytes(der, len, "der")?)?;
+        CorimVerifier::keys(verifier)?.push(key);
+        Ok(())
+    })
+}
+
+/// Adds a CBOR encoded COSE_Key of `len` bytes to a key verifier
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_add_cose_key(
+    verifier: *mut CorimVerifier,
+    cbor: *const u8,
+    len: usize,
+) -> CorimStatus {
+    call(|| {
+        let cose_key = decode(bytes(cbor, len, "cbor")?, "COSE_Key")?;
+        let key = CoseVerifyingKey::from_cose_key(&cose_key)?;
+        CorimVerifier::keys(verifier)?.push(key);
+        Ok(())
+    })
+}
+
+/// Adds every certificate of a PEM file to a trust anchor verifier
+#[no_mangle]
+pub unsafe extern "C" fn corim_verifier_add_trust_anchors_pem(
+    verifier: *mut CorimVerifier,
+    pem: *const c_char,
+) -> CorimStatus {
+    call(|| {
+        let pem = text(pem, "pem")?;
+        match &mut arg_mut(verifier, "verifier")?.0 {
+            Verifier::TrustAnchors(anchors) => Ok(anchors.add_pem(pem)?),
+            Verifier::Keys(_) => Err(Failure::new(
+                CorimStatus::InvalidArgument,
+                "the verifier holds keys, not trust anchors",
+            )),
+        }
+    })
+}
+
+/// Verifies the signatures of `corim` at `time`, in seconds since the Unix epoch, and checks
+/// that the signature validity period of each verified signer contains `time`.
+///
+/// `policy` applies to multi-signed CoRIMs. `payload` holds the `payload_len` bytes of the
+/// payload of a detached signed CoRIM and may be null otherwise. On success the number of
+/// signers that verified is stored in `*signers`, which may be null. A signature that does not
+/// verify returns [`CorimStatus::Cose`].
+#[no_mangle]
+pub unsafe extern "C" fn corim_verify(
+    corim: *const Corim,
+    verifier: *const CorimVerifier,
+    policy: CorimSignerPolicy,
+    time: u64,
+    payload: *const u8,
+    payload_len: usize,
+    signers: *mut usize,
+) -> CorimStatus {
+    call(|| {
+        let corim = &arg(corim, "corim")?.0;
+        let verifier = &arg(verifier, "verifier")?.0;
+        let payload = (!payload.is_null())
+            .then(|| bytes(payload, payload_len, "payload"))
+            .transpose()?;
+
+        let verified = corim.verify(verifier, policy.into(), time, payload)?;
+        if !signers.is_null() {
+            output(signers, "signers", verified.len())?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{corim::tests::UNSIGNED, corim_decode, corim_free};
+    use corim_rs::{
+        core::Bytes,
+        corim::{
+            COSESign1Corim, ConciseRimTypeChoice, CorimMetaMap, CorimSignerMap,
+            ProtectedCorimHeaderMap,
+        },
+        cose::CoseSigningKey,
+    };
+    use std::{ffi::CString, ptr};
+    use x509_cert::{
+        der::{DecodePem, Encode},
+        Certificate,
+    };
+
+    const ROOT: &str = include_str!("../../testdata/x509/root.pem");
+    const INTERMEDIATE: &str = include_str!("../../testdata/x509/intermediate.pem");
+    const LEAF: &str = include_str!("../../testdata/x509/leaf.pem");
+    const LEAF_KEY: &str = include_str!("../../testdata/x509/leaf.key");
+
+    // 2025-01-01T00:00:00Z
+    const NOW: u64 = 1_735_689_600;
+
+    fn der(pem: &str) -> Vec<u8> {
+        Certificate::from_pem(pem).unwrap().to_der().unwrap()
+    }
+
+    /// Signs the unsigned test CoRIM with the leaf key, carrying the leaf and intermediate
+    /// certificates in the x5chain header
+    fn signed() -> Vec<u8> {
+        let ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) =
+            corim_rs::cbor::from_slice(UNSIGNED).unwrap()
+        else {
+            panic!("the test CoRIM is not unsigned");
+        };
+
+        let mut corim = COSESign1Corim {
+            protected: ProtectedCorimHeaderMap {
+                alg: 0.into(),
+                content_type: "application/rim+cbor".into(),
+                kid: Bytes::from(b"key-001".as_slice()),
+                corim_meta: CorimMetaMap {
+                    signer: CorimSignerMap {
+                        signer_name: "Test CoRIM Signer".into(),
+                        signer_uri: None,
+                        extension: None,
+                    },
+                    signature_validity: None,
+                },
+                cose_map: None,
+            }
+            .into(),
+            unprotected: Default::default(),
+            payload: map.into(),
+            signature: Bytes::from(vec![]).into(),
+        };
+        corim
+            .protected
+            .set_x5chain([der(LEAF), der(INTERMEDIATE)])
+            .unwrap();
+        corim
+            .sign(&CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap())
+            .unwrap();
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&ConciseRimTypeChoice::SignedCorim(corim.into()), &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_verify() {
+        let signed = signed();
+        let leaf = der(LEAF);
+
+        unsafe {
+            let mut corim = ptr::null_mut();
+            assert_eq!(
+                corim_decode(signed.as_ptr(), signed.len(), &mut corim),
+                CorimStatus::Ok
+            );
+
+            let mut verifier = ptr::null_mut();
+            assert_eq!(corim_verifier_new_keys(&mut verifier), CorimStatus::Ok);
+            assert_eq!(
+                corim_verify(
+                    corim,
+                    verifier,
+                    CorimSignerPolicy::All,
+                    NOW,
+                    ptr::null(),
+                    0,
+                    ptr::null_mut()
+                ),
+                CorimStatus::Cose
+            );
+
+            assert_eq!(
+                corim_verifier_add_public_key_der(verifier, leaf.as_ptr(), leaf.len()),
+                CorimStatus::Cose
+            );
+            assert_eq!(
+                corim_verifier_add_certificate_der(verifier, leaf.as_ptr(), leaf.len()),
+                CorimStatus::Ok
+            );
+            let root = CString::new(ROOT).unwrap();
+            assert_eq!(
+                corim_verifier_add_trust_anchors_pem(verifier, root.as_ptr()),
+                CorimStatus::InvalidArgument
+            );
+
+            let mut signers = 0;
+            assert_eq!(
+                corim_verify(
+                    corim,
+                    verifier,
+                    CorimSignerPolicy::All,
+                    NOW,
+                    ptr::null(),
+                    0,
+                    &mut signers
+                ),
+                CorimStatus::Ok
+            );
+            assert_eq!(signers, 1);
+            corim_verifier_free(verifier);
+
+            assert_eq!(
+                corim_verifier_new_trust_anchors(&mut verifier),
+                CorimStatus::Ok
+            );
+            assert_eq!(
+                corim_verifier_add_trust_anchors_pem(verifier, root.as_ptr()),
+                CorimStatus::Ok
+            );
+            signers = 0;
+            assert_eq!(
+                corim_verify(
+                    corim,
+                    verifier,
+                    CorimSignerPolicy::Any,
+                    NOW,
+                    ptr::null(),
+                    0,
+                    &mut signers
+                ),
+                CorimStatus::Ok
+            );
+            assert_eq!(signers, 1);
+            corim_verifier_free(verifier);
+            corim_free(corim);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: MIT
+
+//! The `Comid` and `ComidBuilder` classes
+
+use corim_rs::{
+    comid::{
+        ConciseMidTag, TagIdTypeChoice, TagIdentityMap, TaggedConciseMidTag, TriplesMapBuilder,
+    },
+    describe::describe_tag_id,
+    numbers::Integer,
+    triples::{EndorsedTripleRecord, EnvironmentMap, MeasurementMap, ReferenceTripleRecord},
+};
+use pyo3::{prelude::*, types::PyBytes};
+
+use crate::{decode, encode, error, from_json, python_json, to_python};
+
+/// A CoMID tag
+#[pyclass(module = "corim", frozen, skip_from_py_object)]
+#[derive(Debug, Clone)]
+pub struct Comid(pub(crate) ConciseMidTag<'static>);
+
+#[pymethods]
+impl Comid {
+    /// Decodes a CoMID, tagged with CBOR tag 506, from CBOR
+    #[staticmethod]
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        decode::<TaggedConciseMidTag>(data).map(|comid| Self(comid.as_ref().clone()))
+    }
+
+    /// Encodes the CoMID as CBOR, tagged with CBOR tag 506
+    fn encode<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        encode(py, &TaggedConciseMidTag::new(self.0.clone()))
+    }
+
+    /// Converts a dict in the JSON representation of the crate into a CoMID
+    #[staticmethod]
+    fn from_dict(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        from_json::<ConciseMidTag>(&python_json(value)?).map(Self)
+    }
+
+    /// Converts the CoMID into a dict in the JSON representation of the crate
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_python(py, &self.0)
+    }
+
+    /// The tag identifier of the CoMID
+    #[getter]
+    fn tag_id(&self) -> String {
+        describe_tag_id(&self.0.tag_identity.tag_id)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Comid({:?})", self.tag_id())
+    }
+}
+
+/// Builds a CoMID from reference and endorsed values.
+///
+/// Environments and measurements are dicts in the JSON representation of the crate's
+/// `environment-map` and `measurement-map`.
+#[pyclass(module = "corim")]
+#[derive(Debug)]
+pub struct ComidBuilder {
+    tag_id: String,
+    tag_version: Option<u64>,
+    reference_triples: Vec<ReferenceTripleRecord<'static>>,
+    endorsed_triples: Vec<EndorsedTripleRecord<'static>>,
+}
+
+#[pymethods]
+impl ComidBuilder {
+    /// Starts a CoMID identified by `tag_id`
+    #[new]
+    #[pyo3(signature = (tag_id, tag_version=None))]
+    fn new(tag_id: String, tag_version: Option<u64>) -> Self {
+        Self {
+            tag_id,
+            tag_version,
+            reference_triples: vec![],
+            endorsed_triples: vec![],
+        }
+    }
+
+    /// Adds a reference triple: the `measurements` expected of `environment`
+    fn add_reference_values<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        environment: &Bound<'py, PyAny>,
+        measurements: &Bound<'py, PyAny>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        slf.reference_triples.push(ReferenceTripleRecord {
+            ref_env: from_json::<EnvironmentMap>(&python_json(environment)?)?,
+            ref_claims: from_json::<Vec<MeasurementMap>>(&python_json(measurements)?)?,
+        });
+        Ok(slf)
+    }
+
+    /// Adds an endorsed triple: the `measurements` endorsed for `environment`
+    fn add_endorsed_values<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        environment: &Bound<'py, PyAny>,
+        measurements: &Bound<'py, PyAny>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        slf.endorsed_triples.push(EndorsedTripleRecord {
+            condition: from_json::<EnvironmentMap>(&python_json(environment)?)?,
+            endorsement: from_json::<Vec<MeasurementMap>>(&python_json(measurements)?)?,
+        });
+        Ok(slf)
+    }
+
+    /// Builds the CoMID, raising `CorimError` if no triples were added
+    fn build(&self) -> PyResult<Comid> {
+        let mut triples = TriplesMapBuilder::default();
+        if !self.reference_triples.is_empty() {
+            triples = triples.reference_triples(self.reference_triples.clone());
+        }
+        if !self.endorsed_triples.is_empty() {
+            triples = triples.endorsed_triples(self.endorsed_triples.clone());
+        }
+
+        Ok(Comid(ConciseMidTag {
+            language: None,
+            tag_identity: TagIdentityMap {
+                tag_id: TagIdTypeChoice::Tstr(self.tag_id.clone().into()),
+                tag_version: self.tag_version.map(Integer::from),
+            },
+            entities: None,
+            linked_tags: None,
+            triples: triples.build().map_err(error)?,
+            extension: None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::run;
+
+    #[test]
+    fn test_build() {
+        run(cr#"
+environment = {"class": {"vendor": "ACME Inc.", "model": "RoadRunner"}}
+comid = (
+    corim.ComidBuilder("acme-roadrunner-comid", tag_version=1)
+    .add_reference_values(environment, [{"mkey": "firmware", "mval": {"svn": 3}}])
+    .add_endorsed_values(environment, [{"mval": {"name": "RoadRunner firmware"}}])
+    .build()
+)
+assert comid.tag_id == "acme-roadrunner-comid"
+assert corim.Comid.decode(comid.encode()).to_dict() == comid.to_dict()
+
+# Maps are keyed by their CBOR keys: triples-map is entry 4 of the CoMID, reference-triples
+# entry 0 and endorsed-triples entry 1 of the triples-map
+triples = comid.to_dict()["4"]
+assert triples["0"] == [[environment, [{"mkey": "firmware", "mval": {"svn": 3}}]]]
+assert len(triples["1"]) == 1
+
+unsigned = corim.Corim("acme-roadrunner-corim", [comid])
+decoded = corim.Corim.decode(unsigned.encode())
+assert decoded.id == "acme-roadrunner-corim"
+assert decoded.comids[0].to_dict() == comid.to_dict()
+
+try:
+    corim.ComidBuilder("empty").build()
+    raise AssertionError("building an empty CoMID did not fail")
+except corim.CorimError:
+    pass
+
+try:
+    corim.ComidBuilder("invalid").add_reference_values({"class": 1}, [])
+    raise AssertionError("an invalid environment was accepted")
+except ValueError:
+    pass
+"#);
+    }
+}
@@ -0,0 +1,381 @@
+// SPDX-License-Identifier: MIT
+
+//! The `Corim` class: decoding, encoding, signing and verification of CoRIMs
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use corim_rs::{
+    core::{Bytes, CoseKey, Text},
+    corim::{
+        COSESign1Corim, ConciseRimTypeChoice, ConciseTagTypeChoice, CorimIdTypeChoice, CorimMap,
+        CorimMetaMap, CorimSignerMap, ProtectedCorimHeaderMap, UnprotectedCorimHeaderMap,
+        ValidityMap,
+    },
+    cose::{CoseSigningKey, CoseVerifyingKey, SignerPolicy, Verifier},
+    x509::TrustAnchorStore,
+};
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyBytes, PyDict, PyString},
+};
+use x509_cert::{der::Encode, Certificate};
+
+use crate::{decode, encode, error, from_json, python_json, to_python, Comid};
+
+/// Content type of the payload of a signed CoRIM
+const CONTENT_TYPE: &str = "application/rim+cbor";
+
+/// A CoRIM, unsigned or signed
+#[pyclass(module = "corim", frozen)]
+#[derive(Debug)]
+pub struct Corim(pub(crate) ConciseRimTypeChoice<'static>);
+
+impl Corim {
+    /// Returns the CoRIM map, which detached signed CoRIMs do not carry
+    fn map(&self) -> Option<&CorimMap<'static>> {
+        match &self.0 {
+            ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) => Some(map.as_ref()),
+            ConciseRimTypeChoice::SignedCorim(signed) => Some(signed.payload.as_ref()),
+            ConciseRimTypeChoice::MultiSignedCorim(signed) => Some(signed.payload.as_ref()),
+            ConciseRimTypeChoice::DetachedSignedCorim(_) => None,
+        }
+    }
+}
+
+#[pymethods]
+impl Corim {
+    /// Creates an unsigned CoRIM identified by `id` that carries the CoMIDs `tags`
+    #[new]
+    fn new(id: String, tags: Vec<PyRef<'_, Comid>>) -> Self {
+        let map = CorimMap {
+            id: CorimIdTypeChoice::Tstr(id.into()),
+            tags: tags
+                .iter()
+                .map(|comid| ConciseTagTypeChoice::Mid(comid.0.clone().into()))
+                .collect(),
+            dependent_rims: None,
+            profile: None,
+            rim_validity: None,
+            entities: None,
+            extension: None,
+        };
+        Self(ConciseRimTypeChoice::TaggedUnsignedCorimMap(map.into()))
+    }
+
+    /// Decodes an unsigned or signed CoRIM from CBOR
+    #[staticmethod]
+    fn decode(data: &[u8]) -> PyResult<Self> {
+        decode(data).map(Self)
+    }
+
+    /// Encodes the CoRIM as CBOR
+    fn encode<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        encode(py, &self.0)
+    }
+
+    /// Converts a dict in the JSON representation of the crate into a CoRIM
+    #[staticmethod]
+    fn from_dict(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        from_json::<ConciseRimTypeChoice>(&python_json(value)?).map(Self)
+    }
+
+    /// Converts the CoRIM into a dict in the JSON representation of the crate
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_python(py, &self.0)
+    }
+
+    /// The form of the CoRIM: `unsigned-corim`, `signed-corim`, `detached-signed-corim` or
+    /// `multi-signed-corim`
+    #[getter]
+    fn kind(&self) -> &'static str {
+        match &self.0 {
+            ConciseRimTypeChoice::TaggedUnsignedCorimMap(_) => "unsigned-corim",
+            ConciseRimTypeChoice::SignedCorim(_) => "signed-corim",
+            ConciseRimTypeChoice::DetachedSignedCorim(_) => "detached-signed-corim",
+            ConciseRimTypeChoice::MultiSignedCorim(_) => "multi-signed-corim",
+        }
+    }
+
+    /// The identifier of the CoRIM, or `None` for a detached signed CoRIM
+    #[getter]
+    fn id(&self) -> Option<String> {
+        self.map().map(|map| match &map.id {
+            CorimIdTypeChoice::Tstr(id) => id.to_string(),
+            CorimIdTypeChoice::Uuid(id) => id.to_string(),
+        })
+    }
+
+    /// The CoMIDs of the CoRIM
+    #[getter]
+    fn comids(&self) -> Vec<Comid> {
+        self.map()
+            .into_iter()
+            .flat_map(|map| &map.tags)
+            .filter_map(|tag| match tag {
+                ConciseTagTypeChoice::Mid(comid) => Some(Comid(comid.as_ref().clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Signs an unsigned CoRIM, returning a COSE_Sign1 signed CoRIM.
+    ///
+    /// `key` is a PKCS#8 PEM private key, or the CBOR encoding of a COSE_Key whose `kid` is
+    /// used when `kid` is not given. `validity` is a `(not_before, not_after)` pair of times in
+    /// seconds since the Unix epoch, where `not_before` may be `None`. `x5chain` holds the PEM
+    /// certificate chain of the key, leaf first, and is carried in the protected header.
+    #[pyo3(signature = (key, signer_name, kid=None, signer_uri=None, validity=None, x5chain=None))]
+    fn sign(
+        &self,
+        key: &Bound<'_, PyAny>,
+        signer_name: String,
+        kid: Option<Vec<u8>>,
+        signer_uri: Option<String>,
+        validity: Option<(Option<u64>, u64)>,
+        x5chain: Option<&str>,
+    ) -> PyResult<Self> {
+        let ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) = &self.0 else {
+            return Err(PyValueError::new_err("the CoRIM is already signed"));
+        };
+
+        let (key, key_kid) = signing_key(key)?;
+        let kid = match (kid, key_kid) {
+            (Some(kid), _) => Bytes::from(kid),
+            (None, Some(kid)) => kid,
+            (None, None) => {
+                return Err(PyValueError::new_err(
+                    "kid is required unless the key is a COSE_Key with a kid",
+                ))
+            }
+        };
+
+        let signature_validity = validity
+            .map(|(not_before, not_after)| {
+                if not_before.is_some_and(|not_before| not_before > not_after) {
+                    return Err(PyValueError::new_err("not_before is after not_after"));
+                }
+                Ok(ValidityMap::new(
+                    not_before.map(|t| i128::from(t).into()),
+                    i128::from(not_after).into(),
+                ))
+            })
+            .transpose()?;
+
+        let mut protected = ProtectedCorimHeaderMap {
+            alg: i64::from(key.algorithm()).into(),
+            content_type: CONTENT_TYPE.into(),
+            kid,
+            corim_meta: CorimMetaMap {
+                signer: CorimSignerMap {
+                    signer_name: signer_name.into(),
+                    signer_uri: signer_uri.map(|uri| Text::from(uri).into()),
+                    extension: None,
+                },
+                signature_validity,
+            },
+            cose_map: None,
+        };
+
+        if let Some(pem) = x5chain {
+            let chain = pem_certificates(pem)?;
+            if CoseVerifyingKey::from_certificate_der(&chain[0]).map_err(error)?
+                != key.verifying_key()
+            {
+                return Err(PyValueError::new_err(
+                    "the leaf certificate of the x5chain does not match the signing key",
+                ));
+            }
+            protected.set_x5chain(chain).map_err(error)?;
+        }
+
+        let mut signed = COSESign1Corim {
+            protected: protected.into(),
+            unprotected: UnprotectedCorimHeaderMap::default(),
+            payload: map.clone().into(),
+            signature: Bytes::default().into(),
+        };
+        signed.sign(&key).map_err(error)?;
+
+        Ok(Self(ConciseRimTypeChoice::SignedCorim(signed.into())))
+    }
+
+    /// Verifies the signatures of a signed CoRIM, raising `CorimError` if they do not verify,
+    /// and returns the signers that verified as dicts with `index`, `kid` and `meta` entries.
+    ///
+    /// Signatures are verified against `keys`, a list of PEM public keys or CBOR encoded
+    /// COSE_Keys, or against `trust_anchors`, the PEM certificates the x5chain of each signer
+    /// must chain to. `policy` is `any` or `all` and applies to multi-signed CoRIMs. `time`,
+    /// in seconds since the Unix epoch, defaults to now. `payload` is the payload of a
+    /// detached signed CoRIM.
+    #[pyo3(signature = (keys=None, trust_anchors=None, policy="any", time=None, payload=None))]
+    fn verify<'py>(
+        &self,
+        py: Python<'py>,
+        keys: Option<Vec<Bound<'py, PyAny>>>,
+        trust_anchors: Option<&str>,
+        policy: &str,
+        time: Option<u64>,
+        payload: Option<&[u8]>,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let verifier = match (keys, trust_anchors) {
+            (Some(keys), None) => {
+                Verifier::Keys(keys.iter().map(verifying_key).collect::<PyResult<_>>()?)
+            }
+            (None, Some(pem)) => {
+                let mut anchors = TrustAnchorStore::new();
+                anchors.add_pem(pem).map_err(error)?;
+                Verifier::TrustAnchors(anchors)
+            }
+            _ => {
+                return Err(PyValueError::new_err(
+                    "exactly one of keys and trust_anchors is required",
+                ))
+            }
+        };
+        let policy = match policy {
+            "any" => SignerPolicy::AnyOf,
+            "all" => SignerPolicy::AllOf,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown policy {other:?}, expected any or all"
+                )))
+            }
+        };
+        let time = match time {
+            Some(time) => time,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(error)?
+                .as_secs(),
+        };
+
+        let signers = self
+            .0
+            .verify(&verifier, policy, time, payload)
+            .map_err(error)?;
+
+        signers
+            .iter()
+            .map(|signer| {
+                let dict = PyDict::new(py);
+                dict.set_item("index", signer.index)?;
+                dict.set_item("kid", PyBytes::new(py, signer.kid.as_ref()))?;
+                dict.set_item("meta", to_python(py, signer.meta)?)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        match self.id() {
+            Some(id) => format!("Corim({}, {id:?})", self.kind()),
+            None => format!("Corim({})", self.kind()),
+        }
+    }
+}
+
+/// Reads a private key from a PKCS#8 PEM string or a CBOR encoded COSE_Key, along with the
+/// `kid` of a COSE_Key
+fn signing_key(key: &Bound<'_, PyAny>) -> PyResult<(CoseSigningKey, Option<Bytes<'static>>)> {
+    if let Ok(pem) = key.cast::<PyString>() {
+        let key = CoseSigningKey::from_pkcs8_pem(pem.to_str()?).map_err(error)?;
+        return Ok((key, None));
+    }
+
+    let cose_key: CoseKey = decode(key.extract::<&[u8]>()?)?;
+    let signing_key = CoseSigningKey::from_cose_key(&cose_key).map_err(error)?;
+    Ok((signing_key, cose_key.kid))
+}
+
+/// Reads a public key from a PEM string or a CBOR encoded COSE_Key
+fn verifying_key(key: &Bound<'_, PyAny>) -> PyResult<CoseVerifyingKey> {
+    if let Ok(pem) = key.cast::<PyString>() {
+        return CoseVerifyingKey::from_public_key_pem(pem.to_str()?).map_err(error);
+    }
+
+    let cose_key: CoseKey = decode(key.extract::<&[u8]>()?)?;
+    CoseVerifyingKey::from_cose_key(&cose_key).map_err(error)
+}
+
+/// Reads every certificate of a PEM string, in order, as DER
+fn pem_certificates(pem: &str) -> PyResult<Vec<Vec<u8>>> {
+    let certs = Certificate::load_pem_chain(pem.as_bytes())
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    if certs.is_empty() {
+        return Err(PyValueError::new_err("x5chain holds no certificates"));
+    }
+
+    certs
+        .iter()
+        .map(|cert| cert.to_der().map_err(error))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::run;
+
+    #[test]
+    fn test_decode_and_convert() {
+        run(cr#"
+data = open("../testdata/corim/unsigned.cbor", "rb").read()
+unsigned = corim.Corim.decode(data)
+assert unsigned.kind == "unsigned-corim"
+assert unsigned.id == "acme-roadrunner-corim"
+assert unsigned.encode() == data
+assert repr(unsigned) == "Corim(unsigned-corim, \"acme-roadrunner-corim\")"
+
+value = unsigned.to_dict()
+assert value["type"] == "unsigned-corim"
+assert corim.Corim.from_dict(value).encode() == data
+
+comids = unsigned.comids
+assert len(comids) == 1
+assert comids[0].tag_id == "acme-roadrunner-comid"
+"#);
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        run(cr#"
+leaf_key = open("../testdata/x509/leaf.key").read()
+chain = open("../testdata/x509/leaf.pem").read() + open("../testdata/x509/intermediate.pem").read()
+root = open("../testdata/x509/root.pem").read()
+other_root = open("../testdata/x509/other-root.pem").read()
+now = 1735689600
+
+unsigned = corim.Corim.decode(open("../testdata/corim/unsigned.cbor", "rb").read())
+signed = unsigned.sign(
+    leaf_key, "ACME Inc.", kid=b"key-1", validity=(None, now + 60), x5chain=chain
+)
+assert signed.kind == "signed-corim"
+signed = corim.Corim.decode(signed.encode())
+
+signers = signed.verify(trust_anchors=root, time=now)
+assert len(signers) == 1
+assert signers[0]["index"] == 0
+assert signers[0]["kid"] == b"key-1"
+assert signers[0]["meta"]["0"]["0"] == "ACME Inc."
+
+for kwargs in [{"trust_anchors": other_root, "time": now}, {"trust_anchors": root, "time": now + 120}]:
+    try:
+        signed.verify(**kwargs)
+        raise AssertionError("verification did not fail")
+    except corim.CorimError:
+        pass
+
+try:
+    signed.sign(leaf_key, "ACME Inc.", kid=b"key-1")
+    raise AssertionError("signing a signed CoRIM did not fail")
+except ValueError:
+    pass
+
+try:
+    signed.verify(time=now)
+    raise AssertionError("verification without keys did not fail")
+except ValueError:
+    pass
+"#);
+    }
+}
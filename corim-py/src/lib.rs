@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MIT
+
+//! # corim-py
+//!
+//! Python bindings for corim-rs, built with [maturin](https://www.maturin.rs) into a module
+//! named `corim`:
+//!
+//! ```python
+//! import corim
+//!
+//! comid = (
+//!     corim.ComidBuilder("acme-roadrunner-comid")
+//!     .add_reference_values(
+//!         {"class": {"vendor": "ACME Inc.", "model": "RoadRunner"}},
+//!         [{"mkey": "firmware", "mval": {"svn": 3}}],
+//!     )
+//!     .build()
+//! )
+//! unsigned = corim.Corim("acme-roadrunner-corim", [comid])
+//! signed = unsigned.sign(open("key.pem").read(), "ACME Inc.", kid=b"key-1")
+//! signers = corim.Corim.decode(signed.encode()).verify(keys=[open("pub.pem").read()])
+//! ```
+//!
+//! Values are converted to and from Python objects through the JSON representation of the
+//! crate, so `to_dict` returns, and `from_dict` accepts, what `json.loads` makes of that JSON.
+//! Failures of the library are raised as `corim.CorimError`, invalid arguments as `ValueError`.
+
+use std::fmt::Display;
+
+use corim_rs::owned::IntoOwned;
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+use serde::{Deserialize, Serialize};
+
+mod comid;
+mod corim;
+
+pub use comid::*;
+pub use corim::*;
+
+create_exception!(
+    corim,
+    CorimError,
+    PyException,
+    "Raised when a value cannot be decoded, encoded, signed or verified"
+);
+
+/// Converts a failure of the library into a [`CorimError`]
+pub(crate) fn error(e: impl Display) -> PyErr {
+    CorimError::new_err(e.to_string())
+}
+
+/// Encodes a value as CBOR
+pub(crate) fn encode<'py, T: Serialize>(
+    py: Python<'py>,
+    value: &T,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let mut bytes = vec![];
+    ciborium::into_writer(value, &mut bytes).map_err(error)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Decodes a value from CBOR
+pub(crate) fn decode<T: Deserialize<'static>>(data: &[u8]) -> PyResult<T> {
+    corim_rs::cbor::from_reader(data).map_err(error)
+}
+
+/// Converts a value to Python objects through its JSON representation
+pub(crate) fn to_python<'py, T: Serialize>(
+    py: Python<'py>,
+    value: &T,
+) -> PyResult<Bound<'py, PyAny>> {
+    let json = serde_json::to_string(value).map_err(error)?;
+    py.import("json")?.call_method1("loads", (json,))
+}
+
+/// Converts Python objects to JSON, to be read by [`from_json`]
+pub(crate) fn python_json(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()
+}
+
+/// Reads a value from the JSON of Python objects, copying any text it borrows from `json`
+pub(crate) fn from_json<'a, T>(json: &'a str) -> PyResult<T::Owned>
+where
+    T: Deserialize<'a> + IntoOwned,
+{
+    serde_json::from_str::<T>(json)
+        .map(IntoOwned::into_owned)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Decoding, generation, signing and verification of CoRIMs
+#[pymodule]
+#[pyo3(name = "corim")]
+fn corim_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Corim>()?;
+    m.add_class::<Comid>()?;
+    m.add_class::<ComidBuilder>()?;
+    m.add("CorimError", m.py().get_type::<CorimError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+    use std::ffi::CStr;
+
+    /// Runs Python code with the module imported as `corim`
+    pub(crate) fn run(code: &CStr) {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "corim").unwrap();
+            corim_module(&module).unwrap();
+            let locals = PyDict::new(py);
+            locals.set_item("corim", module).unwrap();
+            if let Err(e) = py.run(code, None, Some(&locals)) {
+                e.display(py);
+                panic!("the Python test failed: {e}");
+            }
+        })
+    }
+
+    #[test]
+    fn test_errors() {
+        run(cr#"
+try:
+    corim.Corim.decode(b"\x00")
+    raise AssertionError("decoding did not fail")
+except corim.CorimError as e:
+    assert isinstance(e, Exception)
+
+try:
+    corim.Corim.from_dict({"type": "unsigned-corim"})
+    raise AssertionError("conversion did not fail")
+except ValueError as e:
+    assert "value" in str(e)
+"#);
+    }
+}
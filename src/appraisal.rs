@@ -0,0 +1,733 @@
+// SPDX-License-Identifier: MIT
+
+//! Appraisal of evidence against the reference values of CoRIMs
+//!
+//! A [`ReferenceStore`] collects the reference and endorsed triples of the CoMIDs carried by
+//! one or more reference CoRIMs. [`ReferenceStore::appraise`] then matches the claims that
+//! evidence makes about each of its environments against the reference values whose
+//! environment they match, and reports the outcome of every comparison in a [`Report`].
+//!
+//! # Key Components
+//!
+//! * [`ReferenceStore`] - The reference and endorsed values of a set of CoRIMs
+//! * [`EvidenceClaims`] - The measurements evidence claims for one environment
+//! * [`Report`] - The outcome of an appraisal, down to each compared value
+//! * [`environment_matches`] - Whether evidence matches the environment of a triple
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use corim_rs::{appraisal::{EvidenceClaims, ReferenceStore}, corim::ConciseRimTypeChoice};
+//!
+//! # fn example(cbor: &[u8], evidence: &str) -> corim_rs::result::Result<()> {
+//! let corim: ConciseRimTypeChoice = corim_rs::cbor::from_slice(cbor)?;
+//!
+//! let mut store = ReferenceStore::default();
+//! store.add("platform.cbor", &corim)?;
+//!
+//! let report = store.appraise(&EvidenceClaims::from_json(evidence)?);
+//! println!("{}", if report.pass { "pass" } else { "fail" });
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Signatures are not checked when CoRIMs are added; verify signed CoRIMs with
+//! [`ConciseRimTypeChoice::verify`] first.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    describe::{describe, describe_environment, describe_measurement, describe_tag_id},
+    owned::IntoOwned,
+    AppraisalError, ConciseMidTag, ConciseRimTypeChoice, ConciseTagTypeChoice, Digest,
+    EndorsedTripleRecord, EnvironmentMap, FlagsMap, IntegrityRegisters, MeasurementMap,
+    MeasurementValuesMap, RawValueType, ReferenceTripleRecord, Result, SvnTypeChoice,
+};
+
+/// Detail of a check whose reference value has no counterpart in the evidence
+const MISSING: &str = "missing from evidence";
+
+/// Outcome of comparing one value: what matched, or why the values differ
+type Check = core::result::Result<String, String>;
+
+/// The claims made by evidence about one environment
+#[derive(Debug, Clone)]
+pub struct EvidenceClaims {
+    /// The environment the claims are about
+    pub environment: EnvironmentMap<'static>,
+    /// The measurements of the environment
+    pub measurements: Vec<MeasurementMap<'static>>,
+}
+
+impl<'de> Deserialize<'de> for EvidenceClaims {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// The claims as decoded, borrowing from the input
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Claims<'a> {
+            #[serde(borrow)]
+            environment: EnvironmentMap<'a>,
+            #[serde(borrow, default)]
+            measurements: Vec<MeasurementMap<'a>>,
+        }
+
+        let claims = Claims::deserialize(deserializer)?;
+        Ok(Self {
+            environment: claims.environment.into_owned(),
+            measurements: claims.measurements.into_owned(),
+        })
+    }
+}
+
+impl EvidenceClaims {
+    /// Parses evidence claims from JSON: an object with `environment` and `measurements`
+    /// entries, in the JSON form of the corresponding CoMID types, or an array of such objects
+    pub fn from_json(text: &str) -> Result<Vec<Self>> {
+        let claims = if text.trim_start().starts_with('[') {
+            serde_json::from_str(text)
+        } else {
+            serde_json::from_str(text).map(|claims| vec![claims])
+        };
+
+        Ok(claims.map_err(|e| AppraisalError::InvalidEvidence(e.to_string()))?)
+    }
+}
+
+/// A triple, along with a description of where it was found
+#[derive(Debug, Clone)]
+struct Entry<T> {
+    source: String,
+    triple: T,
+}
+
+/// The reference and endorsed values of a set of reference CoRIMs
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceStore {
+    references: Vec<Entry<ReferenceTripleRecord<'static>>>,
+    endorsements: Vec<Entry<EndorsedTripleRecord<'static>>>,
+}
+
+impl ReferenceStore {
+    /// Adds the reference and endorsed triples of every CoMID in `corim`, which is identified
+    /// by `name` in reports. The triples are copied, so `corim` may borrow from its input.
+    pub fn add(&mut self, name: &str, corim: &ConciseRimTypeChoice<'_>) -> Result<()> {
+        let map = match corim {
+            ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) => map.as_ref(),
+            ConciseRimTypeChoice::SignedCorim(signed) => signed.payload.as_ref(),
+            ConciseRimTypeChoice::MultiSignedCorim(signed) => signed.payload.as_ref(),
+            ConciseRimTypeChoice::DetachedSignedCorim(_) => {
+                Err(AppraisalError::DetachedPayload(name.to_string()))?
+            }
+        };
+
+        for tag in &map.tags {
+            if let ConciseTagTypeChoice::Mid(comid) = tag {
+                self.add_comid(name, comid);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_comid(&mut self, name: &str, comid: &ConciseMidTag<'_>) {
+        let tag_id = describe_tag_id(&comid.tag_identity.tag_id);
+        let triples = &comid.triples;
+
+        for (index, triple) in triples.reference_triples.iter().flatten().enumerate() {
+            self.references.push(Entry {
+                source: format!("{name}: {tag_id} reference-triples[{index}]"),
+                triple: triple.clone().into_owned(),
+            });
+        }
+        for (index, triple) in triples.endorsed_triples.iter().flatten().enumerate() {
+            self.endorsements.push(Entry {
+                source: format!("{name}: {tag_id} endorsed-triples[{index}]"),
+                triple: triple.clone().into_owned(),
+            });
+        }
+    }
+
+    /// Returns the reference triples whose environment `environment` matches, as defined by
+    /// [`environment_matches`]
+    pub fn reference_values<'s>(
+        &'s self,
+        environment: &'s EnvironmentMap,
+    ) -> impl Iterator<Item = &'s ReferenceTripleRecord<'static>> {
+        self.references
+            .iter()
+            .filter(|entry| environment_matches(&entry.triple.ref_env, environment))
+            .map(|entry| &entry.triple)
+    }
+
+    /// Appraises each environment of `evidence`. An environment passes if at least one
+    /// reference triple whose environment it matches has all of its measurements corroborated
+    /// by the evidence; endorsed triples whose condition it matches are then applied. The
+    /// appraisal passes if every environment passes.
+    pub fn appraise(&self, evidence: &[EvidenceClaims]) -> Report {
+        let environments = evidence
+            .iter()
+            .map(|claims| self.appraise_environment(claims))
+            .collect::<Vec<_>>();
+
+        Report {
+            pass: !environments.is_empty() && environments.iter().all(|env| env.pass),
+            environments,
+        }
+    }
+
+    fn appraise_environment(&self, claims: &EvidenceClaims) -> EnvironmentReport {
+        let references = self
+            .references
+            .iter()
+            .filter(|entry| environment_matches(&entry.triple.ref_env, &claims.environment))
+            .map(|entry| {
+                let measurements = entry
+                    .triple
+                    .ref_claims
+                    .iter()
+                    .map(|reference| appraise_measurement(reference, &claims.measurements))
+                    .collect::<Vec<_>>();
+
+                ReferenceReport {
+                    source: entry.source.clone(),
+                    pass: measurements.iter().all(|m| m.pass),
+                    measurements,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let pass = references.iter().any(|reference| reference.pass);
+
+        let endorsements = if pass {
+            self.endorsements
+                .iter()
+                .filter(|entry| environment_matches(&entry.triple.condition, &claims.environment))
+                .map(|entry| EndorsementReport {
+                    source: entry.source.clone(),
+                    measurements: entry
+                        .triple
+                        .endorsement
+                        .iter()
+                        .map(describe_measurement)
+                        .collect(),
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        EnvironmentReport {
+            environment: describe_environment(&claims.environment),
+            pass,
+            reason: references
+                .is_empty()
+                .then(|| "no reference values match the environment".to_string()),
+            references,
+            endorsements,
+        }
+    }
+}
+
+/// Returns whether `reference` is unset or equal to `evidence`
+fn matches_if_set<T: PartialEq>(reference: &Option<T>, evidence: &Option<T>) -> bool {
+    reference.is_none() || reference == evidence
+}
+
+/// Returns whether every field set in the `reference` environment, including each field of
+/// its class, has the same value in the `evidence` environment
+pub fn environment_matches(reference: &EnvironmentMap, evidence: &EnvironmentMap) -> bool {
+    let class = match (&reference.class, &evidence.class) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(reference), Some(evidence)) => {
+            matches_if_set(&reference.class_id, &evidence.class_id)
+                && matches_if_set(&reference.vendor, &evidence.vendor)
+                && matches_if_set(&reference.model, &evidence.model)
+                && matches_if_set(&reference.layer, &evidence.layer)
+                && matches_if_set(&reference.index, &evidence.index)
+        }
+    };
+
+    class
+        && matches_if_set(&reference.instance, &evidence.instance)
+        && matches_if_set(&reference.group, &evidence.group)
+}
+
+fn appraise_measurement(
+    reference: &MeasurementMap,
+    evidence: &[MeasurementMap],
+) -> MeasurementReport {
+    let measurement = match &reference.mkey {
+        Some(mkey) => describe(mkey),
+        None => "<no mkey>".to_string(),
+    };
+
+    let Some(found) = evidence.iter().find(|m| m.mkey == reference.mkey) else {
+        return MeasurementReport {
+            measurement,
+            pass: false,
+            checks: vec![CheckReport::new(
+                "mkey",
+                Err("no evidence measurement has this key".to_string()),
+            )],
+        };
+    };
+
+    let checks = compare_values(&reference.mval, &found.mval);
+
+    MeasurementReport {
+        measurement,
+        pass: checks.iter().all(|check| check.pass),
+        checks,
+    }
+}
+
+/// Compares each value set in `reference` with the corresponding `evidence` value
+fn compare_values(
+    reference: &MeasurementValuesMap,
+    evidence: &MeasurementValuesMap,
+) -> Vec<CheckReport> {
+    let mut checks = vec![];
+
+    macro_rules! check {
+        ($field:ident, $name:literal, $compare:expr) => {
+            if let Some(value) = &reference.$field {
+                checks.push(CheckReport::new(
+                    $name,
+                    $compare(value, evidence.$field.as_ref()),
+                ));
+            }
+        };
+    }
+
+    check!(version, "version", equal);
+    check!(svn, "svn", svn);
+    check!(
+        digests,
+        "digests",
+        |r: &Vec<Digest>, e: Option<&Vec<Digest>>| { digests(r, e.map(Vec::as_slice)) }
+    );
+    check!(flags, "flags", flags);
+    check!(raw, "raw-value", raw);
+    check!(mac_addr, "mac-addr", equal);
+    check!(ip_addr, "ip-addr", equal);
+    check!(serial_number, "serial-number", equal);
+    check!(ueid, "ueid", equal);
+    check!(uuid, "uuid", equal);
+    check!(name, "name", equal);
+    check!(cryptokeys, "cryptokeys", equal);
+    check!(integrity_registers, "integrity-registers", registers);
+    check!(extensions, "extensions", equal);
+
+    checks
+}
+
+fn equal<T: PartialEq + Serialize>(reference: &T, evidence: Option<&T>) -> Check {
+    match evidence {
+        None => Err(MISSING.to_string()),
+        Some(evidence) if evidence == reference => Ok(describe(reference)),
+        Some(evidence) => Err(format!(
+            "expected {}, found {}",
+            describe(reference),
+            describe(evidence)
+        )),
+    }
+}
+
+/// An exact reference svn must equal the evidence svn, a minimum must not exceed it
+fn svn(reference: &SvnTypeChoice, evidence: Option<&SvnTypeChoice>) -> Check {
+    let found = match evidence.ok_or(MISSING)? {
+        SvnTypeChoice::Svn(svn) => *svn,
+        SvnTypeChoice::TaggedSvn(svn) => *svn.as_ref(),
+        SvnTypeChoice::TaggedMinSvn(_) => Err("evidence must carry an exact svn, not a minimum")?,
+    };
+
+    let expected = match reference {
+        SvnTypeChoice::TaggedMinSvn(min) => {
+            let min = *min.as_ref();
+            return if found >= min {
+                Ok(format!("{found} >= minimum {min}"))
+            } else {
+                Err(format!("{found} is below the minimum {min}"))
+            };
+        }
+        SvnTypeChoice::Svn(svn) => *svn,
+        SvnTypeChoice::TaggedSvn(svn) => *svn.as_ref(),
+    };
+
+    if found == expected {
+        Ok(found.to_string())
+    } else {
+        Err(format!("expected {expected}, found {found}"))
+    }
+}
+
+/// The evidence must carry a digest using one of the reference algorithms, and every evidence
+/// digest using a reference algorithm must equal a reference digest
+fn digests(reference: &[Digest], evidence: Option<&[Digest]>) -> Check {
+    let common = evidence
+        .ok_or(MISSING)?
+        .iter()
+        .filter(|found| reference.iter().any(|r| r.alg == found.alg))
+        .collect::<Vec<_>>();
+
+    if common.is_empty() {
+        let algorithms = reference
+            .iter()
+            .map(|r| r.alg.to_string())
+            .collect::<Vec<_>>();
+        Err(format!(
+            "no evidence digest uses a reference algorithm ({})",
+            algorithms.join(", ")
+        ))?
+    }
+
+    if let Some(found) = common.iter().find(|found| !reference.contains(found)) {
+        Err(format!("{} digest {found} does not match", found.alg))?
+    }
+
+    let algorithms = common.iter().map(|d| d.alg.to_string()).collect::<Vec<_>>();
+    Ok(format!("{} matched", algorithms.join(", ")))
+}
+
+/// Every flag set in the reference must have the same value in the evidence
+fn flags(reference: &FlagsMap, evidence: Option<&FlagsMap>) -> Check {
+    let evidence = evidence.ok_or(MISSING)?;
+    let pairs = [
+        (
+            "is-configured",
+            reference.is_configured,
+            evidence.is_configured,
+        ),
+        ("is-secure", reference.is_secure, evidence.is_secure),
+        ("is-recovery", reference.is_recovery, evidence.is_recovery),
+        ("is-debug", reference.is_debug, evidence.is_debug),
+        (
+            "is-replay-protected",
+            reference.is_replay_protected,
+            evidence.is_replay_protected,
+        ),
+        (
+            "is-integrity-protected",
+            reference.is_integrity_protected,
+            evidence.is_integrity_protected,
+        ),
+        (
+            "is-runtime-meas",
+            reference.is_runtime_meas,
+            evidence.is_runtime_meas,
+        ),
+        (
+            "is-immutable",
+            reference.is_immutable,
+            evidence.is_immutable,
+        ),
+        ("is-tcb", reference.is_tcb, evidence.is_tcb),
+        (
+            "is-confidentiality-protected",
+            reference.is_confidentiality_protected,
+            evidence.is_confidentiality_protected,
+        ),
+    ];
+
+    let mut matched = vec![];
+    for (name, expected, found) in pairs {
+        let Some(expected) = expected else { continue };
+        match found {
+            Some(found) if found == expected => matched.push(format!("{name}={expected}")),
+            Some(found) => Err(format!("{name}: expected {expected}, found {found}"))?,
+            None => Err(format!("{name}: {MISSING}"))?,
+        }
+    }
+
+    if let Some(extensions) = &reference.extensions {
+        equal(extensions, evidence.extensions.as_ref()).map_err(|e| format!("extensions: {e}"))?;
+        matched.push("extensions".to_string());
+    }
+
+    Ok(matched.join(", "))
+}
+
+/// Compares raw values, restricted to the bits set in the reference mask if there is one
+fn raw(reference: &RawValueType, evidence: Option<&RawValueType>) -> Check {
+    let bytes = |raw: &RawValueType| -> Vec<u8> {
+        raw.raw_value
+            .as_bytes()
+            .or_else(|| raw.raw_value.as_raw_mask_value().map(|(value, _)| value))
+            .unwrap_or_default()
+            .to_vec()
+    };
+
+    let expected = bytes(reference);
+    let found = bytes(evidence.ok_or(MISSING)?);
+    let mask = match reference.raw_value.as_raw_mask_value() {
+        Some((_, mask)) => Some(mask),
+        None => reference.raw_value_mask.as_ref().map(|mask| mask.as_ref()),
+    };
+
+    let matches = match mask {
+        Some(mask) => {
+            expected.len() == found.len()
+                && expected
+                    .iter()
+                    .zip(&found)
+                    .zip(mask.iter().chain(core::iter::repeat(&0)))
+                    .all(|((expected, found), mask)| expected & mask == found & mask)
+        }
+        None => expected == found,
+    };
+
+    let masked = if mask.is_some() { " under mask" } else { "" };
+    if matches {
+        Ok(format!("{}{masked}", hex(&expected)))
+    } else {
+        Err(format!(
+            "expected {}{masked}, found {}",
+            hex(&expected),
+            hex(&found)
+        ))
+    }
+}
+
+/// Every reference register must be present in the evidence with matching digests
+fn registers(reference: &IntegrityRegisters, evidence: Option<&IntegrityRegisters>) -> Check {
+    let evidence = evidence.ok_or(MISSING)?;
+
+    for (label, expected) in &reference.0 {
+        let found = evidence
+            .0
+            .get(label)
+            .ok_or_else(|| format!("register {}: {MISSING}", describe(label)))?;
+        digests(expected, Some(found)).map_err(|e| format!("register {}: {e}", describe(label)))?;
+    }
+
+    Ok(format!("{} registers matched", reference.0.len()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Outcome of an appraisal
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    /// Whether every environment of the evidence passed
+    pub pass: bool,
+    /// The outcome for each environment of the evidence, in order
+    pub environments: Vec<EnvironmentReport>,
+}
+
+/// Outcome of the appraisal of one environment of the evidence
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentReport {
+    /// Description of the environment
+    pub environment: String,
+    /// Whether the measurements of any matching reference triple were all corroborated
+    pub pass: bool,
+    /// Why the environment failed, if no reference triple matched it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The reference triples whose environment matched
+    pub references: Vec<ReferenceReport>,
+    /// The endorsed triples applied to a passing environment
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub endorsements: Vec<EndorsementReport>,
+}
+
+/// Outcome of comparing the evidence with one reference triple
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceReport {
+    /// Where the reference triple was found
+    pub source: String,
+    /// Whether every measurement of the triple was corroborated
+    pub pass: bool,
+    /// The outcome for each measurement of the triple
+    pub measurements: Vec<MeasurementReport>,
+}
+
+/// Outcome of comparing the evidence with one reference measurement
+#[derive(Debug, Clone, Serialize)]
+pub struct MeasurementReport {
+    /// Description of the measured element
+    pub measurement: String,
+    /// Whether every check passed
+    pub pass: bool,
+    /// The outcome for each value set in the reference measurement
+    pub checks: Vec<CheckReport>,
+}
+
+/// Outcome of comparing one measurement value
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    /// Name of the compared value, e.g. `digests`
+    pub field: &'static str,
+    /// Whether the evidence value matched
+    pub pass: bool,
+    /// What matched, or why the values differ
+    pub detail: String,
+}
+
+/// An endorsed triple applied to a passing environment
+#[derive(Debug, Clone, Serialize)]
+pub struct EndorsementReport {
+    /// Where the endorsed triple was found
+    pub source: String,
+    /// Descriptions of the endorsed measurements
+    pub measurements: Vec<String>,
+}
+
+impl CheckReport {
+    fn new(field: &'static str, result: Check) -> Self {
+        let (pass, detail) = match result {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        Self {
+            field,
+            pass,
+            detail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bytes, RawValueTypeChoice};
+
+    const UNSIGNED: &[u8] = include_bytes!("../testdata/corim/unsigned.cbor");
+
+    /// SHA-256 of "firmware image v1", the firmware digest of the reference CoRIM
+    const FIRMWARE_DIGEST: &str = "sha-256;XP_FZ_Dl8qdF9zaFHbFVlEHm8Tsj7aHpBm7izNZOxag";
+
+    fn corim() -> ConciseRimTypeChoice<'static> {
+        crate::cbor::from_slice(UNSIGNED).unwrap()
+    }
+
+    fn store() -> ReferenceStore {
+        let mut store = ReferenceStore::default();
+        store.add("unsigned.cbor", &corim()).unwrap();
+        store
+    }
+
+    fn evidence(svn: u32, digest: &str) -> Vec<EvidenceClaims> {
+        EvidenceClaims::from_json(&format!(
+            r#"{{
+                "environment": {{"class": {{
+                    "class-id": {{"type": "oid", "value": "2.16.840.1.113741.1.2.3"}},
+                    "vendor": "ACME Inc.",
+                    "model": "RoadRunner"
+                }}}},
+                "measurements": [{{"mkey": "firmware", "mval": {{"svn": {svn}, "digests": ["{digest}"]}}}}]
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_appraise_fail() {
+        let report = store().appraise(&evidence(
+            2,
+            "sha-256;AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        ));
+        assert!(!report.pass);
+
+        let checks = &report.environments[0].references[0].measurements[0].checks;
+        assert_eq!(checks[0].detail, "expected 3, found 2");
+        assert!(!checks[0].pass);
+        assert!(checks[1].detail.starts_with("sha-256 digest sha-256;AAAA"));
+        assert!(checks[1].detail.ends_with(" does not match"));
+
+        let mut other = evidence(3, FIRMWARE_DIGEST);
+        other[0].environment.class.as_mut().unwrap().model = Some("Coyote".into());
+        let report = store().appraise(&other);
+        assert!(!report.pass);
+        assert_eq!(
+            report.environments[0].reason.as_deref(),
+            Some("no reference values match the environment")
+        );
+
+        let mut other = evidence(3, FIRMWARE_DIGEST);
+        other[0].measurements[0].mkey = Some("bootloader".into());
+        let report = store().appraise(&other);
+        assert_eq!(
+            report.environments[0].references[0].measurements[0].checks[0].detail,
+            "no evidence measurement has this key"
+        );
+    }
+
+    #[test]
+    fn test_min_svn_and_endorsements() {
+        let mut store = store();
+        let ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) = corim() else {
+            unreachable!()
+        };
+        let mut map = map.as_ref().clone();
+        let ConciseTagTypeChoice::Mid(comid) = &mut map.tags[0] else {
+            panic!("expected a CoMID");
+        };
+        let triples = &mut comid.triples;
+
+        triples.reference_triples.as_mut().unwrap()[0].ref_claims[0].mval =
+            serde_json::from_str(r#"{"svn": {"type": "min-svn", "value": 2}}"#).unwrap();
+        triples.endorsed_triples = Some(vec![serde_json::from_str(
+            r#"[{"class": {"vendor": "ACME Inc."}}, [{"mkey": "firmware", "mval": {"name": "certified"}}]]"#,
+        )
+        .unwrap()]);
+
+        store.references.clear();
+        store
+            .add(
+                "min.cbor",
+                &ConciseRimTypeChoice::TaggedUnsignedCorimMap(map.into()),
+            )
+            .unwrap();
+
+        let report = store.appraise(&evidence(3, FIRMWARE_DIGEST));
+        assert!(report.pass);
+        assert_eq!(
+            report.environments[0].references[0].measurements[0].checks[0].detail,
+            "3 >= minimum 2"
+        );
+        assert_eq!(report.environments[0].endorsements.len(), 1);
+        assert!(report.environments[0].endorsements[0].measurements[0].contains("certified"));
+
+        let report = store.appraise(&evidence(1, FIRMWARE_DIGEST));
+        assert!(!report.pass);
+        assert!(report.environments[0].endorsements.is_empty());
+    }
+
+    #[test]
+    fn test_reference_values() {
+        let store = store();
+        let claims = evidence(3, FIRMWARE_DIGEST);
+        assert_eq!(store.reference_values(&claims[0].environment).count(), 1);
+
+        let vendor_only: EnvironmentMap =
+            serde_json::from_str(r#"{"class": {"vendor": "ACME Inc."}}"#).unwrap();
+        assert_eq!(store.reference_values(&vendor_only).count(), 0);
+        assert!(environment_matches(&vendor_only, &claims[0].environment));
+    }
+
+    #[test]
+    fn test_raw_value_mask() {
+        let raw = |value: &[u8], mask: Option<&[u8]>| RawValueType {
+            raw_value: RawValueTypeChoice::TaggedBytes(Bytes::from(value.to_vec()).into()),
+            raw_value_mask: mask.map(|mask| Bytes::from(mask.to_vec())),
+        };
+        let reference = raw(&[0xf0, 0x0f], Some(&[0xf0, 0x00]));
+
+        assert_eq!(
+            super::raw(&reference, Some(&raw(&[0xf0, 0xff], None))),
+            Ok("f00f under mask".to_string())
+        );
+        assert_eq!(
+            super::raw(&reference, Some(&raw(&[0x0f, 0x0f], None))),
+            Err("expected f00f under mask, found 0f0f".to_string())
+        );
+        assert!(super::raw(&raw(&[1], None), Some(&raw(&[1, 2], None))).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT
+
+//! The `appraise` command: matches evidence claims against the reference and endorsed values
+//! carried by reference CoRIMs
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use corim_rs::appraisal::{EvidenceClaims, ReferenceStore, Report};
+
+use crate::{
+    input::{decode, decode_corim, read_file, Encoding},
+    inspect::Node,
+    write_stdout, CliResult,
+};
+
+#[derive(Debug, Args)]
+pub struct AppraiseArgs {
+    /// Reference CoRIMs, signed or unsigned. Signatures are not checked; use `corim verify`.
+    #[arg(long, num_args = 1.., required = true)]
+    pub refs: Vec<PathBuf>,
+
+    /// Evidence claims as JSON: an object with `environment` and `measurements` entries, in the
+    /// JSON form of the corresponding CoMID types, or an array of such objects
+    #[arg(long)]
+    pub evidence: PathBuf,
+
+    /// Encoding of the reference CoRIMs
+    #[arg(long, value_enum, default_value_t = Encoding::Auto)]
+    pub encoding: Encoding,
+
+    /// Print the report as JSON instead of a tree
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: AppraiseArgs) -> CliResult<()> {
+    let mut store = ReferenceStore::default();
+    for path in &args.refs {
+        let context = |e: Box<dyn std::error::Error>| format!("{}: {e}", path.display());
+        let cbor = decode(&read_file(path)?, args.encoding).map_err(context)?;
+        let corim = decode_corim(&cbor).map_err(context)?;
+        store.add(&path.display().to_string(), &corim)?;
+    }
+
+    let report = store.appraise(&read_evidence(&args.evidence)?);
+
+    if args.json {
+        write_stdout(&format!("{}\n", serde_json::to_string_pretty(&report)?))?;
+    } else {
+        write_stdout(&report_tree(&report))?;
+    }
+
+    if report.pass {
+        Ok(())
+    } else {
+        Err("appraisal failed".into())
+    }
+}
+
+fn read_evidence(path: &Path) -> CliResult<Vec<EvidenceClaims>> {
+    let text = String::from_utf8(read_file(path)?)
+        .map_err(|_| format!("{}: evidence is not valid UTF-8", path.display()))?;
+
+    EvidenceClaims::from_json(&text).map_err(|e| format!("{}: {e}", path.display()).into())
+}
+
+fn outcome(pass: bool) -> &'static str {
+    if pass {
+        "pass"
+    } else {
+        "fail"
+    }
+}
+
+/// Renders an appraisal report as an indented tree
+pub fn report_tree(report: &Report) -> String {
+    let mut root = Node::new(format!("appraisal: {}", outcome(report.pass)));
+
+    if report.environments.is_empty() {
+        root.leaf("no evidence claims");
+    }
+
+    for (index, env) in report.environments.iter().enumerate() {
+        let node = root.child(format!(
+            "evidence[{index}]: {}: {}",
+            env.environment,
+            outcome(env.pass)
+        ));
+        if let Some(reason) = &env.reason {
+            node.leaf(reason);
+        }
+
+        for reference in &env.references {
+            let reference_node = node.child(format!(
+                "reference {}: {}",
+                reference.source,
+                outcome(reference.pass)
+            ));
+            for measurement in &reference.measurements {
+                let measurement_node = reference_node.child(format!(
+                    "{}: {}",
+                    measurement.measurement,
+                    outcome(measurement.pass)
+                ));
+                for check in &measurement.checks {
+                    measurement_node.leaf(format!(
+                        "{}: {} ({})",
+                        check.field,
+                        outcome(check.pass),
+                        check.detail
+                    ));
+                }
+            }
+        }
+
+        for endorsement in &env.endorsements {
+            let endorsement_node = node.child(format!("endorsed {}", endorsement.source));
+            for measurement in &endorsement.measurements {
+                endorsement_node.leaf(measurement);
+            }
+        }
+    }
+
+    root.render()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNSIGNED: &[u8] = include_bytes!("../../../testdata/corim/unsigned.cbor");
+
+    /// SHA-256 of "firmware image v1", the firmware digest of the reference CoRIM
+    const FIRMWARE_DIGEST: &str = "sha-256;XP_FZ_Dl8qdF9zaFHbFVlEHm8Tsj7aHpBm7izNZOxag";
+
+    #[test]
+    fn test_appraise_pass() {
+        let mut store = ReferenceStore::default();
+        store
+            .add("unsigned.cbor", &decode_corim(UNSIGNED).unwrap())
+            .unwrap();
+
+        let evidence = EvidenceClaims::from_json(&format!(
+            r#"{{
+                "environment": {{"class": {{
+                    "class-id": {{"type": "oid", "value": "2.16.840.1.113741.1.2.3"}},
+                    "vendor": "ACME Inc.",
+                    "model": "RoadRunner"
+                }}}},
+                "measurements": [{{"mkey": "firmware", "mval": {{"svn": 3, "digests": ["{FIRMWARE_DIGEST}"]}}}}]
+            }}"#
+        ))
+        .unwrap();
+        let report = store.appraise(&evidence);
+        assert!(report.pass);
+
+        let tree = report_tree(&report);
+        assert!(tree.starts_with("appraisal: pass\n"));
+        assert!(tree
+            .contains("reference unsigned.cbor: acme-roadrunner-comid reference-triples[0]: pass"));
+        assert!(tree.contains("firmware: pass\n"));
+        assert!(tree.contains("svn: pass (3)"));
+        assert!(tree.contains("digests: pass (sha-256 matched)"));
+    }
+}
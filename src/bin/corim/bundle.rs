@@ -0,0 +1,412 @@
+// SPDX-License-Identifier: MIT
+
+//! The `extract` and `bundle` commands: split a CoRIM into standalone tag files, and assemble
+//! standalone tag files into a new CoRIM
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+use corim_rs::{
+    core::{ObjectIdentifier, OidType, Text, TextOrBytes},
+    corim::{
+        ConciseRimTypeChoice, ConciseTagTypeChoice, CorimEntityMap, CorimIdTypeChoice, CorimMap,
+        CorimRoleTypeChoice, ProfileTypeChoice, ValidityMap,
+    },
+    describe::describe_tag_id,
+    diff::TagKey,
+};
+
+use crate::{
+    create::{write_cbor, TagKind},
+    input::{corim_map, decode, read_file, Encoding, InputArgs},
+    sign::parse_validity,
+    write_output, write_stdout, CliResult,
+};
+
+#[derive(Debug, Args)]
+pub struct ExtractArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Directory the tags are written to, as `<kind>-<tag-id>.cbor`
+    #[arg(short = 'd', long, default_value = ".")]
+    pub output_dir: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct BundleArgs {
+    /// CBOR encoded CoMID, CoSWID and CoTL tags, in the order they are added to the CoRIM
+    #[arg(required = true)]
+    pub tags: Vec<PathBuf>,
+
+    /// Identifier of the new CoRIM
+    #[arg(long)]
+    pub id: String,
+
+    /// Profile of the new CoRIM: an OID in dotted-decimal form, or a URI
+    #[arg(long, value_parser = parse_profile)]
+    pub profile: Option<ProfileTypeChoice<'static>>,
+
+    /// Entity responsible for the CoRIM, as `NAME:ROLE[,ROLE...]` with roles
+    /// `manifest-creator` and `manifest-signer` (may be repeated)
+    #[arg(long, value_parser = parse_entity)]
+    pub entity: Vec<CorimEntityMap<'static>>,
+
+    /// Validity period of the CoRIM, as `[NOT_BEFORE]..NOT_AFTER` in seconds since the Unix
+    /// epoch
+    #[arg(long, value_parser = parse_validity)]
+    pub validity: Option<ValidityMap>,
+
+    /// Output file, or `-` for standard output
+    #[arg(short, long, default_value = "-")]
+    pub output: PathBuf,
+}
+
+pub fn run_extract(args: ExtractArgs) -> CliResult<()> {
+    let corim = args.input.read_corim()?;
+    let map = corim_map(&corim)?;
+
+    std::fs::create_dir_all(&args.output_dir)
+        .map_err(|e| format!("failed to create {}: {e}", args.output_dir.display()))?;
+
+    let mut output = String::new();
+    for (path, tag) in tag_paths(map, &args.output_dir) {
+        write_cbor(tag, &path)?;
+        output.push_str(&format!("{}\n", path.display()));
+    }
+    write_stdout(&output)
+}
+
+pub fn run_bundle(args: BundleArgs) -> CliResult<()> {
+    let tags = args
+        .tags
+        .iter()
+        .map(|path| read_tag(path))
+        .collect::<CliResult<Vec<_>>>()?;
+
+    let corim = bundle(
+        CorimIdTypeChoice::Tstr(args.id.into()),
+        tags,
+        args.profile,
+        args.entity,
+        args.validity,
+    )?;
+
+    let mut bytes = vec![];
+    ciborium::into_writer(
+        &ConciseRimTypeChoice::TaggedUnsignedCorimMap(corim.into()),
+        &mut bytes,
+    )?;
+    write_output(&args.output, &bytes)
+}
+
+/// Assembles `tags` into a new CoRIM, rejecting tags that share a tag identifier
+pub fn bundle<'a>(
+    id: CorimIdTypeChoice<'a>,
+    tags: Vec<ConciseTagTypeChoice<'a>>,
+    profile: Option<ProfileTypeChoice<'a>>,
+    entities: Vec<CorimEntityMap<'a>>,
+    rim_validity: Option<ValidityMap>,
+) -> CliResult<CorimMap<'a>> {
+    let mut seen = vec![];
+    for tag in &tags {
+        let key = TagKey::of(tag);
+        if seen.contains(&key) {
+            Err(format!(
+                "more than one {} has the tag id {}",
+                TagKind::of(tag).name(),
+                tag_id(tag)
+            ))?
+        }
+        seen.push(key);
+    }
+
+    let corim = CorimMap {
+        id,
+        tags,
+        dependent_rims: None,
+        profile,
+        rim_validity,
+        entities: (!entities.is_empty()).then_some(entities),
+        extension: None,
+    };
+
+    corim.validate()?;
+    Ok(corim)
+}
+
+/// Reads a tag from a CBOR file, detecting its kind from its CBOR tag
+fn read_tag(path: &Path) -> CliResult<ConciseTagTypeChoice<'static>> {
+    let cbor = decode(&read_file(path)?, Encoding::Auto)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    corim_rs::cbor::from_reader(cbor.as_slice())
+        .map_err(|e| format!("{}: failed to decode tag: {e}", path.display()).into())
+}
+
+/// Returns the path each tag of `corim` is extracted to. Tags sharing a kind and tag id are
+/// told apart by their index, or by the next number not yet used if that name is taken.
+fn tag_paths<'a>(
+    corim: &'a CorimMap<'a>,
+    dir: &Path,
+) -> Vec<(PathBuf, &'a ConciseTagTypeChoice<'a>)> {
+    let mut used = HashSet::new();
+
+    corim
+        .tags
+        .iter()
+        .enumerate()
+        .map(|(index, tag)| {
+            let stem = tag_file_stem(tag);
+            let mut name = stem.clone();
+            let mut suffix = index;
+            while !used.insert(name.clone()) {
+                name = format!("{stem}-{suffix}");
+                suffix += 1;
+            }
+            (dir.join(format!("{name}.cbor")), tag)
+        })
+        .collect()
+}
+
+/// `<kind>-<tag-id>`, with characters that are unsafe in file names replaced
+fn tag_file_stem(tag: &ConciseTagTypeChoice) -> String {
+    let kind = match TagKey::of(tag) {
+        TagKey::Comid(_) => "comid",
+        TagKey::Coswid(_) => "coswid",
+        TagKey::Cotl(_) => "cotl",
+    };
+    let id = tag_id(tag)
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+
+    format!("{kind}-{id}")
+}
+
+fn tag_id(tag: &ConciseTagTypeChoice) -> String {
+    match TagKey::of(tag) {
+        TagKey::Comid(id) | TagKey::Cotl(id) => describe_tag_id(id),
+        TagKey::Coswid(TextOrBytes::Text(id)) => id.to_string(),
+        TagKey::Coswid(TextOrBytes::Bytes(id)) => hex::encode(id.as_ref()),
+    }
+}
+
+fn parse_profile(text: &str) -> Result<ProfileTypeChoice<'static>, String> {
+    let is_oid = text.split('.').count() > 1
+        && text
+            .split('.')
+            .all(|arc| !arc.is_empty() && arc.bytes().all(|b| b.is_ascii_digit()));
+
+    if is_oid {
+        let oid = ObjectIdentifier::try_from(text).map_err(|_| format!("invalid OID {text}"))?;
+        Ok(ProfileTypeChoice::OidType(OidType::from(oid)))
+    } else {
+        Ok(ProfileTypeChoice::Uri(Text::from(text.to_string()).into()))
+    }
+}
+
+fn parse_entity(text: &str) -> Result<CorimEntityMap<'static>, String> {
+    let (name, roles) = text
+        .rsplit_once(':')
+        .ok_or("expected NAME:ROLE[,ROLE...]")?;
+
+    if name.is_empty() {
+        return Err("the entity name is empty".to_string());
+    }
+
+    let role = roles
+        .split(',')
+        .map(|role| CorimRoleTypeChoice::try_from(role.trim()).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(CorimEntityMap {
+        entity_name: Text::from(name.to_string()),
+        reg_id: None,
+        role,
+        extension: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::decode_corim;
+    use corim_rs::comid::TagIdTypeChoice;
+
+    const UNSIGNED: &[u8] = include_bytes!("../../../testdata/corim/unsigned.cbor");
+
+    #[test]
+    fn test_extract_and_bundle() {
+        let corim = decode_corim(UNSIGNED).unwrap();
+        let map = corim_map(&corim).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        std::fs::write(dir.path().join("in.cbor"), UNSIGNED).unwrap();
+        run_extract(ExtractArgs {
+            input: InputArgs {
+                input: dir.path().join("in.cbor"),
+                encoding: Encoding::Auto,
+            },
+            output_dir: dir.path().to_path_buf(),
+        })
+        .unwrap();
+
+        let paths = tag_paths(map, dir.path())
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        assert_eq!(paths.len(), map.tags.len());
+        assert!(paths.iter().all(|path| path.exists()));
+
+        let output = dir.path().join("bundle.cbor");
+        run_bundle(BundleArgs {
+            tags: paths,
+            id: "platform-1".to_string(),
+            profile: Some(parse_profile("https://example.com/profile").unwrap()),
+            entity: vec![parse_entity("ACME Inc.:manifest-creator,manifest-signer").unwrap()],
+            validity: Some(ValidityMap::new(None, 2_000_000_000.into())),
+            output: output.clone(),
+        })
+        .unwrap();
+
+        let bundled = decode_corim(&std::fs::read(&output).unwrap()).unwrap();
+        let bundled = corim_map(&bundled).unwrap();
+        assert_eq!(bundled.id.as_str(), Some("platform-1"));
+        assert_eq!(bundled.tags, map.tags);
+        assert_eq!(
+            bundled.entities.as_ref().unwrap()[0].role,
+            [
+                CorimRoleTypeChoice::ManifestCreator,
+                CorimRoleTypeChoice::ManifestSigner
+            ]
+        );
+        assert!(bundled
+            .rim_validity
+            .as_ref()
+            .unwrap()
+            .contains(1_999_999_999));
+    }
+
+    #[test]
+    fn test_extract_creates_output_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_dir = dir.path().join("tags").join("platform");
+
+        std::fs::write(dir.path().join("in.cbor"), UNSIGNED).unwrap();
+        run_extract(ExtractArgs {
+            input: InputArgs {
+                input: dir.path().join("in.cbor"),
+                encoding: Encoding::Auto,
+            },
+            output_dir: output_dir.clone(),
+        })
+        .unwrap();
+
+        assert!(std::fs::read_dir(&output_dir).unwrap().next().is_some());
+    }
+
+    #[test]
+    fn test_bundle_duplicate_tag_ids() {
+        let corim = decode_corim(UNSIGNED).unwrap();
+        let tag = corim_map(&corim).unwrap().tags[0].clone();
+
+        let err = bundle("corim-1".into(), vec![tag.clone(), tag], None, vec![], None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("more than one "), "{err}");
+    }
+
+    #[test]
+    fn test_bundle_tag_ids_with_the_same_file_stem() {
+        let corim = decode_corim(UNSIGNED).unwrap();
+        let tag = corim_map(&corim).unwrap().tags[0].clone();
+        let with_id = |id: &'static str| {
+            let mut tag = tag.clone();
+            let ConciseTagTypeChoice::Mid(comid) = &mut tag else {
+                panic!("expected a CoMID");
+            };
+            comid.tag_identity.tag_id = TagIdTypeChoice::Tstr(id.into());
+            tag
+        };
+        let tags = vec![with_id("a/b"), with_id("a_b")];
+        assert_eq!(tag_file_stem(&tags[0]), tag_file_stem(&tags[1]));
+
+        let corim = bundle("corim-1".into(), tags, None, vec![], None).unwrap();
+        assert_eq!(corim.tags.len(), 2);
+    }
+
+    #[test]
+    fn test_tag_paths() {
+        let corim = decode_corim(UNSIGNED).unwrap();
+        let mut map = corim_map(&corim).unwrap().clone();
+        map.tags.push(map.tags[0].clone());
+
+        let paths = tag_paths(&map, Path::new("out"));
+        let (first, last) = (&paths[0].0, &paths[paths.len() - 1].0);
+        assert_ne!(first, last);
+        assert_eq!(
+            last.to_str().unwrap(),
+            format!(
+                "{}-{}.cbor",
+                first.to_str().unwrap().trim_end_matches(".cbor"),
+                paths.len() - 1
+            )
+        );
+    }
+
+    #[test]
+    fn test_tag_paths_suffix_collision() {
+        let corim = decode_corim(UNSIGNED).unwrap();
+        let mut map = corim_map(&corim).unwrap().clone();
+        let tag = map.tags[0].clone();
+        let with_id = |id: &'static str| {
+            let mut tag = tag.clone();
+            let ConciseTagTypeChoice::Mid(comid) = &mut tag else {
+                panic!("expected a CoMID");
+            };
+            comid.tag_identity.tag_id = TagIdTypeChoice::Tstr(id.into());
+            tag
+        };
+        // The second "x" would be suffixed with its index, which the first tag already uses
+        map.tags = vec![with_id("x-2"), with_id("x"), with_id("x")];
+
+        let paths = tag_paths(&map, Path::new("out"))
+            .into_iter()
+            .map(|(path, _)| path.to_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "out/comid-x-2.cbor",
+                "out/comid-x.cbor",
+                "out/comid-x-3.cbor"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        assert!(matches!(
+            parse_profile("1.3.6.1.4.1.42").unwrap(),
+            ProfileTypeChoice::OidType(_)
+        ));
+        assert!(matches!(
+            parse_profile("tag:example.com,2025:profile").unwrap(),
+            ProfileTypeChoice::Uri(_)
+        ));
+
+        let entity = parse_entity("ACME: Labs:manifest-signer").unwrap();
+        assert_eq!(entity.entity_name, Text::from("ACME: Labs"));
+        assert_eq!(entity.role, [CorimRoleTypeChoice::ManifestSigner]);
+
+        assert!(parse_entity("ACME Inc.").is_err());
+        assert!(parse_entity(":manifest-creator").is_err());
+        assert!(parse_entity("ACME Inc.:tag-creator").is_err());
+    }
+}
@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: MIT
+
+//! The `create` commands: compile CoRIMs and tags from JSON templates into CBOR
+
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+use corim_rs::{
+    comid::ConciseMidTag,
+    corim::{ConciseRimTypeChoice, ConciseTagTypeChoice, CorimMap},
+    coswid::ConciseSwidTag,
+    cotl::ConciseTlTag,
+    owned::IntoOwned,
+};
+use serde::Deserialize;
+
+use crate::{input::read_file, write_output, CliResult};
+
+#[derive(Debug, Args)]
+pub struct CreateCorimArgs {
+    /// JSON template for the CoRIM map
+    #[arg(short, long)]
+    pub template: PathBuf,
+
+    /// CoMID to add to the tags of the CoRIM, as a JSON template or CBOR (may be repeated)
+    #[arg(long)]
+    pub comid: Vec<PathBuf>,
+
+    /// CoSWID to add to the tags of the CoRIM, as a JSON template or CBOR (may be repeated)
+    #[arg(long)]
+    pub coswid: Vec<PathBuf>,
+
+    /// CoTL to add to the tags of the CoRIM, as a JSON template or CBOR (may be repeated)
+    #[arg(long)]
+    pub cotl: Vec<PathBuf>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TagCommand {
+    /// Compile a tag from a JSON template into CBOR
+    Create(CreateTagArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CreateTagArgs {
+    /// JSON template for the tag
+    #[arg(short, long)]
+    pub template: PathBuf,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct OutputArgs {
+    /// Output file, or `-` for standard output. Defaults to the template path with a
+    /// `.cbor` extension.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+impl OutputArgs {
+    fn path(&self, template: &Path) -> PathBuf {
+        self.output
+            .clone()
+            .unwrap_or_else(|| template.with_extension("cbor"))
+    }
+}
+
+/// The kinds of tag a CoRIM can carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagKind {
+    Comid,
+    Coswid,
+    Cotl,
+}
+
+impl TagKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Comid => "CoMID",
+            Self::Coswid => "CoSWID",
+            Self::Cotl => "CoTL",
+        }
+    }
+
+    pub fn of(tag: &ConciseTagTypeChoice) -> Self {
+        match tag {
+            ConciseTagTypeChoice::Mid(_) => Self::Comid,
+            ConciseTagTypeChoice::Swid(_) => Self::Coswid,
+            ConciseTagTypeChoice::Tl(_) => Self::Cotl,
+        }
+    }
+}
+
+pub fn run_corim(args: CreateCorimArgs) -> CliResult<()> {
+    let text = read_template(&args.template)?;
+    let mut corim: CorimMap = parse_template(&args.template, &text)?;
+
+    for (kind, paths) in [
+        (TagKind::Comid, &args.comid),
+        (TagKind::Coswid, &args.coswid),
+        (TagKind::Cotl, &args.cotl),
+    ] {
+        for path in paths {
+            corim.tags.push(read_tag(path, kind)?);
+        }
+    }
+
+    corim.validate().map_err(|e| invalid(&args.template, e))?;
+
+    let corim = ConciseRimTypeChoice::TaggedUnsignedCorimMap(corim.into());
+    write_cbor(&corim, &args.output.path(&args.template))
+}
+
+pub fn run_tag(kind: TagKind, command: TagCommand) -> CliResult<()> {
+    let TagCommand::Create(args) = command;
+
+    let text = read_template(&args.template)?;
+    let tag = parse_tag_template(&args.template, &text, kind)?;
+    tag.validate().map_err(|e| invalid(&args.template, e))?;
+
+    write_cbor(&tag, &args.output.path(&args.template))
+}
+
+/// Reads a tag of the given kind, either from a JSON template for the untagged structure
+/// or from CBOR encoding the tagged structure
+fn read_tag(path: &Path, kind: TagKind) -> CliResult<ConciseTagTypeChoice<'static>> {
+    decode_tag(path, read_file(path)?, kind)
+}
+
+/// Decodes a tag read by [`read_tag`] from `path`, which is only named in errors
+fn decode_tag(
+    path: &Path,
+    data: Vec<u8>,
+    kind: TagKind,
+) -> CliResult<ConciseTagTypeChoice<'static>> {
+    if data.trim_ascii_start().starts_with(b"{") {
+        let text = template_text(path, data)?;
+        return parse_tag_template(path, &text, kind).map(IntoOwned::into_owned);
+    }
+
+    let tag: ConciseTagTypeChoice = corim_rs::cbor::from_reader(data.as_slice())
+        .map_err(|e| format!("{}: failed to decode tag: {e}", path.display()))?;
+
+    if TagKind::of(&tag) != kind {
+        Err(format!(
+            "{}: expected a {}, found a {}",
+            path.display(),
+            kind.name(),
+            TagKind::of(&tag).name()
+        ))?
+    }
+
+    Ok(tag)
+}
+
+fn parse_tag_template<'a>(
+    path: &Path,
+    text: &'a str,
+    kind: TagKind,
+) -> CliResult<ConciseTagTypeChoice<'a>> {
+    Ok(match kind {
+        TagKind::Comid => parse_template::<ConciseMidTag>(path, text)?.into(),
+        TagKind::Coswid => parse_template::<ConciseSwidTag>(path, text)?.into(),
+        TagKind::Cotl => parse_template::<ConciseTlTag>(path, text)?.into(),
+    })
+}
+
+fn read_template(path: &Path) -> CliResult<String> {
+    template_text(path, read_file(path)?)
+}
+
+fn template_text(path: &Path, data: Vec<u8>) -> CliResult<String> {
+    Ok(String::from_utf8(data)
+        .map_err(|_| format!("{}: template is not valid UTF-8", path.display()))?)
+}
+
+/// Parses the JSON template `text` read from `path`, borrowing from `text`
+fn parse_template<'a, T>(path: &Path, text: &'a str) -> CliResult<T>
+where
+    T: Deserialize<'a>,
+{
+    serde_json::from_str(text).map_err(|e| invalid(path, e))
+}
+
+fn invalid(path: &Path, e: impl std::fmt::Display) -> Box<dyn std::error::Error> {
+    format!("invalid template {}:\n{e}", path.display()).into()
+}
+
+pub fn write_cbor<T: serde::Serialize>(value: &T, path: &Path) -> CliResult<()> {
+    let mut bytes = vec![];
+    ciborium::into_writer(value, &mut bytes)?;
+    write_output(path, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(dir: &tempfile::TempDir, name: &str, json: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, json).unwrap();
+        path
+    }
+
+    const COMID: &str = r#"{
+        "1": {"0": "comid-1"},
+        "4": {
+            "0": [[
+                {"class": {"vendor": "ACME Inc.", "model": "RoadRunner"}},
+                [{"mkey": "firmware", "mval": {"svn": 3}}]
+            ]]
+        }
+    }"#;
+
+    #[test]
+    fn test_create_comid() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = template(&dir, "comid.json", COMID);
+
+        run_tag(
+            TagKind::Comid,
+            TagCommand::Create(CreateTagArgs {
+                template: path.clone(),
+                output: OutputArgs { output: None },
+            }),
+        )
+        .unwrap();
+
+        let bytes = std::fs::read(dir.path().join("comid.cbor")).unwrap();
+        assert_eq!(&bytes[..3], &[0xd9, 0x01, 0xfa]); // tag(506)
+
+        let tag: ConciseTagTypeChoice = corim_rs::cbor::from_slice(&bytes).unwrap();
+        assert_eq!(
+            tag.as_comid().unwrap().tag_identity.tag_id.as_str(),
+            Some("comid-1")
+        );
+    }
+
+    #[test]
+    fn test_create_corim() {
+        let dir = tempfile::tempdir().unwrap();
+        let comid = template(&dir, "comid.json", COMID);
+        let corim = template(
+            &dir,
+            "corim.json",
+            r#"{
+                "0": "corim-1",
+                "1": [{"type": "coswid", "value": {
+                    "0": "swid-1", "12": 0, "1": "Firmware", "2": {"31": "ACME Inc.", "33": 1}
+                }}]
+            }"#,
+        );
+
+        let output = dir.path().join("out.cbor");
+        run_corim(CreateCorimArgs {
+            template: corim,
+            comid: vec![comid],
+            coswid: vec![],
+            cotl: vec![],
+            output: OutputArgs {
+                output: Some(output.clone()),
+            },
+        })
+        .unwrap();
+
+        let bytes = std::fs::read(&output).unwrap();
+        let corim: ConciseRimTypeChoice = corim_rs::cbor::from_slice(&bytes).unwrap();
+        let map = corim.as_unsigned_corim_map().unwrap();
+
+        assert_eq!(map.id.as_str(), Some("corim-1"));
+        assert_eq!(map.tags.len(), 2);
+        assert!(map.tags[0].as_coswid().is_some());
+        assert!(map.tags[1].as_comid().is_some());
+    }
+
+    #[test]
+    fn test_tag_template_from_stdin() {
+        // Standard input can only be read once, so the template is parsed from the data that
+        // was read to detect its format
+        let tag = decode_tag(Path::new("-"), COMID.as_bytes().to_vec(), TagKind::Comid).unwrap();
+        assert_eq!(
+            tag.as_comid().unwrap().tag_identity.tag_id.as_str(),
+            Some("comid-1")
+        );
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let empty_tags = template(&dir, "corim.json", r#"{"0": "corim-1", "1": []}"#);
+        let err = parse_template::<CorimMap>(&empty_tags, &read_template(&empty_tags).unwrap())
+            .map(|corim| corim.validate())
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "tags: must contain at least one entry");
+
+        let empty = template(
+            &dir,
+            "empty.json",
+            r#"{
+                "1": {"0": "comid-1"},
+                "4": {"0": [[{"class": {}}, [{"mval": {"digests": []}}]], [{"class": {"vendor": "a"}}, []]]}
+            }"#,
+        );
+        let tag = read_tag(&empty, TagKind::Comid).unwrap();
+        assert_eq!(
+            tag.validate().unwrap_err().to_string(),
+            [
+                "triples.reference-triples[0].ref-env.class: a ClassMap must have at least one non-empty field",
+                "triples.reference-triples[0].ref-claims[0].mval.digests: must contain at least one entry",
+                "triples.reference-triples[1].ref-claims: must contain at least one entry",
+            ]
+            .join("\n")
+        );
+
+        let empty_mval = template(
+            &dir,
+            "mval.json",
+            r#"{"1": {"0": "comid-1"}, "4": {"0": [[{"class": {"vendor": "a"}}, [{"mval": {}}]]]}}"#,
+        );
+        let err = read_tag(&empty_mval, TagKind::Comid).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("a MeasurementValuesMap must have at least one non-empty field"));
+
+        let no_triples = template(&dir, "none.json", r#"{"1": {"0": "comid-1"}, "4": {}}"#);
+        let tag = read_tag(&no_triples, TagKind::Comid).unwrap();
+        assert_eq!(
+            tag.validate().unwrap_err().to_string(),
+            "triples: a TriplesMap must have at least one non-empty field"
+        );
+
+        let malformed = template(&dir, "malformed.json", r#"{"1": {"0": "comid-1"}"#);
+        let err = read_tag(&malformed, TagKind::Comid).unwrap_err();
+        assert!(err.to_string().starts_with("invalid template "));
+        assert!(err.to_string().contains("line 1"));
+    }
+}
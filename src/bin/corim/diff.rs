@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MIT
+
+//! The `diff` command: compares two CoRIMs tag by tag
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use corim_rs::{
+    corim::ConciseRimTypeChoice,
+    describe::{describe, describe_environment, describe_tag_id},
+    diff::{Change, ComidDiff, CorimDiff, TagChange, TagKey},
+};
+use serde::Serialize;
+
+use crate::{
+    input::{corim_map, decode, decode_corim, read_file, Encoding},
+    inspect::Node,
+    write_stdout, CliResult,
+};
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// The old CoRIM, signed or unsigned
+    pub old: PathBuf,
+
+    /// The new CoRIM, signed or unsigned
+    pub new: PathBuf,
+
+    /// Encoding of both CoRIMs
+    #[arg(long, value_enum, default_value_t = Encoding::Auto)]
+    pub encoding: Encoding,
+
+    /// Print the differences as JSON instead of a tree
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub fn run(args: DiffArgs) -> CliResult<()> {
+    let old = read_corim(&args.old, args.encoding)?;
+    let new = read_corim(&args.new, args.encoding)?;
+    let diff = corim_map(&old)?.diff(corim_map(&new)?);
+
+    if args.json {
+        write_stdout(&format!("{}\n", serde_json::to_string_pretty(&diff)?))
+    } else {
+        write_stdout(&to_tree(&diff))
+    }
+}
+
+fn read_corim(path: &Path, encoding: Encoding) -> CliResult<ConciseRimTypeChoice<'static>> {
+    let context = |e: Box<dyn std::error::Error>| format!("{}: {e}", path.display());
+    let cbor = decode(&read_file(path)?, encoding).map_err(context)?;
+    Ok(decode_corim(&cbor).map_err(context)?)
+}
+
+/// Renders the differences as a tree
+pub fn to_tree(diff: &CorimDiff) -> String {
+    if diff.is_empty() {
+        return "no differences\n".to_string();
+    }
+
+    let mut root = Node::new("differences");
+
+    if let Some(change) = &diff.id {
+        root.leaf(format!("id: {}", describe_change(change)));
+    }
+    if let Some(change) = &diff.dependent_rims {
+        root.leaf(format!("dependent-rims: {}", describe_change(change)));
+    }
+    if let Some(change) = &diff.profile {
+        root.leaf(format!("profile: {}", describe_change(change)));
+    }
+    if let Some(change) = &diff.rim_validity {
+        root.leaf(format!("rim-validity: {}", describe_change(change)));
+    }
+    if let Some(change) = &diff.entities {
+        root.leaf(format!("entities: {}", describe_change(change)));
+    }
+    if let Some(change) = &diff.extension {
+        root.leaf(format!("extension: {}", describe_change(change)));
+    }
+
+    for tag in &diff.tags {
+        let key = describe_tag_key(&tag.key);
+        match &tag.change {
+            TagChange::Added { .. } => root.leaf(format!("{key}: added")),
+            TagChange::Removed { .. } => root.leaf(format!("{key}: removed")),
+            TagChange::Modified { .. } => root.leaf(format!("{key}: modified")),
+            TagChange::ModifiedComid { diff } => {
+                add_comid(root.child(format!("{key}: modified")), diff)
+            }
+        }
+    }
+
+    root.render()
+}
+
+fn add_comid(node: &mut Node, diff: &ComidDiff) {
+    if let Some(change) = &diff.tag_version {
+        node.leaf(format!("tag-version: {}", describe_change(change)));
+    }
+
+    for measurement in &diff.measurements {
+        let mut text = format!(
+            "{} {}",
+            describe(&measurement.triple),
+            describe_environment(measurement.environment)
+        );
+        if let Some(mkey) = measurement.mkey {
+            text.push_str(&format!(" {}", describe(mkey)));
+        }
+
+        match &measurement.change {
+            Change::Added { new } => node.leaf(format!("{text}: added {}", describe(new))),
+            Change::Removed { old } => node.leaf(format!("{text}: removed {}", describe(old))),
+            Change::Modified { .. } => {
+                let child = node.child(format!("{text}: modified"));
+                if let Some(change) = &measurement.svn {
+                    child.leaf(format!("svn: {}", describe_change(change)));
+                }
+                for change in &measurement.digests {
+                    child.leaf(format!("digest: {}", describe_change(change)));
+                }
+                if !measurement.other.is_empty() {
+                    child.leaf(format!("also changed: {}", measurement.other.join(", ")));
+                }
+            }
+        }
+    }
+
+    for key in &diff.keys {
+        node.leaf(format!(
+            "{} {}: key {}",
+            describe(&key.triple),
+            describe_environment(key.environment),
+            describe_change(&key.change)
+        ));
+    }
+
+    if diff.other_changes {
+        node.leaf("other changes to the tag metadata or triples");
+    }
+}
+
+fn describe_tag_key(key: &TagKey) -> String {
+    match key {
+        TagKey::Comid(id) => format!("comid {}", describe_tag_id(id)),
+        TagKey::Coswid(id) => format!("coswid {}", describe(id)),
+        TagKey::Cotl(id) => format!("cotl {}", describe_tag_id(id)),
+    }
+}
+
+fn describe_change<T: Serialize>(change: &Change<T>) -> String {
+    match change {
+        Change::Added { new } => format!("added {}", describe(new)),
+        Change::Removed { old } => format!("removed {}", describe(old)),
+        Change::Modified { old, new } => format!("{} -> {}", describe(old), describe(new)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNSIGNED: &[u8] = include_bytes!("../../../testdata/corim/unsigned.cbor");
+    const SIGNED: &[u8] = include_bytes!("../../../testdata/corim/signed.cbor");
+
+    #[test]
+    fn test_diff_signed_and_unsigned() {
+        let unsigned = decode_corim(UNSIGNED).unwrap();
+        let signed = decode_corim(SIGNED).unwrap();
+        let diff = corim_map(&unsigned)
+            .unwrap()
+            .diff(corim_map(&signed).unwrap());
+
+        assert!(diff.is_empty());
+        assert_eq!(to_tree(&diff), "no differences\n");
+    }
+
+    #[test]
+    fn test_diff_tree() {
+        let old = decode_corim(UNSIGNED).unwrap();
+        let old = corim_map(&old).unwrap();
+
+        let mut new = old.clone();
+        let json = serde_json::to_string(&new.tags[0])
+            .unwrap()
+            .replace(r#""svn":3"#, r#""svn":4"#);
+        new.tags[0] = serde_json::from_str(&json).unwrap();
+
+        let tree = to_tree(&old.diff(&new));
+        assert!(tree.starts_with("differences\n"), "{tree}");
+        assert!(tree.contains(": modified\n"), "{tree}");
+        assert!(tree.contains("svn: 3 -> 4"), "{tree}");
+
+        new.tags.clear();
+        let tree = to_tree(&old.diff(&new));
+        assert!(tree.contains(": removed"), "{tree}");
+
+        let mut new = old.clone();
+        new.entities = Some(vec![]);
+        let tree = to_tree(&old.diff(&new));
+        assert!(tree.contains("entities: "), "{tree}");
+    }
+}
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: MIT
+
+//! Reading and decoding of CoRIM input files
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use base64::{
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use clap::{Args, ValueEnum};
+use corim_rs::{
+    corim::{ConciseRimTypeChoice, CorimMap},
+    edn,
+};
+
+use crate::CliResult;
+
+/// Encoding of an input file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Encoding {
+    /// Detect the encoding from the content
+    #[default]
+    Auto,
+    /// Raw CBOR
+    Cbor,
+    /// Base64 (standard or URL-safe alphabet, padding optional) encoded CBOR
+    Base64,
+    /// Hex encoded CBOR
+    Hex,
+    /// CBOR diagnostic notation, e.g. an example copied from a draft
+    Edn,
+}
+
+/// Arguments selecting a CBOR input
+#[derive(Debug, Args)]
+pub struct InputArgs {
+    /// Input file, or `-` to read from standard input
+    #[arg(default_value = "-")]
+    pub input: PathBuf,
+
+    /// Encoding of the input
+    #[arg(long, value_enum, default_value_t = Encoding::Auto)]
+    pub encoding: Encoding,
+}
+
+impl InputArgs {
+    /// Reads the input and returns the decoded CBOR bytes
+    pub fn read_cbor(&self) -> CliResult<Vec<u8>> {
+        let data = read_file(&self.input)?;
+        decode(&data, self.encoding)
+    }
+
+    /// Reads the input and decodes it as a CoRIM
+    pub fn read_corim(&self) -> CliResult<ConciseRimTypeChoice<'static>> {
+        decode_corim(&self.read_cbor()?)
+    }
+}
+
+/// Reads the contents of `path`, or standard input if `path` is `-`
+pub fn read_file(path: &Path) -> CliResult<Vec<u8>> {
+    if path.as_os_str() == "-" {
+        let mut data = vec![];
+        std::io::stdin().read_to_end(&mut data)?;
+        Ok(data)
+    } else {
+        std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()).into())
+    }
+}
+
+/// Decodes `data` from `encoding` into raw CBOR bytes
+pub fn decode(data: &[u8], encoding: Encoding) -> CliResult<Vec<u8>> {
+    match encoding {
+        Encoding::Cbor => Ok(data.to_vec()),
+        Encoding::Hex => decode_hex(data),
+        Encoding::Base64 => decode_base64(data),
+        Encoding::Edn => decode_edn(data),
+        Encoding::Auto => detect(data),
+    }
+}
+
+/// Every CoRIM starts with a CBOR tag, whose initial byte is never ASCII. ASCII input is
+/// therefore treated as diagnostic notation, when it contains brackets or quotes that neither
+/// hex nor base64 use, as hex, when it only contains hex digits, or otherwise as base64.
+fn detect(data: &[u8]) -> CliResult<Vec<u8>> {
+    if !data.is_ascii() {
+        return Ok(data.to_vec());
+    }
+
+    let text = strip_whitespace(data);
+
+    if text.is_empty() {
+        Err("input is empty")?
+    }
+
+    if text.iter().any(|b| b"([{<'\"".contains(b)) {
+        decode_edn(data)
+    } else if text.len() % 2 == 0 && text.iter().all(u8::is_ascii_hexdigit) {
+        decode_hex(&text)
+    } else {
+        decode_base64(&text)
+    }
+}
+
+fn strip_whitespace(data: &[u8]) -> Vec<u8> {
+    data.iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect()
+}
+
+fn decode_hex(data: &[u8]) -> CliResult<Vec<u8>> {
+    hex::decode(strip_whitespace(data)).map_err(|e| format!("invalid hex input: {e}").into())
+}
+
+fn decode_base64(data: &[u8]) -> CliResult<Vec<u8>> {
+    let text = strip_whitespace(data);
+
+    [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(&text).ok())
+        .ok_or_else(|| "invalid base64 input".into())
+}
+
+fn decode_edn(data: &[u8]) -> CliResult<Vec<u8>> {
+    let text = std::str::from_utf8(data).map_err(|_| "diagnostic notation is not UTF-8")?;
+    Ok(edn::to_cbor(text)?)
+}
+
+/// Decodes CBOR bytes as a CoRIM, distinguishing the variants by their CBOR tag
+pub fn decode_corim(cbor: &[u8]) -> CliResult<ConciseRimTypeChoice<'static>> {
+    corim_rs::cbor::from_reader(cbor).map_err(|e| format!("failed to decode CoRIM: {e}").into())
+}
+
+/// Returns the CoRIM map of an unsigned CoRIM, or the payload of a signed one
+pub fn corim_map<'a>(corim: &'a ConciseRimTypeChoice<'a>) -> CliResult<&'a CorimMap<'a>> {
+    match corim {
+        ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) => Ok(map.as_ref()),
+        ConciseRimTypeChoice::SignedCorim(signed) => Ok(signed.payload.as_ref()),
+        ConciseRimTypeChoice::MultiSignedCorim(signed) => Ok(signed.payload.as_ref()),
+        ConciseRimTypeChoice::DetachedSignedCorim(_) => {
+            Err("a detached signed CoRIM carries no payload".into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNSIGNED: &[u8] = include_bytes!("../../../testdata/corim/unsigned.cbor");
+
+    #[test]
+    fn test_detect_encoding() {
+        let hex = hex::encode(UNSIGNED);
+        let base64 = STANDARD.encode(UNSIGNED);
+        let base64_url = URL_SAFE_NO_PAD.encode(UNSIGNED);
+
+        assert_eq!(decode(UNSIGNED, Encoding::Auto).unwrap(), UNSIGNED);
+        assert_eq!(decode(hex.as_bytes(), Encoding::Auto).unwrap(), UNSIGNED);
+        assert_eq!(decode(base64.as_bytes(), Encoding::Auto).unwrap(), UNSIGNED);
+        assert_eq!(
+            decode(base64_url.as_bytes(), Encoding::Auto).unwrap(),
+            UNSIGNED
+        );
+
+        let wrapped = format!("{}\n{}\n", &hex[..64], &hex[64..]);
+        assert_eq!(decode(wrapped.as_bytes(), Encoding::Hex).unwrap(), UNSIGNED);
+
+        assert!(decode(b"", Encoding::Auto).is_err());
+        assert!(decode(b"not base64!", Encoding::Auto).is_err());
+        assert!(decode(b"abc", Encoding::Hex).is_err());
+
+        let edn = b"501({ / id / \"0\": h'01' })";
+        let cbor = [0xd9, 0x01, 0xf5, 0xa1, 0x61, b'0', 0x41, 0x01];
+        assert_eq!(decode(edn, Encoding::Auto).unwrap(), cbor);
+        assert_eq!(decode(edn, Encoding::Edn).unwrap(), cbor);
+        assert!(decode(b"0x01", Encoding::Edn).is_ok());
+        assert!(decode(b"[1 2]", Encoding::Auto).is_err());
+    }
+
+    #[test]
+    fn test_decode_corim() {
+        let corim = decode_corim(UNSIGNED).unwrap();
+        assert!(corim.as_unsigned_corim_map().is_some());
+
+        let corim = decode_corim(include_bytes!("../../../testdata/corim/signed.cbor")).unwrap();
+        assert!(corim.as_signed_corim().is_some());
+
+        assert!(decode_corim(&[0xd9, 0x01, 0xf6, 0xa0]).is_err());
+    }
+}
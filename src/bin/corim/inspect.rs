@@ -0,0 +1,776 @@
+// SPDX-License-Identifier: MIT
+
+//! The `inspect` command: decodes a CoRIM and prints a summary tree
+
+use clap::Args;
+use corim_rs::{
+    comid::{ConciseMidTag, TriplesMap},
+    core::{Bytes, CoseAlgorithm, TextOrBytes},
+    corim::{
+        ConciseRimTypeChoice, ConciseTagTypeChoice, CorimIdTypeChoice, CorimMap, CorimMetaMap,
+        ProfileTypeChoice, ValidityMap,
+    },
+    coswid::ConciseSwidTag,
+    cotl::ConciseTlTag,
+    describe::{describe, describe_environment, describe_measurement, describe_tag_id},
+    edn,
+    result::Result,
+};
+use serde::Serialize;
+use x509_cert::der::Decode as _;
+
+use crate::{input::InputArgs, write_stdout, CliResult};
+
+#[derive(Debug, Args)]
+pub struct InspectArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Print the summary as JSON instead of a tree
+    #[arg(long)]
+    pub json: bool,
+
+    /// Print the input in CBOR Extended Diagnostic Notation instead of a summary
+    #[arg(long, conflicts_with = "json")]
+    pub edn: bool,
+}
+
+pub fn run(args: InspectArgs) -> CliResult<()> {
+    if args.edn {
+        let cbor = args.input.read_cbor()?;
+        let edn = edn::from_cbor(&cbor, edn::Rule::Any, &edn::EdnOptions::default())?;
+        return write_stdout(&format!("{edn}\n"));
+    }
+
+    let corim = args.input.read_corim()?;
+    let report = Report::new(&corim)?;
+
+    if args.json {
+        write_stdout(&format!("{}\n", serde_json::to_string_pretty(&report)?))
+    } else {
+        write_stdout(&report.to_tree())
+    }
+}
+
+/// Summary of a decoded CoRIM
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// The `ConciseRimTypeChoice` variant
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signers: Vec<SignerReport>,
+    /// The CoRIM map, absent for detached payloads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corim: Option<CorimReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignerReport {
+    pub alg: String,
+    pub kid: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_validity: Option<ValidityReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub x5chain: Vec<String>,
+    pub signature_len: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidityReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<String>,
+    pub not_after: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CorimReport {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validity: Option<ValidityReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<EntityReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependent_rims: Vec<String>,
+    pub tags: Vec<TagReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntityReport {
+    pub name: String,
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TagReport {
+    Comid {
+        tag_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tag_version: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        entities: Vec<EntityReport>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        linked_tags: Vec<String>,
+        triples: Vec<TripleReport>,
+    },
+    Coswid {
+        tag_id: String,
+        tag_version: String,
+        software_name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        software_version: Option<String>,
+        entities: Vec<EntityReport>,
+    },
+    Cotl {
+        tag_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tag_version: Option<String>,
+        tags: Vec<String>,
+        validity: ValidityReport,
+    },
+}
+
+/// Summary of a single triple record
+#[derive(Debug, Serialize)]
+pub struct TripleReport {
+    pub kind: &'static str,
+    pub environment: String,
+    pub details: Vec<String>,
+}
+
+impl Report {
+    pub fn new(corim: &ConciseRimTypeChoice) -> Result<Self> {
+        let report = match corim {
+            ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) => Self {
+                kind: "unsigned-corim",
+                content_type: None,
+                signers: vec![],
+                corim: Some(CorimReport::new(map)),
+            },
+            ConciseRimTypeChoice::SignedCorim(signed) => Self {
+                kind: "signed-corim",
+                content_type: Some(signed.protected.content_type.to_string()),
+                signers: vec![SignerReport::new(
+                    signed.algorithm()?,
+                    &signed.protected.kid,
+                    &signed.protected.corim_meta,
+                    signed.x5chain()?,
+                    signed.signature.len(),
+                )],
+                corim: Some(CorimReport::new(&signed.payload)),
+            },
+            ConciseRimTypeChoice::DetachedSignedCorim(signed) => Self {
+                kind: "detached-signed-corim",
+                content_type: Some(signed.protected.content_type.to_string()),
+                signers: vec![SignerReport::new(
+                    signed.algorithm()?,
+                    &signed.protected.kid,
+                    &signed.protected.corim_meta,
+                    signed.x5chain()?,
+                    signed.signature.len(),
+                )],
+                corim: None,
+            },
+            ConciseRimTypeChoice::MultiSignedCorim(signed) => Self {
+                kind: "multi-signed-corim",
+                content_type: Some(signed.protected.content_type.to_string()),
+                signers: signed
+                    .signatures
+                    .iter()
+                    .map(|signature| {
+                        Ok(SignerReport::new(
+                            signature.algorithm()?,
+                            &signature.protected.kid,
+                            &signature.protected.corim_meta,
+                            signature.x5chain()?,
+                            signature.signature.len(),
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+                corim: Some(CorimReport::new(&signed.payload)),
+            },
+        };
+
+        Ok(report)
+    }
+
+    /// Renders the report as an indented tree
+    pub fn to_tree(&self) -> String {
+        let mut root = Node::new(self.kind);
+
+        if let Some(content_type) = &self.content_type {
+            root.leaf(format!("content-type: {content_type}"));
+        }
+
+        for (index, signer) in self.signers.iter().enumerate() {
+            let node = root.child(format!("signer[{index}]: {}", signer.name));
+            node.leaf(format!("alg: {}", signer.alg));
+            node.leaf(format!("kid: {}", signer.kid));
+            if let Some(uri) = &signer.uri {
+                node.leaf(format!("uri: {uri}"));
+            }
+            if let Some(validity) = &signer.signature_validity {
+                node.leaf(format!("signature-validity: {validity}"));
+            }
+            if !signer.x5chain.is_empty() {
+                let chain = node.child("x5chain");
+                for subject in &signer.x5chain {
+                    chain.leaf(subject);
+                }
+            }
+            node.leaf(format!("signature: {} bytes", signer.signature_len));
+        }
+
+        match &self.corim {
+            Some(corim) => corim.add_to(&mut root),
+            None => root.leaf("payload: detached"),
+        }
+
+        root.render()
+    }
+}
+
+impl SignerReport {
+    fn new(
+        alg: CoseAlgorithm,
+        kid: &Bytes,
+        meta: &CorimMetaMap,
+        x5chain: Option<Vec<Bytes>>,
+        signature_len: usize,
+    ) -> Self {
+        Self {
+            alg: alg.to_string(),
+            kid: describe_bytes(kid),
+            name: meta.signer.signer_name.to_string(),
+            uri: meta.signer.signer_uri.as_ref().map(|uri| uri.to_string()),
+            signature_validity: meta.signature_validity.as_ref().map(ValidityReport::new),
+            x5chain: x5chain
+                .unwrap_or_default()
+                .iter()
+                .map(|der| match x509_cert::Certificate::from_der(der) {
+                    Ok(cert) => cert.tbs_certificate.subject.to_string(),
+                    Err(e) => format!("<invalid certificate: {e}>"),
+                })
+                .collect(),
+            signature_len,
+        }
+    }
+}
+
+impl ValidityReport {
+    pub fn new(validity: &ValidityMap) -> Self {
+        Self {
+            not_before: validity.not_before.as_ref().map(|t| t.to_string()),
+            not_after: validity.not_after.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.not_before {
+            Some(not_before) => write!(f, "{not_before}..{}", self.not_after),
+            None => write!(f, "..{}", self.not_after),
+        }
+    }
+}
+
+impl CorimReport {
+    fn new(corim: &CorimMap) -> Self {
+        Self {
+            id: match &corim.id {
+                CorimIdTypeChoice::Tstr(id) => id.to_string(),
+                CorimIdTypeChoice::Uuid(id) => id.to_string(),
+            },
+            profile: corim.profile.as_ref().map(|profile| match profile {
+                ProfileTypeChoice::Uri(uri) => uri.to_string(),
+                ProfileTypeChoice::OidType(oid) => oid.to_string(),
+            }),
+            validity: corim.rim_validity.as_ref().map(ValidityReport::new),
+            entities: corim
+                .entities
+                .iter()
+                .flatten()
+                .map(|entity| EntityReport {
+                    name: entity.entity_name.to_string(),
+                    roles: entity.role.iter().map(kebab_case).collect(),
+                })
+                .collect(),
+            dependent_rims: corim
+                .dependent_rims
+                .iter()
+                .flatten()
+                .flat_map(|locator| locator.href.iter().map(|href| href.to_string()))
+                .collect(),
+            tags: corim.tags.iter().map(TagReport::new).collect(),
+        }
+    }
+
+    fn add_to(&self, root: &mut Node) {
+        let node = root.child(format!("corim: {}", self.id));
+
+        if let Some(profile) = &self.profile {
+            node.leaf(format!("profile: {profile}"));
+        }
+        if let Some(validity) = &self.validity {
+            node.leaf(format!("validity: {validity}"));
+        }
+        add_entities(node, &self.entities);
+        if !self.dependent_rims.is_empty() {
+            let deps = node.child("dependent-rims");
+            for href in &self.dependent_rims {
+                deps.leaf(href);
+            }
+        }
+
+        let tags = node.child(format!("tags ({})", self.tags.len()));
+        for tag in &self.tags {
+            tag.add_to(tags);
+        }
+    }
+}
+
+impl TagReport {
+    fn new(tag: &ConciseTagTypeChoice) -> Self {
+        match tag {
+            ConciseTagTypeChoice::Mid(comid) => Self::comid(comid),
+            ConciseTagTypeChoice::Swid(coswid) => Self::coswid(coswid),
+            ConciseTagTypeChoice::Tl(cotl) => Self::cotl(cotl),
+        }
+    }
+
+    fn comid(comid: &ConciseMidTag) -> Self {
+        Self::Comid {
+            tag_id: describe_tag_id(&comid.tag_identity.tag_id),
+            tag_version: comid
+                .tag_identity
+                .tag_version
+                .as_ref()
+                .map(|v| v.to_string()),
+            entities: comid
+                .entities
+                .iter()
+                .flatten()
+                .map(|entity| EntityReport {
+                    name: entity.entity_name.to_string(),
+                    roles: entity.role.iter().map(kebab_case).collect(),
+                })
+                .collect(),
+            linked_tags: comid
+                .linked_tags
+                .iter()
+                .flatten()
+                .map(|linked| {
+                    format!(
+                        "{} ({})",
+                        describe_tag_id(&linked.linked_tag_id),
+                        kebab_case(&linked.tag_rel)
+                    )
+                })
+                .collect(),
+            triples: triple_reports(&comid.triples),
+        }
+    }
+
+    fn coswid(coswid: &ConciseSwidTag) -> Self {
+        Self::Coswid {
+            tag_id: match &coswid.tag_id {
+                TextOrBytes::Text(text) => text.to_string(),
+                TextOrBytes::Bytes(bytes) => describe_bytes(bytes),
+            },
+            tag_version: coswid.tag_version.to_string(),
+            software_name: coswid.software_name.to_string(),
+            software_version: coswid.software_version.as_ref().map(|v| v.to_string()),
+            entities: coswid
+                .entity
+                .iter()
+                .map(|entity| EntityReport {
+                    name: entity.entity_name.to_string(),
+                    roles: entity.role.iter().map(|role| coswid_role(*role)).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn cotl(cotl: &ConciseTlTag) -> Self {
+        Self::Cotl {
+            tag_id: describe_tag_id(&cotl.tag_identity.tag_id),
+            tag_version: cotl
+                .tag_identity
+                .tag_version
+                .as_ref()
+                .map(|v| v.to_string()),
+            tags: cotl
+                .tags_list
+                .iter()
+                .map(|tag| describe_tag_id(&tag.tag_id))
+                .collect(),
+            validity: ValidityReport::new(&cotl.tl_validity),
+        }
+    }
+
+    fn add_to(&self, root: &mut Node) {
+        match self {
+            Self::Comid {
+                tag_id,
+                tag_version,
+                entities,
+                linked_tags,
+                triples,
+            } => {
+                let node = root.child(format!("comid: {tag_id}"));
+                if let Some(version) = tag_version {
+                    node.leaf(format!("tag-version: {version}"));
+                }
+                add_entities(node, entities);
+                if !linked_tags.is_empty() {
+                    let linked = node.child("linked-tags");
+                    for tag in linked_tags {
+                        linked.leaf(tag);
+                    }
+                }
+                let node = node.child(format!("triples ({})", triples.len()));
+                for triple in triples {
+                    let triple_node =
+                        node.child(format!("{}: {}", triple.kind, triple.environment));
+                    for detail in &triple.details {
+                        triple_node.leaf(detail);
+                    }
+                }
+            }
+            Self::Coswid {
+                tag_id,
+                tag_version,
+                software_name,
+                software_version,
+                entities,
+            } => {
+                let node = root.child(format!("coswid: {tag_id}"));
+                node.leaf(format!("tag-version: {tag_version}"));
+                match software_version {
+                    Some(version) => node.leaf(format!("software: {software_name} {version}")),
+                    None => node.leaf(format!("software: {software_name}")),
+                }
+                add_entities(node, entities);
+            }
+            Self::Cotl {
+                tag_id,
+                tag_version,
+                tags,
+                validity,
+            } => {
+                let node = root.child(format!("cotl: {tag_id}"));
+                if let Some(version) = tag_version {
+                    node.leaf(format!("tag-version: {version}"));
+                }
+                node.leaf(format!("validity: {validity}"));
+                let list = node.child(format!("tags ({})", tags.len()));
+                for tag in tags {
+                    list.leaf(tag);
+                }
+            }
+        }
+    }
+}
+
+fn triple_reports(triples: &TriplesMap) -> Vec<TripleReport> {
+    let mut reports = vec![];
+
+    for triple in triples.reference_triples.iter().flatten() {
+        reports.push(TripleReport {
+            kind: "reference",
+            environment: describe_environment(&triple.ref_env),
+            details: triple.ref_claims.iter().map(describe_measurement).collect(),
+        });
+    }
+
+    for triple in triples.endorsed_triples.iter().flatten() {
+        reports.push(TripleReport {
+            kind: "endorsed",
+            environment: describe_environment(&triple.condition),
+            details: triple
+                .endorsement
+                .iter()
+                .map(describe_measurement)
+                .collect(),
+        });
+    }
+
+    for (kind, environment, keys) in triples
+        .identity_triples
+        .iter()
+        .flatten()
+        .map(|t| ("identity", &t.environment, &t.key_list))
+        .chain(
+            triples
+                .attest_key_triples
+                .iter()
+                .flatten()
+                .map(|t| ("attest-key", &t.environment, &t.key_list)),
+        )
+    {
+        reports.push(TripleReport {
+            kind,
+            environment: describe_environment(environment),
+            details: keys
+                .iter()
+                .map(|key| format!("key: {}", describe(key)))
+                .collect(),
+        });
+    }
+
+    for (kind, triple) in triples
+        .dependency_triples
+        .iter()
+        .flatten()
+        .map(|t| ("dependency", (&t.domain_choice, &t.environment_map)))
+        .chain(
+            triples
+                .membership_triples
+                .iter()
+                .flatten()
+                .map(|t| ("membership", (&t.domain_choice, &t.environment_map))),
+        )
+    {
+        let (domain, environments) = triple;
+        reports.push(TripleReport {
+            kind,
+            environment: describe(domain),
+            details: environments
+                .iter()
+                .map(|env| format!("environment: {}", describe_environment(env)))
+                .collect(),
+        });
+    }
+
+    for triple in triples.coswid_triples.iter().flatten() {
+        reports.push(TripleReport {
+            kind: "coswid",
+            environment: describe_environment(&triple.environment_map),
+            details: triple
+                .coswid_tags
+                .iter()
+                .map(|tag| format!("tag: {}", describe(tag)))
+                .collect(),
+        });
+    }
+
+    for triple in triples
+        .conditional_endorsement_series_triples
+        .iter()
+        .flatten()
+    {
+        let mut details: Vec<String> = triple
+            .condition
+            .claims_list
+            .iter()
+            .map(|claim| format!("condition {}", describe_measurement(claim)))
+            .collect();
+        details.extend(triple.series.iter().enumerate().map(|(index, record)| {
+            format!(
+                "series[{index}]: {} selection, {} addition",
+                record.selection.len(),
+                record.addition.len()
+            )
+        }));
+        reports.push(TripleReport {
+            kind: "conditional-endorsement-series",
+            environment: describe_environment(&triple.condition.environment),
+            details,
+        });
+    }
+
+    for triple in triples.conditional_endorsement_triples.iter().flatten() {
+        reports.push(TripleReport {
+            kind: "conditional-endorsement",
+            environment: triple
+                .conditions
+                .iter()
+                .map(|condition| describe_environment(&condition.environment))
+                .collect::<Vec<_>>()
+                .join("; "),
+            details: triple
+                .endorsements
+                .iter()
+                .flat_map(|endorsement| {
+                    endorsement.endorsement.iter().map(|claim| {
+                        format!(
+                            "{} => {}",
+                            describe_environment(&endorsement.condition),
+                            describe_measurement(claim)
+                        )
+                    })
+                })
+                .collect(),
+        });
+    }
+
+    reports
+}
+
+fn add_entities(node: &mut Node, entities: &[EntityReport]) {
+    for entity in entities {
+        node.leaf(format!(
+            "entity: {} ({})",
+            entity.name,
+            entity.roles.join(", ")
+        ));
+    }
+}
+
+pub fn describe_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if !text.is_empty() && text.chars().all(|c| c.is_ascii_graphic()) => {
+            format!("{} ({text:?})", hex::encode(bytes))
+        }
+        _ => hex::encode(bytes),
+    }
+}
+
+fn coswid_role(role: u8) -> String {
+    match role {
+        1 => "tag-creator".to_string(),
+        2 => "software-creator".to_string(),
+        3 => "aggregator".to_string(),
+        4 => "distributor".to_string(),
+        5 => "licensor".to_string(),
+        6 => "maintainer".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Converts the `Debug` name of a unit variant (e.g. `TagCreator`) to kebab-case
+fn kebab_case<T: std::fmt::Debug>(value: &T) -> String {
+    let name = format!("{value:?}");
+    let mut out = String::with_capacity(name.len() + 4);
+    for (index, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if index > 0 {
+                out.push('-');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// A node of the rendered tree
+#[derive(Debug)]
+pub struct Node {
+    text: String,
+    children: Vec<Node>,
+}
+
+impl Node {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            children: vec![],
+        }
+    }
+
+    pub fn child(&mut self, text: impl Into<String>) -> &mut Node {
+        self.children.push(Node::new(text));
+        self.children.last_mut().unwrap()
+    }
+
+    pub fn leaf(&mut self, text: impl Into<String>) {
+        self.children.push(Node::new(text));
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = format!("{}\n", self.text);
+        self.render_children("", &mut out);
+        out
+    }
+
+    fn render_children(&self, prefix: &str, out: &mut String) {
+        for (index, child) in self.children.iter().enumerate() {
+            let last = index + 1 == self.children.len();
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            out.push_str(&format!("{prefix}{branch}{}\n", child.text));
+            child.render_children(&format!("{prefix}{indent}"), out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::decode_corim;
+
+    const UNSIGNED: &[u8] = include_bytes!("../../../testdata/corim/unsigned.cbor");
+    const SIGNED: &[u8] = include_bytes!("../../../testdata/corim/signed.cbor");
+
+    #[test]
+    fn test_unsigned_tree() {
+        let report = Report::new(&decode_corim(UNSIGNED).unwrap()).unwrap();
+        let tree = report.to_tree();
+
+        assert!(tree.starts_with("unsigned-corim\n└── corim: acme-roadrunner-corim\n"));
+        assert!(tree.contains("├── validity: 1704067200..1893456000\n"));
+        assert!(tree.contains("├── comid: acme-roadrunner-comid\n"));
+        assert!(tree.contains("│   ├── entity: ACME Inc. ("));
+        assert!(tree.contains("triples (2)"));
+        assert!(tree.contains(
+            "reference: class={class-id=2.16.840.1.113741.1.2.3 (oid), model=RoadRunner"
+        ));
+        assert!(tree.contains(
+            "firmware: digests=[sha-256;XP_FZ_Dl8qdF9zaFHbFVlEHm8Tsj7aHpBm7izNZOxag], svn=3"
+        ));
+        assert!(tree.contains("key: -----BEGIN CERTIFICATE----- MIIB"));
+        assert!(tree.contains("attest-key: class={model=RoadRunner, vendor=ACME Inc.}"));
+        assert!(tree.contains("└── coswid: acme-roadrunner-fw\n"));
+        assert!(tree.contains("software: RoadRunner Firmware 1.0.0"));
+    }
+
+    #[test]
+    fn test_signed_json() {
+        let report = Report::new(&decode_corim(SIGNED).unwrap()).unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["kind"], "signed-corim");
+        assert_eq!(json["content_type"], "application/rim+cbor");
+        assert_eq!(json["signers"][0]["alg"], "ES256");
+        assert_eq!(json["signers"][0]["name"], "Test CoRIM Signer");
+        assert_eq!(json["signers"][0]["uri"], "https://acme.example/signer");
+        assert_eq!(json["corim"]["id"], "acme-roadrunner-corim");
+        assert_eq!(json["corim"]["tags"][0]["type"], "comid");
+        assert_eq!(json["corim"]["tags"][0]["triples"][0]["kind"], "reference");
+        assert_eq!(json["corim"]["tags"][1]["type"], "coswid");
+
+        let tree = report.to_tree();
+        assert!(tree.contains("├── signer[0]: Test CoRIM Signer\n"));
+        assert!(tree.contains("│   ├── alg: ES256\n"));
+    }
+
+    #[test]
+    fn test_describe_value() {
+        let value = serde_json::json!({
+            "class": {"vendor": "ACME", "class-id": {"type": "oid", "value": "1.2.3"}},
+            "layers": [1, 2],
+        });
+
+        assert_eq!(
+            describe(&value),
+            "class={class-id=1.2.3 (oid), vendor=ACME}, layers=[1, 2]"
+        );
+        assert_eq!(
+            kebab_case(&corim_rs::corim::CorimRoleTypeChoice::ManifestCreator),
+            "manifest-creator"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: MIT
+
+//! Loading of software keys from local files
+
+use std::path::Path;
+
+use corim_rs::{
+    core::{Bytes, CoseKey},
+    cose::{CoseSigningKey, CoseVerifyingKey},
+};
+use x509_cert::der::Encode as _;
+
+use crate::{
+    input::{decode, read_file, Encoding},
+    CliResult,
+};
+
+/// A private key, along with the key identifier carried by a COSE_Key
+#[derive(Debug)]
+pub struct SigningKeyFile {
+    pub key: CoseSigningKey,
+    pub kid: Option<Bytes<'static>>,
+}
+
+/// Reads a private key from a PKCS#8 PEM file or a CBOR encoded COSE_Key
+pub fn read_signing_key(path: &Path) -> CliResult<SigningKeyFile> {
+    let data = read_file(path)?;
+    let context = |e: corim_rs::error::Error| format!("{}: {e}", path.display());
+
+    match pem_text(&data) {
+        Some(pem) => Ok(SigningKeyFile {
+            key: CoseSigningKey::from_pkcs8_pem(pem).map_err(context)?,
+            kid: None,
+        }),
+        None => {
+            let cose_key = read_cose_key(path, &data)?;
+            Ok(SigningKeyFile {
+                key: CoseSigningKey::from_cose_key(&cose_key).map_err(context)?,
+                kid: cose_key.kid,
+            })
+        }
+    }
+}
+
+/// Reads a public key from a PEM public key or certificate, or a CBOR encoded COSE_Key
+pub fn read_verifying_key(path: &Path) -> CliResult<CoseVerifyingKey> {
+    let data = read_file(path)?;
+    let context = |e: corim_rs::error::Error| format!("{}: {e}", path.display());
+
+    let Some(pem) = pem_text(&data) else {
+        let cose_key = read_cose_key(path, &data)?;
+        return Ok(CoseVerifyingKey::from_cose_key(&cose_key).map_err(context)?);
+    };
+
+    if pem.contains("-----BEGIN CERTIFICATE-----") {
+        let (_, der) = x509_cert::der::pem::decode_vec(pem.as_bytes())
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        Ok(CoseVerifyingKey::from_certificate_der(&der).map_err(context)?)
+    } else {
+        Ok(CoseVerifyingKey::from_public_key_pem(pem).map_err(context)?)
+    }
+}
+
+/// Reads every certificate from a PEM file, in order, as DER
+pub fn read_pem_certificates(path: &Path) -> CliResult<Vec<Vec<u8>>> {
+    let data = read_file(path)?;
+    let certs = x509_cert::Certificate::load_pem_chain(&data)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    if certs.is_empty() {
+        Err(format!("{}: no certificates found", path.display()))?
+    }
+
+    Ok(certs
+        .iter()
+        .map(|cert| cert.to_der())
+        .collect::<Result<_, _>>()?)
+}
+
+fn pem_text(data: &[u8]) -> Option<&str> {
+    std::str::from_utf8(data)
+        .ok()
+        .filter(|text| text.trim_start().starts_with("-----BEGIN "))
+}
+
+fn read_cose_key(path: &Path, data: &[u8]) -> CliResult<CoseKey<'static>> {
+    let cbor = decode(data, Encoding::Auto)?;
+    corim_rs::cbor::from_reader(cbor.as_slice())
+        .map_err(|e| format!("{}: expected a PEM file or a COSE_Key: {e}", path.display()).into())
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT
+
+//! # corim
+//!
+//! Command-line tool for working with Concise Reference Integrity Manifests (CoRIMs).
+//!
+//! Built when the `cli` feature is enabled:
+//!
+//! ```text
+//! cargo install corim-rs --features cli
+//! corim comid create --template comid.json
+//! corim create --template corim.json --comid comid.cbor --output manifest.cbor
+//! corim sign manifest.cbor --key signer.key --signer-name "ACME Inc." --kid acme-1 \
+//!     --validity ..1893456000 --output signed.cbor
+//! corim verify signed.cbor --trust-anchor root.pem
+//! corim appraise --refs signed.cbor --evidence evidence.json
+//! corim diff old.cbor signed.cbor
+//! corim extract supplier.cbor --output-dir tags/
+//! corim bundle tags/*.cbor --id platform-1 --entity "ACME Inc.:manifest-creator" \
+//!     --output platform.cbor
+//! corim inspect signed.cbor
+//! ```
+
+mod appraise;
+mod bundle;
+mod create;
+mod diff;
+mod input;
+mod inspect;
+mod key;
+mod sign;
+mod verify;
+
+use std::{io::Write, path::Path, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+
+/// Result type used by the command implementations
+pub type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// Inspect and manipulate CoRIM manifests
+#[derive(Debug, Parser)]
+#[command(name = "corim", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Decode a CoRIM and print a summary of its contents
+    Inspect(inspect::InspectArgs),
+    /// Compile an unsigned CoRIM from a JSON template
+    Create(create::CreateCorimArgs),
+    /// Sign an unsigned CoRIM, producing a COSE_Sign1 signed CoRIM
+    Sign(sign::SignArgs),
+    /// Verify the signatures of a signed CoRIM and their validity periods
+    Verify(verify::VerifyArgs),
+    /// Appraise evidence claims against the reference values of reference CoRIMs
+    Appraise(appraise::AppraiseArgs),
+    /// Compare two CoRIMs, reporting added, removed and modified tags, measurements and keys
+    Diff(diff::DiffArgs),
+    /// Write each tag of a CoRIM to its own file
+    Extract(bundle::ExtractArgs),
+    /// Assemble standalone tag files into a new unsigned CoRIM
+    Bundle(bundle::BundleArgs),
+    /// Work with CoMID tags
+    Comid {
+        #[command(subcommand)]
+        command: create::TagCommand,
+    },
+    /// Work with CoSWID tags
+    Coswid {
+        #[command(subcommand)]
+        command: create::TagCommand,
+    },
+    /// Work with CoTL tags
+    Cotl {
+        #[command(subcommand)]
+        command: create::TagCommand,
+    },
+}
+
+/// Writes `text` to standard output. A closed pipe (e.g. output piped into `head`) is not
+/// treated as an error.
+pub fn write_stdout(text: &str) -> CliResult<()> {
+    match std::io::stdout().lock().write_all(text.as_bytes()) {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Writes `data` to `path`, or to standard output if `path` is `-`
+pub fn write_output(path: &Path, data: &[u8]) -> CliResult<()> {
+    if path.as_os_str() == "-" {
+        std::io::stdout().lock().write_all(data)?;
+        Ok(())
+    } else {
+        std::fs::write(path, data)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()).into())
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Inspect(args) => inspect::run(args),
+        Command::Create(args) => create::run_corim(args),
+        Command::Sign(args) => sign::run(args),
+        Command::Verify(args) => verify::run(args),
+        Command::Appraise(args) => appraise::run(args),
+        Command::Diff(args) => diff::run(args),
+        Command::Extract(args) => bundle::run_extract(args),
+        Command::Bundle(args) => bundle::run_bundle(args),
+        Command::Comid { command } => create::run_tag(create::TagKind::Comid, command),
+        Command::Coswid { command } => create::run_tag(create::TagKind::Coswid, command),
+        Command::Cotl { command } => create::run_tag(create::TagKind::Cotl, command),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: MIT
+
+//! The `sign` command: wraps an unsigned CoRIM into a COSE_Sign1 signed CoRIM
+
+use std::path::PathBuf;
+
+use clap::Args;
+use corim_rs::{
+    core::{Bytes, Text},
+    corim::{
+        COSESign1Corim, ConciseRimTypeChoice, CorimMap, CorimMetaMap, CorimSignerMap,
+        ProtectedCorimHeaderMap, TaggedUnsignedCorimMap, UnprotectedCorimHeaderMap, ValidityMap,
+    },
+    cose::{CoseSigningKey, CoseVerifyingKey},
+};
+
+use crate::{
+    input::InputArgs,
+    key::{read_pem_certificates, read_signing_key},
+    write_output, CliResult,
+};
+
+/// Content type of the payload of a signed CoRIM
+const CONTENT_TYPE: &str = "application/rim+cbor";
+
+#[derive(Debug, Args)]
+pub struct SignArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Private key: a PKCS#8 PEM file or a CBOR encoded COSE_Key
+    #[arg(short, long)]
+    pub key: PathBuf,
+
+    /// Name of the signer, recorded in the protected header
+    #[arg(long)]
+    pub signer_name: String,
+
+    /// URI identifying the signer
+    #[arg(long)]
+    pub signer_uri: Option<String>,
+
+    /// Key identifier. Defaults to the `kid` of a COSE_Key.
+    #[arg(long)]
+    pub kid: Option<String>,
+
+    /// Validity period of the signature, as `[NOT_BEFORE]..NOT_AFTER` in seconds since the
+    /// Unix epoch
+    #[arg(long, value_parser = parse_validity)]
+    pub validity: Option<ValidityMap>,
+
+    /// PEM certificate chain of the signing key, leaf first, added as the x5chain header
+    #[arg(long)]
+    pub x5chain: Option<PathBuf>,
+
+    /// Output file, or `-` for standard output
+    #[arg(short, long, default_value = "-")]
+    pub output: PathBuf,
+}
+
+pub fn run(args: SignArgs) -> CliResult<()> {
+    let corim = match args.input.read_corim()? {
+        ConciseRimTypeChoice::TaggedUnsignedCorimMap(map) => map.as_ref().clone(),
+        _ => Err("the input is already signed, expected an unsigned CoRIM (tag 501)")?,
+    };
+
+    let key_file = read_signing_key(&args.key)?;
+    let kid = match (args.kid, key_file.kid) {
+        (Some(kid), _) => Bytes::from(kid.into_bytes()),
+        (None, Some(kid)) => kid,
+        (None, None) => Err("--kid is required unless the key is a COSE_Key with a kid")?,
+    };
+
+    let x5chain = args
+        .x5chain
+        .as_deref()
+        .map(read_pem_certificates)
+        .transpose()?;
+
+    let meta = CorimMetaMap {
+        signer: CorimSignerMap {
+            signer_name: args.signer_name.into(),
+            signer_uri: args.signer_uri.map(|uri| Text::from(uri).into()),
+            extension: None,
+        },
+        signature_validity: args.validity,
+    };
+
+    let signed = sign(corim, &key_file.key, kid, meta, x5chain)?;
+
+    let mut bytes = vec![];
+    ciborium::into_writer(
+        &ConciseRimTypeChoice::SignedCorim(signed.into()),
+        &mut bytes,
+    )?;
+    write_output(&args.output, &bytes)
+}
+
+/// Signs `corim` with `key`, checking that the leaf of `x5chain`, if any, certifies the key
+pub fn sign<'a>(
+    corim: CorimMap<'a>,
+    key: &CoseSigningKey,
+    kid: Bytes<'a>,
+    meta: CorimMetaMap<'a>,
+    x5chain: Option<Vec<Vec<u8>>>,
+) -> CliResult<COSESign1Corim<'a>> {
+    let mut protected = ProtectedCorimHeaderMap {
+        alg: i64::from(key.algorithm()).into(),
+        content_type: CONTENT_TYPE.into(),
+        kid,
+        corim_meta: meta,
+        cose_map: None,
+    };
+
+    if let Some(chain) = x5chain {
+        if CoseVerifyingKey::from_certificate_der(&chain[0])? != key.verifying_key() {
+            Err("the leaf certificate of the x5chain does not match the signing key")?
+        }
+        protected.set_x5chain(chain)?;
+    }
+
+    let mut signed = COSESign1Corim {
+        protected: protected.into(),
+        unprotected: UnprotectedCorimHeaderMap::default(),
+        payload: TaggedUnsignedCorimMap::new(corim).into(),
+        signature: Bytes::default().into(),
+    };
+    signed.sign(key)?;
+
+    Ok(signed)
+}
+
+/// Parses a validity period given as `[NOT_BEFORE]..NOT_AFTER`
+pub fn parse_validity(text: &str) -> Result<ValidityMap, String> {
+    let (not_before, not_after) = text
+        .split_once("..")
+        .ok_or("expected [NOT_BEFORE]..NOT_AFTER")?;
+    let time = |value: &str| {
+        value
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("invalid time {value:?}: {e}"))
+    };
+
+    let not_before = match not_before.trim() {
+        "" => None,
+        value => Some(time(value)?),
+    };
+    let not_after = time(not_after)?;
+
+    if not_before.is_some_and(|not_before| not_before > not_after) {
+        return Err("NOT_BEFORE is after NOT_AFTER".to_string());
+    }
+
+    Ok(ValidityMap::new(
+        not_before.map(|t| i128::from(t).into()),
+        i128::from(not_after).into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::decode_corim;
+
+    const UNSIGNED: &[u8] = include_bytes!("../../../testdata/corim/unsigned.cbor");
+    const LEAF_KEY: &str = include_str!("../../../testdata/x509/leaf.key");
+    const LEAF: &str = include_str!("../../../testdata/x509/leaf.pem");
+    const ROOT: &str = include_str!("../../../testdata/x509/root.pem");
+
+    #[test]
+    fn test_parse_validity() {
+        assert_eq!(
+            parse_validity("100..200").unwrap(),
+            ValidityMap::new(Some(100.into()), 200.into())
+        );
+        assert_eq!(
+            parse_validity("..200").unwrap(),
+            ValidityMap::new(None, 200.into())
+        );
+        assert!(parse_validity("200..100").is_err());
+        assert!(parse_validity("200").is_err());
+        assert!(parse_validity("..soon").is_err());
+    }
+
+    #[test]
+    fn test_sign() {
+        let ConciseRimTypeChoice::TaggedUnsignedCorimMap(corim) = decode_corim(UNSIGNED).unwrap()
+        else {
+            panic!("expected an unsigned CoRIM");
+        };
+        let corim = corim.as_ref().clone();
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let der = |pem: &str| x509_cert::der::pem::decode_vec(pem.as_bytes()).unwrap().1;
+        let meta = || CorimMetaMap {
+            signer: CorimSignerMap {
+                signer_name: "Test CoRIM Signer".into(),
+                signer_uri: None,
+                extension: None,
+            },
+            signature_validity: Some(ValidityMap::new(None, 2_000_000_000.into())),
+        };
+
+        let signed = sign(
+            corim.clone(),
+            &key,
+            Bytes::from(b"kid".to_vec()),
+            meta(),
+            Some(vec![der(LEAF)]),
+        )
+        .unwrap();
+
+        signed.verify(&key.verifying_key()).unwrap();
+        assert_eq!(signed.protected.content_type, Text::from(CONTENT_TYPE));
+        assert_eq!(signed.x5chain().unwrap().unwrap().len(), 1);
+        assert_eq!(signed.payload.as_ref(), &corim);
+
+        let err = sign(corim, &key, Bytes::default(), meta(), Some(vec![der(ROOT)])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the leaf certificate of the x5chain does not match the signing key"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: MIT
+
+//! The `verify` command: checks the signatures of a signed CoRIM and their validity periods
+
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use corim_rs::{
+    corim::ConciseRimTypeChoice,
+    cose::{SignerPolicy, VerifiedSigner, Verifier},
+    x509::TrustAnchorStore,
+};
+
+use crate::{
+    input::{read_file, InputArgs},
+    inspect::describe_bytes,
+    key::read_verifying_key,
+    write_stdout, CliResult,
+};
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Public key: a PEM public key or certificate, or a CBOR encoded COSE_Key (may be
+    /// repeated)
+    #[arg(
+        short,
+        long,
+        required_unless_present = "trust_anchor",
+        conflicts_with = "trust_anchor"
+    )]
+    pub key: Vec<PathBuf>,
+
+    /// PEM file of trust anchor certificates. Each signer is verified with the leaf of its
+    /// x5chain, which must chain to one of the anchors (may be repeated).
+    #[arg(long)]
+    pub trust_anchor: Vec<PathBuf>,
+
+    /// Whether any or all signers of a multi-signed CoRIM must verify
+    #[arg(long, value_enum, default_value_t = Policy::All)]
+    pub policy: Policy,
+
+    /// Payload of a detached signed CoRIM
+    #[arg(long)]
+    pub payload: Option<PathBuf>,
+
+    /// Time at which signature validity periods and certificates are checked, in seconds since
+    /// the Unix epoch. Defaults to the current time.
+    #[arg(long)]
+    pub time: Option<u64>,
+}
+
+/// Signer policy for multi-signed CoRIMs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Policy {
+    /// At least one signer must verify
+    Any,
+    /// Every signer must verify
+    All,
+}
+
+impl From<Policy> for SignerPolicy {
+    fn from(policy: Policy) -> Self {
+        match policy {
+            Policy::Any => SignerPolicy::AnyOf,
+            Policy::All => SignerPolicy::AllOf,
+        }
+    }
+}
+
+pub fn run(args: VerifyArgs) -> CliResult<()> {
+    let corim = args.input.read_corim()?;
+
+    let verifier = if args.trust_anchor.is_empty() {
+        Verifier::Keys(
+            args.key
+                .iter()
+                .map(|path| read_verifying_key(path))
+                .collect::<CliResult<_>>()?,
+        )
+    } else {
+        let mut anchors = TrustAnchorStore::new();
+        for path in &args.trust_anchor {
+            let pem = String::from_utf8(read_file(path)?)?;
+            anchors
+                .add_pem(&pem)
+                .map_err(|e| format!("{}: {e}", path.display()))?;
+        }
+        Verifier::TrustAnchors(anchors)
+    };
+
+    let payload = args.payload.as_deref().map(read_file).transpose()?;
+    let time = args.time.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    });
+
+    let signers = verify(&corim, &verifier, args.policy, time, payload.as_deref())?;
+
+    let mut output = String::new();
+    for signer in signers {
+        output.push_str(&format!(
+            "verified signer[{}]: {} (kid {})\n",
+            signer.index,
+            signer.meta.signer.signer_name,
+            describe_bytes(signer.kid)
+        ));
+    }
+    write_stdout(&output)
+}
+
+/// Verifies the signatures of `corim` at `time`, as [`ConciseRimTypeChoice::verify`] does.
+/// `payload` is the payload of a detached signed CoRIM.
+pub fn verify<'a>(
+    corim: &'a ConciseRimTypeChoice<'a>,
+    verifier: &Verifier,
+    policy: Policy,
+    time: u64,
+    payload: Option<&[u8]>,
+) -> CliResult<Vec<VerifiedSigner<'a>>> {
+    if matches!(corim, ConciseRimTypeChoice::DetachedSignedCorim(_)) && payload.is_none() {
+        Err("--payload is required to verify a detached signed CoRIM")?
+    }
+
+    Ok(corim.verify(verifier, policy.into(), time, payload)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input::decode_corim, sign::sign};
+    use corim_rs::{
+        core::{Bytes, Text},
+        corim::{CorimMetaMap, CorimSignerMap, ValidityMap},
+        cose::CoseSigningKey,
+    };
+
+    const UNSIGNED: &[u8] = include_bytes!("../../../testdata/corim/unsigned.cbor");
+    const LEAF_KEY: &str = include_str!("../../../testdata/x509/leaf.key");
+    const LEAF: &str = include_str!("../../../testdata/x509/leaf.pem");
+    const INTERMEDIATE: &str = include_str!("../../../testdata/x509/intermediate.pem");
+    const ROOT: &str = include_str!("../../../testdata/x509/root.pem");
+    const OTHER_ROOT: &str = include_str!("../../../testdata/x509/other-root.pem");
+
+    // 2025-01-01T00:00:00Z
+    const NOW: u64 = 1_735_689_600;
+
+    fn signed(validity: Option<ValidityMap>) -> ConciseRimTypeChoice<'static> {
+        let ConciseRimTypeChoice::TaggedUnsignedCorimMap(corim) = decode_corim(UNSIGNED).unwrap()
+        else {
+            panic!("expected an unsigned CoRIM");
+        };
+        let der = |pem: &str| x509_cert::der::pem::decode_vec(pem.as_bytes()).unwrap().1;
+        let meta = CorimMetaMap {
+            signer: CorimSignerMap {
+                signer_name: "Test CoRIM Signer".into(),
+                signer_uri: None,
+                extension: None,
+            },
+            signature_validity: validity,
+        };
+
+        let signed = sign(
+            corim.as_ref().clone(),
+            &CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap(),
+            Bytes::from(b"acme-signing-key".to_vec()),
+            meta,
+            Some(vec![der(LEAF), der(INTERMEDIATE)]),
+        )
+        .unwrap();
+
+        ConciseRimTypeChoice::SignedCorim(signed.into())
+    }
+
+    fn anchors(pem: &str) -> Verifier {
+        let mut anchors = TrustAnchorStore::new();
+        anchors.add_pem(pem).unwrap();
+        Verifier::TrustAnchors(anchors)
+    }
+
+    #[test]
+    fn test_verify_with_keys() {
+        let corim = signed(None);
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let other = CoseSigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+
+        let keys = Verifier::Keys(vec![other.verifying_key(), key.verifying_key()]);
+        let signers = verify(&corim, &keys, Policy::All, NOW, None).unwrap();
+        assert_eq!(signers.len(), 1);
+        assert_eq!(
+            signers[0].meta.signer.signer_name,
+            Text::from("Test CoRIM Signer")
+        );
+
+        let keys = Verifier::Keys(vec![other.verifying_key()]);
+        assert!(verify(&corim, &keys, Policy::All, NOW, None).is_err());
+
+        let unsigned = decode_corim(UNSIGNED).unwrap();
+        let err = verify(&unsigned, &keys, Policy::All, NOW, None).unwrap_err();
+        assert_eq!(err.to_string(), "the CoRIM is not signed");
+    }
+
+    #[test]
+    fn test_verify_with_trust_anchors() {
+        let corim = signed(None);
+
+        assert_eq!(
+            verify(&corim, &anchors(ROOT), Policy::All, NOW, None)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(verify(&corim, &anchors(OTHER_ROOT), Policy::All, NOW, None).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_validity() {
+        let corim = signed(Some(ValidityMap::new(Some(NOW.into()), (NOW + 10).into())));
+        let keys = Verifier::Keys(vec![CoseSigningKey::from_pkcs8_pem(LEAF_KEY)
+            .unwrap()
+            .verifying_key()]);
+
+        assert!(verify(&corim, &keys, Policy::All, NOW + 10, None).is_ok());
+
+        let err = verify(&corim, &keys, Policy::All, NOW + 11, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the signature of signer[0] is not valid at 1735689611 \
+             (signature validity 1735689600..1735689610)"
+        );
+    }
+}
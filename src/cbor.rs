@@ -0,0 +1,1151 @@
+// SPDX-License-Identifier: MIT
+
+//! CBOR decoding that borrows text and byte strings from its input
+//!
+//! [`from_slice`] decodes a value from a byte slice. Text, byte strings and the identifiers
+//! built from them, such as UEIDs and digests, point into the slice rather than being copied,
+//! so the value borrows the slice. [`IntoOwned`](crate::owned::IntoOwned) copies them out when
+//! the value has to outlive its input. Strings encoded with indefinite lengths are split into
+//! chunks and are always copied.
+//!
+//! [`from_reader`] decodes from an `io::Read`, copying every string, and returns values that
+//! are not tied to any buffer.
+//!
+//! Both follow the serde data model of `ciborium`, so types that use `ciborium::tag` or
+//! `ciborium::Value` decode as they do with `ciborium::from_reader`. As with `ciborium`, bytes
+//! after the first data item are not read.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//!
+//! use corim_rs::{cbor, comid::TagIdTypeChoice};
+//!
+//! let bytes = [0x65, b't', b'a', b'g', b'-', b'1'];
+//! let tag_id: TagIdTypeChoice = cbor::from_slice(&bytes).unwrap();
+//! assert!(matches!(tag_id, TagIdTypeChoice::Tstr(Cow::Borrowed("tag-1"))));
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::str;
+
+use serde::de::{self, value::SeqDeserializer, Deserialize};
+
+use crate::{CborError, Result};
+
+/// Nesting depth of arrays, maps and tags beyond which decoding fails, as in `ciborium`
+const RECURSION_LIMIT: usize = 256;
+
+const TAG_BIGNUM_POS: u64 = 2;
+const TAG_BIGNUM_NEG: u64 = 3;
+
+const SIMPLE_FALSE: u8 = 20;
+const SIMPLE_TRUE: u8 = 21;
+const SIMPLE_NULL: u8 = 22;
+const SIMPLE_UNDEFINED: u8 = 23;
+
+/// Decodes a value from CBOR, borrowing its text and byte strings from `bytes`
+pub fn from_slice<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T> {
+    let mut deserializer = Deserializer::new(SliceInput {
+        data: bytes,
+        offset: 0,
+    });
+    Ok(T::deserialize(&mut deserializer)?)
+}
+
+/// Decodes a value from CBOR read from `reader`, copying its text and byte strings
+///
+/// The value owns all of its data, so it can be given any lifetime, including `'static`.
+#[cfg(feature = "std")]
+pub fn from_reader<T: Deserialize<'static>, R: std::io::Read>(reader: R) -> Result<T> {
+    let mut deserializer = Deserializer::new(ReaderInput {
+        reader,
+        offset: 0,
+        scratch: Vec::new(),
+    });
+    Ok(T::deserialize(&mut deserializer)?)
+}
+
+/// Decodes a value from CBOR in `bytes` as [`from_reader`] does, copying its text and byte
+/// strings, for input that does not live as long as the value
+pub(crate) fn from_slice_owned<'de, T: Deserialize<'de>>(bytes: &[u8]) -> Result<T> {
+    let mut deserializer = Deserializer::new(CopiedInput(SliceInput {
+        data: bytes,
+        offset: 0,
+    }));
+    Ok(T::deserialize(&mut deserializer)?)
+}
+
+/// Bytes read from an [`Input`], borrowed from the input if it is held in memory
+enum Reference<'de, 's> {
+    Borrowed(&'de [u8]),
+    Copied(&'s [u8]),
+}
+
+/// A source of CBOR bytes
+trait Input<'de> {
+    /// Returns the number of bytes read so far
+    fn offset(&self) -> usize;
+
+    /// Fills `buf` with the next bytes
+    fn read_exact(&mut self, buf: &mut [u8]) -> core::result::Result<(), CborError>;
+
+    /// Reads the next `len` bytes
+    fn read<'s>(&'s mut self, len: usize) -> core::result::Result<Reference<'de, 's>, CborError>;
+}
+
+struct SliceInput<'de> {
+    data: &'de [u8],
+    offset: usize,
+}
+
+impl<'de> SliceInput<'de> {
+    fn take(&mut self, len: usize) -> core::result::Result<&'de [u8], CborError> {
+        let data = self.data;
+        let bytes = data
+            .get(self.offset..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or(CborError::Truncated(self.offset))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+}
+
+impl<'de> Input<'de> for SliceInput<'de> {
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> core::result::Result<(), CborError> {
+        buf.copy_from_slice(self.take(buf.len())?);
+        Ok(())
+    }
+
+    fn read<'s>(&'s mut self, len: usize) -> core::result::Result<Reference<'de, 's>, CborError> {
+        self.take(len).map(Reference::Borrowed)
+    }
+}
+
+/// A slice whose bytes are handed out as copies, which values may not borrow
+struct CopiedInput<'s>(SliceInput<'s>);
+
+impl<'de> Input<'de> for CopiedInput<'_> {
+    fn offset(&self) -> usize {
+        self.0.offset
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> core::result::Result<(), CborError> {
+        self.0.read_exact(buf)
+    }
+
+    fn read<'s>(&'s mut self, len: usize) -> core::result::Result<Reference<'de, 's>, CborError> {
+        self.0.take(len).map(Reference::Copied)
+    }
+}
+
+#[cfg(feature = "std")]
+struct ReaderInput<R> {
+    reader: R,
+    offset: usize,
+    scratch: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<'de, R: std::io::Read> Input<'de> for ReaderInput<R> {
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> core::result::Result<(), CborError> {
+        self.reader.read_exact(buf).map_err(|e| match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => CborError::Truncated(self.offset),
+            _ => CborError::Io(e.to_string()),
+        })?;
+        self.offset += buf.len();
+        Ok(())
+    }
+
+    fn read<'s>(&'s mut self, len: usize) -> core::result::Result<Reference<'de, 's>, CborError> {
+        use std::io::Read;
+
+        // The length comes from the input, so the buffer only grows as the bytes arrive
+        self.scratch.clear();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut self.scratch)
+            .map_err(|e| CborError::Io(e.to_string()))?;
+        if self.scratch.len() < len {
+            return Err(CborError::Truncated(self.offset));
+        }
+        self.offset += len;
+        Ok(Reference::Copied(&self.scratch))
+    }
+}
+
+/// The initial bytes of a CBOR data item
+#[derive(Debug, Clone, Copy)]
+enum Header {
+    Positive(u64),
+    Negative(u64),
+    Bytes(Option<usize>),
+    Text(Option<usize>),
+    Array(Option<usize>),
+    Map(Option<usize>),
+    Tag(u64),
+    Simple(u8),
+    Float(f64),
+    Break,
+}
+
+impl Header {
+    fn expected(self, kind: &'static str) -> CborError {
+        de::Error::invalid_type(
+            match self {
+                Self::Positive(x) => de::Unexpected::Unsigned(x),
+                Self::Negative(x) => de::Unexpected::Signed(x as i64 ^ !0),
+                Self::Bytes(..) => de::Unexpected::Other("bytes"),
+                Self::Text(..) => de::Unexpected::Other("string"),
+                Self::Array(..) => de::Unexpected::Seq,
+                Self::Map(..) => de::Unexpected::Map,
+                Self::Tag(..) => de::Unexpected::Other("tag"),
+                Self::Simple(SIMPLE_FALSE) => de::Unexpected::Bool(false),
+                Self::Simple(SIMPLE_TRUE) => de::Unexpected::Bool(true),
+                Self::Simple(SIMPLE_NULL) => de::Unexpected::Other("null"),
+                Self::Simple(SIMPLE_UNDEFINED) => de::Unexpected::Other("undefined"),
+                Self::Simple(..) => de::Unexpected::Other("simple"),
+                Self::Float(x) => de::Unexpected::Float(x),
+                Self::Break => de::Unexpected::Other("break"),
+            },
+            &kind,
+        )
+    }
+}
+
+/// Converts the bits of a half precision float
+fn f16_to_f64(bits: u16) -> f64 {
+    let sign = u32::from(bits & 0x8000) << 16;
+    let exponent = u32::from((bits >> 10) & 0x1f);
+    let fraction = u32::from(bits & 0x3ff);
+
+    let single = match exponent {
+        0 => {
+            // Subnormal values are fraction * 2^-24, which is exact as an f32
+            let value = fraction as f32 / 16_777_216.0;
+            let value = if sign == 0 { value } else { -value };
+            return value.into();
+        }
+        0x1f => sign | 0x7f80_0000 | (fraction << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (fraction << 13),
+    };
+    f32::from_bits(single).into()
+}
+
+/// The contents of a byte or text string
+enum Content<'de, 's> {
+    Reference(Reference<'de, 's>),
+    Owned(Vec<u8>),
+}
+
+impl Content<'_, '_> {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Reference(Reference::Borrowed(bytes)) => bytes,
+            Self::Reference(Reference::Copied(bytes)) => bytes,
+            Self::Owned(bytes) => bytes,
+        }
+    }
+}
+
+struct Deserializer<I> {
+    input: I,
+    peeked: Option<(Header, usize)>,
+    depth: usize,
+}
+
+impl<'de, I: Input<'de>> Deserializer<I> {
+    fn new(input: I) -> Self {
+        Self {
+            input,
+            peeked: None,
+            depth: RECURSION_LIMIT,
+        }
+    }
+
+    /// Returns the offset of the next item
+    fn offset(&self) -> usize {
+        match self.peeked {
+            Some((_, offset)) => offset,
+            None => self.input.offset(),
+        }
+    }
+
+    fn byte(&mut self) -> core::result::Result<u8, CborError> {
+        let mut buf = [0u8; 1];
+        self.input.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn argument(&mut self, info: u8) -> core::result::Result<Option<u64>, CborError> {
+        let mut buf = [0u8; 8];
+        let len = match info {
+            0..=23 => return Ok(Some(info.into())),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            31 => return Ok(None),
+            _ => return Err(CborError::Syntax(self.input.offset() - 1)),
+        };
+        self.input.read_exact(&mut buf[8 - len..])?;
+        Ok(Some(u64::from_be_bytes(buf)))
+    }
+
+    fn pull(&mut self) -> core::result::Result<Header, CborError> {
+        if let Some((header, _)) = self.peeked.take() {
+            return Ok(header);
+        }
+
+        let offset = self.input.offset();
+        let initial = self.byte()?;
+        let info = initial & 0x1f;
+        let argument = self.argument(info)?;
+        let len = |argument: Option<u64>| match argument {
+            Some(len) => usize::try_from(len)
+                .map(Some)
+                .map_err(|_| CborError::Syntax(offset)),
+            None => Ok(None),
+        };
+
+        Ok(match (initial >> 5, argument) {
+            (0, Some(x)) => Header::Positive(x),
+            (1, Some(x)) => Header::Negative(x),
+            (2, _) => Header::Bytes(len(argument)?),
+            (3, _) => Header::Text(len(argument)?),
+            (4, _) => Header::Array(len(argument)?),
+            (5, _) => Header::Map(len(argument)?),
+            (6, Some(x)) => Header::Tag(x),
+            (7, Some(x)) => match info {
+                0..=23 => Header::Simple(info),
+                // Simple values below 32 must use the short form
+                24 if x >= 32 => Header::Simple(x as u8),
+                25 => Header::Float(f16_to_f64(x as u16)),
+                26 => Header::Float(f32::from_bits(x as u32).into()),
+                27 => Header::Float(f64::from_bits(x)),
+                _ => return Err(CborError::Syntax(offset)),
+            },
+            (7, None) => Header::Break,
+            _ => return Err(CborError::Syntax(offset)),
+        })
+    }
+
+    fn push(&mut self, header: Header, offset: usize) {
+        self.peeked = Some((header, offset));
+    }
+
+    fn peek(&mut self) -> core::result::Result<Header, CborError> {
+        let offset = self.offset();
+        let header = self.pull()?;
+        self.push(header, offset);
+        Ok(header)
+    }
+
+    fn recurse<V>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> core::result::Result<V, CborError>,
+    ) -> core::result::Result<V, CborError> {
+        if self.depth == 0 {
+            return Err(CborError::RecursionLimitExceeded);
+        }
+
+        self.depth -= 1;
+        let result = f(self);
+        self.depth += 1;
+        result
+    }
+
+    /// Reads the contents of a string whose header has been read, joining the chunks of an
+    /// indefinite length string
+    fn content(
+        &mut self,
+        header: Header,
+        len: Option<usize>,
+    ) -> core::result::Result<Content<'de, '_>, CborError> {
+        if let Some(len) = len {
+            return self.input.read(len).map(Content::Reference);
+        }
+
+        let mut buffer = Vec::new();
+        loop {
+            let offset = self.input.offset();
+            let chunk = match (header, self.pull()?) {
+                (_, Header::Break) => return Ok(Content::Owned(buffer)),
+                (Header::Bytes(..), Header::Bytes(Some(len)))
+                | (Header::Text(..), Header::Text(Some(len))) => len,
+                _ => return Err(CborError::Syntax(offset)),
+            };
+            buffer.extend_from_slice(Content::Reference(self.input.read(chunk)?).as_slice());
+        }
+    }
+
+    fn visit_byte_string<V: de::Visitor<'de>>(
+        &mut self,
+        len: Option<usize>,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        match self.content(Header::Bytes(len), len)? {
+            Content::Reference(Reference::Borrowed(bytes)) => visitor.visit_borrowed_bytes(bytes),
+            Content::Reference(Reference::Copied(bytes)) => visitor.visit_bytes(bytes),
+            Content::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
+    }
+
+    fn visit_text_string<V: de::Visitor<'de>>(
+        &mut self,
+        len: Option<usize>,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        let offset = self.input.offset();
+        match self.content(Header::Text(len), len)? {
+            Content::Reference(Reference::Borrowed(bytes)) => match str::from_utf8(bytes) {
+                Ok(text) => visitor.visit_borrowed_str(text),
+                Err(_) => Err(CborError::Syntax(offset)),
+            },
+            Content::Reference(Reference::Copied(bytes)) => match str::from_utf8(bytes) {
+                Ok(text) => visitor.visit_str(text),
+                Err(_) => Err(CborError::Syntax(offset)),
+            },
+            Content::Owned(bytes) => match String::from_utf8(bytes) {
+                Ok(text) => visitor.visit_string(text),
+                Err(_) => Err(CborError::Syntax(offset)),
+            },
+        }
+    }
+
+    /// Reads an integer, including bignums of up to 128 bits, skipping any other tags
+    fn integer(&mut self) -> core::result::Result<(bool, u128), CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Positive(x) => Ok((false, x.into())),
+                Header::Negative(x) => Ok((true, x.into())),
+                Header::Tag(TAG_BIGNUM_POS) => Ok((false, self.bignum()?)),
+                Header::Tag(TAG_BIGNUM_NEG) => Ok((true, self.bignum()?)),
+                Header::Tag(..) => continue,
+                header => Err(header.expected("integer")),
+            };
+        }
+    }
+
+    /// Reads the byte string of a bignum whose tag has been read
+    fn bignum(&mut self) -> core::result::Result<u128, CborError> {
+        let len = match self.pull()? {
+            Header::Bytes(len) => len,
+            header => return Err(header.expected("bytes")),
+        };
+
+        let mut value = [0u8; 16];
+        let mut index = 0;
+        for &byte in self.content(Header::Bytes(len), len)?.as_slice() {
+            match index {
+                // Leading zeros do not count towards the size of the bignum
+                0 if byte == 0 => continue,
+                16 => return Err(de::Error::custom("bigint too large")),
+                _ => value[index] = byte,
+            }
+            index += 1;
+        }
+
+        value[..index].reverse();
+        Ok(u128::from_le_bytes(value))
+    }
+}
+
+impl<'de, I: Input<'de>> de::Deserializer<'de> for &mut Deserializer<I> {
+    type Error = CborError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        match self.peek()? {
+            Header::Positive(..) => self.deserialize_u64(visitor),
+            Header::Negative(x) => match i64::try_from(x) {
+                Ok(..) => self.deserialize_i64(visitor),
+                Err(..) => self.deserialize_i128(visitor),
+            },
+            Header::Bytes(..) => self.deserialize_byte_buf(visitor),
+            Header::Text(..) => self.deserialize_string(visitor),
+            Header::Array(..) => self.deserialize_seq(visitor),
+            Header::Map(..) => self.deserialize_map(visitor),
+
+            Header::Tag(tag) => {
+                self.pull()?;
+                let len = match self.peek()? {
+                    Header::Bytes(len) => len,
+                    _ => None,
+                };
+
+                match (tag, len) {
+                    (TAG_BIGNUM_POS, Some(len)) if len <= 16 => visitor.visit_u128(self.bignum()?),
+                    (TAG_BIGNUM_NEG, Some(len)) if len <= 16 => {
+                        match i128::try_from(self.bignum()?) {
+                            Ok(x) => visitor.visit_i128(x ^ !0),
+                            Err(..) => Err(de::Error::custom("integer too large")),
+                        }
+                    }
+                    _ => self.recurse(|me| visitor.visit_enum(TagAccess::new(me, Some(tag)))),
+                }
+            }
+
+            Header::Float(..) => self.deserialize_f64(visitor),
+            Header::Simple(SIMPLE_FALSE | SIMPLE_TRUE) => self.deserialize_bool(visitor),
+            Header::Simple(SIMPLE_NULL | SIMPLE_UNDEFINED) => self.deserialize_option(visitor),
+            header @ Header::Simple(..) => Err(header.expected("known simple value")),
+            header @ Header::Break => Err(header.expected("non-break")),
+        }
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            let offset = self.offset();
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Simple(SIMPLE_FALSE) => visitor.visit_bool(false),
+                Header::Simple(SIMPLE_TRUE) => visitor.visit_bool(true),
+                _ => Err(CborError::semantic(offset, "expected bool")),
+            };
+        }
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Float(x) => visitor.visit_f64(x),
+                header => Err(header.expected("float")),
+            };
+        }
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_i64(visitor)
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        let result = match self.integer()? {
+            (false, raw) => i64::try_from(raw),
+            (true, raw) => i64::try_from(raw).map(|x| x ^ !0),
+        };
+
+        match result {
+            Ok(x) => visitor.visit_i64(x),
+            Err(..) => Err(de::Error::custom("integer too large")),
+        }
+    }
+
+    fn deserialize_i128<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        let result = match self.integer()? {
+            (false, raw) => i128::try_from(raw),
+            (true, raw) => i128::try_from(raw).map(|x| x ^ !0),
+        };
+
+        match result {
+            Ok(x) => visitor.visit_i128(x),
+            Err(..) => Err(de::Error::custom("integer too large")),
+        }
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_u64(visitor)
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        match self.integer()? {
+            (false, raw) => match u64::try_from(raw) {
+                Ok(x) => visitor.visit_u64(x),
+                Err(..) => Err(de::Error::custom("integer too large")),
+            },
+            (true, ..) => Err(de::Error::custom("unexpected negative integer")),
+        }
+    }
+
+    fn deserialize_u128<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        match self.integer()? {
+            (false, raw) => visitor.visit_u128(raw),
+            (true, ..) => Err(de::Error::custom("unexpected negative integer")),
+        }
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            let offset = self.offset();
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                header @ Header::Text(Some(len)) if len <= 4 => {
+                    let mut buf = [0u8; 4];
+                    self.input.read_exact(&mut buf[..len])?;
+                    let text =
+                        str::from_utf8(&buf[..len]).map_err(|_| CborError::Syntax(offset))?;
+                    let mut chars = text.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => visitor.visit_char(c),
+                        _ => Err(header.expected("char")),
+                    }
+                }
+                header => Err(header.expected("char")),
+            };
+        }
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Text(len) => self.visit_text_string(len, visitor),
+                header => Err(header.expected("str")),
+            };
+        }
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Bytes(len) => self.visit_byte_string(len, visitor),
+                Header::Array(len) => self.recurse(|me| visitor.visit_seq(Access(me, len))),
+                header => Err(header.expected("bytes")),
+            };
+        }
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        let offset = self.offset();
+        match self.pull()? {
+            Header::Simple(SIMPLE_NULL | SIMPLE_UNDEFINED) => visitor.visit_none(),
+            header => {
+                self.push(header, offset);
+                visitor.visit_some(self)
+            }
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Simple(SIMPLE_NULL | SIMPLE_UNDEFINED) => visitor.visit_unit(),
+                Header::Tag(..) => continue,
+                header => Err(header.expected("unit")),
+            };
+        }
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Array(len) => self.recurse(|me| visitor.visit_seq(Access(me, len))),
+                // Byte strings are sequences of bytes, as for `Vec<u8>`
+                Header::Bytes(len) => {
+                    let content = self.content(Header::Bytes(len), len)?;
+                    visitor.visit_seq(SeqDeserializer::new(content.as_slice().iter().copied()))
+                }
+                header => Err(header.expected("array")),
+            };
+        }
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Map(len) => self.recurse(|me| visitor.visit_map(Access(me, len))),
+                header => Err(header.expected("map")),
+            };
+        }
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        // The name `ciborium::tag` types use to ask for the tag of the next item
+        if name == "@@TAG@@" {
+            let offset = self.offset();
+            let tag = match self.pull()? {
+                Header::Tag(tag) => Some(tag),
+                header => {
+                    self.push(header, offset);
+                    None
+                }
+            };
+            return self.recurse(|me| visitor.visit_enum(TagAccess::new(me, tag)));
+        }
+
+        loop {
+            let offset = self.offset();
+            match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Map(Some(1)) => (),
+                header @ Header::Text(..) => self.push(header, offset),
+                header => return Err(header.expected("enum")),
+            }
+
+            return self.recurse(|me| visitor.visit_enum(Access(me, Some(0))));
+        }
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        loop {
+            return match self.pull()? {
+                Header::Tag(..) => continue,
+                Header::Text(len) => self.visit_text_string(len, visitor),
+                Header::Bytes(len) => self.visit_byte_string(len, visitor),
+                header => Err(header.expected("str or bytes")),
+            };
+        }
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Access to the items of an array or map, or to an externally tagged enum
+struct Access<'a, I>(&'a mut Deserializer<I>, Option<usize>);
+
+impl<'de, I: Input<'de>> Access<'_, I> {
+    /// Counts down the items, returning false at the end of the array or map
+    fn next(&mut self) -> core::result::Result<bool, CborError> {
+        match self.1 {
+            Some(0) => Ok(false),
+            Some(len) => {
+                self.1 = Some(len - 1);
+                Ok(true)
+            }
+            None => match self.0.peek()? {
+                Header::Break => {
+                    self.0.pull()?;
+                    Ok(false)
+                }
+                _ => Ok(true),
+            },
+        }
+    }
+}
+
+impl<'de, I: Input<'de>> de::SeqAccess<'de> for Access<'_, I> {
+    type Error = CborError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> core::result::Result<Option<T::Value>, CborError> {
+        match self.next()? {
+            true => seed.deserialize(&mut *self.0).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.1
+    }
+}
+
+impl<'de, I: Input<'de>> de::MapAccess<'de> for Access<'_, I> {
+    type Error = CborError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> core::result::Result<Option<K::Value>, CborError> {
+        match self.next()? {
+            true => seed.deserialize(&mut *self.0).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        seed.deserialize(&mut *self.0)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.1
+    }
+}
+
+impl<'de, I: Input<'de>> de::EnumAccess<'de> for Access<'_, I> {
+    type Error = CborError;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> core::result::Result<(V::Value, Self), CborError> {
+        let variant = seed.deserialize(&mut *self.0)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, I: Input<'de>> de::VariantAccess<'de> for Access<'_, I> {
+    type Error = CborError;
+
+    fn unit_variant(self) -> core::result::Result<(), CborError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> core::result::Result<T::Value, CborError> {
+        seed.deserialize(&mut *self.0)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        de::Deserializer::deserialize_any(&mut *self.0, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        de::Deserializer::deserialize_any(&mut *self.0, visitor)
+    }
+}
+
+/// Access to a tagged item as the `@@TAGGED@@` or `@@UNTAGGED@@` variant of an enum, whose
+/// fields are the tag and the item, as `ciborium::tag` types expect
+struct TagAccess<'a, I> {
+    parent: Option<&'a mut Deserializer<I>>,
+    state: usize,
+    tag: Option<u64>,
+}
+
+impl<'a, I> TagAccess<'a, I> {
+    fn new(parent: &'a mut Deserializer<I>, tag: Option<u64>) -> Self {
+        Self {
+            parent: Some(parent),
+            state: 0,
+            tag,
+        }
+    }
+}
+
+impl<'de, I: Input<'de>> de::Deserializer<'de> for &mut TagAccess<'_, I> {
+    type Error = CborError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        self.state += 1;
+        match (self.state, self.tag) {
+            (1, Some(_)) => visitor.visit_str("@@TAGGED@@"),
+            (1, None) => visitor.visit_str("@@UNTAGGED@@"),
+            (_, Some(tag)) => visitor.visit_u64(tag),
+            (_, None) => Err(de::Error::custom("expected tag")),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+impl<'de, I: Input<'de>> de::EnumAccess<'de> for TagAccess<'_, I> {
+    type Error = CborError;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        mut self,
+        seed: V,
+    ) -> core::result::Result<(V::Value, Self), CborError> {
+        let variant = seed.deserialize(&mut self)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, I: Input<'de>> de::VariantAccess<'de> for TagAccess<'_, I> {
+    type Error = CborError;
+
+    fn unit_variant(self) -> core::result::Result<(), CborError> {
+        Err(de::Error::custom("expected tag"))
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(
+        mut self,
+        seed: T,
+    ) -> core::result::Result<T::Value, CborError> {
+        match self.parent.take() {
+            Some(parent) => seed.deserialize(parent),
+            None => Err(de::Error::custom("expected tag")),
+        }
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        visitor.visit_seq(self)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> core::result::Result<V::Value, CborError> {
+        Err(de::Error::custom("expected tag"))
+    }
+}
+
+impl<'de, I: Input<'de>> de::SeqAccess<'de> for TagAccess<'_, I> {
+    type Error = CborError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> core::result::Result<Option<T::Value>, CborError> {
+        if self.state < 2 {
+            return seed.deserialize(&mut *self).map(Some);
+        }
+
+        match self.parent.take() {
+            Some(parent) => seed.deserialize(parent).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip::macros(vec)]
+mod tests {
+    use super::*;
+    use ciborium::Value;
+
+    #[test]
+    fn test_from_slice_matches_ciborium() {
+        let items: Vec<Vec<u8>> = vec![
+            vec![0x00],
+            vec![0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            vec![0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            vec![0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0xc3, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+            vec![0xf9, 0x3c, 0x00],
+            vec![0xf9, 0x00, 0x01],
+            vec![0xf9, 0x7c, 0x00],
+            vec![0xfa, 0x47, 0xc3, 0x50, 0x00],
+            vec![0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a],
+            vec![0xf4, 0xf5, 0xf6],
+            vec![0x5f, 0x42, 1, 2, 0x41, 3, 0xff],
+            vec![0x7f, 0x61, b'a', 0x62, b'b', b'c', 0xff],
+            vec![0x9f, 0x01, 0xbf, 0x61, b'k', 0x80, 0xff, 0xff],
+            vec![0xd8, 0x25, 0x82, 0x01, 0xd9, 0x02, 0x28, 0x43, 1, 2, 3],
+            vec![0xa2, 0x01, 0x62, b'h', b'i', 0x20, 0x44, 1, 2, 3, 4],
+        ];
+
+        for item in items {
+            let expected: Value = ciborium::from_reader(item.as_slice()).unwrap();
+            assert_eq!(from_slice::<Value>(&item).unwrap(), expected);
+            assert_eq!(from_reader::<Value, _>(item.as_slice()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_from_slice_borrows() {
+        let bytes = [0x82, 0x63, b'a', b'b', b'c', 0x42, 1, 2];
+        let (text, data): (&str, &[u8]) = from_slice(&bytes).unwrap();
+        assert_eq!(text, "abc");
+        assert_eq!(data, [1, 2]);
+        assert!(core::ptr::eq(text.as_ptr(), &bytes[2]));
+        assert!(core::ptr::eq(data.as_ptr(), &bytes[6]));
+
+        // Indefinite length strings are copied from their chunks
+        let bytes = [0x7f, 0x61, b'a', 0x61, b'b', 0xff];
+        assert!(from_slice::<&str>(&bytes).is_err());
+        assert_eq!(from_slice::<String>(&bytes).unwrap(), "ab");
+    }
+
+    #[test]
+    fn test_from_slice_errors() {
+        let err = from_slice::<Value>(&[0x63, b'a']).unwrap_err();
+        assert_eq!(err.to_string(), "CBOR input ends within the item at byte 1");
+        let err = from_reader::<Value, _>([0x63, b'a'].as_slice()).unwrap_err();
+        assert_eq!(err.to_string(), "CBOR input ends within the item at byte 1");
+
+        let err = from_slice::<Value>(&[0x1c]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid CBOR at byte 0");
+        let err = from_slice::<Value>(&[0x62, 0xff, 0xfe]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid CBOR at byte 1");
+        let err = from_slice::<Value>(&[0x7f, 0x41, 0x00, 0xff]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid CBOR at byte 1");
+
+        let err = from_slice::<u64>(&[0x20]).unwrap_err();
+        assert_eq!(err.to_string(), "unexpected negative integer");
+        let err = from_slice::<bool>(&[0x00]).unwrap_err();
+        assert_eq!(err.to_string(), "expected bool at byte 0");
+
+        let nested = [0x81; 300];
+        assert!(matches!(
+            from_slice::<Value>(&nested),
+            Err(crate::Error::Cbor(CborError::RecursionLimitExceeded))
+        ));
+    }
+}
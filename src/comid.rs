@@ -85,17 +85,33 @@
 //!
 //! All components support optional extensions through [`ExtensionMap`] for future expandability.
 
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    core::{deserialize_option_text, deserialize_text, json_typed_value, serialize_json_typed},
+    empty_map_as_none, generate_tagged, AttestKeyTripleRecord, ComidError,
+    ConditionalEndorsementSeriesTripleRecord, ConditionalEndorsementTripleRecord,
+    CoswidTripleRecord, DomainDependencyTripleRecord, DomainMembershipTripleRecord,
+    EndorsedTripleRecord, ExtensionMap, IdentityTripleRecord, ReferenceTripleRecord, Result, Text,
+    Tstr, Uint, Uri, UuidType,
+};
+#[cfg(feature = "std")]
 use crate::{
     core::{RawValueType, TaggedBytes},
-    empty_map_as_none, generate_tagged,
     triples::{EnvironmentMap, MeasuredElementTypeChoice, MeasurementMap, MeasurementValuesMap},
-    AttestKeyTripleRecord, ComidError, ConditionalEndorsementSeriesTripleRecord,
-    ConditionalEndorsementTripleRecord, CoswidTripleRecord, DomainDependencyTripleRecord,
-    DomainMembershipTripleRecord, EndorsedTripleRecord, ExtensionMap, IdentityTripleRecord,
-    ReferenceTripleRecord, Result, Text, Tstr, Uint, Uri, UuidType,
 };
+use core::fmt;
 use derive_more::{Constructor, From, TryFrom};
-use serde::{ser::SerializeMap, Deserialize, Serialize};
+use serde::{
+    de::{self, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
 /// A tag version number represented as an unsigned integer
 pub type TagVersionType = Uint;
@@ -116,26 +132,28 @@ generate_tagged!((
 pub struct ConciseMidTag<'a> {
     /// Optional language identifier for the tag content
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub language: Option<Text<'a>>,
     /// Identity information for this tag
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
     pub tag_identity: TagIdentityMap<'a>,
     /// List of entities associated with this tag
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "2")]
+    #[serde(borrow, rename = "2")]
     pub entities: Option<Vec<ComidEntityMap<'a>>>,
     /// Optional references to other related tags
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "3")]
+    #[serde(borrow, rename = "3")]
     pub linked_tags: Option<Vec<LinkedTagMap<'a>>>,
     /// Collection of triples describing the module
-    #[serde(rename = "4")]
+    #[serde(borrow, rename = "4")]
     pub triples: TriplesMap<'a>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
 }
 
@@ -197,6 +215,7 @@ impl<'a> ConciseMidTag<'a> {
     /// comid.add_reference_raw_value(&env, mkey, &reference_data)
     ///     .expect("Failed to add reference value");
     /// ```
+    #[cfg(feature = "std")]
     pub fn add_reference_raw_value<T>(
         &mut self,
         environment: &EnvironmentMap<'a>,
@@ -208,7 +227,7 @@ impl<'a> ConciseMidTag<'a> {
     {
         let mut raw_bytes = vec![];
         ciborium::into_writer(value, &mut raw_bytes)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
         let raw_value = TaggedBytes::new(raw_bytes.into());
 
         let measurement = MeasurementMap {
@@ -286,6 +305,7 @@ impl<'a> ConciseMidTag<'a> {
     /// comid.add_endorsement_raw_value(&env, mkey, &endorsement_data)
     ///     .expect("Failed to add endorsement value");
     /// ```
+    #[cfg(feature = "std")]
     pub fn add_endorsement_raw_value<T>(
         &mut self,
         environment: &EnvironmentMap<'a>,
@@ -297,7 +317,7 @@ impl<'a> ConciseMidTag<'a> {
     {
         let mut raw_bytes = vec![];
         ciborium::into_writer(value, &mut raw_bytes)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
         let raw_value = TaggedBytes::new(raw_bytes.into());
 
         let measurement = MeasurementMap {
@@ -344,7 +364,7 @@ impl<'a> ConciseMidTag<'a> {
 #[repr(C)]
 pub struct TagIdentityMap<'a> {
     /// Unique identifier for the tag
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub tag_id: TagIdTypeChoice<'a>,
     /// Optional version number for the tag
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -357,9 +377,11 @@ pub struct TagIdentityMap<'a> {
 /// This enum allows CoMID tags to be identified by either a text string
 /// or a UUID, following the schema definition in the CoRIM specification.
 /// Tag identifiers are used in the tag identity map and for linking between tags.
-#[derive(Debug, Serialize, Deserialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
+///
+/// In JSON the UUID alternative is written as `{"type": "uuid", "value": <uuid>}` so that it
+/// can be told apart from text.
+#[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[repr(C)]
-#[serde(untagged)]
 pub enum TagIdTypeChoice<'a> {
     /// Text string identifier
     Tstr(Tstr<'a>),
@@ -367,6 +389,76 @@ pub enum TagIdTypeChoice<'a> {
     Uuid(UuidType),
 }
 
+impl Serialize for TagIdTypeChoice<'_> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Tstr(tstr) => serializer.serialize_str(tstr),
+            Self::Uuid(uuid) if serializer.is_human_readable() => {
+                serialize_json_typed(serializer, "uuid", uuid)
+            }
+            Self::Uuid(uuid) => uuid.serialize(serializer),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for TagIdTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            match serde_json::Value::deserialize(deserializer)? {
+                serde_json::Value::String(tstr) => Ok(Self::Tstr(tstr.into())),
+                serde_json::Value::Object(map) => match json_typed_value(map)? {
+                    (typ, value) if typ == "uuid" => Ok(Self::Uuid(
+                        serde_json::from_value(value).map_err(de::Error::custom)?,
+                    )),
+                    (typ, _) => Err(de::Error::custom(format!(
+                        "unexpected type {typ} for TagIdTypeChoice"
+                    ))),
+                },
+                value => Err(de::Error::custom(format!(
+                    "expected a string or a UUID object, got {value:?}"
+                ))),
+            }
+        } else {
+            deserializer.deserialize_any(TagIdVisitor(core::marker::PhantomData))
+        }
+    }
+}
+
+/// Reads a [`TagIdTypeChoice`] from CBOR, where text is a string and a UUID is bytes
+struct TagIdVisitor<'a>(core::marker::PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> Visitor<'de> for TagIdVisitor<'a> {
+    type Value = TagIdTypeChoice<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a text string or UUID bytes")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> core::result::Result<Self::Value, E> {
+        Ok(TagIdTypeChoice::Tstr(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> core::result::Result<Self::Value, E> {
+        Ok(TagIdTypeChoice::Tstr(v.to_string().into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> core::result::Result<Self::Value, E> {
+        Ok(TagIdTypeChoice::Tstr(v.into()))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> core::result::Result<Self::Value, E> {
+        Ok(TagIdTypeChoice::Uuid(
+            UuidType::try_from(v).map_err(|_| de::Error::custom("invalid UUID bytes"))?,
+        ))
+    }
+}
+
 impl TagIdTypeChoice<'_> {
     /// Returns the tag identifier as a string, if it is a text value
     pub fn as_str(&self) -> Option<&str> {
@@ -422,11 +514,12 @@ impl<'a> From<&'a str> for TagIdTypeChoice<'a> {
 #[repr(C)]
 pub struct ComidEntityMap<'a> {
     /// Name of the entity
-    #[serde(rename = "31")]
+    #[serde(borrow, rename = "31")]
+    #[serde(deserialize_with = "deserialize_text")]
     pub entity_name: Text<'a>,
     /// Optional registration identifier
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "32")]
+    #[serde(borrow, rename = "32")]
     pub reg_id: Option<Uri<'a>>,
     /// One or more roles this entity fulfills
     #[serde(rename = "33")]
@@ -435,16 +528,17 @@ pub struct ComidEntityMap<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
 }
 
 /// Role types that can be assigned to entities
 ///
 /// Each role type represents a specific responsibility that an entity
-/// may have in relation to a module or tag.
-#[derive(Debug, Serialize, Deserialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
+/// may have in relation to a module or tag. Roles are written by name (e.g. `"tag-creator"`) in
+/// JSON.
+#[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[repr(C)]
-#[serde(untagged)]
 pub enum ComidRoleTypeChoice {
     /// Entity that created the tag (value: 0)
     ///
@@ -470,7 +564,7 @@ pub enum ComidRoleTypeChoice {
 #[repr(C)]
 pub struct LinkedTagMap<'a> {
     /// Identifier of the linked tag
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub linked_tag_id: TagIdTypeChoice<'a>,
     /// Relationship type between the tags
     #[serde(rename = "1")]
@@ -480,22 +574,177 @@ pub struct LinkedTagMap<'a> {
 /// Types of relationships between tags
 ///
 /// This enum defines how tags can be related to each other,
-/// supporting versioning and supplemental information scenarios.
-#[derive(Debug, Serialize, Deserialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
+/// supporting versioning and supplemental information scenarios. Relations are written by name
+/// (e.g. `"supplements"`) in JSON.
+#[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[repr(C)]
-#[serde(untagged)]
 pub enum TagRelTypeChoice {
     /// This tag supplements the linked tag by providing additional information
     /// without replacing or invalidating the linked tag's content
     ///
     /// Use this relationship type when adding complementary information to an existing tag.
-    Supplements,
+    Supplements = 0,
     /// This tag completely replaces the linked tag, indicating that the linked
     /// tag should no longer be considered valid or current
     ///
     /// Use this relationship type when creating a new version of a tag that supersedes
     /// an older version.
-    Replaces,
+    Replaces = 1,
+}
+
+impl TryFrom<&str> for ComidRoleTypeChoice {
+    type Error = ComidError;
+
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
+        match value {
+            "tag-creator" => Ok(Self::TagCreator),
+            "creator" => Ok(Self::Creator),
+            "maintainer" => Ok(Self::Maintainer),
+            role => Err(ComidError::InvalidRole(role.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ComidRoleTypeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::TagCreator => "tag-creator",
+            Self::Creator => "creator",
+            Self::Maintainer => "maintainer",
+        })
+    }
+}
+
+impl Serialize for ComidRoleTypeChoice {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_u8(*self as u8)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ComidRoleTypeChoice {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(CodePointVisitor {
+            variants: &[Self::TagCreator, Self::Creator, Self::Maintainer],
+            expecting: "int or string CoMID role",
+        })
+    }
+}
+
+impl TryFrom<&str> for TagRelTypeChoice {
+    type Error = ComidError;
+
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
+        match value {
+            "supplements" => Ok(Self::Supplements),
+            "replaces" => Ok(Self::Replaces),
+            rel => Err(ComidError::InvalidTagRel(rel.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for TagRelTypeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Supplements => "supplements",
+            Self::Replaces => "replaces",
+        })
+    }
+}
+
+impl Serialize for TagRelTypeChoice {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_u8(*self as u8)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TagRelTypeChoice {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(CodePointVisitor {
+            variants: &[Self::Supplements, Self::Replaces],
+            expecting: "int or string tag relation",
+        })
+    }
+}
+
+/// Reads a code point given by name in JSON or by its value in CBOR. Earlier releases of this
+/// crate encoded every variant as null, which is still read, as the first variant.
+struct CodePointVisitor<T: 'static> {
+    /// The variants, indexed by their code point
+    variants: &'static [T],
+    expecting: &'static str,
+}
+
+impl<T> Visitor<'_> for CodePointVisitor<T>
+where
+    T: Copy + for<'s> TryFrom<&'s str, Error = ComidError>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_unit<E>(self) -> core::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(self.variants[0])
+    }
+
+    fn visit_none<E>(self) -> core::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_unit()
+    }
+
+    fn visit_u64<E>(self, v: u64) -> core::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        usize::try_from(v)
+            .ok()
+            .and_then(|index| self.variants.get(index))
+            .copied()
+            .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> core::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(de::Error::invalid_value(de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::try_from(v).map_err(de::Error::custom)
+    }
 }
 
 /// Collection of different types of triples describing the module characteristics. It is
@@ -506,37 +755,37 @@ pub enum TagRelTypeChoice {
 pub struct TriplesMap<'a> {
     /// Optional reference triples that link to external references
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub reference_triples: Option<Vec<ReferenceTripleRecord<'a>>>,
 
     /// Optional endorsement triples that contain verification information
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
     pub endorsed_triples: Option<Vec<EndorsedTripleRecord<'a>>>,
 
     /// Optional identity triples that provide identity information
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "2")]
+    #[serde(borrow, rename = "2")]
     pub identity_triples: Option<Vec<IdentityTripleRecord<'a>>>,
 
     /// Optional attestation key triples containing cryptographic keys
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "3")]
+    #[serde(borrow, rename = "3")]
     pub attest_key_triples: Option<Vec<AttestKeyTripleRecord<'a>>>,
 
     /// Optional domain dependency triples describing relationships between domains
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "4")]
+    #[serde(borrow, rename = "4")]
     pub dependency_triples: Option<Vec<DomainDependencyTripleRecord<'a>>>,
 
     /// Optional domain membership triples describing domain associations
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "5")]
+    #[serde(borrow, rename = "5")]
     pub membership_triples: Option<Vec<DomainMembershipTripleRecord<'a>>>,
 
     /// Optional SWID triples containing software identification data
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "6")]
+    #[serde(borrow, rename = "6")]
     pub coswid_triples: Option<Vec<CoswidTripleRecord<'a>>>,
 
     /// Optional conditional endorsement series triples for complex endorsement chains
@@ -547,13 +796,14 @@ pub struct TriplesMap<'a> {
 
     /// Optional conditional endorsement triples for conditional verification
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "10")]
+    #[serde(borrow, rename = "10")]
     pub conditional_endorsement_triples: Option<Vec<ConditionalEndorsementTripleRecord<'a>>>,
 
     /// Optional extensible attributes for future expansion
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
 }
 
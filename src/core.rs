@@ -37,9 +37,16 @@
 //! This module implements core functionality as specified in the IETF CoRIM specification
 //! and related standards (RFC 8152 for COSE structures).
 
-use std::{
-    borrow::Cow,
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
     collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
     fmt::Display,
     marker::PhantomData,
     ops::{Deref, DerefMut, Index, IndexMut},
@@ -48,7 +55,7 @@ use std::{
 use base64::{self, engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use derive_more::{AsMut, AsRef, Constructor, Deref, DerefMut, From, TryFrom};
 use serde::{
-    de::{self, SeqAccess, Unexpected, Visitor},
+    de::{self, IntoDeserializer, SeqAccess, Unexpected, Visitor},
     ser::{self, Error as _, SerializeMap, SerializeSeq},
     Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -77,64 +84,151 @@ pub type Bool = bool;
 /// Floating Point variables.
 pub type Float = f32;
 
-#[derive(Debug, Default, From, PartialEq, Eq, PartialOrd, Ord, Clone, Constructor)]
-pub struct Bytes {
-    bytes: Vec<u8>,
+/// Deserializes text, borrowing it from the input when the deserializer lends it out, as
+/// [`cbor::from_slice`](crate::cbor::from_slice) and `serde_json::from_str` do.
+pub(crate) fn deserialize_text<'de: 'a, 'a, D>(deserializer: D) -> Result<Text<'a>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer
+        .deserialize_str(TextVisitor(PhantomData))
+        .map(|BorrowText(text)| text)
+}
+
+/// Deserializes optional text as [`deserialize_text`] does
+pub(crate) fn deserialize_option_text<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Option<Text<'a>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<BorrowText>::deserialize(deserializer)?.map(|BorrowText(text)| text))
+}
+
+/// Text that deserializes as [`deserialize_text`] does, for use in containers such as `Option`
+/// and `Vec`, whose elements serde deserializes with their own `Deserialize` implementation.
+/// That of `Cow<str>` always copies.
+pub(crate) struct BorrowText<'a>(pub(crate) Text<'a>);
+
+impl<'a> From<BorrowText<'a>> for Text<'a> {
+    fn from(BorrowText(text): BorrowText<'a>) -> Self {
+        text
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowText<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TextVisitor(PhantomData))
+    }
+}
+
+struct TextVisitor<'a>(PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> Visitor<'de> for TextVisitor<'a> {
+    type Value = BorrowText<'a>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(BorrowText(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(BorrowText(Cow::Owned(v.to_owned())))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(BorrowText(Cow::Owned(v)))
+    }
+}
+
+/// A CBOR byte string. Bytes decoded with [`cbor::from_slice`](crate::cbor::from_slice) borrow
+/// from the input; mutable access copies them first.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Bytes<'a> {
+    bytes: Cow<'a, [u8]>,
 }
 
-impl From<&[u8]> for Bytes {
-    fn from(value: &[u8]) -> Self {
+impl Bytes<'_> {
+    pub fn new(bytes: Vec<u8>) -> Self {
         Self {
-            bytes: value.to_vec(),
+            bytes: Cow::Owned(bytes),
         }
     }
 }
 
-impl TryFrom<&str> for Bytes {
+impl From<Vec<u8>> for Bytes<'_> {
+    fn from(value: Vec<u8>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<'a> From<&'a [u8]> for Bytes<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Self {
+            bytes: Cow::Borrowed(value),
+        }
+    }
+}
+
+impl<'a> From<Cow<'a, [u8]>> for Bytes<'a> {
+    fn from(bytes: Cow<'a, [u8]>) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'a> From<Bytes<'a>> for Cow<'a, [u8]> {
+    fn from(value: Bytes<'a>) -> Self {
+        value.bytes
+    }
+}
+
+impl TryFrom<&str> for Bytes<'_> {
     type Error = base64::DecodeError;
 
     fn try_from(v: &str) -> Result<Self, Self::Error> {
-        Ok(Self {
-            bytes: URL_SAFE_NO_PAD.decode(v)?,
-        })
+        Ok(Self::new(URL_SAFE_NO_PAD.decode(v)?))
     }
 }
 
-impl TryFrom<String> for Bytes {
+impl TryFrom<String> for Bytes<'_> {
     type Error = base64::DecodeError;
 
     fn try_from(v: String) -> Result<Self, Self::Error> {
-        Ok(Self {
-            bytes: URL_SAFE_NO_PAD.decode(v)?,
-        })
+        Ok(Self::new(URL_SAFE_NO_PAD.decode(v)?))
     }
 }
 
-impl From<&Bytes> for Vec<u8> {
-    fn from(value: &Bytes) -> Self {
-        value.bytes.clone()
+impl From<&Bytes<'_>> for Vec<u8> {
+    fn from(value: &Bytes<'_>) -> Self {
+        value.bytes.to_vec()
     }
 }
 
-impl Display for Bytes {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Display for Bytes<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(URL_SAFE_NO_PAD.encode(&self.bytes).as_str())
     }
 }
 
-impl AsRef<[u8]> for Bytes {
+impl AsRef<[u8]> for Bytes<'_> {
     fn as_ref(&self) -> &[u8] {
         &self.bytes
     }
 }
 
-impl AsMut<[u8]> for Bytes {
+impl AsMut<[u8]> for Bytes<'_> {
     fn as_mut(&mut self) -> &mut [u8] {
-        &mut self.bytes
+        self.bytes.to_mut()
     }
 }
 
-impl Deref for Bytes {
+impl Deref for Bytes<'_> {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
@@ -142,19 +236,19 @@ impl Deref for Bytes {
     }
 }
 
-impl DerefMut for Bytes {
+impl DerefMut for Bytes<'_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.bytes
+        self.bytes.to_mut()
     }
 }
 
-impl<const N: usize> PartialEq<[u8; N]> for Bytes {
+impl<const N: usize> PartialEq<[u8; N]> for Bytes<'_> {
     fn eq(&self, other: &[u8; N]) -> bool {
-        self.bytes.as_slice() == other.as_slice()
+        *self.bytes == other[..]
     }
 }
 
-impl Index<usize> for Bytes {
+impl Index<usize> for Bytes<'_> {
     type Output = u8;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -162,36 +256,36 @@ impl Index<usize> for Bytes {
     }
 }
 
-impl IndexMut<usize> for Bytes {
+impl IndexMut<usize> for Bytes<'_> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.bytes[index]
+        &mut self.bytes.to_mut()[index]
     }
 }
 
-impl Serialize for Bytes {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+impl Serialize for Bytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         if serializer.is_human_readable() {
             serializer.serialize_str(&self.to_string())
         } else {
-            serializer.serialize_bytes(self.bytes.as_slice())
+            serializer.serialize_bytes(&self.bytes)
         }
     }
 }
 
-impl<'de> Deserialize<'de> for Bytes {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for Bytes<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct BytesVisitor;
+        struct BytesVisitor<'a>(PhantomData<&'a ()>);
 
-        impl<'de> Visitor<'de> for BytesVisitor {
-            type Value = Bytes;
+        impl<'de: 'a, 'a> Visitor<'de> for BytesVisitor<'a> {
+            type Value = Bytes<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a string or a byte array")
             }
 
@@ -220,23 +314,33 @@ impl<'de> Deserialize<'de> for Bytes {
             where
                 E: de::Error,
             {
-                Ok(Bytes {
-                    bytes: Vec::from(v),
-                })
+                Ok(Bytes::new(Vec::from(v)))
             }
 
             fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                self.visit_bytes(v)
+                Ok(Bytes::from(v))
             }
 
             fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                Ok(Bytes { bytes: v })
+                Ok(Bytes::new(v))
+            }
+
+            // ciborium also reads an array of integers as bytes
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(Bytes::new(bytes))
             }
         }
 
@@ -246,16 +350,15 @@ impl<'de> Deserialize<'de> for Bytes {
             // is_human_readable() of the original deserializer and always return true. This means
             // that we must be prepared to handle bytes as well as strings, even when dealing with
             // ostensibly human-readable formats.
-            deserializer.deserialize_any(BytesVisitor)
+            deserializer.deserialize_any(BytesVisitor(PhantomData))
         } else {
-            let bytes = Vec::<u8>::deserialize(deserializer)?;
-            Ok(Bytes { bytes })
+            deserializer.deserialize_byte_buf(BytesVisitor(PhantomData))
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, Clone, Default)]
-pub struct ExtensionMap<'a>(pub BTreeMap<Integer, ExtensionValue<'a>>);
+pub struct ExtensionMap<'a>(#[serde(borrow)] pub BTreeMap<Integer, ExtensionValue<'a>>);
 
 impl<'a> ExtensionMap<'a> {
     pub fn insert(&mut self, key: Integer, value: ExtensionValue<'a>) {
@@ -288,7 +391,7 @@ pub enum ExtensionValue<'a> {
     /// Boolean values
     Bool(Bool),
     /// A bstr
-    Bytes(Bytes),
+    Bytes(Bytes<'a>),
     /// A signed integer
     Int(Int),
     /// A UTF-8 string value
@@ -610,7 +713,7 @@ impl Serialize for ExtensionValue<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for ExtensionValue<'_> {
+impl<'de: 'a, 'a> Deserialize<'de> for ExtensionValue<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -648,19 +751,198 @@ impl<'de> Deserialize<'de> for ExtensionValue<'_> {
                 v => v.try_into().map_err(de::Error::custom),
             }
         } else {
-            ciborium::Value::deserialize(deserializer)?
-                .try_into()
-                .map_err(de::Error::custom)
+            deserializer.deserialize_any(ExtensionValueVisitor(PhantomData))
+        }
+    }
+}
+
+/// Reads an [`ExtensionValue`] from CBOR as it is decoded, without buffering it in a
+/// [`ciborium::Value`]
+struct ExtensionValueVisitor<'a>(PhantomData<&'a ()>);
+
+impl ExtensionValueVisitor<'_> {
+    fn integer<E: de::Error>(v: i128) -> Result<ExtensionValue<'static>, E> {
+        if v >= 0 {
+            Ok(ExtensionValue::Uint(v.into()))
+        } else {
+            Ok(ExtensionValue::Int(v.into()))
         }
     }
 }
 
+impl<'de: 'a, 'a> Visitor<'de> for ExtensionValueVisitor<'a> {
+    type Value = ExtensionValue<'a>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a CBOR extension value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Null)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Self::integer(v.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Self::integer(v.into())
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        Self::integer(v)
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        Self::integer(v.try_into().map_err(E::custom)?)
+    }
+
+    fn visit_f64<E: de::Error>(self, _v: f64) -> Result<Self::Value, E> {
+        Err(E::custom(CoreError::InvalidValue(
+            "floating point extension values not supported".to_string(),
+        )))
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Text(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Text(v.to_string().into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Text(v.into()))
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Bytes(v.into()))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Bytes(v.to_vec().into()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Bytes(v.into()))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(ExtensionValue::Array(values))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut entries = BTreeMap::new();
+        while let Some(ExtensionLabel(label)) = map.next_key()? {
+            entries.insert(label, map.next_value()?);
+        }
+        Ok(ExtensionValue::Map(entries))
+    }
+
+    // ciborium presents a tagged item as an enum, as for TagChoiceVisitor
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        let (_, variant) = data.variant::<de::IgnoredAny>()?;
+        de::VariantAccess::tuple_variant(variant, 2, TaggedExtensionValueVisitor(PhantomData))
+    }
+}
+
+/// Reads the tag number and the tagged value of a tagged [`ExtensionValue`]
+struct TaggedExtensionValueVisitor<'a>(PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> Visitor<'de> for TaggedExtensionValueVisitor<'a> {
+    type Value = ExtensionValue<'a>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("a tagged CBOR extension value")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let tag: u64 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::custom("missing tag"))?;
+        let value: ExtensionValue<'a> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::custom("missing tagged value"))?;
+        Ok(ExtensionValue::Tag(tag, Box::new(value)))
+    }
+}
+
+/// A CBOR map key of an [`ExtensionValue::Map`], which must be an integer or a text string
+struct ExtensionLabel<'a>(Label<'a>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for ExtensionLabel<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LabelVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for LabelVisitor<'a> {
+            type Value = ExtensionLabel<'a>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("an integer or text map key")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(ExtensionLabel(Label::Int(i128::from(v).into())))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(ExtensionLabel(Label::Int(i128::from(v).into())))
+            }
+
+            fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+                Ok(ExtensionLabel(Label::Int(v.into())))
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(ExtensionLabel(Label::Text(Cow::Borrowed(v))))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(ExtensionLabel(Label::Text(v.to_string().into())))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(ExtensionLabel(Label::Text(v.into())))
+            }
+        }
+
+        deserializer.deserialize_any(LabelVisitor(PhantomData))
+    }
+}
+
 /// UUID type representing a 16-byte unique identifier
 #[derive(Default, Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct UuidType(pub FixedBytes<16>);
 
 impl TryFrom<&[u8]> for UuidType {
-    type Error = std::array::TryFromSliceError;
+    type Error = core::array::TryFromSliceError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         Ok(Self(FixedBytes(value.try_into()?)))
@@ -688,7 +970,7 @@ impl TryFrom<String> for UuidType {
 }
 
 impl Display for UuidType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(uuid::Uuid::from_bytes(self.0 .0).to_string().as_ref())
     }
 }
@@ -763,14 +1045,12 @@ impl<'de> Deserialize<'de> for UuidType {
 
 /// UEID type representing a Unique Entity Identifier between 7 and 33 bytes long
 #[derive(Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct UeidType(Bytes);
+pub struct UeidType<'a>(pub(crate) Bytes<'a>);
 
-impl TryFrom<&[u8]> for UeidType {
-    type Error = CoreError;
-
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+impl<'a> UeidType<'a> {
+    fn validate(value: Bytes<'a>) -> Result<Self, CoreError> {
         if value.len() >= 7 && value.len() <= 33 {
-            Ok(Self(Bytes::from(value)))
+            Ok(Self(value))
         } else {
             Err(CoreError::InvalidValue(
                 "UEID must be between 7 and 33 bytes long".to_string(),
@@ -779,39 +1059,55 @@ impl TryFrom<&[u8]> for UeidType {
     }
 }
 
-impl TryFrom<&str> for UeidType {
+impl<'a> TryFrom<&'a [u8]> for UeidType<'a> {
+    type Error = CoreError;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::validate(Bytes::from(value))
+    }
+}
+
+impl TryFrom<Vec<u8>> for UeidType<'_> {
+    type Error = CoreError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::validate(Bytes::from(value))
+    }
+}
+
+impl TryFrom<&str> for UeidType<'_> {
     type Error = CoreError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        URL_SAFE_NO_PAD
-            .decode(value)
-            .map_err(|e| CoreError::InvalidValue(e.to_string()))?
-            .as_slice()
-            .try_into()
+        Self::validate(Bytes::new(
+            URL_SAFE_NO_PAD
+                .decode(value)
+                .map_err(|e| CoreError::InvalidValue(e.to_string()))?,
+        ))
     }
 }
 
-impl TryFrom<String> for UeidType {
+impl TryFrom<String> for UeidType<'_> {
     type Error = CoreError;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        Self::try_from(<std::string::String as AsRef<str>>::as_ref(&value))
+        Self::try_from(<alloc::string::String as AsRef<str>>::as_ref(&value))
     }
 }
 
-impl AsRef<[u8]> for UeidType {
+impl AsRef<[u8]> for UeidType<'_> {
     fn as_ref(&self) -> &[u8] {
         &self.0
     }
 }
 
-impl AsMut<[u8]> for UeidType {
+impl AsMut<[u8]> for UeidType<'_> {
     fn as_mut(&mut self) -> &mut [u8] {
         &mut self.0
     }
 }
 
-impl Deref for UeidType {
+impl Deref for UeidType<'_> {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
@@ -819,13 +1115,13 @@ impl Deref for UeidType {
     }
 }
 
-impl DerefMut for UeidType {
+impl DerefMut for UeidType<'_> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
-impl Index<usize> for UeidType {
+impl Index<usize> for UeidType<'_> {
     type Output = u8;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -833,19 +1129,19 @@ impl Index<usize> for UeidType {
     }
 }
 
-impl IndexMut<usize> for UeidType {
+impl IndexMut<usize> for UeidType<'_> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.0[index]
     }
 }
 
-impl Display for UeidType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Display for UeidType<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(URL_SAFE_NO_PAD.encode(self).as_ref())
     }
 }
 
-impl Serialize for UeidType {
+impl Serialize for UeidType<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -858,8 +1154,8 @@ impl Serialize for UeidType {
     }
 }
 
-impl<'de> Deserialize<'de> for UeidType {
-    fn deserialize<D>(deserializer: D) -> Result<UeidType, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for UeidType<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<UeidType<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -868,8 +1164,7 @@ impl<'de> Deserialize<'de> for UeidType {
                 .try_into()
                 .map_err(de::Error::custom)
         } else {
-            UeidType::try_from(Bytes::deserialize(deserializer)?.as_ref())
-                .map_err(de::Error::custom)
+            UeidType::validate(Bytes::deserialize(deserializer)?).map_err(de::Error::custom)
         }
     }
 }
@@ -880,9 +1175,21 @@ impl<'de> Deserialize<'de> for UeidType {
 // dot-delimited integers) representing a path through the OID tree. Each node in the tree is
 // controlled by an assigning authority.
 #[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, AsRef, AsMut, Deref, DerefMut)]
-pub struct ObjectIdentifier(Bytes);
+pub struct ObjectIdentifier<'a>(pub(crate) Bytes<'a>);
+
+impl<'a> ObjectIdentifier<'a> {
+    /// Checks that `bytes` encode an OID, keeping them unless their encoding is not canonical
+    fn parse(bytes: Bytes<'a>) -> Result<Self, oid::ObjectIdentifierError> {
+        let encoded: Vec<u8> = oid::ObjectIdentifier::try_from(bytes.as_ref())?.into();
+        if encoded == bytes.as_ref() {
+            Ok(Self(bytes))
+        } else {
+            Ok(Self(encoded.into()))
+        }
+    }
+}
 
-impl TryFrom<&str> for ObjectIdentifier {
+impl TryFrom<&str> for ObjectIdentifier<'_> {
     type Error = oid::ObjectIdentifierError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -891,16 +1198,15 @@ impl TryFrom<&str> for ObjectIdentifier {
     }
 }
 
-impl TryFrom<&[u8]> for ObjectIdentifier {
+impl<'a> TryFrom<&'a [u8]> for ObjectIdentifier<'a> {
     type Error = oid::ObjectIdentifierError;
 
-    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let bytes: Vec<u8> = oid::ObjectIdentifier::try_from(value)?.into();
-        Ok(Self(bytes.into()))
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::parse(value.into())
     }
 }
 
-impl TryFrom<Vec<u8>> for ObjectIdentifier {
+impl TryFrom<Vec<u8>> for ObjectIdentifier<'_> {
     type Error = oid::ObjectIdentifierError;
 
     fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
@@ -909,20 +1215,20 @@ impl TryFrom<Vec<u8>> for ObjectIdentifier {
     }
 }
 
-impl AsRef<[u8]> for ObjectIdentifier {
+impl AsRef<[u8]> for ObjectIdentifier<'_> {
     fn as_ref(&self) -> &[u8] {
         &self.0
     }
 }
 
-impl AsMut<[u8]> for ObjectIdentifier {
+impl AsMut<[u8]> for ObjectIdentifier<'_> {
     fn as_mut(&mut self) -> &mut [u8] {
         &mut self.0
     }
 }
 
-impl Display for ObjectIdentifier {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Display for ObjectIdentifier<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let obj = oid::ObjectIdentifier::try_from(self.0.as_ref());
         match obj {
             Ok(oid) => f.write_str(Into::<String>::into(oid).as_str()),
@@ -931,7 +1237,7 @@ impl Display for ObjectIdentifier {
     }
 }
 
-impl Serialize for ObjectIdentifier {
+impl Serialize for ObjectIdentifier<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -946,34 +1252,42 @@ impl Serialize for ObjectIdentifier {
     }
 }
 
-impl<'de> Deserialize<'de> for ObjectIdentifier {
-    fn deserialize<D>(deserializer: D) -> Result<ObjectIdentifier, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for ObjectIdentifier<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<ObjectIdentifier<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let bytes: Vec<u8> = oid::ObjectIdentifier::deserialize(deserializer)?.into();
-        Ok(Self(bytes.into()))
+        if deserializer.is_human_readable() {
+            let bytes: Vec<u8> = oid::ObjectIdentifier::deserialize(deserializer)?.into();
+            Ok(Self(bytes.into()))
+        } else {
+            Self::parse(Bytes::deserialize(deserializer)?)
+                .map_err(|e| de::Error::custom(format!("invalid OID: {e:?}")))
+        }
     }
 }
 
 generate_tagged!(
     (1, IntegerTime, Int, "time", "A representation of time in integer format using CBOR tag 1"),
-    (32, Uri, Text<'a>, 'a,  "uri", "A URI text string with CBOR tag 32"),
     (37, TaggedUuidType, UuidType, "uuid", "UUID type wrapped with CBOR tag 37"),
-    (111, OidType, ObjectIdentifier, "oid", "An Object Identifier (OID) represented as bytes using CBOR tag 111"),
-    (550, TaggedUeidType, UeidType, "ueid", "UEID type wrapped with CBOR tag 550"),
+    (111, OidType, ObjectIdentifier<'a>, 'a, "oid", "An Object Identifier (OID) represented as bytes using CBOR tag 111"),
+    (550, TaggedUeidType, UeidType<'a>, 'a, "ueid", "UEID type wrapped with CBOR tag 550"),
     (552, SvnType, Uint, "svn", "A Security Version Number (SVN) using CBOR tag 552"),
     (553, MinSvnType, Uint, "min-svn", "A minimum Security Version Number (SVN) using CBOR tag 553"),
+    (557, ThumbprintType, Digest<'a>, 'a, "thumbprint", "A cryptographic thumbprint using CBOR tag 557"),
+    (558, CoseKeyType, CoseKeySetOrKey<'a>, 'a, "cose-key", "CBOR tag 558 wrapper for COSE Key Structures"),
+    (559, CertThumbprintType, Digest<'a>, 'a, "cert-thumbprint", "A certificate thumbprint using CBOR tag 559"),
+    (560, TaggedBytes, Bytes<'a>, 'a, "bytes", "A generic byte string using CBOR tag 560"),
+    (561, CertPathThumbprintType, Digest<'a>, 'a, "cert-path-thumbprint", "A certificate path thumbprint using CBOR tag 561"),
+    (562, PkixAsn1DerCertType, Bytes<'a>, 'a, "pkix-asn1-der-cert", "A PKIX certificate in ASN.1 DER format using CBOR tag 562"),
+    (563, TaggedMaskedRawValue, MaskedRawValue<'a>, 'a, "masked-raw-value", "Represents a masked raw value with its mask"),
+);
+
+generate_tagged!(@text
+    (32, Uri, Text<'a>, 'a,  "uri", "A URI text string with CBOR tag 32"),
     (554, PkixBase64KeyType, Tstr<'a>, 'a, "pkix-base64-key", "A PKIX key in base64 format using CBOR tag 554"),
     (555, PkixBase64CertType, Tstr<'a>, 'a, "pkix-base64-cert", "A PKIX certificate in base64 format using CBOR tag 555"),
     (556, PkixBase64CertPathType, Tstr<'a>, 'a, "pkix-base64-cert-path", "A PKIX certificate path in base64 format using CBOR tag 556"),
-    (557, ThumbprintType, Digest, "thumbprint", "A cryptographic thumbprint using CBOR tag 557"),
-    (558, CoseKeyType, CoseKeySetOrKey, "cose-key", "CBOR tag 558 wrapper for COSE Key Structures"),
-    (559, CertThumbprintType, Digest, "cert-thumbprint", "A certificate thumbprint using CBOR tag 559"),
-    (560, TaggedBytes, Bytes, "bytes", "A generic byte string using CBOR tag 560"),
-    (561, CertPathThumbprintType, Digest, "cert-path-thumbprint", "A certificate path thumbprint using CBOR tag 561"),
-    (562, PkixAsn1DerCertType, Bytes, "pkix-asn1-der-cert", "A PKIX certificate in ASN.1 DER format using CBOR tag 562"),
-    (563, TaggedMaskedRawValue, MaskedRawValue, "masked-raw-value", "Represents a masked raw value with its mask"),
 );
 
 /// Represents a value that can be either text or bytes
@@ -982,9 +1296,11 @@ generate_tagged!(
 #[serde(untagged)]
 pub enum TextOrBytes<'a> {
     /// UTF-8 string value
+    #[serde(borrow, deserialize_with = "deserialize_text")]
     Text(Text<'a>),
     /// Raw bytes value
-    Bytes(TaggedBytes),
+    #[serde(borrow)]
+    Bytes(TaggedBytes<'a>),
 }
 
 impl TextOrBytes<'_> {
@@ -1019,14 +1335,16 @@ impl TextOrBytes<'_> {
 
 impl<'a> From<&'a str> for TextOrBytes<'a> {
     fn from(value: &'a str) -> Self {
-        Self::Text(std::borrow::Cow::Borrowed(value))
+        Self::Text(alloc::borrow::Cow::Borrowed(value))
     }
 }
 
 /// Represents a value that can be either text or fixed-size bytes
 #[repr(C)]
-#[derive(Debug, Serialize, Deserialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
-#[serde(untagged)]
+///
+/// In JSON the bytes alternative is written as `{"type": "bytes", "value": <base64>}` so that
+/// it can be told apart from text.
+#[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub enum TextOrBytesSized<'a, const N: usize> {
     /// UTF-8 string value
     Text(Text<'a>),
@@ -1034,6 +1352,86 @@ pub enum TextOrBytesSized<'a, const N: usize> {
     Bytes(FixedBytes<N>),
 }
 
+impl<const N: usize> Serialize for TextOrBytesSized<'_, N> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Text(text) => serializer.serialize_str(text),
+            Self::Bytes(bytes) if serializer.is_human_readable() => {
+                serialize_json_typed(serializer, "bytes", &URL_SAFE_NO_PAD.encode(bytes.as_ref()))
+            }
+            Self::Bytes(bytes) => bytes.serialize(serializer),
+        }
+    }
+}
+
+impl<'a, const N: usize> TextOrBytesSized<'a, N> {
+    fn from_bytes<E: de::Error>(bytes: &[u8]) -> core::result::Result<Self, E> {
+        <[u8; N]>::try_from(bytes)
+            .map(|bytes| Self::Bytes(FixedBytes(bytes)))
+            .map_err(|_| de::Error::invalid_length(bytes.len(), &format!("{N} bytes").as_str()))
+    }
+}
+
+impl<'de: 'a, 'a, const N: usize> Deserialize<'de> for TextOrBytesSized<'a, N> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TextOrBytesVisitor<'a, const N: usize>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a, const N: usize> Visitor<'de> for TextOrBytesVisitor<'a, N> {
+            type Value = TextOrBytesSized<'a, N>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("a text or byte string")
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(TextOrBytesSized::Text(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(TextOrBytesSized::Text(Cow::Owned(v.to_owned())))
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(TextOrBytesSized::Text(Cow::Owned(v)))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                TextOrBytesSized::from_bytes(v)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            let bytes = match serde_json::Value::deserialize(deserializer)? {
+                serde_json::Value::String(text) => return Ok(Self::Text(text.into())),
+                serde_json::Value::Object(map) => match json_typed_value(map)? {
+                    (typ, serde_json::Value::String(value)) if typ == "bytes" => {
+                        URL_SAFE_NO_PAD.decode(value).map_err(de::Error::custom)?
+                    }
+                    (typ, _) => {
+                        return Err(de::Error::custom(format!(
+                            "unexpected type {typ} for TextOrBytesSized"
+                        )))
+                    }
+                },
+                value => {
+                    return Err(de::Error::custom(format!(
+                        "expected a string or a bytes object, got {value:?}"
+                    )))
+                }
+            };
+            Self::from_bytes(&bytes)
+        } else {
+            deserializer.deserialize_any(TextOrBytesVisitor(PhantomData))
+        }
+    }
+}
+
 impl<const N: usize> TextOrBytesSized<'_, N> {
     pub fn is_empty(&self) -> bool {
         match self {
@@ -1067,15 +1465,15 @@ impl<const N: usize> TextOrBytesSized<'_, N> {
 /// Represents a hash entry with algorithm ID and hash value
 #[repr(C)]
 #[derive(Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct HashEntry {
+pub struct HashEntry<'a> {
     /// Algorithm identifier for the hash
     pub hash_alg_id: CoseAlgorithm,
     /// The hash value as bytes
-    pub hash_value: Bytes,
+    pub hash_value: Bytes<'a>,
 }
 
-impl Serialize for HashEntry {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+impl Serialize for HashEntry<'_> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -1094,23 +1492,21 @@ impl Serialize for HashEntry {
     }
 }
 
-impl<'de> Deserialize<'de> for HashEntry {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for HashEntry<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
+        use core::fmt;
         use serde::de::{SeqAccess, Visitor};
-        use std::fmt;
 
-        struct HashEntryVisitor;
+        struct HashEntryVisitor<'a>(PhantomData<&'a ()>);
 
-        impl<'de> Visitor<'de> for HashEntryVisitor {
-            type Value = HashEntry;
+        impl<'de: 'a, 'a> Visitor<'de> for HashEntryVisitor<'a> {
+            type Value = HashEntry<'a>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str(
-                    "a sequence with at least one element (hash_alg_id followed by bytes)",
-                )
+                formatter.write_str("a sequence of hash_alg_id followed by the hash value")
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -1122,20 +1518,18 @@ impl<'de> Deserialize<'de> for HashEntry {
                     .next_element::<CoseAlgorithm>()?
                     .ok_or_else(|| serde::de::Error::custom("missing hash_alg_id"))?;
 
-                // Collect the remaining elements as bytes
-                let mut bytes = Vec::new();
-                while let Some(byte) = seq.next_element::<u8>()? {
-                    bytes.push(byte);
-                }
+                let hash_value = seq
+                    .next_element::<Bytes>()?
+                    .ok_or_else(|| serde::de::Error::custom("missing hash_value"))?;
 
                 Ok(HashEntry {
                     hash_alg_id,
-                    hash_value: bytes.into(),
+                    hash_value,
                 })
             }
         }
 
-        deserializer.deserialize_seq(HashEntryVisitor)
+        deserializer.deserialize_seq(HashEntryVisitor(PhantomData))
     }
 }
 
@@ -1145,6 +1539,7 @@ impl<'de> Deserialize<'de> for HashEntry {
 #[serde(untagged)]
 pub enum Label<'a> {
     /// Text label
+    #[serde(borrow, deserialize_with = "deserialize_text")]
     Text(Text<'a>),
     /// Integer label
     Int(Int),
@@ -1191,7 +1586,7 @@ impl Label<'_> {
 
 impl<'a> From<&'a str> for Label<'a> {
     fn from(value: &'a str) -> Self {
-        Self::Text(std::borrow::Cow::Borrowed(value))
+        Self::Text(alloc::borrow::Cow::Borrowed(value))
     }
 }
 
@@ -1210,7 +1605,7 @@ impl TryFrom<ciborium::Value> for Label<'_> {
 }
 
 impl Display for Label<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let temp: String;
 
         match self {
@@ -1276,7 +1671,7 @@ impl<'a> From<&'a str> for Ulabel<'a> {
 }
 
 impl Display for Ulabel<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Ulabel::Text(t) => {
                 write!(f, "\"{t}\"")
@@ -1310,7 +1705,7 @@ impl Serialize for Ulabel<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for Ulabel<'_> {
+impl<'de: 'a, 'a> Deserialize<'de> for Ulabel<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -1320,10 +1715,35 @@ impl<'de> Deserialize<'de> for Ulabel<'_> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for UlabelVisitor<'a> {
+        impl UlabelVisitor<'_> {
+            /// Returns the text of a text label, or the value of a uint label
+            fn parse<'s, E>(&self, v: &'s str) -> Result<Result<&'s str, Uint>, E>
+            where
+                E: de::Error,
+            {
+                if !self.is_human_readable {
+                    return Ok(Ok(v));
+                }
+
+                if v.is_empty() {
+                    return Err(de::Error::custom("empty label"));
+                }
+
+                // In JSON, both Text and Uint label keys are serialized as strings (as all
+                // JSON keys must be strings). Text keys are distinguished by that they start
+                // (and end) with a ".
+                if v.chars().nth(0).unwrap() == '"' {
+                    Ok(Ok(&v[1..v.len() - 1]))
+                } else {
+                    Ok(Err(v.parse::<u64>().map_err(de::Error::custom)?.into()))
+                }
+            }
+        }
+
+        impl<'de: 'a, 'a> Visitor<'de> for UlabelVisitor<'a> {
             type Value = Ulabel<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a string or uint contianing the label")
             }
 
@@ -1338,24 +1758,10 @@ impl<'de> Deserialize<'de> for Ulabel<'_> {
             where
                 E: de::Error,
             {
-                if self.is_human_readable {
-                    if v.is_empty() {
-                        return Err(de::Error::custom("empty label"));
-                    }
-
-                    // In JSON, both Text and Uint label keys are serialized as strings (as all
-                    // JSON keys must be strings). Text keys are distinguished by that they start
-                    // (and end) with a ".
-                    if v.chars().nth(0).unwrap() == '"' {
-                        Ok(Ulabel::Text(v[1..v.len() - 1].to_string().into()))
-                    } else {
-                        Ok(Ulabel::Uint(
-                            v.parse::<u64>().map_err(de::Error::custom)?.into(),
-                        ))
-                    }
-                } else {
-                    Ok(Ulabel::Text(v.to_string().into()))
-                }
+                Ok(match self.parse(v)? {
+                    Ok(text) => Ulabel::Text(text.to_string().into()),
+                    Err(uint) => Ulabel::Uint(uint),
+                })
             }
 
             fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
@@ -1369,7 +1775,10 @@ impl<'de> Deserialize<'de> for Ulabel<'_> {
             where
                 E: de::Error,
             {
-                self.visit_str(v)
+                Ok(match self.parse(v)? {
+                    Ok(text) => Ulabel::Text(Cow::Borrowed(text)),
+                    Err(uint) => Ulabel::Uint(uint),
+                })
             }
         }
 
@@ -1443,6 +1852,26 @@ impl<T> OneOrMore<T> {
             OneOrMore::More(items) => items.get(index),
         }
     }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        match self {
+            OneOrMore::One(item) => core::slice::from_ref(item).iter(),
+            OneOrMore::More(items) => items.iter(),
+        }
+    }
+}
+
+/// Deserializes one or more texts as [`deserialize_text`] does
+fn deserialize_text_one_or_more<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<OneOrMore<Text<'a>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match OneOrMore::<BorrowText>::deserialize(deserializer)? {
+        OneOrMore::One(text) => OneOrMore::One(text.into()),
+        OneOrMore::More(texts) => OneOrMore::More(texts.into_iter().map(Text::from).collect()),
+    })
 }
 
 /// Represents an attribute value that can be either text or integer, single or multiple
@@ -1450,6 +1879,7 @@ impl<T> OneOrMore<T> {
 #[serde(untagged)]
 #[repr(C)]
 pub enum AttributeValue<'a> {
+    #[serde(borrow, deserialize_with = "deserialize_text_one_or_more")]
     Text(OneOrMore<Text<'a>>),
     Int(OneOrMore<Int>),
 }
@@ -1506,9 +1936,10 @@ impl<'a> AttributeValue<'a> {
 pub struct GlobalAttributes<'a> {
     /// Optional language tag (ex. en_US)
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow, default, deserialize_with = "deserialize_option_text")]
     pub lang: Option<Text<'a>>,
     /// Arbitrary attributes
-    #[serde(flatten)]
+    #[serde(borrow, flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     pub attributes: Option<ExtensionMap<'a>>,
@@ -1572,14 +2003,21 @@ pub enum CotlMapRegistry {
 /// Represents a digest value with its algorithm identifier
 #[repr(C)]
 #[derive(Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct Digest {
+pub struct Digest<'a> {
     /// Algorithm identifier for the digest
     pub alg: HashAlgorithm,
     /// The digest value as bytes
-    pub val: Bytes,
+    pub val: Bytes<'a>,
+}
+
+impl Digest<'_> {
+    /// Returns `true` if this digest is the digest of `data`
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.alg.digest(data).val == self.val
+    }
 }
 
-impl TryFrom<&str> for Digest {
+impl TryFrom<&str> for Digest<'_> {
     type Error = CoreError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -1605,13 +2043,13 @@ impl TryFrom<&str> for Digest {
     }
 }
 
-impl Display for Digest {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Display for Digest<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{};{}", self.alg, URL_SAFE_NO_PAD.encode(&self.val),)
     }
 }
 
-impl Serialize for Digest {
+impl Serialize for Digest<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -1630,17 +2068,17 @@ impl Serialize for Digest {
     }
 }
 
-impl<'de> Deserialize<'de> for Digest {
+impl<'de: 'a, 'a> Deserialize<'de> for Digest<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct DigestVisitor;
+        struct DigestVisitor<'a>(PhantomData<&'a ()>);
 
-        impl<'de> Visitor<'de> for DigestVisitor {
-            type Value = Digest;
+        impl<'de: 'a, 'a> Visitor<'de> for DigestVisitor<'a> {
+            type Value = Digest<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str(
                     "a CBOR sequence of [alg, val] where alg is an int or text and val is bytes",
                 )
@@ -1674,23 +2112,23 @@ impl<'de> Deserialize<'de> for Digest {
         }
 
         if deserializer.is_human_readable() {
-            deserializer.deserialize_str(DigestVisitor)
+            deserializer.deserialize_str(DigestVisitor(PhantomData))
         } else {
-            deserializer.deserialize_seq(DigestVisitor)
+            deserializer.deserialize_seq(DigestVisitor(PhantomData))
         }
     }
 }
 /// Represents either a COSE key set or a single COSE key
 #[repr(C)]
 #[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub enum CoseKeySetOrKey {
+pub enum CoseKeySetOrKey<'a> {
     /// A set of COSE keys
-    KeySet(Vec<CoseKey>),
+    KeySet(Vec<CoseKey<'a>>),
     /// A single COSE key
-    Key(CoseKey),
+    Key(CoseKey<'a>),
 }
 
-impl CoseKeySetOrKey {
+impl<'a> CoseKeySetOrKey<'a> {
     pub fn is_empty(&self) -> bool {
         match self {
             CoseKeySetOrKey::KeySet(keys) => keys.is_empty(),
@@ -1705,14 +2143,14 @@ impl CoseKeySetOrKey {
         }
     }
 
-    pub fn as_key_set(&self) -> Option<&[CoseKey]> {
+    pub fn as_key_set(&self) -> Option<&[CoseKey<'a>]> {
         match self {
             CoseKeySetOrKey::KeySet(keys) => Some(keys),
             _ => None,
         }
     }
 
-    pub fn as_key(&self) -> Option<&CoseKey> {
+    pub fn as_key(&self) -> Option<&CoseKey<'a>> {
         match self {
             CoseKeySetOrKey::Key(key) => Some(key),
             _ => None,
@@ -1720,7 +2158,7 @@ impl CoseKeySetOrKey {
     }
 }
 
-impl Serialize for CoseKeySetOrKey {
+impl Serialize for CoseKeySetOrKey<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -1732,19 +2170,20 @@ impl Serialize for CoseKeySetOrKey {
     }
 }
 
-impl<'de> Deserialize<'de> for CoseKeySetOrKey {
-    fn deserialize<D>(deserializer: D) -> Result<CoseKeySetOrKey, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for CoseKeySetOrKey<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<CoseKeySetOrKey<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct CoseKeySetOrKeyVisitor {
+        struct CoseKeySetOrKeyVisitor<'a> {
             is_human_readable: bool,
+            marker: PhantomData<&'a ()>,
         }
 
-        impl<'de> Visitor<'de> for CoseKeySetOrKeyVisitor {
-            type Value = CoseKeySetOrKey;
+        impl<'de: 'a, 'a> Visitor<'de> for CoseKeySetOrKeyVisitor<'a> {
+            type Value = CoseKeySetOrKey<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a COSE key (map) or key set (array of keys)")
             }
 
@@ -1785,6 +2224,7 @@ impl<'de> Deserialize<'de> for CoseKeySetOrKey {
         // Use deserialize_any to let serde determine the input type
         deserializer.deserialize_any(CoseKeySetOrKeyVisitor {
             is_human_readable: is_hr,
+            marker: PhantomData,
         })
     }
 }
@@ -1828,11 +2268,11 @@ impl<'de> Deserialize<'de> for CoseKeySetOrKey {
 ///     ]))
 ///     .build().unwrap();
 /// ```
-pub struct CoseKeyBuilder {
-    cose_key: CoseKey,
+pub struct CoseKeyBuilder<'a> {
+    cose_key: CoseKey<'a>,
 }
 
-impl CoseKeyBuilder {
+impl<'a> CoseKeyBuilder<'a> {
     pub fn new() -> Self {
         CoseKeyBuilder {
             cose_key: CoseKey {
@@ -1859,7 +2299,7 @@ impl CoseKeyBuilder {
         self
     }
 
-    pub fn kid(mut self, kid: Bytes) -> Self {
+    pub fn kid(mut self, kid: Bytes<'a>) -> Self {
         self.cose_key.kid = Some(kid);
         self
     }
@@ -1874,7 +2314,7 @@ impl CoseKeyBuilder {
         self
     }
 
-    pub fn base_iv(mut self, base_iv: Bytes) -> Self {
+    pub fn base_iv(mut self, base_iv: Bytes<'a>) -> Self {
         self.cose_key.base_iv = Some(base_iv);
         self
     }
@@ -1884,27 +2324,27 @@ impl CoseKeyBuilder {
         self
     }
 
-    pub fn x(mut self, x: Bytes) -> Self {
+    pub fn x(mut self, x: Bytes<'a>) -> Self {
         self.cose_key.x = Some(x);
         self
     }
 
-    pub fn y(mut self, y: Bytes) -> Self {
+    pub fn y(mut self, y: Bytes<'a>) -> Self {
         self.cose_key.y = Some(y);
         self
     }
 
-    pub fn d(mut self, d: Bytes) -> Self {
+    pub fn d(mut self, d: Bytes<'a>) -> Self {
         self.cose_key.d = Some(d);
         self
     }
 
-    pub fn k(mut self, k: Bytes) -> Self {
+    pub fn k(mut self, k: Bytes<'a>) -> Self {
         self.cose_key.k = Some(k);
         self
     }
 
-    pub fn build(self) -> Result<CoseKey, CoreError> {
+    pub fn build(self) -> Result<CoseKey<'a>, CoreError> {
         match self.cose_key.kty {
             CoseKty::Invalid => {
                 return Err(CoreError::InvalidValue("invalid key type".to_string()));
@@ -2066,7 +2506,7 @@ impl CoseKeyBuilder {
     }
 }
 
-impl Default for CoseKeyBuilder {
+impl Default for CoseKeyBuilder<'_> {
     fn default() -> Self {
         Self::new()
     }
@@ -2075,30 +2515,90 @@ impl Default for CoseKeyBuilder {
 /// Represents a COSE key structure as defined in RFC 8152
 #[derive(Debug, From, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[repr(C)]
-pub struct CoseKey {
+pub struct CoseKey<'a> {
     /// Key type identifier (kty)
     pub kty: CoseKty,
     /// Key identifier (kid)
-    pub kid: Option<Bytes>,
+    pub kid: Option<Bytes<'a>>,
     /// Algorithm identifier (alg)
     pub alg: Option<CoseAlgorithm>,
     /// Allowed operations for this key
     pub key_ops: Option<Vec<CoseKeyOperation>>,
     /// Base initialization vector
-    pub base_iv: Option<Bytes>,
+    pub base_iv: Option<Bytes<'a>>,
     /// COSE curve for OKP/EC2 keys
     pub crv: Option<CoseEllipticCurve>,
     /// Public Key X parameter for OKP/EC2 Keys
-    pub x: Option<Bytes>,
+    pub x: Option<Bytes<'a>>,
     /// Public Key Y parameter for EC2 Keys
-    pub y: Option<Bytes>,
+    pub y: Option<Bytes<'a>>,
     /// Private Key D parameter for OKP/EC2 Keys
-    pub d: Option<Bytes>,
+    pub d: Option<Bytes<'a>>,
     /// Key value for Symmetric Keys
-    pub k: Option<Bytes>,
+    pub k: Option<Bytes<'a>>,
 }
 
-impl Serialize for CoseKey {
+impl CoseKey<'_> {
+    /// Returns the deterministic CBOR encoding of the required public parameters of the key,
+    /// as defined by RFC 9679 for COSE Key thumbprints
+    pub fn thumbprint_input(&self) -> Result<Vec<u8>, CoreError> {
+        let missing = |param: &str| {
+            CoreError::InvalidValue(format!(
+                "COSE key thumbprint requires the \"{param}\" parameter"
+            ))
+        };
+        let bytes = |param: &Option<Bytes>, name: &str| {
+            param
+                .as_ref()
+                .map(|b| ciborium::Value::Bytes(b.to_vec()))
+                .ok_or_else(|| missing(name))
+        };
+        let crv = || {
+            self.crv
+                .clone()
+                .map(|crv| ciborium::Value::from(i64::from(crv)))
+                .ok_or_else(|| missing("crv"))
+        };
+
+        // Labels are listed in deterministic (bytewise lexicographic) encoding order:
+        // 1 (kty), -1, -2, -3.
+        let kty = ciborium::Value::from(i8::from(self.kty.clone()));
+        let params = match self.kty {
+            CoseKty::Okp => vec![(-1, crv()?), (-2, bytes(&self.x, "x")?)],
+            CoseKty::Ec2 => vec![
+                (-1, crv()?),
+                (-2, bytes(&self.x, "x")?),
+                (-3, bytes(&self.y, "y")?),
+            ],
+            CoseKty::Symmetric => vec![(-1, bytes(&self.k, "k")?)],
+            _ => {
+                return Err(CoreError::InvalidValue(format!(
+                    "COSE key thumbprints are not supported for key type {:?}",
+                    self.kty
+                )))
+            }
+        };
+
+        let mut map = vec![(ciborium::Value::from(1), kty)];
+        map.extend(
+            params
+                .into_iter()
+                .map(|(label, value)| (ciborium::Value::from(label), value)),
+        );
+
+        let mut buf = vec![];
+        ciborium::into_writer(&ciborium::Value::Map(map), &mut buf)
+            .map_err(|e| CoreError::InvalidValue(e.to_string()))?;
+        Ok(buf)
+    }
+
+    /// Computes the RFC 9679 COSE Key thumbprint of the key using `alg`
+    pub fn thumbprint(&self, alg: &HashAlgorithm) -> Result<Digest<'static>, CoreError> {
+        Ok(alg.digest(&self.thumbprint_input()?))
+    }
+}
+
+impl Serialize for CoseKey<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -2182,19 +2682,20 @@ impl Serialize for CoseKey {
     }
 }
 
-impl<'de> Deserialize<'de> for CoseKey {
+impl<'de: 'a, 'a> Deserialize<'de> for CoseKey<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct CoseKeyVisitor {
+        struct CoseKeyVisitor<'a> {
             is_human_readable: bool,
+            marker: PhantomData<&'a ()>,
         }
 
-        impl<'de> Visitor<'de> for CoseKeyVisitor {
-            type Value = CoseKey;
+        impl<'de: 'a, 'a> Visitor<'de> for CoseKeyVisitor<'a> {
+            type Value = CoseKey<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a map containing the COSE key")
             }
 
@@ -2319,6 +2820,7 @@ impl<'de> Deserialize<'de> for CoseKey {
         let is_hr = deserializer.is_human_readable();
         deserializer.deserialize_map(CoseKeyVisitor {
             is_human_readable: is_hr,
+            marker: PhantomData,
         })
     }
 }
@@ -2326,22 +2828,22 @@ impl<'de> Deserialize<'de> for CoseKey {
 // When deserializing CoseKey, label -1 may refer to either crv or k field, depending on the key
 // type. Since we cannot guarantee that we'll see the key type (label 1) before label -1, we need
 // to deserialize as this, and then populate the correct field once the key type is known.
-enum CurveOrBytes {
+enum CurveOrBytes<'a> {
     Curve(CoseEllipticCurve),
-    Bytes(Bytes),
+    Bytes(Bytes<'a>),
 }
 
-impl<'de> Deserialize<'de> for CurveOrBytes {
+impl<'de: 'a, 'a> Deserialize<'de> for CurveOrBytes<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct CurveOrBytesVisitor;
+        struct CurveOrBytesVisitor<'a>(PhantomData<&'a ()>);
 
-        impl Visitor<'_> for CurveOrBytesVisitor {
-            type Value = CurveOrBytes;
+        impl<'de: 'a, 'a> Visitor<'de> for CurveOrBytesVisitor<'a> {
+            type Value = CurveOrBytes<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str(
                     "either a byte string key or integer curve ID, depending on kty field",
                 )
@@ -2351,14 +2853,14 @@ impl<'de> Deserialize<'de> for CurveOrBytes {
             where
                 E: de::Error,
             {
-                Ok(CurveOrBytes::Bytes(Bytes::from(v)))
+                Ok(CurveOrBytes::Bytes(Bytes::new(v.to_vec())))
             }
 
-            fn visit_borrowed_bytes<E>(self, v: &'_ [u8]) -> Result<Self::Value, E>
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                self.visit_bytes(v)
+                Ok(CurveOrBytes::Bytes(Bytes::from(v)))
             }
 
             fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
@@ -2382,19 +2884,19 @@ impl<'de> Deserialize<'de> for CurveOrBytes {
             }
         }
 
-        deserializer.deserialize_any(CurveOrBytesVisitor)
+        deserializer.deserialize_any(CurveOrBytesVisitor(PhantomData))
     }
 }
 
 #[derive(Default, Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[repr(C)]
 /// Raw value data structure with associated mask
-pub struct MaskedRawValue {
-    pub value: Bytes,
-    pub mask: Bytes,
+pub struct MaskedRawValue<'a> {
+    pub value: Bytes<'a>,
+    pub mask: Bytes<'a>,
 }
 
-impl Serialize for MaskedRawValue {
+impl Serialize for MaskedRawValue<'_> {
     // Should serialize to the following CDDL:
     //
     // tagged-masked-raw-value = #6.563([
@@ -2413,17 +2915,17 @@ impl Serialize for MaskedRawValue {
     }
 }
 
-impl<'de> Deserialize<'de> for MaskedRawValue {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for MaskedRawValue<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct MaskedRawValueVisitor;
+        struct MaskedRawValueVisitor<'a>(PhantomData<&'a ()>);
 
-        impl<'de> Visitor<'de> for MaskedRawValueVisitor {
-            type Value = MaskedRawValue;
+        impl<'de: 'a, 'a> Visitor<'de> for MaskedRawValueVisitor<'a> {
+            type Value = MaskedRawValue<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a sequence with exactly two elements (value, mask)")
             }
 
@@ -2441,7 +2943,7 @@ impl<'de> Deserialize<'de> for MaskedRawValue {
             }
         }
 
-        deserializer.deserialize_seq(MaskedRawValueVisitor)
+        deserializer.deserialize_seq(MaskedRawValueVisitor(PhantomData))
     }
 }
 
@@ -2450,22 +2952,24 @@ impl<'de> Deserialize<'de> for MaskedRawValue {
 )]
 #[repr(C)]
 /// Container for raw values with optional masking
-pub struct RawValueType {
-    pub raw_value: RawValueTypeChoice,
-    pub raw_value_mask: Option<RawValueMaskType>,
+pub struct RawValueType<'a> {
+    #[serde(borrow)]
+    pub raw_value: RawValueTypeChoice<'a>,
+    #[serde(borrow)]
+    pub raw_value_mask: Option<RawValueMaskType<'a>>,
 }
 
 /// Type alias for raw value masks
-pub type RawValueMaskType = Bytes;
+pub type RawValueMaskType<'a> = Bytes<'a>;
 
 #[derive(Debug, From, PartialEq, Eq, PartialOrd, Ord, Clone)]
 /// Represents different types of raw values
-pub enum RawValueTypeChoice {
-    TaggedBytes(TaggedBytes),
-    TaggedMaskedRawValue(TaggedMaskedRawValue),
+pub enum RawValueTypeChoice<'a> {
+    TaggedBytes(TaggedBytes<'a>),
+    TaggedMaskedRawValue(TaggedMaskedRawValue<'a>),
 }
 
-impl RawValueTypeChoice {
+impl RawValueTypeChoice<'_> {
     pub fn as_bytes(&self) -> Option<&[u8]> {
         match self {
             Self::TaggedBytes(tagged_bytes) => Some(tagged_bytes.as_ref().as_ref()),
@@ -2481,7 +2985,7 @@ impl RawValueTypeChoice {
     }
 }
 
-impl Serialize for RawValueTypeChoice {
+impl Serialize for RawValueTypeChoice<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -2495,8 +2999,8 @@ impl Serialize for RawValueTypeChoice {
     }
 }
 
-impl<'de> Deserialize<'de> for RawValueTypeChoice {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for RawValueTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -2520,36 +3024,28 @@ impl<'de> Deserialize<'de> for RawValueTypeChoice {
                 ))),
             }
         } else {
-            match ciborium::Value::deserialize(deserializer)? {
-                ciborium::Value::Tag(tag, inner) => {
-                    // Re-serializing the inner Value so that we can deserialize it
-                    // into an appropriate type, once we figure out what that is
-                    // based on the tag.
-                    let mut buf: Vec<u8> = Vec::new();
-                    ciborium::into_writer(&inner, &mut buf).unwrap();
-
-                    match tag {
-                        560 => {
-                            let bytes: Bytes =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(RawValueTypeChoice::TaggedBytes(TaggedBytes::from(bytes)))
-                        }
-                        563 => {
-                            let mrv: MaskedRawValue =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(RawValueTypeChoice::TaggedMaskedRawValue(
-                                TaggedMaskedRawValue::from(mrv),
-                            ))
-                        }
-                        n => Err(de::Error::custom(format!(
-                            "unexpected RawValueTypeChoice tag {n}"
-                        ))),
-                    }
-                }
-                _ => Err(de::Error::custom(
-                    "did not see a tag for RawValueTypeChoice",
-                )),
-            }
+            deserialize_tag_choice(deserializer)
+        }
+    }
+}
+
+impl<'de: 'a, 'a> TagChoice<'de> for RawValueTypeChoice<'a> {
+    const NAME: &'static str = "RawValueTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            560 => Ok(RawValueTypeChoice::TaggedBytes(TaggedBytes::from(
+                Bytes::deserialize(deserializer)?,
+            ))),
+            563 => Ok(RawValueTypeChoice::TaggedMaskedRawValue(
+                TaggedMaskedRawValue::from(MaskedRawValue::deserialize(deserializer)?),
+            )),
+            n => Err(de::Error::custom(format!(
+                "unexpected RawValueTypeChoice tag {n}"
+            ))),
         }
     }
 }
@@ -2562,6 +3058,221 @@ pub(crate) struct TaggedJsonValue<'a> {
     pub value: &'a serde_json::value::RawValue,
 }
 
+/// Writes `value` as a `{"type": name, "value": value}` object, the JSON form used for the
+/// non-text alternatives of identifier choices.
+pub(crate) fn serialize_json_typed<S, T>(
+    serializer: S,
+    name: &str,
+    value: &T,
+) -> core::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + ?Sized,
+{
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("type", name)?;
+    map.serialize_entry("value", value)?;
+    map.end()
+}
+
+/// Splits a JSON `{"type": name, "value": value}` object into its name and value.
+pub(crate) fn json_typed_value<E: de::Error>(
+    mut map: serde_json::Map<String, serde_json::Value>,
+) -> core::result::Result<(String, serde_json::Value), E> {
+    match (map.remove("type"), map.remove("value")) {
+        (Some(serde_json::Value::String(typ)), Some(value)) if map.is_empty() => Ok((typ, value)),
+        _ => Err(E::custom(
+            "expected an object with only \"type\" and \"value\" fields",
+        )),
+    }
+}
+
+/// A type choice whose CBOR alternatives are told apart by their tag. [`deserialize_tag_choice`]
+/// reads the tag number and then decodes the alternative it selects straight from the input,
+/// without buffering the tagged value.
+pub(crate) trait TagChoice<'de>: Sized {
+    /// The name of the choice, used in error messages
+    const NAME: &'static str;
+
+    /// Deserializes the alternative identified by `tag` from the tagged value
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+
+    /// Deserializes an untagged item, which is an error unless the choice has an alternative for
+    /// untagged extension values.
+    fn untagged<D>(_deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Err(de::Error::custom(format!(
+            "did not see a tag for {}",
+            Self::NAME
+        )))
+    }
+}
+
+/// Deserializes a [`TagChoice`] from CBOR in a single pass
+pub(crate) fn deserialize_tag_choice<'de, D, T>(
+    deserializer: D,
+) -> core::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TagChoice<'de>,
+{
+    deserializer.deserialize_any(TagChoiceVisitor(PhantomData))
+}
+
+struct TagChoiceVisitor<T>(PhantomData<T>);
+
+impl<'de, T: TagChoice<'de>> Visitor<'de> for TagChoiceVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "a tagged {}", T::NAME)
+    }
+
+    // ciborium presents a tagged item as an enum whose tuple variant yields the tag number
+    // followed by the tagged value.
+    fn visit_enum<A>(self, data: A) -> core::result::Result<T, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        let (_, variant) = data.variant::<de::IgnoredAny>()?;
+        de::VariantAccess::tuple_variant(variant, 2, TaggedVisitor(PhantomData))
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> core::result::Result<T, E> {
+        T::untagged(Untagged(v.into_deserializer()))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> core::result::Result<T, E> {
+        T::untagged(Untagged(v.into_deserializer()))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> core::result::Result<T, E> {
+        T::untagged(Untagged(v.into_deserializer()))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> core::result::Result<T, E> {
+        T::untagged(Untagged(v.into_deserializer()))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> core::result::Result<T, E> {
+        T::untagged(Untagged(v.into_deserializer()))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> core::result::Result<T, E> {
+        T::untagged(Untagged(v.into_deserializer()))
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> core::result::Result<T, E> {
+        T::untagged(Untagged(de::value::BorrowedStrDeserializer::new(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> core::result::Result<T, E> {
+        T::untagged(Untagged(v.into_deserializer()))
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> core::result::Result<T, E> {
+        T::untagged(Untagged(de::value::BorrowedBytesDeserializer::new(v)))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> core::result::Result<T, E> {
+        T::untagged(Untagged(de::value::BytesDeserializer::new(v)))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> core::result::Result<T, E> {
+        T::untagged(Untagged(().into_deserializer()))
+    }
+
+    fn visit_none<E: de::Error>(self) -> core::result::Result<T, E> {
+        T::untagged(Untagged(().into_deserializer()))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> core::result::Result<T, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        T::untagged(Untagged(de::value::SeqAccessDeserializer::new(seq)))
+    }
+
+    fn visit_map<A>(self, map: A) -> core::result::Result<T, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        T::untagged(Untagged(de::value::MapAccessDeserializer::new(map)))
+    }
+}
+
+/// Hands an untagged item that [`TagChoiceVisitor`] has started to read on to
+/// [`TagChoice::untagged`]. serde's value deserializers report themselves as human-readable,
+/// which would select the JSON forms of the types read from them.
+struct Untagged<D>(D);
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Untagged<D> {
+    type Error = D::Error;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_any(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any enum
+    }
+}
+
+/// Reads the tag number and the tagged value of a tagged item
+struct TaggedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: TagChoice<'de>> Visitor<'de> for TaggedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(formatter, "a tagged {}", T::NAME)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> core::result::Result<T, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let tag: u64 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::custom("missing tag"))?;
+        seq.next_element_seed(TaggedSeed {
+            tag,
+            marker: PhantomData,
+        })?
+        .ok_or_else(|| de::Error::custom("missing tagged value"))
+    }
+}
+
+/// Deserializes the alternative of a [`TagChoice`] selected by `tag`
+struct TaggedSeed<T> {
+    tag: u64,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: TagChoice<'de>> de::DeserializeSeed<'de> for TaggedSeed<T> {
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> core::result::Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::tagged(self.tag, deserializer)
+    }
+}
+
 /// Version scheme enumeration as defined in the specification
 #[repr(i64)]
 #[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -2664,7 +3375,7 @@ impl TryFrom<String> for VersionScheme<'_> {
 }
 
 impl Display for VersionScheme<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let tmp: String;
 
         let name = match self {
@@ -2704,7 +3415,7 @@ impl Serialize for VersionScheme<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for VersionScheme<'_> {
+impl<'de: 'a, 'a> Deserialize<'de> for VersionScheme<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -2713,10 +3424,10 @@ impl<'de> Deserialize<'de> for VersionScheme<'_> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'a> Visitor<'_> for VersionSchemeVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for VersionSchemeVisitor<'a> {
             type Value = VersionScheme<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("int or string VersionScheme identifier")
             }
 
@@ -2745,11 +3456,11 @@ impl<'de> Deserialize<'de> for VersionScheme<'_> {
                 VersionScheme::try_from(v.to_owned()).map_err(de::Error::custom)
             }
 
-            fn visit_borrowed_str<E>(self, v: &'_ str) -> Result<Self::Value, E>
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                self.visit_str(v)
+                VersionScheme::try_from(v).map_err(de::Error::custom)
             }
 
             fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
@@ -2823,6 +3534,193 @@ impl HashAlgorithm {
             _ => None,
         }
     }
+
+    /// Returns a [`Hasher`] for incrementally computing a digest with this algorithm
+    pub fn hasher(&self) -> Hasher {
+        Hasher::new(self.clone())
+    }
+
+    /// Computes the [`Digest`] of `data` using this algorithm
+    ///
+    /// ```rust
+    /// use corim_rs::core::HashAlgorithm;
+    ///
+    /// let digest = HashAlgorithm::Sha256_32.digest(b"abc");
+    ///
+    /// assert_eq!(digest.alg, HashAlgorithm::Sha256_32);
+    /// assert_eq!(digest.val.as_ref(), &[0xba, 0x78, 0x16, 0xbf]);
+    /// ```
+    pub fn digest(&self, data: &[u8]) -> Digest<'static> {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Returns the length in bytes of digests produced by this algorithm
+    pub fn output_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha256_128 => 16,
+            HashAlgorithm::Sha256_120 => 15,
+            HashAlgorithm::Sha256_96 => 12,
+            HashAlgorithm::Sha256_64 => 8,
+            HashAlgorithm::Sha256_32 => 4,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+            HashAlgorithm::Sha3_224 => 28,
+            HashAlgorithm::Sha3_256 => 32,
+            HashAlgorithm::Sha3_384 => 48,
+            HashAlgorithm::Sha3_512 => 64,
+            HashAlgorithm::Blake2s256 => 32,
+            HashAlgorithm::Blake2b256 => 32,
+            HashAlgorithm::Blake2b512 => 64,
+            HashAlgorithm::K12_256 => 32,
+            HashAlgorithm::K12_512 => 64,
+        }
+    }
+}
+
+/// Incrementally computes a [`Digest`] using a [`HashAlgorithm`]. Data may be supplied
+/// through [`Hasher::update`] or, with the `std` feature, by copying from a reader as the
+/// hasher implements `std::io::Write`.
+///
+/// The truncated SHA-256 algorithms (e.g. [`HashAlgorithm::Sha256_64`]) produce the
+/// leftmost bytes of the full SHA-256 hash, as specified by RFC 6920.
+///
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// use corim_rs::core::{Hasher, HashAlgorithm};
+///
+/// let mut hasher = Hasher::new(HashAlgorithm::Sha384);
+/// std::io::copy(&mut "some data".as_bytes(), &mut hasher).unwrap();
+///
+/// assert_eq!(hasher.finalize(), HashAlgorithm::Sha384.digest(b"some data"));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Hasher {
+    alg: HashAlgorithm,
+    state: HasherState,
+}
+
+#[derive(Clone)]
+enum HasherState {
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+    Sha3_224(sha3::Sha3_224),
+    Sha3_256(sha3::Sha3_256),
+    Sha3_384(sha3::Sha3_384),
+    Sha3_512(sha3::Sha3_512),
+    Blake2s256(blake2::Blake2s256),
+    Blake2b256(blake2::Blake2b<blake2::digest::consts::U32>),
+    Blake2b512(blake2::Blake2b512),
+    K12(tiny_keccak::KangarooTwelve<&'static [u8]>),
+}
+
+impl Hasher {
+    /// Creates a new hasher for `alg`
+    pub fn new(alg: HashAlgorithm) -> Self {
+        use sha2::Digest as _;
+
+        let state = match alg {
+            HashAlgorithm::Sha256
+            | HashAlgorithm::Sha256_128
+            | HashAlgorithm::Sha256_120
+            | HashAlgorithm::Sha256_96
+            | HashAlgorithm::Sha256_64
+            | HashAlgorithm::Sha256_32 => HasherState::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha384 => HasherState::Sha384(sha2::Sha384::new()),
+            HashAlgorithm::Sha512 => HasherState::Sha512(sha2::Sha512::new()),
+            HashAlgorithm::Sha3_224 => HasherState::Sha3_224(sha3::Sha3_224::new()),
+            HashAlgorithm::Sha3_256 => HasherState::Sha3_256(sha3::Sha3_256::new()),
+            HashAlgorithm::Sha3_384 => HasherState::Sha3_384(sha3::Sha3_384::new()),
+            HashAlgorithm::Sha3_512 => HasherState::Sha3_512(sha3::Sha3_512::new()),
+            HashAlgorithm::Blake2s256 => HasherState::Blake2s256(blake2::Blake2s256::new()),
+            HashAlgorithm::Blake2b256 => HasherState::Blake2b256(blake2::Blake2b::new()),
+            HashAlgorithm::Blake2b512 => HasherState::Blake2b512(blake2::Blake2b512::new()),
+            HashAlgorithm::K12_256 | HashAlgorithm::K12_512 => {
+                HasherState::K12(tiny_keccak::KangarooTwelve::new(b""))
+            }
+        };
+
+        Self { alg, state }
+    }
+
+    /// Returns the algorithm used by this hasher
+    pub fn algorithm(&self) -> &HashAlgorithm {
+        &self.alg
+    }
+
+    /// Feeds `data` into the hasher
+    pub fn update(&mut self, data: &[u8]) {
+        use sha2::Digest as _;
+        use tiny_keccak::Hasher as _;
+
+        match &mut self.state {
+            HasherState::Sha256(h) => h.update(data),
+            HasherState::Sha384(h) => h.update(data),
+            HasherState::Sha512(h) => h.update(data),
+            HasherState::Sha3_224(h) => h.update(data),
+            HasherState::Sha3_256(h) => h.update(data),
+            HasherState::Sha3_384(h) => h.update(data),
+            HasherState::Sha3_512(h) => h.update(data),
+            HasherState::Blake2s256(h) => h.update(data),
+            HasherState::Blake2b256(h) => h.update(data),
+            HasherState::Blake2b512(h) => h.update(data),
+            HasherState::K12(h) => h.update(data),
+        }
+    }
+
+    /// Consumes the hasher and returns the [`Digest`] of the data fed into it
+    pub fn finalize(self) -> Digest<'static> {
+        use sha2::Digest as _;
+        use tiny_keccak::Hasher as _;
+
+        let len = self.alg.output_len();
+        let mut val = match self.state {
+            HasherState::Sha256(h) => h.finalize().to_vec(),
+            HasherState::Sha384(h) => h.finalize().to_vec(),
+            HasherState::Sha512(h) => h.finalize().to_vec(),
+            HasherState::Sha3_224(h) => h.finalize().to_vec(),
+            HasherState::Sha3_256(h) => h.finalize().to_vec(),
+            HasherState::Sha3_384(h) => h.finalize().to_vec(),
+            HasherState::Sha3_512(h) => h.finalize().to_vec(),
+            HasherState::Blake2s256(h) => h.finalize().to_vec(),
+            HasherState::Blake2b256(h) => h.finalize().to_vec(),
+            HasherState::Blake2b512(h) => h.finalize().to_vec(),
+            HasherState::K12(h) => {
+                let mut out = vec![0; len];
+                h.finalize(&mut out);
+                out
+            }
+        };
+
+        val.truncate(len);
+
+        Digest {
+            alg: self.alg,
+            val: val.into(),
+        }
+    }
+}
+
+impl core::fmt::Debug for Hasher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Hasher").field("alg", &self.alg).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 impl TryFrom<u8> for HashAlgorithm {
@@ -2906,8 +3804,8 @@ impl TryFrom<String> for HashAlgorithm {
     }
 }
 
-impl std::fmt::Display for HashAlgorithm {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let s = match *self {
             HashAlgorithm::Sha256 => "sha-256",
             HashAlgorithm::Sha256_128 => "sha-256-128",
@@ -2958,7 +3856,7 @@ impl<'de> Deserialize<'de> for HashAlgorithm {
         impl Visitor<'_> for HashAlgorithmVisitor {
             type Value = HashAlgorithm;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str(
                     "an integer ID or a string name form the IANA hash algorithm registry",
                 )
@@ -3494,7 +4392,7 @@ impl TryFrom<&str> for CoseAlgorithm {
 }
 
 impl Display for CoseAlgorithm {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let s: String;
 
         let name = match self {
@@ -3683,7 +4581,7 @@ impl TryFrom<&str> for CoseKty {
 }
 
 impl Display for CoseKty {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let kty = match self {
             CoseKty::Invalid => "<INVALID>",
             CoseKty::Okp => "OKP",
@@ -3822,7 +4720,7 @@ impl TryFrom<&str> for CoseKeyOperation {
 }
 
 impl Display for CoseKeyOperation {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let op = match self {
             CoseKeyOperation::Sign => "sign",
             CoseKeyOperation::Verify => "verify",
@@ -3865,7 +4763,7 @@ impl<'de> Deserialize<'de> for CoseKeyOperation {
         impl Visitor<'_> for CoseKeyOpsVisitor {
             type Value = CoseKeyOperation;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a string or integer COSE key operations identifier")
             }
 
@@ -4081,7 +4979,7 @@ impl TryFrom<&str> for CoseEllipticCurve {
 }
 
 impl Display for CoseEllipticCurve {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let s: String;
 
         let name = match self {
@@ -4175,12 +5073,96 @@ where
     }
 }
 
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC time, e.g. `2024-01-01T00:00:00Z`
+#[cfg(feature = "std")]
+pub(crate) fn format_rfc3339(seconds: i128) -> String {
+    let (days, seconds) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+
+    // Howard Hinnant's civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i128::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Parses an RFC 3339 time into seconds since the Unix epoch, ignoring fractional seconds
+#[cfg(feature = "std")]
+pub(crate) fn parse_rfc3339(text: &str) -> Option<i128> {
+    let number = |range: core::ops::Range<usize>| -> Option<i128> {
+        let digits = text.get(range)?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    if separators
+        .iter()
+        .any(|&(index, separator)| text.as_bytes().get(index) != Some(&separator))
+        || !matches!(text.as_bytes().get(10), Some(b'T' | b't' | b' '))
+    {
+        return None;
+    }
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    let mut rest = &text[19..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        rest = fraction.trim_start_matches(|c: char| c.is_ascii_digit());
+    }
+    let offset = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), ..] if rest.len() == 6 && rest.as_bytes()[3] == b':' => {
+            let offset = number(text.len() - 5..text.len() - 3)? * 3_600
+                + number(text.len() - 2..text.len())? * 60;
+            if *sign == b'+' {
+                offset
+            } else {
+                -offset
+            }
+        }
+        _ => return None,
+    };
+
+    // Howard Hinnant's days_from_civil
+    let year = year - i128::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second - offset)
+}
+
 #[cfg(test)]
 #[rustfmt::skip::macros(vec)]
 mod tests {
     use super::*;
     mod hash_entry {
-        use super::{CoseAlgorithm, HashEntry};
+        use super::{CoseAlgorithm, Cow, HashEntry};
         #[test]
         fn test_hash_entry_serialize() {
             /*********************************************************************
@@ -4201,6 +5183,10 @@ mod tests {
             ciborium::into_writer(&hash_entry, &mut actual).unwrap();
 
             assert_eq!(actual.as_slice(), expected.as_slice());
+
+            let hash_entry_de: HashEntry = crate::cbor::from_slice(&expected).unwrap();
+            assert_eq!(hash_entry_de, hash_entry);
+            assert!(matches!(hash_entry_de.hash_value.bytes, Cow::Borrowed(_)));
         }
     }
 
@@ -4233,7 +5219,7 @@ mod tests {
 
             let expected: super::Bytes = vec![1, 2, 3, 4, 5].into();
 
-            let actual: super::Bytes = ciborium::from_reader(&input[..]).unwrap();
+            let actual: super::Bytes = crate::cbor::from_slice(&input[..]).unwrap();
 
             assert_eq!(actual, expected);
         }
@@ -4297,6 +5283,129 @@ mod tests {
         }
     }
 
+    mod text_or_bytes {
+        use super::super::*;
+
+        #[test]
+        fn test_text_or_bytes_sized_serde() {
+            let values: Vec<TextOrBytesSized<16>> = vec![
+                TextOrBytesSized::Text("swid-123".into()),
+                TextOrBytesSized::Bytes(FixedBytes([0xAB; 16])),
+            ];
+
+            let mut actual: Vec<u8> = vec![];
+            ciborium::into_writer(&values, &mut actual).unwrap();
+            assert_eq!(&actual[..2], &[0x82, 0x68]);
+            assert_eq!(&actual[10..12], &[0x50, 0xAB]);
+
+            let values_de: Vec<TextOrBytesSized<16>> =
+                crate::cbor::from_slice(actual.as_slice()).unwrap();
+            assert_eq!(values_de, values);
+
+            let json = serde_json::to_string(&values).unwrap();
+            assert_eq!(
+                json,
+                r#"["swid-123",{"type":"bytes","value":"q6urq6urq6urq6urq6urqw"}]"#
+            );
+
+            let values_de: Vec<TextOrBytesSized<16>> = serde_json::from_str(&json).unwrap();
+            assert_eq!(values_de, values);
+
+            assert!(serde_json::from_str::<TextOrBytesSized<16>>(
+                r#"{"type":"bytes","value":"AQID"}"#
+            )
+            .is_err());
+            assert!(
+                crate::cbor::from_slice::<TextOrBytesSized<16>>([0x43, 1, 2, 3].as_slice())
+                    .is_err()
+            );
+        }
+    }
+
+    mod hash_algorithm {
+        use super::super::*;
+
+        fn from_hex(hex: &str) -> Vec<u8> {
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+                .collect()
+        }
+
+        #[test]
+        fn test_digest() {
+            let test_cases = vec![
+                (HashAlgorithm::Sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+                (HashAlgorithm::Sha256_128, "ba7816bf8f01cfea414140de5dae2223"),
+                (HashAlgorithm::Sha256_120, "ba7816bf8f01cfea414140de5dae22"),
+                (HashAlgorithm::Sha256_96, "ba7816bf8f01cfea414140de"),
+                (HashAlgorithm::Sha256_64, "ba7816bf8f01cfea"),
+                (HashAlgorithm::Sha256_32, "ba7816bf"),
+                (HashAlgorithm::Sha384, "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"),
+                (HashAlgorithm::Sha512, "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+                (HashAlgorithm::Sha3_224, "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"),
+                (HashAlgorithm::Sha3_256, "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+                (HashAlgorithm::Sha3_384, "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"),
+                (HashAlgorithm::Sha3_512, "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"),
+                (HashAlgorithm::Blake2s256, "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"),
+                (HashAlgorithm::Blake2b256, "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"),
+                (HashAlgorithm::Blake2b512, "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"),
+            ];
+
+            for (alg, expected) in test_cases.into_iter() {
+                let actual = alg.digest(b"abc");
+
+                assert_eq!(actual.alg, alg);
+                assert_eq!(actual.val.as_ref(), &from_hex(expected)[..], "{alg}");
+                assert_eq!(actual.val.len(), alg.output_len());
+                assert!(actual.matches(b"abc"));
+                assert!(!actual.matches(b"abd"));
+            }
+        }
+
+        #[test]
+        fn test_digest_k12() {
+            // KangarooTwelve test vectors for an empty message and customization string
+            let expected = from_hex(concat!(
+                "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5",
+                "4269c056b8c82e48276038b6d292966cc07a3d4645272e31ff38508139eb0a71",
+            ));
+
+            assert_eq!(
+                HashAlgorithm::K12_256.digest(b"").val.as_ref(),
+                &expected[..32]
+            );
+            assert_eq!(
+                HashAlgorithm::K12_512.digest(b"").val.as_ref(),
+                &expected[..]
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "std")]
+        fn test_hasher_write() {
+            use std::io::Write;
+
+            let data = vec![0x5a; 10_000];
+
+            for alg in [
+                HashAlgorithm::Sha256_96,
+                HashAlgorithm::Sha3_384,
+                HashAlgorithm::K12_256,
+            ] {
+                let mut hasher = alg.hasher();
+
+                for chunk in data.chunks(333) {
+                    hasher.write_all(chunk).unwrap();
+                }
+                hasher.flush().unwrap();
+
+                assert_eq!(hasher.algorithm(), &alg);
+                assert_eq!(hasher.finalize(), alg.digest(&data));
+            }
+        }
+    }
+
     mod ueid {
         use super::super::*;
 
@@ -4417,7 +5526,7 @@ mod tests {
             let expected = IntegerTime::from(value);
             let bytes: [u8; 6] = [0xC1, 0x1A, 0x5E, 0x2C, 0xE3, 0x00]; // C1 1A 0x5E, 0x2C, 0xE3, 0x00 = Tag 1, 1580000000
                                                                        // Deserialize
-            let actual: IntegerTime = ciborium::from_reader(bytes.as_slice()).unwrap();
+            let actual: IntegerTime = crate::cbor::from_slice(bytes.as_slice()).unwrap();
             assert_eq!(expected, actual);
             // Serialize (note: might use shorter encoding)
             let mut buffer = Vec::new();
@@ -4430,7 +5539,7 @@ mod tests {
             let expected = IntegerTime::from(value);
             let bytes: [u8; 2] = [0xC1, 0x17]; // C1 17 = Tag 1, 23
                                                // Deserialize
-            let actual: IntegerTime = ciborium::from_reader(bytes.as_slice()).unwrap();
+            let actual: IntegerTime = crate::cbor::from_slice(bytes.as_slice()).unwrap();
             assert_eq!(expected, actual);
             // Serialize
             let mut buffer = Vec::new();
@@ -4445,7 +5554,7 @@ mod tests {
             let expected = IntegerTime::from(value);
             let bytes: [u8; 3] = [0xC1, 0x18, 0xFF]; // C1 18 FF = Tag 1, 255
                                                      // Deserialize
-            let actual: IntegerTime = ciborium::from_reader(bytes.as_slice()).unwrap();
+            let actual: IntegerTime = crate::cbor::from_slice(bytes.as_slice()).unwrap();
             assert_eq!(expected, actual);
             // Serialize
             let mut buffer = Vec::new();
@@ -4457,7 +5566,7 @@ mod tests {
         fn test_deserialize_integer_time_3_bytes() {
             let expected: IntegerTime = Integer::from(1000i32).into();
             let bytes: [u8; 4] = [0xC1, 0x19, 0x03, 0xE8]; // 1000 = 0x03E8
-            let actual: IntegerTime = ciborium::from_reader(bytes.as_slice()).unwrap();
+            let actual: IntegerTime = crate::cbor::from_slice(bytes.as_slice()).unwrap();
             assert_eq!(expected, actual);
         }
 
@@ -4468,7 +5577,7 @@ mod tests {
             let expected = IntegerTime::from(value);
             let bytes: [u8; 6] = [0xC1, 0x1A, 0x00, 0x00, 0x03, 0xE8]; // C1 1A 00 03 E8 = Tag 1, 1000
                                                                        // Deserialize
-            let actual: IntegerTime = ciborium::from_reader(bytes.as_slice()).unwrap();
+            let actual: IntegerTime = crate::cbor::from_slice(bytes.as_slice()).unwrap();
             assert_eq!(expected, actual);
             // Serialize (note: might use shorter encoding)
             let mut buffer = Vec::new();
@@ -4483,7 +5592,7 @@ mod tests {
             let expected = IntegerTime::from(value);
             let bytes: [u8; 6] = [0xC1, 0x1A, 0x5E, 0x2C, 0xE3, 0x00]; // C1 1A 00 03 E8 = Tag 1, 1000
                                                                        // Deserialize
-            let actual: IntegerTime = ciborium::from_reader(bytes.as_slice()).unwrap();
+            let actual: IntegerTime = crate::cbor::from_slice(bytes.as_slice()).unwrap();
             assert_eq!(expected, actual);
             // Serialize (note: might use shorter encoding)
             let mut buffer = Vec::new();
@@ -4511,7 +5620,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             // Deserialize
-            let actual: Uri = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: Uri = crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
 
             // Verify inner value
@@ -4541,7 +5650,8 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             // Deserialize
-            let actual: TaggedUuidType = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: TaggedUuidType =
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
 
             // Verify inner value
@@ -4567,7 +5677,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             // Deserialize
-            let actual: OidType = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: OidType = crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
 
             // Verify inner value
@@ -4614,7 +5724,8 @@ mod tests {
             ciborium::into_writer(&expected, &mut buffer).unwrap();
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
-            let actual: TaggedUeidType = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: TaggedUeidType =
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4631,7 +5742,7 @@ mod tests {
             ciborium::into_writer(&expected, &mut buffer).unwrap();
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
-            let actual: SvnType = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: SvnType = crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4648,7 +5759,7 @@ mod tests {
             ciborium::into_writer(&expected, &mut buffer).unwrap();
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
-            let actual: MinSvnType = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: MinSvnType = crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4671,7 +5782,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             let actual: PkixBase64KeyType =
-                ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4694,7 +5805,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             let actual: PkixBase64CertType =
-                ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4717,7 +5828,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             let actual: PkixBase64CertPathType =
-                ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4743,7 +5854,8 @@ mod tests {
             ciborium::into_writer(&expected, &mut buffer).unwrap();
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
-            let actual: ThumbprintType = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: ThumbprintType =
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4798,7 +5910,7 @@ mod tests {
             ciborium::into_writer(&expected, &mut buffer).unwrap();
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
-            let actual: CoseKeyType = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: CoseKeyType = crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4824,7 +5936,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             let actual: CertThumbprintType =
-                ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4843,7 +5955,7 @@ mod tests {
             ciborium::into_writer(&expected, &mut buffer).unwrap();
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
-            let actual: TaggedBytes = ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+            let actual: TaggedBytes = crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
 
             // Verify inner value
@@ -4872,7 +5984,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             let actual: CertPathThumbprintType =
-                ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4894,7 +6006,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             let actual: PkixAsn1DerCertType =
-                ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
         }
 
@@ -4925,7 +6037,7 @@ mod tests {
             assert_eq!(buffer, expected_bytes, "Serialization mismatch");
 
             let actual: TaggedMaskedRawValue =
-                ciborium::from_reader(expected_bytes.as_slice()).unwrap();
+                crate::cbor::from_slice(expected_bytes.as_slice()).unwrap();
             assert_eq!(expected, actual, "Deserialization mismatch");
 
             // Verify inner values
@@ -4956,7 +6068,7 @@ mod tests {
 
             assert_eq!(buffer, expected);
 
-            let alg: CoseAlgorithm = ciborium::from_reader(expected.as_slice()).unwrap();
+            let alg: CoseAlgorithm = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
             assert_eq!(alg, CoseAlgorithm::Sha512_256);
 
@@ -4984,7 +6096,7 @@ mod tests {
 
             assert_eq!(buffer, expected);
 
-            let alg_de: CoseAlgorithm = ciborium::from_reader(expected.as_slice()).unwrap();
+            let alg_de: CoseAlgorithm = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
             assert_eq!(alg_de, alg);
 
@@ -5029,7 +6141,7 @@ mod tests {
 
             assert_eq!(buffer, expected);
 
-            let kty: CoseKty = ciborium::from_reader(expected.as_slice()).unwrap();
+            let kty: CoseKty = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
             assert_eq!(kty, CoseKty::Okp);
 
@@ -5055,7 +6167,7 @@ mod tests {
 
             assert_eq!(buffer, expected);
 
-            let op: CoseKeyOperation = ciborium::from_reader(expected.as_slice()).unwrap();
+            let op: CoseKeyOperation = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
             assert_eq!(op, CoseKeyOperation::Sign);
 
@@ -5083,7 +6195,7 @@ mod tests {
 
             assert_eq!(buffer, expected);
 
-            let curve_de: CoseEllipticCurve = ciborium::from_reader(expected.as_slice()).unwrap();
+            let curve_de: CoseEllipticCurve = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
             assert_eq!(curve_de, curve);
 
@@ -5131,7 +6243,7 @@ mod tests {
 
         assert_eq!(actual, expected);
 
-        let vs_de: VersionScheme = ciborium::from_reader(expected.as_slice()).unwrap();
+        let vs_de: VersionScheme = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(vs_de, vs);
 
@@ -5157,7 +6269,7 @@ mod tests {
 
         assert_eq!(actual, expected);
 
-        let vs_de: VersionScheme = ciborium::from_reader(expected.as_slice()).unwrap();
+        let vs_de: VersionScheme = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(vs_de, vs);
 
@@ -5182,7 +6294,7 @@ mod tests {
 
         assert_eq!(actual, expected);
 
-        let vs_de: VersionScheme = ciborium::from_reader(expected.as_slice()).unwrap();
+        let vs_de: VersionScheme = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(vs_de, vs);
 
@@ -5262,7 +6374,7 @@ mod tests {
                     ExtensionValue::Uint(1.into()),
                     ExtensionValue::Uint(2.into()),
                     ExtensionValue::Uint(3.into()),
-                ].into()),
+                ]),
                 expected_json: "[1,2,3]",
                 expected_cbor: vec![
                     0x83, // array(3)
@@ -5303,7 +6415,7 @@ mod tests {
             assert_eq!(actual_cbor, tc.expected_cbor);
 
             let extension_de: ExtensionValue =
-                ciborium::from_reader(actual_cbor.as_slice()).unwrap();
+                crate::cbor::from_slice(actual_cbor.as_slice()).unwrap();
 
             assert_eq!(extension_de, tc.extension);
 
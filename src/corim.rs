@@ -11,12 +11,14 @@
 //! - [`Corim`] - The top-level type representing either a signed or unsigned manifest
 //! - [`CorimMap`] - The main manifest structure containing tags and metadata (CBOR tag 501)
 //! - [`COSESign1Corim`] - A signed manifest wrapper using COSE_Sign1 (CBOR tag 18)
+//! - [`DetachedCOSESign1Corim`] - A COSE_Sign1 signed manifest whose payload is carried separately
+//! - [`COSESignCorim`] - A manifest signed by multiple parties using COSE_Sign (CBOR tag 98)
 //!
 //! ## Key Features
 //!
 //! * **Multiple Tag Types**: Support for CoSWID, CoMID, and CoTL tags
 //! * **Flexible Identification**: Manifests can be identified by UUID or string
-//! * **Signing Support**: Both signed (COSE_Sign1) and unsigned manifests
+//! * **Signing Support**: Unsigned, single-signer (COSE_Sign1) and multi-signer (COSE_Sign) manifests
 //! * **Validity Periods**: Optional time-based validity for manifests and signatures
 //! * **Entity Attribution**: Track manifest creators and signers
 //! * **Extensibility**: Extension points for future capabilities
@@ -36,11 +38,18 @@
 //! │   ├── entities
 //! │   └── extension
 //! │
-//! └── COSESign1Corim (signed)
+//! ├── COSESign1Corim (signed)
+//! │   ├── protected
+//! │   ├── unprotected
+//! │   ├── payload
+//! │   └── signature
+//! │
+//! └── COSESignCorim (multi-signed)
 //!     ├── protected
 //!     ├── unprotected
 //!     ├── payload
-//!     └── signature
+//!     └── signatures
+//!         └── protected, unprotected, signature
 //! ```
 //!
 //! ## Example Usage
@@ -69,34 +78,54 @@
 //! This implementation uses the following CBOR tags:
 //! - 501: Unsigned CoRIM manifest
 //! - 18: COSE_Sign1 signed manifest
+//! - 98: COSE_Sign multi-signer manifest
 //!
 //! ## Specification Compliance
 //!
 //! This implementation adheres to the CoRIM specification and supports all mandatory
 //! and optional fields defined in the standard.
 
-use std::{collections::BTreeMap, fmt};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
 
 use crate::{
     comid::ConciseMidTag,
-    core::{Bytes, Label, ObjectIdentifier, TaggedJsonValue},
+    core::{
+        deserialize_tag_choice, deserialize_text, json_typed_value, serialize_json_typed,
+        BorrowText, Bytes, ExtensionValue, Label, ObjectIdentifier, TagChoice, TaggedJsonValue,
+    },
     coswid::ConciseSwidTag,
     cotl::ConciseTlTag,
-    empty_map_as_none, generate_tagged, Digest, ExtensionMap, Int, OidType, TaggedBytes,
-    TaggedConciseMidTag, TaggedConciseSwidTag, TaggedConciseTlTag, Text, Time, Tstr, Uri, UuidType,
+    empty_map_as_none, generate_tagged,
+    owned::IntoOwned,
+    CorimError, Digest, ExtensionMap, Int, OidType, TaggedBytes, TaggedConciseMidTag,
+    TaggedConciseSwidTag, TaggedConciseTlTag, Text, Time, Tstr, Uri, UuidType,
 };
 
 use derive_more::{Constructor, From, TryFrom};
 use serde::{
-    de::{self, Visitor},
+    de::{self, DeserializeSeed, Visitor},
     ser::SerializeMap,
     Deserialize, Deserializer, Serialize, Serializer,
 };
+use serde_json::value::RawValue;
+
 /// Represents a Concise Reference Integrity Manifest (CoRIM)
 pub type Corim<'a> = ConciseRimTypeChoice<'a>;
 
 pub type SignedCorim<'a> = TaggedCOSESign1Corim<'a>;
 
+pub type DetachedSignedCorim<'a> = TaggedDetachedCOSESign1Corim<'a>;
+
+pub type MultiSignedCorim<'a> = TaggedCOSESignCorim<'a>;
+
 pub type UnsignedCorimMap<'a> = CorimMap<'a>;
 
 /// A type choice representing either a signed or unsigned CoRIM manifest
@@ -108,22 +137,40 @@ pub enum ConciseRimTypeChoice<'a> {
     TaggedUnsignedCorimMap(TaggedUnsignedCorimMap<'a>),
     /// A COSE Sign1 protected CoRIM
     SignedCorim(SignedCorim<'a>),
+    /// A COSE Sign1 protected CoRIM whose payload is distributed separately
+    DetachedSignedCorim(DetachedSignedCorim<'a>),
+    /// A COSE Sign protected CoRIM carrying one or more signatures
+    MultiSignedCorim(MultiSignedCorim<'a>),
 }
 
 impl ConciseRimTypeChoice<'_> {
-    pub fn as_unsigned_corim_map(&self) -> Option<CorimMap> {
+    pub fn as_unsigned_corim_map(&self) -> Option<CorimMap<'_>> {
         match self {
             Self::TaggedUnsignedCorimMap(val) => Some(val.as_ref().clone()),
             _ => None,
         }
     }
 
-    pub fn as_signed_corim(&self) -> Option<COSESign1Corim> {
+    pub fn as_signed_corim(&self) -> Option<COSESign1Corim<'_>> {
         match self {
             Self::SignedCorim(val) => Some(val.as_ref().clone()),
             _ => None,
         }
     }
+
+    pub fn as_detached_signed_corim(&self) -> Option<DetachedCOSESign1Corim<'_>> {
+        match self {
+            Self::DetachedSignedCorim(val) => Some(val.as_ref().clone()),
+            _ => None,
+        }
+    }
+
+    pub fn as_multi_signed_corim(&self) -> Option<COSESignCorim<'_>> {
+        match self {
+            Self::MultiSignedCorim(val) => Some(val.as_ref().clone()),
+            _ => None,
+        }
+    }
 }
 
 impl Serialize for ConciseRimTypeChoice<'_> {
@@ -134,23 +181,111 @@ impl Serialize for ConciseRimTypeChoice<'_> {
         match self {
             Self::TaggedUnsignedCorimMap(tagged) => tagged.serialize(serializer),
             Self::SignedCorim(tagged) => tagged.serialize(serializer),
+            Self::DetachedSignedCorim(tagged) => tagged.serialize(serializer),
+            Self::MultiSignedCorim(tagged) => tagged.serialize(serializer),
+        }
+    }
+}
+
+/// Deserializes the `{"type": ..., "value": ...}` form a [`ConciseRimTypeChoice`] takes in
+/// human-readable formats. JSON members are unordered, so a "value" entry read before the "type"
+/// entry is kept as raw JSON text until the type is known.
+struct JsonRimVisitor<'a>(core::marker::PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> Visitor<'de> for JsonRimVisitor<'a> {
+    type Value = ConciseRimTypeChoice<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(
+            "a map with \"type\" (unsigned-corim, signed-corim, detached-signed-corim or \
+             multi-signed-corim) and \"value\" entries",
+        )
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut typ: Option<String> = None;
+        let mut value: Option<Box<RawValue>> = None;
+        let mut corim: Option<ConciseRimTypeChoice<'a>> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match (key.as_str(), &typ) {
+                ("type", _) => typ = Some(map.next_value()?),
+                ("value", Some(typ)) => {
+                    corim = Some(map.next_value_seed(JsonRimValue::new(typ.clone()))?)
+                }
+                ("value", None) => value = Some(map.next_value()?),
+                (other, _) => return Err(de::Error::unknown_field(other, &["type", "value"])),
+            }
+        }
+
+        match (corim, typ, value) {
+            (Some(corim), _, _) => Ok(corim),
+            // The buffered value does not live as long as the input, so nothing may borrow it
+            (None, Some(typ), Some(value)) => JsonRimValue::new(typ)
+                .deserialize(&mut serde_json::Deserializer::from_str(value.get()))
+                .map(IntoOwned::into_owned)
+                .map_err(de::Error::custom),
+            (None, None, _) => Err(de::Error::missing_field("type")),
+            (None, Some(_), None) => Err(de::Error::missing_field("value")),
+        }
+    }
+}
+
+/// Deserializes the "value" entry of a [`ConciseRimTypeChoice`] with the given "type"
+struct JsonRimValue<'a>(String, core::marker::PhantomData<&'a ()>);
+
+impl JsonRimValue<'_> {
+    fn new(typ: String) -> Self {
+        Self(typ, core::marker::PhantomData)
+    }
+}
+
+impl<'de: 'a, 'a> DeserializeSeed<'de> for JsonRimValue<'a> {
+    type Value = ConciseRimTypeChoice<'a>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.0.as_str() {
+            "unsigned-corim" => {
+                Ok(TaggedUnsignedCorimMap::new(Deserialize::deserialize(deserializer)?).into())
+            }
+            "signed-corim" => Ok(SignedCorim::new(Deserialize::deserialize(deserializer)?).into()),
+            "detached-signed-corim" => {
+                Ok(DetachedSignedCorim::new(Deserialize::deserialize(deserializer)?).into())
+            }
+            "multi-signed-corim" => {
+                Ok(MultiSignedCorim::new(Deserialize::deserialize(deserializer)?).into())
+            }
+            other => Err(de::Error::custom(format!(
+                "unexpected type {other} for ConciseRimTypeChoice"
+            ))),
         }
     }
 }
 
-impl<'de> Deserialize<'de> for ConciseRimTypeChoice<'_> {
+impl<'de: 'a, 'a> Deserialize<'de> for ConciseRimTypeChoice<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct TagVisitor<'a>(std::marker::PhantomData<&'a ()>);
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_map(JsonRimVisitor(core::marker::PhantomData));
+        }
+
+        struct TagVisitor<'a>(core::marker::PhantomData<&'a ()>);
 
-        impl<'de, 'a> Visitor<'de> for TagVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for TagVisitor<'a> {
             type Value = ConciseRimTypeChoice<'a>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter
-                    .write_str("a ConciseRimTypeChoice variant distinguished by CBOR tag (501, 18)")
+                formatter.write_str(
+                    "a ConciseRimTypeChoice variant distinguished by CBOR tag (501, 18, 98)",
+                )
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -168,17 +303,41 @@ impl<'de> Deserialize<'de> for ConciseRimTypeChoice<'_> {
                         Ok(ConciseRimTypeChoice::TaggedUnsignedCorimMap(value))
                     }
                     18 => {
-                        let value: SignedCorim<'a> = seq
+                        let parts: COSESign1Parts<'a> = seq
                             .next_element()?
                             .ok_or_else(|| de::Error::custom("missing tagged value"))?;
-                        Ok(ConciseRimTypeChoice::SignedCorim(value))
+                        if parts.payload.is_some() {
+                            Ok(ConciseRimTypeChoice::SignedCorim(SignedCorim::new(
+                                parts.into_attached()?,
+                            )))
+                        } else {
+                            Ok(ConciseRimTypeChoice::DetachedSignedCorim(
+                                DetachedSignedCorim::new(parts.into_detached()?),
+                            ))
+                        }
+                    }
+                    98 => {
+                        let value: MultiSignedCorim<'a> = seq
+                            .next_element()?
+                            .ok_or_else(|| de::Error::custom("missing tagged value"))?;
+                        Ok(ConciseRimTypeChoice::MultiSignedCorim(value))
                     }
                     _ => Err(de::Error::custom(format!("unsupported CBOR tag: {}", tag))),
                 }
             }
+
+            // ciborium presents a tagged item as an enum whose tuple variant
+            // yields the tag number followed by the tagged value.
+            fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::EnumAccess<'de>,
+            {
+                let (_, variant) = data.variant::<de::IgnoredAny>()?;
+                de::VariantAccess::tuple_variant(variant, 2, self)
+            }
         }
 
-        deserializer.deserialize_any(TagVisitor(std::marker::PhantomData))
+        deserializer.deserialize_any(TagVisitor(core::marker::PhantomData))
     }
 }
 
@@ -198,6 +357,22 @@ generate_tagged!(
         'a,
         "signed-corim",
         "A CBOR tagged, signed CoRIM."
+    ),
+    (
+        18,
+        TaggedDetachedCOSESign1Corim,
+        DetachedCOSESign1Corim<'a>,
+        'a,
+        "detached-signed-corim",
+        "A CBOR tagged, signed CoRIM with a detached payload."
+    ),
+    (
+        98,
+        TaggedCOSESignCorim,
+        COSESignCorim<'a>,
+        'a,
+        "multi-signed-corim",
+        "A CBOR tagged CoRIM signed by one or more signers."
     )
 );
 
@@ -209,18 +384,18 @@ generate_tagged!(
 #[repr(C)]
 pub struct CorimMap<'a> {
     /// Unique identifier for the CoRIM
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub id: CorimIdTypeChoice<'a>,
     /// Collection of tags contained in this CoRIM
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
     pub tags: Vec<ConciseTagTypeChoice<'a>>,
     /// Optional references to other CoRIMs this one depends on
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "2")]
+    #[serde(borrow, rename = "2")]
     pub dependent_rims: Option<Vec<CorimLocatorMap<'a>>>,
     /// Optional profile information
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "3")]
+    #[serde(borrow, rename = "3")]
     pub profile: Option<ProfileTypeChoice<'a>>,
     /// Optional validity period for the CoRIM
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -228,18 +403,20 @@ pub struct CorimMap<'a> {
     pub rim_validity: Option<ValidityMap>,
     /// Optional list of entities associated with this CoRIM
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "5")]
+    #[serde(borrow, rename = "5")]
     pub entities: Option<Vec<CorimEntityMap<'a>>>,
     /// Optional extensible attributes
-    #[serde(flatten)]
+    #[serde(borrow, flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extension: Option<CorimMapExtension>,
+    pub extension: Option<CorimMapExtension<'a>>,
 }
 
 /// Represents either a string or UUID identifier for a CoRIM
+///
+/// In JSON the UUID alternative is written as `{"type": "uuid", "value": <uuid>}` so that it
+/// can be told apart from text.
 #[repr(C)]
-#[derive(Debug, Serialize, Deserialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
-#[serde(untagged)]
+#[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub enum CorimIdTypeChoice<'a> {
     /// Text string identifier
     Tstr(Tstr<'a>),
@@ -247,6 +424,76 @@ pub enum CorimIdTypeChoice<'a> {
     Uuid(UuidType),
 }
 
+impl Serialize for CorimIdTypeChoice<'_> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Tstr(tstr) => serializer.serialize_str(tstr),
+            Self::Uuid(uuid) if serializer.is_human_readable() => {
+                serialize_json_typed(serializer, "uuid", uuid)
+            }
+            Self::Uuid(uuid) => uuid.serialize(serializer),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for CorimIdTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            match serde_json::Value::deserialize(deserializer)? {
+                serde_json::Value::String(tstr) => Ok(Self::Tstr(tstr.into())),
+                serde_json::Value::Object(map) => match json_typed_value(map)? {
+                    (typ, value) if typ == "uuid" => Ok(Self::Uuid(
+                        serde_json::from_value(value).map_err(de::Error::custom)?,
+                    )),
+                    (typ, _) => Err(de::Error::custom(format!(
+                        "unexpected type {typ} for CorimIdTypeChoice"
+                    ))),
+                },
+                value => Err(de::Error::custom(format!(
+                    "expected a string or a UUID object, got {value:?}"
+                ))),
+            }
+        } else {
+            deserializer.deserialize_any(CorimIdVisitor(core::marker::PhantomData))
+        }
+    }
+}
+
+/// Reads a [`CorimIdTypeChoice`] from CBOR, where text is a string and a UUID is bytes
+struct CorimIdVisitor<'a>(core::marker::PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> Visitor<'de> for CorimIdVisitor<'a> {
+    type Value = CorimIdTypeChoice<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a text string or UUID bytes")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(CorimIdTypeChoice::Tstr(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(CorimIdTypeChoice::Tstr(v.to_string().into()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(CorimIdTypeChoice::Tstr(v.into()))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(CorimIdTypeChoice::Uuid(
+            UuidType::try_from(v).map_err(|_| de::Error::custom("invalid UUID bytes"))?,
+        ))
+    }
+}
+
 impl CorimIdTypeChoice<'_> {
     pub fn as_str(&self) -> Option<&str> {
         match self {
@@ -273,6 +520,7 @@ impl<'a> From<&'a str> for CorimIdTypeChoice<'a> {
 #[repr(C)]
 #[derive(Debug, Serialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum ConciseTagTypeChoice<'a> {
     /// A Concise Software Identity (CoSWID) tag
     Swid(TaggedConciseSwidTag<'a>),
@@ -282,110 +530,150 @@ pub enum ConciseTagTypeChoice<'a> {
     Tl(TaggedConciseTlTag<'a>),
 }
 
-impl<'de> Deserialize<'de> for ConciseTagTypeChoice<'_> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+/// Deserializes the `{"type": ..., "value": ...}` form a [`ConciseTagTypeChoice`] takes in
+/// human-readable formats, buffering a "value" entry that precedes the "type" entry as for
+/// [`ConciseRimTypeChoice`].
+struct JsonTagVisitor<'a>(core::marker::PhantomData<&'a ()>);
+
+impl<'de: 'a, 'a> Visitor<'de> for JsonTagVisitor<'a> {
+    type Value = ConciseTagTypeChoice<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map with \"type\" (coswid, comid or cotl) and \"value\" entries")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
     where
-        D: Deserializer<'de>,
+        A: de::MapAccess<'de>,
     {
-        struct TagVisitor<'a>(std::marker::PhantomData<&'a ()>);
-        impl<'de, 'a> Visitor<'de> for TagVisitor<'a> {
-            type Value = ConciseTagTypeChoice<'a>;
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a tagged CBOR value (505, 506, 508)")
-            }
-            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
-            where
-                D: Deserializer<'de>,
-            {
-                let tagged_value = ciborium::value::Value::deserialize(deserializer)?;
-                match tagged_value {
-                    ciborium::value::Value::Tag(tag, inner) => match tag {
-                        505 => {
-                            let mut bytes: Vec<u8> = Vec::new();
-                            ciborium::ser::into_writer(&inner, &mut bytes).map_err(|_| {
-                                serde::de::Error::custom("Failed to serialize the map")
-                            })?;
-                            let swid: ConciseSwidTag<'a> = ciborium::from_reader(&bytes[..])
-                                .map_err(|_| {
-                                    serde::de::Error::custom("Failed to deserialize bytes")
-                                })?;
-                            Ok(ConciseTagTypeChoice::Swid(TaggedConciseSwidTag::new(swid)))
-                        }
-                        506 => {
-                            let mut bytes = Vec::new();
-                            ciborium::ser::into_writer(&inner, &mut bytes).map_err(|_| {
-                                serde::de::Error::custom("Failure to serialize the map")
-                            })?;
-                            let mid: ConciseMidTag<'a> = ciborium::from_reader(&bytes[..])
-                                .map_err(|_| {
-                                    serde::de::Error::custom("Failed to deserialize bytes")
-                                })?;
-                            Ok(ConciseTagTypeChoice::Mid(TaggedConciseMidTag::new(mid)))
-                        }
-                        508 => {
-                            let mut bytes = Vec::new();
-                            ciborium::ser::into_writer(&inner, &mut bytes).map_err(|_| {
-                                serde::de::Error::custom("Failure to serialize the map")
-                            })?;
-                            let tl: ConciseTlTag<'a> =
-                                ciborium::from_reader(&bytes[..]).map_err(|_| {
-                                    serde::de::Error::custom("Failed to deserialize bytes")
-                                })?;
-                            Ok(ConciseTagTypeChoice::Tl(TaggedConciseTlTag::new(tl)))
-                        }
-                        other => Err(serde::de::Error::custom(format!(
-                            "Unsupported tag: {}, expected 505, 506, or 508",
-                            other
-                        ))),
-                    },
-                    _ => Err(serde::de::Error::custom("Expected a tagged CBOR value")),
+        let mut typ: Option<String> = None;
+        let mut value: Option<Box<RawValue>> = None;
+        let mut tag: Option<ConciseTagTypeChoice<'a>> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match (key.as_str(), &typ) {
+                ("type", _) => typ = Some(map.next_value()?),
+                ("value", Some(typ)) => {
+                    tag = Some(map.next_value_seed(JsonTagValue::new(typ.clone()))?)
                 }
+                ("value", None) => value = Some(map.next_value()?),
+                (other, _) => return Err(de::Error::unknown_field(other, &["type", "value"])),
             }
         }
-        deserializer.deserialize_newtype_struct(
-            "ConciseTagTypeChoice",
-            TagVisitor(std::marker::PhantomData),
-        )
+
+        match (tag, typ, value) {
+            (Some(tag), _, _) => Ok(tag),
+            (None, Some(typ), Some(value)) => JsonTagValue::new(typ)
+                .deserialize(&mut serde_json::Deserializer::from_str(value.get()))
+                .map(IntoOwned::into_owned)
+                .map_err(de::Error::custom),
+            (None, None, _) => Err(de::Error::missing_field("type")),
+            (None, Some(_), None) => Err(de::Error::missing_field("value")),
+        }
+    }
+}
+
+/// Deserializes the "value" entry of a [`ConciseTagTypeChoice`] with the given "type"
+struct JsonTagValue<'a>(String, core::marker::PhantomData<&'a ()>);
+
+impl JsonTagValue<'_> {
+    fn new(typ: String) -> Self {
+        Self(typ, core::marker::PhantomData)
+    }
+}
+
+impl<'de: 'a, 'a> DeserializeSeed<'de> for JsonTagValue<'a> {
+    type Value = ConciseTagTypeChoice<'a>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match self.0.as_str() {
+            "coswid" => Ok(ConciseSwidTag::deserialize(deserializer)?.into()),
+            "comid" => Ok(ConciseMidTag::deserialize(deserializer)?.into()),
+            "cotl" => Ok(ConciseTlTag::deserialize(deserializer)?.into()),
+            other => Err(de::Error::custom(format!(
+                "unexpected type {other} for ConciseTagTypeChoice, expected coswid, comid or cotl"
+            ))),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ConciseTagTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_map(JsonTagVisitor(core::marker::PhantomData));
+        }
+
+        deserialize_tag_choice(deserializer)
+    }
+}
+
+impl<'de: 'a, 'a> TagChoice<'de> for ConciseTagTypeChoice<'a> {
+    const NAME: &'static str = "ConciseTagTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            505 => Ok(ConciseTagTypeChoice::Swid(TaggedConciseSwidTag::new(
+                ConciseSwidTag::deserialize(deserializer)?,
+            ))),
+            506 => Ok(ConciseTagTypeChoice::Mid(TaggedConciseMidTag::new(
+                ConciseMidTag::deserialize(deserializer)?,
+            ))),
+            508 => Ok(ConciseTagTypeChoice::Tl(TaggedConciseTlTag::new(
+                ConciseTlTag::deserialize(deserializer)?,
+            ))),
+            other => Err(de::Error::custom(format!(
+                "Unsupported tag: {other}, expected 505, 506, or 508"
+            ))),
+        }
     }
 }
 
 impl ConciseTagTypeChoice<'_> {
-    pub fn as_coswid(&self) -> Option<ConciseSwidTag> {
+    pub fn as_coswid(&self) -> Option<ConciseSwidTag<'_>> {
         match self {
             Self::Swid(coswid) => Some(coswid.as_ref().clone()),
             _ => None,
         }
     }
 
-    pub fn as_comid(&self) -> Option<ConciseMidTag> {
+    pub fn as_comid(&self) -> Option<ConciseMidTag<'_>> {
         match self {
             Self::Mid(comid) => Some(comid.as_ref().clone()),
             _ => None,
         }
     }
 
-    pub fn as_cotl(&self) -> Option<ConciseTlTag> {
+    pub fn as_cotl(&self) -> Option<ConciseTlTag<'_>> {
         match self {
             Self::Tl(cotl) => Some(cotl.as_ref().clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_coswid(&self) -> Option<&ConciseSwidTag> {
+    pub fn as_ref_coswid(&self) -> Option<&ConciseSwidTag<'_>> {
         match self {
             Self::Swid(coswid) => Some(coswid.as_ref()),
             _ => None,
         }
     }
 
-    pub fn as_ref_comid(&self) -> Option<&ConciseMidTag> {
+    pub fn as_ref_comid(&self) -> Option<&ConciseMidTag<'_>> {
         match self {
             Self::Mid(comid) => Some(comid.as_ref()),
             _ => None,
         }
     }
 
-    pub fn as_ref_cotl(&self) -> Option<&ConciseTlTag> {
+    pub fn as_ref_cotl(&self) -> Option<&ConciseTlTag<'_>> {
         match self {
             Self::Tl(cotl) => Some(cotl.as_ref()),
             _ => None,
@@ -421,12 +709,12 @@ impl<'a> From<ConciseTlTag<'a>> for ConciseTagTypeChoice<'a> {
 )]
 pub struct CorimLocatorMap<'a> {
     /// URI(s) where the dependent CoRIM can be found
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub href: Vec<Uri<'a>>,
     /// Optional cryptographic thumbprint for verification
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "1")]
-    pub thumbprint: Option<Digest>,
+    #[serde(borrow, rename = "1")]
+    pub thumbprint: Option<Digest<'a>>,
 }
 
 /// Profile identifier that can be either a URI or OID
@@ -436,18 +724,18 @@ pub enum ProfileTypeChoice<'a> {
     /// URI-based profile identifier
     Uri(Uri<'a>),
     /// OID-based profile identifier
-    OidType(OidType),
+    OidType(OidType<'a>),
 }
 
 impl ProfileTypeChoice<'_> {
-    pub fn as_uri(&self) -> Option<Uri> {
+    pub fn as_uri(&self) -> Option<Uri<'_>> {
         match self {
             Self::Uri(uri) => Some(uri.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_uri(&self) -> Option<&Uri> {
+    pub fn as_ref_uri(&self) -> Option<&Uri<'_>> {
         match self {
             Self::Uri(uri) => Some(uri),
             _ => None,
@@ -474,7 +762,7 @@ impl Serialize for ProfileTypeChoice<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for ProfileTypeChoice<'_> {
+impl<'de: 'a, 'a> Deserialize<'de> for ProfileTypeChoice<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -498,32 +786,28 @@ impl<'de> Deserialize<'de> for ProfileTypeChoice<'_> {
                 ))),
             }
         } else {
-            match ciborium::Value::deserialize(deserializer)? {
-                ciborium::Value::Tag(tag, inner) => {
-                    // Re-serializing the inner Value so that we can deserialize it
-                    // into an appropriate type, once we figure out what that is
-                    // based on the tag.
-                    let mut buf: Vec<u8> = Vec::new();
-                    ciborium::into_writer(&inner, &mut buf).unwrap();
-
-                    match tag {
-                        111 => {
-                            let oid: ObjectIdentifier =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(ProfileTypeChoice::OidType(oid.into()))
-                        }
-                        37 => {
-                            let uri: Text =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(ProfileTypeChoice::Uri(uri.into()))
-                        }
-                        n => Err(de::Error::custom(format!(
-                            "unexpected ProfileTypeChoice tag {n}"
-                        ))),
-                    }
-                }
-                _ => Err(de::Error::custom("did not see a tag for ProfileTypeChoice")),
-            }
+            deserialize_tag_choice(deserializer)
+        }
+    }
+}
+
+impl<'de: 'a, 'a> TagChoice<'de> for ProfileTypeChoice<'a> {
+    const NAME: &'static str = "ProfileTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            111 => Ok(ProfileTypeChoice::OidType(
+                ObjectIdentifier::deserialize(deserializer)?.into(),
+            )),
+            32 => Ok(ProfileTypeChoice::Uri(
+                deserialize_text(deserializer)?.into(),
+            )),
+            n => Err(de::Error::custom(format!(
+                "unexpected ProfileTypeChoice tag {n}"
+            ))),
         }
     }
 }
@@ -542,6 +826,17 @@ pub struct ValidityMap {
     pub not_after: Time,
 }
 
+impl ValidityMap {
+    /// Returns whether `time` (seconds since the Unix epoch) falls within the validity period.
+    /// Both bounds are inclusive.
+    pub fn contains(&self, time: u64) -> bool {
+        let time = i128::from(time);
+        self.not_before
+            .is_none_or(|not_before| not_before.0 <= time)
+            && time <= self.not_after.0
+    }
+}
+
 /// Information about an entity associated with the CoRIM
 #[repr(C)]
 #[derive(
@@ -549,26 +844,27 @@ pub struct ValidityMap {
 )]
 pub struct CorimEntityMap<'a> {
     /// Name of the entity
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0", deserialize_with = "deserialize_text")]
     pub entity_name: Text<'a>,
     /// Optional registration identifier for the entity
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
     pub reg_id: Option<Uri<'a>>,
     /// Role of the entity in relation to the CoRIM
     #[serde(rename = "2")]
     pub role: Vec<CorimRoleTypeChoice>,
     /// Optional extensible attributes
-    #[serde(flatten)]
+    #[serde(borrow, flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     pub extension: Option<ExtensionMap<'a>>,
 }
 
 /// Roles that entities can have in relation to a CoRIM manifest
-#[derive(Debug, Serialize, Deserialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
+///
+/// Roles are encoded as integers in CBOR and by name (e.g. `"manifest-creator"`) in JSON.
+#[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[repr(u8)]
-#[serde(untagged)]
 pub enum CorimRoleTypeChoice {
     /// Entity that created the manifest content
     ManifestCreator = 1,
@@ -577,12 +873,205 @@ pub enum CorimRoleTypeChoice {
     ManifestSigner = 2,
 }
 
+impl TryFrom<&str> for CorimRoleTypeChoice {
+    type Error = CorimError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "manifest-creator" => Ok(Self::ManifestCreator),
+            "manifest-signer" => Ok(Self::ManifestSigner),
+            role => Err(CorimError::InvalidRole(role.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for CorimRoleTypeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ManifestCreator => "manifest-creator",
+            Self::ManifestSigner => "manifest-signer",
+        })
+    }
+}
+
+impl Serialize for CorimRoleTypeChoice {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            serializer.serialize_u8(*self as u8)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CorimRoleTypeChoice {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct RoleVisitor;
+
+        impl Visitor<'_> for RoleVisitor {
+            type Value = CorimRoleTypeChoice;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("int or string CoRIM role")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match v {
+                    1 => Ok(CorimRoleTypeChoice::ManifestCreator),
+                    2 => Ok(CorimRoleTypeChoice::ManifestSigner),
+                    _ => Err(de::Error::invalid_value(de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match u64::try_from(v) {
+                    Ok(v) => self.visit_u64(v),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Signed(v), &self)),
+                }
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                CorimRoleTypeChoice::try_from(v).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(RoleVisitor)
+    }
+}
+
 /// Extension map for CoRIM-specific extensions
 #[repr(C)]
 #[derive(
     Debug, Serialize, Deserialize, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone,
 )]
-pub struct CorimMapExtension(pub TaggedBytes);
+pub struct CorimMapExtension<'a>(#[serde(borrow)] pub TaggedBytes<'a>);
+
+/// A value carried in a COSE structure as a byte string holding its CBOR encoding
+/// (`bstr .cbor`), such as a protected header or the payload of a signed CoRIM.
+///
+/// Signatures cover these byte strings rather than the values they decode to, and other
+/// encoders may write the same value differently, e.g. with map entries in another order or
+/// with indefinite lengths. A value decoded from CBOR therefore keeps the bytes it was read
+/// from, and they are written back and signed or verified unchanged. Mutable access to the
+/// value discards them, after which the value is encoded by this crate.
+///
+/// Values compare by their decoded value. In JSON only the value is written, so the original
+/// bytes do not survive a JSON round trip.
+#[derive(Debug, Clone)]
+pub struct Encoded<'a, T> {
+    pub(crate) value: T,
+    pub(crate) bytes: Option<Bytes<'a>>,
+}
+
+impl<T> Encoded<'_, T> {
+    /// Wraps a value, which is encoded by this crate when it is written
+    pub fn new(value: T) -> Self {
+        Self { value, bytes: None }
+    }
+
+    /// Returns the bytes the value was decoded from, unless it has been modified since
+    pub fn original_bytes(&self) -> Option<&[u8]> {
+        self.bytes.as_ref().map(AsRef::as_ref)
+    }
+
+    /// Returns the value, discarding the bytes it was decoded from
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Serialize> Encoded<'_, T> {
+    /// Returns the bytes the value was decoded from, or else its CBOR encoding
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        if let Some(bytes) = &self.bytes {
+            return Ok(bytes.as_ref().to_vec());
+        }
+
+        let mut buf = Vec::new();
+        ciborium::ser::into_writer(&self.value, &mut buf)
+            .map_err(|e| CorimError::Encoding(e.to_string()))?;
+        Ok(buf)
+    }
+}
+
+impl<'a, T> Encoded<'a, T>
+where
+    T: Deserialize<'a>,
+{
+    /// Decodes a value from `bytes`, keeping them to be written back unchanged. When `bytes`
+    /// are borrowed, the value borrows its text and byte strings from them as with
+    /// [`cbor::from_slice`](crate::cbor::from_slice).
+    pub fn from_bytes(bytes: impl Into<Bytes<'a>>) -> crate::Result<Self> {
+        let bytes: Cow<'a, [u8]> = bytes.into().into();
+        let value = match &bytes {
+            Cow::Borrowed(slice) => crate::cbor::from_slice(slice),
+            Cow::Owned(vec) => crate::cbor::from_slice_owned(vec),
+        }
+        .map_err(|e| CorimError::Decoding(e.to_string()))?;
+
+        Ok(Self {
+            value,
+            bytes: Some(bytes.into()),
+        })
+    }
+}
+
+impl<T> From<T> for Encoded<'_, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> core::ops::Deref for Encoded<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> core::ops::DerefMut for Encoded<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // The caller may change the value, so the original bytes can no longer stand for it
+        self.bytes = None;
+        &mut self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for Encoded<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Encoded<'_, T> {}
+
+impl<T: PartialOrd> PartialOrd for Encoded<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord> Ord for Encoded<'_, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
 
 /*
 COSE-Sign1-corim = [
@@ -597,35 +1086,37 @@ COSE-Sign1-corim = [
 #[repr(C)]
 pub struct COSESign1Corim<'a> {
     /// Protected header containing signing metadata (must be integrity protected)
-    pub protected: ProtectedCorimHeaderMap<'a>,
+    pub protected: Encoded<'a, ProtectedCorimHeaderMap<'a>>,
     /// Unprotected header attributes (not integrity protected)
     pub unprotected: UnprotectedCorimHeaderMap<'a>,
     /// The actual CoRIM payload being signed
-    pub payload: TaggedUnsignedCorimMap<'a>,
+    pub payload: Encoded<'a, TaggedUnsignedCorimMap<'a>>,
     /// Cryptographic signature over the protected header and payload
-    pub signature: TaggedBytes,
+    pub signature: TaggedBytes<'a>,
 }
 
 /// Unprotected header for a signed CoRIM
-pub type UnprotectedCorimHeaderMap<'a> = BTreeMap<Label<'a>, ExtensionMap<'a>>;
+pub type UnprotectedCorimHeaderMap<'a> = BTreeMap<Label<'a>, ExtensionValue<'a>>;
 
 impl Serialize for COSESign1Corim<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        use serde::ser::Error;
         use serde::ser::SerializeSeq;
 
+        let is_human_readable = serializer.is_human_readable();
+
         // COSE_Sign1 is a 4-element array, all elements must be present
         let mut seq = serializer.serialize_seq(Some(4))?;
 
-        // 1. Convert protected header to CBOR bytes
-        let mut protected_cbor = vec![];
-        ciborium::ser::into_writer(&self.protected, &mut protected_cbor).map_err(|e| {
-            S::Error::custom(format!("Failed to serialize protected header: {}", e))
-        })?;
-        seq.serialize_element(&Bytes::from(protected_cbor))?;
+        // 1. Protected header as CBOR bytes
+        serialize_encoded(
+            &mut seq,
+            is_human_readable,
+            "protected header",
+            &self.protected,
+        )?;
 
         // 2. Unprotected header - must be present (empty map if None)
         // Per RFC 8152, this must be present even if empty
@@ -635,13 +1126,10 @@ impl Serialize for COSESign1Corim<'_> {
         // })?;
 
         // seq.serialize_element(&Bytes::from(unprotected_cbor))?;
-        seq.serialize_element(&self.unprotected)?;
+        serialize_unprotected(&mut seq, is_human_readable, &self.unprotected)?;
 
         // 3. Payload as CBOR bytes
-        let mut payload_cbor = Vec::new();
-        ciborium::ser::into_writer(&self.payload, &mut payload_cbor)
-            .map_err(|e| S::Error::custom(format!("Failed to serialize payload: {}", e)))?;
-        seq.serialize_element(&Bytes::from(payload_cbor))?;
+        serialize_encoded(&mut seq, is_human_readable, "payload", &self.payload)?;
 
         // 4. Signature as bytes
         seq.serialize_element(&self.signature)?;
@@ -649,19 +1137,30 @@ impl Serialize for COSESign1Corim<'_> {
         seq.end()
     }
 }
-impl<'de> Deserialize<'de> for COSESign1Corim<'_> {
+/// The elements of a COSE_Sign1 array, with the payload absent when it is detached
+struct COSESign1Parts<'a> {
+    protected: Encoded<'a, ProtectedCorimHeaderMap<'a>>,
+    unprotected: UnprotectedCorimHeaderMap<'a>,
+    payload: Option<Encoded<'a, TaggedUnsignedCorimMap<'a>>>,
+    signature: TaggedBytes<'a>,
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for COSESign1Parts<'a> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
+        use core::fmt;
+        use core::marker::PhantomData;
         use serde::de::{Error, SeqAccess, Visitor};
-        use std::fmt;
-        use std::marker::PhantomData;
 
-        struct COSESign1Visitor<'a>(PhantomData<&'a ()>);
+        struct COSESign1Visitor<'a> {
+            is_human_readable: bool,
+            marker: PhantomData<&'a ()>,
+        }
 
-        impl<'de, 'a> Visitor<'de> for COSESign1Visitor<'a> {
-            type Value = COSESign1Corim<'a>;
+        impl<'de: 'a, 'a> Visitor<'de> for COSESign1Visitor<'a> {
+            type Value = COSESign1Parts<'a>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("a COSE_Sign1 structure as a 4-element array")
@@ -672,38 +1171,35 @@ impl<'de> Deserialize<'de> for COSESign1Corim<'_> {
                 A: SeqAccess<'de>,
             {
                 // 1. Protected header as CBOR bytes
-                let protected_bytes: Bytes = seq
-                    .next_element()?
-                    .ok_or_else(|| A::Error::custom("missing protected header"))?;
+                let protected: Encoded<'a, ProtectedCorimHeaderMap<'a>> =
+                    next_encoded(&mut seq, self.is_human_readable, "protected header")?
+                        .ok_or_else(|| A::Error::custom("missing protected header"))?;
 
                 // 2. Unprotected header
-                let unprotected: UnprotectedCorimHeaderMap<'a> = seq
-                    .next_element()?
+                let unprotected = next_unprotected(&mut seq, self.is_human_readable)?
                     .ok_or_else(|| A::Error::custom("missing unprotected header"))?;
 
-                // 3. Payload as CBOR bytes
-                let payload_bytes: Bytes = seq
-                    .next_element()?
-                    .ok_or_else(|| A::Error::custom("missing payload"))?;
+                // 3. Payload as CBOR bytes, or nil when detached
+                let payload = if self.is_human_readable {
+                    seq.next_element::<Option<TaggedUnsignedCorimMap<'a>>>()?
+                        .ok_or_else(|| A::Error::custom("missing payload"))?
+                        .map(Encoded::new)
+                } else {
+                    let payload_bytes: Option<Bytes> = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::custom("missing payload"))?;
+
+                    payload_bytes
+                        .map(|payload_bytes| decode_encoded(payload_bytes, "payload"))
+                        .transpose()?
+                };
 
                 // 4. Signature as bytes
-                let signature: TaggedBytes = seq
+                let signature: TaggedBytes<'a> = seq
                     .next_element()?
                     .ok_or_else(|| A::Error::custom("missing signature"))?;
 
-                // Deserialize protected header from bytes
-                let protected: ProtectedCorimHeaderMap<'a> =
-                    ciborium::de::from_reader(protected_bytes.as_ref()).map_err(|e| {
-                        A::Error::custom(format!("Failed to deserialize protected header: {}", e))
-                    })?;
-
-                // Deserialize payload directly into TaggedUnsignedCorimMap
-                let payload: TaggedUnsignedCorimMap<'a> =
-                    ciborium::de::from_reader(payload_bytes.as_ref()).map_err(|e| {
-                        A::Error::custom(format!("Failed to deserialize payload: {}", e))
-                    })?;
-
-                Ok(COSESign1Corim {
+                Ok(COSESign1Parts {
                     protected,
                     unprotected,
                     payload,
@@ -712,34 +1208,680 @@ impl<'de> Deserialize<'de> for COSESign1Corim<'_> {
             }
         }
 
-        deserializer.deserialize_seq(COSESign1Visitor(PhantomData))
+        let is_human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_seq(COSESign1Visitor {
+            is_human_readable,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for COSESign1Corim<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        COSESign1Parts::deserialize(deserializer)?.into_attached()
+    }
+}
+
+impl<'a> COSESign1Parts<'a> {
+    fn into_attached<E: de::Error>(self) -> Result<COSESign1Corim<'a>, E> {
+        Ok(COSESign1Corim {
+            protected: self.protected,
+            unprotected: self.unprotected,
+            payload: self.payload.ok_or_else(|| {
+                E::custom("missing payload (use DetachedCOSESign1Corim for detached payloads)")
+            })?,
+            signature: self.signature,
+        })
+    }
+
+    fn into_detached<E: de::Error>(self) -> Result<DetachedCOSESign1Corim<'a>, E> {
+        if self.payload.is_some() {
+            return Err(E::custom("expected a detached (nil) payload"));
+        }
+
+        Ok(DetachedCOSESign1Corim {
+            protected: self.protected,
+            unprotected: self.unprotected,
+            signature: self.signature,
+        })
+    }
+}
+
+/*
+COSE-Sign1-corim-detached = [
+  protected: bstr .cbor protected-corim-header-map
+  unprotected: unprotected-corim-header-map
+  payload: nil
+  signature: bstr
+]
+*/
+/// COSE_Sign1 structure for a signed CoRIM whose payload is distributed separately
+#[derive(Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct DetachedCOSESign1Corim<'a> {
+    /// Protected header containing signing metadata (must be integrity protected)
+    pub protected: Encoded<'a, ProtectedCorimHeaderMap<'a>>,
+    /// Unprotected header attributes (not integrity protected)
+    pub unprotected: UnprotectedCorimHeaderMap<'a>,
+    /// Cryptographic signature over the protected header and the detached payload
+    pub signature: TaggedBytes<'a>,
+}
+
+impl Serialize for DetachedCOSESign1Corim<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let is_human_readable = serializer.is_human_readable();
+        let mut seq = serializer.serialize_seq(Some(4))?;
+
+        serialize_encoded(
+            &mut seq,
+            is_human_readable,
+            "protected header",
+            &self.protected,
+        )?;
+        serialize_unprotected(&mut seq, is_human_readable, &self.unprotected)?;
+
+        // The payload is carried separately and encoded as nil
+        seq.serialize_element(&Option::<Bytes>::None)?;
+
+        seq.serialize_element(&self.signature)?;
+
+        seq.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for DetachedCOSESign1Corim<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        COSESign1Parts::deserialize(deserializer)?.into_detached()
     }
 }
+
 /// Protected header for a signed CoRIM
-#[derive(
-    Default, Debug, Serialize, Deserialize, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone,
-)]
+#[derive(Default, Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[repr(C)]
 pub struct ProtectedCorimHeaderMap<'a> {
     /// Algorithm identifier for the signature
-    #[serde(rename = "1")]
     pub alg: Int,
     /// Content type indicator (should be "application/rim+cbor")
-    #[serde(rename = "3")]
     pub content_type: Text<'a>,
     /// Key identifier for the signing key
-    #[serde(rename = "4")]
-    pub kid: Bytes,
+    pub kid: Bytes<'a>,
     /// CoRIM-specific metadata
-    #[serde(rename = "8")]
     pub corim_meta: CorimMetaMap<'a>,
+    /// Optional COSE header parameters (e.g. x5chain, x5t)
+    pub cose_map: Option<CoseMap<'a>>,
+}
+
+impl Serialize for ProtectedCorimHeaderMap<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let is_human_readable = serializer.is_human_readable();
+        let mut map = serializer.serialize_map(None)?;
+
+        serialize_header_entry(&mut map, is_human_readable, 1, &self.alg)?;
+        serialize_header_entry(&mut map, is_human_readable, 3, &self.content_type)?;
+        serialize_header_entry(&mut map, is_human_readable, 4, &self.kid)?;
+        serialize_header_entry(&mut map, is_human_readable, 8, &self.corim_meta)?;
+
+        if let Some(cose_map) = &self.cose_map {
+            for (key, value) in cose_map.0.iter() {
+                serialize_header_entry(&mut map, is_human_readable, key.0, value)?;
+            }
+        }
+
+        map.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ProtectedCorimHeaderMap<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ProtectedCorimHeaderMapVisitor<'a>(core::marker::PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for ProtectedCorimHeaderMapVisitor<'a> {
+            type Value = ProtectedCorimHeaderMap<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map containing protected CoRIM header fields")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut alg: Option<Int> = None;
+                let mut content_type: Option<Text<'a>> = None;
+                let mut kid: Option<Bytes<'a>> = None;
+                let mut corim_meta: Option<CorimMetaMap<'a>> = None;
+                let mut cose_map = CoseMap::default();
+
+                // Header labels are integers in COSE, but have historically been
+                // written as text keys by this crate, so accept both forms.
+                while let Some(label) = map.next_key::<Label>()? {
+                    let key = header_label(label)?;
+
+                    match key.0 {
+                        1 => alg = Some(map.next_value()?),
+                        3 => content_type = Some(map.next_value::<BorrowText>()?.into()),
+                        4 => kid = Some(map.next_value()?),
+                        8 => corim_meta = Some(map.next_value()?),
+                        _ => {
+                            cose_map.insert(key, map.next_value::<ExtensionValue<'a>>()?);
+                        }
+                    }
+                }
+
+                Ok(ProtectedCorimHeaderMap {
+                    alg: alg.ok_or_else(|| de::Error::missing_field("alg"))?,
+                    content_type: content_type
+                        .ok_or_else(|| de::Error::missing_field("content-type"))?,
+                    kid: kid.ok_or_else(|| de::Error::missing_field("kid"))?,
+                    corim_meta: corim_meta.ok_or_else(|| de::Error::missing_field("corim-meta"))?,
+                    cose_map: (!cose_map.0.is_empty()).then_some(cose_map),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(ProtectedCorimHeaderMapVisitor(core::marker::PhantomData))
+    }
+}
+
+/*
+COSE-Sign-corim = [
+  protected: bstr .cbor protected-corim-body-header-map
+  unprotected: unprotected-corim-header-map
+  payload: bstr .cbor tagged-unsigned-corim-map
+  signatures: [+ COSE-Signature-corim]
+]
+*/
+/// COSE_Sign structure for a CoRIM signed by multiple parties with CBOR tag 98
+#[derive(Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct COSESignCorim<'a> {
+    /// Protected header shared by all signers
+    pub protected: Encoded<'a, ProtectedCorimBodyHeaderMap<'a>>,
+    /// Unprotected header attributes (not integrity protected)
+    pub unprotected: UnprotectedCorimHeaderMap<'a>,
+    /// The actual CoRIM payload being signed
+    pub payload: Encoded<'a, TaggedUnsignedCorimMap<'a>>,
+    /// One signature per signer, each over the body and its own protected header
+    pub signatures: Vec<COSESignatureCorim<'a>>,
+}
+
+impl Serialize for COSESignCorim<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let is_human_readable = serializer.is_human_readable();
+        let mut seq = serializer.serialize_seq(Some(4))?;
+
+        serialize_encoded(
+            &mut seq,
+            is_human_readable,
+            "protected header",
+            &self.protected,
+        )?;
+        serialize_unprotected(&mut seq, is_human_readable, &self.unprotected)?;
+        serialize_encoded(&mut seq, is_human_readable, "payload", &self.payload)?;
+
+        seq.serialize_element(&self.signatures)?;
+
+        seq.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for COSESignCorim<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{Error, SeqAccess};
+
+        struct COSESignVisitor<'a> {
+            is_human_readable: bool,
+            marker: core::marker::PhantomData<&'a ()>,
+        }
+
+        impl<'de: 'a, 'a> Visitor<'de> for COSESignVisitor<'a> {
+            type Value = COSESignCorim<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a COSE_Sign structure as a 4-element array")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let protected: Encoded<'a, ProtectedCorimBodyHeaderMap<'a>> =
+                    next_encoded(&mut seq, self.is_human_readable, "protected header")?
+                        .ok_or_else(|| A::Error::custom("missing protected header"))?;
+
+                let unprotected = next_unprotected(&mut seq, self.is_human_readable)?
+                    .ok_or_else(|| A::Error::custom("missing unprotected header"))?;
+
+                let payload: Encoded<'a, TaggedUnsignedCorimMap<'a>> =
+                    next_encoded(&mut seq, self.is_human_readable, "payload")?
+                        .ok_or_else(|| A::Error::custom("missing payload"))?;
+
+                let signatures: Vec<COSESignatureCorim<'a>> = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::custom("missing signatures"))?;
+
+                if signatures.is_empty() {
+                    return Err(A::Error::custom("at least one signature is required"));
+                }
+
+                Ok(COSESignCorim {
+                    protected,
+                    unprotected,
+                    payload,
+                    signatures,
+                })
+            }
+        }
+
+        let is_human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_seq(COSESignVisitor {
+            is_human_readable,
+            marker: core::marker::PhantomData,
+        })
+    }
+}
+
+/*
+COSE-Signature-corim = [
+  protected: bstr .cbor protected-corim-signer-header-map
+  unprotected: unprotected-corim-header-map
+  signature: bstr
+]
+*/
+/// A single signer's COSE_Signature within a [`COSESignCorim`]
+#[derive(Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct COSESignatureCorim<'a> {
+    /// Protected header describing this signer
+    pub protected: Encoded<'a, ProtectedCorimSignerHeaderMap<'a>>,
+    /// Unprotected header attributes for this signer (not integrity protected)
+    pub unprotected: UnprotectedCorimHeaderMap<'a>,
+    /// Signature over the body protected header, this protected header and the payload
+    pub signature: Bytes<'a>,
+}
+
+impl Serialize for COSESignatureCorim<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let is_human_readable = serializer.is_human_readable();
+        let mut seq = serializer.serialize_seq(Some(3))?;
+
+        serialize_encoded(
+            &mut seq,
+            is_human_readable,
+            "protected header",
+            &self.protected,
+        )?;
+        serialize_unprotected(&mut seq, is_human_readable, &self.unprotected)?;
+        seq.serialize_element(&self.signature)?;
+
+        seq.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for COSESignatureCorim<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::{Error, SeqAccess};
+
+        struct COSESignatureVisitor<'a> {
+            is_human_readable: bool,
+            marker: core::marker::PhantomData<&'a ()>,
+        }
+
+        impl<'de: 'a, 'a> Visitor<'de> for COSESignatureVisitor<'a> {
+            type Value = COSESignatureCorim<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a COSE_Signature structure as a 3-element array")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let protected: Encoded<'a, ProtectedCorimSignerHeaderMap<'a>> =
+                    next_encoded(&mut seq, self.is_human_readable, "protected header")?
+                        .ok_or_else(|| A::Error::custom("missing protected header"))?;
+
+                let unprotected = next_unprotected(&mut seq, self.is_human_readable)?
+                    .ok_or_else(|| A::Error::custom("missing unprotected header"))?;
+
+                let signature: Bytes<'a> = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::custom("missing signature"))?;
+
+                Ok(COSESignatureCorim {
+                    protected,
+                    unprotected,
+                    signature,
+                })
+            }
+        }
+
+        let is_human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_seq(COSESignatureVisitor {
+            is_human_readable,
+            marker: core::marker::PhantomData,
+        })
+    }
+}
+
+/// Converts a COSE header label to its integer form, accepting text encoded integers
+fn header_label<E: de::Error>(label: Label) -> Result<Int, E> {
+    match &label {
+        Label::Int(i) => Ok(*i),
+        Label::Text(t) => t
+            .parse::<Int>()
+            .map_err(|_| E::custom(format!("unexpected protected header label \"{t}\""))),
+    }
+}
+
+/// Writes an [`Encoded`] element of a COSE array as a byte string, holding the bytes it was
+/// decoded from when it has not been modified. In JSON the value is written as is.
+fn serialize_encoded<A, T>(
+    seq: &mut A,
+    is_human_readable: bool,
+    what: &str,
+    value: &Encoded<'_, T>,
+) -> Result<(), A::Error>
+where
+    A: serde::ser::SerializeSeq,
+    T: Serialize,
+{
+    if is_human_readable {
+        return seq.serialize_element(&value.value);
+    }
+
+    let cbor = value.to_bytes().map_err(|e| {
+        <A::Error as serde::ser::Error>::custom(format!("Failed to serialize {what}: {e}"))
+    })?;
+    seq.serialize_element(&Bytes::from(cbor))
+}
+
+/// Reads an element written by [`serialize_encoded`], returning `None` at the end of the array
+fn next_encoded<'de, A, T>(
+    seq: &mut A,
+    is_human_readable: bool,
+    what: &str,
+) -> Result<Option<Encoded<'de, T>>, A::Error>
+where
+    A: de::SeqAccess<'de>,
+    T: Deserialize<'de>,
+{
+    if is_human_readable {
+        return Ok(seq.next_element()?.map(Encoded::new));
+    }
+
+    seq.next_element::<Bytes>()?
+        .map(|bytes| decode_encoded(bytes, what))
+        .transpose()
+}
+
+/// Decodes the CBOR in a byte string element of a COSE array, keeping the bytes
+fn decode_encoded<'a, T, E>(bytes: Bytes<'a>, what: &str) -> Result<Encoded<'a, T>, E>
+where
+    T: Deserialize<'a>,
+    E: de::Error,
+{
+    Encoded::from_bytes(bytes).map_err(|e| E::custom(format!("Failed to deserialize {what}: {e}")))
+}
+
+/// Writes an unprotected header. JSON keys are strings, so in JSON the labels are written as
+/// text and read back with [`Label::parse`], as the keys of extension maps are.
+fn serialize_unprotected<A>(
+    seq: &mut A,
+    is_human_readable: bool,
+    headers: &UnprotectedCorimHeaderMap,
+) -> Result<(), A::Error>
+where
+    A: serde::ser::SerializeSeq,
+{
+    if is_human_readable {
+        let headers: BTreeMap<String, &ExtensionValue> = headers
+            .iter()
+            .map(|(label, value)| (label.to_string(), value))
+            .collect();
+        seq.serialize_element(&headers)
+    } else {
+        seq.serialize_element(headers)
+    }
+}
+
+/// Reads an unprotected header written by [`serialize_unprotected`]
+fn next_unprotected<'de, A>(
+    seq: &mut A,
+    is_human_readable: bool,
+) -> Result<Option<UnprotectedCorimHeaderMap<'de>>, A::Error>
+where
+    A: de::SeqAccess<'de>,
+{
+    if !is_human_readable {
+        return seq.next_element();
+    }
+
+    Ok(seq
+        .next_element::<BTreeMap<String, ExtensionValue>>()?
+        .map(|headers| {
+            headers
+                .into_iter()
+                .map(|(label, value)| (Label::parse(&label), value))
+                .collect()
+        }))
+}
+
+/// Writes a COSE header entry, using integer labels in CBOR and text labels in JSON
+fn serialize_header_entry<M, V>(
+    map: &mut M,
+    is_human_readable: bool,
+    key: i128,
+    value: &V,
+) -> Result<(), M::Error>
+where
+    M: SerializeMap,
+    V: Serialize + ?Sized,
+{
+    if is_human_readable {
+        map.serialize_entry(&key.to_string(), value)
+    } else {
+        map.serialize_entry(&Int::from(key), value)
+    }
+}
+
+/// Protected header shared by all signers of a [`COSESignCorim`]
+#[derive(Default, Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct ProtectedCorimBodyHeaderMap<'a> {
+    /// Content type indicator (should be "application/rim+cbor")
+    pub content_type: Text<'a>,
     /// Optional COSE header parameters
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(deserialize_with = "empty_map_as_none")]
-    #[serde(flatten)]
     pub cose_map: Option<CoseMap<'a>>,
 }
 
+impl Serialize for ProtectedCorimBodyHeaderMap<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let is_human_readable = serializer.is_human_readable();
+        let mut map = serializer.serialize_map(None)?;
+
+        serialize_header_entry(&mut map, is_human_readable, 3, &self.content_type)?;
+
+        if let Some(cose_map) = &self.cose_map {
+            for (key, value) in cose_map.0.iter() {
+                serialize_header_entry(&mut map, is_human_readable, key.0, value)?;
+            }
+        }
+
+        map.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ProtectedCorimBodyHeaderMap<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ProtectedCorimBodyHeaderMapVisitor<'a>(core::marker::PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for ProtectedCorimBodyHeaderMapVisitor<'a> {
+            type Value = ProtectedCorimBodyHeaderMap<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map containing protected CoRIM body header fields")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut content_type: Option<Text<'a>> = None;
+                let mut cose_map = CoseMap::default();
+
+                while let Some(label) = map.next_key::<Label>()? {
+                    let key = header_label(label)?;
+
+                    match key.0 {
+                        3 => content_type = Some(map.next_value::<BorrowText>()?.into()),
+                        _ => {
+                            cose_map.insert(key, map.next_value::<ExtensionValue<'a>>()?);
+                        }
+                    }
+                }
+
+                Ok(ProtectedCorimBodyHeaderMap {
+                    content_type: content_type
+                        .ok_or_else(|| de::Error::missing_field("content-type"))?,
+                    cose_map: (!cose_map.0.is_empty()).then_some(cose_map),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(ProtectedCorimBodyHeaderMapVisitor(
+            core::marker::PhantomData,
+        ))
+    }
+}
+
+/// Protected header for a single signer of a [`COSESignCorim`]
+#[derive(Default, Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[repr(C)]
+pub struct ProtectedCorimSignerHeaderMap<'a> {
+    /// Algorithm identifier for the signature
+    pub alg: Int,
+    /// Key identifier for the signing key
+    pub kid: Bytes<'a>,
+    /// CoRIM-specific metadata describing this signer
+    pub corim_meta: CorimMetaMap<'a>,
+    /// Optional COSE header parameters (e.g. x5chain, x5t)
+    pub cose_map: Option<CoseMap<'a>>,
+}
+
+impl Serialize for ProtectedCorimSignerHeaderMap<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let is_human_readable = serializer.is_human_readable();
+        let mut map = serializer.serialize_map(None)?;
+
+        serialize_header_entry(&mut map, is_human_readable, 1, &self.alg)?;
+        serialize_header_entry(&mut map, is_human_readable, 4, &self.kid)?;
+        serialize_header_entry(&mut map, is_human_readable, 8, &self.corim_meta)?;
+
+        if let Some(cose_map) = &self.cose_map {
+            for (key, value) in cose_map.0.iter() {
+                serialize_header_entry(&mut map, is_human_readable, key.0, value)?;
+            }
+        }
+
+        map.end()
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ProtectedCorimSignerHeaderMap<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ProtectedCorimSignerHeaderMapVisitor<'a>(core::marker::PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for ProtectedCorimSignerHeaderMapVisitor<'a> {
+            type Value = ProtectedCorimSignerHeaderMap<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map containing protected CoRIM signer header fields")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut alg: Option<Int> = None;
+                let mut kid: Option<Bytes<'a>> = None;
+                let mut corim_meta: Option<CorimMetaMap<'a>> = None;
+                let mut cose_map = CoseMap::default();
+
+                while let Some(label) = map.next_key::<Label>()? {
+                    let key = header_label(label)?;
+
+                    match key.0 {
+                        1 => alg = Some(map.next_value()?),
+                        4 => kid = Some(map.next_value()?),
+                        8 => corim_meta = Some(map.next_value()?),
+                        _ => {
+                            cose_map.insert(key, map.next_value::<ExtensionValue<'a>>()?);
+                        }
+                    }
+                }
+
+                Ok(ProtectedCorimSignerHeaderMap {
+                    alg: alg.ok_or_else(|| de::Error::missing_field("alg"))?,
+                    kid: kid.ok_or_else(|| de::Error::missing_field("kid"))?,
+                    corim_meta: corim_meta.ok_or_else(|| de::Error::missing_field("corim-meta"))?,
+                    cose_map: (!cose_map.0.is_empty()).then_some(cose_map),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(ProtectedCorimSignerHeaderMapVisitor(
+            core::marker::PhantomData,
+        ))
+    }
+}
+
 /// Metadata about the CoRIM signing operation
 #[derive(
     Default, Debug, Serialize, Deserialize, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone,
@@ -747,7 +1889,7 @@ pub struct ProtectedCorimHeaderMap<'a> {
 #[repr(C)]
 pub struct CorimMetaMap<'a> {
     /// Information about the signer
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub signer: CorimSignerMap<'a>,
     /// Optional validity period for the signature
     #[serde(rename = "1")]
@@ -762,15 +1904,15 @@ pub struct CorimMetaMap<'a> {
 #[repr(C)]
 pub struct CorimSignerMap<'a> {
     /// Name of the signing entity
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0", deserialize_with = "deserialize_text")]
     pub signer_name: EntityNameTypeChoice<'a>,
     /// Optional URI identifying the signer
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
     pub signer_uri: Option<Uri<'a>>,
     /// Optional COSE-specific extensions
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
-    #[serde(flatten)]
+    #[serde(borrow, flatten)]
     pub extension: Option<CoseMap<'a>>,
 }
 
@@ -794,7 +1936,7 @@ mod tests {
     use crate::triples::{
         ClassMap, EnvironmentMap, MeasurementMap, MeasurementValuesMap, ReferenceTripleRecord,
     };
-    use std::collections::BTreeMap;
+    use alloc::collections::BTreeMap;
 
     use super::*;
 
@@ -811,23 +1953,19 @@ mod tests {
     fn test_cose_sign1_corim_serialize_deserialize() {
         let expected = vec![
             0x84, // array(4)
-              0x58, 0x41, // bstr(65) -- COSE protected header
+              0x58, 0x3d, // bstr(61) -- COSE protected header
                 0xbf, // map(indef)
-                  0x61, // key: tstr(1)
-                    0x31, // "1"
+                  0x01, // key: 1
                   0x26, // value: -7
-                  0x61, // key: tstr(1)
-                    0x33, // "3"
+                  0x03, // key: 3
                   0x74, // value: tstr(20)
                     0x61, 0x70, 0x70, 0x6c, 0x69, 0x63, 0x61, 0x74, // "applicat"
                     0x69, 0x6f, 0x6e, 0x2f, 0x72, 0x69, 0x6d, 0x2b, // "ion/rim+"
                     0x63, 0x62, 0x6f, 0x72,                         // "cbor"
-                  0x61, // key: tstr(1)
-                    0x34, // "4"
+                  0x04, // key: 4
                   0x47, // value: bstr(7)
                     0x6b, 0x65, 0x79, 0x2d, 0x30, 0x30, 0x31,
-                  0x61, // key: tstr(1)
-                    0x38, // "8"
+                  0x08, // key: 8
                   0xa1, // value: map(1)
                     0x61, // key: tstr(1)
                       0x30, // "0"
@@ -907,7 +2045,7 @@ mod tests {
                               0x62, // key: tstr(2)
                                 0x33, 0x33, // "33"
                               0x81, // value: array(1)
-                                0xf6, // null
+                                0x00, // tag-creator
                             0xff, // break(map)
                           0x61, // key: tstr(1)
                             0x34, // "4"
@@ -994,9 +2132,10 @@ mod tests {
                     signature_validity: None,
                 },
                 cose_map: None,
-            },
+            }
+            .into(),
             unprotected: BTreeMap::new(),
-            payload: UnsignedCorimMap {
+            payload: TaggedUnsignedCorimMap::new(UnsignedCorimMap {
                 id: "corim-001".into(),
                 tags: vec![
                     ConciseSwidTag {
@@ -1048,7 +2187,7 @@ mod tests {
                 rim_validity: None,
                 entities: None,
                 extension: None,
-            }
+            })
             .into(),
             signature: Bytes::from(vec![0]).into(),
         };
@@ -1060,12 +2199,61 @@ mod tests {
 
         assert_eq!(expected.as_slice(), &actual);
 
-        let deserialized: COSESign1Corim = ciborium::de::from_reader(actual.as_slice())
+        let deserialized: COSESign1Corim = crate::cbor::from_slice(actual.as_slice())
             .expect("Failed to deserialize COSE_Sign1 CoRIM");
 
         assert_eq!(cose_corim, deserialized);
     }
 
+    #[test]
+    fn test_protected_header_legacy_text_labels() {
+        let header = ProtectedCorimHeaderMap {
+            alg: Integer(-7),
+            content_type: "application/rim+cbor".into(),
+            kid: vec![0x01].into(),
+            corim_meta: CorimMetaMap {
+                signer: CorimSignerMap {
+                    signer_name: "Example Signer".into(),
+                    ..Default::default()
+                },
+                signature_validity: None,
+            },
+            cose_map: None,
+        };
+
+        // Written by earlier versions of this crate
+        let legacy = vec![
+            0xbf, // map(indef)
+              0x61, 0x31, 0x26, // "1": -7
+              0x61, 0x33, 0x74, // "3": tstr(20)
+                0x61, 0x70, 0x70, 0x6c, 0x69, 0x63, 0x61, 0x74, // "applicat"
+                0x69, 0x6f, 0x6e, 0x2f, 0x72, 0x69, 0x6d, 0x2b, // "ion/rim+"
+                0x63, 0x62, 0x6f, 0x72,                         // "cbor"
+              0x61, 0x34, 0x41, 0x01, // "4": h'01'
+              0x61, 0x38, 0xa1, // "8": map(1)
+                0x61, 0x30, 0xa1, // "0": map(1)
+                  0x61, 0x30, 0x6e, // "0": tstr(14)
+                    0x45, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65, 0x20, // "Example "
+                    0x53, 0x69, 0x67, 0x6e, 0x65, 0x72,             // "Signer"
+            0xff, // break
+        ];
+        let decoded: ProtectedCorimHeaderMap = crate::cbor::from_slice(legacy.as_slice()).unwrap();
+        assert_eq!(decoded, header);
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&header, &mut bytes).unwrap();
+        assert_eq!(&bytes[..3], &[0xbf, 0x01, 0x26]);
+        let decoded: ProtectedCorimHeaderMap = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, header);
+
+        let json = serde_json::to_string(&header).unwrap();
+        assert!(json.starts_with(r#"{"1":-7,"3":"application/rim+cbor""#));
+        assert_eq!(
+            serde_json::from_str::<ProtectedCorimHeaderMap>(&json).unwrap(),
+            header
+        );
+    }
+
     #[test]
     fn test_profile_type_choice() {
         let profile = ProfileTypeChoice::OidType(OidType::from(
@@ -1084,7 +2272,7 @@ mod tests {
 
         assert_eq!(actual, expected);
 
-        let profile_de: ProfileTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let profile_de: ProfileTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(profile_de, profile);
 
@@ -1098,4 +2286,205 @@ mod tests {
 
         assert_eq!(profile_de, profile);
     }
+
+    #[test]
+    fn test_concise_tag_type_choice_json() {
+        let json = r#"{"type":"coswid","value":{"0":"swid-1","12":0,"1":"Firmware","2":{"31":"ACME Inc.","33":1}}}"#;
+
+        let tag: ConciseTagTypeChoice = serde_json::from_str(json).unwrap();
+        assert_eq!(tag.as_coswid().unwrap().software_name, "Firmware");
+
+        let actual = serde_json::to_string(&tag).unwrap();
+        let tag_de: ConciseTagTypeChoice = serde_json::from_str(&actual).unwrap();
+        assert_eq!(tag_de, tag);
+
+        let reordered: ConciseTagTypeChoice = serde_json::from_str(
+            r#"{"value":{"0":"swid-1","12":0,"1":"Firmware","2":{"31":"ACME Inc.","33":1}},"type":"coswid"}"#,
+        )
+        .unwrap();
+        assert_eq!(reordered, tag);
+        assert!(
+            serde_json::from_str::<ConciseTagTypeChoice>(r#"{"value":{"0":"swid-1"}}"#)
+                .unwrap_err()
+                .to_string()
+                .contains("missing field `type`")
+        );
+
+        assert!(
+            serde_json::from_str::<ConciseTagTypeChoice>(r#"{"type":"swid","value":{}}"#).is_err()
+        );
+        assert!(serde_json::from_str::<ConciseTagTypeChoice>(r#"{"type":"comid"}"#).is_err());
+    }
+
+    #[test]
+    fn test_validity_map_contains() {
+        let validity = ValidityMap::new(Some(100.into()), 200.into());
+        assert!(!validity.contains(99));
+        assert!(validity.contains(100));
+        assert!(validity.contains(200));
+        assert!(!validity.contains(201));
+
+        let validity = ValidityMap::new(None, 200.into());
+        assert!(validity.contains(0));
+        assert!(!validity.contains(201));
+    }
+
+    #[test]
+    fn test_profile_type_choice_uri_serde() {
+        let profile = ProfileTypeChoice::Uri(Text::from("https://example.com/profile").into());
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&profile, &mut bytes).unwrap();
+        assert_eq!(&bytes[..2], &[0xd8, 0x20]); // tag(32)
+        let actual: ProfileTypeChoice = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(actual, profile);
+    }
+
+    #[test]
+    fn test_corim_role_serde() {
+        let roles = vec![
+            CorimRoleTypeChoice::ManifestCreator,
+            CorimRoleTypeChoice::ManifestSigner,
+        ];
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&roles, &mut bytes).unwrap();
+        assert_eq!(bytes, [0x82, 0x01, 0x02]);
+        let actual: Vec<CorimRoleTypeChoice> = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(actual, roles);
+
+        let json = serde_json::to_string(&roles).unwrap();
+        assert_eq!(json, r#"["manifest-creator","manifest-signer"]"#);
+        let actual: Vec<CorimRoleTypeChoice> = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, roles);
+
+        assert!(serde_json::from_str::<CorimRoleTypeChoice>("3").is_err());
+        assert!(serde_json::from_str::<CorimRoleTypeChoice>(r#""tag-creator""#).is_err());
+    }
+
+    #[test]
+    fn test_corim_json_roundtrip() {
+        for fixture in [
+            include_bytes!("../testdata/corim/unsigned.cbor").as_slice(),
+            include_bytes!("../testdata/corim/signed.cbor").as_slice(),
+        ] {
+            let corim: Corim = crate::cbor::from_slice(fixture).unwrap();
+            let json = serde_json::to_string(&corim).unwrap();
+            let decoded: Corim = serde_json::from_str(&json).unwrap();
+
+            let mut bytes = vec![];
+            ciborium::into_writer(&decoded, &mut bytes).unwrap();
+            assert_eq!(bytes, fixture);
+
+            // Another tool may write "value" before "type"
+            let object: serde_json::Value = serde_json::from_str(&json).unwrap();
+            let reordered = format!(
+                r#"{{"value":{},"type":{}}}"#,
+                object["value"], object["type"]
+            );
+            let decoded: Corim = serde_json::from_str(&reordered).unwrap();
+            let mut bytes = vec![];
+            ciborium::into_writer(&decoded, &mut bytes).unwrap();
+            assert_eq!(bytes, fixture);
+        }
+
+        assert!(serde_json::from_str::<Corim>(r#"{"value":{}}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("missing field `type`"));
+        assert!(serde_json::from_str::<Corim>(r#"{"type":"corim","value":{}}"#).is_err());
+        assert!(serde_json::from_str::<Corim>(r#"{"type":"signed-corim"}"#).is_err());
+    }
+
+    #[test]
+    fn test_corim_id_type_choice_serde() {
+        let ids = vec![
+            CorimIdTypeChoice::from("31fb5abf-023e-4992-aa4e-95f9c1503bfa"),
+            CorimIdTypeChoice::Uuid(
+                UuidType::try_from("31fb5abf-023e-4992-aa4e-95f9c1503bfa").unwrap(),
+            ),
+        ];
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&ids, &mut bytes).unwrap();
+        assert_eq!(&bytes[..3], &[0x82, 0x78, 0x24]);
+        assert_eq!(&bytes[39..41], &[0x50, 0x31]);
+        let actual: Vec<CorimIdTypeChoice> = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(actual, ids);
+
+        let json = serde_json::to_string(&ids).unwrap();
+        assert_eq!(
+            json,
+            r#"["31fb5abf-023e-4992-aa4e-95f9c1503bfa",{"type":"uuid","value":"31fb5abf-023e-4992-aa4e-95f9c1503bfa"}]"#
+        );
+        let actual: Vec<CorimIdTypeChoice> = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, ids);
+
+        assert!(
+            serde_json::from_str::<CorimIdTypeChoice>(r#"{"type":"oid","value":"1.2"}"#).is_err()
+        );
+        assert!(serde_json::from_str::<CorimIdTypeChoice>("7").is_err());
+    }
+
+    #[test]
+    fn test_comid_choices_json() {
+        use crate::comid::{ComidRoleTypeChoice, TagIdTypeChoice, TagRelTypeChoice};
+        use crate::fixed_bytes::FixedBytes;
+
+        let roles = vec![
+            ComidRoleTypeChoice::TagCreator,
+            ComidRoleTypeChoice::Creator,
+            ComidRoleTypeChoice::Maintainer,
+        ];
+        let json = serde_json::to_string(&roles).unwrap();
+        assert_eq!(json, r#"["tag-creator","creator","maintainer"]"#);
+        let actual: Vec<ComidRoleTypeChoice> = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, roles);
+        let actual: Vec<ComidRoleTypeChoice> = serde_json::from_str("[0,1,2]").unwrap();
+        assert_eq!(actual, roles);
+        assert!(serde_json::from_str::<ComidRoleTypeChoice>(r#""signer""#).is_err());
+        assert!(serde_json::from_str::<ComidRoleTypeChoice>("3").is_err());
+
+        let relations = vec![TagRelTypeChoice::Supplements, TagRelTypeChoice::Replaces];
+        let json = serde_json::to_string(&relations).unwrap();
+        assert_eq!(json, r#"["supplements","replaces"]"#);
+        let actual: Vec<TagRelTypeChoice> = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, relations);
+        assert!(serde_json::from_str::<TagRelTypeChoice>(r#""amends""#).is_err());
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&roles, &mut bytes).unwrap();
+        assert_eq!(bytes, [0x83, 0x00, 0x01, 0x02]);
+        let actual: Vec<ComidRoleTypeChoice> = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(actual, roles);
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&relations, &mut bytes).unwrap();
+        assert_eq!(bytes, [0x82, 0x00, 0x01]);
+        let actual: Vec<TagRelTypeChoice> = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(actual, relations);
+
+        // Earlier releases wrote null for every variant
+        let actual: TagRelTypeChoice = crate::cbor::from_slice([0xf6].as_slice()).unwrap();
+        assert_eq!(actual, TagRelTypeChoice::Supplements);
+        let actual: ComidRoleTypeChoice = crate::cbor::from_slice([0xf6].as_slice()).unwrap();
+        assert_eq!(actual, ComidRoleTypeChoice::TagCreator);
+
+        let tag_id = TagIdTypeChoice::Uuid(UuidType::from(FixedBytes([0x22; 16])));
+        let mut bytes = vec![];
+        ciborium::into_writer(&tag_id, &mut bytes).unwrap();
+        assert_eq!(&bytes[..2], &[0x50, 0x22]);
+        let actual: TagIdTypeChoice = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(actual, tag_id);
+
+        let json = serde_json::to_string(&tag_id).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"uuid","value":"22222222-2222-2222-2222-222222222222"}"#
+        );
+        let actual: TagIdTypeChoice = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, tag_id);
+        let actual: TagIdTypeChoice = serde_json::from_str(r#""acme-comid""#).unwrap();
+        assert_eq!(actual.as_str(), Some("acme-comid"));
+    }
 }
@@ -0,0 +1,1779 @@
+// SPDX-License-Identifier: MIT
+
+//! COSE signing and verification for signed CoRIMs
+//!
+//! This module provides the cryptographic operations needed to produce and check
+//! [`COSESign1Corim`] (CBOR tag 18) and multi-signer [`COSESignCorim`] (CBOR tag 98)
+//! structures, along with helpers for the X.509 related COSE header parameters defined in
+//! RFC 9360.
+//!
+//! # Key Components
+//!
+//! * [`CoseSigningKey`] - A software private key used to sign a CoRIM
+//! * [`CoseVerifyingKey`] - A public key used to verify a CoRIM signature
+//! * [`SignerPolicy`] - Whether any or all signers of a multi-signer CoRIM must verify
+//! * [`Verifier`] - The keys or trust anchors [`ConciseRimTypeChoice::verify`] checks any
+//!   signed CoRIM against
+//! * [`COSE_HEADER_X5CHAIN`] / [`COSE_HEADER_X5T`] - Header labels for certificate chains and
+//!   certificate thumbprints
+//!
+//! # Supported Algorithms
+//!
+//! | Algorithm | COSE ID | Sign | Verify |
+//! |-----------|---------|------|--------|
+//! | ES256     | -7      | yes  | yes    |
+//! | ES384     | -35     | yes  | yes    |
+//! | EdDSA     | -8      | yes  | yes    |
+//! | PS256/384/512 | -37/-38/-39 | no | yes |
+//! | RS256/384/512 | -257/-258/-259 | no | yes |
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use corim_rs::cose::{CoseSigningKey, CoseVerifyingKey};
+//! # use corim_rs::corim::COSESign1Corim;
+//! # fn example(mut signed: COSESign1Corim, key_pem: &str) -> corim_rs::result::Result<()> {
+//! let key = CoseSigningKey::from_pkcs8_pem(key_pem)?;
+//!
+//! // Sign the CoRIM, updating the algorithm in the protected header
+//! signed.sign(&key)?;
+//!
+//! // Verify with the matching public key
+//! signed.verify(&key.verifying_key())?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Verification uses the protected header and payload byte strings as they were decoded, so
+//! messages signed by other COSE implementations verify regardless of how they encoded their
+//! maps. Values built or modified locally are signed over the CBOR encoding of this crate.
+
+use ed25519_dalek::pkcs8::DecodePrivateKey as _;
+use p256::ecdsa::signature::{Signer as _, Verifier as _};
+use p256::pkcs8::DecodePublicKey as _;
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+
+use crate::{
+    corim::{
+        COSESign1Corim, COSESignCorim, COSESignatureCorim, ConciseRimTypeChoice, CorimMetaMap,
+        DetachedCOSESign1Corim, Encoded, ProtectedCorimHeaderMap, ProtectedCorimSignerHeaderMap,
+        UnprotectedCorimHeaderMap,
+    },
+    x509::{self, TrustAnchorStore, ValidatedChain},
+    Bytes, CoseAlgorithm, CoseEllipticCurve, CoseError, CoseKey, CoseKty, CoseMap, ExtensionValue,
+    HashEntry, Integer, Label, Result, X509Error,
+};
+
+/// COSE header label for an ordered chain of X.509 certificates (RFC 9360)
+pub const COSE_HEADER_X5CHAIN: Integer = Integer(33);
+
+/// COSE header label for the hash of an X.509 certificate (RFC 9360)
+pub const COSE_HEADER_X5T: Integer = Integer(34);
+
+/// A software private key that can produce COSE signatures
+#[derive(Debug, Clone)]
+pub enum CoseSigningKey {
+    /// ECDSA key on the NIST P-256 curve, used with ES256
+    P256(p256::ecdsa::SigningKey),
+    /// ECDSA key on the NIST P-384 curve, used with ES384
+    P384(p384::ecdsa::SigningKey),
+    /// Ed25519 key, used with EdDSA
+    Ed25519(ed25519_dalek::SigningKey),
+}
+
+impl CoseSigningKey {
+    /// Loads a private key from a DER encoded PKCS#8 document
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self> {
+        if let Ok(key) = p256::ecdsa::SigningKey::from_pkcs8_der(der) {
+            return Ok(Self::P256(key));
+        }
+        if let Ok(key) = p384::ecdsa::SigningKey::from_pkcs8_der(der) {
+            return Ok(Self::P384(key));
+        }
+        if let Ok(key) = ed25519_dalek::SigningKey::from_pkcs8_der(der) {
+            return Ok(Self::Ed25519(key));
+        }
+
+        Err(CoseError::InvalidKey(
+            "expected a PKCS#8 encoded P-256, P-384 or Ed25519 private key".to_string(),
+        ))?
+    }
+
+    /// Loads a private key from a PEM encoded PKCS#8 document
+    pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
+        let (label, der) = der::pem::decode_vec(pem.as_bytes())
+            .map_err(|e| CoseError::InvalidKey(e.to_string()))?;
+
+        if label != "PRIVATE KEY" {
+            return Err(CoseError::InvalidKey(format!(
+                "expected a \"PRIVATE KEY\" PEM block, found \"{label}\""
+            )))?;
+        }
+
+        Self::from_pkcs8_der(&der)
+    }
+
+    /// Loads a private key from a COSE_Key carrying the `d` parameter. If the key also carries
+    /// its public parameters, they must match the private key.
+    pub fn from_cose_key(key: &CoseKey) -> Result<Self> {
+        let d = cose_key_param(&key.d, "d")?;
+        let invalid = |e: p256::ecdsa::Error| CoseError::InvalidKey(e.to_string());
+
+        let signing_key = match (&key.kty, &key.crv) {
+            (CoseKty::Ec2, Some(CoseEllipticCurve::P256)) => {
+                Self::P256(p256::ecdsa::SigningKey::from_slice(d).map_err(invalid)?)
+            }
+            (CoseKty::Ec2, Some(CoseEllipticCurve::P384)) => {
+                Self::P384(p384::ecdsa::SigningKey::from_slice(d).map_err(invalid)?)
+            }
+            (CoseKty::Okp, Some(CoseEllipticCurve::Ed25519)) => {
+                let d = d.try_into().map_err(|_| {
+                    CoseError::InvalidKey("an Ed25519 \"d\" parameter must be 32 bytes".to_string())
+                })?;
+                Self::Ed25519(ed25519_dalek::SigningKey::from_bytes(&d))
+            }
+            _ => return Err(unsupported_cose_key(key))?,
+        };
+
+        if key.x.is_some() && CoseVerifyingKey::from_cose_key(key)? != signing_key.verifying_key() {
+            return Err(CoseError::InvalidKey(
+                "the public parameters of the COSE key do not match its \"d\" parameter"
+                    .to_string(),
+            ))?;
+        }
+
+        Ok(signing_key)
+    }
+
+    /// Returns the COSE algorithm used by signatures produced with this key
+    pub fn algorithm(&self) -> CoseAlgorithm {
+        match self {
+            Self::P256(_) => CoseAlgorithm::ES256,
+            Self::P384(_) => CoseAlgorithm::ES384,
+            Self::Ed25519(_) => CoseAlgorithm::EdDSA,
+        }
+    }
+
+    /// Returns the public half of this key
+    pub fn verifying_key(&self) -> CoseVerifyingKey {
+        match self {
+            Self::P256(key) => CoseVerifyingKey::P256(*key.verifying_key()),
+            Self::P384(key) => CoseVerifyingKey::P384(*key.verifying_key()),
+            Self::Ed25519(key) => CoseVerifyingKey::Ed25519(key.verifying_key()),
+        }
+    }
+
+    /// Signs `message`, returning the signature in its COSE encoding
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Self::P256(key) => {
+                let signature: p256::ecdsa::Signature = key
+                    .try_sign(message)
+                    .map_err(|e| CoseError::Encoding(e.to_string()))?;
+                Ok(signature.to_bytes().to_vec())
+            }
+            Self::P384(key) => {
+                let signature: p384::ecdsa::Signature = key
+                    .try_sign(message)
+                    .map_err(|e| CoseError::Encoding(e.to_string()))?;
+                Ok(signature.to_bytes().to_vec())
+            }
+            Self::Ed25519(key) => Ok(key.sign(message).to_bytes().to_vec()),
+        }
+    }
+}
+
+/// A public key that can verify COSE signatures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoseVerifyingKey {
+    /// ECDSA key on the NIST P-256 curve
+    P256(p256::ecdsa::VerifyingKey),
+    /// ECDSA key on the NIST P-384 curve
+    P384(p384::ecdsa::VerifyingKey),
+    /// Ed25519 key
+    Ed25519(ed25519_dalek::VerifyingKey),
+    /// RSA key, used with the PS* and RS* algorithms
+    Rsa(rsa::RsaPublicKey),
+}
+
+impl CoseVerifyingKey {
+    /// Loads a public key from a DER encoded SubjectPublicKeyInfo
+    pub fn from_public_key_der(der: &[u8]) -> Result<Self> {
+        if let Ok(key) = p256::ecdsa::VerifyingKey::from_public_key_der(der) {
+            return Ok(Self::P256(key));
+        }
+        if let Ok(key) = p384::ecdsa::VerifyingKey::from_public_key_der(der) {
+            return Ok(Self::P384(key));
+        }
+        if let Ok(key) = ed25519_dalek::VerifyingKey::from_public_key_der(der) {
+            return Ok(Self::Ed25519(key));
+        }
+        if let Ok(key) = rsa::RsaPublicKey::from_public_key_der(der) {
+            return Ok(Self::Rsa(key));
+        }
+
+        Err(CoseError::InvalidKey(
+            "expected a P-256, P-384, Ed25519 or RSA SubjectPublicKeyInfo".to_string(),
+        ))?
+    }
+
+    /// Loads a public key from a PEM encoded SubjectPublicKeyInfo
+    pub fn from_public_key_pem(pem: &str) -> Result<Self> {
+        let (label, der) = der::pem::decode_vec(pem.as_bytes())
+            .map_err(|e| CoseError::InvalidKey(e.to_string()))?;
+
+        if label != "PUBLIC KEY" {
+            return Err(CoseError::InvalidKey(format!(
+                "expected a \"PUBLIC KEY\" PEM block, found \"{label}\""
+            )))?;
+        }
+
+        Self::from_public_key_der(&der)
+    }
+
+    /// Loads the subject public key of a DER encoded X.509 certificate
+    pub fn from_certificate_der(der: &[u8]) -> Result<Self> {
+        x509::public_key(&x509::parse_certificate(der)?)
+    }
+
+    /// Loads a public key from the public parameters of a COSE_Key
+    pub fn from_cose_key(key: &CoseKey) -> Result<Self> {
+        let invalid = |e: p256::ecdsa::Error| CoseError::InvalidKey(e.to_string());
+        let sec1 = || -> Result<Vec<u8>> {
+            // Uncompressed SEC1 point: 0x04 || x || y
+            let mut point = vec![0x04];
+            point.extend_from_slice(cose_key_param(&key.x, "x")?);
+            point.extend_from_slice(cose_key_param(&key.y, "y")?);
+            Ok(point)
+        };
+
+        match (&key.kty, &key.crv) {
+            (CoseKty::Ec2, Some(CoseEllipticCurve::P256)) => Ok(Self::P256(
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(&sec1()?).map_err(invalid)?,
+            )),
+            (CoseKty::Ec2, Some(CoseEllipticCurve::P384)) => Ok(Self::P384(
+                p384::ecdsa::VerifyingKey::from_sec1_bytes(&sec1()?).map_err(invalid)?,
+            )),
+            (CoseKty::Okp, Some(CoseEllipticCurve::Ed25519)) => {
+                let x = cose_key_param(&key.x, "x")?.try_into().map_err(|_| {
+                    CoseError::InvalidKey("an Ed25519 \"x\" parameter must be 32 bytes".to_string())
+                })?;
+                Ok(Self::Ed25519(
+                    ed25519_dalek::VerifyingKey::from_bytes(&x)
+                        .map_err(|e| CoseError::InvalidKey(e.to_string()))?,
+                ))
+            }
+            _ => Err(unsupported_cose_key(key))?,
+        }
+    }
+
+    /// Verifies a COSE encoded `signature` over `message` using algorithm `alg`
+    pub fn verify(&self, alg: &CoseAlgorithm, message: &[u8], signature: &[u8]) -> Result<()> {
+        let invalid = |_| CoseError::InvalidSignature;
+
+        match (alg, self) {
+            (CoseAlgorithm::ES256, Self::P256(key)) => {
+                let signature = p256::ecdsa::Signature::from_slice(signature).map_err(invalid)?;
+                key.verify(message, &signature).map_err(invalid)?;
+            }
+            (CoseAlgorithm::ES384, Self::P384(key)) => {
+                let signature = p384::ecdsa::Signature::from_slice(signature).map_err(invalid)?;
+                key.verify(message, &signature).map_err(invalid)?;
+            }
+            (CoseAlgorithm::EdDSA, Self::Ed25519(key)) => {
+                let signature = ed25519_dalek::Signature::from_slice(signature).map_err(invalid)?;
+                key.verify(message, &signature).map_err(invalid)?;
+            }
+            (CoseAlgorithm::PS256, Self::Rsa(key)) => {
+                rsa_pss_verify::<Sha256>(key, message, signature)?;
+            }
+            (CoseAlgorithm::PS384, Self::Rsa(key)) => {
+                rsa_pss_verify::<Sha384>(key, message, signature)?;
+            }
+            (CoseAlgorithm::PS512, Self::Rsa(key)) => {
+                rsa_pss_verify::<Sha512>(key, message, signature)?;
+            }
+            (CoseAlgorithm::RS256, Self::Rsa(key)) => {
+                rsa_pkcs1_verify::<Sha256>(key, message, signature)?;
+            }
+            (CoseAlgorithm::RS384, Self::Rsa(key)) => {
+                rsa_pkcs1_verify::<Sha384>(key, message, signature)?;
+            }
+            (CoseAlgorithm::RS512, Self::Rsa(key)) => {
+                rsa_pkcs1_verify::<Sha512>(key, message, signature)?;
+            }
+            (
+                CoseAlgorithm::ES256
+                | CoseAlgorithm::ES384
+                | CoseAlgorithm::EdDSA
+                | CoseAlgorithm::PS256
+                | CoseAlgorithm::PS384
+                | CoseAlgorithm::PS512
+                | CoseAlgorithm::RS256
+                | CoseAlgorithm::RS384
+                | CoseAlgorithm::RS512,
+                _,
+            ) => {
+                return Err(CoseError::InvalidKey(format!(
+                    "key type cannot be used with {alg}"
+                )))?;
+            }
+            (alg, _) => {
+                return Err(CoseError::UnsupportedAlgorithm(alg.clone().into()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn cose_key_param<'a>(param: &'a Option<Bytes>, name: &str) -> Result<&'a [u8]> {
+    param.as_ref().map(AsRef::as_ref).ok_or_else(|| {
+        CoseError::InvalidKey(format!("COSE key has no \"{name}\" parameter")).into()
+    })
+}
+
+fn unsupported_cose_key(key: &CoseKey) -> CoseError {
+    CoseError::InvalidKey(format!(
+        "unsupported COSE key type {:?} with curve {:?}, expected an EC2 P-256 or P-384 key \
+         or an OKP Ed25519 key",
+        key.kty, key.crv
+    ))
+}
+
+fn rsa_pss_verify<D>(key: &rsa::RsaPublicKey, message: &[u8], signature: &[u8]) -> Result<()>
+where
+    D: sha2::Digest + sha2::digest::FixedOutputReset,
+{
+    let signature =
+        rsa::pss::Signature::try_from(signature).map_err(|_| CoseError::InvalidSignature)?;
+    rsa::pss::VerifyingKey::<D>::new(key.clone())
+        .verify(message, &signature)
+        .map_err(|_| CoseError::InvalidSignature)?;
+    Ok(())
+}
+
+fn rsa_pkcs1_verify<D>(key: &rsa::RsaPublicKey, message: &[u8], signature: &[u8]) -> Result<()>
+where
+    D: sha2::Digest + rsa::pkcs8::AssociatedOid,
+{
+    let signature =
+        rsa::pkcs1v15::Signature::try_from(signature).map_err(|_| CoseError::InvalidSignature)?;
+    rsa::pkcs1v15::VerifyingKey::<D>::new(key.clone())
+        .verify(message, &signature)
+        .map_err(|_| CoseError::InvalidSignature)?;
+    Ok(())
+}
+
+/// Builds the COSE `Sig_structure` for a COSE_Sign1 message (RFC 9052, section 4.4)
+pub(crate) fn sig_structure1(protected: &[u8], payload: &[u8]) -> Result<Vec<u8>> {
+    let structure = ciborium::Value::Array(vec![
+        ciborium::Value::Text("Signature1".to_string()),
+        ciborium::Value::Bytes(protected.to_vec()),
+        ciborium::Value::Bytes(vec![]),
+        ciborium::Value::Bytes(payload.to_vec()),
+    ]);
+
+    let mut buf = vec![];
+    ciborium::into_writer(&structure, &mut buf).map_err(|e| CoseError::Encoding(e.to_string()))?;
+    Ok(buf)
+}
+
+/// Builds the COSE `Sig_structure` for one signer of a COSE_Sign message (RFC 9052, section 4.4)
+pub(crate) fn sig_structure(
+    body_protected: &[u8],
+    sign_protected: &[u8],
+    payload: &[u8],
+) -> Result<Vec<u8>> {
+    let structure = ciborium::Value::Array(vec![
+        ciborium::Value::Text("Signature".to_string()),
+        ciborium::Value::Bytes(body_protected.to_vec()),
+        ciborium::Value::Bytes(sign_protected.to_vec()),
+        ciborium::Value::Bytes(vec![]),
+        ciborium::Value::Bytes(payload.to_vec()),
+    ]);
+
+    let mut buf = vec![];
+    ciborium::into_writer(&structure, &mut buf).map_err(|e| CoseError::Encoding(e.to_string()))?;
+    Ok(buf)
+}
+
+/// Computes a certificate hash for the x5t header parameter
+pub(crate) fn cose_hash(alg: &CoseAlgorithm, data: &[u8]) -> Result<Vec<u8>> {
+    match alg {
+        CoseAlgorithm::Sha256 => Ok(Sha256::digest(data).to_vec()),
+        CoseAlgorithm::Sha256_64 => Ok(Sha256::digest(data)[..8].to_vec()),
+        CoseAlgorithm::Sha384 => Ok(Sha384::digest(data).to_vec()),
+        CoseAlgorithm::Sha512 => Ok(Sha512::digest(data).to_vec()),
+        alg => Err(CoseError::UnsupportedAlgorithm(alg.clone().into()))?,
+    }
+}
+
+fn x5chain_to_value<'a, I>(chain: I) -> Result<ExtensionValue<'a>>
+where
+    I: IntoIterator,
+    I::Item: Into<Bytes<'a>>,
+{
+    let mut certs: Vec<ExtensionValue> = chain
+        .into_iter()
+        .map(|cert| ExtensionValue::Bytes(cert.into()))
+        .collect();
+
+    // A single certificate is carried as a bstr, multiple as an array of bstr.
+    match certs.len() {
+        0 => Err(X509Error::EmptyChain)?,
+        1 => Ok(certs.remove(0)),
+        _ => Ok(ExtensionValue::Array(certs)),
+    }
+}
+
+fn x5chain_from_value<'a>(value: &ExtensionValue<'a>) -> Result<Vec<Bytes<'a>>> {
+    match value {
+        ExtensionValue::Bytes(cert) => Ok(vec![cert.clone()]),
+        ExtensionValue::Array(certs) if !certs.is_empty() => certs
+            .iter()
+            .map(|cert| match cert {
+                ExtensionValue::Bytes(cert) => Ok(cert.clone()),
+                _ => Err(CoseError::InvalidHeader(
+                    "x5chain entries must be byte strings".to_string(),
+                ))?,
+            })
+            .collect(),
+        _ => Err(CoseError::InvalidHeader(
+            "x5chain must be a byte string or a non-empty array of byte strings".to_string(),
+        ))?,
+    }
+}
+
+fn x5t_to_value<'a>(alg: &CoseAlgorithm, cert: &[u8]) -> Result<ExtensionValue<'a>> {
+    let hash = cose_hash(alg, cert)?;
+    Ok(ExtensionValue::Array(vec![
+        ExtensionValue::Int(i64::from(alg.clone()).into()),
+        ExtensionValue::Bytes(hash.into()),
+    ]))
+}
+
+fn x5t_from_value<'a>(value: &ExtensionValue<'a>) -> Result<HashEntry<'a>> {
+    let invalid = || CoseError::InvalidHeader("x5t must be a [alg, hash] array".to_string());
+
+    match value.as_array().map(Vec::as_slice) {
+        Some([alg, ExtensionValue::Bytes(hash)]) => {
+            let alg = alg.as_int().or_else(|| alg.as_uint()).ok_or_else(invalid)?;
+            let alg = i64::try_from(alg.0).map_err(|_| invalid())?;
+            Ok(HashEntry {
+                hash_alg_id: CoseAlgorithm::try_from(alg)?,
+                hash_value: hash.clone(),
+            })
+        }
+        _ => Err(invalid())?,
+    }
+}
+
+/// Implements the x5chain and x5t accessors for protected header maps with a `cose_map` field
+macro_rules! impl_x5_headers {
+    ($($header:ident),* $(,)?) => {
+        $(
+            impl<'a> $header<'a> {
+                fn cose_value(&self, label: Integer) -> Option<&ExtensionValue<'a>> {
+                    self.cose_map.as_ref().and_then(|map| map.0.get(&label))
+                }
+
+                fn set_cose_value(&mut self, label: Integer, value: ExtensionValue<'a>) {
+                    self.cose_map
+                        .get_or_insert_with(CoseMap::default)
+                        .insert(label, value);
+                }
+
+                /// Sets the x5chain header to the DER encoded certificates in `chain`, leaf first
+                pub fn set_x5chain<I>(&mut self, chain: I) -> Result<()>
+                where
+                    I: IntoIterator,
+                    I::Item: Into<Bytes<'a>>,
+                {
+                    let value = x5chain_to_value(chain)?;
+                    self.set_cose_value(COSE_HEADER_X5CHAIN, value);
+                    Ok(())
+                }
+
+                /// Returns the DER encoded certificates from the x5chain header, leaf first
+                pub fn x5chain(&self) -> Result<Option<Vec<Bytes<'a>>>> {
+                    self.cose_value(COSE_HEADER_X5CHAIN)
+                        .map(x5chain_from_value)
+                        .transpose()
+                }
+
+                /// Sets the x5t header to the `alg` hash of the DER encoded certificate `cert`
+                pub fn set_x5t(&mut self, alg: &CoseAlgorithm, cert: &[u8]) -> Result<()> {
+                    let value = x5t_to_value(alg, cert)?;
+                    self.set_cose_value(COSE_HEADER_X5T, value);
+                    Ok(())
+                }
+
+                /// Returns the certificate hash from the x5t header
+                pub fn x5t(&self) -> Result<Option<HashEntry<'a>>> {
+                    self.cose_value(COSE_HEADER_X5T)
+                        .map(x5t_from_value)
+                        .transpose()
+                }
+            }
+        )*
+    };
+}
+
+impl_x5_headers!(ProtectedCorimHeaderMap, ProtectedCorimSignerHeaderMap);
+
+/// Validates an x5chain against `anchors` and checks that any x5t matches its leaf certificate
+fn validate_x5(
+    anchors: &TrustAnchorStore,
+    time: u64,
+    chain: Option<Vec<Bytes<'_>>>,
+    x5t: Option<HashEntry<'_>>,
+) -> Result<ValidatedChain> {
+    let chain =
+        chain.ok_or_else(|| CoseError::InvalidHeader("no x5chain header present".to_string()))?;
+
+    let validated = anchors.validate_at(&chain, time)?;
+
+    if let Some(x5t) = x5t {
+        let expected = cose_hash(&x5t.hash_alg_id, chain[0].as_ref())?;
+        if expected.as_slice() != x5t.hash_value.as_ref() {
+            return Err(X509Error::ThumbprintMismatch.into());
+        }
+    }
+
+    Ok(validated)
+}
+
+fn unprotected_value<'a, 'b>(
+    unprotected: &'b UnprotectedCorimHeaderMap<'a>,
+    label: Integer,
+) -> Option<&'b ExtensionValue<'a>> {
+    unprotected.get(&Label::Int(label))
+}
+
+impl<'a> COSESign1Corim<'a> {
+    /// Returns the CBOR encoding of the protected header, as it was decoded if it has not been
+    /// modified since
+    pub fn protected_bytes(&self) -> Result<Vec<u8>> {
+        self.protected.to_bytes()
+    }
+
+    /// Returns the CBOR encoding of the payload, as it was decoded if it has not been modified
+    /// since
+    pub fn payload_bytes(&self) -> Result<Vec<u8>> {
+        self.payload.to_bytes()
+    }
+
+    /// Returns the COSE `Sig_structure` covered by the signature
+    pub fn to_be_signed(&self) -> Result<Vec<u8>> {
+        sig_structure1(&self.protected_bytes()?, &self.payload_bytes()?)
+    }
+
+    /// Signs the CoRIM with `key`. The `alg` field of the protected header is set to the
+    /// algorithm of the key before the signature is computed.
+    pub fn sign(&mut self, key: &CoseSigningKey) -> Result<()> {
+        self.protected.alg = i64::from(key.algorithm()).into();
+        let signature = key.sign(&self.to_be_signed()?)?;
+        self.signature = Bytes::from(signature).into();
+        Ok(())
+    }
+
+    /// Verifies the signature using `key`
+    pub fn verify(&self, key: &CoseVerifyingKey) -> Result<()> {
+        key.verify(
+            &self.algorithm()?,
+            &self.to_be_signed()?,
+            self.signature.as_ref(),
+        )
+    }
+
+    /// Validates the x5chain against `anchors` at the current system time, then verifies the
+    /// signature using the leaf certificate's public key.
+    pub fn verify_with_trust_anchors(&self, anchors: &TrustAnchorStore) -> Result<ValidatedChain> {
+        self.verify_with_trust_anchors_at(anchors, x509::unix_time_now())
+    }
+
+    /// Validates the x5chain against `anchors` at `time` (seconds since the Unix epoch), then
+    /// verifies the signature using the leaf certificate's public key. If an x5t header is
+    /// present it must match the leaf certificate.
+    pub fn verify_with_trust_anchors_at(
+        &self,
+        anchors: &TrustAnchorStore,
+        time: u64,
+    ) -> Result<ValidatedChain> {
+        let validated = validate_x5(anchors, time, self.x5chain()?, self.x5t()?)?;
+
+        self.verify(&validated.leaf_verifying_key()?)?;
+        Ok(validated)
+    }
+
+    /// Splits the CoRIM into a detached signature and the CBOR encoded payload it covers
+    pub fn detach(&self) -> Result<(DetachedCOSESign1Corim<'a>, Vec<u8>)> {
+        let detached = DetachedCOSESign1Corim {
+            protected: self.protected.clone(),
+            unprotected: self.unprotected.clone(),
+            signature: self.signature.clone(),
+        };
+        Ok((detached, self.payload_bytes()?))
+    }
+}
+
+impl<'a> DetachedCOSESign1Corim<'a> {
+    /// Returns the CBOR encoding of the protected header, as it was decoded if it has not been
+    /// modified since
+    pub fn protected_bytes(&self) -> Result<Vec<u8>> {
+        self.protected.to_bytes()
+    }
+
+    /// Returns the COSE `Sig_structure` covering the detached `payload`
+    pub fn to_be_signed(&self, payload: &[u8]) -> Result<Vec<u8>> {
+        sig_structure1(&self.protected_bytes()?, payload)
+    }
+
+    /// Signs the detached `payload` with `key`. The `alg` field of the protected header is set
+    /// to the algorithm of the key before the signature is computed.
+    pub fn sign(&mut self, payload: &[u8], key: &CoseSigningKey) -> Result<()> {
+        self.protected.alg = i64::from(key.algorithm()).into();
+        let signature = key.sign(&self.to_be_signed(payload)?)?;
+        self.signature = Bytes::from(signature).into();
+        Ok(())
+    }
+
+    /// Verifies the signature over the detached `payload` using `key`
+    pub fn verify(&self, payload: &[u8], key: &CoseVerifyingKey) -> Result<()> {
+        key.verify(
+            &self.algorithm()?,
+            &self.to_be_signed(payload)?,
+            self.signature.as_ref(),
+        )
+    }
+
+    /// Validates the x5chain against `anchors` at the current system time, then verifies the
+    /// signature over the detached `payload` using the leaf certificate's public key.
+    pub fn verify_with_trust_anchors(
+        &self,
+        payload: &[u8],
+        anchors: &TrustAnchorStore,
+    ) -> Result<ValidatedChain> {
+        self.verify_with_trust_anchors_at(payload, anchors, x509::unix_time_now())
+    }
+
+    /// Validates the x5chain against `anchors` at `time` (seconds since the Unix epoch), then
+    /// verifies the signature over the detached `payload` using the leaf certificate's public
+    /// key.
+    pub fn verify_with_trust_anchors_at(
+        &self,
+        payload: &[u8],
+        anchors: &TrustAnchorStore,
+        time: u64,
+    ) -> Result<ValidatedChain> {
+        let validated = validate_x5(anchors, time, self.x5chain()?, self.x5t()?)?;
+
+        self.verify(payload, &validated.leaf_verifying_key()?)?;
+        Ok(validated)
+    }
+
+    /// Decodes the detached `payload` and combines it with this signature. The payload is not
+    /// verified; call [`DetachedCOSESign1Corim::verify`] first. The result borrows from
+    /// `payload`.
+    pub fn attach<'p>(&self, payload: &'p [u8]) -> Result<COSESign1Corim<'p>>
+    where
+        'a: 'p,
+    {
+        let payload = Encoded::from_bytes(payload)
+            .map_err(|e| CoseError::Encoding(format!("invalid detached payload: {e}")))?;
+
+        Ok(COSESign1Corim {
+            protected: self.protected.clone(),
+            unprotected: self.unprotected.clone(),
+            payload,
+            signature: self.signature.clone(),
+        })
+    }
+}
+
+/// Determines how many signers of a [`COSESignCorim`] must verify
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SignerPolicy {
+    /// At least one signer must verify
+    #[default]
+    AnyOf,
+    /// Every signer must verify
+    AllOf,
+}
+
+/// Implements the algorithm, x5chain and x5t accessors for COSE structures with `protected`
+/// and `unprotected` header fields
+macro_rules! impl_cose_headers {
+    ($($cose:ident),* $(,)?) => {
+        $(
+            impl<'a> $cose<'a> {
+                /// Returns the signature algorithm from the protected header
+                pub fn algorithm(&self) -> Result<CoseAlgorithm> {
+                    let alg = i64::try_from(self.protected.alg.0).map_err(|_| {
+                        CoseError::InvalidHeader(format!(
+                            "invalid algorithm {}",
+                            self.protected.alg
+                        ))
+                    })?;
+                    Ok(CoseAlgorithm::try_from(alg)?)
+                }
+
+                /// Returns the x5chain from the protected header, falling back to the
+                /// unprotected header
+                pub fn x5chain(&self) -> Result<Option<Vec<Bytes<'a>>>> {
+                    match self.protected.x5chain()? {
+                        Some(chain) => Ok(Some(chain)),
+                        None => unprotected_value(&self.unprotected, COSE_HEADER_X5CHAIN)
+                            .map(x5chain_from_value)
+                            .transpose(),
+                    }
+                }
+
+                /// Returns the x5t from the protected header, falling back to the unprotected
+                /// header
+                pub fn x5t(&self) -> Result<Option<HashEntry<'a>>> {
+                    match self.protected.x5t()? {
+                        Some(x5t) => Ok(Some(x5t)),
+                        None => unprotected_value(&self.unprotected, COSE_HEADER_X5T)
+                            .map(x5t_from_value)
+                            .transpose(),
+                    }
+                }
+
+                /// Sets the x5chain in the unprotected header
+                pub fn set_unprotected_x5chain<I>(&mut self, chain: I) -> Result<()>
+                where
+                    I: IntoIterator,
+                    I::Item: Into<Bytes<'a>>,
+                {
+                    let value = x5chain_to_value(chain)?;
+                    self.unprotected
+                        .insert(Label::Int(COSE_HEADER_X5CHAIN), value);
+                    Ok(())
+                }
+
+                /// Sets the x5t in the unprotected header
+                pub fn set_unprotected_x5t(
+                    &mut self,
+                    alg: &CoseAlgorithm,
+                    cert: &[u8],
+                ) -> Result<()> {
+                    let value = x5t_to_value(alg, cert)?;
+                    self.unprotected.insert(Label::Int(COSE_HEADER_X5T), value);
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_cose_headers!(COSESign1Corim, DetachedCOSESign1Corim, COSESignatureCorim);
+
+impl<'a> COSESignCorim<'a> {
+    /// Returns the CBOR encoding of the body protected header, as it was decoded if it has not
+    /// been modified since
+    pub fn protected_bytes(&self) -> Result<Vec<u8>> {
+        self.protected.to_bytes()
+    }
+
+    /// Returns the CBOR encoding of the payload, as it was decoded if it has not been modified
+    /// since
+    pub fn payload_bytes(&self) -> Result<Vec<u8>> {
+        self.payload.to_bytes()
+    }
+
+    fn signature_at(&self, index: usize) -> Result<&COSESignatureCorim<'a>> {
+        Ok(self
+            .signatures
+            .get(index)
+            .ok_or(CoseError::SignatureNotFound(index))?)
+    }
+
+    fn sign_protected_to_be_signed(
+        &self,
+        protected: &Encoded<ProtectedCorimSignerHeaderMap<'a>>,
+    ) -> Result<Vec<u8>> {
+        sig_structure(
+            &self.protected_bytes()?,
+            &protected.to_bytes()?,
+            &self.payload_bytes()?,
+        )
+    }
+
+    /// Returns the COSE `Sig_structure` covered by the signature at `index`
+    pub fn to_be_signed(&self, index: usize) -> Result<Vec<u8>> {
+        self.sign_protected_to_be_signed(&self.signature_at(index)?.protected)
+    }
+
+    /// Adds a signature from `key`, described by `protected`. The `alg` field of the protected
+    /// header is set to the algorithm of the key before the signature is computed.
+    pub fn add_signature(
+        &mut self,
+        mut protected: ProtectedCorimSignerHeaderMap<'a>,
+        key: &CoseSigningKey,
+    ) -> Result<()> {
+        protected.alg = i64::from(key.algorithm()).into();
+        let protected = Encoded::new(protected);
+        let signature = key.sign(&self.sign_protected_to_be_signed(&protected)?)?;
+
+        self.signatures.push(COSESignatureCorim {
+            protected,
+            unprotected: UnprotectedCorimHeaderMap::default(),
+            signature: signature.into(),
+        });
+        Ok(())
+    }
+
+    /// Verifies the signature at `index` using `key`
+    pub fn verify_signature(&self, index: usize, key: &CoseVerifyingKey) -> Result<()> {
+        let signature = self.signature_at(index)?;
+        key.verify(
+            &signature.algorithm()?,
+            &self.to_be_signed(index)?,
+            signature.signature.as_ref(),
+        )
+    }
+
+    /// Verifies the signatures against `keys`.
+    ///
+    /// With [`SignerPolicy::AnyOf`] at least one signature must verify under one of the keys,
+    /// with [`SignerPolicy::AllOf`] every signature must. Returns the indices of the signatures
+    /// that verified.
+    pub fn verify(&self, keys: &[CoseVerifyingKey], policy: SignerPolicy) -> Result<Vec<usize>> {
+        if self.signatures.is_empty() {
+            return Err(CoseError::PolicyNotSatisfied(
+                "the CoRIM carries no signatures".to_string(),
+            ))?;
+        }
+
+        let verified = (0..self.signatures.len())
+            .filter(|index| {
+                keys.iter()
+                    .any(|key| self.verify_signature(*index, key).is_ok())
+            })
+            .collect::<Vec<_>>();
+
+        match policy {
+            SignerPolicy::AnyOf if verified.is_empty() => Err(CoseError::PolicyNotSatisfied(
+                "no key verified any signature".to_string(),
+            ))?,
+            SignerPolicy::AllOf if verified.len() < self.signatures.len() => {
+                Err(CoseError::PolicyNotSatisfied(format!(
+                    "{} of {} signatures verified",
+                    verified.len(),
+                    self.signatures.len()
+                )))?
+            }
+            _ => Ok(verified),
+        }
+    }
+
+    /// Validates each signer's x5chain against `anchors` at the current system time and verifies
+    /// its signature with the leaf certificate's public key. See
+    /// [`COSESignCorim::verify_with_trust_anchors_at`].
+    pub fn verify_with_trust_anchors(
+        &self,
+        anchors: &TrustAnchorStore,
+        policy: SignerPolicy,
+    ) -> Result<Vec<(usize, ValidatedChain)>> {
+        self.verify_with_trust_anchors_at(anchors, x509::unix_time_now(), policy)
+    }
+
+    /// Validates each signer's x5chain against `anchors` at `time` (seconds since the Unix
+    /// epoch) and verifies its signature with the leaf certificate's public key.
+    ///
+    /// With [`SignerPolicy::AnyOf`] at least one signer must verify, with
+    /// [`SignerPolicy::AllOf`] every signer must verify and the first failure is returned.
+    /// Returns the index and validated chain of each signer that verified.
+    pub fn verify_with_trust_anchors_at(
+        &self,
+        anchors: &TrustAnchorStore,
+        time: u64,
+        policy: SignerPolicy,
+    ) -> Result<Vec<(usize, ValidatedChain)>> {
+        let mut verified = vec![];
+        let mut last_error = None;
+
+        for (index, signature) in self.signatures.iter().enumerate() {
+            let result = validate_x5(anchors, time, signature.x5chain()?, signature.x5t()?)
+                .and_then(|validated| {
+                    self.verify_signature(index, &validated.leaf_verifying_key()?)?;
+                    Ok(validated)
+                });
+
+            match (result, policy) {
+                (Ok(validated), _) => verified.push((index, validated)),
+                (Err(e), SignerPolicy::AllOf) => return Err(e),
+                (Err(e), SignerPolicy::AnyOf) => last_error = Some(e),
+            }
+        }
+
+        if verified.is_empty() {
+            return Err(last_error.unwrap_or_else(|| {
+                CoseError::PolicyNotSatisfied("the CoRIM carries no signatures".to_string()).into()
+            }));
+        }
+
+        Ok(verified)
+    }
+}
+
+/// What the signatures of a CoRIM are verified against by [`ConciseRimTypeChoice::verify`]
+#[derive(Debug)]
+pub enum Verifier {
+    /// Public keys supplied directly
+    Keys(Vec<CoseVerifyingKey>),
+    /// Trust anchors for the x5chain carried by each signer
+    TrustAnchors(TrustAnchorStore),
+}
+
+/// A signer whose signature verified
+#[derive(Debug)]
+pub struct VerifiedSigner<'a> {
+    /// Index of the signer, always 0 for single-signer CoRIMs
+    pub index: usize,
+    /// Key identifier from the signer's protected header
+    pub kid: &'a Bytes<'a>,
+    /// Signer metadata from the signer's protected header
+    pub meta: &'a CorimMetaMap<'a>,
+}
+
+impl<'a> VerifiedSigner<'a> {
+    fn new(index: usize, protected: &'a ProtectedCorimHeaderMap<'a>) -> Self {
+        Self {
+            index,
+            kid: &protected.kid,
+            meta: &protected.corim_meta,
+        }
+    }
+}
+
+impl<'a> ConciseRimTypeChoice<'a> {
+    /// Verifies the signatures of a signed CoRIM at `time` (seconds since the Unix epoch), then
+    /// checks that the signature validity period of each verified signer contains `time`.
+    ///
+    /// With [`Verifier::Keys`] a single-signer CoRIM verifies if any key verifies it. `policy`
+    /// decides whether any or every signer of a multi-signer CoRIM must verify, under one of the
+    /// keys or against the trust anchors. `payload` is the payload of a detached signed CoRIM.
+    /// Returns the signers that verified.
+    pub fn verify(
+        &'a self,
+        verifier: &Verifier,
+        policy: SignerPolicy,
+        time: u64,
+        payload: Option<&[u8]>,
+    ) -> Result<Vec<VerifiedSigner<'a>>> {
+        let signers = match (self, verifier) {
+            (Self::TaggedUnsignedCorimMap(_), _) => Err(CoseError::NotSigned)?,
+            (Self::SignedCorim(signed), Verifier::Keys(keys)) => {
+                verify_any(keys, |key| signed.verify(key))?;
+                vec![VerifiedSigner::new(0, &signed.protected)]
+            }
+            (Self::SignedCorim(signed), Verifier::TrustAnchors(anchors)) => {
+                signed.verify_with_trust_anchors_at(anchors, time)?;
+                vec![VerifiedSigner::new(0, &signed.protected)]
+            }
+            (Self::DetachedSignedCorim(signed), verifier) => {
+                let payload = payload.ok_or(CoseError::MissingPayload)?;
+                match verifier {
+                    Verifier::Keys(keys) => verify_any(keys, |key| signed.verify(payload, key))?,
+                    Verifier::TrustAnchors(anchors) => {
+                        signed.verify_with_trust_anchors_at(payload, anchors, time)?;
+                    }
+                }
+                vec![VerifiedSigner::new(0, &signed.protected)]
+            }
+            (Self::MultiSignedCorim(signed), verifier) => {
+                let indices = match verifier {
+                    Verifier::Keys(keys) => signed.verify(keys, policy)?,
+                    Verifier::TrustAnchors(anchors) => signed
+                        .verify_with_trust_anchors_at(anchors, time, policy)?
+                        .into_iter()
+                        .map(|(index, _)| index)
+                        .collect(),
+                };
+                indices
+                    .into_iter()
+                    .map(|index| {
+                        let signature = &signed.signatures[index];
+                        VerifiedSigner {
+                            index,
+                            kid: &signature.protected.kid,
+                            meta: &signature.protected.corim_meta,
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        let (valid, invalid): (Vec<_>, Vec<_>) = signers.into_iter().partition(|signer| {
+            signer
+                .meta
+                .signature_validity
+                .as_ref()
+                .is_none_or(|validity| validity.contains(time))
+        });
+
+        match invalid.first() {
+            Some(signer) if valid.is_empty() || policy == SignerPolicy::AllOf => {
+                Err(CoseError::SignatureNotValidAt {
+                    index: signer.index,
+                    time,
+                    validity: signer.meta.signature_validity.clone(),
+                })?
+            }
+            _ => Ok(valid),
+        }
+    }
+}
+
+/// Succeeds if any of `keys` verifies, otherwise returns the error from the last key
+fn verify_any(
+    keys: &[CoseVerifyingKey],
+    verify: impl Fn(&CoseVerifyingKey) -> Result<()>,
+) -> Result<()> {
+    let mut result = Err(CoseError::InvalidKey("no verification keys given".to_string()).into());
+
+    for key in keys {
+        result = verify(key);
+        if result.is_ok() {
+            break;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+#[rustfmt::skip::macros(vec)]
+mod tests {
+    use super::*;
+    use crate::corim::{
+        Corim, CorimMap, CorimMetaMap, CorimSignerMap, DetachedSignedCorim, MultiSignedCorim,
+        ProtectedCorimBodyHeaderMap, SignedCorim, TaggedUnsignedCorimMap,
+    };
+    use crate::coswid::{ConciseSwidTag, EntityEntry};
+    use crate::owned::IntoOwned;
+    use crate::Text;
+
+    const ROOT: &str = include_str!("../testdata/x509/root.pem");
+    const INTERMEDIATE: &str = include_str!("../testdata/x509/intermediate.pem");
+    const LEAF: &str = include_str!("../testdata/x509/leaf.pem");
+    const LEAF_KEY: &str = include_str!("../testdata/x509/leaf.key");
+
+    // 2025-01-01T00:00:00Z
+    const NOW: u64 = 1_735_689_600;
+
+    fn pem_to_der(pem: &str) -> Vec<u8> {
+        der::pem::decode_vec(pem.as_bytes()).unwrap().1
+    }
+
+    fn unsigned_corim() -> COSESign1Corim<'static> {
+        COSESign1Corim {
+            protected: ProtectedCorimHeaderMap {
+                alg: 0.into(),
+                content_type: "application/rim+cbor".into(),
+                kid: Bytes::from(b"key-001".as_slice()),
+                corim_meta: CorimMetaMap {
+                    signer: CorimSignerMap {
+                        signer_name: "Test CoRIM Signer".into(),
+                        signer_uri: None,
+                        extension: None,
+                    },
+                    signature_validity: None,
+                },
+                cose_map: None,
+            }
+            .into(),
+            unprotected: UnprotectedCorimHeaderMap::default(),
+            payload: TaggedUnsignedCorimMap::new(CorimMap {
+                id: "corim-001".into(),
+                tags: vec![ConciseSwidTag {
+                    tag_id: "swid-123".into(),
+                    tag_version: Integer(0),
+                    software_name: "Example Software".into(),
+                    entity: EntityEntry {
+                        entity_name: "Example Entity".into(),
+                        reg_id: None,
+                        role: 1.into(),
+                        thumbprint: None,
+                        extensions: None,
+                        global_attributes: None,
+                    }
+                    .into(),
+                    corpus: None,
+                    patch: None,
+                    supplemental: None,
+                    software_version: None,
+                    version_scheme: None,
+                    media: None,
+                    software_meta: None,
+                    link: None,
+                    payload_or_evidence: None,
+                    extensions: None,
+                    global_attributes: None,
+                }
+                .into()],
+                dependent_rims: None,
+                profile: None,
+                rim_validity: None,
+                entities: None,
+                extension: None,
+            })
+            .into(),
+            signature: Bytes::from(vec![]).into(),
+        }
+    }
+
+    fn anchors() -> TrustAnchorStore {
+        let mut anchors = TrustAnchorStore::new();
+        anchors.add_pem(ROOT).unwrap();
+        anchors
+    }
+
+    fn roundtrip(corim: &COSESign1Corim) -> COSESign1Corim<'static> {
+        let mut bytes = vec![];
+        ciborium::into_writer(corim, &mut bytes).unwrap();
+        crate::cbor::from_reader(bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_sign_verify_roundtrip() {
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let mut corim = unsigned_corim();
+
+        corim.sign(&key).unwrap();
+
+        assert_eq!(corim.algorithm().unwrap(), CoseAlgorithm::ES256);
+        assert_eq!(corim.signature.len(), 64);
+
+        let decoded = roundtrip(&corim);
+        decoded.verify(&key.verifying_key()).unwrap();
+
+        let mut tampered = decoded.clone();
+        tampered.payload.id = "corim-002".into();
+        assert!(matches!(
+            tampered.verify(&key.verifying_key()),
+            Err(crate::Error::Cose(CoseError::InvalidSignature))
+        ));
+    }
+
+    /// Re-encodes the unsigned test CoRIM with definite lengths, as other CBOR encoders do
+    fn external_payload() -> Vec<u8> {
+        let value: ciborium::Value =
+            crate::cbor::from_slice(include_bytes!("../testdata/corim/unsigned.cbor").as_slice())
+                .unwrap();
+        let mut payload = vec![];
+        ciborium::into_writer(&value, &mut payload).unwrap();
+        payload
+    }
+
+    /// The protected header of a CoRIM signed by another COSE implementation, with integer
+    /// labels and definite lengths
+    fn external_header() -> coset::Header {
+        let meta = ciborium::Value::Map(vec![(
+            ciborium::Value::Text("0".to_string()),
+            ciborium::Value::Map(vec![(
+                ciborium::Value::Text("0".to_string()),
+                ciborium::Value::Text("External Signer".to_string()),
+            )]),
+        )]);
+
+        coset::HeaderBuilder::new()
+            .algorithm(coset::iana::Algorithm::ES256)
+            .content_type("application/rim+cbor".to_string())
+            .key_id(b"external-key".to_vec())
+            .value(8, meta)
+            .build()
+    }
+
+    #[test]
+    fn test_verify_external_sign1() {
+        use coset::TaggedCborSerializable;
+
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let payload = external_payload();
+        let bytes = coset::CoseSign1Builder::new()
+            .protected(external_header())
+            .payload(payload.clone())
+            .create_signature(b"", |tbs| key.sign(tbs).unwrap())
+            .build()
+            .to_tagged_vec()
+            .unwrap();
+
+        let corim: Corim = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        let signed = corim.as_signed_corim().unwrap();
+        assert_eq!(signed.algorithm().unwrap(), CoseAlgorithm::ES256);
+        assert_eq!(
+            signed.protected.corim_meta.signer.signer_name,
+            Text::from("External Signer")
+        );
+        assert_eq!(signed.payload_bytes().unwrap(), payload);
+        signed.verify(&key.verifying_key()).unwrap();
+
+        // The signed bytes are written back unchanged, so the re-encoded CoRIM still verifies
+        let mut encoded = vec![];
+        ciborium::into_writer(&corim, &mut encoded).unwrap();
+        let decoded: Corim = crate::cbor::from_slice(encoded.as_slice()).unwrap();
+        let resigned = decoded.as_signed_corim().unwrap();
+        let external = coset::CoseSign1::from_tagged_slice(&bytes).unwrap();
+        assert_eq!(
+            resigned.protected_bytes().unwrap(),
+            external.protected.original_data.unwrap()
+        );
+        assert_eq!(resigned.payload_bytes().unwrap(), payload);
+        resigned.verify(&key.verifying_key()).unwrap();
+
+        // Once the payload is changed it is encoded by this crate, and no longer verifies
+        let mut tampered = signed.clone();
+        tampered.payload.id = "corim-002".into();
+        assert!(matches!(
+            tampered.verify(&key.verifying_key()),
+            Err(crate::Error::Cose(CoseError::InvalidSignature))
+        ));
+    }
+
+    #[test]
+    fn test_verify_external_sign() {
+        use coset::TaggedCborSerializable;
+
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let mut signer = external_header();
+        signer.content_type = None;
+        let signature = coset::CoseSignatureBuilder::new().protected(signer).build();
+        let bytes = coset::CoseSignBuilder::new()
+            .protected(
+                coset::HeaderBuilder::new()
+                    .content_type("application/rim+cbor".to_string())
+                    .build(),
+            )
+            .payload(external_payload())
+            .add_created_signature(signature, b"", |tbs| key.sign(tbs).unwrap())
+            .build()
+            .to_tagged_vec()
+            .unwrap();
+
+        let corim: Corim = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        let signed = corim.as_multi_signed_corim().unwrap();
+        assert_eq!(
+            signed
+                .verify(&[key.verifying_key()], SignerPolicy::AllOf)
+                .unwrap(),
+            vec![0]
+        );
+
+        let mut encoded = vec![];
+        ciborium::into_writer(&corim, &mut encoded).unwrap();
+        let decoded: Corim = crate::cbor::from_slice(encoded.as_slice()).unwrap();
+        let resigned = decoded.as_multi_signed_corim().unwrap();
+        resigned.verify_signature(0, &key.verifying_key()).unwrap();
+
+        // Modifying the signer's header re-encodes it, and the signature no longer verifies
+        let mut tampered = signed.clone();
+        tampered.signatures[0].protected.kid = b"other-key".to_vec().into();
+        assert!(matches!(
+            tampered.verify_signature(0, &key.verifying_key()),
+            Err(crate::Error::Cose(CoseError::InvalidSignature))
+        ));
+    }
+
+    #[test]
+    fn test_ed25519_sign_verify() {
+        let key = CoseSigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+        let mut corim = unsigned_corim();
+
+        corim.sign(&key).unwrap();
+
+        assert_eq!(corim.algorithm().unwrap(), CoseAlgorithm::EdDSA);
+        roundtrip(&corim).verify(&key.verifying_key()).unwrap();
+    }
+
+    fn cose_key(
+        kty: CoseKty,
+        crv: CoseEllipticCurve,
+        x: &[u8],
+        y: Option<&[u8]>,
+    ) -> CoseKey<'static> {
+        CoseKey {
+            kty,
+            kid: None,
+            alg: None,
+            key_ops: None,
+            base_iv: None,
+            crv: Some(crv),
+            x: Some(x.to_vec().into()),
+            y: y.map(|y| y.to_vec().into()),
+            d: None,
+            k: None,
+        }
+    }
+
+    #[test]
+    fn test_cose_key_sign_verify() {
+        let CoseSigningKey::P256(pkcs8) = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap() else {
+            panic!("expected a P-256 key");
+        };
+        let point = pkcs8.verifying_key().to_encoded_point(false);
+
+        let mut key = cose_key(
+            CoseKty::Ec2,
+            CoseEllipticCurve::P256,
+            point.x().unwrap(),
+            Some(point.y().unwrap()),
+        );
+        let verifying_key = CoseVerifyingKey::from_cose_key(&key).unwrap();
+        assert_eq!(
+            verifying_key,
+            CoseVerifyingKey::P256(*pkcs8.verifying_key())
+        );
+
+        key.d = Some(pkcs8.to_bytes().to_vec().into());
+        let signing_key = CoseSigningKey::from_cose_key(&key).unwrap();
+
+        let mut corim = unsigned_corim();
+        corim.sign(&signing_key).unwrap();
+        corim.verify(&verifying_key).unwrap();
+
+        let ed25519 = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let mut key = cose_key(
+            CoseKty::Okp,
+            CoseEllipticCurve::Ed25519,
+            ed25519.verifying_key().as_bytes(),
+            None,
+        );
+        key.d = Some(ed25519.to_bytes().to_vec().into());
+        assert_eq!(
+            CoseSigningKey::from_cose_key(&key).unwrap().algorithm(),
+            CoseAlgorithm::EdDSA
+        );
+
+        key.x = Some(vec![1; 32].into());
+        assert!(matches!(
+            CoseSigningKey::from_cose_key(&key),
+            Err(crate::Error::Cose(CoseError::InvalidKey(_)))
+        ));
+
+        key.crv = Some(CoseEllipticCurve::X25519);
+        assert!(CoseSigningKey::from_cose_key(&key).is_err());
+        assert!(CoseVerifyingKey::from_cose_key(&key).is_err());
+    }
+
+    #[test]
+    fn test_verifying_key_from_certificate() {
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        assert_eq!(
+            CoseVerifyingKey::from_certificate_der(&pem_to_der(LEAF)).unwrap(),
+            key.verifying_key()
+        );
+
+        assert!(matches!(
+            CoseVerifyingKey::from_certificate_der(b"not a certificate"),
+            Err(crate::Error::X509(X509Error::InvalidCertificate(_)))
+        ));
+    }
+
+    #[test]
+    fn test_protected_x5chain_roundtrip() {
+        let chain = vec![pem_to_der(LEAF), pem_to_der(INTERMEDIATE)];
+        let mut corim = unsigned_corim();
+
+        corim.protected.set_x5chain(chain.clone()).unwrap();
+        corim
+            .protected
+            .set_x5t(&CoseAlgorithm::Sha256, &chain[0])
+            .unwrap();
+
+        let decoded = roundtrip(&corim);
+        assert_eq!(decoded, corim);
+
+        let x5chain = decoded.x5chain().unwrap().unwrap();
+        assert_eq!(x5chain.len(), 2);
+        assert_eq!(x5chain[0].as_ref(), chain[0].as_slice());
+        assert_eq!(x5chain[1].as_ref(), chain[1].as_slice());
+
+        let x5t = decoded.x5t().unwrap().unwrap();
+        assert_eq!(x5t.hash_alg_id, CoseAlgorithm::Sha256);
+        assert_eq!(x5t.hash_value.as_ref(), &Sha256::digest(&chain[0])[..]);
+    }
+
+    #[test]
+    fn test_unprotected_x5chain_single_certificate() {
+        let leaf = pem_to_der(LEAF);
+        let mut corim = unsigned_corim();
+
+        corim.set_unprotected_x5chain([leaf.clone()]).unwrap();
+
+        let decoded = roundtrip(&corim);
+        assert_eq!(decoded.protected.x5chain().unwrap(), None);
+        assert_eq!(
+            decoded.unprotected.get(&Label::Int(COSE_HEADER_X5CHAIN)),
+            Some(&ExtensionValue::Bytes(leaf.clone().into()))
+        );
+        assert_eq!(decoded.x5chain().unwrap(), Some(vec![leaf.into()]));
+    }
+
+    #[test]
+    fn test_verify_with_trust_anchors() {
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let chain = vec![pem_to_der(LEAF), pem_to_der(INTERMEDIATE)];
+        let mut corim = unsigned_corim();
+
+        corim.protected.set_x5chain(chain.clone()).unwrap();
+        corim
+            .protected
+            .set_x5t(&CoseAlgorithm::Sha256_64, &chain[0])
+            .unwrap();
+        corim.sign(&key).unwrap();
+
+        let decoded = roundtrip(&corim);
+        let validated = decoded
+            .verify_with_trust_anchors_at(&anchors(), NOW)
+            .unwrap();
+        assert_eq!(
+            validated.leaf().tbs_certificate.subject.to_string(),
+            "CN=Test CoRIM Signer,O=corim-rs test"
+        );
+
+        // A chain carried in the unprotected header is also accepted
+        let mut unprotected = unsigned_corim();
+        unprotected.set_unprotected_x5chain(chain).unwrap();
+        unprotected.sign(&key).unwrap();
+        roundtrip(&unprotected)
+            .verify_with_trust_anchors_at(&anchors(), NOW)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_verify_with_trust_anchors_failures() {
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let leaf = pem_to_der(LEAF);
+        let intermediate = pem_to_der(INTERMEDIATE);
+
+        // Missing x5chain
+        let mut corim = unsigned_corim();
+        corim.sign(&key).unwrap();
+        assert!(matches!(
+            corim.verify_with_trust_anchors_at(&anchors(), NOW),
+            Err(crate::Error::Cose(CoseError::InvalidHeader(_)))
+        ));
+
+        // x5t for a different certificate
+        let mut corim = unsigned_corim();
+        corim
+            .protected
+            .set_x5chain([leaf.clone(), intermediate.clone()])
+            .unwrap();
+        corim
+            .protected
+            .set_x5t(&CoseAlgorithm::Sha256, &intermediate)
+            .unwrap();
+        corim.sign(&key).unwrap();
+        assert!(matches!(
+            corim.verify_with_trust_anchors_at(&anchors(), NOW),
+            Err(crate::Error::X509(X509Error::ThumbprintMismatch))
+        ));
+
+        // Signed by a key other than the leaf
+        let other = CoseSigningKey::P256(p256::ecdsa::SigningKey::from_slice(&[1; 32]).unwrap());
+        let mut corim = unsigned_corim();
+        corim.protected.set_x5chain([leaf, intermediate]).unwrap();
+        corim.sign(&other).unwrap();
+        assert!(matches!(
+            corim.verify_with_trust_anchors_at(&anchors(), NOW),
+            Err(crate::Error::Cose(CoseError::InvalidSignature))
+        ));
+    }
+
+    fn signer_header(name: &'static str) -> ProtectedCorimSignerHeaderMap<'static> {
+        ProtectedCorimSignerHeaderMap {
+            alg: 0.into(),
+            kid: Bytes::from(name.as_bytes()),
+            corim_meta: CorimMetaMap {
+                signer: CorimSignerMap {
+                    signer_name: name.into(),
+                    signer_uri: None,
+                    extension: None,
+                },
+                signature_validity: None,
+            },
+            cose_map: None,
+        }
+    }
+
+    fn unsigned_multi_corim() -> COSESignCorim<'static> {
+        let single = unsigned_corim();
+        COSESignCorim {
+            protected: ProtectedCorimBodyHeaderMap {
+                content_type: "application/rim+cbor".into(),
+                cose_map: None,
+            }
+            .into(),
+            unprotected: UnprotectedCorimHeaderMap::default(),
+            payload: single.payload,
+            signatures: vec![],
+        }
+    }
+
+    #[test]
+    fn test_multi_signer_sign_verify() {
+        let vendor = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let oem = CoseSigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+        let other = CoseSigningKey::P256(p256::ecdsa::SigningKey::from_slice(&[1; 32]).unwrap());
+
+        let mut corim = unsigned_multi_corim();
+        corim
+            .add_signature(signer_header("Vendor"), &vendor)
+            .unwrap();
+        corim.add_signature(signer_header("OEM"), &oem).unwrap();
+
+        let rim = Corim::from(MultiSignedCorim::from(corim));
+        let mut bytes = vec![];
+        ciborium::into_writer(&rim, &mut bytes).unwrap();
+        assert_eq!(&bytes[..3], &[0xd8, 0x62, 0x84]);
+
+        let decoded: Corim = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        let decoded = decoded.as_multi_signed_corim().unwrap();
+        assert_eq!(decoded.signatures.len(), 2);
+        assert_eq!(
+            decoded.signatures[0].algorithm().unwrap(),
+            CoseAlgorithm::ES256
+        );
+        assert_eq!(
+            decoded.signatures[1].algorithm().unwrap(),
+            CoseAlgorithm::EdDSA
+        );
+        assert_eq!(
+            decoded.signatures[1]
+                .protected
+                .corim_meta
+                .signer
+                .signer_name,
+            Text::from("OEM")
+        );
+
+        let keys = [vendor.verifying_key(), oem.verifying_key()];
+        assert_eq!(
+            decoded.verify(&keys, SignerPolicy::AllOf).unwrap(),
+            vec![0, 1]
+        );
+        assert_eq!(
+            decoded.verify(&keys[1..], SignerPolicy::AnyOf).unwrap(),
+            vec![1]
+        );
+
+        let keys = [vendor.verifying_key(), other.verifying_key()];
+        assert_eq!(decoded.verify(&keys, SignerPolicy::AnyOf).unwrap(), vec![0]);
+        assert!(matches!(
+            decoded.verify(&keys, SignerPolicy::AllOf),
+            Err(crate::Error::Cose(CoseError::PolicyNotSatisfied(_)))
+        ));
+        assert!(matches!(
+            decoded.verify(&[other.verifying_key()], SignerPolicy::AnyOf),
+            Err(crate::Error::Cose(CoseError::PolicyNotSatisfied(_)))
+        ));
+
+        // Every signature must verify, not only one per supplied key
+        let mut extra = decoded.clone();
+        extra
+            .add_signature(signer_header("Unknown"), &other)
+            .unwrap();
+        let keys = [vendor.verifying_key(), oem.verifying_key()];
+        assert_eq!(
+            extra.verify(&keys, SignerPolicy::AnyOf).unwrap(),
+            vec![0, 1]
+        );
+        assert!(matches!(
+            extra.verify(&keys, SignerPolicy::AllOf),
+            Err(crate::Error::Cose(CoseError::PolicyNotSatisfied(_)))
+        ));
+        assert!(matches!(
+            Corim::from(MultiSignedCorim::from(extra)).verify(
+                &Verifier::Keys(keys.to_vec()),
+                SignerPolicy::AllOf,
+                NOW,
+                None
+            ),
+            Err(crate::Error::Cose(CoseError::PolicyNotSatisfied(_)))
+        ));
+
+        // A signature over one signer's header does not cover another signer's header
+        let mut tampered = decoded.clone();
+        tampered.signatures[0]
+            .protected
+            .corim_meta
+            .signer
+            .signer_name = "Mallory".into();
+        assert!(matches!(
+            tampered.verify_signature(0, &vendor.verifying_key()),
+            Err(crate::Error::Cose(CoseError::InvalidSignature))
+        ));
+        tampered.verify_signature(1, &oem.verifying_key()).unwrap();
+
+        assert!(matches!(
+            decoded.verify_signature(2, &oem.verifying_key()),
+            Err(crate::Error::Cose(CoseError::SignatureNotFound(2)))
+        ));
+    }
+
+    #[test]
+    fn test_multi_signer_verify_with_trust_anchors() {
+        let vendor = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let oem = CoseSigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+
+        let mut header = signer_header("Vendor");
+        header
+            .set_x5chain([pem_to_der(LEAF), pem_to_der(INTERMEDIATE)])
+            .unwrap();
+
+        let mut corim = unsigned_multi_corim();
+        corim.add_signature(header, &vendor).unwrap();
+        corim.add_signature(signer_header("OEM"), &oem).unwrap();
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&corim, &mut bytes).unwrap();
+        let decoded: COSESignCorim = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, corim);
+
+        let verified = decoded
+            .verify_with_trust_anchors_at(&anchors(), NOW, SignerPolicy::AnyOf)
+            .unwrap();
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].0, 0);
+
+        // The OEM signature carries no x5chain, so it cannot be anchored
+        assert!(matches!(
+            decoded.verify_with_trust_anchors_at(&anchors(), NOW, SignerPolicy::AllOf),
+            Err(crate::Error::Cose(CoseError::InvalidHeader(_)))
+        ));
+    }
+
+    #[test]
+    fn test_detached_payload() {
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let mut corim = unsigned_corim();
+        corim.sign(&key).unwrap();
+
+        let (detached, payload) = corim.detach().unwrap();
+
+        let mut bytes = vec![];
+        ciborium::into_writer(
+            &Corim::from(DetachedSignedCorim::from(detached.clone())),
+            &mut bytes,
+        )
+        .unwrap();
+
+        // tag(18), array(4), bstr(protected)..., map(0), nil, tag(560) signature
+        assert_eq!(&bytes[..2], &[0xd2, 0x84]);
+        let protected_len = detached.protected_bytes().unwrap().len();
+        assert_eq!(bytes[4 + protected_len..6 + protected_len], [0xa0, 0xf6]);
+
+        let decoded: Corim = crate::cbor::from_slice(bytes.as_slice()).unwrap();
+        assert!(decoded.as_signed_corim().is_none());
+        let decoded = decoded.as_detached_signed_corim().unwrap();
+        assert_eq!(decoded, detached);
+
+        decoded.verify(&payload, &key.verifying_key()).unwrap();
+        assert!(matches!(
+            decoded.verify(&payload[1..], &key.verifying_key()),
+            Err(crate::Error::Cose(CoseError::InvalidSignature))
+        ));
+
+        assert_eq!(decoded.attach(&payload).unwrap(), corim);
+
+        // An attached-payload structure cannot be decoded from a detached encoding and
+        // vice versa
+        let untagged = &bytes[1..];
+        assert!(crate::cbor::from_slice::<COSESign1Corim>(untagged).is_err());
+
+        let mut attached = vec![];
+        ciborium::into_writer(&corim, &mut attached).unwrap();
+        assert!(crate::cbor::from_slice::<DetachedCOSESign1Corim>(attached.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_detached_sign_verify_with_trust_anchors() {
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let payload = unsigned_corim().payload_bytes().unwrap();
+
+        let (mut detached, _) = unsigned_corim().detach().unwrap();
+        detached
+            .protected
+            .set_x5chain([pem_to_der(LEAF), pem_to_der(INTERMEDIATE)])
+            .unwrap();
+        detached.sign(&payload, &key).unwrap();
+
+        detached
+            .verify_with_trust_anchors_at(&payload, &anchors(), NOW)
+            .unwrap();
+        assert!(detached
+            .verify_with_trust_anchors_at(b"other payload", &anchors(), NOW)
+            .is_err());
+    }
+
+    fn json_roundtrip(corim: &Corim) -> Corim<'static> {
+        let json = serde_json::to_string(corim).unwrap();
+        let decoded: Corim = serde_json::from_str(&json).unwrap();
+
+        // The JSON form carries everything needed to reproduce the signed CBOR bytes
+        let mut expected = vec![];
+        ciborium::into_writer(corim, &mut expected).unwrap();
+        let mut actual = vec![];
+        ciborium::into_writer(&decoded, &mut actual).unwrap();
+        assert_eq!(actual, expected);
+
+        decoded.into_owned()
+    }
+
+    #[test]
+    fn test_signed_corim_json_roundtrip() {
+        let key = CoseSigningKey::from_pkcs8_pem(LEAF_KEY).unwrap();
+        let chain = vec![pem_to_der(LEAF), pem_to_der(INTERMEDIATE)];
+
+        let mut corim = unsigned_corim();
+        corim.protected.set_x5chain(chain.clone()).unwrap();
+        corim
+            .protected
+            .set_x5t(&CoseAlgorithm::Sha256, &chain[0])
+            .unwrap();
+        corim.set_unprotected_x5chain([chain[1].clone()]).unwrap();
+        corim.sign(&key).unwrap();
+
+        let json = serde_json::to_value(Corim::from(SignedCorim::from(corim.clone()))).unwrap();
+        assert_eq!(json["type"], "signed-corim");
+        assert_eq!(json["value"][2]["type"], "unsigned-corim");
+        assert_eq!(json["value"][3]["type"], "bytes");
+
+        let decoded = json_roundtrip(&Corim::from(SignedCorim::from(corim.clone())));
+        let decoded = decoded.as_signed_corim().unwrap();
+        decoded.verify(&key.verifying_key()).unwrap();
+        decoded
+            .verify_with_trust_anchors_at(&anchors(), NOW)
+            .unwrap();
+
+        let (detached, payload) = corim.detach().unwrap();
+        let decoded = json_roundtrip(&Corim::from(DetachedSignedCorim::from(detached)));
+        decoded
+            .as_detached_signed_corim()
+            .unwrap()
+            .verify(&payload, &key.verifying_key())
+            .unwrap();
+
+        let oem = CoseSigningKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&[7; 32]));
+        let mut multi = unsigned_multi_corim();
+        multi.add_signature(signer_header("Vendor"), &key).unwrap();
+        multi.add_signature(signer_header("OEM"), &oem).unwrap();
+        let decoded = json_roundtrip(&Corim::from(MultiSignedCorim::from(multi)));
+        assert_eq!(
+            decoded
+                .as_multi_signed_corim()
+                .unwrap()
+                .verify(
+                    &[key.verifying_key(), oem.verifying_key()],
+                    SignerPolicy::AllOf
+                )
+                .unwrap(),
+            vec![0, 1]
+        );
+    }
+}
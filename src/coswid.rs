@@ -70,7 +70,10 @@
 //! This implementation adheres to RFC 9393 (CoSWID) and supports all mandatory
 //! and optional fields defined in the standard.
 
+use alloc::boxed::Box;
+
 use crate::{
+    core::{deserialize_option_text, deserialize_text},
     empty_map_as_none, generate_tagged, AnyUri, ExtensionMap, GlobalAttributes, HashEntry, Int,
     Integer, IntegerTime, Label, OneOrMore, Role, Text, TextOrBytes, TextOrBytesSized, Uint, Uri,
     VersionScheme,
@@ -88,7 +91,7 @@ generate_tagged!((505, TaggedConciseSwidTag, ConciseSwidTag<'a>, 'a, "coswid", "
 #[repr(C)]
 pub struct ConciseSwidTag<'a> {
     /// Unique identifier for the tag
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub tag_id: TextOrBytes<'a>,
     /// Version number for the tag
     #[serde(rename = "12")]
@@ -106,44 +109,50 @@ pub struct ConciseSwidTag<'a> {
     #[serde(rename = "11")]
     pub supplemental: Option<bool>,
     /// Name of the software product
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
+    #[serde(deserialize_with = "deserialize_text")]
     pub software_name: Text<'a>,
     /// Version of the software product
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "13")]
+    #[serde(borrow, rename = "13")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub software_version: Option<Text<'a>>,
     /// Scheme used for version numbering
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "14")]
+    #[serde(borrow, rename = "14")]
     pub version_scheme: Option<VersionScheme<'a>>,
     /// Media type or environment context
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "10")]
+    #[serde(borrow, rename = "10")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub media: Option<Text<'a>>,
     /// Additional metadata about the software
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "5")]
+    #[serde(borrow, rename = "5")]
     pub software_meta: Option<OneOrMore<SoftwareMetaEntry<'a>>>,
     /// List of entities associated with the software
-    #[serde(rename = "2")]
+    #[serde(borrow, rename = "2")]
     pub entity: OneOrMore<EntityEntry<'a>>,
     /// Optional links to related resources
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "4")]
+    #[serde(borrow, rename = "4")]
     pub link: Option<OneOrMore<LinkEntry<'a>>>,
     /// Optional payload or evidence data
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub payload_or_evidence: Option<PayloadOrEvidence<'a>>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extensions: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to the whole tag
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -157,27 +166,32 @@ pub struct ConciseSwidTag<'a> {
 pub struct SoftwareMetaEntry<'a> {
     /// Current activation status of the software (e.g., "trial", "full", "deleted")
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "43")]
+    #[serde(borrow, rename = "43")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub activation_status: Option<Text<'a>>,
 
     /// Distribution channel type (e.g., "retail", "enterprise", "beta")
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "44")]
+    #[serde(borrow, rename = "44")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub channel_type: Option<Text<'a>>,
 
     /// Informal or marketing version name
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "45")]
+    #[serde(borrow, rename = "45")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub coloquial_version: Option<Text<'a>>,
 
     /// Detailed description of the software
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "46")]
+    #[serde(borrow, rename = "46")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub description: Option<Text<'a>>,
 
     /// Edition or variation of the software
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "47")]
+    #[serde(borrow, rename = "47")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub edition: Option<Text<'a>>,
 
     /// Indicates if entitlement data is required to use the software
@@ -187,59 +201,69 @@ pub struct SoftwareMetaEntry<'a> {
 
     /// Key used for software entitlement
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "49")]
+    #[serde(borrow, rename = "49")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub entitlement_key: Option<Text<'a>>,
 
     /// Tool that generated this metadata (16 bytes max)
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "50")]
+    #[serde(borrow, rename = "50")]
     pub generator: Option<TextOrBytesSized<'a, 16>>,
 
     /// Persistent identifier for the software
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "51")]
+    #[serde(borrow, rename = "51")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub persistent_id: Option<Text<'a>>,
 
     /// Product name
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "52")]
+    #[serde(borrow, rename = "52")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub product: Option<Text<'a>>,
 
     /// Product family name
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "53")]
+    #[serde(borrow, rename = "53")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub product_family: Option<Text<'a>>,
 
     /// Revision identifier
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "54")]
+    #[serde(borrow, rename = "54")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub revision: Option<Text<'a>>,
 
     /// Brief description of the software
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "55")]
+    #[serde(borrow, rename = "55")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub summary: Option<Text<'a>>,
 
     /// UNSPSC classification code
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "56")]
+    #[serde(borrow, rename = "56")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub unspsc_code: Option<Text<'a>>,
 
     /// Version of UNSPSC codeset used
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "57")]
+    #[serde(borrow, rename = "57")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub unspsc_version: Option<Text<'a>>,
 
     /// Optional extensible attributes
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub extensions: Option<ExtensionMap<'a>>,
 
     /// Global attributes that apply to this metadata entry
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -250,11 +274,12 @@ pub struct SoftwareMetaEntry<'a> {
 )]
 pub struct EntityEntry<'a> {
     /// Name of the entity
-    #[serde(rename = "31")]
+    #[serde(borrow, rename = "31")]
+    #[serde(deserialize_with = "deserialize_text")]
     pub entity_name: Text<'a>,
     /// Optional registration identifier URI for the entity
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "32")]
+    #[serde(borrow, rename = "32")]
     pub reg_id: Option<Uri<'a>>,
     /// One or more roles this entity fulfills
     #[serde(rename = "33")]
@@ -262,16 +287,18 @@ pub struct EntityEntry<'a> {
     /// Optional cryptographic hash for entity verification
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "34")]
-    pub thumbprint: Option<HashEntry>,
+    pub thumbprint: Option<HashEntry<'a>>,
     /// Optional extensible attributes
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub extensions: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this entity
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -281,38 +308,43 @@ pub struct EntityEntry<'a> {
 pub struct LinkEntry<'a> {
     /// Optional identifier for the linked artifact
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "37")]
+    #[serde(borrow, rename = "37")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub artifact: Option<Text<'a>>,
     /// URI reference to the linked resource
-    #[serde(rename = "38")]
+    #[serde(borrow, rename = "38")]
     pub href: AnyUri<'a>,
     /// Optional media type or context
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "10")]
+    #[serde(borrow, rename = "10")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub media: Option<Text<'a>>,
     /// Optional ownership status of the linked resource
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "39")]
+    #[serde(borrow, rename = "39")]
     pub ownership: Option<Ownership<'a>>,
     /// Relationship type between this tag and the linked resource
-    #[serde(rename = "40")]
+    #[serde(borrow, rename = "40")]
     pub rel: Rel<'a>,
     /// Optional MIME type of the linked resource
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "41")]
+    #[serde(borrow, rename = "41")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub media_type: Option<Text<'a>>,
     /// Optional usage requirement level
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "42")]
+    #[serde(borrow, rename = "42")]
     pub r#use: Option<Use<'a>>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this link
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -328,6 +360,7 @@ pub enum Ownership<'a> {
     /// Resource is shared among multiple parties
     Shared = 3,
     /// Custom ownership type using integer or text label
+    #[serde(borrow)]
     IntOrText(Label<'a>),
 }
 
@@ -359,6 +392,7 @@ pub enum Rel<'a> {
     /// Additional content
     Supplemental = 11,
     /// Custom relationship type
+    #[serde(borrow)]
     IntOrText(Label<'a>),
 }
 
@@ -373,31 +407,33 @@ pub enum Rel<'a> {
 #[serde(untagged)]
 pub enum PayloadOrEvidence<'a> {
     /// Describes the intended state of the software
+    #[serde(borrow)]
     Payload(Payload<'a>),
     /// Describes the observed state of the software
+    #[serde(borrow)]
     Evidence(Evidence<'a>),
 }
 
 impl PayloadOrEvidence<'_> {
-    pub fn as_payload(&self) -> Option<Payload> {
+    pub fn as_payload(&self) -> Option<Payload<'_>> {
         match self {
             Self::Payload(payload) => Some(payload.clone()),
             _ => None,
         }
     }
-    pub fn as_ref_payload(&self) -> Option<&Payload> {
+    pub fn as_ref_payload(&self) -> Option<&Payload<'_>> {
         match self {
             Self::Payload(payload) => Some(payload),
             _ => None,
         }
     }
-    pub fn as_evidence(&self) -> Option<Evidence> {
+    pub fn as_evidence(&self) -> Option<Evidence<'_>> {
         match self {
             Self::Evidence(evidence) => Some(evidence.clone()),
             _ => None,
         }
     }
-    pub fn as_ref_evidence(&self) -> Option<&Evidence> {
+    pub fn as_ref_evidence(&self) -> Option<&Evidence<'_>> {
         match self {
             Self::Evidence(evidence) => Some(evidence),
             _ => None,
@@ -411,9 +447,9 @@ impl PayloadOrEvidence<'_> {
 )]
 #[repr(C)]
 pub struct Payload<'a> {
-    #[serde(rename = "6")]
+    #[serde(borrow, rename = "6")]
     /// The payload entry containing resource information
-    payload: PayloadEntry<'a>,
+    pub payload: PayloadEntry<'a>,
 }
 
 /// Detailed payload information about software resources
@@ -425,15 +461,18 @@ pub struct PayloadEntry<'a> {
     /// Collection of resources in the software
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub resource_collection: Option<ResourceCollection<'a>>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this payload entry
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -450,19 +489,21 @@ pub struct ResourceCollection<'a> {
     /// Group of filesystem path elements
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub path_elements_group: Option<PathElementsGroup<'a>>,
     /// Optional list of processes
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "18")]
+    #[serde(borrow, rename = "18")]
     pub process: Option<OneOrMore<ProcessEntry<'a>>>,
     /// Optional list of resources
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "19")]
+    #[serde(borrow, rename = "19")]
     pub resource: Option<OneOrMore<ResourceEntry<'a>>>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub extensions: Option<ExtensionMap<'a>>,
 }
 
@@ -474,11 +515,11 @@ pub struct ResourceCollection<'a> {
 pub struct PathElementsGroup<'a> {
     /// Optional list of directory entries.
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "16")]
+    #[serde(borrow, rename = "16")]
     pub directory: Option<OneOrMore<DirectoryEntry<'a>>>,
     /// Optional list of file entries
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "17")]
+    #[serde(borrow, rename = "17")]
     pub file: Option<OneOrMore<FileEntry<'a>>>,
 }
 
@@ -491,18 +532,22 @@ pub struct DirectoryEntry<'a> {
     /// Basic filesystem item information
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub filesystem_item: Option<FileSystemItem<'a>>,
     /// Optional path elements group (boxed to cover possible infinite recursion).
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(borrow)]
     pub path_elements: Option<Box<PathElementsGroup<'a>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extensions: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this directory
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -518,14 +563,17 @@ pub struct FileSystemItem<'a> {
     pub key: Option<bool>,
     /// Optional location in the filesystem
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "23")]
+    #[serde(borrow, rename = "23")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub location: Option<Text<'a>>,
     /// Name of the filesystem item
-    #[serde(rename = "24")]
+    #[serde(borrow, rename = "24")]
+    #[serde(deserialize_with = "deserialize_text")]
     pub fs_name: Text<'a>,
     /// Optional root directory path
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "25")]
+    #[serde(borrow, rename = "25")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub root: Option<Text<'a>>,
 }
 
@@ -538,6 +586,7 @@ pub struct FileEntry<'a> {
     /// Basic filesystem item information
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub filesystem_item: Option<FileSystemItem<'a>>,
     /// Optional file size in bytes
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -545,21 +594,24 @@ pub struct FileEntry<'a> {
     pub size: Option<Uint>,
     /// Optional version identifier for the file
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "21")]
+    #[serde(borrow, rename = "21")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub file_version: Option<Text<'a>>,
     /// Optional cryptographic hash of file contents
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "7")]
-    pub hash: Option<HashEntry>,
+    pub hash: Option<HashEntry<'a>>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extensions: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this file
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -570,7 +622,8 @@ pub struct FileEntry<'a> {
 #[repr(C)]
 pub struct ProcessEntry<'a> {
     /// Name of the process
-    #[serde(rename = "27")]
+    #[serde(borrow, rename = "27")]
+    #[serde(deserialize_with = "deserialize_text")]
     pub process_name: Text<'a>,
     /// Optional process identifier
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -580,11 +633,13 @@ pub struct ProcessEntry<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this process
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -595,17 +650,20 @@ pub struct ProcessEntry<'a> {
 #[repr(C)]
 pub struct ResourceEntry<'a> {
     /// Type identifier for the resource
-    #[serde(rename = "29")]
+    #[serde(borrow, rename = "29")]
+    #[serde(deserialize_with = "deserialize_text")]
     pub r#type: Text<'a>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this resource
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -615,7 +673,7 @@ pub struct ResourceEntry<'a> {
 )]
 #[repr(C)]
 pub struct Evidence<'a> {
-    #[serde(rename = "3")]
+    #[serde(borrow, rename = "3")]
     /// The evidence entry containing observed resource information
     pub evidence: EvidenceEntry<'a>,
 }
@@ -629,6 +687,7 @@ pub struct EvidenceEntry<'a> {
     /// Collection of observed resources
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub resource_collection: Option<ResourceCollection<'a>>,
     /// Optional timestamp when evidence was collected
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -636,21 +695,25 @@ pub struct EvidenceEntry<'a> {
     pub date: Option<IntegerTime>,
     /// Optional identifier of the device where evidence was collected
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "36")]
+    #[serde(borrow, rename = "36")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub device_id: Option<Text<'a>>,
     /// Optional location where evidence was collected
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "23")]
+    #[serde(borrow, rename = "23")]
+    #[serde(default, deserialize_with = "deserialize_option_text")]
     pub location: Option<Text<'a>>,
     /// Optional extensible attributes
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
     #[serde(flatten)]
+    #[serde(borrow)]
     pub extension: Option<ExtensionMap<'a>>,
     /// Global attributes that apply to this evidence entry
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(deserialize_with = "empty_map_as_none")]
+    #[serde(borrow)]
     pub global_attributes: Option<GlobalAttributes<'a>>,
 }
 
@@ -666,6 +729,7 @@ pub enum Use<'a> {
     /// Resource is recommended
     Recommended = 3,
     /// Custom usage requirement
+    #[serde(borrow)]
     IntOrText(Label<'a>),
 }
 
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: MIT
 
+use alloc::vec::Vec;
+
 use derive_more::{Constructor, From};
 use serde::{ser::SerializeMap, Deserialize, Serialize};
 
@@ -23,11 +25,11 @@ They can be used to establish trust relationships and manage tag distribution."#
 #[repr(C)]
 pub struct ConciseTlTag<'a> {
     /// Identity information for this trust list tag
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub tag_identity: TagIdentityMap<'a>,
 
     /// List of trusted tags referenced by this trust list
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
     pub tags_list: Vec<TagIdentityMap<'a>>,
 
     /// Validity period for this trust list
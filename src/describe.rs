@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MIT
+
+//! Compact, single-line descriptions of CoRIM values
+//!
+//! Values are described from their JSON serialization: maps become `key=value` lists, type
+//! choices are written as their value followed by the type in parentheses, and long strings are
+//! shortened. The descriptions are meant for reports read by people, such as the outcome of an
+//! [`appraisal`](crate::appraisal), and are not parsed back.
+//!
+//! ```rust
+//! use corim_rs::{describe::describe_environment, triples::EnvironmentMap};
+//!
+//! let env: EnvironmentMap = serde_json::from_str(
+//!     r#"{"class": {"vendor": "ACME", "model": "RoadRunner"}}"#,
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     describe_environment(&env),
+//!     "class={model=RoadRunner, vendor=ACME}"
+//! );
+//! ```
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{comid::TagIdTypeChoice, triples::EnvironmentMap, MeasurementMap};
+
+/// Longest string described in full
+const MAX_VALUE_LEN: usize = 64;
+
+/// Describes a CoMID or CoTL tag identifier
+pub fn describe_tag_id(tag_id: &TagIdTypeChoice) -> String {
+    match tag_id {
+        TagIdTypeChoice::Tstr(id) => id.to_string(),
+        TagIdTypeChoice::Uuid(id) => id.to_string(),
+    }
+}
+
+/// Describes an environment, or returns `<empty environment>` if no field is set
+pub fn describe_environment(env: &EnvironmentMap) -> String {
+    let description = describe(env);
+    if description.is_empty() {
+        "<empty environment>".to_string()
+    } else {
+        description
+    }
+}
+
+/// Describes a measurement as its key, if there is one, followed by its values
+pub fn describe_measurement(measurement: &MeasurementMap) -> String {
+    let mval = describe(&measurement.mval);
+    match &measurement.mkey {
+        Some(mkey) => format!("{}: {mval}", describe(mkey)),
+        None => mval,
+    }
+}
+
+/// Produces a compact, single-line description of a value from its JSON serialization
+pub fn describe<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(value) => describe_value(&value),
+        Err(e) => format!("<{e}>"),
+    }
+}
+
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => {
+            // Multi-line values, such as PEM blocks, are collapsed onto a single line
+            let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
+            if s.chars().count() > MAX_VALUE_LEN {
+                format!("{}…", s.chars().take(MAX_VALUE_LEN).collect::<String>())
+            } else {
+                s
+            }
+        }
+        Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(describe_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        // Type choices are serialized to JSON as {"type": ..., "value": ...}
+        Value::Object(map) if map.len() == 2 && map.get("type").is_some_and(Value::is_string) => {
+            match (map.get("type"), map.get("value")) {
+                (Some(Value::String(kind)), Some(value)) => {
+                    format!("{} ({kind})", describe_value(value))
+                }
+                _ => describe_object(map),
+            }
+        }
+        Value::Object(map) => describe_object(map),
+    }
+}
+
+fn describe_object(map: &serde_json::Map<String, Value>) -> String {
+    map.iter()
+        .map(|(key, value)| match value {
+            Value::Object(inner) if !inner.contains_key("type") => {
+                format!("{key}={{{}}}", describe_value(value))
+            }
+            _ => format!("{key}={}", describe_value(value)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_value() {
+        let value = serde_json::json!({
+            "class": {"vendor": "ACME", "class-id": {"type": "oid", "value": "1.2.3"}},
+            "layers": [1, 2],
+            "pem": format!("-----BEGIN KEY-----\n{}\n-----END KEY-----", "A".repeat(64)),
+        });
+
+        assert_eq!(
+            describe_value(&value),
+            format!(
+                "class={{class-id=1.2.3 (oid), vendor=ACME}}, layers=[1, 2], \
+                 pem=-----BEGIN KEY----- {}…",
+                "A".repeat(44)
+            )
+        );
+        assert_eq!(
+            describe_environment(&EnvironmentMap::default()),
+            "<empty environment>"
+        );
+    }
+}
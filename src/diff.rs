@@ -0,0 +1,779 @@
+// SPDX-License-Identifier: MIT
+
+//! Structural comparison of CoRIMs and CoMIDs
+//!
+//! [`CorimMap::diff`] aligns the tags of two CoRIMs by their kind and tag identifier and
+//! reports the tags that were added, removed or modified. CoMIDs present in both are compared
+//! with [`ConciseMidTag::diff`], which aligns the measurements of reference and endorsed
+//! triples by environment and measured element (`mkey`), and the keys of identity and
+//! attest-key triples by environment, so that individual digests, SVNs and keys can be
+//! compared rather than whole tags.
+//!
+//! # Key Components
+//!
+//! * [`Change`] - A value that was added, removed or modified
+//! * [`CorimDiff`] - The differences between two CoRIM maps
+//! * [`ComidDiff`] - The differences between two CoMID tags
+//! * [`MeasurementDiff`] - A change to the measurement of one environment and `mkey`
+//! * [`KeyDiff`] - A key added to or removed from an environment
+//!
+//! # Example
+//!
+//! ```rust
+//! use corim_rs::{corim::CorimMap, diff::TagChange};
+//!
+//! fn changed_tags(old: &CorimMap, new: &CorimMap) -> usize {
+//!     old.diff(new)
+//!         .tags
+//!         .iter()
+//!         .filter(|tag| !matches!(tag.change, TagChange::Added { .. }))
+//!         .count()
+//! }
+//! ```
+
+use serde::Serialize;
+
+use crate::{
+    ConciseMidTag, ConciseTagTypeChoice, CorimEntityMap, CorimIdTypeChoice, CorimLocatorMap,
+    CorimMap, CorimMapExtension, CryptoKeyTypeChoice, Digest, EnvironmentMap,
+    MeasuredElementTypeChoice, MeasurementMap, MeasurementValuesMap, ProfileTypeChoice,
+    SvnTypeChoice, TagIdTypeChoice, TagVersionType, TextOrBytes, TriplesMap,
+    TriplesRecordCondition, ValidityMap,
+};
+
+/// A value present in only one of the compared structures, or in both with different contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "kebab-case")]
+pub enum Change<T> {
+    /// Present only in the new structure
+    Added { new: T },
+    /// Present only in the old structure
+    Removed { old: T },
+    /// Present in both structures with different values
+    Modified { old: T, new: T },
+}
+
+impl<T: PartialEq> Change<T> {
+    /// Compares two optional values, returning `None` if they are equal
+    pub fn between(old: Option<T>, new: Option<T>) -> Option<Self> {
+        match (old, new) {
+            (None, None) => None,
+            (None, Some(new)) => Some(Self::Added { new }),
+            (Some(old), None) => Some(Self::Removed { old }),
+            (Some(old), Some(new)) if old == new => None,
+            (Some(old), Some(new)) => Some(Self::Modified { old, new }),
+        }
+    }
+}
+
+/// The differences between two CoRIM maps
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CorimDiff<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Change<&'a CorimIdTypeChoice<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependent_rims: Option<Change<&'a Vec<CorimLocatorMap<'a>>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Change<&'a ProfileTypeChoice<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rim_validity: Option<Change<&'a ValidityMap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Change<&'a Vec<CorimEntityMap<'a>>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<Change<&'a CorimMapExtension<'a>>>,
+    /// Tags that were added, removed or modified, in the order of the old CoRIM followed by
+    /// the tags added by the new CoRIM
+    pub tags: Vec<TagDiff<'a>>,
+}
+
+impl CorimDiff<'_> {
+    /// Returns `true` if no differences were found
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none()
+            && self.dependent_rims.is_none()
+            && self.profile.is_none()
+            && self.rim_validity.is_none()
+            && self.entities.is_none()
+            && self.extension.is_none()
+            && self.tags.is_empty()
+    }
+}
+
+/// Identifies a tag across two CoRIMs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "tag-id", rename_all = "kebab-case")]
+pub enum TagKey<'a> {
+    Comid(&'a TagIdTypeChoice<'a>),
+    Coswid(&'a TextOrBytes<'a>),
+    Cotl(&'a TagIdTypeChoice<'a>),
+}
+
+impl<'a> TagKey<'a> {
+    /// Returns the key identifying `tag`
+    pub fn of(tag: &'a ConciseTagTypeChoice<'a>) -> Self {
+        match tag {
+            ConciseTagTypeChoice::Mid(comid) => Self::Comid(&comid.tag_identity.tag_id),
+            ConciseTagTypeChoice::Swid(coswid) => Self::Coswid(&coswid.tag_id),
+            ConciseTagTypeChoice::Tl(cotl) => Self::Cotl(&cotl.tag_identity.tag_id),
+        }
+    }
+}
+
+/// A tag that was added, removed or modified
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagDiff<'a> {
+    pub key: TagKey<'a>,
+    #[serde(flatten)]
+    pub change: TagChange<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "kebab-case")]
+pub enum TagChange<'a> {
+    /// A tag present only in the new CoRIM
+    Added { tag: &'a ConciseTagTypeChoice<'a> },
+    /// A tag present only in the old CoRIM
+    Removed { tag: &'a ConciseTagTypeChoice<'a> },
+    /// A CoMID present in both CoRIMs with different contents
+    ModifiedComid { diff: ComidDiff<'a> },
+    /// A CoSWID or CoTL present in both CoRIMs with different contents, compared as a whole
+    Modified {
+        old: &'a ConciseTagTypeChoice<'a>,
+        new: &'a ConciseTagTypeChoice<'a>,
+    },
+}
+
+/// The kind of triple a measurement or key was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TripleKind {
+    Reference,
+    Endorsed,
+    Identity,
+    AttestKey,
+}
+
+/// The differences between two CoMID tags
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ComidDiff<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_version: Option<Change<&'a TagVersionType>>,
+    /// Measurements of reference and endorsed triples that were added, removed or modified
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub measurements: Vec<MeasurementDiff<'a>>,
+    /// Keys of identity and attest-key triples that were added or removed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<KeyDiff<'a>>,
+    /// Whether any other part of the tag changed: its language, entities, linked tags,
+    /// extensions, the conditions of key triples or triples of any other kind
+    pub other_changes: bool,
+}
+
+impl ComidDiff<'_> {
+    /// Returns `true` if no differences were found
+    pub fn is_empty(&self) -> bool {
+        self.tag_version.is_none()
+            && self.measurements.is_empty()
+            && self.keys.is_empty()
+            && !self.other_changes
+    }
+}
+
+/// A measurement, identified by the triple kind, environment and `mkey`, that was added,
+/// removed or modified
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeasurementDiff<'a> {
+    pub triple: TripleKind,
+    pub environment: &'a EnvironmentMap<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mkey: Option<&'a MeasuredElementTypeChoice<'a>>,
+    #[serde(flatten)]
+    pub change: Change<&'a MeasurementValuesMap<'a>>,
+    /// For a modified measurement, the digests that changed, aligned by algorithm
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub digests: Vec<Change<&'a Digest<'a>>>,
+    /// For a modified measurement, the change to its SVN
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub svn: Option<Change<&'a SvnTypeChoice>>,
+    /// For a modified measurement, the names of the other values that changed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub other: Vec<&'static str>,
+}
+
+/// A key of an identity or attest-key triple that was added to or removed from an environment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyDiff<'a> {
+    pub triple: TripleKind,
+    pub environment: &'a EnvironmentMap<'a>,
+    #[serde(flatten)]
+    pub change: Change<&'a CryptoKeyTypeChoice<'a>>,
+}
+
+impl<'a> CorimMap<'a> {
+    /// Compares this CoRIM with a `new` version of it
+    pub fn diff<'b>(&'b self, new: &'b CorimMap<'a>) -> CorimDiff<'b> {
+        diff_corim(self, new)
+    }
+}
+
+impl<'a> ConciseMidTag<'a> {
+    /// Compares this CoMID with a `new` version of it
+    pub fn diff<'b>(&'b self, new: &'b ConciseMidTag<'a>) -> ComidDiff<'b> {
+        diff_comid(self, new)
+    }
+}
+
+fn diff_corim<'a>(old: &'a CorimMap<'a>, new: &'a CorimMap<'a>) -> CorimDiff<'a> {
+    let mut tags = vec![];
+    let mut paired = vec![false; new.tags.len()];
+
+    for old_tag in &old.tags {
+        let key = TagKey::of(old_tag);
+        let found = new
+            .tags
+            .iter()
+            .enumerate()
+            .find(|(index, new_tag)| !paired[*index] && TagKey::of(new_tag) == key);
+
+        let change = match found {
+            None => Some(TagChange::Removed { tag: old_tag }),
+            Some((index, new_tag)) => {
+                paired[index] = true;
+                diff_tag(old_tag, new_tag)
+            }
+        };
+
+        tags.extend(change.map(|change| TagDiff { key, change }));
+    }
+
+    for (new_tag, _) in new.tags.iter().zip(paired).filter(|(_, paired)| !paired) {
+        tags.push(TagDiff {
+            key: TagKey::of(new_tag),
+            change: TagChange::Added { tag: new_tag },
+        });
+    }
+
+    CorimDiff {
+        id: Change::between(Some(&old.id), Some(&new.id)),
+        dependent_rims: Change::between(old.dependent_rims.as_ref(), new.dependent_rims.as_ref()),
+        profile: Change::between(old.profile.as_ref(), new.profile.as_ref()),
+        rim_validity: Change::between(old.rim_validity.as_ref(), new.rim_validity.as_ref()),
+        entities: Change::between(old.entities.as_ref(), new.entities.as_ref()),
+        extension: Change::between(old.extension.as_ref(), new.extension.as_ref()),
+        tags,
+    }
+}
+
+fn diff_tag<'a>(
+    old: &'a ConciseTagTypeChoice<'a>,
+    new: &'a ConciseTagTypeChoice<'a>,
+) -> Option<TagChange<'a>> {
+    match (old, new) {
+        (ConciseTagTypeChoice::Mid(old), ConciseTagTypeChoice::Mid(new)) => {
+            let diff = diff_comid(old, new);
+            (!diff.is_empty()).then_some(TagChange::ModifiedComid { diff })
+        }
+        _ if old == new => None,
+        _ => Some(TagChange::Modified { old, new }),
+    }
+}
+
+fn diff_comid<'a>(old: &'a ConciseMidTag<'a>, new: &'a ConciseMidTag<'a>) -> ComidDiff<'a> {
+    let mut measurements = vec![];
+    let mut paired = vec![false; measurement_entries(&new.triples).len()];
+    let new_entries = measurement_entries(&new.triples);
+
+    for (triple, environment, old_measurement) in measurement_entries(&old.triples) {
+        let found = new_entries
+            .iter()
+            .enumerate()
+            .find(|(index, (kind, env, measurement))| {
+                !paired[*index]
+                    && *kind == triple
+                    && *env == environment
+                    && measurement.mkey == old_measurement.mkey
+            });
+
+        match found {
+            None => measurements.push(measurement_diff(
+                triple,
+                environment,
+                old_measurement,
+                Change::Removed {
+                    old: &old_measurement.mval,
+                },
+            )),
+            Some((index, (_, _, new_measurement))) => {
+                paired[index] = true;
+                if old_measurement != *new_measurement {
+                    measurements.push(modified_measurement(
+                        triple,
+                        environment,
+                        old_measurement,
+                        new_measurement,
+                    ));
+                }
+            }
+        }
+    }
+
+    for ((triple, environment, new_measurement), _) in new_entries
+        .into_iter()
+        .zip(paired)
+        .filter(|(_, paired)| !paired)
+    {
+        measurements.push(measurement_diff(
+            triple,
+            environment,
+            new_measurement,
+            Change::Added {
+                new: &new_measurement.mval,
+            },
+        ));
+    }
+
+    let old_keys = key_entries(&old.triples);
+    let new_keys = key_entries(&new.triples);
+    let keys = old_keys
+        .iter()
+        .filter(|entry| !new_keys.contains(entry))
+        .map(|&(triple, environment, key)| KeyDiff {
+            triple,
+            environment,
+            change: Change::Removed { old: key },
+        })
+        .chain(
+            new_keys
+                .iter()
+                .filter(|entry| !old_keys.contains(entry))
+                .map(|&(triple, environment, key)| KeyDiff {
+                    triple,
+                    environment,
+                    change: Change::Added { new: key },
+                }),
+        )
+        .collect();
+
+    ComidDiff {
+        tag_version: Change::between(
+            old.tag_identity.tag_version.as_ref(),
+            new.tag_identity.tag_version.as_ref(),
+        ),
+        measurements,
+        keys,
+        other_changes: other_changes(old, new),
+    }
+}
+
+/// Every measurement of the reference and endorsed triples, with its triple kind and
+/// environment
+fn measurement_entries<'a>(
+    triples: &'a TriplesMap<'a>,
+) -> Vec<(TripleKind, &'a EnvironmentMap<'a>, &'a MeasurementMap<'a>)> {
+    let reference = triples.reference_triples.iter().flatten().flat_map(|t| {
+        t.ref_claims
+            .iter()
+            .map(move |m| (TripleKind::Reference, &t.ref_env, m))
+    });
+    let endorsed = triples.endorsed_triples.iter().flatten().flat_map(|t| {
+        t.endorsement
+            .iter()
+            .map(move |m| (TripleKind::Endorsed, &t.condition, m))
+    });
+
+    reference.chain(endorsed).collect()
+}
+
+/// Every key of the identity and attest-key triples, with its triple kind and environment
+fn key_entries<'a>(
+    triples: &'a TriplesMap<'a>,
+) -> Vec<(
+    TripleKind,
+    &'a EnvironmentMap<'a>,
+    &'a CryptoKeyTypeChoice<'a>,
+)> {
+    let identity = triples.identity_triples.iter().flatten().flat_map(|t| {
+        t.key_list
+            .iter()
+            .map(move |key| (TripleKind::Identity, &t.environment, key))
+    });
+    let attest_key = triples.attest_key_triples.iter().flatten().flat_map(|t| {
+        t.key_list
+            .iter()
+            .map(move |key| (TripleKind::AttestKey, &t.environment, key))
+    });
+
+    identity.chain(attest_key).collect()
+}
+
+fn measurement_diff<'a>(
+    triple: TripleKind,
+    environment: &'a EnvironmentMap<'a>,
+    measurement: &'a MeasurementMap<'a>,
+    change: Change<&'a MeasurementValuesMap<'a>>,
+) -> MeasurementDiff<'a> {
+    MeasurementDiff {
+        triple,
+        environment,
+        mkey: measurement.mkey.as_ref(),
+        change,
+        digests: vec![],
+        svn: None,
+        other: vec![],
+    }
+}
+
+fn modified_measurement<'a>(
+    triple: TripleKind,
+    environment: &'a EnvironmentMap<'a>,
+    old: &'a MeasurementMap<'a>,
+    new: &'a MeasurementMap<'a>,
+) -> MeasurementDiff<'a> {
+    let (old_values, new_values) = (&old.mval, &new.mval);
+
+    let mut other = vec![];
+    macro_rules! compare {
+        ($($field:ident => $name:literal),* $(,)?) => {
+            $(
+                if old_values.$field != new_values.$field {
+                    other.push($name);
+                }
+            )*
+        };
+    }
+    compare!(
+        version => "version",
+        flags => "flags",
+        raw => "raw-value",
+        mac_addr => "mac-addr",
+        ip_addr => "ip-addr",
+        serial_number => "serial-number",
+        ueid => "ueid",
+        uuid => "uuid",
+        name => "name",
+        cryptokeys => "cryptokeys",
+        integrity_registers => "integrity-registers",
+        extensions => "extensions",
+    );
+    if old.authorized_by != new.authorized_by {
+        other.push("authorized-by");
+    }
+
+    MeasurementDiff {
+        digests: diff_digests(
+            old_values.digests.as_deref().unwrap_or_default(),
+            new_values.digests.as_deref().unwrap_or_default(),
+        ),
+        svn: Change::between(old_values.svn.as_ref(), new_values.svn.as_ref()),
+        other,
+        ..measurement_diff(
+            triple,
+            environment,
+            old,
+            Change::Modified {
+                old: old_values,
+                new: new_values,
+            },
+        )
+    }
+}
+
+/// Digests present in only one list are paired with an unmatched digest of the same algorithm
+/// in the other, if there is one, and reported as modified
+fn diff_digests<'a>(old: &'a [Digest<'a>], new: &'a [Digest<'a>]) -> Vec<Change<&'a Digest<'a>>> {
+    let removed = old.iter().filter(|d| !new.contains(d)).collect::<Vec<_>>();
+    let mut added = new.iter().filter(|d| !old.contains(d)).collect::<Vec<_>>();
+
+    let mut changes = vec![];
+    for old in removed {
+        match added.iter().position(|new| new.alg == old.alg) {
+            Some(index) => changes.push(Change::Modified {
+                old,
+                new: added.remove(index),
+            }),
+            None => changes.push(Change::Removed { old }),
+        }
+    }
+    changes.extend(added.into_iter().map(|new| Change::Added { new }));
+
+    changes
+}
+
+fn other_changes(old: &ConciseMidTag, new: &ConciseMidTag) -> bool {
+    let (old_triples, new_triples) = (&old.triples, &new.triples);
+
+    old.language != new.language
+        || old.entities != new.entities
+        || old.linked_tags != new.linked_tags
+        || old.extension != new.extension
+        || key_conditions(old_triples) != key_conditions(new_triples)
+        || old_triples.dependency_triples != new_triples.dependency_triples
+        || old_triples.membership_triples != new_triples.membership_triples
+        || old_triples.coswid_triples != new_triples.coswid_triples
+        || old_triples.conditional_endorsement_series_triples
+            != new_triples.conditional_endorsement_series_triples
+        || old_triples.conditional_endorsement_triples
+            != new_triples.conditional_endorsement_triples
+        || old_triples.extension != new_triples.extension
+}
+
+/// The conditions of the identity and attest-key triples, with their environment
+fn key_conditions<'a>(
+    triples: &'a TriplesMap<'a>,
+) -> Vec<(
+    &'a EnvironmentMap<'a>,
+    &'a Option<TriplesRecordCondition<'a>>,
+)> {
+    let identity = triples.identity_triples.iter().flatten();
+    let attest_key = triples.attest_key_triples.iter().flatten();
+
+    identity
+        .map(|t| (&t.environment, &t.conditions))
+        .chain(attest_key.map(|t| (&t.environment, &t.conditions)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{owned::IntoOwned, Bytes, CorimRoleTypeChoice, Text, Uri};
+
+    fn comid(tag_id: &str, triples: &str) -> ConciseMidTag<'static> {
+        let json = format!(r#"{{"1": {{"0": "{tag_id}"}}, "4": {triples}}}"#);
+        serde_json::from_str::<ConciseMidTag>(&json)
+            .unwrap()
+            .into_owned()
+    }
+
+    fn corim(tags: &[ConciseMidTag<'static>]) -> CorimMap<'static> {
+        let tags = tags
+            .iter()
+            .map(|tag| {
+                format!(
+                    r#"{{"type": "comid", "value": {}}}"#,
+                    serde_json::to_string(tag).unwrap()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let json = format!(r#"{{"0": "corim-1", "1": [{tags}]}}"#);
+        serde_json::from_str::<CorimMap>(&json)
+            .unwrap()
+            .into_owned()
+    }
+
+    const ENV: &str = r#"{"class": {"vendor": "ACME Inc.", "model": "RoadRunner"}}"#;
+
+    fn reference(mval: &str) -> String {
+        format!(r#"{{"0": [[{ENV}, [{{"mkey": "firmware", "mval": {mval}}}]]]}}"#)
+    }
+
+    #[test]
+    fn test_change_between() {
+        assert_eq!(Change::between(Some(1), Some(1)), None);
+        assert_eq!(Change::between(None::<u8>, None), None);
+        assert_eq!(
+            Change::between(None, Some(2)),
+            Some(Change::Added { new: 2 })
+        );
+        assert_eq!(
+            Change::between(Some(1), None),
+            Some(Change::Removed { old: 1 })
+        );
+        assert_eq!(
+            Change::between(Some(1), Some(2)),
+            Some(Change::Modified { old: 1, new: 2 })
+        );
+    }
+
+    #[test]
+    fn test_comid_diff_measurements() {
+        let old = comid(
+            "comid-1",
+            &reference(r#"{"svn": 3, "digests": ["sha-256;AQID", "sha-384;BAUG"], "name": "fw"}"#),
+        );
+        let new = comid(
+            "comid-1",
+            &reference(
+                r#"{"svn": 4, "digests": ["sha-256;BwgJ", "sha-384;BAUG", "sha-512;CgsM"], "name": "fw"}"#,
+            ),
+        );
+
+        assert!(old.diff(&old).is_empty());
+
+        let diff = old.diff(&new);
+        assert!(!diff.other_changes);
+        assert!(diff.keys.is_empty());
+        assert_eq!(diff.measurements.len(), 1);
+
+        let measurement = &diff.measurements[0];
+        assert_eq!(measurement.triple, TripleKind::Reference);
+        assert!(matches!(measurement.change, Change::Modified { .. }));
+        assert_eq!(
+            measurement.svn,
+            Some(Change::Modified {
+                old: &SvnTypeChoice::Svn(3u64.into()),
+                new: &SvnTypeChoice::Svn(4u64.into()),
+            })
+        );
+        assert!(measurement.other.is_empty());
+
+        let digests = serde_json::to_string(&measurement.digests).unwrap();
+        assert_eq!(
+            digests,
+            r#"[{"change":"modified","old":"sha-256;AQID","new":"sha-256;BwgJ"},{"change":"added","new":"sha-512;CgsM"}]"#
+        );
+    }
+
+    #[test]
+    fn test_comid_diff_alignment() {
+        let old = comid("comid-1", &reference(r#"{"svn": 3}"#));
+        let new = comid(
+            "comid-1",
+            &format!(r#"{{"0": [[{ENV}, [{{"mkey": "loader", "mval": {{"svn": 3}}}}]]]}}"#),
+        );
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.measurements.len(), 2);
+        assert!(matches!(
+            diff.measurements[0].change,
+            Change::Removed { .. }
+        ));
+        assert!(matches!(diff.measurements[1].change, Change::Added { .. }));
+        assert!(diff
+            .measurements
+            .iter()
+            .all(|m| m.digests.is_empty() && m.svn.is_none()));
+    }
+
+    #[test]
+    fn test_comid_diff_authorized_by() {
+        let authorized_by = |key: &str| {
+            format!(
+                r#"{{"0": [[{ENV}, [{{"mkey": "firmware", "mval": {{"svn": 3}}, "authorized-by": [{{"type": "thumbprint", "value": "{key}"}}]}}]]]}}"#
+            )
+        };
+        let old = comid("comid-1", &authorized_by("sha-256;AQID"));
+        let new = comid("comid-1", &authorized_by("sha-256;BwgJ"));
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert!(!diff.other_changes);
+        assert_eq!(diff.measurements.len(), 1);
+
+        let measurement = &diff.measurements[0];
+        assert!(matches!(measurement.change, Change::Modified { old, new } if old == new));
+        assert!(measurement.digests.is_empty());
+        assert!(measurement.svn.is_none());
+        assert_eq!(measurement.other, ["authorized-by"]);
+    }
+
+    #[test]
+    fn test_comid_diff_keys() {
+        let keys = |keys: &str| {
+            format!(
+                r#"{{"0": [[{ENV}, [{{"mkey": "firmware", "mval": {{"svn": 3}}}}]]], "3": [[{ENV}, [{keys}]]]}}"#
+            )
+        };
+        let old = comid(
+            "comid-1",
+            &keys(r#"{"type": "thumbprint", "value": "sha-256;AQID"}"#),
+        );
+        let new = comid(
+            "comid-1",
+            &keys(r#"{"type": "thumbprint", "value": "sha-256;BwgJ"}"#),
+        );
+
+        let diff = old.diff(&new);
+        assert!(diff.measurements.is_empty());
+        assert_eq!(diff.keys.len(), 2);
+        assert!(diff
+            .keys
+            .iter()
+            .all(|key| key.triple == TripleKind::AttestKey));
+        assert!(matches!(diff.keys[0].change, Change::Removed { .. }));
+        assert!(matches!(diff.keys[1].change, Change::Added { .. }));
+    }
+
+    #[test]
+    fn test_corim_diff() {
+        let first = comid("comid-1", &reference(r#"{"svn": 3}"#));
+        let second = comid("comid-2", &reference(r#"{"svn": 3}"#));
+        let updated = comid("comid-1", &reference(r#"{"svn": 4}"#));
+        let third = comid("comid-3", &reference(r#"{"svn": 1}"#));
+
+        let old = corim(&[first, second]);
+        assert!(old.diff(&old).is_empty());
+
+        let new = corim(&[third, updated]);
+        let diff = old.diff(&new);
+        assert!(diff.id.is_none());
+        assert_eq!(diff.tags.len(), 3);
+
+        let ids = diff
+            .tags
+            .iter()
+            .map(|tag| match tag.key {
+                TagKey::Comid(id) => id.as_str().unwrap(),
+                _ => panic!("expected a CoMID"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["comid-1", "comid-2", "comid-3"]);
+
+        assert!(matches!(
+            &diff.tags[0].change,
+            TagChange::ModifiedComid { diff } if diff.measurements.len() == 1
+        ));
+        assert!(matches!(diff.tags[1].change, TagChange::Removed { .. }));
+        assert!(matches!(diff.tags[2].change, TagChange::Added { .. }));
+    }
+
+    #[test]
+    fn test_corim_diff_dependent_rims() {
+        let old = corim(&[]);
+        let mut new = old.clone();
+        new.dependent_rims = Some(vec![CorimLocatorMap {
+            href: vec![Uri::from(Text::from("https://example.com/base.corim"))],
+            thumbprint: None,
+        }]);
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert!(matches!(diff.dependent_rims, Some(Change::Added { .. })));
+        assert!(diff.tags.is_empty());
+    }
+
+    #[test]
+    fn test_corim_diff_entities() {
+        let entity = |name: &'static str| CorimEntityMap {
+            entity_name: name.into(),
+            reg_id: None,
+            role: vec![CorimRoleTypeChoice::ManifestCreator],
+            extension: None,
+        };
+        let mut old = corim(&[]);
+        old.entities = Some(vec![entity("ACME Inc.")]);
+        let mut new = old.clone();
+        new.entities = Some(vec![entity("ACME Corp.")]);
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert!(matches!(diff.entities, Some(Change::Modified { .. })));
+        assert!(diff.tags.is_empty());
+    }
+
+    #[test]
+    fn test_corim_diff_extension() {
+        let mut old = corim(&[]);
+        old.extension = Some(CorimMapExtension(Bytes::from(vec![1, 2, 3]).into()));
+        let mut new = old.clone();
+        new.extension = None;
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert!(matches!(diff.extension, Some(Change::Removed { .. })));
+        assert!(diff.tags.is_empty());
+    }
+}
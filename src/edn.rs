@@ -0,0 +1,1266 @@
+// SPDX-License-Identifier: MIT
+
+//! CBOR Extended Diagnostic Notation (EDN) printing and parsing
+//!
+//! This module renders CoRIM structures in the diagnostic notation used by the IETF drafts
+//! (RFC 8949 section 8 and its extensions), which is far easier to read than a hex dump when
+//! debugging an encoding:
+//!
+//! * CBOR tags are rendered as `501({...})`
+//! * Byte strings are rendered as `h'...'`
+//! * Byte strings holding an embedded CBOR map, array or tag, such as the protected header and
+//!   payload of a signed CoRIM, are rendered as `<<...>>`
+//! * Map keys that are integers, or text encoded integers, can be preceded by a comment naming
+//!   the CDDL field, for example `/ tag-identity / 1`
+//!
+//! The parser goes the other way, so that examples from the drafts can be pasted into tests
+//! and tools. It accepts tags, `h'...'` and `b64'...'` byte strings, embedded `<<...>>` CBOR,
+//! comments and indefinite-length markers, and produces CBOR bytes or a typed value.
+//!
+//! Values are serialized to CBOR first, so the output always reflects the encoding this crate
+//! produces. Field names are looked up from a [`Rule`], which is known for the types
+//! implementing [`ToEdn`] and follows from CBOR tags (e.g. 501 for a CoRIM map, 506 for a
+//! CoMID) within the value.
+//!
+//! # Key Components
+//!
+//! * [`ToEdn`] - Renders the main CoRIM, CoMID, CoSWID and CoTL types as EDN
+//! * [`to_string`] - Renders any serializable value as EDN under a given [`Rule`]
+//! * [`from_cbor`] - Renders an encoded CBOR data item as EDN
+//! * [`EdnOptions`] - Controls field name comments and indentation
+//! * [`to_cbor`] - Parses EDN into CBOR bytes
+//! * [`from_str`] - Parses EDN into a value such as a [`ConciseRimTypeChoice`]
+//!
+//! # Example
+//!
+//! ```rust
+//! use corim_rs::{corim::ValidityMap, edn::ToEdn};
+//!
+//! let validity = ValidityMap::new(Some(1_704_067_200.into()), 1_893_456_000.into());
+//! assert_eq!(
+//!     validity.to_edn().unwrap(),
+//!     "{\n  / not-before / \"0\": 1704067200,\n  / not-after / \"1\": 1893456000\n}"
+//! );
+//!
+//! let parsed: ValidityMap = corim_rs::edn::from_str(&validity.to_edn().unwrap()).unwrap();
+//! assert_eq!(parsed, validity);
+//! ```
+
+use std::fmt::Write as _;
+
+use base64::{
+    engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use ciborium::Value;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AttestKeyTripleRecord, COSESign1Corim, COSESignCorim, ClassMap, ComidEntityMap, ConciseMidTag,
+    ConciseRimTypeChoice, ConciseSwidTag, ConciseTagTypeChoice, ConciseTlTag, CorimEntityMap,
+    CorimMap, CorimMetaMap, EdnError, EndorsedTripleRecord, EnvironmentMap, IdentityTripleRecord,
+    MeasurementMap, MeasurementValuesMap, ProtectedCorimHeaderMap, ReferenceTripleRecord, Result,
+    TagIdentityMap, TriplesMap, ValidityMap,
+};
+
+/// Longest array of scalar values rendered on a single line
+const MAX_INLINE_LEN: usize = 72;
+
+/// Options controlling the rendering of EDN
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdnOptions {
+    /// Precede map keys with a comment naming the CDDL field, where it is known
+    pub comments: bool,
+    /// Number of spaces per level of nesting
+    pub indent: usize,
+}
+
+impl Default for EdnOptions {
+    fn default() -> Self {
+        Self {
+            comments: true,
+            indent: 2,
+        }
+    }
+}
+
+/// The CDDL rule a CBOR data item is expected to match, used to name the fields of maps and
+/// the elements of record arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Rule {
+    /// No known rule. Field names are only found within tagged data items.
+    Any,
+    CorimMap,
+    CorimLocatorMap,
+    CorimEntityMap,
+    ValidityMap,
+    /// An untagged COSE_Sign1 array
+    CoseSign1,
+    /// An untagged COSE_Sign array
+    CoseSign,
+    /// The list of COSE_Signature arrays of a COSE_Sign
+    CoseSignatures,
+    CoseSignature,
+    /// A COSE header map, protected or unprotected
+    CoseHeader,
+    CorimMetaMap,
+    CorimSignerMap,
+    ConciseMidTag,
+    TagIdentityMap,
+    ComidEntityMap,
+    LinkedTagMap,
+    TriplesMap,
+    /// A list of reference or endorsed triple records
+    MeasurementTriples,
+    /// A reference or endorsed triple record
+    MeasurementTriple,
+    /// A list of identity or attest-key triple records
+    KeyTriples,
+    /// An identity or attest-key triple record
+    KeyTriple,
+    EnvironmentMap,
+    ClassMap,
+    MeasurementMap,
+    MeasurementValuesMap,
+    VersionMap,
+    FlagsMap,
+    /// A CoSWID tag, or any map within it, which share a single index of field names
+    ConciseSwidTag,
+    ConciseTlTag,
+}
+
+impl Rule {
+    /// The rule of the data item enclosed in a CBOR tag
+    fn of_tag(tag: u64) -> Option<Self> {
+        Some(match tag {
+            18 => Self::CoseSign1,
+            98 => Self::CoseSign,
+            501 => Self::CorimMap,
+            505 => Self::ConciseSwidTag,
+            506 => Self::ConciseMidTag,
+            508 => Self::ConciseTlTag,
+            _ => return None,
+        })
+    }
+
+    /// Whether arrays matching this rule are records, whose elements have distinct rules,
+    /// rather than lists of the same rule
+    fn is_record(self) -> bool {
+        matches!(
+            self,
+            Self::CoseSign1
+                | Self::CoseSign
+                | Self::CoseSignature
+                | Self::MeasurementTriple
+                | Self::KeyTriple
+        )
+    }
+
+    /// The name of the CDDL field at `key` of a map matching this rule
+    pub fn field(self, key: i128) -> Option<&'static str> {
+        let name = match (self, key) {
+            (Self::CorimMap, 0) => "id",
+            (Self::CorimMap, 1) => "tags",
+            (Self::CorimMap, 2) => "dependent-rims",
+            (Self::CorimMap, 3) => "profile",
+            (Self::CorimMap, 4) => "rim-validity",
+            (Self::CorimMap, 5) => "entities",
+            (Self::CorimLocatorMap, 0) => "href",
+            (Self::CorimLocatorMap, 1) => "thumbprint",
+            (Self::CorimEntityMap, 0) => "entity-name",
+            (Self::CorimEntityMap, 1) => "reg-id",
+            (Self::CorimEntityMap, 2) => "role",
+            (Self::ValidityMap, 0) => "not-before",
+            (Self::ValidityMap, 1) => "not-after",
+            (Self::CoseHeader, 1) => "alg",
+            (Self::CoseHeader, 2) => "crit",
+            (Self::CoseHeader, 3) => "content-type",
+            (Self::CoseHeader, 4) => "kid",
+            (Self::CoseHeader, 8) => "corim-meta",
+            (Self::CoseHeader, 33) => "x5chain",
+            (Self::CoseHeader, 34) => "x5t",
+            (Self::CorimMetaMap, 0) => "signer",
+            (Self::CorimMetaMap, 1) => "signature-validity",
+            (Self::CorimSignerMap, 0) => "signer-name",
+            (Self::CorimSignerMap, 1) => "signer-uri",
+            (Self::ConciseMidTag, 0) => "language",
+            (Self::ConciseMidTag, 1) => "tag-identity",
+            (Self::ConciseMidTag, 2) => "entities",
+            (Self::ConciseMidTag, 3) => "linked-tags",
+            (Self::ConciseMidTag, 4) => "triples",
+            (Self::TagIdentityMap, 0) => "tag-id",
+            (Self::TagIdentityMap, 1) => "tag-version",
+            (Self::ComidEntityMap, 31) => "entity-name",
+            (Self::ComidEntityMap, 32) => "reg-id",
+            (Self::ComidEntityMap, 33) => "role",
+            (Self::LinkedTagMap, 0) => "linked-tag-id",
+            (Self::LinkedTagMap, 1) => "tag-rel",
+            (Self::TriplesMap, 0) => "reference-triples",
+            (Self::TriplesMap, 1) => "endorsed-triples",
+            (Self::TriplesMap, 2) => "identity-triples",
+            (Self::TriplesMap, 3) => "attest-key-triples",
+            (Self::TriplesMap, 4) => "dependency-triples",
+            (Self::TriplesMap, 5) => "membership-triples",
+            (Self::TriplesMap, 6) => "coswid-triples",
+            (Self::TriplesMap, 8) => "conditional-endorsement-series-triples",
+            (Self::TriplesMap, 10) => "conditional-endorsement-triples",
+            (Self::EnvironmentMap, 0) => "class",
+            (Self::EnvironmentMap, 1) => "instance",
+            (Self::EnvironmentMap, 2) => "group",
+            (Self::ClassMap, 0) => "class-id",
+            (Self::ClassMap, 1) => "vendor",
+            (Self::ClassMap, 2) => "model",
+            (Self::ClassMap, 3) => "layer",
+            (Self::ClassMap, 4) => "index",
+            (Self::MeasurementMap, 0) => "mkey",
+            (Self::MeasurementMap, 1) => "mval",
+            (Self::MeasurementMap, 2) => "authorized-by",
+            (Self::MeasurementValuesMap, 0) => "version",
+            (Self::MeasurementValuesMap, 1) => "svn",
+            (Self::MeasurementValuesMap, 2) => "digests",
+            (Self::MeasurementValuesMap, 3) => "flags",
+            (Self::MeasurementValuesMap, 4) => "raw-value",
+            (Self::MeasurementValuesMap, 5) => "raw-value-mask",
+            (Self::MeasurementValuesMap, 6) => "mac-addr",
+            (Self::MeasurementValuesMap, 7) => "ip-addr",
+            (Self::MeasurementValuesMap, 8) => "serial-number",
+            (Self::MeasurementValuesMap, 9) => "ueid",
+            (Self::MeasurementValuesMap, 10) => "uuid",
+            (Self::MeasurementValuesMap, 11) => "name",
+            (Self::MeasurementValuesMap, 13) => "cryptokeys",
+            (Self::MeasurementValuesMap, 14) => "integrity-registers",
+            (Self::VersionMap, 0) => "version",
+            (Self::VersionMap, 1) => "version-scheme",
+            (Self::FlagsMap, 0) => "is-configured",
+            (Self::FlagsMap, 1) => "is-secure",
+            (Self::FlagsMap, 2) => "is-recovery",
+            (Self::FlagsMap, 3) => "is-debug",
+            (Self::FlagsMap, 4) => "is-replay-protected",
+            (Self::FlagsMap, 5) => "is-integrity-protected",
+            (Self::FlagsMap, 6) => "is-runtime-meas",
+            (Self::FlagsMap, 7) => "is-immutable",
+            (Self::FlagsMap, 8) => "is-tcb",
+            (Self::FlagsMap, 9) => "is-confidentiality-protected",
+            (Self::ConciseSwidTag, key) => return coswid_field(key),
+            (Self::ConciseTlTag, 0) => "tag-identity",
+            (Self::ConciseTlTag, 1) => "tags-list",
+            (Self::ConciseTlTag, 2) => "tl-validity",
+            _ => return None,
+        };
+
+        Some(name)
+    }
+
+    /// The rule of the value at `key` of a map matching this rule
+    fn value(self, key: i128) -> Self {
+        match (self, key) {
+            (Self::CorimMap, 2) => Self::CorimLocatorMap,
+            (Self::CorimMap, 4) => Self::ValidityMap,
+            (Self::CorimMap, 5) => Self::CorimEntityMap,
+            (Self::CoseHeader, 8) => Self::CorimMetaMap,
+            (Self::CorimMetaMap, 0) => Self::CorimSignerMap,
+            (Self::CorimMetaMap, 1) => Self::ValidityMap,
+            (Self::ConciseMidTag, 1) => Self::TagIdentityMap,
+            (Self::ConciseMidTag, 2) => Self::ComidEntityMap,
+            (Self::ConciseMidTag, 3) => Self::LinkedTagMap,
+            (Self::ConciseMidTag, 4) => Self::TriplesMap,
+            (Self::TriplesMap, 0 | 1) => Self::MeasurementTriples,
+            (Self::TriplesMap, 2 | 3) => Self::KeyTriples,
+            (Self::EnvironmentMap, 0) => Self::ClassMap,
+            (Self::MeasurementMap, 1) => Self::MeasurementValuesMap,
+            (Self::MeasurementValuesMap, 0) => Self::VersionMap,
+            (Self::MeasurementValuesMap, 3) => Self::FlagsMap,
+            (Self::ConciseSwidTag, _) => Self::ConciseSwidTag,
+            (Self::ConciseTlTag, 0 | 1) => Self::TagIdentityMap,
+            (Self::ConciseTlTag, 2) => Self::ValidityMap,
+            _ => Self::Any,
+        }
+    }
+
+    /// The rule of the element at `index` of an array matching this rule
+    fn element(self, index: usize) -> Self {
+        match (self, index) {
+            (Self::CoseSign1 | Self::CoseSign | Self::CoseSignature, 0 | 1) => Self::CoseHeader,
+            (Self::CoseSign, 3) => Self::CoseSignatures,
+            (Self::CoseSignatures, _) => Self::CoseSignature,
+            (Self::MeasurementTriples, _) => Self::MeasurementTriple,
+            (Self::MeasurementTriple | Self::KeyTriple, 0) => Self::EnvironmentMap,
+            (Self::MeasurementTriple, 1) => Self::MeasurementMap,
+            (Self::KeyTriples, _) => Self::KeyTriple,
+            (rule, _) if !rule.is_record() => rule,
+            _ => Self::Any,
+        }
+    }
+}
+
+/// CoSWID uses a single index of field names for all of its maps
+fn coswid_field(key: i128) -> Option<&'static str> {
+    let name = match key {
+        0 => "tag-id",
+        1 => "software-name",
+        2 => "entity",
+        3 => "evidence",
+        4 => "link",
+        5 => "software-meta",
+        6 => "payload",
+        7 => "hash",
+        8 => "corpus",
+        9 => "patch",
+        10 => "media",
+        11 => "supplemental",
+        12 => "tag-version",
+        13 => "software-version",
+        14 => "version-scheme",
+        15 => "lang",
+        16 => "directory",
+        17 => "file",
+        18 => "process",
+        19 => "resource",
+        20 => "size",
+        21 => "file-version",
+        22 => "key",
+        23 => "location",
+        24 => "fs-name",
+        25 => "root",
+        26 => "path-elements",
+        27 => "process-name",
+        28 => "pid",
+        29 => "type",
+        31 => "entity-name",
+        32 => "reg-id",
+        33 => "role",
+        34 => "thumbprint",
+        35 => "date",
+        36 => "device-id",
+        37 => "artifact",
+        38 => "href",
+        39 => "ownership",
+        40 => "rel",
+        41 => "media-type",
+        42 => "use",
+        43 => "activation-status",
+        44 => "channel-type",
+        45 => "colloquial-version",
+        46 => "description",
+        47 => "edition",
+        48 => "entitlement-data-required",
+        49 => "entitlement-key",
+        50 => "generator",
+        51 => "persistent-id",
+        52 => "product",
+        53 => "product-family",
+        54 => "revision",
+        55 => "summary",
+        56 => "unspsc-code",
+        57 => "unspsc-version",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+/// Renders a value as CBOR Extended Diagnostic Notation
+pub trait ToEdn: Serialize {
+    /// The rule the value is encoded as
+    const RULE: Rule;
+
+    /// Renders the value with the default [`EdnOptions`]
+    fn to_edn(&self) -> Result<String> {
+        self.to_edn_with(&EdnOptions::default())
+    }
+
+    /// Renders the value with the given options
+    fn to_edn_with(&self, options: &EdnOptions) -> Result<String> {
+        to_string(self, Self::RULE, options)
+    }
+}
+
+macro_rules! impl_to_edn {
+    ($($type:ident => $rule:ident),* $(,)?) => {
+        $(
+            impl ToEdn for $type<'_> {
+                const RULE: Rule = Rule::$rule;
+            }
+        )*
+    };
+}
+
+impl_to_edn!(
+    ConciseRimTypeChoice => Any,
+    CorimMap => CorimMap,
+    COSESign1Corim => CoseSign1,
+    COSESignCorim => CoseSign,
+    ProtectedCorimHeaderMap => CoseHeader,
+    CorimMetaMap => CorimMetaMap,
+    CorimEntityMap => CorimEntityMap,
+    ConciseTagTypeChoice => Any,
+    ConciseMidTag => ConciseMidTag,
+    ConciseSwidTag => ConciseSwidTag,
+    ConciseTlTag => ConciseTlTag,
+    TagIdentityMap => TagIdentityMap,
+    ComidEntityMap => ComidEntityMap,
+    TriplesMap => TriplesMap,
+    ReferenceTripleRecord => MeasurementTriple,
+    EndorsedTripleRecord => MeasurementTriple,
+    IdentityTripleRecord => KeyTriple,
+    AttestKeyTripleRecord => KeyTriple,
+    EnvironmentMap => EnvironmentMap,
+    ClassMap => ClassMap,
+    MeasurementMap => MeasurementMap,
+    MeasurementValuesMap => MeasurementValuesMap,
+);
+
+impl ToEdn for ValidityMap {
+    const RULE: Rule = Rule::ValidityMap;
+}
+
+/// Renders any serializable value as EDN, naming fields according to `rule`
+pub fn to_string<T: Serialize + ?Sized>(
+    value: &T,
+    rule: Rule,
+    options: &EdnOptions,
+) -> Result<String> {
+    let value = Value::serialized(value).map_err(|e| EdnError::Encoding(e.to_string()))?;
+
+    let mut out = String::new();
+    Printer {
+        options,
+        out: &mut out,
+    }
+    .value(&value, rule, 0);
+    Ok(out)
+}
+
+/// Renders a single encoded CBOR data item as EDN, naming fields according to `rule`
+pub fn from_cbor(cbor: &[u8], rule: Rule, options: &EdnOptions) -> Result<String> {
+    let mut reader = cbor;
+    let value: Value =
+        ciborium::from_reader(&mut reader).map_err(|e| EdnError::Decoding(e.to_string()))?;
+
+    if !reader.is_empty() {
+        return Err(EdnError::TrailingBytes(reader.len()).into());
+    }
+
+    let mut out = String::new();
+    Printer {
+        options,
+        out: &mut out,
+    }
+    .value(&value, rule, 0);
+    Ok(out)
+}
+
+struct Printer<'a> {
+    options: &'a EdnOptions,
+    out: &'a mut String,
+}
+
+impl Printer<'_> {
+    fn value(&mut self, value: &Value, rule: Rule, depth: usize) {
+        match value {
+            Value::Integer(int) => {
+                let _ = write!(self.out, "{}", i128::from(*int));
+            }
+            Value::Bytes(bytes) => match embedded_cbor(bytes) {
+                Some(embedded) => {
+                    self.out.push_str("<<");
+                    self.value(&embedded, rule, depth);
+                    self.out.push_str(">>");
+                }
+                None => {
+                    self.out.push_str("h'");
+                    for byte in bytes {
+                        let _ = write!(self.out, "{byte:02x}");
+                    }
+                    self.out.push('\'');
+                }
+            },
+            Value::Float(float) => self.float(*float),
+            Value::Text(text) => {
+                // JSON string escapes are also valid in EDN
+                let _ = write!(self.out, "{}", serde_json::Value::from(text.as_str()));
+            }
+            Value::Bool(b) => {
+                let _ = write!(self.out, "{b}");
+            }
+            Value::Null => self.out.push_str("null"),
+            Value::Tag(tag, inner) => {
+                let _ = write!(self.out, "{tag}(");
+                self.value(inner, Rule::of_tag(*tag).unwrap_or(Rule::Any), depth);
+                self.out.push(')');
+            }
+            Value::Array(items) => self.array(items, rule, depth),
+            Value::Map(entries) => self.map(entries, rule, depth),
+            _ => self.out.push_str("undefined"),
+        }
+    }
+
+    fn float(&mut self, float: f64) {
+        if float.is_nan() {
+            self.out.push_str("NaN");
+        } else if float.is_infinite() {
+            self.out
+                .push_str(if float > 0.0 { "Infinity" } else { "-Infinity" });
+        } else {
+            // Debug formatting always includes a fractional part or exponent
+            let _ = write!(self.out, "{float:?}");
+        }
+    }
+
+    fn array(&mut self, items: &[Value], rule: Rule, depth: usize) {
+        if items.is_empty() {
+            self.out.push_str("[]");
+            return;
+        }
+
+        if let Some(inline) = self.inline(items) {
+            self.out.push_str(&inline);
+            return;
+        }
+
+        self.out.push_str("[\n");
+        for (index, item) in items.iter().enumerate() {
+            self.indent(depth + 1);
+            self.value(item, rule.element(index), depth + 1);
+            self.separator(index, items.len());
+        }
+        self.indent(depth);
+        self.out.push(']');
+    }
+
+    fn map(&mut self, entries: &[(Value, Value)], rule: Rule, depth: usize) {
+        if entries.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push_str("{\n");
+        for (index, (key, value)) in entries.iter().enumerate() {
+            let label = integer_key(key);
+
+            self.indent(depth + 1);
+            if let Some(name) = label
+                .and_then(|label| rule.field(label))
+                .filter(|_| self.options.comments)
+            {
+                let _ = write!(self.out, "/ {name} / ");
+            }
+            self.value(key, Rule::Any, depth + 1);
+            self.out.push_str(": ");
+            self.value(
+                value,
+                label.map_or(Rule::Any, |label| rule.value(label)),
+                depth + 1,
+            );
+            self.separator(index, entries.len());
+        }
+        self.indent(depth);
+        self.out.push('}');
+    }
+
+    /// Renders an array of scalars on a single line, if it is short enough
+    fn inline(&self, items: &[Value]) -> Option<String> {
+        let scalar = |item: &Value| match item {
+            Value::Array(_) | Value::Map(_) | Value::Tag(..) => false,
+            Value::Bytes(bytes) => embedded_cbor(bytes).is_none(),
+            _ => true,
+        };
+        if !items.iter().all(scalar) {
+            return None;
+        }
+
+        let mut out = String::new();
+        let mut printer = Printer {
+            options: self.options,
+            out: &mut out,
+        };
+        printer.out.push('[');
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                printer.out.push_str(", ");
+            }
+            printer.value(item, Rule::Any, 0);
+        }
+        printer.out.push(']');
+
+        (out.len() <= MAX_INLINE_LEN).then_some(out)
+    }
+
+    fn indent(&mut self, depth: usize) {
+        let width = depth * self.options.indent;
+        let _ = write!(self.out, "{:width$}", "");
+    }
+
+    fn separator(&mut self, index: usize, len: usize) {
+        if index + 1 < len {
+            self.out.push(',');
+        }
+        self.out.push('\n');
+    }
+}
+
+/// Returns the integer value of a map key, accepting text encoded integers
+fn integer_key(key: &Value) -> Option<i128> {
+    match key {
+        Value::Integer(int) => Some(i128::from(*int)),
+        Value::Text(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Decodes a byte string holding exactly one embedded CBOR map, array or tag. Other byte
+/// strings, such as digests and keys, are rendered as hex even if they happen to be valid
+/// CBOR.
+fn embedded_cbor(bytes: &[u8]) -> Option<Value> {
+    if !matches!(bytes.first()? >> 5, 4..=6) {
+        return None;
+    }
+
+    let mut reader = bytes;
+    let value: Value = ciborium::from_reader(&mut reader).ok()?;
+    reader.is_empty().then_some(value)
+}
+
+/// Parses a single data item in diagnostic notation into its CBOR encoding
+///
+/// Indefinite-length arrays (`[_ ...]`), maps (`{_ ...}`) and strings (`(_ ...)`) are encoded
+/// as such, so the bytes match the example they were copied from. Comments may be written as
+/// `/ ... /` or from `#` to the end of the line.
+///
+/// The printer does not keep indefinite-length markers, so parsing its output gives back the
+/// same data items but not always the same bytes. Signatures over embedded payloads are only
+/// preserved if the payloads are written with definite lengths.
+pub fn to_cbor(edn: &str) -> Result<Vec<u8>> {
+    let mut parser = Parser { input: edn, pos: 0 };
+    let mut out = vec![];
+
+    parser.item(&mut out)?;
+    parser.skip_whitespace()?;
+    if parser.pos < edn.len() {
+        return Err(parser.error("unexpected input after the data item").into());
+    }
+
+    Ok(out)
+}
+
+/// Parses a value from diagnostic notation, e.g. a [`ConciseRimTypeChoice`] from an example
+/// of the CoRIM drafts
+pub fn from_str<T>(edn: &str) -> Result<T>
+where
+    T: Deserialize<'static>,
+{
+    let cbor = to_cbor(edn)?;
+    Ok(crate::cbor::from_slice_owned(&cbor).map_err(|e| EdnError::Decoding(e.to_string()))?)
+}
+
+/// The initial byte of the "break" stop code ending an indefinite-length item
+const BREAK: u8 = 0xff;
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, message: impl Into<String>) -> EdnError {
+        let before = &self.input[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+
+        EdnError::Syntax {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Skips whitespace and comments
+    fn skip_whitespace(&mut self) -> std::result::Result<(), EdnError> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            match self.peek() {
+                Some('/') => {
+                    let end = self.rest()[1..]
+                        .find('/')
+                        .ok_or_else(|| self.error("unterminated comment"))?;
+                    self.pos += end + 2;
+                }
+                Some('#') => {
+                    self.pos += self.rest().find('\n').unwrap_or(self.rest().len());
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Consumes `token`, after any whitespace, if it comes next
+    fn eat(&mut self, token: &str) -> std::result::Result<bool, EdnError> {
+        self.skip_whitespace()?;
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> std::result::Result<(), EdnError> {
+        if self.eat(token)? {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{token}`")))
+        }
+    }
+
+    fn item(&mut self, out: &mut Vec<u8>) -> std::result::Result<(), EdnError> {
+        self.skip_whitespace()?;
+
+        match self.peek() {
+            None => Err(self.error("unexpected end of input")),
+            Some('[') => {
+                self.pos += 1;
+                self.container(out, 4, "]", |parser, out| parser.item(out))
+            }
+            Some('{') => {
+                self.pos += 1;
+                self.container(out, 5, "}", |parser, out| {
+                    parser.item(out)?;
+                    parser.expect(":")?;
+                    parser.item(out)
+                })
+            }
+            Some('"') => {
+                let text = self.text('"')?;
+                write_head(out, 3, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+                Ok(())
+            }
+            Some('\'') => {
+                let text = self.text('\'')?;
+                write_head(out, 2, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+                Ok(())
+            }
+            Some('<') if self.rest().starts_with("<<") => {
+                self.pos += 2;
+                let mut embedded = vec![];
+                if !self.eat(">>")? {
+                    loop {
+                        self.item(&mut embedded)?;
+                        if self.eat(">>")? {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                write_head(out, 2, embedded.len() as u64);
+                out.extend_from_slice(&embedded);
+                Ok(())
+            }
+            Some('(') => {
+                self.pos += 1;
+                self.expect("_")?;
+                self.indefinite_string(out)
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' => self.number(out),
+            Some(c) if c.is_ascii_alphabetic() => self.word(out),
+            Some(c) => Err(self.error(format!("unexpected character `{c}`"))),
+        }
+    }
+
+    /// Parses the items of an array or map up to `close`, the opening bracket having been
+    /// consumed
+    fn container(
+        &mut self,
+        out: &mut Vec<u8>,
+        major: u8,
+        close: &str,
+        mut entry: impl FnMut(&mut Self, &mut Vec<u8>) -> std::result::Result<(), EdnError>,
+    ) -> std::result::Result<(), EdnError> {
+        let indefinite = self.eat("_")?;
+        let mut items = vec![];
+        let mut count = 0;
+
+        while !self.eat(close)? {
+            if count > 0 {
+                self.expect(",")?;
+                // A trailing comma is accepted
+                if self.eat(close)? {
+                    break;
+                }
+            }
+            entry(self, &mut items)?;
+            count += 1;
+        }
+
+        if indefinite {
+            out.push(major << 5 | 31);
+            out.extend_from_slice(&items);
+            out.push(BREAK);
+        } else {
+            write_head(out, major, count);
+            out.extend_from_slice(&items);
+        }
+        Ok(())
+    }
+
+    /// Parses the chunks of an indefinite-length string, `(_` having been consumed
+    fn indefinite_string(&mut self, out: &mut Vec<u8>) -> std::result::Result<(), EdnError> {
+        let mut chunks = vec![];
+        let mut major = None;
+
+        while !self.eat(")")? {
+            if major.is_some() {
+                self.expect(",")?;
+            }
+
+            let start = chunks.len();
+            self.item(&mut chunks)?;
+            let chunk_major = chunks[start] >> 5;
+            if !matches!(chunk_major, 2 | 3) || major.is_some_and(|major| major != chunk_major) {
+                return Err(self.error(
+                    "the chunks of an indefinite-length string must all be byte strings or \
+                     all be text strings",
+                ));
+            }
+            major = Some(chunk_major);
+        }
+
+        out.push(major.unwrap_or(2) << 5 | 31);
+        out.extend_from_slice(&chunks);
+        out.push(BREAK);
+        Ok(())
+    }
+
+    /// Parses a quoted string, unescaping JSON style escape sequences
+    fn text(&mut self, quote: char) -> std::result::Result<String, EdnError> {
+        self.pos += 1;
+        let mut text = String::new();
+        let mut chars = self.rest().char_indices();
+
+        loop {
+            let Some((offset, c)) = chars.next() else {
+                return Err(self.error("unterminated string"));
+            };
+
+            match c {
+                c if c == quote => {
+                    self.pos += offset + 1;
+                    return Ok(text);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, c)| c) {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let hex = |chars: &mut std::str::CharIndices| {
+                                let digits = chars.by_ref().take(4).map(|(_, c)| c);
+                                u32::from_str_radix(&digits.collect::<String>(), 16).ok()
+                            };
+                            let mut code = hex(&mut chars);
+                            // A surrogate pair encodes a character outside the BMP
+                            if let Some(high @ 0xd800..=0xdbff) = code {
+                                let low = chars
+                                    .next()
+                                    .zip(chars.next())
+                                    .filter(|(a, b)| a.1 == '\\' && b.1 == 'u')
+                                    .and_then(|_| hex(&mut chars));
+                                code =
+                                    low.map(|low| 0x10000 + ((high - 0xd800) << 10) + low - 0xdc00);
+                            }
+                            code.and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid unicode escape"))?
+                        }
+                        Some(c) => c,
+                        None => return Err(self.error("unterminated string")),
+                    };
+                    text.push(escaped);
+                }
+                c => text.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self, out: &mut Vec<u8>) -> std::result::Result<(), EdnError> {
+        let token_len = self
+            .rest()
+            .char_indices()
+            .find(|&(index, c)| {
+                !(c.is_ascii_alphanumeric()
+                    || c == '.'
+                    || (index == 0 && (c == '-' || c == '+'))
+                    || ((c == '-' || c == '+') && self.rest()[..index].ends_with(['e', 'E'])))
+            })
+            .map_or(self.rest().len(), |(index, _)| index);
+        let token = self.rest()[..token_len].to_string();
+        let (negative, digits) = match token.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, token.strip_prefix('+').unwrap_or(&token)),
+        };
+
+        if digits == "Infinity" {
+            self.pos += token_len;
+            write_float(
+                out,
+                if negative {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                },
+            );
+            return Ok(());
+        }
+
+        let radix = match digits.get(..2) {
+            Some("0x" | "0X") => Some(16),
+            Some("0o" | "0O") => Some(8),
+            Some("0b" | "0B") => Some(2),
+            _ => None,
+        };
+
+        let is_float = radix.is_none() && digits.contains(['.', 'e', 'E']);
+        if is_float {
+            let float = token
+                .parse::<f64>()
+                .map_err(|_| self.error(format!("invalid number `{token}`")))?;
+            self.pos += token_len;
+            write_float(out, float);
+            return Ok(());
+        }
+
+        let magnitude = match radix {
+            Some(radix) => u128::from_str_radix(&digits[2..], radix),
+            None => digits.parse::<u128>(),
+        }
+        .map_err(|_| self.error(format!("invalid number `{token}`")))?;
+        self.pos += token_len;
+
+        if self.eat("(")? {
+            let tag = u64::try_from(magnitude)
+                .ok()
+                .filter(|_| !negative)
+                .ok_or_else(|| self.error(format!("invalid tag number `{token}`")))?;
+            write_head(out, 6, tag);
+            self.item(out)?;
+            return self.expect(")");
+        }
+
+        let (major, argument) = if negative {
+            (1, magnitude.checked_sub(1))
+        } else {
+            (0, Some(magnitude))
+        };
+        let argument = argument
+            .and_then(|argument| u64::try_from(argument).ok())
+            .ok_or_else(|| self.error(format!("integer `{token}` is out of range")))?;
+        write_head(out, major, argument);
+        Ok(())
+    }
+
+    /// Parses a keyword, or a prefixed byte string such as `h'...'` or `b64'...'`
+    fn word(&mut self, out: &mut Vec<u8>) -> std::result::Result<(), EdnError> {
+        let len = self
+            .rest()
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(self.rest().len());
+        let word = &self.rest()[..len];
+
+        if self.rest()[len..].starts_with('\'') {
+            let prefix = word.to_string();
+            self.pos += len;
+            let start = self.pos;
+            let content = self.text('\'')?;
+            let bytes = match prefix.as_str() {
+                "h" => decode_hex(&strip_comments(&content)),
+                "b64" => decode_base64(&content.split_whitespace().collect::<String>()),
+                _ => {
+                    self.pos = start - len;
+                    return Err(self.error(format!("unsupported string prefix `{prefix}`")));
+                }
+            }
+            .ok_or_else(|| {
+                self.pos = start;
+                self.error(format!("invalid {prefix} string"))
+            })?;
+
+            write_head(out, 2, bytes.len() as u64);
+            out.extend_from_slice(&bytes);
+            return Ok(());
+        }
+
+        match word {
+            "false" => out.push(0xf4),
+            "true" => out.push(0xf5),
+            "null" => out.push(0xf6),
+            "undefined" => out.push(0xf7),
+            "NaN" => write_float(out, f64::NAN),
+            "Infinity" => write_float(out, f64::INFINITY),
+            "simple" => {
+                self.pos += len;
+                self.expect("(")?;
+                self.skip_whitespace()?;
+                let digits = self
+                    .rest()
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(self.rest().len());
+                let value = self.rest()[..digits]
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|value| !(24..32).contains(value))
+                    .ok_or_else(|| self.error("invalid simple value"))?;
+                self.pos += digits;
+                self.expect(")")?;
+                write_head(out, 7, value.into());
+                return Ok(());
+            }
+            _ => return Err(self.error(format!("unexpected `{word}`"))),
+        }
+
+        self.pos += len;
+        Ok(())
+    }
+}
+
+/// Writes the initial bytes of a data item of major type `major`, using the shortest
+/// encoding of `argument`
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+
+    match argument {
+        0..=23 => out.push(major | argument as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, argument as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+/// Writes a float in the shortest form that preserves its value
+fn write_float(out: &mut Vec<u8>, float: f64) {
+    // Encoding into a vector cannot fail
+    let _ = ciborium::into_writer(&Value::Float(float), &mut *out);
+}
+
+/// Removes whitespace and `/ ... /` comments, which may appear within `h'...'` strings
+fn strip_comments(text: &str) -> String {
+    text.split('/')
+        .step_by(2)
+        .flat_map(str::chars)
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+
+    [STANDARD_NO_PAD, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(text).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNED: &[u8] = include_bytes!("../testdata/corim/signed.cbor");
+
+    fn render(value: Value) -> String {
+        to_string(&value, Rule::Any, &EdnOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_edn_scalars() {
+        assert_eq!(render(Value::Integer((-7).into())), "-7");
+        assert_eq!(render(Value::Bytes(vec![0x01, 0xab])), "h'01ab'");
+        assert_eq!(render(Value::Text("a \"b\"\n".into())), r#""a \"b\"\n""#);
+        assert_eq!(render(Value::Float(1.0)), "1.0");
+        assert_eq!(render(Value::Float(f64::NAN)), "NaN");
+        assert_eq!(render(Value::Bool(true)), "true");
+        assert_eq!(render(Value::Null), "null");
+        assert_eq!(
+            render(Value::Tag(37, Box::new(Value::Bytes(vec![0; 2])))),
+            "37(h'0000')"
+        );
+        assert_eq!(render(Value::Array(vec![])), "[]");
+        assert_eq!(render(Value::Map(vec![])), "{}");
+        assert_eq!(
+            render(Value::Array(vec![
+                Value::Integer(1.into()),
+                Value::Bytes(vec![2])
+            ])),
+            "[1, h'02']"
+        );
+    }
+
+    #[test]
+    fn test_edn_embedded_cbor() {
+        let mut embedded = vec![];
+        ciborium::into_writer(&Value::Map(vec![(1.into(), (-7).into())]), &mut embedded).unwrap();
+
+        let value = Value::Array(vec![Value::Bytes(embedded), Value::Bytes(vec![0xa0, 0x00])]);
+        assert_eq!(
+            to_string(&value, Rule::CoseSignature, &EdnOptions::default()).unwrap(),
+            "[\n  <<{\n    / alg / 1: -7\n  }>>,\n  h'a000'\n]"
+        );
+    }
+
+    #[test]
+    fn test_edn_signed_corim() {
+        let edn = from_cbor(SIGNED, Rule::Any, &EdnOptions::default()).unwrap();
+
+        assert!(edn.starts_with("18([\n  <<{\n    / alg / 1: -7,"), "{edn}");
+        assert!(edn.contains("  <<501({\n    / id / \"0\": \"acme-roadrunner-corim\","));
+        assert!(edn.contains("      506({\n        / tag-identity / \"1\": {"));
+        assert!(edn.contains("/ class-id / 0: 111(h'6086480186f84d010203'),"));
+        assert!(edn.contains("/ svn / 1: 3,"));
+        assert!(edn.contains("/ software-name / \"1\": \"RoadRunner Firmware\","));
+
+        let corim = crate::cbor::from_slice::<ConciseRimTypeChoice>(SIGNED).unwrap();
+        assert_eq!(corim.to_edn().unwrap(), edn);
+    }
+
+    #[test]
+    fn test_edn_options() {
+        let options = EdnOptions {
+            comments: false,
+            indent: 4,
+        };
+        let edn = from_cbor(SIGNED, Rule::Any, &options).unwrap();
+        assert!(!edn.contains("/ "));
+        assert!(edn.starts_with("18([\n    <<{\n        1: -7,"), "{edn}");
+
+        let mut trailing = SIGNED.to_vec();
+        trailing.push(0);
+        let err = from_cbor(&trailing, Rule::Any, &options).unwrap_err();
+        assert_eq!(err.to_string(), "1 trailing bytes after the CBOR data item");
+    }
+
+    #[test]
+    fn test_edn_parse_scalars() {
+        let cbor = |edn| to_cbor(edn).unwrap();
+
+        assert_eq!(cbor("0"), [0x00]);
+        assert_eq!(cbor("-1"), [0x20]);
+        assert_eq!(cbor("1000"), [0x19, 0x03, 0xe8]);
+        assert_eq!(cbor("0x10"), [0x10]);
+        assert_eq!(
+            cbor("-18446744073709551616"),
+            [0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+        assert_eq!(cbor("1.5"), [0xf9, 0x3e, 0x00]);
+        assert_eq!(cbor("-Infinity"), [0xf9, 0xfc, 0x00]);
+        assert_eq!(
+            cbor("[true, false, null, undefined, simple(16)]"),
+            [0x85, 0xf5, 0xf4, 0xf6, 0xf7, 0xf0]
+        );
+        assert_eq!(
+            cbor(r#""a\u00e9\ud83d\ude00""#),
+            [0x67, b'a', 0xc3, 0xa9, 0xf0, 0x9f, 0x98, 0x80]
+        );
+        assert_eq!(cbor("'ab'"), [0x42, b'a', b'b']);
+        assert_eq!(cbor("37(h'0001')"), [0xd8, 0x25, 0x42, 0x00, 0x01]);
+        assert_eq!(
+            cbor("{1: -7, \"a\": [],}"),
+            [0xa2, 0x01, 0x26, 0x61, b'a', 0x80]
+        );
+    }
+
+    #[test]
+    fn test_edn_parse_byte_strings() {
+        assert_eq!(
+            to_cbor("h'01 AB / comment / ff'").unwrap(),
+            [0x43, 0x01, 0xab, 0xff]
+        );
+        assert_eq!(to_cbor("b64'AQL_'").unwrap(), [0x43, 0x01, 0x02, 0xff]);
+        assert_eq!(to_cbor("b64'AQL/'").unwrap(), [0x43, 0x01, 0x02, 0xff]);
+        assert_eq!(to_cbor("b64'AQ=='").unwrap(), [0x41, 0x01]);
+        assert_eq!(to_cbor("<<1, \"a\">>").unwrap(), [0x43, 0x01, 0x61, b'a']);
+        assert_eq!(to_cbor("<<>>").unwrap(), [0x40]);
+    }
+
+    #[test]
+    fn test_edn_parse_indefinite_length() {
+        let edn = "[_ 1, # a comment\n {_ 2: (_ h'01', h'02')}, (_ \"a\", \"b\")]";
+        assert_eq!(
+            to_cbor(edn).unwrap(),
+            [
+                0x9f, 0x01, 0xbf, 0x02, 0x5f, 0x41, 0x01, 0x41, 0x02, 0xff, 0xff, 0x7f, 0x61, b'a',
+                0x61, b'b', 0xff, 0xff
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edn_parse_errors() {
+        let err = |edn| to_cbor(edn).unwrap_err().to_string();
+
+        assert_eq!(
+            err("[1,\n  2 3]"),
+            "invalid diagnostic notation at 2:5: expected `,`"
+        );
+        assert_eq!(
+            err("1 2"),
+            "invalid diagnostic notation at 1:3: unexpected input after the data item"
+        );
+        assert!(err("h'0'").contains("invalid h string"));
+        assert!(err("18446744073709551616").contains("out of range"));
+        assert!(err("(_ h'01', \"a\")").contains("indefinite-length string"));
+        assert!(err("\"abc").contains("unterminated string"));
+    }
+
+    #[test]
+    fn test_edn_parse_round_trip() {
+        let edn = from_cbor(SIGNED, Rule::Any, &EdnOptions::default()).unwrap();
+        // The printer writes indefinite-length maps with definite lengths, so the bytes differ
+        let cbor = to_cbor(&edn).unwrap();
+        assert_eq!(
+            from_cbor(&cbor, Rule::Any, &EdnOptions::default()).unwrap(),
+            edn
+        );
+
+        let corim = from_str::<ConciseRimTypeChoice>(&edn).unwrap();
+        assert_eq!(corim.to_edn().unwrap(), edn);
+
+        let validity = from_str::<ValidityMap>(
+            "{ / not-before / \"0\": 1704067200, / not-after / \"1\": 1893456000 }",
+        )
+        .unwrap();
+        assert!(validity.contains(1800000000));
+        assert!(from_str::<ValidityMap>("[]").is_err());
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum AppraisalError {
+    DetachedPayload(String),
+    InvalidEvidence(String),
+    Unknown,
+}
+
+impl core::error::Error for AppraisalError {}
+
+impl core::fmt::Display for AppraisalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::DetachedPayload(name) => {
+                write!(f, "{name}: a detached signed CoRIM carries no payload")
+            }
+            Self::InvalidEvidence(msg) => write!(f, "invalid evidence: {msg}"),
+            Self::Unknown => write!(f, "unknown AppraisalError encountered"),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::{String, ToString};
+
+#[derive(Debug)]
+pub enum CborError {
+    Io(String),
+    Syntax(usize),
+    Truncated(usize),
+    Semantic {
+        offset: Option<usize>,
+        message: String,
+    },
+    RecursionLimitExceeded,
+    Unknown,
+}
+
+impl CborError {
+    pub(crate) fn semantic(offset: usize, message: impl Into<String>) -> Self {
+        Self::Semantic {
+            offset: Some(offset),
+            message: message.into(),
+        }
+    }
+}
+
+impl core::error::Error for CborError {}
+
+impl core::fmt::Display for CborError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(msg) => write!(f, "failed to read CBOR: {msg}"),
+            Self::Syntax(offset) => write!(f, "invalid CBOR at byte {offset}"),
+            Self::Truncated(offset) => {
+                write!(f, "CBOR input ends within the item at byte {offset}")
+            }
+            Self::Semantic {
+                offset: Some(offset),
+                message,
+            } => write!(f, "{message} at byte {offset}"),
+            Self::Semantic {
+                offset: None,
+                message,
+            } => write!(f, "{message}"),
+            Self::RecursionLimitExceeded => write!(f, "CBOR items are nested too deeply"),
+            Self::Unknown => write!(f, "unknown CborError encountered"),
+        }
+    }
+}
+
+impl serde::de::Error for CborError {
+    fn custom<T: core::fmt::Display>(msg: T) -> Self {
+        Self::Semantic {
+            offset: None,
+            message: msg.to_string(),
+        }
+    }
+}
@@ -1,19 +1,25 @@
 // SPDX-License-Identifier: MIT
 
+use alloc::string::String;
+
 #[derive(Debug)]
 pub enum ComidError {
     EmptyTriplesMap,
+    InvalidRole(String),
+    InvalidTagRel(String),
     Unknown,
 }
 
-impl std::error::Error for ComidError {}
+impl core::error::Error for ComidError {}
 
-impl std::fmt::Display for ComidError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ComidError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::EmptyTriplesMap => {
                 write!(f, "a TriplesMap must have at least one non-empty field")
             }
+            Self::InvalidRole(role) => write!(f, "invalid CoMID role \"{role}\""),
+            Self::InvalidTagRel(rel) => write!(f, "invalid tag relation \"{rel}\""),
             Self::Unknown => write!(f, "unknown ComidError encountered"),
         }
     }
@@ -1,15 +1,17 @@
 // SPDX-License-Identifier: MIT
 
+use alloc::string::String;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum CoreError {
     InvalidValue(String),
     Unknown,
 }
 
-impl std::error::Error for CoreError {}
+impl core::error::Error for CoreError {}
 
-impl std::fmt::Display for CoreError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for CoreError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidValue(s) => write!(f, "invalid value: {s}"),
             Self::Unknown => write!(f, "unknown CoreError encountered"),
@@ -1,19 +1,27 @@
 // SPDX-License-Identifier: MIT
 
+use alloc::string::String;
+
 #[derive(Debug)]
 pub enum CorimError {
     InvalidConciseTagTypeChoice,
+    InvalidRole(String),
+    Encoding(String),
+    Decoding(String),
     Unknown,
 }
 
-impl std::error::Error for CorimError {}
+impl core::error::Error for CorimError {}
 
-impl std::fmt::Display for CorimError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for CorimError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidConciseTagTypeChoice => {
                 write!(f, "Invalid ConciseTagTypeChoice encountered")
             }
+            Self::InvalidRole(role) => write!(f, "invalid CoRIM role \"{role}\""),
+            Self::Encoding(e) => write!(f, "failed to encode CBOR: {e}"),
+            Self::Decoding(e) => write!(f, "failed to decode CBOR: {e}"),
             Self::Unknown => write!(f, "unknown CorimError encountered"),
         }
     }
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+use crate::corim::ValidityMap;
+
+#[derive(Debug)]
+pub enum CoseError {
+    UnsupportedAlgorithm(i64),
+    AlgorithmMismatch {
+        expected: i64,
+        found: i64,
+    },
+    InvalidKey(String),
+    InvalidSignature,
+    InvalidHeader(String),
+    Encoding(String),
+    SignatureNotFound(usize),
+    PolicyNotSatisfied(String),
+    NotSigned,
+    MissingPayload,
+    SignatureNotValidAt {
+        index: usize,
+        time: u64,
+        validity: Option<ValidityMap>,
+    },
+    Unknown,
+}
+
+impl core::error::Error for CoseError {}
+
+impl core::fmt::Display for CoseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedAlgorithm(alg) => {
+                write!(f, "unsupported COSE algorithm {alg}")
+            }
+            Self::AlgorithmMismatch { expected, found } => {
+                write!(
+                    f,
+                    "COSE algorithm mismatch: key uses {expected}, header specifies {found}"
+                )
+            }
+            Self::InvalidKey(msg) => write!(f, "invalid key: {msg}"),
+            Self::InvalidSignature => write!(f, "signature verification failed"),
+            Self::InvalidHeader(msg) => write!(f, "invalid COSE header: {msg}"),
+            Self::Encoding(msg) => write!(f, "COSE encoding error: {msg}"),
+            Self::SignatureNotFound(index) => write!(f, "no COSE signature at index {index}"),
+            Self::PolicyNotSatisfied(msg) => write!(f, "signer policy not satisfied: {msg}"),
+            Self::NotSigned => write!(f, "the CoRIM is not signed"),
+            Self::MissingPayload => {
+                write!(
+                    f,
+                    "the payload of a detached signed CoRIM is needed to verify it"
+                )
+            }
+            Self::SignatureNotValidAt {
+                index,
+                time,
+                validity,
+            } => {
+                write!(f, "the signature of signer[{index}] is not valid at {time}")?;
+                match validity {
+                    Some(ValidityMap {
+                        not_before: Some(not_before),
+                        not_after,
+                    }) => write!(f, " (signature validity {not_before}..{not_after})"),
+                    Some(ValidityMap { not_after, .. }) => {
+                        write!(f, " (signature validity ..{not_after})")
+                    }
+                    None => Ok(()),
+                }
+            }
+            Self::Unknown => write!(f, "unknown CoseError encountered"),
+        }
+    }
+}
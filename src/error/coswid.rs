@@ -5,10 +5,10 @@ pub enum CoswidError {
     Unknown,
 }
 
-impl std::error::Error for CoswidError {}
+impl core::error::Error for CoswidError {}
 
-impl std::fmt::Display for CoswidError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for CoswidError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Unknown => write!(f, "unknown CoswidError encountered"),
         }
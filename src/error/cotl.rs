@@ -5,10 +5,10 @@ pub enum CotlError {
     Unknown,
 }
 
-impl std::error::Error for CotlError {}
+impl core::error::Error for CotlError {}
 
-impl std::fmt::Display for CotlError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for CotlError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Unknown => write!(f, "unknown CotlError encountered"),
         }
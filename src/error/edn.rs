@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum EdnError {
+    Encoding(String),
+    Decoding(String),
+    TrailingBytes(usize),
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Unknown,
+}
+
+impl core::error::Error for EdnError {}
+
+impl core::fmt::Display for EdnError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Encoding(msg) => write!(f, "failed to encode value as CBOR: {msg}"),
+            Self::Decoding(msg) => write!(f, "failed to decode CBOR: {msg}"),
+            Self::TrailingBytes(count) => {
+                write!(f, "{count} trailing bytes after the CBOR data item")
+            }
+            Self::Syntax {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "invalid diagnostic notation at {line}:{column}: {message}"
+            ),
+            Self::Unknown => write!(f, "unknown EdnError encountered"),
+        }
+    }
+}
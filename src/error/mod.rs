@@ -1,47 +1,88 @@
 // SPDX-License-Identifier: MIT
 
+mod appraisal;
+mod cbor;
 mod comid;
 mod core;
 mod corim;
+mod cose;
 mod coswid;
 mod cotl;
+mod edn;
 mod numbers;
+mod sbom;
+mod stream;
+mod swid;
 mod triples;
+mod validation;
+mod veraison;
+mod x509;
 
+use alloc::string::String;
+pub use appraisal::*;
+pub use cbor::*;
 pub use comid::*;
 pub use core::*;
 pub use corim::*;
+pub use cose::*;
 pub use coswid::*;
 pub use cotl::*;
 use derive_more::From;
+pub use edn::*;
 pub use numbers::*;
+pub use sbom::*;
+pub use stream::*;
+pub use swid::*;
 pub use triples::*;
+pub use validation::*;
+pub use veraison::*;
+pub use x509::*;
 
 #[derive(Debug, From)]
 pub enum Error {
+    Appraisal(AppraisalError),
+    Cbor(CborError),
     Comid(ComidError),
     Core(CoreError),
     Corim(CorimError),
+    Cose(CoseError),
     Coswid(CoswidError),
     Cotl(CotlError),
+    Edn(EdnError),
     Triples(TriplesError),
+    Validation(ValidationError),
     Numbers(NumbersError),
+    Sbom(SbomError),
+    Stream(StreamError),
+    Swid(SwidError),
+    Veraison(VeraisonError),
+    X509(X509Error),
     Custom(String, String),
     Unknown,
 }
 
-impl std::error::Error for Error {}
+impl ::core::error::Error for Error {}
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ::core::fmt::Display for Error {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
         match &self {
+            Self::Appraisal(err) => write!(f, "{err}"),
+            Self::Cbor(err) => write!(f, "{err}"),
             Self::Comid(err) => write!(f, "{err}"),
             Self::Core(err) => write!(f, "{err}"),
             Self::Corim(err) => write!(f, "{err}"),
+            Self::Cose(err) => write!(f, "{err}"),
             Self::Coswid(err) => write!(f, "{err}"),
             Self::Cotl(err) => write!(f, "{err}"),
+            Self::Edn(err) => write!(f, "{err}"),
             Self::Triples(err) => write!(f, "{err}"),
+            Self::Validation(err) => write!(f, "{err}"),
             Self::Numbers(err) => write!(f, "{err}"),
+            Self::Sbom(err) => write!(f, "{err}"),
+            Self::Stream(err) => write!(f, "{err}"),
+            Self::Swid(err) => write!(f, "{err}"),
+            Self::Veraison(err) => write!(f, "{err}"),
+            Self::X509(err) => write!(f, "{err}"),
             Self::Unknown => write!(f, "unknown error encountered!"),
             Self::Custom(err, msg) => write!(f, "{} - {}", err, msg),
         }
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: MIT
 
+use alloc::string::String;
+
 #[derive(Debug)]
 pub enum NumbersError {
     ValueExceedsType,
@@ -10,10 +12,10 @@ pub enum NumbersError {
     Unknown,
 }
 
-impl std::error::Error for NumbersError {}
+impl core::error::Error for NumbersError {}
 
-impl std::fmt::Display for NumbersError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for NumbersError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::ValueExceedsType => write!(f, "value exceeds expected type"),
             Self::NegativeAsUsize => write!(f, "Cannot use negative value as usize"),
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum SbomError {
+    Json(String),
+    InvalidValue {
+        field: &'static str,
+        message: String,
+    },
+    Unknown,
+}
+
+impl core::error::Error for SbomError {}
+
+impl core::fmt::Display for SbomError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Json(msg) => write!(f, "invalid SBOM JSON: {msg}"),
+            Self::InvalidValue { field, message } => {
+                write!(f, "invalid {field} in SBOM: {message}")
+            }
+            Self::Unknown => write!(f, "unknown SbomError encountered"),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(String),
+    Unexpected {
+        offset: usize,
+        expected: &'static str,
+    },
+    MissingTags,
+    DetachedPayload,
+    InvalidId(String),
+    Tag {
+        index: usize,
+        message: String,
+    },
+    Unknown,
+}
+
+impl core::error::Error for StreamError {}
+
+impl core::fmt::Display for StreamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(msg) => write!(f, "failed to read CoRIM: {msg}"),
+            Self::Unexpected { offset, expected } => {
+                write!(f, "expected {expected} at byte {offset} of the CoRIM")
+            }
+            Self::MissingTags => write!(f, "the CoRIM has no tags entry"),
+            Self::DetachedPayload => write!(
+                f,
+                "the payload of the signed CoRIM is detached and must be read on its own"
+            ),
+            Self::InvalidId(msg) => write!(f, "invalid CoRIM id: {msg}"),
+            Self::Tag { index, message } => {
+                write!(f, "failed to decode tag {index} of the CoRIM: {message}")
+            }
+            Self::Unknown => write!(f, "unknown StreamError encountered"),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum SwidError {
+    Xml(String),
+    InvalidValue {
+        field: &'static str,
+        message: String,
+    },
+    Unmappable(String),
+    Unknown,
+}
+
+impl core::error::Error for SwidError {}
+
+impl core::fmt::Display for SwidError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Xml(msg) => write!(f, "invalid SWID XML: {msg}"),
+            Self::InvalidValue { field, message } => {
+                write!(f, "invalid {field} in SWID tag: {message}")
+            }
+            Self::Unmappable(what) => write!(
+                f,
+                "{what} cannot be converted between a CoSWID tag and SWID XML"
+            ),
+            Self::Unknown => write!(f, "unknown SwidError encountered"),
+        }
+    }
+}
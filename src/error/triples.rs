@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: MIT
 
+use alloc::string::String;
+
 use crate::HashAlgorithm;
 
 #[derive(Debug)]
@@ -11,13 +13,15 @@ pub enum TriplesError {
     InvalidIpAddrType,
     InvalidMacAddrType,
     DigestAlreadyExists(String, HashAlgorithm),
+    InvalidKeyEncoding(String),
+    ThumbprintUnsupported(String),
     Unknown,
 }
 
-impl std::error::Error for TriplesError {}
+impl core::error::Error for TriplesError {}
 
-impl std::fmt::Display for TriplesError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for TriplesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidIpAddrType => write!(f, "invalid IP address type"),
             Self::InvalidMacAddrType => write!(f, "invalid MAC address type"),
@@ -42,6 +46,10 @@ impl std::fmt::Display for TriplesError {
             Self::DigestAlreadyExists(label, alg) => {
                 write!(f, "{alg} digest for label {label} already exists")
             }
+            Self::InvalidKeyEncoding(msg) => write!(f, "invalid key encoding: {msg}"),
+            Self::ThumbprintUnsupported(what) => {
+                write!(f, "thumbprints are not supported for {what}")
+            }
             Self::Unknown => write!(f, "unknown TriplesError encountered"),
         }
     }
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::vec::Vec;
+
+use crate::validation::Violation;
+
+#[derive(Debug)]
+pub enum ValidationError {
+    Violations(Vec<Violation>),
+    Unknown,
+}
+
+impl ValidationError {
+    /// Returns the violations found, each with the path to the offending field
+    pub fn violations(&self) -> &[Violation] {
+        match self {
+            Self::Violations(violations) => violations,
+            Self::Unknown => &[],
+        }
+    }
+}
+
+impl core::error::Error for ValidationError {}
+
+impl core::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Violations(violations) => {
+                for (index, violation) in violations.iter().enumerate() {
+                    if index > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{violation}")?;
+                }
+                Ok(())
+            }
+            Self::Unknown => write!(f, "unknown ValidationError encountered"),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum VeraisonError {
+    Json(String),
+    InvalidValue {
+        field: &'static str,
+        message: String,
+    },
+    Unsupported(String),
+    Unknown,
+}
+
+impl core::error::Error for VeraisonError {}
+
+impl core::fmt::Display for VeraisonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Json(msg) => write!(f, "invalid Veraison template JSON: {msg}"),
+            Self::InvalidValue { field, message } => {
+                write!(f, "invalid {field} in Veraison template: {message}")
+            }
+            Self::Unsupported(what) => write!(
+                f,
+                "{what} cannot be converted to or from a Veraison template"
+            ),
+            Self::Unknown => write!(f, "unknown VeraisonError encountered"),
+        }
+    }
+}
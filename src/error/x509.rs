@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MIT
+
+use alloc::string::String;
+
+#[derive(Debug)]
+pub enum X509Error {
+    EmptyChain,
+    InvalidCertificate(String),
+    NotYetValid {
+        subject: String,
+    },
+    Expired {
+        subject: String,
+    },
+    IssuerMismatch {
+        subject: String,
+        issuer: String,
+    },
+    NotACa {
+        subject: String,
+    },
+    PathLenExceeded {
+        subject: String,
+    },
+    MissingKeyUsage {
+        subject: String,
+        usage: &'static str,
+    },
+    UnrecognizedCriticalExtension {
+        subject: String,
+        oid: String,
+    },
+    UnsupportedSignatureAlgorithm(String),
+    InvalidSignature {
+        subject: String,
+    },
+    UntrustedRoot {
+        issuer: String,
+    },
+    ThumbprintMismatch,
+    Unknown,
+}
+
+impl core::error::Error for X509Error {}
+
+impl core::fmt::Display for X509Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::EmptyChain => write!(f, "certificate chain is empty"),
+            Self::InvalidCertificate(msg) => write!(f, "invalid certificate: {msg}"),
+            Self::NotYetValid { subject } => {
+                write!(f, "certificate \"{subject}\" is not yet valid")
+            }
+            Self::Expired { subject } => write!(f, "certificate \"{subject}\" has expired"),
+            Self::IssuerMismatch { subject, issuer } => {
+                write!(
+                    f,
+                    "certificate \"{subject}\" was not issued by the next certificate in the chain (\"{issuer}\")"
+                )
+            }
+            Self::NotACa { subject } => {
+                write!(f, "certificate \"{subject}\" is not a CA certificate")
+            }
+            Self::PathLenExceeded { subject } => {
+                write!(f, "path length constraint of \"{subject}\" exceeded")
+            }
+            Self::MissingKeyUsage { subject, usage } => {
+                write!(f, "certificate \"{subject}\" does not allow {usage}")
+            }
+            Self::UnrecognizedCriticalExtension { subject, oid } => {
+                write!(
+                    f,
+                    "certificate \"{subject}\" has an unrecognized critical extension {oid}"
+                )
+            }
+            Self::UnsupportedSignatureAlgorithm(oid) => {
+                write!(f, "unsupported certificate signature algorithm {oid}")
+            }
+            Self::InvalidSignature { subject } => {
+                write!(f, "signature on certificate \"{subject}\" is invalid")
+            }
+            Self::UntrustedRoot { issuer } => {
+                write!(f, "no trust anchor found for issuer \"{issuer}\"")
+            }
+            Self::ThumbprintMismatch => {
+                write!(f, "x5t thumbprint does not match the leaf certificate")
+            }
+            Self::Unknown => write!(f, "unknown X509Error encountered"),
+        }
+    }
+}
@@ -32,7 +32,9 @@
 //! - Implements common traits like `Deref`, `AsRef`, etc.
 //! - Efficient zero-copy deserialization when possible
 //! - Clear error messages for size mismatches
-use std::{
+use alloc::{format, vec::Vec};
+
+use core::{
     marker::PhantomData,
     ops::{Deref, DerefMut, Index, IndexMut},
 };
@@ -49,7 +51,7 @@ struct FixedBytesVisitor<'de, const N: usize>(PhantomData<&'de [u8; N]>);
 impl<'de, const N: usize> Visitor<'de> for FixedBytesVisitor<'de, N> {
     type Value = FixedBytes<N>;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(formatter, "a byte array of length {}", N)
     }
 
@@ -110,8 +112,8 @@ impl<'de, const N: usize> Deserialize<'de> for FixedBytes<N> {
     }
 }
 
-impl<const N: usize> std::fmt::Debug for FixedBytes<N> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<const N: usize> core::fmt::Debug for FixedBytes<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "FixedBytes<{}>{:02x?}", N, &self.0[..])
     }
 }
@@ -221,7 +223,7 @@ mod tests {
         let expected = FixedBytes([1, 2, 3, 4, 5]);
         let serialized_bytes: [u8; 6] = [0x45, 0x01, 0x02, 0x03, 0x04, 0x05];
         let deserialized_bytes: FixedBytes<5> =
-            ciborium::from_reader(serialized_bytes.as_slice()).unwrap();
+            crate::cbor::from_slice(serialized_bytes.as_slice()).unwrap();
         assert_eq!(deserialized_bytes, expected);
     }
 }
@@ -13,6 +13,30 @@
 //! The implementation follows the CoRIM specification and uses CBOR for serialization.
 //!
 //! Anywhere a `Vec<T>` is used in this implementation of the CoRIM Specificaiton should never be empty.
+//!
+//! # `no_std`
+//!
+//! The default `std` feature enables the modules that need the standard library. Without it the
+//! crate is `no_std` and needs only `alloc`: the CoRIM, CoMID, CoSWID and CoTL types, their CBOR
+//! and JSON encodings and the hashing in [`core::Hasher`] remain available, and CBOR is decoded
+//! from a byte slice with [`cbor::from_slice`].
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+// Items used by the exported macros, so that they expand in crates without `extern crate alloc`
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::String;
+}
+
+/// Appraisal of evidence against the reference values of CoRIMs
+#[cfg(feature = "std")]
+pub mod appraisal;
+
+/// CBOR decoding that borrows text and byte strings from its input
+pub mod cbor;
 
 /// Module containing CoMID tag types and structures
 pub mod comid;
@@ -23,15 +47,61 @@ pub mod core;
 /// CoRIM manifest types and structures
 pub mod corim;
 
+/// COSE signing and verification for signed CoRIMs
+#[cfg(feature = "std")]
+pub mod cose;
+
 /// CoSWID tag types and structures
 pub mod coswid;
 
+/// Compact, single-line descriptions of CoRIM values for reports
+#[cfg(feature = "std")]
+pub mod describe;
+
+/// Structural comparison of CoRIMs and CoMID tags
+#[cfg(feature = "std")]
+pub mod diff;
+
+/// CBOR Extended Diagnostic Notation printing for CoRIM types
+#[cfg(feature = "std")]
+pub mod edn;
+
+/// Veraison compatible JSON templates for CoMIDs, CoTLs and CoRIMs
+#[cfg(feature = "std")]
+pub mod veraison;
+
+/// ISO/IEC 19770-2 SWID XML conversion for CoSWID tags
+#[cfg(feature = "std")]
+pub mod swid;
+
+/// Conversion between CoSWID and CoMID tags and SPDX or CycloneDX software bills of materials
+#[cfg(feature = "std")]
+pub mod sbom;
+
+/// JSON Schemas for the JSON representation of CoRIMs, CoMIDs, CoSWIDs and CoTLs
+#[cfg(feature = "schema")]
+pub mod schema;
+
+/// Streaming decoding of the tags of a CoRIM from an `io::Read`
+#[cfg(feature = "std")]
+pub mod stream;
+
+/// Conversion of values that borrow text into values that own all of their data
+pub mod owned;
+
 /// CoTL (Trust List) types and structures
 pub mod cotl;
 
 /// Triple types used in CoMID tags
 pub mod triples;
 
+/// Validation of CoRIMs and tags against the rules the builders enforce
+pub mod validation;
+
+/// X.509 certificate chain validation for CoRIM signers
+#[cfg(feature = "std")]
+pub mod x509;
+
 /// Fixed Bytes Arrays
 pub mod fixed_bytes;
 
@@ -79,139 +79,155 @@ macro_rules! generate_tagged {
     // Combined pattern that handles both with and without lifetime parameters
     ($(($tag_num:expr, $title:ident, $type:ty $(, $($lt:lifetime),* )?, $name:literal, $doc_comments:literal)),* $(,)?) => {
         $(
-            #[doc = $doc_comments]
-            #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
-            #[repr(C)]
-            pub struct $title $(< $($lt),* >)? (pub ::ciborium::tag::Accepted<$type, $tag_num>);
-
-            impl $(< $($lt),* >)? $title $(< $($lt),* >)? {
-                /// Creates a new wrapped instance from the provided value
-                #[inline]
-                pub const fn new(value: $type) -> Self {
-                    Self (::ciborium::tag::Accepted(value))
-                }
+            $crate::generate_tagged!(@impl $tag_num, $title, $type, $type, [$($($lt),*)?], $name, $doc_comments);
+        )*
+    };
+    // Tagged text, which borrows from the input where the deserializer lends it out
+    (@text $(($tag_num:expr, $title:ident, $type:ty, $lt:lifetime, $name:literal, $doc_comments:literal)),* $(,)?) => {
+        $(
+            $crate::generate_tagged!(@impl $tag_num, $title, $type, $crate::core::BorrowText<$lt>, [$lt], $name, $doc_comments);
+        )*
+    };
+    // `$de_type` is the type the value is deserialized as before it is converted into `$type`
+    (@impl $tag_num:expr, $title:ident, $type:ty, $de_type:ty, [$($lt:lifetime),*], $name:literal, $doc_comments:literal) => {
+        #[doc = $doc_comments]
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+        #[repr(C)]
+        pub struct $title<$($lt),*> (pub ::ciborium::tag::Accepted<$type, $tag_num>);
+
+        impl<$($lt),*> $title<$($lt),*> {
+            /// Creates a new wrapped instance from the provided value
+            #[inline]
+            pub const fn new(value: $type) -> Self {
+                Self (::ciborium::tag::Accepted(value))
             }
+        }
 
-            impl $(< $($lt),* >)? std::convert::AsRef<$type> for $title $(< $($lt),* >)? {
-                fn as_ref(&self) -> &$type {
-                    &self.0.0
-                }
+        impl<$($lt),*> ::core::convert::AsRef<$type> for $title<$($lt),*> {
+            fn as_ref(&self) -> &$type {
+                &self.0.0
             }
+        }
 
-            impl $(< $($lt),* >)? std::convert::AsMut<$type> for $title $(< $($lt),* >)? {
-                fn as_mut(&mut self) -> &mut $type {
-                    &mut self.0.0
-                }
+        impl<$($lt),*> ::core::convert::AsMut<$type> for $title<$($lt),*> {
+            fn as_mut(&mut self) -> &mut $type {
+                &mut self.0.0
             }
+        }
 
-            impl $(< $($lt),* >)? std::ops::Deref for $title $(< $($lt),* >)? {
-                type Target = $type;
+        impl<$($lt),*> ::core::ops::Deref for $title<$($lt),*> {
+            type Target = $type;
 
-                fn deref(&self) -> &Self::Target {
-                    &self.0.0
-                }
+            fn deref(&self) -> &Self::Target {
+                &self.0.0
             }
+        }
 
-            impl $(< $($lt),* >)? std::ops::DerefMut for $title $(< $($lt),* >)? {
-                fn deref_mut(&mut self) -> &mut Self::Target {
-                    &mut self.0.0
-                }
+        impl<$($lt),*> ::core::ops::DerefMut for $title<$($lt),*> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0.0
             }
+        }
 
-            impl $(< $($lt),* >)? std::convert::From<$type> for $title $(< $($lt),* >)? {
-                fn from(value: $type) -> Self {
-                    Self::new(value)
-                }
+        impl<$($lt),*> ::core::convert::From<$type> for $title<$($lt),*> {
+            fn from(value: $type) -> Self {
+                Self::new(value)
             }
+        }
 
-            impl $(< $($lt),* >)? ::serde::ser::Serialize for $title $(< $($lt),* >)? {
-                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
-                where
-                    S: serde::Serializer,
-                {
-                    if serializer.is_human_readable() {
-                        let mut state = serializer.serialize_map(Some(2))?;
-                        state.serialize_entry("type", $name)?;
-                        state.serialize_entry("value", &self.0.0)?;
-                        state.end()
-                    } else {
-                        self.0.serialize(serializer)
-                    }
+        impl<$($lt),*> ::serde::ser::Serialize for $title<$($lt),*> {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                if serializer.is_human_readable() {
+                    let mut state = serializer.serialize_map(Some(2))?;
+                    state.serialize_entry("type", $name)?;
+                    state.serialize_entry("value", &self.0.0)?;
+                    state.end()
+                } else {
+                    self.0.serialize(serializer)
                 }
             }
+        }
+
+        impl<'de, $($lt),*> ::serde::de::Deserialize<'de> for $title<$($lt),*>
+        where
+            $('de: $lt,)*
+        {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<$title<$($lt),*>, D::Error>
+            where
+                D: ::serde::de::Deserializer<'de>,
+            {
+                struct __Visitor<'de, $($lt),*> {
+                    marker: ::core::marker::PhantomData<$title<$($lt),*>>,
+                    lifetime: ::core::marker::PhantomData<&'de ()>,
+                }
 
-            impl < $( $($lt),* ,)? 'de> ::serde::de::Deserialize<'de> for $title $(< $($lt),* >)? {
-                fn deserialize<D>(deserializer: D) -> ::core::result::Result<$title $(< $($lt),* >)?  , D::Error>
+                impl<'de, $($lt),*> ::serde::de::Visitor<'de> for __Visitor<'de, $($lt),*>
                 where
-                    D: ::serde::de::Deserializer<'de>,
+                    $('de: $lt,)*
                 {
-                    struct __Visitor <'de, $( $($lt),* )?> {
-                        marker: std::marker::PhantomData< $title $(< $($lt),* >)? >,
-                        lifetime: std::marker::PhantomData<&'de () >,
-                    }
-
-                    impl<'de, $( $($lt),* ,)? > ::serde::de::Visitor<'de> for __Visitor<'de, $( $($lt),* )?> {
-                        type Value = $title $(< $($lt),* >)?;
+                    type Value = $title<$($lt),*>;
 
-                        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                            formatter.write_str(
-                                &format!("{} struct", stringify!(title)),
-                                )
-                        }
+                    fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        formatter.write_fmt(::core::format_args!("{} struct", stringify!(title)))
+                    }
 
-                        fn visit_map<A>(self, mut map: A) -> ::core::result::Result<Self::Value, A::Error>
-                        where
-                            A: ::serde::de::MapAccess<'de>
-                        {
-                            let mut ret = Err(::serde::de::Error::custom(format!("no \"value\" entry in map")));
-                            let mut seen_tag: bool = false;
-                            loop {
-                                match map.next_key::<&str>()? {
-                                    Some("type") => {
-                                        let typ: String = map.next_value()?;
-                                        if typ != $name {
-                                            return Err(::serde::de::Error::custom(format!(
-                                                        "expected type {}, found {}",
-                                                        $name,
-                                                        typ,
-                                                    )));
-                                        }
-
-                                        seen_tag = true;
-                                    },
-                                    Some("value") => {
-                                        ret = Ok($title::new(map.next_value::<$type>()?));
-                                    },
-                                    Some(s) => {
-                                        return Err(::serde::de::Error::custom(
-                                                format!("unexpected map entry: {}", s)
-                                                ));
-                                    },
-                                    None => break,
-                                }
+                    fn visit_map<A>(self, mut map: A) -> ::core::result::Result<Self::Value, A::Error>
+                    where
+                        A: ::serde::de::MapAccess<'de>
+                    {
+                        let mut ret = Err(::serde::de::Error::custom("no \"value\" entry in map"));
+                        let mut seen_tag: bool = false;
+                        loop {
+                            match map.next_key::<&str>()? {
+                                Some("type") => {
+                                    let typ: $crate::__private::String = map.next_value()?;
+                                    if typ != $name {
+                                        return Err(::serde::de::Error::custom(::core::format_args!(
+                                                    "expected type {}, found {}",
+                                                    $name,
+                                                    typ,
+                                                )));
+                                    }
+
+                                    seen_tag = true;
+                                },
+                                Some("value") => {
+                                    let value: $de_type = map.next_value()?;
+                                    ret = Ok($title::new(value.into()));
+                                },
+                                Some(s) => {
+                                    return Err(::serde::de::Error::custom(
+                                            ::core::format_args!("unexpected map entry: {}", s)
+                                            ));
+                                },
+                                None => break,
                             }
+                        }
 
-                            if seen_tag {
-                                ret
-                            } else {
-                                Err(::serde::de::Error::custom("no \"tag\" entry in map"))
-                            }
+                        if seen_tag {
+                            ret
+                        } else {
+                            Err(::serde::de::Error::custom("no \"type\" entry in map"))
                         }
                     }
+                }
 
-                    if deserializer.is_human_readable() {
-                        deserializer.deserialize_map(__Visitor
-                            {
-                                marker: std::marker::PhantomData,
-                                lifetime: std::marker::PhantomData,
-                            })
-                    } else {
-                        Ok($title(::ciborium::tag::Accepted::deserialize(deserializer)?))
-                    }
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_map(__Visitor
+                        {
+                            marker: ::core::marker::PhantomData,
+                            lifetime: ::core::marker::PhantomData,
+                        })
+                } else {
+                    let value: ::ciborium::tag::Accepted<$de_type, $tag_num> =
+                        ::serde::de::Deserialize::deserialize(deserializer)?;
+                    Ok($title::new(value.0.into()))
                 }
             }
-
-        )*
+        }
     };
 }
 
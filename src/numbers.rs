@@ -1,6 +1,11 @@
 // SPDX-License-Identifier: MIT
 
-use std::{
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use core::{
     cmp::Ordering,
     fmt::{Debug, Display},
     hash::Hash,
@@ -1482,7 +1487,7 @@ impl Integer {
     pub fn fits_into<T: IntegerType + 'static>(&self) -> bool {
         let val = self.0;
 
-        if std::any::TypeId::of::<T>() == std::any::TypeId::of::<u128>() {
+        if core::any::TypeId::of::<T>() == core::any::TypeId::of::<u128>() {
             if val < 0 {
                 return false;
             }
@@ -1650,13 +1655,13 @@ impl WrappedInteger for Integer {
 }
 
 impl Display for Integer {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
 impl Debug for Integer {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Integer({})", self.0)
     }
 }
@@ -1699,7 +1704,7 @@ impl FromStr for Integer {
 }
 
 impl Hash for Integer {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.0.hash(state);
     }
 }
@@ -1865,7 +1870,7 @@ impl<'de> serde::Deserialize<'de> for Integer {
         impl<'de> serde::de::Visitor<'de> for IntegerVisitor {
             type Value = Integer;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("an integer")
             }
 
@@ -2054,8 +2059,6 @@ impl<'de> serde::Deserialize<'de> for Integer {
 
 #[cfg(test)]
 mod tests {
-    use std::u32;
-
     use super::*;
 
     #[test]
@@ -2075,7 +2078,7 @@ mod tests {
         let expected = Integer(22);
 
         let serialized: Vec<u8> = vec![0x16]; // CBOR encoding for 22
-        let deserialized: Integer = ciborium::from_reader(serialized.as_slice()).unwrap();
+        let deserialized: Integer = crate::cbor::from_slice(serialized.as_slice()).unwrap();
         assert_eq!(deserialized, expected);
     }
 
@@ -2096,7 +2099,7 @@ mod tests {
         let expected = Integer(42);
 
         let serialized: Vec<u8> = vec![0x18, 0x2A]; // CBOR encoding for 42
-        let deserialized: Integer = ciborium::from_reader(serialized.as_slice()).unwrap();
+        let deserialized: Integer = crate::cbor::from_slice(serialized.as_slice()).unwrap();
         assert_eq!(deserialized, expected);
     }
 
@@ -2117,7 +2120,7 @@ mod tests {
         let expected = Integer(16502);
 
         let serialized: Vec<u8> = vec![0x19, 0x40, 0x76]; // CBOR encoding for 16502
-        let deserialized: Integer = ciborium::from_reader(serialized.as_slice()).unwrap();
+        let deserialized: Integer = crate::cbor::from_slice(serialized.as_slice()).unwrap();
         assert_eq!(deserialized, expected);
     }
 
@@ -2138,7 +2141,7 @@ mod tests {
         let expected = Integer(16777216);
 
         let serialized: Vec<u8> = vec![0x1A, 0x01, 0x00, 0x00, 0x00]; // CBOR encoding for 16777216
-        let deserialized: Integer = ciborium::from_reader(serialized.as_slice()).unwrap();
+        let deserialized: Integer = crate::cbor::from_slice(serialized.as_slice()).unwrap();
         assert_eq!(deserialized, expected);
     }
 
@@ -2168,7 +2171,7 @@ mod tests {
         let expected = Integer(4294967296);
 
         let serialized: Vec<u8> = vec![0x1B, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]; // CBOR encoding for 4294967296
-        let deserialized: Integer = ciborium::from_reader(serialized.as_slice()).unwrap();
+        let deserialized: Integer = crate::cbor::from_slice(serialized.as_slice()).unwrap();
         assert_eq!(deserialized, expected);
     }
 
@@ -0,0 +1,816 @@
+// SPDX-License-Identifier: MIT
+
+//! Conversion of values that borrow text into values that own all of their data.
+//!
+//! Text is stored as [`Text<'a>`](crate::core::Text), a `Cow<'a, str>`, so a tag built from
+//! borrowed strings is tied to the buffer they point into. [`IntoOwned::into_owned`] copies any
+//! borrowed text and returns the same value with a `'static` lifetime, for stores that outlive
+//! the input.
+//!
+//! Values decoded with [`cbor::from_slice`](crate::cbor::from_slice) borrow their text and byte
+//! strings from the input in the same way, and `into_owned` detaches them from it.
+//!
+//! # Example
+//!
+//! ```rust
+//! use corim_rs::{comid::TagIdTypeChoice, owned::IntoOwned};
+//!
+//! fn detach(id: &str) -> TagIdTypeChoice<'static> {
+//!     TagIdTypeChoice::Tstr(id.into()).into_owned()
+//! }
+//!
+//! let id = String::from("tag-1");
+//! let tag_id = detach(&id);
+//! drop(id);
+//! assert_eq!(tag_id.as_str(), Some("tag-1"));
+//! ```
+
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, vec::Vec};
+
+use crate::{
+    AttestKeyTripleRecord, AttributeValue, Bool, Bytes, COSESign1Corim, COSESignCorim,
+    COSESignatureCorim, CertPathThumbprintType, CertThumbprintType, ClassIdTypeChoice, ClassMap,
+    ComidEntityMap, ComidRoleTypeChoice, ConciseMidTag, ConciseRimTypeChoice, ConciseSwidTag,
+    ConciseTagTypeChoice, ConciseTlTag, ConditionalEndorsementSeriesTripleRecord,
+    ConditionalEndorsementTripleRecord, ConditionalSeriesRecord, CorimEntityMap, CorimIdTypeChoice,
+    CorimLocatorMap, CorimMap, CorimMapExtension, CorimMetaMap, CorimRoleTypeChoice,
+    CorimSignerMap, CoseAlgorithm, CoseEllipticCurve, CoseKey, CoseKeyOperation, CoseKeySetOrKey,
+    CoseKeyType, CoseKty, CoswidTripleRecord, CryptoKeyTypeChoice, DetachedCOSESign1Corim, Digest,
+    DirectoryEntry, DomainDependencyTripleRecord, DomainMembershipTripleRecord, DomainTypeChoice,
+    Encoded, EndorsedTripleRecord, EntityEntry, EnvironmentMap, Evidence, EvidenceEntry,
+    ExtensionMap, ExtensionValue, FileEntry, FileSystemItem, FixedBytes, FlagsMap,
+    GlobalAttributes, GroupIdTypeChoice, HashAlgorithm, HashEntry, IdentityTripleRecord,
+    InstanceIdTypeChoice, Integer, IntegerTime, IntegrityRegisters, IpAddrTypeChoice, Label,
+    LinkEntry, LinkedTagMap, MacAddrTypeChoice, MaskedRawValue, MeasuredElementTypeChoice,
+    MeasurementMap, MeasurementValuesMap, MinSvnType, ObjectIdentifier, OidType, OneOrMore,
+    Ownership, PathElementsGroup, Payload, PayloadEntry, PayloadOrEvidence, PkixAsn1DerCertType,
+    PkixBase64CertPathType, PkixBase64CertType, PkixBase64KeyType, ProcessEntry, ProfileTypeChoice,
+    ProtectedCorimBodyHeaderMap, ProtectedCorimHeaderMap, ProtectedCorimSignerHeaderMap,
+    RawValueType, RawValueTypeChoice, ReferenceTripleRecord, Rel, ResourceCollection,
+    ResourceEntry, SoftwareMetaEntry, StatefulEnvironmentRecord, SvnType, SvnTypeChoice,
+    TagIdTypeChoice, TagIdentityMap, TagRelTypeChoice, TaggedBytes, TaggedCOSESign1Corim,
+    TaggedCOSESignCorim, TaggedConciseMidTag, TaggedConciseSwidTag, TaggedConciseTlTag,
+    TaggedDetachedCOSESign1Corim, TaggedMaskedRawValue, TaggedUeidType, TaggedUnsignedCorimMap,
+    TaggedUuidType, TextOrBytes, TextOrBytesSized, ThumbprintType, TriplesMap,
+    TriplesRecordCondition, UeidType, Ulabel, Uri, Use, UuidType, ValidityMap, VersionMap,
+    VersionScheme,
+};
+
+/// Converts a value into one that owns all of its data
+pub trait IntoOwned {
+    /// The owned form of the type, which for the CoRIM types is the type itself with a
+    /// `'static` lifetime
+    type Owned: 'static;
+
+    /// Converts the value, copying any borrowed text
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl IntoOwned for Cow<'_, str> {
+    type Owned = Cow<'static, str>;
+
+    fn into_owned(self) -> Cow<'static, str> {
+        Cow::Owned(Cow::into_owned(self))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Box::new(IntoOwned::into_owned(*self))
+    }
+}
+
+impl<K, V> IntoOwned for BTreeMap<K, V>
+where
+    K: IntoOwned,
+    K::Owned: Ord,
+    V: IntoOwned,
+{
+    type Owned = BTreeMap<K::Owned, V::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter()
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect()
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for OneOrMore<T> {
+    type Owned = OneOrMore<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::One(value) => OneOrMore::One(value.into_owned()),
+            Self::More(values) => OneOrMore::More(values.into_owned()),
+        }
+    }
+}
+
+impl<const N: usize> IntoOwned for FixedBytes<N> {
+    type Owned = Self;
+
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+impl<const N: usize> IntoOwned for TextOrBytesSized<'_, N> {
+    type Owned = TextOrBytesSized<'static, N>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Self::Text(text) => TextOrBytesSized::Text(IntoOwned::into_owned(text)),
+            Self::Bytes(bytes) => TextOrBytesSized::Bytes(bytes),
+        }
+    }
+}
+
+impl IntoOwned for Bytes<'_> {
+    type Owned = Bytes<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Bytes::from(Cow::from(self).into_owned())
+    }
+}
+
+impl IntoOwned for UeidType<'_> {
+    type Owned = UeidType<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        UeidType(self.0.into_owned())
+    }
+}
+
+impl IntoOwned for ObjectIdentifier<'_> {
+    type Owned = ObjectIdentifier<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ObjectIdentifier(self.0.into_owned())
+    }
+}
+
+impl IntoOwned for CorimMapExtension<'_> {
+    type Owned = CorimMapExtension<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        CorimMapExtension(self.0.into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Encoded<'_, T> {
+    type Owned = Encoded<'static, T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Encoded {
+            value: self.value.into_owned(),
+            bytes: self.bytes.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ExtensionMap<'_> {
+    type Owned = ExtensionMap<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ExtensionMap(self.0.into_owned())
+    }
+}
+
+impl IntoOwned for IntegrityRegisters<'_> {
+    type Owned = IntegrityRegisters<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        IntegrityRegisters(self.0.into_owned())
+    }
+}
+
+/// Implements [`IntoOwned`] as the identity for types without a lifetime
+macro_rules! owned {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl IntoOwned for $type {
+                type Owned = Self;
+
+                fn into_owned(self) -> Self::Owned {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [`IntoOwned`] for structs with a lifetime by converting each field
+macro_rules! owned_struct {
+    ($($type:ident { $($field:ident),* $(,)? }),* $(,)?) => {
+        $(
+            impl IntoOwned for $type<'_> {
+                type Owned = $type<'static>;
+
+                fn into_owned(self) -> Self::Owned {
+                    $type {
+                        $($field: IntoOwned::into_owned(self.$field),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [`IntoOwned`] for enums with a lifetime by converting the fields of each variant
+macro_rules! owned_enum {
+    ($($type:ident { $($variant:ident $(($($field:ident),*))?),* $(,)? }),* $(,)?) => {
+        $(
+            impl IntoOwned for $type<'_> {
+                type Owned = $type<'static>;
+
+                fn into_owned(self) -> Self::Owned {
+                    match self {
+                        $(
+                            $type::$variant $(($($field),*))? => {
+                                $type::$variant $(($(IntoOwned::into_owned($field)),*))?
+                            }
+                        )*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Implements [`IntoOwned`] for the CBOR tagged wrappers of types with a lifetime
+macro_rules! owned_tagged {
+    ($($type:ident),* $(,)?) => {
+        $(
+            impl IntoOwned for $type<'_> {
+                type Owned = $type<'static>;
+
+                fn into_owned(self) -> Self::Owned {
+                    $type::new(IntoOwned::into_owned(self.0 .0))
+                }
+            }
+        )*
+    };
+}
+
+owned!(
+    Bool,
+    u8,
+    u64,
+    Integer,
+    UuidType,
+    HashAlgorithm,
+    CoseAlgorithm,
+    CoseKty,
+    CoseKeyOperation,
+    CoseEllipticCurve,
+    ComidRoleTypeChoice,
+    TagRelTypeChoice,
+    ValidityMap,
+    CorimRoleTypeChoice,
+    SvnTypeChoice,
+    MacAddrTypeChoice,
+    IpAddrTypeChoice,
+    IntegerTime,
+    TaggedUuidType,
+    SvnType,
+    MinSvnType,
+);
+
+owned_tagged!(
+    OidType,
+    TaggedUeidType,
+    ThumbprintType,
+    CoseKeyType,
+    CertThumbprintType,
+    TaggedBytes,
+    CertPathThumbprintType,
+    PkixAsn1DerCertType,
+    TaggedMaskedRawValue,
+    Uri,
+    PkixBase64KeyType,
+    PkixBase64CertType,
+    PkixBase64CertPathType,
+    TaggedConciseMidTag,
+    TaggedConciseSwidTag,
+    TaggedConciseTlTag,
+    TaggedUnsignedCorimMap,
+    TaggedCOSESign1Corim,
+    TaggedDetachedCOSESign1Corim,
+    TaggedCOSESignCorim,
+);
+
+owned_enum!(
+    ExtensionValue {
+        Null,
+        Bool(value),
+        Bytes(value),
+        Int(value),
+        Text(value),
+        Uint(value),
+        Array(value),
+        Map(value),
+        Tag(tag, value),
+    },
+    TextOrBytes { Text(text), Bytes(bytes) },
+    Label { Text(text), Int(int) },
+    Ulabel { Text(text), Uint(uint) },
+    AttributeValue { Text(text), Int(int) },
+    VersionScheme {
+        Multipartnumeric,
+        MultipartnumericSuffix,
+        Alphanumeric,
+        Decimal,
+        Semver,
+        PrivateUse(label),
+    },
+    CoseKeySetOrKey { KeySet(keys), Key(key) },
+    RawValueTypeChoice {
+        TaggedBytes(bytes),
+        TaggedMaskedRawValue(value),
+    },
+    GroupIdTypeChoice { Uuid(uuid), Bytes(bytes) },
+    TagIdTypeChoice { Tstr(tstr), Uuid(uuid) },
+    ConciseRimTypeChoice {
+        TaggedUnsignedCorimMap(corim),
+        SignedCorim(corim),
+        DetachedSignedCorim(corim),
+        MultiSignedCorim(corim),
+    },
+    CorimIdTypeChoice { Tstr(tstr), Uuid(uuid) },
+    ConciseTagTypeChoice { Swid(tag), Mid(tag), Tl(tag) },
+    ProfileTypeChoice { Uri(uri), OidType(oid) },
+    Ownership { Abandon, Private, Shared, IntOrText(label) },
+    Rel {
+        Ancestor,
+        Component,
+        Feature,
+        InstallationMedia,
+        PackageInstaller,
+        Parent,
+        Patches,
+        Requires,
+        SeeAlso,
+        Supersedes,
+        Supplemental,
+        IntOrText(label),
+    },
+    PayloadOrEvidence { Payload(payload), Evidence(evidence) },
+    Use { Optional, Required, Recommended, IntOrText(label) },
+    ClassIdTypeChoice { Oid(oid), Uuid(uuid), Bytes(bytes), Extension(value) },
+    InstanceIdTypeChoice { Ueid(ueid), Uuid(uuid), CryptoKey(key), Bytes(bytes) },
+    CryptoKeyTypeChoice {
+        PkixBase64Key(key),
+        PkixBase64Cert(cert),
+        PkixBase64CertPath(path),
+        CoseKey(key),
+        Thumbprint(digest),
+        CertThumbprint(digest),
+        CertPathThumbprint(digest),
+        PkixAsn1DerCert(cert),
+        Bytes(bytes),
+    },
+    MeasuredElementTypeChoice {
+        Oid(oid),
+        Uuid(uuid),
+        UInt(uint),
+        Tstr(tstr),
+        Extension(value),
+    },
+    DomainTypeChoice { Uint(uint), Text(text), Uuid(uuid), Oid(oid) },
+);
+
+owned_struct!(
+    HashEntry {
+        hash_alg_id,
+        hash_value,
+    },
+    Digest { alg, val },
+    CoseKey {
+        kty,
+        kid,
+        alg,
+        key_ops,
+        base_iv,
+        crv,
+        x,
+        y,
+        d,
+        k,
+    },
+    MaskedRawValue { value, mask },
+    RawValueType {
+        raw_value,
+        raw_value_mask,
+    },
+    GlobalAttributes { lang, attributes },
+    ConciseMidTag {
+        language,
+        tag_identity,
+        entities,
+        linked_tags,
+        triples,
+        extension,
+    },
+    TagIdentityMap {
+        tag_id,
+        tag_version
+    },
+    ComidEntityMap {
+        entity_name,
+        reg_id,
+        role,
+        extension,
+    },
+    LinkedTagMap {
+        linked_tag_id,
+        tag_rel,
+    },
+    TriplesMap {
+        reference_triples,
+        endorsed_triples,
+        identity_triples,
+        attest_key_triples,
+        dependency_triples,
+        membership_triples,
+        coswid_triples,
+        conditional_endorsement_series_triples,
+        conditional_endorsement_triples,
+        extension,
+    },
+    CorimMap {
+        id,
+        tags,
+        dependent_rims,
+        profile,
+        rim_validity,
+        entities,
+        extension,
+    },
+    CorimLocatorMap { href, thumbprint },
+    CorimEntityMap {
+        entity_name,
+        reg_id,
+        role,
+        extension,
+    },
+    COSESign1Corim {
+        protected,
+        unprotected,
+        payload,
+        signature,
+    },
+    DetachedCOSESign1Corim {
+        protected,
+        unprotected,
+        signature,
+    },
+    ProtectedCorimHeaderMap {
+        alg,
+        content_type,
+        kid,
+        corim_meta,
+        cose_map,
+    },
+    COSESignCorim {
+        protected,
+        unprotected,
+        payload,
+        signatures,
+    },
+    COSESignatureCorim {
+        protected,
+        unprotected,
+        signature,
+    },
+    ProtectedCorimBodyHeaderMap {
+        content_type,
+        cose_map,
+    },
+    ProtectedCorimSignerHeaderMap {
+        alg,
+        kid,
+        corim_meta,
+        cose_map,
+    },
+    CorimMetaMap {
+        signer,
+        signature_validity,
+    },
+    CorimSignerMap {
+        signer_name,
+        signer_uri,
+        extension,
+    },
+    ConciseSwidTag {
+        tag_id,
+        tag_version,
+        corpus,
+        patch,
+        supplemental,
+        software_name,
+        software_version,
+        version_scheme,
+        media,
+        software_meta,
+        entity,
+        link,
+        payload_or_evidence,
+        extensions,
+        global_attributes,
+    },
+    SoftwareMetaEntry {
+        activation_status,
+        channel_type,
+        coloquial_version,
+        description,
+        edition,
+        entitlement_data_required,
+        entitlement_key,
+        generator,
+        persistent_id,
+        product,
+        product_family,
+        revision,
+        summary,
+        unspsc_code,
+        unspsc_version,
+        extensions,
+        global_attributes,
+    },
+    EntityEntry {
+        entity_name,
+        reg_id,
+        role,
+        thumbprint,
+        extensions,
+        global_attributes,
+    },
+    LinkEntry {
+        artifact,
+        href,
+        media,
+        ownership,
+        rel,
+        media_type,
+        r#use,
+        extension,
+        global_attributes,
+    },
+    Payload { payload },
+    PayloadEntry {
+        resource_collection,
+        extension,
+        global_attributes,
+    },
+    ResourceCollection {
+        path_elements_group,
+        process,
+        resource,
+        extensions,
+    },
+    PathElementsGroup { directory, file },
+    DirectoryEntry {
+        filesystem_item,
+        path_elements,
+        extensions,
+        global_attributes,
+    },
+    FileSystemItem {
+        key,
+        location,
+        fs_name,
+        root,
+    },
+    FileEntry {
+        filesystem_item,
+        size,
+        file_version,
+        hash,
+        extensions,
+        global_attributes,
+    },
+    ProcessEntry {
+        process_name,
+        pid,
+        extension,
+        global_attributes,
+    },
+    ResourceEntry {
+        r#type,
+        extension,
+        global_attributes,
+    },
+    Evidence { evidence },
+    EvidenceEntry {
+        resource_collection,
+        date,
+        device_id,
+        location,
+        extension,
+        global_attributes,
+    },
+    ConciseTlTag {
+        tag_identity,
+        tags_list,
+        tl_validity,
+    },
+    ReferenceTripleRecord {
+        ref_env,
+        ref_claims,
+    },
+    EnvironmentMap {
+        class,
+        instance,
+        group,
+    },
+    ClassMap {
+        class_id,
+        vendor,
+        model,
+        layer,
+        index,
+    },
+    MeasurementMap {
+        mkey,
+        mval,
+        authorized_by,
+    },
+    MeasurementValuesMap {
+        version,
+        svn,
+        digests,
+        flags,
+        raw,
+        mac_addr,
+        ip_addr,
+        serial_number,
+        ueid,
+        uuid,
+        name,
+        cryptokeys,
+        integrity_registers,
+        extensions,
+    },
+    VersionMap {
+        version,
+        version_scheme,
+    },
+    FlagsMap {
+        is_configured,
+        is_secure,
+        is_recovery,
+        is_debug,
+        is_replay_protected,
+        is_integrity_protected,
+        is_runtime_meas,
+        is_immutable,
+        is_tcb,
+        is_confidentiality_protected,
+        extensions,
+    },
+    EndorsedTripleRecord {
+        condition,
+        endorsement,
+    },
+    IdentityTripleRecord {
+        environment,
+        key_list,
+        conditions,
+    },
+    TriplesRecordCondition {
+        mkey,
+        authorized_by
+    },
+    AttestKeyTripleRecord {
+        environment,
+        key_list,
+        conditions,
+    },
+    DomainDependencyTripleRecord {
+        domain_choice,
+        environment_map,
+    },
+    DomainMembershipTripleRecord {
+        domain_choice,
+        environment_map,
+    },
+    CoswidTripleRecord {
+        environment_map,
+        coswid_tags,
+    },
+    ConditionalEndorsementSeriesTripleRecord { condition, series },
+    StatefulEnvironmentRecord {
+        environment,
+        claims_list,
+    },
+    ConditionalSeriesRecord {
+        selection,
+        addition,
+    },
+    ConditionalEndorsementTripleRecord {
+        conditions,
+        endorsements,
+    },
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNSIGNED: &[u8] = include_bytes!("../testdata/corim/unsigned.cbor");
+    const SIGNED: &[u8] = include_bytes!("../testdata/corim/signed.cbor");
+
+    /// Builds a CoMID whose text borrows from `name`
+    #[cfg(feature = "std")]
+    fn comid(name: &str) -> ConciseMidTag<'_> {
+        let mut comid = ConciseMidTag::from_veraison_json(
+            r#"{
+                "tag-identity": {"id": "00000000-0000-0000-0000-000000000001"},
+                "entities": [{"name": "ACME", "roles": ["tagCreator"]}],
+                "triples": {
+                    "reference-values": [{
+                        "environment": {"class": {"vendor": "ACME", "model": "RoadRunner"}},
+                        "measurements": [{"value": {"name": "firmware"}}]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+        comid.tag_identity.tag_id = TagIdTypeChoice::Tstr(Cow::Borrowed(name));
+        comid.entities.as_mut().unwrap()[0].entity_name = Cow::Borrowed(name);
+        comid
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_into_owned_copies_borrowed_text() {
+        let name = String::from("borrowed");
+        let borrowed = comid(&name);
+        let owned: ConciseMidTag<'static> = borrowed.clone().into_owned();
+        assert_eq!(owned, borrowed);
+
+        assert!(matches!(
+            owned.tag_identity.tag_id,
+            TagIdTypeChoice::Tstr(Cow::Owned(_))
+        ));
+        drop(borrowed);
+        drop(name);
+        assert_eq!(owned.entities.unwrap()[0].entity_name, "borrowed");
+    }
+
+    #[test]
+    fn test_into_owned_decoded_corim() {
+        let corim: ConciseRimTypeChoice = crate::cbor::from_slice(UNSIGNED).unwrap();
+        let expected = corim.as_unsigned_corim_map().unwrap().into_owned();
+        let owned = corim.into_owned();
+        assert_eq!(owned.as_unsigned_corim_map().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_from_slice_borrows_decoded_corim() {
+        let corim: ConciseRimTypeChoice = crate::cbor::from_slice(SIGNED).unwrap();
+        let signed = corim.as_signed_corim().unwrap();
+        let input = SIGNED.as_ptr_range();
+        assert!(input.contains(&signed.signature.as_ptr()));
+        assert!(input.contains(&signed.payload.bytes.as_ref().unwrap().as_ptr()));
+
+        let names = signed
+            .payload
+            .as_ref()
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                ConciseTagTypeChoice::Mid(comid) => comid.entities.as_ref(),
+                _ => None,
+            })
+            .flatten()
+            .map(|entity| &entity.entity_name)
+            .collect::<Vec<_>>();
+        assert!(!names.is_empty());
+        assert!(names.iter().all(|name| matches!(name, Cow::Borrowed(_))));
+
+        let expected = signed.clone().into_owned();
+        let owned = corim.into_owned();
+        let signed = owned.as_signed_corim().unwrap();
+        assert_eq!(signed, expected);
+        assert!(!input.contains(&signed.signature.as_ptr()));
+    }
+}
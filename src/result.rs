@@ -3,4 +3,4 @@
 use crate::error::Error;
 
 /// CoRIM Result
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
@@ -0,0 +1,1580 @@
+// SPDX-License-Identifier: MIT
+
+//! Software bills of materials
+//!
+//! Release pipelines commonly describe what they ship as an SPDX 2 or CycloneDX SBOM in JSON.
+//! [`ConciseSwidTag::from_spdx_json`] and [`ConciseSwidTag::from_cyclonedx_json`] turn such an
+//! SBOM into CoSWID tags, one for each package or component, in document order:
+//!
+//! - the name and version become the `software_name` and `software_version`, and a description
+//!   or package URL becomes the `description` or `persistent_id` of the software metadata;
+//! - the supplier becomes an entity with the software-creator role, and the authors of the SBOM
+//!   (the `Organization` and `Person` creators of an SPDX document, the metadata authors of a
+//!   CycloneDX BOM) become tag creators. Without authors, the supplier is also the tag creator;
+//! - files become payload [`FileEntry`]s, with the strongest of their SHA-1 and SHA-2 checksums
+//!   as the [`FileEntry::hash`]. Files belong to the packages that list or contain them, and in
+//!   CycloneDX to the component they are nested in or that depends on them, or else the
+//!   component the BOM describes;
+//! - relationships between packages become links to the tag of the related package:
+//!
+//! | SPDX relationship                          | CycloneDX            | Link           |
+//! |--------------------------------------------|----------------------|----------------|
+//! | `DEPENDS_ON`, `DEPENDENCY_OF`              | `dependencies`       | `requires`     |
+//! | `OPTIONAL_DEPENDENCY_OF`                   |                      | `requires`, with `optional` use |
+//! | `CONTAINS`                                 | nested `components`  | `component`    |
+//! | `CONTAINED_BY`                             |                      | `parent`       |
+//! | `ANCESTOR_OF`, `DESCENDANT_OF`             |                      | `ancestor`     |
+//! | `PATCH_FOR`, `PATCH_APPLIED`               |                      | `patches`      |
+//!
+//! Links are added to the tag of the dependent, containing, descendant or patching package, and
+//! use the `swid:` URI of the tag they point to. Other relationships have no CoSWID counterpart
+//! and are not mapped.
+//!
+//! Tag identifiers are the SPDX element identifier or CycloneDX `bom-ref` of the package,
+//! qualified by the SPDX document namespace or CycloneDX serial number as
+//! `<namespace>#<identifier>`. CycloneDX tags take the version of the BOM as their tag version.
+//!
+//! ## Export
+//!
+//! For tools that only read CycloneDX, such as vulnerability scanners,
+//! [`ConciseSwidTag::to_cyclonedx_json`] and [`ConciseMidTag::to_cyclonedx_json`] go the other
+//! way:
+//!
+//! - a CoSWID tag becomes a BOM describing the tagged software, with its software creator as the
+//!   supplier and its tag creators as the authors. The payload and evidence files become `file`
+//!   components with their hashes, and sizes and versions as properties;
+//! - each measurement with digests in the reference triples of a CoMID tag becomes a `firmware`
+//!   component. The vendor and model of the environment class become the supplier and name, the
+//!   digests become hashes, and the other measured and environment values become properties.
+//!
+//! Hashes that CycloneDX has no algorithm for are kept as `coswid:hash` or `corim:digest`
+//! properties, holding the algorithm and the hex value.
+//!
+//! # Example
+//!
+//! ```rust
+//! use corim_rs::coswid::ConciseSwidTag;
+//!
+//! let bom = r#"{
+//!   "bomFormat": "CycloneDX",
+//!   "specVersion": "1.5",
+//!   "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+//!   "version": 1,
+//!   "components": [
+//!     {
+//!       "type": "library",
+//!       "bom-ref": "pkg:cargo/serde@1.0.219",
+//!       "name": "serde",
+//!       "version": "1.0.219",
+//!       "supplier": { "name": "serde-rs" }
+//!     }
+//!   ]
+//! }"#;
+//!
+//! let tags = ConciseSwidTag::from_cyclonedx_json(bom).unwrap();
+//! assert_eq!(tags[0].software_name, "serde");
+//! assert_eq!(
+//!     tags[0].tag_id.as_str(),
+//!     Some("urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79#pkg:cargo/serde@1.0.219")
+//! );
+//! ```
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ConciseMidTag, ConciseSwidTag, CoseAlgorithm, Digest, EntityEntry, EnvironmentMap, FileEntry,
+    FileSystemItem, HashAlgorithm, HashEntry, Integer, LinkEntry, MeasurementMap, OneOrMore,
+    PathElementsGroup, Payload, PayloadEntry, PayloadOrEvidence, Rel, ResourceCollection, Result,
+    SbomError, SoftwareMetaEntry, SvnTypeChoice, TagIdTypeChoice, Text, TextOrBytes, Uri, Use,
+};
+
+const TAG_CREATOR: u8 = 1;
+const SOFTWARE_CREATOR: u8 = 2;
+
+/// Hash algorithms that can be carried over, strongest first
+const HASH_ALGORITHMS: [(&str, CoseAlgorithm); 4] = [
+    ("SHA-512", CoseAlgorithm::Sha512),
+    ("SHA-384", CoseAlgorithm::Sha384),
+    ("SHA-256", CoseAlgorithm::Sha256),
+    ("SHA-1", CoseAlgorithm::Sha1),
+];
+
+/// CycloneDX names of the CoMID digest algorithms it supports
+const DIGEST_ALGORITHMS: [(&str, HashAlgorithm); 8] = [
+    ("SHA-256", HashAlgorithm::Sha256),
+    ("SHA-384", HashAlgorithm::Sha384),
+    ("SHA-512", HashAlgorithm::Sha512),
+    ("SHA3-256", HashAlgorithm::Sha3_256),
+    ("SHA3-384", HashAlgorithm::Sha3_384),
+    ("SHA3-512", HashAlgorithm::Sha3_512),
+    ("BLAKE2b-256", HashAlgorithm::Blake2b256),
+    ("BLAKE2b-512", HashAlgorithm::Blake2b512),
+];
+
+impl ConciseSwidTag<'_> {
+    /// Reads the packages of an SPDX 2 JSON document as CoSWID tags
+    pub fn from_spdx_json(json: &str) -> Result<Vec<ConciseSwidTag<'static>>> {
+        import_spdx(from_json(json)?)?.into_tags()
+    }
+
+    /// Reads the components of a CycloneDX JSON BOM as CoSWID tags
+    pub fn from_cyclonedx_json(json: &str) -> Result<Vec<ConciseSwidTag<'static>>> {
+        import_cyclonedx(from_json(json)?)?.into_tags()
+    }
+
+    /// Renders the tag as a CycloneDX JSON BOM describing the tagged software and its files
+    pub fn to_cyclonedx_json(&self) -> Result<String> {
+        to_json(&export_coswid(self)?)
+    }
+}
+
+impl ConciseMidTag<'_> {
+    /// Renders the reference values with digests as a CycloneDX JSON BOM
+    pub fn to_cyclonedx_json(&self) -> Result<String> {
+        to_json(&export_comid(self)?)
+    }
+}
+
+/// An SPDX 2 document
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxDocument {
+    spdx_version: String,
+    document_namespace: Option<String>,
+    creation_info: Option<SpdxCreationInfo>,
+    #[serde(default)]
+    packages: Vec<SpdxPackage>,
+    #[serde(default)]
+    files: Vec<SpdxFile>,
+    #[serde(default)]
+    relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Deserialize)]
+struct SpdxCreationInfo {
+    #[serde(default)]
+    creators: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPackage {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: String,
+    version_info: Option<String>,
+    supplier: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    has_files: Vec<String>,
+    #[serde(default)]
+    external_refs: Vec<SpdxExternalRef>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxExternalRef {
+    reference_type: String,
+    reference_locator: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxFile {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    file_name: String,
+    #[serde(default)]
+    checksums: Vec<SpdxChecksum>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxChecksum {
+    algorithm: String,
+    checksum_value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxRelationship {
+    spdx_element_id: String,
+    relationship_type: String,
+    related_spdx_element: String,
+}
+
+/// A CycloneDX BOM
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxBom {
+    bom_format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spec_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    serial_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<CycloneDxMetadata>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    components: Vec<CycloneDxComponent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<CycloneDxDependency>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CycloneDxMetadata {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<CycloneDxContact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    component: Option<CycloneDxComponent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    supplier: Option<CycloneDxContact>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    properties: Vec<CycloneDxProperty>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CycloneDxContact {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CycloneDxComponent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(rename = "bom-ref", skip_serializing_if = "Option::is_none")]
+    bom_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    supplier: Option<CycloneDxContact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publisher: Option<String>,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<CycloneDxHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    components: Vec<CycloneDxComponent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    properties: Vec<CycloneDxProperty>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CycloneDxHash {
+    alg: String,
+    content: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxDependency {
+    #[serde(rename = "ref")]
+    reference: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CycloneDxProperty {
+    name: String,
+    value: String,
+}
+
+/// An SBOM, in terms of the CoSWID tags it becomes
+struct Sbom {
+    namespace: Option<String>,
+    tag_version: Integer,
+    creators: Vec<String>,
+    components: Vec<Component>,
+}
+
+/// A package or component, in terms of the CoSWID tag it becomes
+struct Component {
+    id: String,
+    name: String,
+    version: Option<String>,
+    supplier: Option<String>,
+    description: Option<String>,
+    purl: Option<String>,
+    files: Vec<FileEntry<'static>>,
+    /// Links to other components, by identifier
+    links: Vec<(Rel<'static>, Option<Use<'static>>, String)>,
+}
+
+impl Sbom {
+    fn tag_id(&self, id: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{namespace}#{id}"),
+            None => id.to_string(),
+        }
+    }
+
+    fn component_mut(&mut self, id: &str) -> Option<&mut Component> {
+        self.components
+            .iter_mut()
+            .find(|component| component.id == id)
+    }
+
+    fn into_tags(self) -> Result<Vec<ConciseSwidTag<'static>>> {
+        let ids: BTreeSet<&str> = self.components.iter().map(|c| c.id.as_str()).collect();
+
+        self.components
+            .iter()
+            .map(|component| {
+                let mut entities: Vec<EntityEntry<'static>> = self
+                    .creators
+                    .iter()
+                    .map(|name| entity(name, vec![TAG_CREATOR]))
+                    .collect();
+                if let Some(supplier) = &component.supplier {
+                    let is_creator = entities.is_empty() || self.creators.contains(supplier);
+                    entities.retain(|e| e.entity_name != supplier.as_str());
+                    entities.push(match is_creator {
+                        true => entity(supplier, vec![TAG_CREATOR, SOFTWARE_CREATOR]),
+                        false => entity(supplier, vec![SOFTWARE_CREATOR]),
+                    });
+                }
+
+                let links: Vec<LinkEntry<'static>> = component
+                    .links
+                    .iter()
+                    .filter(|(.., target)| ids.contains(target.as_str()))
+                    .map(|(rel, r#use, target)| LinkEntry {
+                        artifact: None,
+                        href: Uri::from(Text::from(format!("swid:{}", self.tag_id(target)))),
+                        media: None,
+                        ownership: None,
+                        rel: rel.clone(),
+                        media_type: None,
+                        r#use: r#use.clone(),
+                        extension: None,
+                        global_attributes: None,
+                    })
+                    .collect();
+
+                let software_meta = (component.description.is_some() || component.purl.is_some())
+                    .then(|| {
+                        OneOrMore::One(SoftwareMetaEntry {
+                            description: component.description.clone().map(Text::from),
+                            persistent_id: component.purl.clone().map(Text::from),
+                            ..Default::default()
+                        })
+                    });
+
+                let payload_or_evidence = one_or_more(component.files.clone()).map(|file| {
+                    PayloadOrEvidence::Payload(Payload::new(PayloadEntry {
+                        resource_collection: Some(ResourceCollection {
+                            path_elements_group: Some(PathElementsGroup {
+                                directory: None,
+                                file: Some(file),
+                            }),
+                            process: None,
+                            resource: None,
+                            extensions: None,
+                        }),
+                        extension: None,
+                        global_attributes: None,
+                    }))
+                });
+
+                Ok(ConciseSwidTag {
+                    tag_id: Text::from(self.tag_id(&component.id)).into(),
+                    tag_version: self.tag_version,
+                    corpus: None,
+                    patch: None,
+                    supplemental: None,
+                    software_name: component.name.clone().into(),
+                    software_version: component.version.clone().map(Text::from),
+                    version_scheme: None,
+                    media: None,
+                    software_meta,
+                    entity: one_or_more(entities).ok_or_else(|| {
+                        invalid(
+                            "supplier",
+                            format!(
+                                "{} has no supplier and the SBOM has no author to name as the tag creator",
+                                component.name
+                            ),
+                        )
+                    })?,
+                    link: one_or_more(links),
+                    payload_or_evidence,
+                    extensions: None,
+                    global_attributes: None,
+                })
+            })
+            .collect()
+    }
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(json: &str) -> Result<T> {
+    Ok(serde_json::from_str(json).map_err(|err| SbomError::Json(err.to_string()))?)
+}
+
+fn to_json(value: &impl Serialize) -> Result<String> {
+    Ok(serde_json::to_string_pretty(value).map_err(|err| SbomError::Json(err.to_string()))?)
+}
+
+fn invalid(field: &'static str, message: impl std::fmt::Display) -> SbomError {
+    SbomError::InvalidValue {
+        field,
+        message: message.to_string(),
+    }
+}
+
+fn one_or_more<T>(mut items: Vec<T>) -> Option<OneOrMore<T>> {
+    match items.len() {
+        0 => None,
+        1 => items.pop().map(OneOrMore::One),
+        _ => Some(OneOrMore::More(items)),
+    }
+}
+
+fn entity(name: &str, roles: Vec<u8>) -> EntityEntry<'static> {
+    EntityEntry {
+        entity_name: name.to_string().into(),
+        reg_id: None,
+        role: one_or_more(roles).unwrap_or(OneOrMore::More(vec![])),
+        thumbprint: None,
+        extensions: None,
+        global_attributes: None,
+    }
+}
+
+/// Makes a file entry for a path, with the strongest of the given `(algorithm, hex)` checksums
+fn file_entry<'a>(
+    path: &str,
+    checksums: impl Iterator<Item = (&'a str, &'a str)>,
+) -> Result<FileEntry<'static>> {
+    let checksums: Vec<(String, &str)> = checksums
+        .map(|(alg, value)| (alg.replace('-', "").to_ascii_uppercase(), value))
+        .collect();
+
+    let hash = HASH_ALGORITHMS
+        .iter()
+        .find_map(|(name, alg)| {
+            checksums
+                .iter()
+                .find(|(checksum_alg, _)| *checksum_alg == name.replace('-', ""))
+                .map(|(_, value)| (alg, *value))
+        })
+        .map(|(alg, value)| -> Result<_> {
+            Ok(HashEntry {
+                hash_alg_id: alg.clone(),
+                hash_value: decode_hex(value)
+                    .ok_or_else(|| invalid("checksum", format!("expected hex, got \"{value}\"")))?
+                    .into(),
+            })
+        })
+        .transpose()?;
+
+    let path = path.strip_prefix("./").unwrap_or(path);
+    let (location, name) = match path.rsplit_once('/') {
+        Some((location, name)) if !location.is_empty() => (Some(location), name),
+        _ => (None, path),
+    };
+
+    Ok(FileEntry {
+        filesystem_item: Some(FileSystemItem {
+            key: None,
+            location: location.map(|location| Text::from(location.to_string())),
+            fs_name: name.to_string().into(),
+            root: None,
+        }),
+        size: None,
+        file_version: None,
+        hash,
+        extensions: None,
+        global_attributes: None,
+    })
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+/// Strips the `Organization:`/`Person:` prefix and email suffix of an SPDX actor. Tools and
+/// `NOASSERTION` are not entities.
+fn spdx_actor(actor: &str) -> Option<String> {
+    let name = actor
+        .strip_prefix("Organization:")
+        .or_else(|| actor.strip_prefix("Person:"))?;
+    let name = match name.rfind(" (") {
+        Some(index) if name.ends_with(')') => &name[..index],
+        _ => name,
+    };
+
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+fn import_spdx(document: SpdxDocument) -> Result<Sbom> {
+    if !document.spdx_version.starts_with("SPDX-2.") {
+        return Err(invalid(
+            "spdxVersion",
+            format!("expected SPDX 2, got \"{}\"", document.spdx_version),
+        )
+        .into());
+    }
+
+    let mut sbom = Sbom {
+        namespace: document.document_namespace,
+        tag_version: Integer(0),
+        creators: document
+            .creation_info
+            .iter()
+            .flat_map(|info| info.creators.iter())
+            .filter_map(|creator| spdx_actor(creator))
+            .collect(),
+        components: vec![],
+    };
+
+    let file = |id: &str| document.files.iter().find(|file| file.spdx_id == id);
+
+    for package in &document.packages {
+        let mut file_ids: Vec<&str> = package.has_files.iter().map(String::as_str).collect();
+        for relationship in &document.relationships {
+            let contained = match relationship.relationship_type.as_str() {
+                "CONTAINS" if relationship.spdx_element_id == package.spdx_id => {
+                    &relationship.related_spdx_element
+                }
+                "CONTAINED_BY" if relationship.related_spdx_element == package.spdx_id => {
+                    &relationship.spdx_element_id
+                }
+                _ => continue,
+            };
+            if !file_ids.contains(&contained.as_str()) {
+                file_ids.push(contained);
+            }
+        }
+
+        let files = file_ids
+            .into_iter()
+            .filter_map(file)
+            .map(|file| {
+                file_entry(
+                    &file.file_name,
+                    file.checksums.iter().map(|checksum| {
+                        (
+                            checksum.algorithm.as_str(),
+                            checksum.checksum_value.as_str(),
+                        )
+                    }),
+                )
+            })
+            .collect::<Result<_>>()?;
+
+        sbom.components.push(Component {
+            id: package.spdx_id.clone(),
+            name: package.name.clone(),
+            version: package.version_info.clone(),
+            supplier: package.supplier.as_deref().and_then(spdx_actor),
+            description: package.description.clone(),
+            purl: package
+                .external_refs
+                .iter()
+                .find(|reference| reference.reference_type == "purl")
+                .map(|reference| reference.reference_locator.clone()),
+            files,
+            links: vec![],
+        });
+    }
+
+    for relationship in &document.relationships {
+        let (a, b) = (
+            relationship.spdx_element_id.as_str(),
+            relationship.related_spdx_element.as_str(),
+        );
+        let (source, rel, r#use, target) = match relationship.relationship_type.as_str() {
+            "DEPENDS_ON" => (a, Rel::Requires, None, b),
+            "DEPENDENCY_OF" => (b, Rel::Requires, None, a),
+            "OPTIONAL_DEPENDENCY_OF" => (b, Rel::Requires, Some(Use::Optional), a),
+            "CONTAINS" => (a, Rel::Component, None, b),
+            "CONTAINED_BY" => (a, Rel::Parent, None, b),
+            "ANCESTOR_OF" => (b, Rel::Ancestor, None, a),
+            "DESCENDANT_OF" => (a, Rel::Ancestor, None, b),
+            "PATCH_FOR" | "PATCH_APPLIED" => (a, Rel::Patches, None, b),
+            _ => continue,
+        };
+
+        if let Some(component) = sbom.component_mut(source) {
+            component.links.push((rel, r#use, target.to_string()));
+        }
+    }
+
+    Ok(sbom)
+}
+
+fn import_cyclonedx(bom: CycloneDxBom) -> Result<Sbom> {
+    if bom.bom_format != "CycloneDX" {
+        return Err(invalid(
+            "bomFormat",
+            format!("expected CycloneDX, got \"{}\"", bom.bom_format),
+        )
+        .into());
+    }
+
+    let (creators, described, metadata_supplier) = match bom.metadata {
+        Some(metadata) => (
+            metadata
+                .authors
+                .into_iter()
+                .filter_map(|author| author.name)
+                .collect(),
+            metadata.component,
+            metadata.supplier.and_then(|supplier| supplier.name),
+        ),
+        None => (vec![], None, None),
+    };
+
+    let mut sbom = Sbom {
+        namespace: bom.serial_number,
+        tag_version: Integer(bom.version.unwrap_or(1).into()),
+        creators,
+        components: vec![],
+    };
+
+    // Files that are not nested in a component, by reference
+    let mut files = vec![];
+
+    let described_id = match described {
+        Some(mut component) => {
+            if component.supplier.is_none() {
+                component.supplier =
+                    metadata_supplier.map(|name| CycloneDxContact { name: Some(name) });
+            }
+            import_cyclonedx_component(component, None, &mut sbom, &mut files)?
+        }
+        None => None,
+    };
+    for component in bom.components {
+        import_cyclonedx_component(component, None, &mut sbom, &mut files)?;
+    }
+
+    for dependency in &bom.dependencies {
+        for target in &dependency.depends_on {
+            if let Some(index) = files.iter().position(|(id, _)| id == target) {
+                let (_, file) = files.remove(index);
+                if let Some(component) = sbom.component_mut(&dependency.reference) {
+                    component.files.push(file);
+                }
+            } else if let Some(component) = sbom.component_mut(&dependency.reference) {
+                component.links.push((Rel::Requires, None, target.clone()));
+            }
+        }
+    }
+
+    if let Some(component) = described_id.and_then(|id| sbom.component_mut(&id)) {
+        component
+            .files
+            .extend(files.into_iter().map(|(_, file)| file));
+    }
+
+    Ok(sbom)
+}
+
+/// Adds a component and the components nested in it, returning its identifier. Files are
+/// added to `parent`, or to `files` if they are not nested.
+fn import_cyclonedx_component(
+    component: CycloneDxComponent,
+    parent: Option<&str>,
+    sbom: &mut Sbom,
+    files: &mut Vec<(String, FileEntry<'static>)>,
+) -> Result<Option<String>> {
+    let id = component
+        .bom_ref
+        .clone()
+        .or_else(|| component.purl.clone())
+        .unwrap_or_else(|| match &component.version {
+            Some(version) => format!("{}@{version}", component.name),
+            None => component.name.clone(),
+        });
+
+    if component.kind == "file" {
+        let file = file_entry(
+            &component.name,
+            component
+                .hashes
+                .iter()
+                .map(|hash| (hash.alg.as_str(), hash.content.as_str())),
+        )?;
+        match parent.and_then(|parent| sbom.component_mut(parent)) {
+            Some(parent) => parent.files.push(file),
+            None => files.push((id, file)),
+        }
+        return Ok(None);
+    }
+
+    if let Some(parent) = parent.and_then(|parent| sbom.component_mut(parent)) {
+        parent.links.push((Rel::Component, None, id.clone()));
+    }
+
+    sbom.components.push(Component {
+        id: id.clone(),
+        name: component.name,
+        version: component.version,
+        supplier: component
+            .supplier
+            .and_then(|supplier| supplier.name)
+            .or(component.publisher),
+        description: component.description,
+        purl: component.purl,
+        files: vec![],
+        links: vec![],
+    });
+
+    for child in component.components {
+        import_cyclonedx_component(child, Some(&id), sbom, files)?;
+    }
+
+    Ok(Some(id))
+}
+
+/// Returns the JSON form of a value as property text, without the quotes of strings
+fn json_text(value: &impl Serialize) -> Result<String> {
+    Ok(
+        match serde_json::to_value(value).map_err(|err| SbomError::Json(err.to_string()))? {
+            serde_json::Value::String(text) => text,
+            value => value.to_string(),
+        },
+    )
+}
+
+fn property(name: &str, value: impl ToString) -> CycloneDxProperty {
+    CycloneDxProperty {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn contact(name: &str) -> CycloneDxContact {
+    CycloneDxContact {
+        name: Some(name.to_string()),
+    }
+}
+
+/// Makes a BOM, numbered by the tag identifier if that is a UUID
+fn bom(
+    tag_id: &str,
+    metadata: CycloneDxMetadata,
+    components: Vec<CycloneDxComponent>,
+) -> CycloneDxBom {
+    CycloneDxBom {
+        bom_format: "CycloneDX".to_string(),
+        spec_version: Some("1.6".to_string()),
+        serial_number: uuid::Uuid::parse_str(tag_id)
+            .ok()
+            .map(|uuid| uuid.urn().to_string()),
+        version: Some(1),
+        metadata: Some(metadata),
+        components,
+        dependencies: vec![],
+    }
+}
+
+fn export_coswid(tag: &ConciseSwidTag) -> Result<CycloneDxBom> {
+    let tag_id = match &tag.tag_id {
+        TextOrBytes::Text(text) => text.to_string(),
+        TextOrBytes::Bytes(bytes) => match uuid::Uuid::from_slice(bytes.as_ref()) {
+            Ok(uuid) => uuid.to_string(),
+            Err(_) => encode_hex(bytes.as_ref()),
+        },
+    };
+
+    let has_role = |entity: &&EntityEntry, role: u8| entity.role.iter().any(|r| *r == role);
+    let meta: Vec<&SoftwareMetaEntry> =
+        tag.software_meta.iter().flat_map(OneOrMore::iter).collect();
+
+    let mut properties = vec![
+        property("coswid:tag-id", &tag_id),
+        property("coswid:tag-version", tag.tag_version),
+    ];
+    for (name, flag) in [
+        ("coswid:corpus", tag.corpus),
+        ("coswid:patch", tag.patch),
+        ("coswid:supplemental", tag.supplemental),
+    ] {
+        if flag == Some(true) {
+            properties.push(property(name, true));
+        }
+    }
+    if let Some(scheme) = &tag.version_scheme {
+        properties.push(property("coswid:version-scheme", scheme));
+    }
+
+    let mut components = vec![];
+    match &tag.payload_or_evidence {
+        Some(PayloadOrEvidence::Payload(payload)) => {
+            export_files(&payload.payload.resource_collection, false, &mut components)?
+        }
+        Some(PayloadOrEvidence::Evidence(evidence)) => export_files(
+            &evidence.evidence.resource_collection,
+            true,
+            &mut components,
+        )?,
+        None => {}
+    }
+
+    let metadata = CycloneDxMetadata {
+        authors: tag
+            .entity
+            .iter()
+            .filter(|entity| has_role(entity, TAG_CREATOR))
+            .map(|entity| contact(&entity.entity_name))
+            .collect(),
+        component: Some(CycloneDxComponent {
+            kind: "application".to_string(),
+            bom_ref: Some(tag_id.clone()),
+            supplier: tag
+                .entity
+                .iter()
+                .find(|entity| has_role(entity, SOFTWARE_CREATOR))
+                .map(|entity| contact(&entity.entity_name)),
+            name: tag.software_name.to_string(),
+            version: tag.software_version.as_ref().map(ToString::to_string),
+            description: meta
+                .iter()
+                .find_map(|meta| meta.description.as_ref())
+                .map(ToString::to_string),
+            purl: meta
+                .iter()
+                .find_map(|meta| meta.persistent_id.as_ref())
+                .filter(|id| id.starts_with("pkg:"))
+                .map(ToString::to_string),
+            properties,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    Ok(bom(&tag_id, metadata, components))
+}
+
+/// Adds the files of a resource collection as `file` components
+fn export_files(
+    collection: &Option<ResourceCollection>,
+    evidence: bool,
+    components: &mut Vec<CycloneDxComponent>,
+) -> Result<()> {
+    fn path(directory: Option<&str>, item: &FileSystemItem) -> String {
+        [directory, item.location.as_deref(), Some(&item.fs_name)]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn add(
+        group: &PathElementsGroup,
+        directory: Option<&str>,
+        evidence: bool,
+        components: &mut Vec<CycloneDxComponent>,
+    ) -> Result<()> {
+        for entry in group.directory.iter().flat_map(OneOrMore::iter) {
+            let path = entry
+                .filesystem_item
+                .as_ref()
+                .map(|item| path(directory, item));
+            if let Some(elements) = &entry.path_elements {
+                add(
+                    elements,
+                    path.as_deref().or(directory),
+                    evidence,
+                    components,
+                )?;
+            }
+        }
+
+        for file in group.file.iter().flat_map(OneOrMore::iter) {
+            let item = file.filesystem_item.as_ref().ok_or_else(|| {
+                invalid(
+                    "file",
+                    "a file without a name cannot be a CycloneDX component",
+                )
+            })?;
+
+            let mut hashes = vec![];
+            let mut properties = vec![];
+            if let Some(hash) = &file.hash {
+                let content = encode_hex(hash.hash_value.as_ref());
+                match HASH_ALGORITHMS
+                    .iter()
+                    .find(|(_, alg)| *alg == hash.hash_alg_id)
+                {
+                    Some((name, _)) => hashes.push(CycloneDxHash {
+                        alg: name.to_string(),
+                        content,
+                    }),
+                    None => properties.push(property(
+                        "coswid:hash",
+                        format!("{}:{content}", hash.hash_alg_id),
+                    )),
+                }
+            }
+            if let Some(size) = &file.size {
+                properties.push(property("coswid:size", size));
+            }
+            if let Some(version) = &file.file_version {
+                properties.push(property("coswid:file-version", version));
+            }
+            if evidence {
+                properties.push(property("coswid:evidence", true));
+            }
+
+            components.push(CycloneDxComponent {
+                kind: "file".to_string(),
+                name: path(directory, item),
+                hashes,
+                properties,
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+
+    match collection
+        .as_ref()
+        .and_then(|c| c.path_elements_group.as_ref())
+    {
+        Some(group) => add(group, None, evidence, components),
+        None => Ok(()),
+    }
+}
+
+fn export_comid(tag: &ConciseMidTag) -> Result<CycloneDxBom> {
+    let tag_id = match &tag.tag_identity.tag_id {
+        TagIdTypeChoice::Tstr(text) => text.to_string(),
+        TagIdTypeChoice::Uuid(uuid) => uuid.to_string(),
+    };
+
+    let mut properties = vec![property("corim:tag-id", &tag_id)];
+    if let Some(version) = &tag.tag_identity.tag_version {
+        properties.push(property("corim:tag-version", version));
+    }
+
+    let mut components = vec![];
+    for (i, triple) in tag.triples.reference_triples.iter().flatten().enumerate() {
+        for (j, measurement) in triple.ref_claims.iter().enumerate() {
+            if let Some(digests) = measurement.mval.digests.as_ref().filter(|d| !d.is_empty()) {
+                let bom_ref = format!("reference-{i}-{j}");
+                components.push(export_measurement(
+                    &triple.ref_env,
+                    measurement,
+                    digests,
+                    bom_ref,
+                )?);
+            }
+        }
+    }
+
+    let metadata = CycloneDxMetadata {
+        properties,
+        ..Default::default()
+    };
+
+    Ok(bom(&tag_id, metadata, components))
+}
+
+/// Makes a `firmware` component for a reference measurement with digests
+fn export_measurement(
+    environment: &EnvironmentMap,
+    measurement: &MeasurementMap,
+    digests: &[Digest],
+    bom_ref: String,
+) -> Result<CycloneDxComponent> {
+    let class = environment.class.as_ref();
+    let mval = &measurement.mval;
+    let mkey = measurement.mkey.as_ref().map(json_text).transpose()?;
+
+    let name = class
+        .and_then(|class| class.model.as_deref())
+        .or(mval.name.as_deref())
+        .or(mkey.as_deref())
+        .ok_or_else(|| {
+            invalid(
+                "model",
+                format!("{bom_ref} has no model or measurement name to name a component by"),
+            )
+        })?
+        .to_string();
+
+    let mut properties = vec![];
+    let mut add = |name: &str, value: Option<String>| {
+        if let Some(value) = value {
+            properties.push(property(name, value));
+        }
+    };
+    add(
+        "corim:class-id",
+        class
+            .and_then(|class| class.class_id.as_ref())
+            .map(json_text)
+            .transpose()?,
+    );
+    add(
+        "corim:layer",
+        class
+            .and_then(|class| class.layer)
+            .map(|layer| layer.to_string()),
+    );
+    add(
+        "corim:index",
+        class
+            .and_then(|class| class.index)
+            .map(|index| index.to_string()),
+    );
+    add(
+        "corim:instance",
+        environment.instance.as_ref().map(json_text).transpose()?,
+    );
+    add(
+        "corim:group",
+        environment.group.as_ref().map(json_text).transpose()?,
+    );
+    add("corim:mkey", mkey.clone());
+    add("corim:name", mval.name.as_ref().map(ToString::to_string));
+    add(
+        "corim:version-scheme",
+        mval.version
+            .as_ref()
+            .and_then(|version| version.version_scheme.as_ref())
+            .map(ToString::to_string),
+    );
+    match &mval.svn {
+        Some(SvnTypeChoice::Svn(svn)) => add("corim:svn", Some(svn.to_string())),
+        Some(SvnTypeChoice::TaggedSvn(svn)) => add("corim:svn", Some(svn.as_ref().to_string())),
+        Some(SvnTypeChoice::TaggedMinSvn(svn)) => {
+            add("corim:min-svn", Some(svn.as_ref().to_string()))
+        }
+        None => {}
+    }
+    add(
+        "corim:serial-number",
+        mval.serial_number.as_ref().map(ToString::to_string),
+    );
+
+    let mut hashes = vec![];
+    for digest in digests {
+        let content = encode_hex(digest.val.as_ref());
+        match DIGEST_ALGORITHMS.iter().find(|(_, alg)| *alg == digest.alg) {
+            Some((name, _)) => hashes.push(CycloneDxHash {
+                alg: name.to_string(),
+                content,
+            }),
+            None => add("corim:digest", Some(format!("{}:{content}", digest.alg))),
+        }
+    }
+
+    Ok(CycloneDxComponent {
+        kind: "firmware".to_string(),
+        bom_ref: Some(bom_ref),
+        supplier: class.and_then(|class| class.vendor.as_deref()).map(contact),
+        name,
+        version: mval
+            .version
+            .as_ref()
+            .map(|version| version.version.to_string()),
+        hashes,
+        properties,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPDX: &str = r#"{
+      "spdxVersion": "SPDX-2.3",
+      "dataLicense": "CC0-1.0",
+      "SPDXID": "SPDXRef-DOCUMENT",
+      "name": "firmware",
+      "documentNamespace": "https://example.com/spdx/firmware-1.2",
+      "creationInfo": {
+        "created": "2025-01-01T00:00:00Z",
+        "creators": ["Organization: ACME Inc. (sbom@acme.example)", "Tool: builder-1.0"]
+      },
+      "packages": [
+        {
+          "SPDXID": "SPDXRef-firmware",
+          "name": "firmware",
+          "versionInfo": "1.2",
+          "supplier": "Organization: ACME Inc.",
+          "description": "Board firmware",
+          "hasFiles": ["SPDXRef-image"],
+          "externalRefs": [{
+            "referenceCategory": "PACKAGE-MANAGER",
+            "referenceType": "purl",
+            "referenceLocator": "pkg:generic/acme/firmware@1.2"
+          }]
+        },
+        {
+          "SPDXID": "SPDXRef-crypto",
+          "name": "crypto",
+          "versionInfo": "3.0.1",
+          "supplier": "Person: Jane Doe (jane@example.com)"
+        },
+        {
+          "SPDXID": "SPDXRef-debug",
+          "name": "debug-shell",
+          "supplier": "NOASSERTION"
+        }
+      ],
+      "files": [
+        {
+          "SPDXID": "SPDXRef-image",
+          "fileName": "./boot/image.bin",
+          "checksums": [
+            { "algorithm": "SHA1", "checksumValue": "0102" },
+            { "algorithm": "SHA256", "checksumValue": "a1b2c3" }
+          ]
+        },
+        {
+          "SPDXID": "SPDXRef-license",
+          "fileName": "LICENSE",
+          "checksums": [{ "algorithm": "MD5", "checksumValue": "00" }]
+        }
+      ],
+      "relationships": [
+        { "spdxElementId": "SPDXRef-DOCUMENT", "relationshipType": "DESCRIBES", "relatedSpdxElement": "SPDXRef-firmware" },
+        { "spdxElementId": "SPDXRef-firmware", "relationshipType": "DEPENDS_ON", "relatedSpdxElement": "SPDXRef-crypto" },
+        { "spdxElementId": "SPDXRef-debug", "relationshipType": "OPTIONAL_DEPENDENCY_OF", "relatedSpdxElement": "SPDXRef-firmware" },
+        { "spdxElementId": "SPDXRef-firmware", "relationshipType": "CONTAINS", "relatedSpdxElement": "SPDXRef-license" },
+        { "spdxElementId": "SPDXRef-crypto", "relationshipType": "CONTAINED_BY", "relatedSpdxElement": "SPDXRef-firmware" },
+        { "spdxElementId": "SPDXRef-crypto", "relationshipType": "DEPENDS_ON", "relatedSpdxElement": "DocumentRef-other:SPDXRef-libc" }
+      ]
+    }"#;
+
+    const CYCLONEDX: &str = r#"{
+      "bomFormat": "CycloneDX",
+      "specVersion": "1.5",
+      "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+      "version": 3,
+      "metadata": {
+        "authors": [{ "name": "Release Team" }],
+        "supplier": { "name": "ACME Inc." },
+        "component": { "type": "firmware", "bom-ref": "fw", "name": "firmware", "version": "1.2" }
+      },
+      "components": [
+        {
+          "type": "library",
+          "bom-ref": "pkg:cargo/ring@0.17.8",
+          "name": "ring",
+          "version": "0.17.8",
+          "publisher": "Brian Smith",
+          "purl": "pkg:cargo/ring@0.17.8",
+          "components": [
+            {
+              "type": "file",
+              "name": "lib/libring.a",
+              "hashes": [
+                { "alg": "SHA-256", "content": "aa" },
+                { "alg": "SHA-512", "content": "bbcc" }
+              ]
+            },
+            { "type": "library", "bom-ref": "untrusted", "name": "untrusted" }
+          ]
+        },
+        { "type": "file", "bom-ref": "kernel", "name": "boot/kernel", "hashes": [{ "alg": "SHA-384", "content": "dd" }] },
+        { "type": "file", "bom-ref": "config", "name": "config.toml" }
+      ],
+      "dependencies": [
+        { "ref": "fw", "dependsOn": ["pkg:cargo/ring@0.17.8", "kernel"] }
+      ]
+    }"#;
+
+    fn roles(entity: &EntityEntry) -> Vec<u8> {
+        entity.role.iter().copied().collect()
+    }
+
+    fn files_of<'a>(tag: &'a ConciseSwidTag<'static>) -> Vec<&'a FileEntry<'static>> {
+        tag.payload_or_evidence
+            .iter()
+            .filter_map(|payload| match payload {
+                PayloadOrEvidence::Payload(payload) => payload.payload.resource_collection.as_ref(),
+                _ => None,
+            })
+            .filter_map(|collection| collection.path_elements_group.as_ref()?.file.as_ref())
+            .flat_map(|file| file.iter())
+            .collect()
+    }
+
+    fn links<'a>(tag: &'a ConciseSwidTag<'static>) -> Vec<(&'a str, &'a Rel<'static>)> {
+        tag.link
+            .iter()
+            .flat_map(|link| link.iter())
+            .map(|link| (&link.href[..], &link.rel))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_spdx_json() {
+        let tags = ConciseSwidTag::from_spdx_json(SPDX).unwrap();
+        assert_eq!(tags.len(), 3);
+
+        let firmware = &tags[0];
+        assert_eq!(
+            firmware.tag_id.as_str(),
+            Some("https://example.com/spdx/firmware-1.2#SPDXRef-firmware")
+        );
+        assert_eq!(firmware.tag_version, Integer(0));
+        assert_eq!(firmware.software_name, "firmware");
+        assert_eq!(firmware.software_version.as_deref(), Some("1.2"));
+
+        let entities: Vec<_> = firmware.entity.iter().collect();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].entity_name, "ACME Inc.");
+        assert_eq!(roles(entities[0]), vec![TAG_CREATOR, SOFTWARE_CREATOR]);
+
+        let meta = firmware.software_meta.as_ref().unwrap().as_one().unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Board firmware"));
+        assert_eq!(
+            meta.persistent_id.as_deref(),
+            Some("pkg:generic/acme/firmware@1.2")
+        );
+
+        let files = files_of(firmware);
+        assert_eq!(files.len(), 2);
+        let image = files[0].filesystem_item.as_ref().unwrap();
+        assert_eq!(image.location.as_deref(), Some("boot"));
+        assert_eq!(image.fs_name, "image.bin");
+        let hash = files[0].hash.as_ref().unwrap();
+        assert_eq!(hash.hash_alg_id, CoseAlgorithm::Sha256);
+        assert_eq!(hash.hash_value.as_ref(), [0xa1, 0xb2, 0xc3]);
+        assert_eq!(
+            files[1].filesystem_item.as_ref().unwrap().fs_name,
+            "LICENSE"
+        );
+        assert!(files[1].hash.is_none());
+
+        assert_eq!(
+            links(firmware),
+            vec![
+                (
+                    "swid:https://example.com/spdx/firmware-1.2#SPDXRef-crypto",
+                    &Rel::Requires
+                ),
+                (
+                    "swid:https://example.com/spdx/firmware-1.2#SPDXRef-debug",
+                    &Rel::Requires
+                ),
+            ]
+        );
+        let optional = firmware.link.as_ref().unwrap().iter().nth(1).unwrap();
+        assert_eq!(optional.r#use, Some(Use::Optional));
+
+        let crypto = &tags[1];
+        let entities: Vec<_> = crypto.entity.iter().collect();
+        assert_eq!(entities[0].entity_name, "ACME Inc.");
+        assert_eq!(roles(entities[0]), vec![TAG_CREATOR]);
+        assert_eq!(entities[1].entity_name, "Jane Doe");
+        assert_eq!(roles(entities[1]), vec![SOFTWARE_CREATOR]);
+        assert_eq!(
+            links(crypto),
+            vec![(
+                "swid:https://example.com/spdx/firmware-1.2#SPDXRef-firmware",
+                &Rel::Parent
+            )]
+        );
+        assert!(crypto.payload_or_evidence.is_none());
+        assert!(crypto.software_meta.is_none());
+
+        let debug = &tags[2];
+        let entities: Vec<_> = debug.entity.iter().collect();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].entity_name, "ACME Inc.");
+        assert!(debug.link.is_none());
+    }
+
+    #[test]
+    fn test_from_cyclonedx_json() {
+        let tags = ConciseSwidTag::from_cyclonedx_json(CYCLONEDX).unwrap();
+        let names: Vec<_> = tags.iter().map(|tag| tag.software_name.as_ref()).collect();
+        assert_eq!(names, vec!["firmware", "ring", "untrusted"]);
+
+        let serial = "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79";
+        let firmware = &tags[0];
+        assert_eq!(
+            firmware.tag_id.as_str(),
+            Some(format!("{serial}#fw").as_str())
+        );
+        assert_eq!(firmware.tag_version, Integer(3));
+
+        let entities: Vec<_> = firmware.entity.iter().collect();
+        assert_eq!(entities[0].entity_name, "Release Team");
+        assert_eq!(roles(entities[0]), vec![TAG_CREATOR]);
+        assert_eq!(entities[1].entity_name, "ACME Inc.");
+        assert_eq!(roles(entities[1]), vec![SOFTWARE_CREATOR]);
+
+        let requires = format!("swid:{serial}#pkg:cargo/ring@0.17.8");
+        assert_eq!(links(firmware), vec![(requires.as_str(), &Rel::Requires)]);
+
+        let names: Vec<_> = files_of(firmware)
+            .iter()
+            .map(|file| file.filesystem_item.as_ref().unwrap().fs_name.as_ref())
+            .collect();
+        assert_eq!(names, vec!["kernel", "config.toml"]);
+        let kernel = files_of(firmware)[0].hash.as_ref().unwrap();
+        assert_eq!(kernel.hash_alg_id, CoseAlgorithm::Sha384);
+
+        let ring = &tags[1];
+        assert_eq!(
+            ring.entity.iter().nth(1).unwrap().entity_name,
+            "Brian Smith"
+        );
+        assert_eq!(
+            ring.software_meta
+                .as_ref()
+                .unwrap()
+                .as_one()
+                .unwrap()
+                .persistent_id
+                .as_deref(),
+            Some("pkg:cargo/ring@0.17.8")
+        );
+        let component = format!("swid:{serial}#untrusted");
+        assert_eq!(links(ring), vec![(component.as_str(), &Rel::Component)]);
+        let library = files_of(ring);
+        assert_eq!(library.len(), 1);
+        let item = library[0].filesystem_item.as_ref().unwrap();
+        assert_eq!(item.location.as_deref(), Some("lib"));
+        assert_eq!(item.fs_name, "libring.a");
+        let hash = library[0].hash.as_ref().unwrap();
+        assert_eq!(hash.hash_alg_id, CoseAlgorithm::Sha512);
+        assert_eq!(hash.hash_value.as_ref(), [0xbb, 0xcc]);
+    }
+
+    fn property<'a>(component: &'a serde_json::Value, name: &str) -> Option<&'a str> {
+        component["properties"]
+            .as_array()?
+            .iter()
+            .find(|property| property["name"] == name)?["value"]
+            .as_str()
+    }
+
+    #[test]
+    fn test_coswid_to_cyclonedx_json() {
+        let tags = ConciseSwidTag::from_spdx_json(SPDX).unwrap();
+        let json = tags[0].to_cyclonedx_json().unwrap();
+        let bom: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(bom["specVersion"], "1.6");
+        assert!(bom.get("serialNumber").is_none());
+
+        let metadata = &bom["metadata"];
+        assert_eq!(metadata["authors"][0]["name"], "ACME Inc.");
+        let software = &metadata["component"];
+        assert_eq!(software["type"], "application");
+        assert_eq!(
+            software["bom-ref"],
+            "https://example.com/spdx/firmware-1.2#SPDXRef-firmware"
+        );
+        assert_eq!(software["name"], "firmware");
+        assert_eq!(software["version"], "1.2");
+        assert_eq!(software["supplier"]["name"], "ACME Inc.");
+        assert_eq!(software["description"], "Board firmware");
+        assert_eq!(software["purl"], "pkg:generic/acme/firmware@1.2");
+        assert_eq!(property(software, "coswid:tag-version"), Some("0"));
+
+        let files = bom["components"].as_array().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["type"], "file");
+        assert_eq!(files[0]["name"], "boot/image.bin");
+        assert_eq!(files[0]["hashes"][0]["alg"], "SHA-256");
+        assert_eq!(files[0]["hashes"][0]["content"], "a1b2c3");
+        assert_eq!(files[1]["name"], "LICENSE");
+        assert!(files[1].get("hashes").is_none());
+
+        // Reading the BOM back gives the tag and its files
+        let tags = ConciseSwidTag::from_cyclonedx_json(&json).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].software_name, "firmware");
+        let hash = files_of(&tags[0])[0].hash.clone().unwrap();
+        assert_eq!(hash.hash_alg_id, CoseAlgorithm::Sha256);
+        assert_eq!(hash.hash_value.as_ref(), [0xa1, 0xb2, 0xc3]);
+    }
+
+    #[test]
+    fn test_coswid_to_cyclonedx_json_properties() {
+        let mut tag = ConciseSwidTag::from_cyclonedx_json(CYCLONEDX)
+            .unwrap()
+            .remove(1);
+        tag.tag_id =
+            TextOrBytes::Bytes(crate::TaggedBytes::from(crate::Bytes::from(vec![0x31; 16])));
+        tag.patch = Some(true);
+        if let Some(PayloadOrEvidence::Payload(payload)) = &mut tag.payload_or_evidence {
+            let collection = payload.payload.resource_collection.as_mut().unwrap();
+            let group = collection.path_elements_group.as_mut().unwrap();
+            let OneOrMore::One(file) = group.file.as_mut().unwrap() else {
+                panic!("expected a single file");
+            };
+            file.size = Some(Integer(42));
+            file.hash.as_mut().unwrap().hash_alg_id = CoseAlgorithm::SHAKE256;
+        }
+
+        let bom: serde_json::Value =
+            serde_json::from_str(&tag.to_cyclonedx_json().unwrap()).unwrap();
+        assert_eq!(
+            bom["serialNumber"],
+            "urn:uuid:31313131-3131-3131-3131-313131313131"
+        );
+        let software = &bom["metadata"]["component"];
+        assert_eq!(software["supplier"]["name"], "Brian Smith");
+        assert_eq!(property(software, "coswid:patch"), Some("true"));
+
+        let file = &bom["components"][0];
+        assert_eq!(file["name"], "lib/libring.a");
+        assert!(file.get("hashes").is_none());
+        assert_eq!(property(file, "coswid:size"), Some("42"));
+        assert_eq!(
+            property(file, "coswid:hash"),
+            Some(format!("{}:bbcc", CoseAlgorithm::SHAKE256).as_str())
+        );
+    }
+
+    #[test]
+    fn test_comid_to_cyclonedx_json() {
+        let template = r#"{
+  "tag-identity": {
+    "id": "43bbe37f-2e61-4b33-aed3-53cff1428b16",
+    "version": 2
+  },
+  "triples": {
+    "reference-values": [
+      {
+        "environment": {
+          "class": {
+            "id": {
+              "type": "oid",
+              "value": "2.16.840.1.113741.1.2.3"
+            },
+            "vendor": "ACME",
+            "model": "RoadRunner"
+          }
+        },
+        "measurements": [
+          {
+            "key": {
+              "type": "string",
+              "value": "boot"
+            },
+            "value": {
+              "version": {
+                "value": "1.0.0",
+                "scheme": "semver"
+              },
+              "svn": {
+                "type": "exact-value",
+                "value": 3
+              },
+              "digests": [
+                "sha-256;h0KPxSKAPTEGXnvOPPA/5HUJZjHl4Hu9eg/eYMTPJcc=",
+                "sha-256-32;AAECAw=="
+              ]
+            }
+          },
+          {
+            "value": {
+              "serial-number": "C02X1234"
+            }
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+        let comid = ConciseMidTag::from_veraison_json(template).unwrap();
+        let bom: serde_json::Value =
+            serde_json::from_str(&comid.to_cyclonedx_json().unwrap()).unwrap();
+
+        assert_eq!(
+            bom["serialNumber"],
+            "urn:uuid:43bbe37f-2e61-4b33-aed3-53cff1428b16"
+        );
+        assert_eq!(
+            property(&bom["metadata"], "corim:tag-id"),
+            Some("43bbe37f-2e61-4b33-aed3-53cff1428b16")
+        );
+        assert_eq!(property(&bom["metadata"], "corim:tag-version"), Some("2"));
+
+        let components = bom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 1);
+        let firmware = &components[0];
+        assert_eq!(firmware["type"], "firmware");
+        assert_eq!(firmware["bom-ref"], "reference-0-0");
+        assert_eq!(firmware["supplier"]["name"], "ACME");
+        assert_eq!(firmware["name"], "RoadRunner");
+        assert_eq!(firmware["version"], "1.0.0");
+        assert_eq!(
+            firmware["hashes"],
+            serde_json::json!([{
+                "alg": "SHA-256",
+                "content": "87428fc522803d31065e7bce3cf03fe475096631e5e07bbd7a0fde60c4cf25c7"
+            }])
+        );
+        assert_eq!(property(firmware, "corim:mkey"), Some("boot"));
+        assert_eq!(property(firmware, "corim:svn"), Some("3"));
+        assert_eq!(property(firmware, "corim:version-scheme"), Some("semver"));
+        assert_eq!(
+            property(firmware, "corim:digest"),
+            Some("sha-256-32:00010203")
+        );
+        assert!(property(firmware, "corim:class-id")
+            .unwrap()
+            .contains("2.16.840.1.113741.1.2.3"));
+    }
+
+    #[test]
+    fn test_sbom_errors() {
+        let err = ConciseSwidTag::from_spdx_json("{").unwrap_err();
+        assert!(err.to_string().starts_with("invalid SBOM JSON"));
+
+        let err = ConciseSwidTag::from_spdx_json(r#"{"spdxVersion": "SPDX-3.0"}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid spdxVersion in SBOM: expected SPDX 2, got \"SPDX-3.0\""
+        );
+
+        let err = ConciseSwidTag::from_cyclonedx_json(r#"{"bomFormat": "SPDX"}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid bomFormat in SBOM: expected CycloneDX, got \"SPDX\""
+        );
+
+        let anonymous =
+            r#"{"bomFormat": "CycloneDX", "components": [{"type": "library", "name": "anon"}]}"#;
+        let err = ConciseSwidTag::from_cyclonedx_json(anonymous).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid supplier in SBOM: anon has no supplier and the SBOM has no author to name as the tag creator"
+        );
+
+        let checksum = SPDX.replace("a1b2c3", "xyz");
+        let err = ConciseSwidTag::from_spdx_json(&checksum).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid checksum in SBOM: expected hex, got \"xyz\""
+        );
+    }
+}
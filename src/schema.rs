@@ -0,0 +1,1098 @@
+// SPDX-License-Identifier: MIT
+
+//! JSON Schemas for the JSON representation of CoRIMs and their tags
+//!
+//! The schemas describe the JSON the serde implementations of this crate produce and accept:
+//! maps keyed by their CBOR keys as strings (`"0"`, `"1"`, ...) or, for the CoMID
+//! environment and measurement maps, by name (`"class"`, `"mval"`, ...), tagged values written
+//! as `{"type": <name>, "value": <value>}`, and byte strings in unpadded base64url. Besides the
+//! structure, they capture the constraints of the CDDL that the types do not enforce, such as
+//! the arrays that must not be empty and the maps that need at least one entry.
+//!
+//! Each schema is a JSON Schema (draft 2020-12) document whose definitions are named after the
+//! CDDL rules of the CoRIM and CoSWID specifications:
+//!
+//! ```rust
+//! use corim_rs::schema;
+//!
+//! let schema = schema::concise_mid_tag();
+//! assert_eq!(schema["$ref"], "#/$defs/concise-mid-tag");
+//! assert!(schema["$defs"]["environment-map"].is_object());
+//! ```
+//!
+//! Extension points are left open: the integer keys of extensible maps and the extension
+//! alternatives of type choices accept any JSON value.
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use serde_json::{json, Map, Value};
+
+/// The JSON Schema dialect of the schemas
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The schema of a `corim-map`, the value of an `unsigned-corim`
+pub fn corim_map() -> Value {
+    document("corim-map", "CoRIM")
+}
+
+/// The schema of a `concise-mid-tag`, the value of a `comid`
+pub fn concise_mid_tag() -> Value {
+    document("concise-mid-tag", "CoMID")
+}
+
+/// The schema of a `concise-swid-tag`, the value of a `coswid`
+pub fn concise_swid_tag() -> Value {
+    document("concise-swid-tag", "CoSWID")
+}
+
+/// The schema of a `concise-tl-tag`, the value of a `cotl`
+pub fn concise_tl_tag() -> Value {
+    document("concise-tl-tag", "CoTL")
+}
+
+/// A schema document validating `root`, with every definition
+fn document(root: &str, title: &str) -> Value {
+    json!({
+        "$schema": DIALECT,
+        "title": title,
+        "$ref": format!("#/$defs/{root}"),
+        "$defs": definitions(),
+    })
+}
+
+/// The keys of extension entries: integers, written as strings
+const EXTENSION_KEY: &str = "^-?[0-9]+$";
+
+/// Which entries a map accepts besides its own
+#[derive(Clone, Copy)]
+enum Extensions {
+    /// No other entries
+    Closed,
+    /// Extension entries with integer keys
+    Integer,
+    /// Extension entries with integer keys and the `lang` global attribute of CoSWID
+    Global,
+}
+
+/// A reference to a definition
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{name}") })
+}
+
+/// A value tagged with `typ`, written as `{"type": typ, "value": value}`
+fn tagged(typ: &str, value: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": typ },
+            "value": value,
+        },
+        "required": ["type", "value"],
+        "additionalProperties": false,
+    })
+}
+
+/// An array of at least one `items`, a `[+ items]` in CDDL
+fn non_empty(items: Value) -> Value {
+    json!({ "type": "array", "items": items, "minItems": 1 })
+}
+
+/// A single `item` or an array of at least one, a `one-or-more<item>` in CoSWID
+fn one_or_more(item: Value) -> Value {
+    json!({ "anyOf": [item.clone(), non_empty(item)] })
+}
+
+/// An array of the `elements`, in order, of which the first `required` must be present
+fn record(elements: Vec<Value>, required: usize) -> Value {
+    json!({
+        "type": "array",
+        "prefixItems": elements,
+        "items": false,
+        "minItems": required,
+    })
+}
+
+/// A map of `properties`, of which those in `required` must be present
+fn map(properties: Value, required: &[&str], extensions: Extensions) -> Value {
+    let mut properties = properties;
+    let mut schema = json!({
+        "type": "object",
+        "required": required,
+        "additionalProperties": false,
+    });
+    if let Extensions::Global = extensions {
+        properties["lang"] = json!({ "type": "string" });
+    }
+    if let Extensions::Integer | Extensions::Global = extensions {
+        schema["patternProperties"] = json!({ EXTENSION_KEY: {} });
+    }
+    schema["properties"] = properties;
+    schema
+}
+
+/// Adds the properties of `other` to the object `properties`
+fn merge(mut properties: Value, other: Value) -> Value {
+    if let (Value::Object(properties), Value::Object(other)) = (&mut properties, other) {
+        properties.extend(other);
+    }
+    properties
+}
+
+/// A value that is not a `{"type": ..., "value": ...}` object, the extension alternative of
+/// type choices told apart from the others by their "type"
+fn untyped() -> Value {
+    json!({ "not": { "type": "object", "required": ["type"] } })
+}
+
+/// Every definition, named after its CDDL rule
+fn definitions() -> Map<String, Value> {
+    let mut defs = Map::new();
+    core_definitions(&mut defs);
+    corim_definitions(&mut defs);
+    comid_definitions(&mut defs);
+    triples_definitions(&mut defs);
+    coswid_definitions(&mut defs);
+    defs.insert(
+        "concise-tl-tag".into(),
+        map(
+            json!({
+                "0": reference("tag-identity-map"),
+                "1": non_empty(reference("tag-identity-map")),
+                "2": reference("validity-map"),
+            }),
+            &["0", "1", "2"],
+            Extensions::Closed,
+        ),
+    );
+    defs
+}
+
+/// The definitions of the types of the `core` module
+fn core_definitions(defs: &mut Map<String, Value>) {
+    let base64url = "^[A-Za-z0-9_-]*$";
+    let entries = [
+        ("int", json!({ "type": "integer" })),
+        ("uint", json!({ "type": "integer", "minimum": 0 })),
+        ("text", json!({ "type": "string" })),
+        (
+            "bytes",
+            json!({ "type": "string", "pattern": base64url, "contentEncoding": "base64url" }),
+        ),
+        (
+            "uuid",
+            json!({
+                "type": "string",
+                "pattern": "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+            }),
+        ),
+        (
+            "ueid",
+            json!({ "type": "string", "pattern": base64url, "minLength": 10, "maxLength": 44 }),
+        ),
+        (
+            "oid",
+            json!({ "type": "string", "pattern": "^[0-2](\\.(0|[1-9][0-9]*))+$" }),
+        ),
+        (
+            "digest",
+            json!({ "type": "string", "pattern": "^[^;]+;[A-Za-z0-9_-]*$" }),
+        ),
+        ("tagged-uri", tagged("uri", reference("text"))),
+        ("tagged-time", tagged("time", reference("int"))),
+        ("tagged-uuid-type", tagged("uuid", reference("uuid"))),
+        ("tagged-oid-type", tagged("oid", reference("oid"))),
+        ("tagged-ueid-type", tagged("ueid", reference("ueid"))),
+        ("tagged-bytes", tagged("bytes", reference("bytes"))),
+        (
+            "text-or-bytes",
+            json!({ "anyOf": [reference("text"), reference("tagged-bytes")] }),
+        ),
+        (
+            "text-or-bytes-16",
+            json!({
+                "anyOf": [
+                    reference("text"),
+                    tagged(
+                        "bytes",
+                        json!({ "type": "string", "pattern": "^[A-Za-z0-9_-]{22}$" }),
+                    ),
+                ],
+            }),
+        ),
+        (
+            "label",
+            json!({ "anyOf": [reference("text"), reference("int")] }),
+        ),
+        ("version-scheme", reference("text")),
+        (
+            "hash-entry",
+            record(vec![reference("text"), reference("bytes")], 2),
+        ),
+        (
+            "cose-key",
+            map(
+                json!({
+                    "kty": reference("text"),
+                    "kid": reference("bytes"),
+                    "alg": reference("text"),
+                    "key_ops": non_empty(reference("text")),
+                    "base_iv": reference("bytes"),
+                    "crv": reference("text"),
+                    "x": reference("bytes"),
+                    "y": reference("bytes"),
+                    "d": reference("bytes"),
+                    "k": reference("bytes"),
+                }),
+                &["kty"],
+                Extensions::Closed,
+            ),
+        ),
+        (
+            "cose-key-set-or-key",
+            json!({ "anyOf": [reference("cose-key"), non_empty(reference("cose-key"))] }),
+        ),
+    ];
+    defs.extend(entries.map(|(name, schema)| (name.into(), schema)));
+}
+
+/// The definitions of the types of the `corim` module
+fn corim_definitions(defs: &mut Map<String, Value>) {
+    let entries = [
+        (
+            "corim-map",
+            map(
+                json!({
+                    "0": reference("corim-id-type-choice"),
+                    "1": non_empty(reference("concise-tag-type-choice")),
+                    "2": non_empty(reference("corim-locator-map")),
+                    "3": reference("profile-type-choice"),
+                    "4": reference("validity-map"),
+                    "5": non_empty(reference("corim-entity-map")),
+                }),
+                &["0", "1"],
+                Extensions::Closed,
+            ),
+        ),
+        (
+            "corim-id-type-choice",
+            json!({ "anyOf": [reference("text"), reference("tagged-uuid-type")] }),
+        ),
+        (
+            "concise-tag-type-choice",
+            json!({
+                "oneOf": [
+                    tagged("coswid", reference("concise-swid-tag")),
+                    tagged("comid", reference("concise-mid-tag")),
+                    tagged("cotl", reference("concise-tl-tag")),
+                ],
+            }),
+        ),
+        (
+            "corim-locator-map",
+            map(
+                json!({
+                    "0": non_empty(reference("tagged-uri")),
+                    "1": reference("digest"),
+                }),
+                &["0"],
+                Extensions::Closed,
+            ),
+        ),
+        (
+            "profile-type-choice",
+            json!({ "oneOf": [reference("tagged-uri"), reference("tagged-oid-type")] }),
+        ),
+        (
+            "validity-map",
+            map(
+                json!({
+                    "0": { "anyOf": [reference("int"), { "type": "null" }] },
+                    "1": reference("int"),
+                }),
+                &["1"],
+                Extensions::Closed,
+            ),
+        ),
+        (
+            "corim-entity-map",
+            map(
+                json!({
+                    "0": reference("text"),
+                    "1": reference("tagged-uri"),
+                    "2": non_empty(json!({ "enum": ["manifest-creator", "manifest-signer"] })),
+                }),
+                &["0", "2"],
+                Extensions::Integer,
+            ),
+        ),
+    ];
+    defs.extend(entries.map(|(name, schema)| (name.into(), schema)));
+}
+
+/// The definitions of the types of the `comid` module
+fn comid_definitions(defs: &mut Map<String, Value>) {
+    let triples = [
+        ("0", "reference-triple-record"),
+        ("1", "endorsed-triple-record"),
+        ("2", "identity-triple-record"),
+        ("3", "attest-key-triple-record"),
+        ("4", "domain-dependency-triple-record"),
+        ("5", "domain-membership-triple-record"),
+        ("6", "coswid-triple-record"),
+        ("8", "conditional-endorsement-series-triple-record"),
+        ("10", "conditional-endorsement-triple-record"),
+    ]
+    .map(|(key, record)| (key.into(), non_empty(reference(record))));
+    let mut triples_map = map(
+        Value::Object(triples.into_iter().collect()),
+        &[],
+        Extensions::Integer,
+    );
+    triples_map["minProperties"] = json!(1);
+
+    let entries = [
+        (
+            "concise-mid-tag",
+            map(
+                json!({
+                    "0": reference("text"),
+                    "1": reference("tag-identity-map"),
+                    "2": non_empty(reference("comid-entity-map")),
+                    "3": non_empty(reference("linked-tag-map")),
+                    "4": reference("triples-map"),
+                }),
+                &["1", "4"],
+                Extensions::Integer,
+            ),
+        ),
+        (
+            "tag-identity-map",
+            map(
+                json!({
+                    "0": reference("tag-id-type-choice"),
+                    "1": reference("uint"),
+                }),
+                &["0"],
+                Extensions::Closed,
+            ),
+        ),
+        (
+            "tag-id-type-choice",
+            json!({ "anyOf": [reference("text"), reference("tagged-uuid-type")] }),
+        ),
+        (
+            "comid-entity-map",
+            map(
+                json!({
+                    "31": reference("text"),
+                    "32": reference("tagged-uri"),
+                    "33": non_empty(json!({ "enum": ["tag-creator", "creator", "maintainer"] })),
+                }),
+                &["31", "33"],
+                Extensions::Integer,
+            ),
+        ),
+        (
+            "linked-tag-map",
+            map(
+                json!({
+                    "0": reference("tag-id-type-choice"),
+                    "1": { "enum": ["supplements", "replaces"] },
+                }),
+                &["0", "1"],
+                Extensions::Closed,
+            ),
+        ),
+        ("triples-map", triples_map),
+    ];
+    defs.extend(entries.map(|(name, schema)| (name.into(), schema)));
+}
+
+/// The definitions of the types of the `triples` module
+fn triples_definitions(defs: &mut Map<String, Value>) {
+    let measurements = || non_empty(reference("measurement-map"));
+    let keys = || non_empty(reference("crypto-key-type-choice"));
+    let environment_record = || record(vec![reference("environment-map"), measurements()], 2);
+    let key_record = || {
+        record(
+            vec![
+                reference("environment-map"),
+                keys(),
+                json!({ "anyOf": [reference("triples-record-condition"), { "type": "null" }] }),
+            ],
+            2,
+        )
+    };
+    let domain_record = || {
+        record(
+            vec![
+                reference("domain-type-choice"),
+                non_empty(reference("environment-map")),
+            ],
+            2,
+        )
+    };
+
+    let mut environment_map = map(
+        json!({
+            "class": reference("class-map"),
+            "instance": reference("instance-id-type-choice"),
+            "group": reference("group-id-type-choice"),
+        }),
+        &[],
+        Extensions::Closed,
+    );
+    environment_map["minProperties"] = json!(1);
+
+    let mut class_map = map(
+        json!({
+            "class-id": reference("class-id-type-choice"),
+            "vendor": reference("text"),
+            "model": reference("text"),
+            "layer": reference("uint"),
+            "index": reference("uint"),
+        }),
+        &[],
+        Extensions::Closed,
+    );
+    class_map["minProperties"] = json!(1);
+
+    let mut measurement_values_map = map(
+        json!({
+            "version": reference("version-map"),
+            "svn": reference("svn-type-choice"),
+            "digests": non_empty(reference("digest")),
+            "flags": reference("flags-map"),
+            "raw-value": reference("raw-value-type-choice"),
+            "raw-value-mask": reference("bytes"),
+            "mac-addr": reference("mac-addr-type-choice"),
+            "ip-addr": reference("ip-addr-type-choice"),
+            "serial-number": reference("text"),
+            "ueid": reference("ueid"),
+            "uuid": reference("uuid"),
+            "name": reference("text"),
+            "cryptokeys": keys(),
+            "integrity-registers": reference("integrity-registers"),
+        }),
+        &[],
+        Extensions::Integer,
+    );
+    measurement_values_map["minProperties"] = json!(1);
+    measurement_values_map["dependentRequired"] = json!({ "raw-value-mask": ["raw-value"] });
+
+    let flags = [
+        "is-configured",
+        "is-secure",
+        "is-recovery",
+        "is-debug",
+        "is-replay-protected",
+        "is-integrity-protected",
+        "is-runtime-meas",
+        "is-immutable",
+        "is-tcb",
+        "is-confidentiality-protected",
+    ]
+    .map(|flag| (flag.into(), json!({ "type": "boolean" })));
+
+    let mut condition = map(
+        json!({
+            "0": reference("measured-element-type-choice"),
+            "1": keys(),
+        }),
+        &[],
+        Extensions::Closed,
+    );
+    condition["minProperties"] = json!(1);
+
+    let entries = [
+        ("reference-triple-record", environment_record()),
+        ("endorsed-triple-record", environment_record()),
+        ("identity-triple-record", key_record()),
+        ("attest-key-triple-record", key_record()),
+        ("domain-dependency-triple-record", domain_record()),
+        ("domain-membership-triple-record", domain_record()),
+        (
+            "coswid-triple-record",
+            record(
+                vec![
+                    reference("environment-map"),
+                    non_empty(reference("text-or-bytes-16")),
+                ],
+                2,
+            ),
+        ),
+        (
+            "conditional-endorsement-series-triple-record",
+            record(
+                vec![
+                    reference("stateful-environment-record"),
+                    non_empty(reference("conditional-series-record")),
+                ],
+                2,
+            ),
+        ),
+        ("stateful-environment-record", environment_record()),
+        (
+            "conditional-series-record",
+            record(vec![measurements(), measurements()], 2),
+        ),
+        (
+            "conditional-endorsement-triple-record",
+            record(
+                vec![
+                    non_empty(reference("stateful-environment-record")),
+                    non_empty(reference("endorsed-triple-record")),
+                ],
+                2,
+            ),
+        ),
+        ("triples-record-condition", condition),
+        (
+            "domain-type-choice",
+            json!({
+                "anyOf": [
+                    reference("uint"),
+                    reference("text"),
+                    reference("tagged-uuid-type"),
+                    reference("tagged-oid-type"),
+                ],
+            }),
+        ),
+        ("environment-map", environment_map),
+        ("class-map", class_map),
+        (
+            "class-id-type-choice",
+            json!({
+                "anyOf": [
+                    reference("tagged-oid-type"),
+                    reference("tagged-uuid-type"),
+                    reference("tagged-bytes"),
+                    untyped(),
+                ],
+            }),
+        ),
+        (
+            "instance-id-type-choice",
+            json!({
+                "anyOf": [
+                    reference("tagged-ueid-type"),
+                    reference("tagged-uuid-type"),
+                    reference("crypto-key-type-choice"),
+                ],
+            }),
+        ),
+        (
+            "group-id-type-choice",
+            json!({ "oneOf": [reference("tagged-uuid-type"), reference("tagged-bytes")] }),
+        ),
+        (
+            "crypto-key-type-choice",
+            json!({
+                "oneOf": [
+                    tagged("pkix-base64-key", reference("text")),
+                    tagged("pkix-base64-cert", reference("text")),
+                    tagged("pkix-base64-cert-path", reference("text")),
+                    tagged("cose-key", reference("cose-key-set-or-key")),
+                    tagged("thumbprint", reference("digest")),
+                    tagged("cert-thumbprint", reference("digest")),
+                    tagged("cert-path-thumbprint", reference("digest")),
+                    tagged("pkix-asn1-der-cert", reference("bytes")),
+                    reference("tagged-bytes"),
+                ],
+            }),
+        ),
+        (
+            "measurement-map",
+            map(
+                json!({
+                    "mkey": reference("measured-element-type-choice"),
+                    "mval": reference("measurement-values-map"),
+                    "authorized-by": keys(),
+                }),
+                &["mval"],
+                Extensions::Closed,
+            ),
+        ),
+        (
+            "measured-element-type-choice",
+            json!({
+                "anyOf": [
+                    reference("tagged-oid-type"),
+                    reference("tagged-uuid-type"),
+                    reference("uint"),
+                    reference("text"),
+                    { "type": ["array", "boolean", "null"] },
+                    { "type": "object", "not": { "required": ["type"] } },
+                ],
+            }),
+        ),
+        ("measurement-values-map", measurement_values_map),
+        (
+            "version-map",
+            map(
+                json!({
+                    "version": reference("text"),
+                    "version-scheme": reference("version-scheme"),
+                }),
+                &["version"],
+                Extensions::Closed,
+            ),
+        ),
+        (
+            "svn-type-choice",
+            json!({
+                "anyOf": [
+                    reference("uint"),
+                    tagged("svn", reference("uint")),
+                    tagged("min-svn", reference("uint")),
+                ],
+            }),
+        ),
+        (
+            "flags-map",
+            map(
+                Value::Object(flags.into_iter().collect()),
+                &[],
+                Extensions::Integer,
+            ),
+        ),
+        (
+            "raw-value-type-choice",
+            json!({
+                "oneOf": [
+                    reference("tagged-bytes"),
+                    tagged(
+                        "masked-raw-value",
+                        record(vec![reference("bytes"), reference("bytes")], 2),
+                    ),
+                ],
+            }),
+        ),
+        (
+            "mac-addr-type-choice",
+            json!({
+                "type": "string",
+                "pattern": "^[0-9A-Fa-f]{1,2}(-[0-9A-Fa-f]{1,2}){5}((-[0-9A-Fa-f]{1,2}){2})?$",
+            }),
+        ),
+        (
+            "ip-addr-type-choice",
+            json!({
+                "type": "string",
+                "anyOf": [{ "format": "ipv4" }, { "format": "ipv6" }],
+            }),
+        ),
+        (
+            "integrity-registers",
+            json!({
+                "type": "object",
+                "propertyNames": { "pattern": "^([0-9]+|\".*\")$" },
+                "additionalProperties": non_empty(reference("digest")),
+                "minProperties": 1,
+            }),
+        ),
+    ];
+    defs.extend(entries.map(|(name, schema)| (name.into(), schema)));
+}
+
+/// The definitions of the types of the `coswid` module
+fn coswid_definitions(defs: &mut Map<String, Value>) {
+    // The unit variants of the link-entry code points are written as null
+    let code_point = || json!({ "anyOf": [{ "type": "null" }, reference("label")] });
+    let path_elements = || {
+        json!({
+            "16": one_or_more(reference("directory-entry")),
+            "17": one_or_more(reference("file-entry")),
+        })
+    };
+    let resource_collection = || {
+        merge(
+            path_elements(),
+            json!({
+                "18": one_or_more(reference("process-entry")),
+                "19": one_or_more(reference("resource-entry")),
+            }),
+        )
+    };
+    let filesystem_item = || {
+        json!({
+            "22": { "type": "boolean" },
+            "23": reference("text"),
+            "24": reference("text"),
+            "25": reference("text"),
+        })
+    };
+
+    let mut concise_swid_tag = map(
+        json!({
+            "0": reference("text-or-bytes"),
+            "12": reference("int"),
+            "8": { "type": "boolean" },
+            "9": { "type": "boolean" },
+            "11": { "type": "boolean" },
+            "1": reference("text"),
+            "13": reference("text"),
+            "14": reference("version-scheme"),
+            "10": reference("text"),
+            "5": one_or_more(reference("software-meta-entry")),
+            "2": one_or_more(reference("entity-entry")),
+            "4": one_or_more(reference("link-entry")),
+            "6": reference("payload-entry"),
+            "3": reference("evidence-entry"),
+        }),
+        &["0", "12", "1", "2"],
+        Extensions::Global,
+    );
+    // A tag carries either a payload or evidence
+    concise_swid_tag["not"] = json!({ "required": ["3", "6"] });
+
+    let mut software_meta = Map::new();
+    for key in 43..=57 {
+        let schema = match key {
+            48 => json!({ "type": "boolean" }),
+            50 => reference("text-or-bytes-16"),
+            _ => reference("text"),
+        };
+        software_meta.insert(format!("{key}"), schema);
+    }
+
+    let entries = [
+        ("concise-swid-tag", concise_swid_tag),
+        (
+            "software-meta-entry",
+            map(Value::Object(software_meta), &[], Extensions::Global),
+        ),
+        (
+            "entity-entry",
+            map(
+                json!({
+                    "31": reference("text"),
+                    "32": reference("tagged-uri"),
+                    "33": one_or_more(json!({ "type": "integer", "minimum": 0, "maximum": 255 })),
+                    "34": reference("hash-entry"),
+                }),
+                &["31", "33"],
+                Extensions::Global,
+            ),
+        ),
+        (
+            "link-entry",
+            map(
+                json!({
+                    "37": reference("text"),
+                    "38": reference("tagged-uri"),
+                    "10": reference("text"),
+                    "39": code_point(),
+                    "40": code_point(),
+                    "41": reference("text"),
+                    "42": code_point(),
+                    // Unlike the other maps, link-entry nests its extensions
+                    "extension": {
+                        "type": "object",
+                        "propertyNames": { "pattern": EXTENSION_KEY },
+                    },
+                }),
+                &["38", "40"],
+                Extensions::Global,
+            ),
+        ),
+        (
+            "payload-entry",
+            map(resource_collection(), &[], Extensions::Global),
+        ),
+        (
+            "evidence-entry",
+            map(
+                merge(
+                    resource_collection(),
+                    json!({
+                        "35": reference("tagged-time"),
+                        "36": reference("text"),
+                        "23": reference("text"),
+                    }),
+                ),
+                &[],
+                Extensions::Global,
+            ),
+        ),
+        (
+            "path-elements-group",
+            map(path_elements(), &[], Extensions::Closed),
+        ),
+        (
+            "directory-entry",
+            map(
+                merge(
+                    filesystem_item(),
+                    json!({ "path_elements": reference("path-elements-group") }),
+                ),
+                &[],
+                Extensions::Global,
+            ),
+        ),
+        (
+            "file-entry",
+            map(
+                merge(
+                    filesystem_item(),
+                    json!({
+                        "20": reference("uint"),
+                        "21": reference("text"),
+                        "7": reference("hash-entry"),
+                    }),
+                ),
+                &[],
+                Extensions::Global,
+            ),
+        ),
+        (
+            "process-entry",
+            map(
+                json!({
+                    "27": reference("text"),
+                    "28": reference("int"),
+                }),
+                &["27"],
+                Extensions::Global,
+            ),
+        ),
+        (
+            "resource-entry",
+            map(
+                json!({ "29": reference("text") }),
+                &["29"],
+                Extensions::Global,
+            ),
+        ),
+    ];
+    defs.extend(entries.map(|(name, schema)| (name.into(), schema)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        comid::ConciseMidTag,
+        core::{CoseAlgorithm, HashEntry},
+        corim::ConciseRimTypeChoice,
+        coswid::ConciseSwidTag,
+        cotl::ConciseTlTag,
+    };
+    use serde::{Deserialize, Serialize};
+
+    const UNSIGNED: &[u8] = include_bytes!("../testdata/corim/unsigned.cbor");
+
+    const COMID: &str = r#"{
+        "0": "en-GB",
+        "1": {"0": {"type": "uuid", "value": "31fb5abf-023e-4992-aa4e-95f9c1503bfa"}, "1": 2},
+        "2": [{"31": "ACME Inc.", "32": {"type": "uri", "value": "https://acme.example"},
+               "33": ["creator", "maintainer"]}],
+        "3": [{"0": "acme-roadrunner-base", "1": "supplements"}],
+        "4": {
+            "0": [[
+                {"class": {"class-id": {"type": "uuid", "value": "67b28b6c-34cc-40a1-8e3a-4a6e5b9f8a1c"},
+                           "layer": 1, "index": 2},
+                 "instance": {"type": "ueid", "value": "AQIDBAUGBwgJ"},
+                 "group": {"type": "bytes", "value": "AQID"}},
+                [{"mkey": {"type": "oid", "value": "2.16.840.1.113741.1.2.3"}, "mval": {
+                    "version": {"version": "1.2.3", "version-scheme": "semver"},
+                    "svn": {"type": "min-svn", "value": 2},
+                    "flags": {"is-debug": false, "is-secure": true},
+                    "raw-value": {"type": "masked-raw-value", "value": ["AQID", "_w8A"]},
+                    "mac-addr": "02-00-5E-10-00-01",
+                    "ip-addr": "192.0.2.1",
+                    "serial-number": "RR-0001",
+                    "ueid": "AQIDBAUGBwgJ",
+                    "uuid": "31fb5abf-023e-4992-aa4e-95f9c1503bfa",
+                    "name": "boot firmware",
+                    "integrity-registers": {
+                        "0": ["sha-256;XP_FZ_Dl8qdF9zaFHbFVlEHm8Tsj7aHpBm7izNZOxag"],
+                        "\"pcr-1\"": ["sha-256;XP_FZ_Dl8qdF9zaFHbFVlEHm8Tsj7aHpBm7izNZOxag"]
+                    },
+                    "-1": "extension"
+                 }, "authorized-by": [{"type": "thumbprint",
+                                       "value": "sha-256;XP_FZ_Dl8qdF9zaFHbFVlEHm8Tsj7aHpBm7izNZOxag"}]},
+                 {"mkey": 7, "mval": {"raw-value": {"type": "bytes", "value": "AQID"},
+                                      "raw-value-mask": "_w8A", "ip-addr": "2001:db8::1"}}]
+            ]],
+            "1": [[{"class": {"class-id": {"tag": 600, "value": "AQID"}, "vendor": "ACME Inc."}},
+                   [{"mval": {"svn": {"type": "svn", "value": 3}}}]]],
+            "2": [[{"instance": {"type": "uuid", "value": "31fb5abf-023e-4992-aa4e-95f9c1503bfa"}},
+                   [{"type": "cose-key", "value": {"kty": "EC2", "crv": "P-256",
+                                                   "x": "AQID", "y": "BAUG"}}],
+                   {"0": "firmware"}]],
+            "3": [[{"group": {"type": "uuid", "value": "31fb5abf-023e-4992-aa4e-95f9c1503bfa"}},
+                   [{"type": "pkix-base64-key", "value": "MFkwEwYHKoZIzj0CAQ"}]]],
+            "4": [[{"type": "uuid", "value": "31fb5abf-023e-4992-aa4e-95f9c1503bfa"},
+                   [{"class": {"model": "RoadRunner"}}]]],
+            "5": [[7, [{"class": {"model": "RoadRunner"}}]]],
+            "6": [[{"class": {"model": "RoadRunner"}},
+                   ["acme-roadrunner-fw", {"type": "bytes", "value": "AAECAwQFBgcICQoLDA0ODw"}]]],
+            "8": [[[{"class": {"model": "RoadRunner"}}, [{"mval": {"name": "selected"}}]],
+                   [[[{"mval": {"svn": 1}}], [{"mval": {"name": "added"}}]]]]],
+            "10": [[[[{"class": {"model": "RoadRunner"}}, [{"mval": {"svn": 1}}]]],
+                    [[{"class": {"model": "RoadRunner"}}, [{"mval": {"name": "endorsed"}}]]]]]
+        },
+        "-70000": {"extension": true}
+    }"#;
+
+    const COSWID: &str = r#"{
+        "0": {"type": "bytes", "value": "AAECAwQFBgcICQoLDA0ODw"},
+        "12": 3,
+        "8": true,
+        "1": "RoadRunner Firmware",
+        "13": "1.0.0",
+        "14": "semver",
+        "5": {"43": "active", "48": false, "50": "acme-build"},
+        "2": [{"31": "ACME Inc.", "32": {"type": "uri", "value": "https://acme.example"},
+               "33": [1, 2]}],
+        "4": {"38": {"type": "uri", "value": "https://acme.example/base"}, "40": "parent",
+              "extension": {}},
+        "6": {
+            "16": {"24": "firmware", "path_elements": {"17": {"24": "boot.bin", "20": 4096}}},
+            "17": [{"24": "app.bin", "21": "1.0"}],
+            "18": {"27": "init", "28": 1},
+            "19": {"29": "memory"}
+        },
+        "lang": "en-GB",
+        "-5": "extension"
+    }"#;
+
+    const COTL: &str = r#"{
+        "0": {"0": "acme-trust-list", "1": 1},
+        "1": [{"0": "acme-roadrunner-comid"},
+              {"0": {"type": "uuid", "value": "31fb5abf-023e-4992-aa4e-95f9c1503bfa"}}],
+        "2": {"0": 1704067200, "1": 1893456000}
+    }"#;
+
+    /// Compiles `schema`, failing if it is not a valid schema
+    fn validator(schema: &Value) -> jsonschema::Validator {
+        jsonschema::validator_for(schema).unwrap()
+    }
+
+    /// Asserts that `schema` accepts `value`
+    fn assert_valid(schema: &Value, value: &Value) {
+        let errors: Vec<String> = validator(schema)
+            .iter_errors(value)
+            .map(|e| format!("{} at {}", e, e.instance_path()))
+            .collect();
+        assert!(errors.is_empty(), "{value} is invalid: {errors:#?}");
+    }
+
+    /// Asserts that `json` is read by the serde implementation of `T` and that `schema`
+    /// accepts both `json` and the JSON `T` writes back
+    fn check<'a, T: Serialize + Deserialize<'a>>(schema: &Value, json: &'a str) {
+        let value: T = serde_json::from_str(json).unwrap();
+        assert_valid(schema, &serde_json::from_str(json).unwrap());
+        assert_valid(schema, &serde_json::to_value(&value).unwrap());
+    }
+
+    /// Asserts that `schema` rejects `json` after `edit`
+    fn assert_invalid(schema: &Value, json: &str, edit: impl FnOnce(&mut Value)) {
+        let mut value: Value = serde_json::from_str(json).unwrap();
+        edit(&mut value);
+        assert!(!validator(schema).is_valid(&value), "{value} is valid");
+    }
+
+    #[test]
+    fn test_schemas() {
+        for schema in [
+            corim_map(),
+            concise_mid_tag(),
+            concise_swid_tag(),
+            concise_tl_tag(),
+        ] {
+            assert_eq!(schema["$schema"], DIALECT);
+            let root = schema["$ref"].as_str().unwrap();
+            assert!(schema["$defs"][&root["#/$defs/".len()..]].is_object());
+            jsonschema::meta::validate(&schema).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_unsigned_corim() {
+        let corim: ConciseRimTypeChoice = crate::cbor::from_slice(UNSIGNED).unwrap();
+        let corim = corim.as_unsigned_corim_map().unwrap();
+        assert_valid(&corim_map(), &serde_json::to_value(&corim).unwrap());
+
+        for tag in &corim.tags {
+            if let Some(comid) = tag.as_ref_comid() {
+                assert_valid(&concise_mid_tag(), &serde_json::to_value(comid).unwrap());
+            }
+            if let Some(coswid) = tag.as_ref_coswid() {
+                assert_valid(&concise_swid_tag(), &serde_json::to_value(coswid).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_tags() {
+        check::<ConciseMidTag>(&concise_mid_tag(), COMID);
+        check::<ConciseSwidTag>(&concise_swid_tag(), COSWID);
+        check::<ConciseTlTag>(&concise_tl_tag(), COTL);
+
+        // Hash entries are only checked as written, as they are not read back from JSON
+        let hash = HashEntry::new(CoseAlgorithm::Sha256, vec![0x5c; 32].into());
+        let mut coswid: Value = serde_json::from_str(COSWID).unwrap();
+        coswid["2"][0]["34"] = serde_json::to_value(&hash).unwrap();
+        coswid["6"]["17"][0]["7"] = serde_json::to_value(&hash).unwrap();
+        assert_valid(&concise_swid_tag(), &coswid);
+    }
+
+    #[test]
+    fn test_constraints() {
+        let comid = concise_mid_tag();
+        let reference = "/4/0/0";
+        // Non-empty arrays and maps
+        assert_invalid(&comid, COMID, |v| v["2"] = json!([]));
+        assert_invalid(&comid, COMID, |v| v["4"] = json!({}));
+        assert_invalid(&comid, COMID, |v| {
+            *v.pointer_mut(&format!("{reference}/1")).unwrap() = json!([])
+        });
+        assert_invalid(&comid, COMID, |v| {
+            *v.pointer_mut(&format!("{reference}/0")).unwrap() = json!({})
+        });
+        assert_invalid(&comid, COMID, |v| {
+            *v.pointer_mut(&format!("{reference}/1/1/mval")).unwrap() = json!({})
+        });
+        // Tagged values of the wrong type
+        assert_invalid(&comid, COMID, |v| {
+            v["1"]["0"] = json!({"type": "oid", "value": "31fb5abf-023e-4992-aa4e-95f9c1503bfa"})
+        });
+        assert_invalid(&comid, COMID, |v| {
+            *v.pointer_mut(&format!("{reference}/0/group/type")).unwrap() = json!("ueid")
+        });
+        // Unknown and missing entries
+        assert_invalid(&comid, COMID, |v| v["extension"] = json!(1));
+        assert_invalid(&comid, COMID, |v| {
+            v.pointer_mut(&format!("{reference}/0"))
+                .unwrap()
+                .as_object_mut()
+                .unwrap()
+                .insert("vendor".into(), json!("ACME Inc."));
+        });
+        assert_invalid(&comid, COMID, |v| {
+            v.pointer_mut(&format!("{reference}/1/1/mval"))
+                .unwrap()
+                .as_object_mut()
+                .unwrap()
+                .remove("raw-value");
+        });
+
+        let corim = corim_map();
+        let json = r#"{"0": "corim", "1": [{"type": "cotl", "value": {
+            "0": {"0": "tl"}, "1": [{"0": "comid"}], "2": {"1": 0}
+        }}]}"#;
+        assert_valid(&corim, &serde_json::from_str(json).unwrap());
+        assert_invalid(&corim, json, |v| v["1"] = json!([]));
+        assert_invalid(&corim, json, |v| v["1"][0]["type"] = json!("comid"));
+        assert_invalid(&corim, json, |v| {
+            v["1"][0].as_object_mut().unwrap().remove("type");
+        });
+
+        let coswid = concise_swid_tag();
+        assert_invalid(&coswid, COSWID, |v| v["3"] = json!({"36": "device"}));
+        assert_invalid(&coswid, COSWID, |v| v["2"] = json!([]));
+        assert_invalid(&coswid, COSWID, |v| v["name"] = json!("RoadRunner"));
+    }
+}
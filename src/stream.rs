@@ -0,0 +1,492 @@
+// SPDX-License-Identifier: MIT
+
+//! Streaming decoding of the tags of a CoRIM
+//!
+//! Decoding a [`Corim`](crate::corim::Corim) materializes every tag it carries, which for
+//! platform CoRIMs with thousands of CoMIDs is a lot of memory. A [`TagReader`] instead reads
+//! the CoRIM from an [`io::Read`](std::io::Read) and yields the entries of its `tags` array
+//! one at a time, so that only the tag being decoded is held in memory.
+//!
+//! The reader accepts unsigned CoRIMs (CBOR tag 501) and CoRIMs signed with COSE_Sign1 (CBOR
+//! tag 18) or COSE_Sign (CBOR tag 98), whose payload is read in place. The signatures, which
+//! follow the payload, are not read, so **the tags of a signed CoRIM are not verified**: use
+//! the [`cose`](crate::cose) module on the whole CoRIM where that matters. Detached payloads
+//! are not part of the CoRIM, and can be read with a [`TagReader`] of their own.
+//!
+//! Entries of the CoRIM map other than the `id` and `tags` are skipped. The `id` is available
+//! from [`TagReader::id`] when it precedes the tags, as it does in deterministic encoding.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::{fs::File, io::BufReader};
+//!
+//! use corim_rs::stream::TagReader;
+//!
+//! let reader = TagReader::new(BufReader::new(File::open("platform.corim")?))?;
+//! for tag in reader {
+//!     if let Some(comid) = tag?.as_ref_comid() {
+//!         println!("{:?}", comid.tag_identity.tag_id);
+//!     }
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::{self, Read};
+
+use crate::{ConciseTagTypeChoice, CorimIdTypeChoice, Result, StreamError};
+
+/// CBOR tag of an unsigned CoRIM map
+const UNSIGNED_CORIM: u64 = 501;
+/// CBOR tag of a COSE_Sign1 message
+const COSE_SIGN1: u64 = 18;
+/// CBOR tag of a COSE_Sign message
+const COSE_SIGN: u64 = 98;
+
+/// CoRIM map key of the tags
+const TAGS: u64 = 1;
+/// CoRIM map key of the identifier
+const ID: u64 = 0;
+
+/// Nesting limit for skipped items, as for ciborium
+const RECURSION_LIMIT: usize = 256;
+
+/// Reads the tags of a CoRIM one at a time
+///
+/// The reader is positioned at the start of the `tags` array by [`TagReader::new`], and each
+/// call to [`Iterator::next`] decodes the next tag. Iteration ends after the last tag, or after
+/// the first error.
+pub struct TagReader<R> {
+    input: Input<R>,
+    id: Option<CorimIdTypeChoice<'static>>,
+    /// Tags left to read, or `None` for an indefinite-length array
+    remaining: Option<u64>,
+    index: usize,
+    done: bool,
+}
+
+impl<R: Read> TagReader<R> {
+    /// Reads a CoRIM up to its tags
+    pub fn new(reader: R) -> Result<Self> {
+        let mut input = Input {
+            reader,
+            offset: 0,
+            peeked: None,
+        };
+
+        let mut tag = input.expect_tag("a CoRIM (CBOR tag 501, 18 or 98)")?;
+        if tag == COSE_SIGN1 || tag == COSE_SIGN {
+            input.expect(ARRAY, "a COSE message array")?;
+            input.skip(0)?;
+            input.skip(0)?;
+            let offset = input.offset;
+            match input.header()? {
+                (BYTES, Some(_)) => {}
+                (SIMPLE, Some(NULL)) => return Err(StreamError::DetachedPayload.into()),
+                _ => {
+                    return Err(StreamError::Unexpected {
+                        offset,
+                        expected: "a definite-length payload byte string",
+                    }
+                    .into())
+                }
+            }
+            tag = input.expect_tag("an unsigned CoRIM payload (CBOR tag 501)")?;
+        }
+        if tag != UNSIGNED_CORIM {
+            return Err(StreamError::Unexpected {
+                offset: input.offset,
+                expected: "a CoRIM (CBOR tag 501, 18 or 98)",
+            }
+            .into());
+        }
+
+        let mut entries = input.expect(MAP, "a CoRIM map")?;
+        let mut id = None;
+        loop {
+            if entries == Some(0) || (entries.is_none() && input.at_break()?) {
+                return Err(StreamError::MissingTags.into());
+            }
+            entries = entries.map(|count| count - 1);
+
+            match input.key()? {
+                Some(TAGS) => break,
+                Some(ID) => {
+                    id = Some(
+                        crate::cbor::from_reader(&mut input)
+                            .map_err(|err| StreamError::InvalidId(err.to_string()))?,
+                    )
+                }
+                _ => input.skip(0)?,
+            }
+        }
+
+        let remaining = input.expect(ARRAY, "an array of tags")?;
+
+        Ok(Self {
+            input,
+            id,
+            remaining,
+            index: 0,
+            done: false,
+        })
+    }
+
+    /// Returns the identifier of the CoRIM, if it precedes the tags
+    pub fn id(&self) -> Option<&CorimIdTypeChoice<'static>> {
+        self.id.as_ref()
+    }
+
+    fn next_tag(&mut self) -> Result<Option<ConciseTagTypeChoice<'static>>> {
+        match self.remaining {
+            Some(0) => return Ok(None),
+            Some(count) => self.remaining = Some(count - 1),
+            None if self.input.at_break()? => return Ok(None),
+            None => {}
+        }
+
+        let tag = crate::cbor::from_reader(&mut self.input).map_err(|err| StreamError::Tag {
+            index: self.index,
+            message: err.to_string(),
+        })?;
+        self.index += 1;
+
+        Ok(Some(tag))
+    }
+}
+
+impl<R: Read> Iterator for TagReader<R> {
+    type Item = Result<ConciseTagTypeChoice<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let next = self.next_tag().transpose();
+        self.done = !matches!(next, Some(Ok(_)));
+        next
+    }
+}
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+const NULL: u64 = 22;
+const BREAK: u8 = 0xff;
+
+/// A reader that can look at the next byte, and knows how far it has read
+struct Input<R> {
+    reader: R,
+    offset: usize,
+    peeked: Option<u8>,
+}
+
+impl<R: Read> Read for Input<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let count = match self.peeked.take() {
+            Some(byte) => {
+                buf[0] = byte;
+                1
+            }
+            None => self.reader.read(buf)?,
+        };
+        self.offset += count;
+
+        Ok(count)
+    }
+}
+
+impl<R: Read> Input<R> {
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut bytes = [0; N];
+        self.read_exact(&mut bytes)
+            .map_err(|err| StreamError::Io(err.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Returns `true`, consuming it, if the next byte is a break
+    fn at_break(&mut self) -> Result<bool> {
+        let [byte] = self.read_bytes()?;
+        if byte == BREAK {
+            return Ok(true);
+        }
+
+        self.peeked = Some(byte);
+        self.offset -= 1;
+        Ok(false)
+    }
+
+    /// Reads the major type and argument of the next item. The argument is `None` for
+    /// indefinite lengths.
+    fn header(&mut self) -> Result<(u8, Option<u64>)> {
+        let offset = self.offset;
+        let [initial] = self.read_bytes()?;
+        let argument = match initial & 0x1f {
+            info @ 0..=23 => Some(info as u64),
+            24 => Some(u8::from_be_bytes(self.read_bytes()?) as u64),
+            25 => Some(u16::from_be_bytes(self.read_bytes()?) as u64),
+            26 => Some(u32::from_be_bytes(self.read_bytes()?) as u64),
+            27 => Some(u64::from_be_bytes(self.read_bytes()?)),
+            31 if matches!(initial >> 5, BYTES..=MAP) => None,
+            _ => {
+                return Err(StreamError::Unexpected {
+                    offset,
+                    expected: "a well-formed CBOR item",
+                }
+                .into())
+            }
+        };
+
+        Ok((initial >> 5, argument))
+    }
+
+    /// Reads a CoRIM map key, returning its number. Keys may be integers or, as this crate
+    /// writes them, text.
+    fn key(&mut self) -> Result<Option<u64>> {
+        let offset = self.offset;
+        match self.header()? {
+            (UNSIGNED, key) => Ok(key),
+            (TEXT, Some(length)) if length <= 20 => {
+                let mut text = vec![0; length as usize];
+                self.read_exact(&mut text)
+                    .map_err(|err| StreamError::Io(err.to_string()))?;
+                Ok(std::str::from_utf8(&text)
+                    .ok()
+                    .and_then(|text| text.parse().ok()))
+            }
+            (NEGATIVE, _) => Ok(None),
+            (TEXT, length) => {
+                self.skip_content((TEXT, length), offset, 0)?;
+                Ok(None)
+            }
+            _ => Err(StreamError::Unexpected {
+                offset,
+                expected: "a CoRIM map key",
+            }
+            .into()),
+        }
+    }
+
+    /// Reads the header of an item of the given major type, returning its length
+    fn expect(&mut self, major: u8, expected: &'static str) -> Result<Option<u64>> {
+        let offset = self.offset;
+        match self.header()? {
+            (found, length) if found == major => Ok(length),
+            _ => Err(StreamError::Unexpected { offset, expected }.into()),
+        }
+    }
+
+    fn expect_tag(&mut self, expected: &'static str) -> Result<u64> {
+        let offset = self.offset;
+        self.expect(TAG, expected)?
+            .ok_or_else(|| StreamError::Unexpected { offset, expected }.into())
+    }
+
+    /// Reads past the next item without keeping it
+    fn skip(&mut self, depth: usize) -> Result<()> {
+        let offset = self.offset;
+        let header = self.header()?;
+        self.skip_content(header, offset, depth)
+    }
+
+    /// Reads past the content of an item whose header has been read
+    fn skip_content(
+        &mut self,
+        (major, argument): (u8, Option<u64>),
+        offset: usize,
+        depth: usize,
+    ) -> Result<()> {
+        if depth > RECURSION_LIMIT {
+            return Err(StreamError::Unexpected {
+                offset,
+                expected: "items nested less deeply",
+            }
+            .into());
+        }
+
+        let items = match (major, argument) {
+            (UNSIGNED | NEGATIVE | SIMPLE, _) => return Ok(()),
+            (TAG, _) => 1,
+            (BYTES | TEXT, Some(length)) => {
+                let skipped = io::copy(&mut self.take(length), &mut io::sink())
+                    .map_err(|err| StreamError::Io(err.to_string()))?;
+                if skipped != length {
+                    return Err(StreamError::Io("unexpected end of input".to_string()).into());
+                }
+                return Ok(());
+            }
+            (ARRAY, Some(length)) => length,
+            (MAP, Some(length)) => length.checked_mul(2).ok_or(StreamError::Unexpected {
+                offset,
+                expected: "a map of fewer entries",
+            })?,
+            // Indefinite-length strings, arrays and maps end with a break
+            _ => {
+                while !self.at_break()? {
+                    self.skip(depth + 1)?;
+                }
+                return Ok(());
+            }
+        };
+
+        for _ in 0..items {
+            self.skip(depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Corim, TaggedUnsignedCorimMap};
+
+    /// A reader that counts the bytes it hands out
+    struct Counting<'a> {
+        bytes: &'a [u8],
+        read: std::rc::Rc<std::cell::Cell<usize>>,
+    }
+
+    impl Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let count = self.bytes.read(buf)?;
+            self.read.set(self.read.get() + count);
+            Ok(count)
+        }
+    }
+
+    fn tags(corim: &Corim) -> Vec<ConciseTagTypeChoice<'static>> {
+        let map = match corim {
+            Corim::TaggedUnsignedCorimMap(map) => map.as_ref(),
+            Corim::SignedCorim(signed) => signed.as_ref().payload.as_ref(),
+            _ => unreachable!(),
+        };
+        let mut bytes = vec![];
+        ciborium::into_writer(&map.tags, &mut bytes).unwrap();
+        crate::cbor::from_reader(bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_tag_reader() {
+        for fixture in [
+            include_bytes!("../testdata/corim/unsigned.cbor").as_slice(),
+            include_bytes!("../testdata/corim/signed.cbor").as_slice(),
+        ] {
+            let corim: Corim = crate::cbor::from_slice(fixture).unwrap();
+            let expected = tags(&corim);
+
+            let reader = TagReader::new(fixture).unwrap();
+            assert!(reader.id().is_some());
+            let streamed: Vec<_> = reader.collect::<Result<_>>().unwrap();
+            assert_eq!(streamed, expected);
+        }
+    }
+
+    #[test]
+    fn test_tag_reader_is_lazy() {
+        let fixture = include_bytes!("../testdata/corim/unsigned.cbor");
+        let corim: Corim = crate::cbor::from_slice(fixture.as_slice()).unwrap();
+        let expected = tags(&corim);
+        assert!(expected.len() > 1);
+
+        let read = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut reader = TagReader::new(Counting {
+            bytes: fixture,
+            read: read.clone(),
+        })
+        .unwrap();
+        let before = read.get();
+
+        assert_eq!(reader.next().unwrap().unwrap(), expected[0]);
+        let mut first = vec![];
+        ciborium::into_writer(&expected[0], &mut first).unwrap();
+        assert_eq!(read.get() - before, first.len());
+
+        assert_eq!(reader.count(), expected.len() - 1);
+    }
+
+    #[test]
+    fn test_tag_reader_indefinite_lengths() {
+        let fixture = include_bytes!("../testdata/corim/unsigned.cbor");
+        let corim: Corim = crate::cbor::from_slice(fixture.as_slice()).unwrap();
+        let expected = tags(&corim);
+
+        // An indefinite-length map with an unknown entry before the tags, which are an
+        // indefinite-length array
+        let mut bytes = vec![0xd9, 0x01, 0xf5, 0xbf];
+        bytes.extend([0x18, 0x63, 0x5f, 0x41, 0x00, 0x41, 0x01, 0xff]);
+        bytes.extend([0x01, 0x9f]);
+        for tag in &expected {
+            ciborium::into_writer(tag, &mut bytes).unwrap();
+        }
+        bytes.extend([0xff, 0x00, 0x61, 0x78, 0xff]);
+
+        let reader = TagReader::new(bytes.as_slice()).unwrap();
+        assert!(reader.id().is_none());
+        let streamed: Vec<_> = reader.collect::<Result<_>>().unwrap();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_tag_reader_errors() {
+        let err = TagReader::new([0xa0].as_slice()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "expected a CoRIM (CBOR tag 501, 18 or 98) at byte 0 of the CoRIM"
+        );
+
+        let empty = TaggedUnsignedCorimMap::new(crate::CorimMap {
+            id: "empty".into(),
+            tags: vec![],
+            dependent_rims: None,
+            profile: None,
+            rim_validity: None,
+            entities: None,
+            extension: None,
+        });
+        let mut bytes = vec![];
+        ciborium::into_writer(&empty, &mut bytes).unwrap();
+        assert_eq!(TagReader::new(bytes.as_slice()).unwrap().count(), 0);
+
+        // Detached COSE_Sign1 payload
+        let detached = [0xd2, 0x84, 0x40, 0xa0, 0xf6, 0x40];
+        let err = TagReader::new(detached.as_slice()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "the payload of the signed CoRIM is detached and must be read on its own"
+        );
+
+        // A map without tags
+        let err = TagReader::new([0xd9, 0x01, 0xf5, 0xa1, 0x00, 0x61, 0x78].as_slice())
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "the CoRIM has no tags entry");
+
+        // A tag that is not a CoSWID, CoMID or CoTL, after which iteration stops
+        let mut reader =
+            TagReader::new([0xd9, 0x01, 0xf5, 0xa1, 0x01, 0x82, 0xc1, 0x00, 0x00].as_slice())
+                .unwrap();
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("failed to decode tag 0 of the CoRIM"));
+        assert!(reader.next().is_none());
+
+        // Truncated input
+        let fixture = include_bytes!("../testdata/corim/unsigned.cbor");
+        let mut reader = TagReader::new(&fixture[..fixture.len() / 2]).unwrap();
+        assert!(reader.any(|tag| tag.is_err()));
+    }
+}
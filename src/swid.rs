@@ -0,0 +1,1313 @@
+// SPDX-License-Identifier: MIT
+
+//! ISO/IEC 19770-2 SWID XML conversion
+//!
+//! CoSWID (RFC 9393) is a concise encoding of the software identification tags of
+//! ISO/IEC 19770-2:2015, and each CoSWID field is named after the SWID XML element or attribute
+//! it corresponds to. [`ConciseSwidTag::to_swid_xml`] writes a CoSWID tag as a
+//! `SoftwareIdentity` document and [`ConciseSwidTag::from_swid_xml`] reads one back, so that
+//! tags can be exchanged with asset management tools that only understand the XML form.
+//!
+//! Software metadata, entities and links become `Meta`, `Entity` and `Link` elements, and a
+//! payload or evidence becomes a `Payload` or `Evidence` element holding `Directory`, `File`,
+//! `Process` and `Resource` elements. Code points are written by the names RFC 9393 registers
+//! for them (`tagCreator`, `see-also`, `semver`, ...) and as numbers otherwise. File hashes are
+//! written in hex as a `hash` attribute in the namespace of the XML Encryption digest URI of
+//! their algorithm, e.g. `SHA256:hash` with `xmlns:SHA256="http://www.w3.org/2001/04/xmlenc#sha256"`,
+//! and entity thumbprints as hex, their SHA-2 algorithm being implied by their length. Binary
+//! tag identifiers and generators are written as UUID strings and are read back as text.
+//!
+//! Values with no counterpart on the other side are reported as [`SwidError::Unmappable`]
+//! rather than dropped: CoSWID extension entries and integer-labelled global attributes, hash
+//! algorithms other than SHA-256, SHA-384 and SHA-512, and XML elements and attributes that
+//! SWID does not define for CoSWID, such as signatures or vendor attributes on `Meta`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use corim_rs::coswid::ConciseSwidTag;
+//!
+//! let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+//! <SoftwareIdentity xmlns="http://standards.iso.org/iso/19770/-2/2015/schema.xsd" tagId="acme-rr-1.0" tagVersion="0" name="RoadRunner" version="1.0">
+//!   <Entity name="ACME Inc." regid="acme.example" role="tagCreator softwareCreator"/>
+//! </SoftwareIdentity>
+//! "#;
+//!
+//! let tag = ConciseSwidTag::from_swid_xml(xml).unwrap();
+//! assert_eq!(tag.software_name, "RoadRunner");
+//! assert_eq!(tag.to_swid_xml().unwrap(), xml);
+//! ```
+
+use std::collections::BTreeMap;
+
+use roxmltree::{Document, Node};
+
+use crate::{
+    core::{format_rfc3339, parse_rfc3339},
+    ConciseSwidTag, CoseAlgorithm, DirectoryEntry, EntityEntry, Evidence, EvidenceEntry,
+    ExtensionMap, FileEntry, FileSystemItem, GlobalAttributes, HashEntry, Integer, IntegerTime,
+    Label, LinkEntry, OneOrMore, Ownership, PathElementsGroup, Payload, PayloadEntry,
+    PayloadOrEvidence, ProcessEntry, Rel, ResourceCollection, ResourceEntry, Result,
+    SoftwareMetaEntry, SwidError, Text, TextOrBytes, TextOrBytesSized, Uri, Use, VersionScheme,
+};
+
+/// Namespace of the ISO/IEC 19770-2:2015 schema
+pub const SWID_NAMESPACE: &str = "http://standards.iso.org/iso/19770/-2/2015/schema.xsd";
+
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Hash algorithms with a SWID XML `hash` attribute, with the prefix and namespace it is
+/// written in
+const HASH_NAMESPACES: [(CoseAlgorithm, &str, &str); 3] = [
+    (
+        CoseAlgorithm::Sha256,
+        "SHA256",
+        "http://www.w3.org/2001/04/xmlenc#sha256",
+    ),
+    (
+        CoseAlgorithm::Sha384,
+        "SHA384",
+        "http://www.w3.org/2001/04/xmldsig-more#sha384",
+    ),
+    (
+        CoseAlgorithm::Sha512,
+        "SHA512",
+        "http://www.w3.org/2001/04/xmlenc#sha512",
+    ),
+];
+
+const ROLES: [(u8, &str); 6] = [
+    (1, "tagCreator"),
+    (2, "softwareCreator"),
+    (3, "aggregator"),
+    (4, "distributor"),
+    (5, "licensor"),
+    (6, "maintainer"),
+];
+
+impl ConciseSwidTag<'_> {
+    /// Writes the CoSWID tag as an ISO/IEC 19770-2 SWID XML document
+    pub fn to_swid_xml(&self) -> Result<String> {
+        let mut namespaces = BTreeMap::new();
+        let mut root = export_tag(self, &mut namespaces)?;
+
+        root.attributes
+            .insert(0, ("xmlns".into(), SWID_NAMESPACE.into()));
+        for (index, (prefix, namespace)) in namespaces.into_iter().enumerate() {
+            root.attributes
+                .insert(index + 1, (format!("xmlns:{prefix}"), namespace.into()));
+        }
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        root.write(&mut xml, 0);
+        Ok(xml)
+    }
+
+    /// Reads a CoSWID tag from an ISO/IEC 19770-2 SWID XML document
+    pub fn from_swid_xml(xml: &str) -> Result<ConciseSwidTag<'static>> {
+        let document = Document::parse(xml).map_err(|err| SwidError::Xml(err.to_string()))?;
+        let root = document.root_element();
+
+        if root.tag_name().name() != "SoftwareIdentity"
+            || !matches!(root.tag_name().namespace(), None | Some(SWID_NAMESPACE))
+        {
+            return Err(SwidError::Xml(format!(
+                "expected a SoftwareIdentity element, found {}",
+                root.tag_name().name()
+            ))
+            .into());
+        }
+
+        import_tag(root)
+    }
+}
+
+/// An element of the document being written
+struct Element {
+    name: &'static str,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            attributes: vec![],
+            children: vec![],
+        }
+    }
+
+    fn attribute(&mut self, name: impl Into<String>, value: impl ToString) {
+        self.attributes.push((name.into(), value.to_string()));
+    }
+
+    fn optional(&mut self, name: &'static str, value: Option<impl ToString>) {
+        if let Some(value) = value {
+            self.attribute(name, value);
+        }
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+
+        out.push_str(&indent);
+        out.push('<');
+        out.push_str(self.name);
+        for (name, value) in &self.attributes {
+            out.push(' ');
+            out.push_str(name);
+            out.push_str("=\"");
+            escape(value, out);
+            out.push('"');
+        }
+
+        if self.children.is_empty() {
+            out.push_str("/>\n");
+        } else {
+            out.push_str(">\n");
+            for child in &self.children {
+                child.write(out, depth + 1);
+            }
+            out.push_str(&indent);
+            out.push_str("</");
+            out.push_str(self.name);
+            out.push_str(">\n");
+        }
+    }
+}
+
+fn escape(value: &str, out: &mut String) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' => out.push_str("&#9;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn unmappable(what: impl Into<String>) -> SwidError {
+    SwidError::Unmappable(what.into())
+}
+
+fn invalid(field: &'static str, message: impl std::fmt::Display) -> SwidError {
+    SwidError::InvalidValue {
+        field,
+        message: message.to_string(),
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(text: &str, field: &'static str) -> Result<Vec<u8>> {
+    let error = || invalid(field, format!("expected hex, got \"{text}\""));
+    if text.len() % 2 != 0 {
+        return Err(error().into());
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|index| {
+            text.get(index..index + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| error().into())
+        })
+        .collect()
+}
+
+fn uuid_text(bytes: &[u8], what: &str) -> Result<String> {
+    uuid::Uuid::from_slice(bytes)
+        .map(|uuid| uuid.to_string())
+        .map_err(|_| unmappable(format!("{what} of {} bytes", bytes.len())).into())
+}
+
+fn code_point_name(label: &Label, names: &[(u8, &str)]) -> String {
+    match label {
+        Label::Int(Integer(int)) => names
+            .iter()
+            .find(|(value, _)| i128::from(*value) == *int)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| int.to_string()),
+        Label::Text(text) => text.to_string(),
+    }
+}
+
+fn check_extensions(extensions: &Option<ExtensionMap>, what: &str) -> Result<()> {
+    match extensions {
+        Some(extensions) if !extensions.0.is_empty() => {
+            let keys: Vec<String> = extensions.0.keys().map(|key| key.to_string()).collect();
+            Err(unmappable(format!("{what} extension {}", keys.join(", "))).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn export_global_attributes(
+    element: &mut Element,
+    global_attributes: &Option<GlobalAttributes>,
+    what: &str,
+) -> Result<()> {
+    let Some(global_attributes) = global_attributes else {
+        return Ok(());
+    };
+
+    check_extensions(
+        &global_attributes.attributes,
+        &format!("{what} global attribute"),
+    )?;
+    element.optional("xml:lang", global_attributes.lang.as_ref());
+    Ok(())
+}
+
+fn export_tag(
+    tag: &ConciseSwidTag,
+    namespaces: &mut BTreeMap<&'static str, &'static str>,
+) -> Result<Element> {
+    check_extensions(&tag.extensions, "tag")?;
+
+    let mut element = Element::new("SoftwareIdentity");
+    element.attribute(
+        "tagId",
+        match &tag.tag_id {
+            TextOrBytes::Text(text) => text.to_string(),
+            TextOrBytes::Bytes(bytes) => uuid_text(bytes.as_ref(), "a binary tag id")?,
+        },
+    );
+    element.attribute("tagVersion", tag.tag_version);
+    element.optional("corpus", tag.corpus);
+    element.optional("patch", tag.patch);
+    element.optional("supplemental", tag.supplemental);
+    element.attribute("name", &tag.software_name);
+    element.optional("version", tag.software_version.as_ref());
+    element.optional("versionScheme", tag.version_scheme.as_ref());
+    element.optional("media", tag.media.as_ref());
+    export_global_attributes(&mut element, &tag.global_attributes, "tag")?;
+
+    for entity in tag.entity.iter() {
+        element.children.push(export_entity(entity)?);
+    }
+    for link in tag.link.iter().flat_map(OneOrMore::iter) {
+        element.children.push(export_link(link)?);
+    }
+    for meta in tag.software_meta.iter().flat_map(OneOrMore::iter) {
+        element.children.push(export_meta(meta)?);
+    }
+
+    match &tag.payload_or_evidence {
+        Some(PayloadOrEvidence::Payload(payload)) => {
+            let mut child = Element::new("Payload");
+            export_global_attributes(&mut child, &payload.payload.global_attributes, "payload")?;
+            check_extensions(&payload.payload.extension, "payload")?;
+            export_resources(&mut child, &payload.payload.resource_collection, namespaces)?;
+            element.children.push(child);
+        }
+        Some(PayloadOrEvidence::Evidence(evidence)) => {
+            let evidence = &evidence.evidence;
+            let mut child = Element::new("Evidence");
+            child.optional(
+                "date",
+                evidence
+                    .date
+                    .as_ref()
+                    .map(|date| format_rfc3339(date.0 .0 .0)),
+            );
+            child.optional("deviceId", evidence.device_id.as_ref());
+            child.optional("location", evidence.location.as_ref());
+            export_global_attributes(&mut child, &evidence.global_attributes, "evidence")?;
+            check_extensions(&evidence.extension, "evidence")?;
+            export_resources(&mut child, &evidence.resource_collection, namespaces)?;
+            element.children.push(child);
+        }
+        None => {}
+    }
+
+    Ok(element)
+}
+
+fn export_meta(meta: &SoftwareMetaEntry) -> Result<Element> {
+    check_extensions(&meta.extensions, "software meta")?;
+
+    let mut element = Element::new("Meta");
+    element.optional("activationStatus", meta.activation_status.as_ref());
+    element.optional("channelType", meta.channel_type.as_ref());
+    element.optional("colloquialVersion", meta.coloquial_version.as_ref());
+    element.optional("description", meta.description.as_ref());
+    element.optional("edition", meta.edition.as_ref());
+    element.optional("entitlementDataRequired", meta.entitlement_data_required);
+    element.optional("entitlementKey", meta.entitlement_key.as_ref());
+    element.optional(
+        "generator",
+        match &meta.generator {
+            Some(TextOrBytesSized::Text(text)) => Some(text.to_string()),
+            Some(TextOrBytesSized::Bytes(bytes)) => Some(uuid_text(bytes.as_ref(), "generator")?),
+            None => None,
+        },
+    );
+    element.optional("persistentId", meta.persistent_id.as_ref());
+    element.optional("product", meta.product.as_ref());
+    element.optional("productFamily", meta.product_family.as_ref());
+    element.optional("revision", meta.revision.as_ref());
+    element.optional("summary", meta.summary.as_ref());
+    element.optional("unspscCode", meta.unspsc_code.as_ref());
+    element.optional("unspscVersion", meta.unspsc_version.as_ref());
+    export_global_attributes(&mut element, &meta.global_attributes, "software meta")?;
+    Ok(element)
+}
+
+fn export_entity(entity: &EntityEntry) -> Result<Element> {
+    check_extensions(&entity.extensions, "entity")?;
+
+    let mut element = Element::new("Entity");
+    element.attribute("name", &entity.entity_name);
+    element.optional("regid", entity.reg_id.as_ref().map(|uri| uri.as_ref()));
+    element.attribute(
+        "role",
+        entity
+            .role
+            .iter()
+            .map(|role| code_point_name(&Label::Int((*role).into()), &ROLES))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    if let Some(thumbprint) = &entity.thumbprint {
+        if !HASH_NAMESPACES
+            .iter()
+            .any(|(alg, ..)| *alg == thumbprint.hash_alg_id)
+        {
+            return Err(unmappable(format!(
+                "an entity thumbprint using {:?}",
+                thumbprint.hash_alg_id
+            ))
+            .into());
+        }
+        element.attribute("thumbprint", encode_hex(&thumbprint.hash_value));
+    }
+    export_global_attributes(&mut element, &entity.global_attributes, "entity")?;
+    Ok(element)
+}
+
+fn export_link(link: &LinkEntry) -> Result<Element> {
+    check_extensions(&link.extension, "link")?;
+
+    let mut element = Element::new("Link");
+    element.optional("artifact", link.artifact.as_ref());
+    element.attribute("href", link.href.as_ref());
+    element.optional("media", link.media.as_ref());
+    element.optional(
+        "ownership",
+        link.ownership.as_ref().map(|ownership| match ownership {
+            Ownership::Abandon => "abandon".to_string(),
+            Ownership::Private => "private".to_string(),
+            Ownership::Shared => "shared".to_string(),
+            Ownership::IntOrText(label) => code_point_name(label, &[]),
+        }),
+    );
+    element.attribute(
+        "rel",
+        match &link.rel {
+            Rel::IntOrText(label) => code_point_name(label, &[]),
+            rel => REL_NAMES
+                .iter()
+                .find(|(value, _)| value == rel)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_default(),
+        },
+    );
+    element.optional("type", link.media_type.as_ref());
+    element.optional(
+        "use",
+        link.r#use.as_ref().map(|r#use| match r#use {
+            Use::Optional => "optional".to_string(),
+            Use::Required => "required".to_string(),
+            Use::Recommended => "recommended".to_string(),
+            Use::IntOrText(label) => code_point_name(label, &[]),
+        }),
+    );
+    export_global_attributes(&mut element, &link.global_attributes, "link")?;
+    Ok(element)
+}
+
+const REL_NAMES: [(Rel<'static>, &str); 11] = [
+    (Rel::Ancestor, "ancestor"),
+    (Rel::Component, "component"),
+    (Rel::Feature, "feature"),
+    (Rel::InstallationMedia, "installationmedia"),
+    (Rel::PackageInstaller, "packageinstaller"),
+    (Rel::Parent, "parent"),
+    (Rel::Patches, "patches"),
+    (Rel::Requires, "requires"),
+    (Rel::SeeAlso, "see-also"),
+    (Rel::Supersedes, "supersedes"),
+    (Rel::Supplemental, "supplemental"),
+];
+
+fn export_resources(
+    element: &mut Element,
+    resources: &Option<ResourceCollection>,
+    namespaces: &mut BTreeMap<&'static str, &'static str>,
+) -> Result<()> {
+    let Some(resources) = resources else {
+        return Ok(());
+    };
+    check_extensions(&resources.extensions, "resource collection")?;
+
+    if let Some(group) = &resources.path_elements_group {
+        export_path_elements(element, group, namespaces)?;
+    }
+    for process in resources.process.iter().flat_map(OneOrMore::iter) {
+        check_extensions(&process.extension, "process")?;
+        let mut child = Element::new("Process");
+        child.attribute("name", &process.process_name);
+        child.optional("pid", process.pid);
+        export_global_attributes(&mut child, &process.global_attributes, "process")?;
+        element.children.push(child);
+    }
+    for resource in resources.resource.iter().flat_map(OneOrMore::iter) {
+        check_extensions(&resource.extension, "resource")?;
+        let mut child = Element::new("Resource");
+        child.attribute("type", &resource.r#type);
+        export_global_attributes(&mut child, &resource.global_attributes, "resource")?;
+        element.children.push(child);
+    }
+
+    Ok(())
+}
+
+fn export_path_elements(
+    element: &mut Element,
+    group: &PathElementsGroup,
+    namespaces: &mut BTreeMap<&'static str, &'static str>,
+) -> Result<()> {
+    for directory in group.directory.iter().flat_map(OneOrMore::iter) {
+        check_extensions(&directory.extensions, "directory")?;
+        let mut child = Element::new("Directory");
+        export_filesystem_item(&mut child, &directory.filesystem_item);
+        export_global_attributes(&mut child, &directory.global_attributes, "directory")?;
+        if let Some(group) = &directory.path_elements {
+            export_path_elements(&mut child, group, namespaces)?;
+        }
+        element.children.push(child);
+    }
+
+    for file in group.file.iter().flat_map(OneOrMore::iter) {
+        check_extensions(&file.extensions, "file")?;
+        let mut child = Element::new("File");
+        export_filesystem_item(&mut child, &file.filesystem_item);
+        child.optional("size", file.size);
+        child.optional("version", file.file_version.as_ref());
+        if let Some(hash) = &file.hash {
+            let (_, prefix, namespace) = HASH_NAMESPACES
+                .iter()
+                .find(|(alg, ..)| *alg == hash.hash_alg_id)
+                .ok_or_else(|| unmappable(format!("a file hash using {:?}", hash.hash_alg_id)))?;
+            namespaces.insert(prefix, namespace);
+            child.attribute(format!("{prefix}:hash"), encode_hex(&hash.hash_value));
+        }
+        export_global_attributes(&mut child, &file.global_attributes, "file")?;
+        element.children.push(child);
+    }
+
+    Ok(())
+}
+
+fn export_filesystem_item(element: &mut Element, item: &Option<FileSystemItem>) {
+    if let Some(item) = item {
+        element.optional("key", item.key);
+        element.optional("location", item.location.as_ref());
+        element.attribute("name", &item.fs_name);
+        element.optional("root", item.root.as_ref());
+    }
+}
+
+/// The attributes of an element being read. Attributes that are not taken are reported as
+/// unmappable by [`Attributes::finish`].
+struct Attributes<'a, 'input> {
+    node: Node<'a, 'input>,
+    taken: Vec<&'static str>,
+}
+
+impl<'a, 'input: 'a> Attributes<'a, 'input> {
+    fn new(node: Node<'a, 'input>) -> Self {
+        Self {
+            node,
+            taken: vec![],
+        }
+    }
+
+    fn text(&mut self, name: &'static str) -> Option<Text<'static>> {
+        self.taken.push(name);
+        self.node
+            .attribute(name)
+            .map(|value| Text::from(value.to_string()))
+    }
+
+    fn required(&mut self, name: &'static str) -> Result<Text<'static>> {
+        self.text(name).ok_or_else(|| {
+            SwidError::InvalidValue {
+                field: name,
+                message: format!("missing on {}", self.node.tag_name().name()),
+            }
+            .into()
+        })
+    }
+
+    fn bool(&mut self, name: &'static str) -> Result<Option<bool>> {
+        self.text(name)
+            .map(|value| match value.as_ref() {
+                "true" | "1" => Ok(true),
+                "false" | "0" => Ok(false),
+                _ => Err(invalid(name, format!("expected a boolean, got \"{value}\"")).into()),
+            })
+            .transpose()
+    }
+
+    fn integer(&mut self, name: &'static str) -> Result<Option<Integer>> {
+        self.text(name)
+            .map(|value| {
+                value.parse::<i128>().map(Integer).map_err(|_| {
+                    invalid(name, format!("expected an integer, got \"{value}\"")).into()
+                })
+            })
+            .transpose()
+    }
+
+    fn global_attributes(&mut self) -> Option<GlobalAttributes<'static>> {
+        self.taken.push("xml:lang");
+        self.node
+            .attribute((XML_NAMESPACE, "lang"))
+            .map(|lang| GlobalAttributes {
+                lang: Some(lang.to_string().into()),
+                attributes: None,
+            })
+    }
+
+    fn hash(&mut self) -> Result<Option<HashEntry<'static>>> {
+        for attribute in self.node.attributes() {
+            let Some((alg, ..)) = HASH_NAMESPACES
+                .iter()
+                .find(|(_, _, namespace)| attribute.namespace() == Some(namespace))
+            else {
+                continue;
+            };
+            if attribute.name() == "hash" {
+                return Ok(Some(HashEntry {
+                    hash_alg_id: alg.clone(),
+                    hash_value: decode_hex(attribute.value(), "hash")?.into(),
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn finish(self) -> Result<()> {
+        let element = self.node.tag_name().name();
+
+        for attribute in self.node.attributes() {
+            let known = match attribute.namespace() {
+                None => self.taken.contains(&attribute.name()),
+                Some(XML_NAMESPACE) => {
+                    attribute.name() == "lang" && self.taken.contains(&"xml:lang")
+                }
+                Some(XSI_NAMESPACE) => true,
+                Some(namespace) => {
+                    element == "File"
+                        && attribute.name() == "hash"
+                        && HASH_NAMESPACES.iter().any(|(_, _, ns)| *ns == namespace)
+                }
+            };
+
+            if !known {
+                return Err(unmappable(match attribute.namespace() {
+                    Some(namespace) => format!(
+                        "the {{{namespace}}}{} attribute of {element}",
+                        attribute.name()
+                    ),
+                    None => format!("the {} attribute of {element}", attribute.name()),
+                })
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the element children of `node`, failing on any that are not SWID elements named
+/// in `names`
+fn children<'a, 'input>(node: Node<'a, 'input>, names: &[&str]) -> Result<Vec<Node<'a, 'input>>> {
+    let namespace = node.tag_name().namespace();
+
+    node.children()
+        .filter(Node::is_element)
+        .map(|child| {
+            if child.tag_name().namespace() == namespace && names.contains(&child.tag_name().name())
+            {
+                Ok(child)
+            } else {
+                Err(unmappable(format!(
+                    "the {} element in {}",
+                    child.tag_name().name(),
+                    node.tag_name().name()
+                ))
+                .into())
+            }
+        })
+        .collect()
+}
+
+fn one_or_more<T>(mut items: Vec<T>) -> Option<OneOrMore<T>> {
+    match items.len() {
+        0 => None,
+        1 => items.pop().map(OneOrMore::One),
+        _ => Some(OneOrMore::More(items)),
+    }
+}
+
+fn import_tag(node: Node) -> Result<ConciseSwidTag<'static>> {
+    let mut attributes = Attributes::new(node);
+
+    let tag_id = TextOrBytes::Text(attributes.required("tagId")?);
+    let tag_version = attributes.integer("tagVersion")?.unwrap_or_default();
+    let corpus = attributes.bool("corpus")?;
+    let patch = attributes.bool("patch")?;
+    let supplemental = attributes.bool("supplemental")?;
+    let software_name = attributes.required("name")?;
+    let software_version = attributes.text("version");
+    let version_scheme = attributes
+        .text("versionScheme")
+        .map(|scheme| {
+            VersionScheme::try_from(scheme.into_owned())
+                .map_err(|err| invalid("versionScheme", err))
+        })
+        .transpose()?;
+    let media = attributes.text("media");
+    let global_attributes = attributes.global_attributes();
+    attributes.finish()?;
+
+    let mut entity = vec![];
+    let mut link = vec![];
+    let mut software_meta = vec![];
+    let mut payload_or_evidence = None;
+
+    for child in children(node, &["Entity", "Link", "Meta", "Payload", "Evidence"])? {
+        match child.tag_name().name() {
+            "Entity" => entity.push(import_entity(child)?),
+            "Link" => link.push(import_link(child)?),
+            "Meta" => software_meta.push(import_meta(child)?),
+            name => {
+                if payload_or_evidence.is_some() {
+                    return Err(unmappable(format!(
+                        "a second Payload or Evidence element ({name})"
+                    ))
+                    .into());
+                }
+                payload_or_evidence = Some(if name == "Payload" {
+                    PayloadOrEvidence::Payload(import_payload(child)?)
+                } else {
+                    PayloadOrEvidence::Evidence(import_evidence(child)?)
+                });
+            }
+        }
+    }
+
+    Ok(ConciseSwidTag {
+        tag_id,
+        tag_version,
+        corpus,
+        patch,
+        supplemental,
+        software_name,
+        software_version,
+        version_scheme,
+        media,
+        software_meta: one_or_more(software_meta),
+        entity: one_or_more(entity)
+            .ok_or_else(|| invalid("Entity", "a SWID tag needs at least one entity"))?,
+        link: one_or_more(link),
+        payload_or_evidence,
+        extensions: None,
+        global_attributes,
+    })
+}
+
+fn import_meta(node: Node) -> Result<SoftwareMetaEntry<'static>> {
+    let mut attributes = Attributes::new(node);
+
+    let meta = SoftwareMetaEntry {
+        activation_status: attributes.text("activationStatus"),
+        channel_type: attributes.text("channelType"),
+        coloquial_version: attributes.text("colloquialVersion"),
+        description: attributes.text("description"),
+        edition: attributes.text("edition"),
+        entitlement_data_required: attributes.bool("entitlementDataRequired")?,
+        entitlement_key: attributes.text("entitlementKey"),
+        generator: attributes.text("generator").map(TextOrBytesSized::Text),
+        persistent_id: attributes.text("persistentId"),
+        product: attributes.text("product"),
+        product_family: attributes.text("productFamily"),
+        revision: attributes.text("revision"),
+        summary: attributes.text("summary"),
+        unspsc_code: attributes.text("unspscCode"),
+        unspsc_version: attributes.text("unspscVersion"),
+        extensions: None,
+        global_attributes: attributes.global_attributes(),
+    };
+    attributes.finish()?;
+    children(node, &[])?;
+
+    Ok(meta)
+}
+
+fn import_entity(node: Node) -> Result<EntityEntry<'static>> {
+    let mut attributes = Attributes::new(node);
+
+    let entity_name = attributes.required("name")?;
+    let reg_id = attributes.text("regid").map(Uri::from);
+    let role = attributes
+        .required("role")?
+        .split_whitespace()
+        .map(|name| {
+            ROLES
+                .iter()
+                .find(|(_, role)| *role == name)
+                .map(|(value, _)| *value)
+                .or_else(|| name.parse().ok())
+                .ok_or_else(|| unmappable(format!("the entity role \"{name}\"")).into())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let thumbprint = attributes
+        .text("thumbprint")
+        .map(|thumbprint| -> Result<_> {
+            let hash_value = decode_hex(&thumbprint, "thumbprint")?;
+            let hash_alg_id = match hash_value.len() {
+                32 => CoseAlgorithm::Sha256,
+                48 => CoseAlgorithm::Sha384,
+                64 => CoseAlgorithm::Sha512,
+                len => {
+                    return Err(invalid(
+                        "thumbprint",
+                        format!("{len} bytes is not the length of a SHA-2 hash"),
+                    )
+                    .into())
+                }
+            };
+            Ok(HashEntry {
+                hash_alg_id,
+                hash_value: hash_value.into(),
+            })
+        })
+        .transpose()?;
+    let global_attributes = attributes.global_attributes();
+    attributes.finish()?;
+    children(node, &[])?;
+
+    Ok(EntityEntry {
+        entity_name,
+        reg_id,
+        role: one_or_more(role).ok_or_else(|| invalid("role", "an entity needs a role"))?,
+        thumbprint,
+        extensions: None,
+        global_attributes,
+    })
+}
+
+fn import_link(node: Node) -> Result<LinkEntry<'static>> {
+    let mut attributes = Attributes::new(node);
+
+    let link = LinkEntry {
+        artifact: attributes.text("artifact"),
+        href: Uri::from(attributes.required("href")?),
+        media: attributes.text("media"),
+        ownership: attributes
+            .text("ownership")
+            .map(|ownership| match ownership.as_ref() {
+                "abandon" => Ownership::Abandon,
+                "private" => Ownership::Private,
+                "shared" => Ownership::Shared,
+                other => Ownership::IntOrText(Label::parse(other)),
+            }),
+        rel: {
+            let rel = attributes.required("rel")?;
+            REL_NAMES
+                .iter()
+                .find(|(_, name)| *name == rel)
+                .map(|(value, _)| value.clone())
+                .unwrap_or_else(|| Rel::IntOrText(Label::parse(&rel)))
+        },
+        media_type: attributes.text("type"),
+        r#use: attributes.text("use").map(|r#use| match r#use.as_ref() {
+            "optional" => Use::Optional,
+            "required" => Use::Required,
+            "recommended" => Use::Recommended,
+            other => Use::IntOrText(Label::parse(other)),
+        }),
+        extension: None,
+        global_attributes: attributes.global_attributes(),
+    };
+    attributes.finish()?;
+    children(node, &[])?;
+
+    Ok(link)
+}
+
+fn import_payload(node: Node) -> Result<Payload<'static>> {
+    let mut attributes = Attributes::new(node);
+    let global_attributes = attributes.global_attributes();
+    attributes.finish()?;
+
+    Ok(Payload::new(PayloadEntry {
+        resource_collection: import_resources(node)?,
+        extension: None,
+        global_attributes,
+    }))
+}
+
+fn import_evidence(node: Node) -> Result<Evidence<'static>> {
+    let mut attributes = Attributes::new(node);
+    let date = attributes
+        .text("date")
+        .map(|date| {
+            parse_rfc3339(&date)
+                .map(|seconds| IntegerTime::from(Integer(seconds)))
+                .ok_or_else(|| invalid("date", format!("expected an xs:dateTime, got \"{date}\"")))
+        })
+        .transpose()?;
+    let device_id = attributes.text("deviceId");
+    let location = attributes.text("location");
+    let global_attributes = attributes.global_attributes();
+    attributes.finish()?;
+
+    Ok(Evidence::new(EvidenceEntry {
+        resource_collection: import_resources(node)?,
+        date,
+        device_id,
+        location,
+        extension: None,
+        global_attributes,
+    }))
+}
+
+fn import_resources(node: Node) -> Result<Option<ResourceCollection<'static>>> {
+    let group = import_path_elements(node, &["Directory", "File", "Process", "Resource"])?;
+
+    let mut process = vec![];
+    let mut resource = vec![];
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "Process" => {
+                let mut attributes = Attributes::new(child);
+                process.push(ProcessEntry {
+                    process_name: attributes.required("name")?,
+                    pid: attributes.integer("pid")?,
+                    extension: None,
+                    global_attributes: attributes.global_attributes(),
+                });
+                attributes.finish()?;
+                children(child, &[])?;
+            }
+            "Resource" => {
+                let mut attributes = Attributes::new(child);
+                resource.push(ResourceEntry {
+                    r#type: attributes.required("type")?,
+                    extension: None,
+                    global_attributes: attributes.global_attributes(),
+                });
+                attributes.finish()?;
+                children(child, &[])?;
+            }
+            _ => {}
+        }
+    }
+
+    if group.is_none() && process.is_empty() && resource.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(ResourceCollection {
+        path_elements_group: group,
+        process: one_or_more(process),
+        resource: one_or_more(resource),
+        extensions: None,
+    }))
+}
+
+fn import_path_elements(node: Node, names: &[&str]) -> Result<Option<PathElementsGroup<'static>>> {
+    let mut directory = vec![];
+    let mut file = vec![];
+
+    for child in children(node, names)? {
+        match child.tag_name().name() {
+            "Directory" => {
+                let mut attributes = Attributes::new(child);
+                directory.push(DirectoryEntry {
+                    filesystem_item: import_filesystem_item(&mut attributes)?,
+                    path_elements: import_path_elements(child, &["Directory", "File"])?
+                        .map(Box::new),
+                    extensions: None,
+                    global_attributes: attributes.global_attributes(),
+                });
+                attributes.finish()?;
+            }
+            "File" => {
+                let mut attributes = Attributes::new(child);
+                file.push(FileEntry {
+                    filesystem_item: import_filesystem_item(&mut attributes)?,
+                    size: attributes.integer("size")?,
+                    file_version: attributes.text("version"),
+                    hash: attributes.hash()?,
+                    extensions: None,
+                    global_attributes: attributes.global_attributes(),
+                });
+                attributes.finish()?;
+                children(child, &[])?;
+            }
+            _ => {}
+        }
+    }
+
+    if directory.is_empty() && file.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(PathElementsGroup {
+        directory: one_or_more(directory),
+        file: one_or_more(file),
+    }))
+}
+
+fn import_filesystem_item(attributes: &mut Attributes) -> Result<Option<FileSystemItem<'static>>> {
+    let key = attributes.bool("key")?;
+    let location = attributes.text("location");
+    let root = attributes.text("root");
+
+    match attributes.text("name") {
+        Some(fs_name) => Ok(Some(FileSystemItem {
+            key,
+            location,
+            fs_name,
+            root,
+        })),
+        None if key.is_none() && location.is_none() && root.is_none() => Ok(None),
+        None => Err(invalid("name", "missing on a Directory or File").into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bytes, ExtensionValue, TaggedBytes};
+
+    fn text(value: &str) -> Option<Text<'static>> {
+        Some(value.to_string().into())
+    }
+
+    fn file(name: &str, hash: u8) -> FileEntry<'static> {
+        FileEntry {
+            filesystem_item: Some(FileSystemItem {
+                key: Some(true),
+                location: None,
+                fs_name: name.to_string().into(),
+                root: None,
+            }),
+            size: Some(Integer(1024)),
+            file_version: text("1.0.0"),
+            hash: Some(HashEntry {
+                hash_alg_id: CoseAlgorithm::Sha256,
+                hash_value: Bytes::from(vec![hash; 32]),
+            }),
+            extensions: None,
+            global_attributes: None,
+        }
+    }
+
+    fn tag() -> ConciseSwidTag<'static> {
+        ConciseSwidTag {
+            tag_id: "acme-roadrunner-1.0".into(),
+            tag_version: Integer(2),
+            corpus: None,
+            patch: Some(false),
+            supplemental: None,
+            software_name: "RoadRunner & Friends".into(),
+            software_version: text("1.0.0"),
+            version_scheme: Some(VersionScheme::Semver),
+            media: None,
+            software_meta: Some(OneOrMore::One(SoftwareMetaEntry {
+                activation_status: text("full"),
+                coloquial_version: text("2025"),
+                entitlement_data_required: Some(false),
+                generator: Some(TextOrBytesSized::Text("corim-rs".into())),
+                product: text("RoadRunner"),
+                unspsc_code: text("43232408"),
+                ..Default::default()
+            })),
+            entity: OneOrMore::More(vec![
+                EntityEntry {
+                    entity_name: "ACME Inc.".into(),
+                    reg_id: Some(Uri::from(Text::from("https://acme.example"))),
+                    role: OneOrMore::More(vec![1, 2]),
+                    thumbprint: Some(HashEntry {
+                        hash_alg_id: CoseAlgorithm::Sha384,
+                        hash_value: Bytes::from(vec![0xAB; 48]),
+                    }),
+                    extensions: None,
+                    global_attributes: None,
+                },
+                EntityEntry {
+                    entity_name: "Coyote Distribution".into(),
+                    reg_id: None,
+                    role: OneOrMore::One(4),
+                    thumbprint: None,
+                    extensions: None,
+                    global_attributes: None,
+                },
+            ]),
+            link: Some(OneOrMore::More(vec![
+                LinkEntry {
+                    artifact: None,
+                    href: Uri::from(Text::from("swid:acme-roadrunner-0.9")),
+                    media: None,
+                    ownership: Some(Ownership::Private),
+                    rel: Rel::Supersedes,
+                    media_type: None,
+                    r#use: Some(Use::Required),
+                    extension: None,
+                    global_attributes: None,
+                },
+                LinkEntry {
+                    artifact: None,
+                    href: Uri::from(Text::from("https://acme.example/licence")),
+                    media: None,
+                    ownership: None,
+                    rel: Rel::IntOrText(Label::Text("license".into())),
+                    media_type: text("text/html"),
+                    r#use: None,
+                    extension: None,
+                    global_attributes: None,
+                },
+            ])),
+            payload_or_evidence: Some(PayloadOrEvidence::Payload(Payload::new(PayloadEntry {
+                resource_collection: Some(ResourceCollection {
+                    path_elements_group: Some(PathElementsGroup {
+                        directory: Some(OneOrMore::One(DirectoryEntry {
+                            filesystem_item: Some(FileSystemItem {
+                                key: None,
+                                location: None,
+                                fs_name: "bin".into(),
+                                root: text("%programdata%"),
+                            }),
+                            path_elements: Some(Box::new(PathElementsGroup {
+                                directory: None,
+                                file: Some(OneOrMore::More(vec![
+                                    file("roadrunner", 1),
+                                    file("roadrunner.conf", 2),
+                                ])),
+                            })),
+                            extensions: None,
+                            global_attributes: None,
+                        })),
+                        file: None,
+                    }),
+                    process: Some(OneOrMore::One(ProcessEntry {
+                        process_name: "roadrunnerd".into(),
+                        pid: None,
+                        extension: None,
+                        global_attributes: None,
+                    })),
+                    resource: None,
+                    extensions: None,
+                }),
+                extension: None,
+                global_attributes: None,
+            }))),
+            extensions: None,
+            global_attributes: Some(GlobalAttributes {
+                lang: text("en-US"),
+                attributes: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_swid_xml_roundtrip() {
+        let tag = tag();
+
+        let xml = tag.to_swid_xml().unwrap();
+        assert!(xml.contains(r#"xmlns:SHA256="http://www.w3.org/2001/04/xmlenc#sha256""#));
+        assert!(xml.contains(r#"name="RoadRunner &amp; Friends""#));
+        assert!(xml.contains(r#"xml:lang="en-US""#));
+        assert!(xml.contains(r#"role="tagCreator softwareCreator""#));
+        assert!(xml.contains(&format!(r#"thumbprint="{}""#, "ab".repeat(48))));
+        assert!(xml.contains(r#"rel="supersedes""#));
+        assert!(xml.contains(&format!(r#"SHA256:hash="{}""#, "01".repeat(32))));
+
+        let actual = ConciseSwidTag::from_swid_xml(&xml).unwrap();
+        assert_eq!(actual, tag);
+        assert_eq!(actual.to_swid_xml().unwrap(), xml);
+    }
+
+    #[test]
+    fn test_swid_xml_evidence() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<SoftwareIdentity xmlns="http://standards.iso.org/iso/19770/-2/2015/schema.xsd" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:SHA512="http://www.w3.org/2001/04/xmlenc#sha512" xsi:schemaLocation="http://standards.iso.org/iso/19770/-2/2015/schema.xsd schema.xsd" tagId="31fb5abf-023e-4992-aa4e-95f9c1503bfa" name="Observed" versionScheme="multipartnumeric">
+  <Entity name="Scanner" role="tagCreator 42"/>
+  <Evidence date="2025-01-01T12:00:00+01:00" deviceId="host-17">
+    <File name="agent.exe" SHA512:hash="ABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABAB"/>
+    <Resource type="registry-key"/>
+  </Evidence>
+</SoftwareIdentity>
+"#;
+
+        let tag = ConciseSwidTag::from_swid_xml(xml).unwrap();
+        assert_eq!(tag.tag_version, Integer(0));
+        assert_eq!(tag.version_scheme, Some(VersionScheme::Multipartnumeric));
+        assert_eq!(
+            tag.entity.as_one().unwrap().role,
+            OneOrMore::More(vec![1, 42])
+        );
+
+        let evidence = tag
+            .payload_or_evidence
+            .as_ref()
+            .unwrap()
+            .as_ref_evidence()
+            .unwrap();
+        assert_eq!(
+            evidence.evidence.date.as_ref().unwrap().0 .0,
+            Integer(1_735_729_200)
+        );
+        assert_eq!(evidence.evidence.device_id, text("host-17"));
+
+        let resources = evidence.evidence.resource_collection.as_ref().unwrap();
+        let file = resources
+            .path_elements_group
+            .as_ref()
+            .unwrap()
+            .file
+            .as_ref()
+            .unwrap()
+            .as_one()
+            .unwrap();
+        assert_eq!(
+            file.hash.as_ref().unwrap().hash_alg_id,
+            CoseAlgorithm::Sha512
+        );
+        assert_eq!(file.hash.as_ref().unwrap().hash_value, [0xAB; 64]);
+        assert_eq!(
+            resources
+                .resource
+                .as_ref()
+                .unwrap()
+                .as_one()
+                .unwrap()
+                .r#type,
+            "registry-key"
+        );
+
+        let written = tag.to_swid_xml().unwrap();
+        assert!(written.contains(r#"date="2025-01-01T11:00:00Z""#));
+        assert!(written.contains(r#"role="tagCreator 42""#));
+        assert_eq!(ConciseSwidTag::from_swid_xml(&written).unwrap(), tag);
+    }
+
+    #[test]
+    fn test_swid_xml_binary_identifiers() {
+        let mut tag = tag();
+        tag.tag_id = TextOrBytes::Bytes(TaggedBytes::from(Bytes::from(vec![0x31; 16])));
+
+        let xml = tag.to_swid_xml().unwrap();
+        assert!(xml.contains(r#"tagId="31313131-3131-3131-3131-313131313131""#));
+        assert_eq!(
+            ConciseSwidTag::from_swid_xml(&xml).unwrap().tag_id,
+            TextOrBytes::Text("31313131-3131-3131-3131-313131313131".into())
+        );
+
+        tag.tag_id = TextOrBytes::Bytes(TaggedBytes::from(Bytes::from(vec![1, 2, 3])));
+        assert!(matches!(
+            tag.to_swid_xml(),
+            Err(crate::Error::Swid(SwidError::Unmappable(_)))
+        ));
+    }
+
+    #[test]
+    fn test_swid_xml_unmappable() {
+        let mut extensions = ExtensionMap::default();
+        extensions.insert(Integer(100), ExtensionValue::Text("vendor".into()));
+
+        let mut with_extension = tag();
+        with_extension.extensions = Some(extensions.clone());
+        let err = with_extension.to_swid_xml().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "tag extension 100 cannot be converted between a CoSWID tag and SWID XML"
+        );
+
+        let mut with_attribute = tag();
+        with_attribute.global_attributes = Some(GlobalAttributes {
+            lang: None,
+            attributes: Some(extensions),
+        });
+        assert!(matches!(
+            with_attribute.to_swid_xml(),
+            Err(crate::Error::Swid(SwidError::Unmappable(_)))
+        ));
+
+        let mut with_hash = tag();
+        let Some(OneOrMore::More(entities)) = Some(&mut with_hash.entity) else {
+            unreachable!()
+        };
+        entities[0].thumbprint.as_mut().unwrap().hash_alg_id = CoseAlgorithm::SHAKE256;
+        assert!(matches!(
+            with_hash.to_swid_xml(),
+            Err(crate::Error::Swid(SwidError::Unmappable(_)))
+        ));
+
+        let signed = r#"<SoftwareIdentity xmlns="http://standards.iso.org/iso/19770/-2/2015/schema.xsd" tagId="t" name="n">
+  <Entity name="e" role="tagCreator"/>
+  <Signature xmlns="http://www.w3.org/2000/09/xmldsig#"/>
+</SoftwareIdentity>"#;
+        let err = ConciseSwidTag::from_swid_xml(signed).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the Signature element in SoftwareIdentity cannot be converted between a CoSWID tag and SWID XML"
+        );
+
+        let vendor_meta = r#"<SoftwareIdentity xmlns="http://standards.iso.org/iso/19770/-2/2015/schema.xsd" tagId="t" name="n">
+  <Entity name="e" role="tagCreator"/>
+  <Meta product="p" vendorBuild="1234"/>
+</SoftwareIdentity>"#;
+        let err = ConciseSwidTag::from_swid_xml(vendor_meta).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the vendorBuild attribute of Meta cannot be converted between a CoSWID tag and SWID XML"
+        );
+
+        let unknown_role = vendor_meta
+            .replace(r#" vendorBuild="1234""#, "")
+            .replace("tagCreator", "owner");
+        assert!(matches!(
+            ConciseSwidTag::from_swid_xml(&unknown_role),
+            Err(crate::Error::Swid(SwidError::Unmappable(_)))
+        ));
+
+        assert!(matches!(
+            ConciseSwidTag::from_swid_xml(r#"<SoftwareIdentity tagId="t" name="n"/>"#),
+            Err(crate::Error::Swid(SwidError::InvalidValue {
+                field: "Entity",
+                ..
+            }))
+        ));
+        assert!(matches!(
+            ConciseSwidTag::from_swid_xml("<SoftwareIdentity"),
+            Err(crate::Error::Swid(SwidError::Xml(_)))
+        ));
+    }
+}
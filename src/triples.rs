@@ -89,8 +89,16 @@
 //! - [`StatefulEnvironmentRecord`]: For tracking environment state
 //! - [`ConditionalSeriesRecord`]: For defining measurement changes
 
-use std::{
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
     collections::{btree_map::Iter, BTreeMap},
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
     fmt::Display,
     marker::PhantomData,
     net::{Ipv4Addr, Ipv6Addr},
@@ -99,14 +107,14 @@ use std::{
 
 use crate::{
     core::{
-        ExtensionValue, PkixBase64CertPathType, RawValueMaskType, RawValueTypeChoice,
-        TaggedJsonValue,
+        deserialize_tag_choice, json_typed_value, serialize_json_typed, BorrowText, ExtensionValue,
+        PkixBase64CertPathType, RawValueMaskType, RawValueTypeChoice, TagChoice, TaggedJsonValue,
     },
     empty::Empty as _,
     Bytes, CertPathThumbprintType, CertThumbprintType, ConciseSwidTagId, CoseKeySetOrKey,
-    CoseKeyType, Digest, ExtensionMap, Integer, MinSvnType, ObjectIdentifier, OidType,
-    PkixAsn1DerCertType, PkixBase64CertType, PkixBase64KeyType, RawValueType, Result, SvnType,
-    TaggedBytes, TaggedUeidType, TaggedUuidType, Text, ThumbprintType, TriplesError, Tstr,
+    CoseKeyType, Digest, ExtensionMap, HashAlgorithm, Integer, MinSvnType, ObjectIdentifier,
+    OidType, PkixAsn1DerCertType, PkixBase64CertType, PkixBase64KeyType, RawValueType, Result,
+    SvnType, TaggedBytes, TaggedUeidType, TaggedUuidType, Text, ThumbprintType, TriplesError, Tstr,
     UeidType, Uint, Ulabel, UuidType, VersionScheme,
 };
 use derive_more::{Constructor, From, TryFrom};
@@ -127,7 +135,7 @@ pub struct ReferenceTripleRecord<'a> {
 }
 
 impl Serialize for ReferenceTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
@@ -138,21 +146,21 @@ impl Serialize for ReferenceTripleRecord<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for ReferenceTripleRecord<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for ReferenceTripleRecord<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        struct ReferenceTripleRecordVisitor<'a>(std::marker::PhantomData<&'a ()>);
+        struct ReferenceTripleRecordVisitor<'a>(core::marker::PhantomData<&'a ()>);
 
-        impl<'de, 'a> serde::de::Visitor<'de> for ReferenceTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> serde::de::Visitor<'de> for ReferenceTripleRecordVisitor<'a> {
             type Value = ReferenceTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a reference triple record")
             }
 
-            fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: serde::de::SeqAccess<'de>,
             {
@@ -169,7 +177,7 @@ impl<'de> Deserialize<'de> for ReferenceTripleRecord<'_> {
             }
         }
 
-        deserializer.deserialize_seq(ReferenceTripleRecordVisitor(std::marker::PhantomData))
+        deserializer.deserialize_seq(ReferenceTripleRecordVisitor(core::marker::PhantomData))
     }
 }
 /// Map describing an environment's characteristics
@@ -181,11 +189,11 @@ pub struct EnvironmentMap<'a> {
     /// Optional instance identifier
     pub instance: Option<InstanceIdTypeChoice<'a>>,
     /// Optional group identifier
-    pub group: Option<GroupIdTypeChoice>,
+    pub group: Option<GroupIdTypeChoice<'a>>,
 }
 
 impl Serialize for EnvironmentMap<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -218,8 +226,8 @@ impl Serialize for EnvironmentMap<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for EnvironmentMap<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for EnvironmentMap<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -228,14 +236,14 @@ impl<'de> Deserialize<'de> for EnvironmentMap<'_> {
             data: PhantomData<&'a ()>,
         }
 
-        impl<'de, 'a> Visitor<'de> for EnvironmentMapVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for EnvironmentMapVisitor<'a> {
             type Value = EnvironmentMap<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a map contianing EnvironmentMap fields")
             }
 
-            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: serde::de::MapAccess<'de>,
             {
@@ -252,7 +260,7 @@ impl<'de> Deserialize<'de> for EnvironmentMap<'_> {
                                     builder.instance(map.next_value::<InstanceIdTypeChoice<'a>>()?);
                             }
                             Some("group") => {
-                                builder = builder.group(map.next_value::<GroupIdTypeChoice>()?);
+                                builder = builder.group(map.next_value::<GroupIdTypeChoice<'a>>()?);
                             }
                             Some(name) => {
                                 return Err(de::Error::custom(format!(
@@ -271,7 +279,7 @@ impl<'de> Deserialize<'de> for EnvironmentMap<'_> {
                                     builder.instance(map.next_value::<InstanceIdTypeChoice<'a>>()?);
                             }
                             Some(2) => {
-                                builder = builder.group(map.next_value::<GroupIdTypeChoice>()?);
+                                builder = builder.group(map.next_value::<GroupIdTypeChoice<'a>>()?);
                             }
                             Some(key) => {
                                 return Err(de::Error::custom(format!(
@@ -302,7 +310,7 @@ pub struct EnvironmentMapBuilder<'a> {
     /// Optional instance identifier
     pub instance: Option<InstanceIdTypeChoice<'a>>,
     /// Optional group identifier
-    pub group: Option<GroupIdTypeChoice>,
+    pub group: Option<GroupIdTypeChoice<'a>>,
 }
 
 impl<'a> EnvironmentMapBuilder<'a> {
@@ -316,7 +324,7 @@ impl<'a> EnvironmentMapBuilder<'a> {
         self
     }
 
-    pub fn group(mut self, group: GroupIdTypeChoice) -> Self {
+    pub fn group(mut self, group: GroupIdTypeChoice<'a>) -> Self {
         self.group = Some(group);
         self
     }
@@ -350,7 +358,7 @@ pub struct ClassMap<'a> {
 }
 
 impl Serialize for ClassMap<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -395,8 +403,8 @@ impl Serialize for ClassMap<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for ClassMap<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for ClassMap<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -405,13 +413,13 @@ impl<'de> Deserialize<'de> for ClassMap<'_> {
             data: PhantomData<&'a ()>,
         }
 
-        impl<'de, 'a> Visitor<'de> for ClassMapVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for ClassMapVisitor<'a> {
             type Value = ClassMap<'a>;
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a map contianing ClassMap fields")
             }
 
-            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: serde::de::MapAccess<'de>,
             {
@@ -424,10 +432,10 @@ impl<'de> Deserialize<'de> for ClassMap<'_> {
                                 class_map.class_id = Some(map.next_value::<ClassIdTypeChoice>()?);
                             }
                             Some("vendor") => {
-                                class_map.vendor = Some(map.next_value::<Tstr>()?);
+                                class_map.vendor = Some(map.next_value::<BorrowText>()?.into());
                             }
                             Some("model") => {
-                                class_map.model = Some(map.next_value::<Tstr>()?);
+                                class_map.model = Some(map.next_value::<BorrowText>()?.into());
                             }
                             Some("layer") => {
                                 class_map.layer = Some(map.next_value::<Uint>()?);
@@ -448,10 +456,10 @@ impl<'de> Deserialize<'de> for ClassMap<'_> {
                                 class_map.class_id = Some(map.next_value::<ClassIdTypeChoice>()?);
                             }
                             Some(1) => {
-                                class_map.vendor = Some(map.next_value::<Tstr>()?);
+                                class_map.vendor = Some(map.next_value::<BorrowText>()?.into());
                             }
                             Some(2) => {
-                                class_map.model = Some(map.next_value::<Tstr>()?);
+                                class_map.model = Some(map.next_value::<BorrowText>()?.into());
                             }
                             Some(3) => {
                                 class_map.layer = Some(map.next_value::<Uint>()?);
@@ -546,13 +554,13 @@ impl<'a> ClassMapBuilder<'a> {
 #[serde(untagged)]
 pub enum ClassIdTypeChoice<'a> {
     /// Object Identifier (OID)
-    Oid(OidType),
+    Oid(OidType<'a>),
     /// UUID identifier
     Uuid(TaggedUuidType),
     /// Raw bytes
-    Bytes(TaggedBytes),
+    Bytes(TaggedBytes<'a>),
     /// Extensions
-    Extension(ExtensionValue<'a>)
+    Extension(ExtensionValue<'a>),
 }
 
 impl ClassIdTypeChoice<'_> {
@@ -679,8 +687,8 @@ impl ClassIdTypeChoice<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for ClassIdTypeChoice<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for ClassIdTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -701,18 +709,14 @@ impl<'de> Deserialize<'de> for ClassIdTypeChoice<'_> {
                                     ObjectIdentifier::try_from(value.as_str())
                                         .map_err(|_| de::Error::custom("invalid OID bytes"))?,
                                 ))),
-                                "uuid" => {
-                                    Ok(ClassIdTypeChoice::Uuid(TaggedUuidType::from(
-                                        UuidType::try_from(value.as_str())
-                                            .map_err(|_| de::Error::custom("invalid UUID bytes"))?,
-                                    )))
-                                }
-                                "bytes" => {
-                                    Ok(ClassIdTypeChoice::Bytes(TaggedBytes::from(
-                                        Bytes::try_from(value.as_str())
-                                            .map_err(|_| de::Error::custom("invalid UUID bytes"))?,
-                                    )))
-                                }
+                                "uuid" => Ok(ClassIdTypeChoice::Uuid(TaggedUuidType::from(
+                                    UuidType::try_from(value.as_str())
+                                        .map_err(|_| de::Error::custom("invalid UUID bytes"))?,
+                                ))),
+                                "bytes" => Ok(ClassIdTypeChoice::Bytes(TaggedBytes::from(
+                                    Bytes::try_from(value.as_str())
+                                        .map_err(|_| de::Error::custom("invalid UUID bytes"))?,
+                                ))),
                                 s => Err(de::Error::custom(format!(
                                     "unexpected type {s} for ClassIdTypeChoice"
                                 ))),
@@ -721,18 +725,17 @@ impl<'de> Deserialize<'de> for ClassIdTypeChoice<'_> {
                                 "type must be as string, got {v:?}"
                             ))),
                         }
-                    } else if map.contains_key("tag") && map.contains_key("value") && map.len() == 2 {
+                    } else if map.contains_key("tag") && map.contains_key("value") && map.len() == 2
+                    {
                         match &map["tag"] {
                             serde_json::Value::Number(n) => match n.as_u64() {
-                                Some(u) => {
-                                    Ok(ClassIdTypeChoice::Extension(ExtensionValue::Tag(
-                                        u,
-                                        Box::new(
-                                            ExtensionValue::try_from(map["value"].clone())
-                                                .map_err(de::Error::custom)?,
-                                        ),
-                                    )))
-                                }
+                                Some(u) => Ok(ClassIdTypeChoice::Extension(ExtensionValue::Tag(
+                                    u,
+                                    Box::new(
+                                        ExtensionValue::try_from(map["value"].clone())
+                                            .map_err(de::Error::custom)?,
+                                    ),
+                                ))),
                                 None => Err(de::Error::custom(format!(
                                     "a number must be an unsinged integer, got {n:?}"
                                 ))),
@@ -746,67 +749,63 @@ impl<'de> Deserialize<'de> for ClassIdTypeChoice<'_> {
                         ))
                     }
                 }
-                value => {
-                    Ok(ClassIdTypeChoice::Extension(
-                        ExtensionValue::try_from(value).map_err(de::Error::custom)?
-                    ))
-                }
-            }
-        } else {
-            match ciborium::Value::deserialize(deserializer)? {
-                ciborium::Value::Tag(tag, inner) => {
-                    // Re-serializing the inner Value so that we can deserialize it
-                    // into an appropriate type, once we figure out what that is
-                    // based on the tag.
-                    let mut buf: Vec<u8> = Vec::new();
-                    ciborium::into_writer(&inner, &mut buf).unwrap();
-
-                    match tag {
-                        111 => {
-                            let oid: ObjectIdentifier =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(ClassIdTypeChoice::Oid(OidType::from(oid)))
-                        }
-                        37 => {
-                            let uuid: UuidType =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(ClassIdTypeChoice::Uuid(TaggedUuidType::from(uuid)))
-                        }
-                        560 => {
-                            let bytes: Bytes =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(ClassIdTypeChoice::Bytes(TaggedBytes::from(bytes)))
-                        }
-                        n => Ok(ClassIdTypeChoice::Extension(ExtensionValue::Tag(
-                            n,
-                            Box::new(
-                                ExtensionValue::try_from(inner.deref().to_owned())
-                                    .map_err(de::Error::custom)?,
-                            ),
-                        ))),
-                    }
-                }
                 value => Ok(ClassIdTypeChoice::Extension(
                     ExtensionValue::try_from(value).map_err(de::Error::custom)?,
                 )),
             }
+        } else {
+            deserialize_tag_choice(deserializer)
         }
     }
 }
 
+impl<'de: 'a, 'a> TagChoice<'de> for ClassIdTypeChoice<'a> {
+    const NAME: &'static str = "ClassIdTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            111 => Ok(ClassIdTypeChoice::Oid(OidType::from(
+                ObjectIdentifier::deserialize(deserializer)?,
+            ))),
+            37 => Ok(ClassIdTypeChoice::Uuid(TaggedUuidType::from(
+                UuidType::deserialize(deserializer)?,
+            ))),
+            560 => Ok(ClassIdTypeChoice::Bytes(TaggedBytes::from(
+                Bytes::deserialize(deserializer)?,
+            ))),
+            n => Ok(ClassIdTypeChoice::Extension(ExtensionValue::Tag(
+                n,
+                Box::new(ExtensionValue::deserialize(deserializer)?),
+            ))),
+        }
+    }
+
+    fn untagged<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ClassIdTypeChoice::Extension(ExtensionValue::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
 /// Possible types for instance identifiers
 #[derive(Debug, Serialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[repr(C)]
 #[serde(untagged)]
 pub enum InstanceIdTypeChoice<'a> {
     /// Unique Entity Identifier
-    Ueid(TaggedUeidType),
+    Ueid(TaggedUeidType<'a>),
     /// UUID identifier
     Uuid(TaggedUuidType),
     /// Cryptographic key identifier
     CryptoKey(CryptoKeyTypeChoice<'a>),
     /// Raw bytes
-    Bytes(TaggedBytes),
+    Bytes(TaggedBytes<'a>),
 }
 
 impl InstanceIdTypeChoice<'_> {
@@ -824,14 +823,14 @@ impl InstanceIdTypeChoice<'_> {
         }
     }
 
-    pub fn as_crypto_key(&self) -> Option<CryptoKeyTypeChoice> {
+    pub fn as_crypto_key(&self) -> Option<CryptoKeyTypeChoice<'_>> {
         match self {
             Self::CryptoKey(key) => Some(key.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_crypto_key(&self) -> Option<&CryptoKeyTypeChoice> {
+    pub fn as_ref_crypto_key(&self) -> Option<&CryptoKeyTypeChoice<'_>> {
         match self {
             Self::CryptoKey(key) => Some(key),
             _ => None,
@@ -852,8 +851,8 @@ impl<'a> From<&'a [u8]> for InstanceIdTypeChoice<'a> {
     }
 }
 
-impl<'de> Deserialize<'de> for InstanceIdTypeChoice<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for InstanceIdTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -885,28 +884,28 @@ impl<'de> Deserialize<'de> for InstanceIdTypeChoice<'_> {
                     ))
                 }
                 "cose-key" => {
-                    let sok: CoseKeySetOrKey = serde_json::from_str(tagged_value.value.get())
+                    let sok: CoseKeySetOrKey<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(InstanceIdTypeChoice::CryptoKey(
                         CryptoKeyTypeChoice::CoseKey(CoseKeyType::from(sok)),
                     ))
                 }
                 "thumbprint" => {
-                    let digest: Digest = serde_json::from_str(tagged_value.value.get())
+                    let digest: Digest<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(InstanceIdTypeChoice::CryptoKey(
                         CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(digest)),
                     ))
                 }
                 "cert-thumbprint" => {
-                    let digest: Digest = serde_json::from_str(tagged_value.value.get())
+                    let digest: Digest<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(InstanceIdTypeChoice::CryptoKey(
                         CryptoKeyTypeChoice::CertThumbprint(CertThumbprintType::from(digest)),
                     ))
                 }
                 "cert-path-thumbprint" => {
-                    let digest: Digest = serde_json::from_str(tagged_value.value.get())
+                    let digest: Digest<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(InstanceIdTypeChoice::CryptoKey(
                         CryptoKeyTypeChoice::CertPathThumbprint(CertPathThumbprintType::from(
@@ -915,14 +914,14 @@ impl<'de> Deserialize<'de> for InstanceIdTypeChoice<'_> {
                     ))
                 }
                 "pkix-asn1-der-cert" => {
-                    let bytes: Bytes = serde_json::from_str(tagged_value.value.get())
+                    let bytes: Bytes<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(InstanceIdTypeChoice::CryptoKey(
                         CryptoKeyTypeChoice::PkixAsn1DerCert(PkixAsn1DerCertType::from(bytes)),
                     ))
                 }
                 "bytes" => {
-                    let bytes: Bytes = serde_json::from_str(tagged_value.value.get())
+                    let bytes: Bytes<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     // TODO(setrofim): both instance-id-type-choice and crypto-key-type-choice
                     // specify tagged-bytes as a variant. It is not possible to distinguish between
@@ -940,7 +939,7 @@ impl<'de> Deserialize<'de> for InstanceIdTypeChoice<'_> {
                     Ok(InstanceIdTypeChoice::Uuid(TaggedUuidType::from(uuid)))
                 }
                 "ueid" => {
-                    let ueid: UeidType = serde_json::from_str(tagged_value.value.get())
+                    let ueid: UeidType<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(InstanceIdTypeChoice::Ueid(TaggedUeidType::from(ueid)))
                 }
@@ -949,109 +948,44 @@ impl<'de> Deserialize<'de> for InstanceIdTypeChoice<'_> {
                 ))),
             }
         } else {
-            match ciborium::Value::deserialize(deserializer)? {
-                ciborium::Value::Tag(tag, inner) => {
-                    // Re-serializing the inner Value so that we can deserialize it
-                    // into an appropriate type, once we figure out what that is
-                    // based on the tag.
-                    let mut buf: Vec<u8> = Vec::new();
-                    ciborium::into_writer(&inner, &mut buf).unwrap();
+            deserialize_tag_choice(deserializer)
+        }
+    }
+}
 
-                    match tag {
-                        554 => {
-                            let tstr: Tstr =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::PkixBase64Key(PkixBase64KeyType::from(tstr)),
-                            ))
-                        }
-                        555 => {
-                            let tstr: Tstr =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::PkixBase64Cert(PkixBase64CertType::from(tstr)),
-                            ))
-                        }
-                        556 => {
-                            let tstr: Tstr =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::PkixBase64CertPath(
-                                    PkixBase64CertPathType::from(tstr),
-                                ),
-                            ))
-                        }
-                        558 => {
-                            let sok: CoseKeySetOrKey =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::CoseKey(CoseKeyType::from(sok)),
-                            ))
-                        }
-                        557 => {
-                            let digest: Digest =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(digest)),
-                            ))
-                        }
-                        559 => {
-                            let digest: Digest =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::CertThumbprint(CertThumbprintType::from(
-                                    digest,
-                                )),
-                            ))
-                        }
-                        561 => {
-                            let digest: Digest =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::CertPathThumbprint(
-                                    CertPathThumbprintType::from(digest),
-                                ),
-                            ))
-                        }
-                        562 => {
-                            let bytes: Bytes =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::CryptoKey(
-                                CryptoKeyTypeChoice::PkixAsn1DerCert(PkixAsn1DerCertType::from(
-                                    bytes,
-                                )),
-                            ))
-                        }
-                        560 => {
-                            let bytes: Bytes =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            // TODO(setrofim): both instance-id-type-choice and crypto-key-type-choice
-                            // specify tagged-bytes as a variant. It is not possible to distinguish between
-                            // them (see https://github.com/ietf-rats-wg/draft-ietf-rats-corim/issues/428),
-                            // so we have to make a choice whether we treat tagged-bytes as an instance ID
-                            // or as a crypto key (that is an instance ID); both interpretations would be
-                            // valid according to the spec (until the above issue is fixed in some way).
-                            // Here, we're choosing to treat it as a generic ID, on the assumption that
-                            // this is more likely to be the intent.
-                            Ok(InstanceIdTypeChoice::Bytes(TaggedBytes::from(bytes)))
-                        }
-                        37 => {
-                            let uuid: UuidType =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::Uuid(TaggedUuidType::from(uuid)))
-                        }
-                        550 => {
-                            let ueid: UeidType =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(InstanceIdTypeChoice::Ueid(TaggedUeidType::from(ueid)))
-                        }
-                        n => Err(de::Error::custom(format!(
-                            "unexpected InstanceIdTypeChoice tag {n}"
-                        ))),
-                    }
-                }
-                _ => Err(de::Error::custom("did not see a tag")),
+impl<'de: 'a, 'a> TagChoice<'de> for InstanceIdTypeChoice<'a> {
+    const NAME: &'static str = "InstanceIdTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            554..=559 | 561 | 562 => Ok(InstanceIdTypeChoice::CryptoKey(
+                CryptoKeyTypeChoice::tagged(tag, deserializer)?,
+            )),
+            560 => {
+                // TODO(setrofim): both instance-id-type-choice and crypto-key-type-choice
+                // specify tagged-bytes as a variant. It is not possible to distinguish between
+                // them (see https://github.com/ietf-rats-wg/draft-ietf-rats-corim/issues/428),
+                // so we have to make a choice whether we treat tagged-bytes as an instance ID
+                // or as a crypto key (that is an instance ID); both interpretations would be
+                // valid according to the spec (until the above issue is fixed in some way).
+                // Here, we're choosing to treat it as a generic ID, on the assumption that
+                // this is more likely to be the intent.
+                Ok(InstanceIdTypeChoice::Bytes(TaggedBytes::from(
+                    Bytes::deserialize(deserializer)?,
+                )))
             }
+            37 => Ok(InstanceIdTypeChoice::Uuid(TaggedUuidType::from(
+                UuidType::deserialize(deserializer)?,
+            ))),
+            550 => Ok(InstanceIdTypeChoice::Ueid(TaggedUeidType::from(
+                UeidType::deserialize(deserializer)?,
+            ))),
+            n => Err(de::Error::custom(format!(
+                "unexpected InstanceIdTypeChoice tag {n}"
+            ))),
         }
     }
 }
@@ -1129,126 +1063,126 @@ pub enum CryptoKeyTypeChoice<'a> {
     /// Base64-encoded PKIX certificate path
     PkixBase64CertPath(PkixBase64CertPathType<'a>),
     /// COSE key structure
-    CoseKey(CoseKeyType),
+    CoseKey(CoseKeyType<'a>),
     /// Generic cryptographic thumbprint
-    Thumbprint(ThumbprintType),
+    Thumbprint(ThumbprintType<'a>),
     /// Certificate thumbprint
-    CertThumbprint(CertThumbprintType),
+    CertThumbprint(CertThumbprintType<'a>),
     /// Certificate path thumbprint
-    CertPathThumbprint(CertPathThumbprintType),
+    CertPathThumbprint(CertPathThumbprintType<'a>),
     /// ASN.1 DER encoded PKIX certificate
-    PkixAsn1DerCert(PkixAsn1DerCertType),
+    PkixAsn1DerCert(PkixAsn1DerCertType<'a>),
     /// Raw bytes
-    Bytes(TaggedBytes),
+    Bytes(TaggedBytes<'a>),
 }
 
-impl CryptoKeyTypeChoice<'_> {
-    pub fn as_pkix_key(&self) -> Option<PkixBase64KeyType> {
+impl<'a> CryptoKeyTypeChoice<'a> {
+    pub fn as_pkix_key(&self) -> Option<PkixBase64KeyType<'_>> {
         match self {
             Self::PkixBase64Key(key) => Some(key.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_pkix_key(&self) -> Option<&PkixBase64KeyType> {
+    pub fn as_ref_pkix_key(&self) -> Option<&PkixBase64KeyType<'_>> {
         match self {
             Self::PkixBase64Key(key) => Some(key),
             _ => None,
         }
     }
 
-    pub fn as_pkix_cert(&self) -> Option<PkixBase64CertType> {
+    pub fn as_pkix_cert(&self) -> Option<PkixBase64CertType<'_>> {
         match self {
             Self::PkixBase64Cert(cert) => Some(cert.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_pkix_cert(&self) -> Option<&PkixBase64CertType> {
+    pub fn as_ref_pkix_cert(&self) -> Option<&PkixBase64CertType<'_>> {
         match self {
             Self::PkixBase64Cert(cert) => Some(cert),
             _ => None,
         }
     }
 
-    pub fn as_pkix_cert_path(&self) -> Option<PkixBase64CertPathType> {
+    pub fn as_pkix_cert_path(&self) -> Option<PkixBase64CertPathType<'_>> {
         match self {
             Self::PkixBase64CertPath(cert_path) => Some(cert_path.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_pkix_cert_path(&self) -> Option<&PkixBase64CertPathType> {
+    pub fn as_ref_pkix_cert_path(&self) -> Option<&PkixBase64CertPathType<'_>> {
         match self {
             Self::PkixBase64CertPath(cert_path) => Some(cert_path),
             _ => None,
         }
     }
 
-    pub fn as_cose_key(&self) -> Option<CoseKeyType> {
+    pub fn as_cose_key(&self) -> Option<CoseKeyType<'a>> {
         match self {
             Self::CoseKey(key) => Some(key.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_cose_key(&self) -> Option<&CoseKeyType> {
+    pub fn as_ref_cose_key(&self) -> Option<&CoseKeyType<'a>> {
         match self {
             Self::CoseKey(key) => Some(key),
             _ => None,
         }
     }
 
-    pub fn as_thumbprint(&self) -> Option<ThumbprintType> {
+    pub fn as_thumbprint(&self) -> Option<ThumbprintType<'a>> {
         match self {
             Self::Thumbprint(thumbprint) => Some(thumbprint.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_thumbprint(&self) -> Option<&ThumbprintType> {
+    pub fn as_ref_thumbprint(&self) -> Option<&ThumbprintType<'a>> {
         match self {
             Self::Thumbprint(thumbprint) => Some(thumbprint),
             _ => None,
         }
     }
 
-    pub fn as_cert_thumbprint(&self) -> Option<CertThumbprintType> {
+    pub fn as_cert_thumbprint(&self) -> Option<CertThumbprintType<'a>> {
         match self {
             Self::CertThumbprint(thumbprint) => Some(thumbprint.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_cert_thumbprint(&self) -> Option<&CertThumbprintType> {
+    pub fn as_ref_cert_thumbprint(&self) -> Option<&CertThumbprintType<'a>> {
         match self {
             Self::CertThumbprint(thumbprint) => Some(thumbprint),
             _ => None,
         }
     }
 
-    pub fn as_cert_path_thumbprint(&self) -> Option<CertPathThumbprintType> {
+    pub fn as_cert_path_thumbprint(&self) -> Option<CertPathThumbprintType<'a>> {
         match self {
             Self::CertPathThumbprint(thumbprint) => Some(thumbprint.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_cert_path_thumbprint(&self) -> Option<&CertPathThumbprintType> {
+    pub fn as_ref_cert_path_thumbprint(&self) -> Option<&CertPathThumbprintType<'a>> {
         match self {
             Self::CertPathThumbprint(thumbprint) => Some(thumbprint),
             _ => None,
         }
     }
 
-    pub fn as_pkix_asn1_der_cert(&self) -> Option<PkixAsn1DerCertType> {
+    pub fn as_pkix_asn1_der_cert(&self) -> Option<PkixAsn1DerCertType<'a>> {
         match self {
             Self::PkixAsn1DerCert(cert) => Some(cert.clone()),
             _ => None,
         }
     }
 
-    pub fn as_ref_pkix_asn1_der_cert(&self) -> Option<&PkixAsn1DerCertType> {
+    pub fn as_ref_pkix_asn1_der_cert(&self) -> Option<&PkixAsn1DerCertType<'a>> {
         match self {
             Self::PkixAsn1DerCert(cert) => Some(cert),
             _ => None,
@@ -1261,10 +1195,131 @@ impl CryptoKeyTypeChoice<'_> {
             _ => &[],
         }
     }
+
+    /// Returns the digest of a [`ThumbprintType`], [`CertThumbprintType`] or
+    /// [`CertPathThumbprintType`]
+    pub fn as_thumbprint_digest(&self) -> Option<&Digest<'a>> {
+        match self {
+            Self::Thumbprint(digest) => Some(digest.as_ref()),
+            Self::CertThumbprint(digest) => Some(digest.as_ref()),
+            Self::CertPathThumbprint(digest) => Some(digest.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Computes the thumbprint of a key or certificate using `alg`.
+    ///
+    /// * PKIX keys produce a [`ThumbprintType`] over the DER encoded SubjectPublicKeyInfo
+    /// * PKIX certificates produce a [`CertThumbprintType`] over the DER encoded certificate
+    /// * PKIX certificate paths produce a [`CertPathThumbprintType`] over the concatenated DER
+    ///   encoded certificates, in the order they appear in the path
+    /// * COSE keys produce a [`ThumbprintType`] as defined by RFC 9679
+    ///
+    /// Thumbprint and raw byte variants cannot be thumbprinted.
+    pub fn thumbprint(&self, alg: &HashAlgorithm) -> Result<CryptoKeyTypeChoice<'static>> {
+        let thumbprint: CryptoKeyTypeChoice<'static> = match self {
+            Self::PkixBase64Key(key) => {
+                let der = single_pkix_blob(decode_pkix_base64(key.as_ref())?, "key")?;
+                spki::SubjectPublicKeyInfoRef::try_from(der.as_slice()).map_err(|e| {
+                    TriplesError::InvalidKeyEncoding(format!("invalid PKIX key: {e}"))
+                })?;
+                ThumbprintType::from(alg.digest(&der)).into()
+            }
+            Self::PkixBase64Cert(cert) => {
+                let der = single_pkix_blob(decode_pkix_base64(cert.as_ref())?, "certificate")?;
+                CertThumbprintType::from(alg.digest(&der)).into()
+            }
+            Self::PkixBase64CertPath(path) => {
+                let der = decode_pkix_base64(path.as_ref())?.concat();
+                CertPathThumbprintType::from(alg.digest(&der)).into()
+            }
+            Self::PkixAsn1DerCert(cert) => {
+                CertThumbprintType::from(alg.digest(cert.as_ref())).into()
+            }
+            Self::CoseKey(key) => match key.as_ref() {
+                CoseKeySetOrKey::Key(key) => ThumbprintType::from(key.thumbprint(alg)?).into(),
+                CoseKeySetOrKey::KeySet(_) => Err(TriplesError::ThumbprintUnsupported(
+                    "COSE key sets".to_string(),
+                ))?,
+            },
+            Self::Thumbprint(_) | Self::CertThumbprint(_) | Self::CertPathThumbprint(_) => Err(
+                TriplesError::ThumbprintUnsupported("thumbprints".to_string()),
+            )?,
+            Self::Bytes(_) => Err(TriplesError::ThumbprintUnsupported(
+                "raw byte keys".to_string(),
+            ))?,
+        };
+
+        Ok(thumbprint)
+    }
+
+    /// Returns `true` if `thumbprint` is a thumbprint of this key or certificate. The
+    /// thumbprint is computed using the algorithm of `thumbprint`, and its kind (key,
+    /// certificate or certificate path thumbprint) must match the kind of `self`.
+    pub fn matches_thumbprint(&self, thumbprint: &CryptoKeyTypeChoice) -> Result<bool> {
+        let expected = thumbprint.as_thumbprint_digest().ok_or_else(|| {
+            TriplesError::ThumbprintUnsupported("comparison against a non-thumbprint".to_string())
+        })?;
+
+        let actual = self.thumbprint(&expected.alg)?;
+
+        Ok(
+            core::mem::discriminant(&actual) == core::mem::discriminant(thumbprint)
+                && actual.as_thumbprint_digest() == Some(expected),
+        )
+    }
+}
+
+/// Decodes the base64 text of a PKIX key, certificate or certificate path. Both bare base64 and
+/// PEM (RFC 7468) encodings are accepted; every PEM block is returned in order.
+fn decode_pkix_base64(text: &str) -> Result<Vec<Vec<u8>>> {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+    let decode = |b64: &str| {
+        STANDARD
+            .decode(b64)
+            .map_err(|e| TriplesError::InvalidKeyEncoding(format!("invalid base64: {e}")))
+    };
+
+    if !text.contains("-----BEGIN ") {
+        let b64: String = text.split_whitespace().collect();
+        return Ok(vec![decode(&b64)?]);
+    }
+
+    let mut blobs = vec![];
+    let mut block: Option<String> = None;
+
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("-----BEGIN ") {
+            block = Some(String::new());
+        } else if line.starts_with("-----END ") {
+            let b64 = block.take().ok_or_else(|| {
+                TriplesError::InvalidKeyEncoding("unexpected PEM END line".to_string())
+            })?;
+            blobs.push(decode(&b64)?);
+        } else if let Some(b64) = block.as_mut() {
+            b64.push_str(line);
+        }
+    }
+
+    if block.is_some() {
+        return Err(TriplesError::InvalidKeyEncoding("unterminated PEM block".to_string()).into());
+    }
+
+    Ok(blobs)
+}
+
+fn single_pkix_blob(mut blobs: Vec<Vec<u8>>, kind: &str) -> Result<Vec<u8>> {
+    match blobs.len() {
+        1 => Ok(blobs.remove(0)),
+        n => Err(TriplesError::InvalidKeyEncoding(format!(
+            "expected a single PKIX {kind}, found {n}"
+        )))?,
+    }
 }
 
-impl<'de> Deserialize<'de> for CryptoKeyTypeChoice<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for CryptoKeyTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -1296,40 +1351,40 @@ impl<'de> Deserialize<'de> for CryptoKeyTypeChoice<'_> {
                     ))
                 }
                 "cose-key" => {
-                    let sok: CoseKeySetOrKey = serde_json::from_str(tagged_value.value.get())
+                    let sok: CoseKeySetOrKey<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(CryptoKeyTypeChoice::CoseKey(CoseKeyType::from(sok)))
                 }
                 "thumbprint" => {
-                    let digest: Digest = serde_json::from_str(tagged_value.value.get())
+                    let digest: Digest<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(
                         digest,
                     )))
                 }
                 "cert-thumbprint" => {
-                    let digest: Digest = serde_json::from_str(tagged_value.value.get())
+                    let digest: Digest<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(CryptoKeyTypeChoice::CertThumbprint(
                         CertThumbprintType::from(digest),
                     ))
                 }
                 "cert-path-thumbprint" => {
-                    let digest: Digest = serde_json::from_str(tagged_value.value.get())
+                    let digest: Digest<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(CryptoKeyTypeChoice::CertPathThumbprint(
                         CertPathThumbprintType::from(digest),
                     ))
                 }
                 "pkix-asn1-der-cert" => {
-                    let bytes: Bytes = serde_json::from_str(tagged_value.value.get())
+                    let bytes: Bytes<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(CryptoKeyTypeChoice::PkixAsn1DerCert(
                         PkixAsn1DerCertType::from(bytes),
                     ))
                 }
                 "bytes" => {
-                    let bytes: Bytes = serde_json::from_str(tagged_value.value.get())
+                    let bytes: Bytes<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(CryptoKeyTypeChoice::Bytes(TaggedBytes::from(bytes)))
                 }
@@ -1338,81 +1393,49 @@ impl<'de> Deserialize<'de> for CryptoKeyTypeChoice<'_> {
                 ))),
             }
         } else {
-            match ciborium::Value::deserialize(deserializer)? {
-                ciborium::Value::Tag(tag, inner) => {
-                    // Re-serializing the inner Value so that we can deserialize it
-                    // into an appropriate type, once we figure out what that is
-                    // based on the tag.
-                    let mut buf: Vec<u8> = Vec::new();
-                    ciborium::into_writer(&inner, &mut buf).unwrap();
+            deserialize_tag_choice(deserializer)
+        }
+    }
+}
 
-                    match tag {
-                        554 => {
-                            let tstr: Tstr =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::PkixBase64Key(PkixBase64KeyType::from(
-                                tstr,
-                            )))
-                        }
-                        555 => {
-                            let tstr: Tstr =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::PkixBase64Cert(
-                                PkixBase64CertType::from(tstr),
-                            ))
-                        }
-                        556 => {
-                            let tstr: Tstr =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::PkixBase64CertPath(
-                                PkixBase64CertPathType::from(tstr),
-                            ))
-                        }
-                        558 => {
-                            let sok: CoseKeySetOrKey =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::CoseKey(CoseKeyType::from(sok)))
-                        }
-                        557 => {
-                            let digest: Digest =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(
-                                digest,
-                            )))
-                        }
-                        559 => {
-                            let digest: Digest =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::CertThumbprint(
-                                CertThumbprintType::from(digest),
-                            ))
-                        }
-                        561 => {
-                            let digest: Digest =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::CertPathThumbprint(
-                                CertPathThumbprintType::from(digest),
-                            ))
-                        }
-                        562 => {
-                            let bytes: Bytes =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::PkixAsn1DerCert(
-                                PkixAsn1DerCertType::from(bytes),
-                            ))
-                        }
-                        560 => {
-                            let bytes: Bytes =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(CryptoKeyTypeChoice::Bytes(TaggedBytes::from(bytes)))
-                        }
-                        n => Err(de::Error::custom(format!(
-                            "unexpected ClassIdTypeChoice tag {n}"
-                        ))),
-                    }
-                }
-                _ => Err(de::Error::custom("did not see a tag")),
-            }
+impl<'de: 'a, 'a> TagChoice<'de> for CryptoKeyTypeChoice<'a> {
+    const NAME: &'static str = "CryptoKeyTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            554 => Ok(CryptoKeyTypeChoice::PkixBase64Key(PkixBase64KeyType::from(
+                Tstr::deserialize(deserializer)?,
+            ))),
+            555 => Ok(CryptoKeyTypeChoice::PkixBase64Cert(
+                PkixBase64CertType::from(Tstr::deserialize(deserializer)?),
+            )),
+            556 => Ok(CryptoKeyTypeChoice::PkixBase64CertPath(
+                PkixBase64CertPathType::from(Tstr::deserialize(deserializer)?),
+            )),
+            558 => Ok(CryptoKeyTypeChoice::CoseKey(CoseKeyType::from(
+                CoseKeySetOrKey::deserialize(deserializer)?,
+            ))),
+            557 => Ok(CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(
+                Digest::deserialize(deserializer)?,
+            ))),
+            559 => Ok(CryptoKeyTypeChoice::CertThumbprint(
+                CertThumbprintType::from(Digest::deserialize(deserializer)?),
+            )),
+            561 => Ok(CryptoKeyTypeChoice::CertPathThumbprint(
+                CertPathThumbprintType::from(Digest::deserialize(deserializer)?),
+            )),
+            562 => Ok(CryptoKeyTypeChoice::PkixAsn1DerCert(
+                PkixAsn1DerCertType::from(Bytes::deserialize(deserializer)?),
+            )),
+            560 => Ok(CryptoKeyTypeChoice::Bytes(TaggedBytes::from(
+                Bytes::deserialize(deserializer)?,
+            ))),
+            n => Err(de::Error::custom(format!(
+                "unexpected CryptoKeyTypeChoice tag {n}"
+            ))),
         }
     }
 }
@@ -1421,14 +1444,14 @@ impl<'de> Deserialize<'de> for CryptoKeyTypeChoice<'_> {
 #[derive(Debug, Serialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
 #[repr(C)]
 #[serde(untagged)]
-pub enum GroupIdTypeChoice {
+pub enum GroupIdTypeChoice<'a> {
     /// UUID identifier
     Uuid(TaggedUuidType),
     /// Raw bytes
-    Bytes(TaggedBytes),
+    Bytes(TaggedBytes<'a>),
 }
 
-impl GroupIdTypeChoice {
+impl GroupIdTypeChoice<'_> {
     pub fn as_uuid_bytes(&self) -> Option<&[u8]> {
         match self {
             Self::Uuid(uuid) => Some(uuid.as_slice()),
@@ -1444,8 +1467,8 @@ impl GroupIdTypeChoice {
     }
 }
 
-impl<'de> Deserialize<'de> for GroupIdTypeChoice {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for GroupIdTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -1459,7 +1482,7 @@ impl<'de> Deserialize<'de> for GroupIdTypeChoice {
                     Ok(GroupIdTypeChoice::Uuid(TaggedUuidType::from(uuid)))
                 }
                 "bytes" => {
-                    let bytes: Bytes = serde_json::from_str(tagged_value.value.get())
+                    let bytes: Bytes<'a> = serde_json::from_str(tagged_value.value.get())
                         .map_err(de::Error::custom)?;
                     Ok(GroupIdTypeChoice::Bytes(TaggedBytes::from(bytes)))
                 }
@@ -1468,32 +1491,28 @@ impl<'de> Deserialize<'de> for GroupIdTypeChoice {
                 ))),
             }
         } else {
-            match ciborium::Value::deserialize(deserializer)? {
-                ciborium::Value::Tag(tag, inner) => {
-                    // Re-serializing the inner Value so that we can deserialize it
-                    // into an appropriate type, once we figure out what that is
-                    // based on the tag.
-                    let mut buf: Vec<u8> = Vec::new();
-                    ciborium::into_writer(&inner, &mut buf).unwrap();
+            deserialize_tag_choice(deserializer)
+        }
+    }
+}
 
-                    match tag {
-                        37 => {
-                            let uuid: UuidType =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(GroupIdTypeChoice::Uuid(TaggedUuidType::from(uuid)))
-                        }
-                        560 => {
-                            let bytes: Bytes =
-                                ciborium::from_reader(buf.as_slice()).map_err(de::Error::custom)?;
-                            Ok(GroupIdTypeChoice::Bytes(TaggedBytes::from(bytes)))
-                        }
-                        n => Err(de::Error::custom(format!(
-                            "unexpected ClassIdTypeChoice tag {n}"
-                        ))),
-                    }
-                }
-                _ => Err(de::Error::custom("did not see a tag")),
-            }
+impl<'de: 'a, 'a> TagChoice<'de> for GroupIdTypeChoice<'a> {
+    const NAME: &'static str = "GroupIdTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            37 => Ok(GroupIdTypeChoice::Uuid(TaggedUuidType::from(
+                UuidType::deserialize(deserializer)?,
+            ))),
+            560 => Ok(GroupIdTypeChoice::Bytes(TaggedBytes::from(
+                Bytes::deserialize(deserializer)?,
+            ))),
+            n => Err(de::Error::custom(format!(
+                "unexpected GroupIdTypeChoice tag {n}"
+            ))),
         }
     }
 }
@@ -1511,7 +1530,7 @@ pub struct MeasurementMap<'a> {
 }
 
 impl Serialize for MeasurementMap<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -1545,8 +1564,8 @@ impl Serialize for MeasurementMap<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for MeasurementMap<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for MeasurementMap<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -1555,14 +1574,14 @@ impl<'de> Deserialize<'de> for MeasurementMap<'_> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for MeasurementMapVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for MeasurementMapVisitor<'a> {
             type Value = MeasurementMap<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a map containing MeasurementMap fields")
             }
 
-            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: de::MapAccess<'de>,
             {
@@ -1637,7 +1656,7 @@ impl<'de> Deserialize<'de> for MeasurementMap<'_> {
 #[serde(untagged)]
 pub enum MeasuredElementTypeChoice<'a> {
     /// Object Identifier (OID)
-    Oid(OidType),
+    Oid(OidType<'a>),
     /// UUID identifier
     Uuid(TaggedUuidType),
     /// Unsigned integer
@@ -1706,8 +1725,8 @@ impl<'a> From<&'a str> for MeasuredElementTypeChoice<'a> {
     }
 }
 
-impl<'de> Deserialize<'de> for MeasuredElementTypeChoice<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for MeasuredElementTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -1788,50 +1807,42 @@ impl<'de> Deserialize<'de> for MeasuredElementTypeChoice<'_> {
                 }
             }
         } else {
-            match ciborium::Value::deserialize(deserializer)? {
-                ciborium::Value::Tag(tag, inner) => match tag {
-                    37 => {
-                        let value: Vec<u8> = match inner.deref() {
-                            ciborium::Value::Bytes(bytes) => Ok(bytes.clone()),
-                            value => Err(de::Error::custom(format!(
-                                "unexpected value {value:?} for MeasuredElementTypeChoice"
-                            ))),
-                        }?;
+            deserialize_tag_choice(deserializer)
+        }
+    }
+}
 
-                        Ok(MeasuredElementTypeChoice::Uuid(TaggedUuidType::from(
-                            UuidType::try_from(value.as_slice())
-                                .map_err(|_| de::Error::custom("invalid UUID bytes"))?,
-                        )))
-                    }
-                    111 => {
-                        let value: Vec<u8> = match inner.deref() {
-                            ciborium::Value::Bytes(bytes) => Ok(bytes.clone()),
-                            value => Err(de::Error::custom(format!(
-                                "unexpected value {value:?} for MeasuredElementTypeChoice"
-                            ))),
-                        }?;
+impl<'de: 'a, 'a> TagChoice<'de> for MeasuredElementTypeChoice<'a> {
+    const NAME: &'static str = "MeasuredElementTypeChoice";
 
-                        Ok(MeasuredElementTypeChoice::Oid(OidType::from(
-                            ObjectIdentifier::try_from(value)
-                                .map_err(|_| de::Error::custom("invalid OID bytes"))?,
-                        )))
-                    }
-                    n => Ok(MeasuredElementTypeChoice::Extension(ExtensionValue::Tag(
-                        n,
-                        Box::new(
-                            ExtensionValue::try_from(inner.deref().to_owned())
-                                .map_err(de::Error::custom)?,
-                        ),
-                    ))),
-                },
-                ciborium::Value::Text(text) => Ok(MeasuredElementTypeChoice::Tstr(text.into())),
-                ciborium::Value::Integer(int) => {
-                    Ok(MeasuredElementTypeChoice::UInt(i128::from(int).into()))
-                }
-                value => Ok(MeasuredElementTypeChoice::Extension(
-                    ExtensionValue::try_from(value).map_err(de::Error::custom)?,
-                )),
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            37 => Ok(MeasuredElementTypeChoice::Uuid(TaggedUuidType::from(
+                UuidType::deserialize(deserializer)?,
+            ))),
+            111 => Ok(MeasuredElementTypeChoice::Oid(OidType::from(
+                ObjectIdentifier::deserialize(deserializer)?,
+            ))),
+            n => Ok(MeasuredElementTypeChoice::Extension(ExtensionValue::Tag(
+                n,
+                Box::new(ExtensionValue::deserialize(deserializer)?),
+            ))),
+        }
+    }
+
+    fn untagged<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match ExtensionValue::deserialize(deserializer)? {
+            ExtensionValue::Text(text) => Ok(MeasuredElementTypeChoice::Tstr(text)),
+            ExtensionValue::Uint(int) | ExtensionValue::Int(int) => {
+                Ok(MeasuredElementTypeChoice::UInt(int))
             }
+            value => Ok(MeasuredElementTypeChoice::Extension(value)),
         }
     }
 }
@@ -1846,11 +1857,11 @@ pub struct MeasurementValuesMap<'a> {
     /// Optional security version number
     pub svn: Option<SvnTypeChoice>,
     /// Optiona cryptographic digests
-    pub digests: Option<DigestsType>,
+    pub digests: Option<DigestsType<'a>>,
     /// Optional status flags
     pub flags: Option<FlagsMap<'a>>,
     /// Optional raw measurement value
-    pub raw: Option<RawValueType>,
+    pub raw: Option<RawValueType<'a>>,
     /// Optional MAC address
     pub mac_addr: Option<MacAddrTypeChoice>,
     /// Optional IP address
@@ -1858,7 +1869,7 @@ pub struct MeasurementValuesMap<'a> {
     /// Optional serial number
     pub serial_number: Option<Text<'a>>,
     /// Optional UEID
-    pub ueid: Option<UeidType>,
+    pub ueid: Option<UeidType<'a>>,
     /// Optional UUID
     pub uuid: Option<UuidType>,
     /// Optional name
@@ -1872,7 +1883,7 @@ pub struct MeasurementValuesMap<'a> {
 }
 
 impl Serialize for MeasurementValuesMap<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -1977,8 +1988,8 @@ impl Serialize for MeasurementValuesMap<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for MeasurementValuesMap<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for MeasurementValuesMap<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -1987,20 +1998,20 @@ impl<'de> Deserialize<'de> for MeasurementValuesMap<'_> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for MeasurementValuesMapVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for MeasurementValuesMapVisitor<'a> {
             type Value = MeasurementValuesMap<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a map containing MeasurementValuesMap fields")
             }
 
-            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: de::MapAccess<'de>,
             {
                 let mut builder = MeasurementValuesMapBuilder::default();
-                let mut raw_value: Option<RawValueTypeChoice> = None;
-                let mut raw_value_mask: Option<RawValueMaskType> = None;
+                let mut raw_value: Option<RawValueTypeChoice<'a>> = None;
+                let mut raw_value_mask: Option<RawValueMaskType<'a>> = None;
                 let mut extensions = ExtensionMap::default();
 
                 loop {
@@ -2013,16 +2024,16 @@ impl<'de> Deserialize<'de> for MeasurementValuesMap<'_> {
                                 builder = builder.svn(map.next_value::<SvnTypeChoice>()?);
                             }
                             Some("digests") => {
-                                builder = builder.digest(map.next_value::<DigestsType>()?);
+                                builder = builder.digest(map.next_value::<DigestsType<'a>>()?);
                             }
                             Some("flags") => {
                                 builder = builder.flags(map.next_value::<FlagsMap>()?);
                             }
                             Some("raw-value") => {
-                                raw_value = Some(map.next_value::<RawValueTypeChoice>()?);
+                                raw_value = Some(map.next_value::<RawValueTypeChoice<'a>>()?);
                             }
                             Some("raw-value-mask") => {
-                                raw_value_mask = Some(map.next_value::<RawValueMaskType>()?);
+                                raw_value_mask = Some(map.next_value::<RawValueMaskType<'a>>()?);
                             }
                             Some("mac-addr") => {
                                 builder = builder.mac_addr(map.next_value::<MacAddrTypeChoice>()?);
@@ -2031,16 +2042,17 @@ impl<'de> Deserialize<'de> for MeasurementValuesMap<'_> {
                                 builder = builder.ip_addr(map.next_value::<IpAddrTypeChoice>()?);
                             }
                             Some("serial-number") => {
-                                builder = builder.serial_number(map.next_value::<Text>()?);
+                                builder =
+                                    builder.serial_number(map.next_value::<BorrowText>()?.into());
                             }
                             Some("ueid") => {
-                                builder = builder.ueid(map.next_value::<UeidType>()?);
+                                builder = builder.ueid(map.next_value::<UeidType<'a>>()?);
                             }
                             Some("uuid") => {
                                 builder = builder.uuid(map.next_value::<UuidType>()?);
                             }
                             Some("name") => {
-                                builder = builder.name(map.next_value::<Text>()?);
+                                builder = builder.name(map.next_value::<BorrowText>()?.into());
                             }
                             Some("cryptokeys") => {
                                 builder = builder
@@ -2067,16 +2079,16 @@ impl<'de> Deserialize<'de> for MeasurementValuesMap<'_> {
                                 builder = builder.svn(map.next_value::<SvnTypeChoice>()?);
                             }
                             Some(2) => {
-                                builder = builder.digest(map.next_value::<DigestsType>()?);
+                                builder = builder.digest(map.next_value::<DigestsType<'a>>()?);
                             }
                             Some(3) => {
                                 builder = builder.flags(map.next_value::<FlagsMap>()?);
                             }
                             Some(4) => {
-                                raw_value = Some(map.next_value::<RawValueTypeChoice>()?);
+                                raw_value = Some(map.next_value::<RawValueTypeChoice<'a>>()?);
                             }
                             Some(5) => {
-                                raw_value_mask = Some(map.next_value::<RawValueMaskType>()?);
+                                raw_value_mask = Some(map.next_value::<RawValueMaskType<'a>>()?);
                             }
                             Some(6) => {
                                 builder = builder.mac_addr(map.next_value::<MacAddrTypeChoice>()?);
@@ -2085,16 +2097,17 @@ impl<'de> Deserialize<'de> for MeasurementValuesMap<'_> {
                                 builder = builder.ip_addr(map.next_value::<IpAddrTypeChoice>()?);
                             }
                             Some(8) => {
-                                builder = builder.serial_number(map.next_value::<Text>()?);
+                                builder =
+                                    builder.serial_number(map.next_value::<BorrowText>()?.into());
                             }
                             Some(9) => {
-                                builder = builder.ueid(map.next_value::<UeidType>()?);
+                                builder = builder.ueid(map.next_value::<UeidType<'a>>()?);
                             }
                             Some(10) => {
                                 builder = builder.uuid(map.next_value::<UuidType>()?);
                             }
                             Some(11) => {
-                                builder = builder.name(map.next_value::<Text>()?);
+                                builder = builder.name(map.next_value::<BorrowText>()?.into());
                             }
                             Some(13) => {
                                 builder = builder
@@ -2146,11 +2159,11 @@ pub struct MeasurementValuesMapBuilder<'a> {
     /// Optional security version number
     pub svn: Option<SvnTypeChoice>,
     /// Optional cryptographic digest
-    pub digest: Option<DigestsType>,
+    pub digest: Option<DigestsType<'a>>,
     /// Optional status flags
     pub flags: Option<FlagsMap<'a>>,
     /// Optional raw measurement value
-    pub raw: Option<RawValueType>,
+    pub raw: Option<RawValueType<'a>>,
     /// Optional MAC address
     pub mac_addr: Option<MacAddrTypeChoice>,
     /// Optional IP address
@@ -2158,7 +2171,7 @@ pub struct MeasurementValuesMapBuilder<'a> {
     /// Optional serial number
     pub serial_number: Option<Text<'a>>,
     /// Optional UEID
-    pub ueid: Option<UeidType>,
+    pub ueid: Option<UeidType<'a>>,
     /// Optional UUID
     pub uuid: Option<UuidType>,
     /// Optional name
@@ -2180,7 +2193,7 @@ impl<'a> MeasurementValuesMapBuilder<'a> {
         self.svn = Some(value);
         self
     }
-    pub fn digest(mut self, value: DigestsType) -> Self {
+    pub fn digest(mut self, value: DigestsType<'a>) -> Self {
         self.digest = Some(value);
         self
     }
@@ -2188,7 +2201,7 @@ impl<'a> MeasurementValuesMapBuilder<'a> {
         self.flags = Some(value);
         self
     }
-    pub fn raw(mut self, value: RawValueType) -> Self {
+    pub fn raw(mut self, value: RawValueType<'a>) -> Self {
         self.raw = Some(value);
         self
     }
@@ -2204,7 +2217,7 @@ impl<'a> MeasurementValuesMapBuilder<'a> {
         self.serial_number = Some(value);
         self
     }
-    pub fn ueid(mut self, value: UeidType) -> Self {
+    pub fn ueid(mut self, value: UeidType<'a>) -> Self {
         self.ueid = Some(value);
         self
     }
@@ -2277,7 +2290,7 @@ pub struct VersionMap<'a> {
 }
 
 impl Serialize for VersionMap<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -2302,8 +2315,8 @@ impl Serialize for VersionMap<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for VersionMap<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for VersionMap<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -2312,14 +2325,14 @@ impl<'de> Deserialize<'de> for VersionMap<'_> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for VersionMapVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for VersionMapVisitor<'a> {
             type Value = VersionMap<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a map containing VersionMap fields")
             }
 
-            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: de::MapAccess<'de>,
             {
@@ -2330,7 +2343,7 @@ impl<'de> Deserialize<'de> for VersionMap<'_> {
                     if self.is_human_readable {
                         match map.next_key::<&str>()? {
                             Some("version") => {
-                                version = Some(map.next_value::<String>()?.into());
+                                version = Some(map.next_value::<BorrowText>()?.into());
                             }
                             Some("version-scheme") => {
                                 version_scheme = Some(map.next_value::<VersionScheme>()?);
@@ -2346,7 +2359,7 @@ impl<'de> Deserialize<'de> for VersionMap<'_> {
                     } else {
                         match map.next_key::<i64>()? {
                             Some(0) => {
-                                version = Some(map.next_value::<String>()?.into());
+                                version = Some(map.next_value::<BorrowText>()?.into());
                             }
                             Some(1) => {
                                 version_scheme = Some(map.next_value::<VersionScheme>()?);
@@ -2417,7 +2430,7 @@ impl SvnTypeChoice {
 }
 
 impl<'de> Deserialize<'de> for SvnTypeChoice {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -2426,18 +2439,18 @@ impl<'de> Deserialize<'de> for SvnTypeChoice {
         impl<'de> Visitor<'de> for SvnTypeChoiceJsonVisitor {
             type Value = SvnTypeChoice;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("either a uint, a tagged SVN, or a tagged MinSVN")
             }
 
-            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            fn visit_u64<E>(self, v: u64) -> core::result::Result<Self::Value, E>
             where
                 E: de::Error,
             {
                 Ok(SvnTypeChoice::Svn(v.into()))
             }
 
-            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: de::MapAccess<'de>,
             {
@@ -2511,7 +2524,7 @@ impl<'de> Deserialize<'de> for SvnTypeChoice {
 }
 
 /// Collection of one or more cryptographic digests
-pub type DigestsType = Vec<Digest>;
+pub type DigestsType<'a> = Vec<Digest<'a>>;
 
 /// Status flags indicating various security and configuration states
 #[derive(Default, Debug, From, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -2542,7 +2555,7 @@ pub struct FlagsMap<'a> {
 }
 
 impl Serialize for FlagsMap<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -2627,8 +2640,8 @@ impl Serialize for FlagsMap<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for FlagsMap<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for FlagsMap<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -2637,14 +2650,14 @@ impl<'de> Deserialize<'de> for FlagsMap<'_> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for FlagsMapVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for FlagsMapVisitor<'a> {
             type Value = FlagsMap<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a map containing FlagsMap fields")
             }
 
-            fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+            fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
             where
                 A: de::MapAccess<'de>,
             {
@@ -2807,9 +2820,9 @@ impl From<Eui64AddrType> for MacAddrTypeChoice {
 }
 
 impl TryFrom<&[u8]> for MacAddrTypeChoice {
-    type Error = std::array::TryFromSliceError;
+    type Error = core::array::TryFromSliceError;
 
-    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: &[u8]) -> core::result::Result<Self, Self::Error> {
         match value.len() {
             6 => Ok(Self::Eui48Addr(value.try_into().unwrap())),
             8 => Ok(Self::Eui64Addr(value.try_into().unwrap())),
@@ -2821,11 +2834,11 @@ impl TryFrom<&[u8]> for MacAddrTypeChoice {
 impl TryFrom<&str> for MacAddrTypeChoice {
     type Error = TriplesError;
 
-    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
         let parts: Vec<u8> = value
             .split("-")
             .map(|x| u8::from_str_radix(x, 16).map_err(|_| TriplesError::InvalidMacAddrType))
-            .collect::<std::result::Result<Vec<u8>, TriplesError>>()?;
+            .collect::<core::result::Result<Vec<u8>, TriplesError>>()?;
 
         let n = parts.len();
         match n {
@@ -2875,7 +2888,7 @@ impl DerefMut for MacAddrTypeChoice {
 }
 
 impl Display for MacAddrTypeChoice {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Eui48Addr(addr) => write!(
                 f,
@@ -2892,7 +2905,7 @@ impl Display for MacAddrTypeChoice {
 }
 
 impl Serialize for MacAddrTypeChoice {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -2910,7 +2923,7 @@ impl Serialize for MacAddrTypeChoice {
 }
 
 impl<'de> Deserialize<'de> for MacAddrTypeChoice {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -2989,7 +3002,7 @@ impl From<Ipv6AddrType> for IpAddrTypeChoice {
     }
 }
 
-impl From<IpAddrTypeChoice> for std::net::IpAddr {
+impl From<IpAddrTypeChoice> for core::net::IpAddr {
     fn from(value: IpAddrTypeChoice) -> Self {
         match value {
             IpAddrTypeChoice::Ipv4(addr) => Self::from(addr.to_owned()),
@@ -2998,7 +3011,7 @@ impl From<IpAddrTypeChoice> for std::net::IpAddr {
     }
 }
 
-impl From<&IpAddrTypeChoice> for std::net::IpAddr {
+impl From<&IpAddrTypeChoice> for core::net::IpAddr {
     fn from(value: &IpAddrTypeChoice) -> Self {
         match value {
             IpAddrTypeChoice::Ipv4(addr) => Self::from(addr.to_owned()),
@@ -3007,15 +3020,15 @@ impl From<&IpAddrTypeChoice> for std::net::IpAddr {
     }
 }
 
-impl From<std::net::IpAddr> for IpAddrTypeChoice {
-    fn from(value: std::net::IpAddr) -> Self {
+impl From<core::net::IpAddr> for IpAddrTypeChoice {
+    fn from(value: core::net::IpAddr) -> Self {
         match value {
-            std::net::IpAddr::V4(addrv4) => {
-                let octets: [u8; 4] = unsafe { std::mem::transmute(addrv4) };
+            core::net::IpAddr::V4(addrv4) => {
+                let octets: [u8; 4] = unsafe { core::mem::transmute(addrv4) };
                 IpAddrTypeChoice::Ipv4(octets)
             }
-            std::net::IpAddr::V6(addrv6) => {
-                let octets: [u8; 16] = unsafe { std::mem::transmute(addrv6) };
+            core::net::IpAddr::V6(addrv6) => {
+                let octets: [u8; 16] = unsafe { core::mem::transmute(addrv6) };
                 IpAddrTypeChoice::Ipv6(octets)
             }
         }
@@ -3023,8 +3036,8 @@ impl From<std::net::IpAddr> for IpAddrTypeChoice {
 }
 
 impl TryFrom<&[u8]> for IpAddrTypeChoice {
-    type Error = std::array::TryFromSliceError;
-    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
+    type Error = core::array::TryFromSliceError;
+    fn try_from(value: &[u8]) -> core::result::Result<Self, Self::Error> {
         match value.len() {
             4 => Ok(Self::Ipv4(value.try_into()?)),
             16 => Ok(Self::Ipv6(value.try_into()?)),
@@ -3034,10 +3047,10 @@ impl TryFrom<&[u8]> for IpAddrTypeChoice {
 }
 
 impl TryFrom<&str> for IpAddrTypeChoice {
-    type Error = std::net::AddrParseError;
+    type Error = core::net::AddrParseError;
 
-    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
-        Ok(value.parse::<std::net::IpAddr>()?.into())
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
+        Ok(value.parse::<core::net::IpAddr>()?.into())
     }
 }
 
@@ -3080,14 +3093,14 @@ impl AsMut<[u8]> for IpAddrTypeChoice {
 }
 
 impl Display for IpAddrTypeChoice {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ip_addr: std::net::IpAddr = self.into();
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let ip_addr: core::net::IpAddr = self.into();
         f.write_str(&format!("{}", ip_addr))
     }
 }
 
 impl Serialize for IpAddrTypeChoice {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3105,7 +3118,7 @@ impl Serialize for IpAddrTypeChoice {
 }
 
 impl<'de> Deserialize<'de> for IpAddrTypeChoice {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3133,7 +3146,7 @@ pub type Ipv6AddrType = [u8; 16];
 /// Collection of integrity register values
 #[derive(Debug, From, Constructor, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
 #[repr(C)]
-pub struct IntegrityRegisters<'a>(pub BTreeMap<Ulabel<'a>, Vec<Digest>>);
+pub struct IntegrityRegisters<'a>(pub BTreeMap<Ulabel<'a>, Vec<Digest<'a>>>);
 
 impl IntegrityRegisters<'_> {
     /// Returns whether the IntegrityRegisters is empty.
@@ -3147,14 +3160,14 @@ impl IntegrityRegisters<'_> {
     }
 
     /// Iterate over (Ulabel, Vec<Digest>) tuples contained in the IntegrityRegisters.
-    pub fn iter(&self) -> Iter<'_, Ulabel, Vec<Digest>> {
+    pub fn iter(&self) -> Iter<'_, Ulabel<'_>, Vec<Digest<'_>>> {
         self.0.iter()
     }
 }
 
 impl<'a> IntegrityRegisters<'a> {
     /// Returns whether the provided digest matches the measured object associated with the label.
-    pub fn check(&self, label: &'a Ulabel<'a>, digest: Digest) -> bool {
+    pub fn check(&self, label: &'a Ulabel<'a>, digest: Digest<'a>) -> bool {
         for (key, digests) in self.iter() {
             if key == label {
                 if digests.contains(&digest) {
@@ -3169,7 +3182,7 @@ impl<'a> IntegrityRegisters<'a> {
     }
 
     /// Adds a digest to the measured object identified by label.
-    pub fn add_digest(&mut self, label: Ulabel<'a>, digest: Digest) -> Result<()> {
+    pub fn add_digest(&mut self, label: Ulabel<'a>, digest: Digest<'a>) -> Result<()> {
         match self.0.get_mut(&label) {
             Some(v) => {
                 for existing_digest in v.iter() {
@@ -3193,14 +3206,14 @@ impl<'a> IntegrityRegisters<'a> {
     /// Adds a digest to the measured object identified by label, replacing the existing digest
     /// with that algorithm, if one is already registered for label. If a digest was replaced, the
     /// old value is returned
-    pub fn replace_digest(&mut self, label: Ulabel<'a>, digest: Digest) -> Option<Digest> {
-        let mut replaced: Option<Digest> = None;
+    pub fn replace_digest(&mut self, label: Ulabel<'a>, digest: Digest<'a>) -> Option<Digest<'a>> {
+        let mut replaced: Option<Digest<'a>> = None;
 
         match self.0.get_mut(&label) {
             Some(v) => {
                 for (i, existing_digest) in v.iter().enumerate() {
                     if existing_digest.alg == digest.alg {
-                        replaced = Some(std::mem::replace(&mut v[i], digest.clone()));
+                        replaced = Some(core::mem::replace(&mut v[i], digest.clone()));
                         break;
                     }
                 }
@@ -3219,7 +3232,7 @@ impl<'a> IntegrityRegisters<'a> {
 }
 
 impl<'a> Index<&Ulabel<'a>> for IntegrityRegisters<'a> {
-    type Output = Vec<Digest>;
+    type Output = Vec<Digest<'a>>;
 
     fn index(&self, index: &Ulabel<'a>) -> &Self::Output {
         &self.0[index]
@@ -3227,7 +3240,7 @@ impl<'a> Index<&Ulabel<'a>> for IntegrityRegisters<'a> {
 }
 
 impl Serialize for IntegrityRegisters<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3241,8 +3254,8 @@ impl Serialize for IntegrityRegisters<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for IntegrityRegisters<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for IntegrityRegisters<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3269,7 +3282,7 @@ pub struct EndorsedTripleRecord<'a> {
 }
 
 impl Serialize for EndorsedTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3280,8 +3293,8 @@ impl Serialize for EndorsedTripleRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for EndorsedTripleRecord<'a> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<EndorsedTripleRecord<'a>, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for EndorsedTripleRecord<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<EndorsedTripleRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3289,17 +3302,17 @@ impl<'de, 'a> Deserialize<'de> for EndorsedTripleRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for EndorsedTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for EndorsedTripleRecordVisitor<'a> {
             type Value = EndorsedTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("a sequence of [EnvironmentMap, Vec<MeasurementMap>]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<EndorsedTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<EndorsedTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3332,7 +3345,7 @@ pub struct IdentityTripleRecord<'a> {
 }
 
 impl Serialize for IdentityTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3351,8 +3364,8 @@ impl Serialize for IdentityTripleRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for IdentityTripleRecord<'a> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<IdentityTripleRecord<'a>, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for IdentityTripleRecord<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<IdentityTripleRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3360,17 +3373,17 @@ impl<'de, 'a> Deserialize<'de> for IdentityTripleRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for IdentityTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for IdentityTripleRecordVisitor<'a> {
             type Value = IdentityTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("A sequence of [EnvironmentMap, Vec<CryptoKeyTypeChoice>, Option<TriplesRecordCondition>]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<IdentityTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<IdentityTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3404,11 +3417,11 @@ impl<'de, 'a> Deserialize<'de> for IdentityTripleRecord<'a> {
 pub struct TriplesRecordCondition<'a> {
     /// Optional measurement key identifier
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "0")]
+    #[serde(borrow, rename = "0")]
     pub mkey: Option<MeasuredElementTypeChoice<'a>>,
     /// Keys authorized to verify the condition
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "1")]
+    #[serde(borrow, rename = "1")]
     pub authorized_by: Option<Vec<CryptoKeyTypeChoice<'a>>>,
 }
 
@@ -3454,7 +3467,7 @@ pub struct AttestKeyTripleRecord<'a> {
 }
 
 impl Serialize for AttestKeyTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3473,8 +3486,8 @@ impl Serialize for AttestKeyTripleRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for AttestKeyTripleRecord<'a> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<AttestKeyTripleRecord<'a>, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for AttestKeyTripleRecord<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<AttestKeyTripleRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3482,17 +3495,17 @@ impl<'de, 'a> Deserialize<'de> for AttestKeyTripleRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for AttestKeyTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for AttestKeyTripleRecordVisitor<'a> {
             type Value = AttestKeyTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("A sequence of [EnvironmentMap, Vec<CryptoKeyTypeChoice>, Option<TriplesRecordCondition>]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<AttestKeyTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<AttestKeyTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3534,7 +3547,7 @@ pub struct DomainDependencyTripleRecord<'a> {
 
 // Need to implement Serialize / Deserialize here.
 impl Serialize for DomainDependencyTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3545,8 +3558,8 @@ impl Serialize for DomainDependencyTripleRecord<'_> {
     }
 }
 
-impl<'de> Deserialize<'de> for DomainDependencyTripleRecord<'_> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for DomainDependencyTripleRecord<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3554,17 +3567,17 @@ impl<'de> Deserialize<'de> for DomainDependencyTripleRecord<'_> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for DomainDependencyTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for DomainDependencyTripleRecordVisitor<'a> {
             type Value = DomainDependencyTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("A sequence of [DomainTypeChoice, Vec<EnvironmentMap>]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<DomainDependencyTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<DomainDependencyTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3588,8 +3601,10 @@ impl<'de> Deserialize<'de> for DomainDependencyTripleRecord<'_> {
 }
 
 /// Types of domain identifiers
-#[derive(Debug, Serialize, Deserialize, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
-#[serde(untagged)]
+///
+/// In JSON the UUID alternative is written as `{"type": "uuid", "value": <uuid>}` so that it
+/// can be told apart from text.
+#[derive(Debug, From, TryFrom, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub enum DomainTypeChoice<'a> {
     /// Unsigned integer identifier
     Uint(Uint),
@@ -3598,7 +3613,97 @@ pub enum DomainTypeChoice<'a> {
     /// UUID identifier
     Uuid(UuidType),
     /// Object Identifier (OID)
-    Oid(OidType),
+    Oid(OidType<'a>),
+}
+
+impl Serialize for DomainTypeChoice<'_> {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Uint(uint) => uint.serialize(serializer),
+            Self::Text(text) => serializer.serialize_str(text),
+            Self::Uuid(uuid) if serializer.is_human_readable() => {
+                serialize_json_typed(serializer, "uuid", uuid)
+            }
+            Self::Uuid(uuid) => uuid.serialize(serializer),
+            Self::Oid(oid) => oid.serialize(serializer),
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for DomainTypeChoice<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            match serde_json::Value::deserialize(deserializer)? {
+                serde_json::Value::Number(n) => match n.as_u64() {
+                    Some(u) => Ok(Self::Uint(u.into())),
+                    None => Err(de::Error::custom(format!(
+                        "a number must be an unsigned integer, got {n:?}"
+                    ))),
+                },
+                serde_json::Value::String(text) => Ok(Self::Text(text.into())),
+                serde_json::Value::Object(map) => match json_typed_value(map)? {
+                    (typ, value) if typ == "uuid" => Ok(Self::Uuid(
+                        serde_json::from_value(value).map_err(de::Error::custom)?,
+                    )),
+                    (typ, serde_json::Value::String(value)) if typ == "oid" => {
+                        Ok(Self::Oid(OidType::from(
+                            ObjectIdentifier::try_from(value.as_str())
+                                .map_err(|_| de::Error::custom("invalid OID"))?,
+                        )))
+                    }
+                    (typ, _) => Err(de::Error::custom(format!(
+                        "unexpected type {typ} for DomainTypeChoice"
+                    ))),
+                },
+                value => Err(de::Error::custom(format!(
+                    "unexpected value {value:?} for DomainTypeChoice"
+                ))),
+            }
+        } else {
+            deserialize_tag_choice(deserializer)
+        }
+    }
+}
+
+impl<'de: 'a, 'a> TagChoice<'de> for DomainTypeChoice<'a> {
+    const NAME: &'static str = "DomainTypeChoice";
+
+    fn tagged<D>(tag: u64, deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match tag {
+            111 => Ok(Self::Oid(OidType::from(ObjectIdentifier::deserialize(
+                deserializer,
+            )?))),
+            n => Err(de::Error::custom(format!(
+                "unexpected DomainTypeChoice tag {n}"
+            ))),
+        }
+    }
+
+    fn untagged<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match ExtensionValue::deserialize(deserializer)? {
+            ExtensionValue::Uint(int) | ExtensionValue::Int(int) => Ok(Self::Uint(int)),
+            ExtensionValue::Text(text) => Ok(Self::Text(text)),
+            ExtensionValue::Bytes(bytes) => Ok(Self::Uuid(
+                UuidType::try_from(bytes.as_ref())
+                    .map_err(|_| de::Error::custom("invalid UUID bytes"))?,
+            )),
+            value => Err(de::Error::custom(format!(
+                "unexpected value {value:?} for DomainTypeChoice"
+            ))),
+        }
+    }
 }
 
 impl DomainTypeChoice<'_> {
@@ -3642,7 +3747,7 @@ pub struct DomainMembershipTripleRecord<'a> {
 }
 
 impl Serialize for DomainMembershipTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3653,10 +3758,10 @@ impl Serialize for DomainMembershipTripleRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for DomainMembershipTripleRecord<'a> {
+impl<'de: 'a, 'a> Deserialize<'de> for DomainMembershipTripleRecord<'a> {
     fn deserialize<D>(
         deserializer: D,
-    ) -> std::result::Result<DomainMembershipTripleRecord<'a>, D::Error>
+    ) -> core::result::Result<DomainMembershipTripleRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3664,17 +3769,17 @@ impl<'de, 'a> Deserialize<'de> for DomainMembershipTripleRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for DomainMembershipTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for DomainMembershipTripleRecordVisitor<'a> {
             type Value = DomainMembershipTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("A sequence of [DomainTypeChoice, Vec<EnvironmentMap>]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<DomainMembershipTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<DomainMembershipTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3708,7 +3813,7 @@ pub struct CoswidTripleRecord<'a> {
 }
 
 impl Serialize for CoswidTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3719,8 +3824,8 @@ impl Serialize for CoswidTripleRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for CoswidTripleRecord<'a> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<CoswidTripleRecord<'a>, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for CoswidTripleRecord<'a> {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<CoswidTripleRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3728,17 +3833,17 @@ impl<'de, 'a> Deserialize<'de> for CoswidTripleRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for CoswidTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for CoswidTripleRecordVisitor<'a> {
             type Value = CoswidTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("A sequence of [EnvironmentMap, Vec<ConciseSwidTagId>]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<CoswidTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<CoswidTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3783,7 +3888,7 @@ pub struct ConditionalEndorsementSeriesTripleRecord<'a> {
 }
 
 impl Serialize for ConditionalEndorsementSeriesTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3794,10 +3899,10 @@ impl Serialize for ConditionalEndorsementSeriesTripleRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for ConditionalEndorsementSeriesTripleRecord<'a> {
+impl<'de: 'a, 'a> Deserialize<'de> for ConditionalEndorsementSeriesTripleRecord<'a> {
     fn deserialize<D>(
         deserializer: D,
-    ) -> std::result::Result<ConditionalEndorsementSeriesTripleRecord<'a>, D::Error>
+    ) -> core::result::Result<ConditionalEndorsementSeriesTripleRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3805,10 +3910,10 @@ impl<'de, 'a> Deserialize<'de> for ConditionalEndorsementSeriesTripleRecord<'a>
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for ConditionalEndorsementSeriesTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for ConditionalEndorsementSeriesTripleRecordVisitor<'a> {
             type Value = ConditionalEndorsementSeriesTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str(
                     "A sequence of [StatefulEnvironmentRecord, Vec<ConditionalSeriesRecord>]",
                 )
@@ -3817,7 +3922,7 @@ impl<'de, 'a> Deserialize<'de> for ConditionalEndorsementSeriesTripleRecord<'a>
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<ConditionalEndorsementSeriesTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<ConditionalEndorsementSeriesTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3850,7 +3955,7 @@ pub struct StatefulEnvironmentRecord<'a> {
 }
 
 impl Serialize for StatefulEnvironmentRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3861,10 +3966,10 @@ impl Serialize for StatefulEnvironmentRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for StatefulEnvironmentRecord<'a> {
+impl<'de: 'a, 'a> Deserialize<'de> for StatefulEnvironmentRecord<'a> {
     fn deserialize<D>(
         deserializer: D,
-    ) -> std::result::Result<StatefulEnvironmentRecord<'a>, D::Error>
+    ) -> core::result::Result<StatefulEnvironmentRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3872,17 +3977,17 @@ impl<'de, 'a> Deserialize<'de> for StatefulEnvironmentRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for StatefulEnvironmentRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for StatefulEnvironmentRecordVisitor<'a> {
             type Value = StatefulEnvironmentRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("A sequence of [EnvironmentMap, [+ MeasurementMap]]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<StatefulEnvironmentRecord<'a>, A::Error>
+            ) -> core::result::Result<StatefulEnvironmentRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3913,7 +4018,7 @@ pub struct ConditionalSeriesRecord<'a> {
 }
 
 impl Serialize for ConditionalSeriesRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3924,8 +4029,10 @@ impl Serialize for ConditionalSeriesRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for ConditionalSeriesRecord<'a> {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<ConditionalSeriesRecord<'a>, D::Error>
+impl<'de: 'a, 'a> Deserialize<'de> for ConditionalSeriesRecord<'a> {
+    fn deserialize<D>(
+        deserializer: D,
+    ) -> core::result::Result<ConditionalSeriesRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3933,17 +4040,17 @@ impl<'de, 'a> Deserialize<'de> for ConditionalSeriesRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for ConditionalSeriesRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for ConditionalSeriesRecordVisitor<'a> {
             type Value = ConditionalSeriesRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str("A sequence of [Vec<MeasurementMap>, Vec<MeasurementMap>]")
             }
 
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<ConditionalSeriesRecord<'a>, A::Error>
+            ) -> core::result::Result<ConditionalSeriesRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -3974,7 +4081,7 @@ pub struct ConditionalEndorsementTripleRecord<'a> {
 }
 
 impl Serialize for ConditionalEndorsementTripleRecord<'_> {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -3985,10 +4092,10 @@ impl Serialize for ConditionalEndorsementTripleRecord<'_> {
     }
 }
 
-impl<'de, 'a> Deserialize<'de> for ConditionalEndorsementTripleRecord<'a> {
+impl<'de: 'a, 'a> Deserialize<'de> for ConditionalEndorsementTripleRecord<'a> {
     fn deserialize<D>(
         deserializer: D,
-    ) -> std::result::Result<ConditionalEndorsementTripleRecord<'a>, D::Error>
+    ) -> core::result::Result<ConditionalEndorsementTripleRecord<'a>, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -3996,10 +4103,10 @@ impl<'de, 'a> Deserialize<'de> for ConditionalEndorsementTripleRecord<'a> {
             marker: PhantomData<&'a str>,
         }
 
-        impl<'de, 'a> Visitor<'de> for ConditionalEndorsementTripleRecordVisitor<'a> {
+        impl<'de: 'a, 'a> Visitor<'de> for ConditionalEndorsementTripleRecordVisitor<'a> {
             type Value = ConditionalEndorsementTripleRecord<'a>;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                 formatter.write_str(
                     "A sequence of [Vec<StatefulEnvironmentRecord>, Vec<EndorsedTripleRecord>]",
                 )
@@ -4008,7 +4115,7 @@ impl<'de, 'a> Deserialize<'de> for ConditionalEndorsementTripleRecord<'a> {
             fn visit_seq<A>(
                 self,
                 mut seq: A,
-            ) -> std::result::Result<ConditionalEndorsementTripleRecord<'a>, A::Error>
+            ) -> core::result::Result<ConditionalEndorsementTripleRecord<'a>, A::Error>
             where
                 A: SeqAccess<'de>,
             {
@@ -4089,8 +4196,8 @@ mod test {
         );
 
         let class_id_ext = ClassIdTypeChoice::Extension(ExtensionValue::Tag(
-                600,
-                Box::new(ExtensionValue::Bytes([0x01, 0x02, 0x03].as_slice().into())),
+            600,
+            Box::new(ExtensionValue::Bytes([0x01, 0x02, 0x03].as_slice().into())),
         ));
 
         let actual = serde_json::to_string(&class_id_ext).unwrap();
@@ -4122,13 +4229,13 @@ mod test {
         assert_eq!(actual, expected);
 
         let class_id_oid_de: ClassIdTypeChoice =
-            ciborium::from_reader(expected.as_slice()).unwrap();
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(class_id_oid_de, class_id_oid);
 
         let class_id_ext = ClassIdTypeChoice::Extension(ExtensionValue::Tag(
-                600,
-                Box::new(ExtensionValue::Bytes([0x01, 0x02, 0x03].as_slice().into())),
+            600,
+            Box::new(ExtensionValue::Bytes([0x01, 0x02, 0x03].as_slice().into())),
         ));
 
         let mut actual: Vec<u8> = Vec::new();
@@ -4142,11 +4249,74 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let class_id_ext_de: ClassIdTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let class_id_ext_de: ClassIdTypeChoice =
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(class_id_ext_de, class_id_ext);
     }
 
+    #[test]
+    fn test_tag_choice_untagged_cbor() {
+        let untagged: Vec<(Vec<u8>, ExtensionValue)> = vec![
+            (vec![0x63, 0x61, 0x62, 0x63], ExtensionValue::Text("abc".into())),
+            (vec![0x18, 0x2a], ExtensionValue::Uint(42.into())),
+            (
+                vec![
+                    0x82, // array(2)
+                      0x01,
+                      0xd9, 0x02, 0x58, // tag(600)
+                        0x41, 0x01, // bstr(1)
+                ],
+                ExtensionValue::Array(vec![
+                    ExtensionValue::Uint(1.into()),
+                    ExtensionValue::Tag(
+                        600,
+                        Box::new(ExtensionValue::Bytes([0x01].as_slice().into())),
+                    ),
+                ]),
+            ),
+            (
+                vec![
+                    0xa1, // map(1)
+                      0x01,
+                      0x61, 0x61, // "a"
+                ],
+                ExtensionValue::Map(BTreeMap::from([(
+                    crate::Label::Int(1.into()),
+                    ExtensionValue::Text("a".into()),
+                )])),
+            ),
+        ];
+
+        for (cbor, value) in untagged {
+            let class_id: ClassIdTypeChoice = crate::cbor::from_slice(cbor.as_slice()).unwrap();
+            assert_eq!(class_id, ClassIdTypeChoice::Extension(value));
+        }
+
+        let err = crate::cbor::from_slice::<ClassIdTypeChoice>([0xf9, 0x3c, 0x00].as_slice())
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("floating point extension values not supported"));
+        let err = crate::cbor::from_slice::<ClassIdTypeChoice>([0xa1, 0xf5, 0x01].as_slice())
+            .unwrap_err();
+        assert!(err.to_string().contains("an integer or text map key"));
+
+        let err =
+            crate::cbor::from_slice::<InstanceIdTypeChoice>([0x41, 0x01].as_slice()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("did not see a tag for InstanceIdTypeChoice"));
+
+        let err = crate::cbor::from_slice::<GroupIdTypeChoice>(
+            [0xd9, 0x02, 0x58, 0x41, 0x01].as_slice(),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unexpected GroupIdTypeChoice tag 600"));
+    }
+
     #[test]
     fn test_class_map_serde() {
         let class_map = ClassMap {
@@ -4183,7 +4353,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let class_map_de: ClassMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let class_map_de: ClassMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(class_map_de, class_map);
 
@@ -4219,7 +4389,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let class_map_de: ClassMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let class_map_de: ClassMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(class_map_de, class_map);
 
@@ -4266,7 +4436,8 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let group_id_de: GroupIdTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let group_id_de: GroupIdTypeChoice =
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(group_id_de, group_id);
     }
@@ -4294,7 +4465,7 @@ mod test {
         assert_eq!(buffer, expected);
 
         let crypto_key_de: CryptoKeyTypeChoice =
-            ciborium::from_reader(expected.as_slice()).unwrap();
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(crypto_key_de, crypto_key);
 
@@ -4369,7 +4540,7 @@ mod test {
 
         assert_eq!(buffer, expected);
 
-        let env_map_de: EnvironmentMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let env_map_de: EnvironmentMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(env_map_de, env_map);
 
@@ -4404,7 +4575,7 @@ mod test {
 
         assert_eq!(buffer, expected);
 
-        let env_map_de: EnvironmentMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let env_map_de: EnvironmentMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(env_map_de, env_map);
 
@@ -4421,7 +4592,6 @@ mod test {
 
     mod integ_regs {
         use super::super::*;
-        use crate::core::*;
 
         #[test]
         fn test_add_replace_check() {
@@ -4530,7 +4700,7 @@ mod test {
 
             assert_eq!(buffer, expected);
 
-            let regs_de: IntegrityRegisters = ciborium::from_reader(expected.as_slice()).unwrap();
+            let regs_de: IntegrityRegisters = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
             assert_eq!(regs_de, regs);
 
@@ -4575,7 +4745,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let addr_de: IpAddrTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let addr_de: IpAddrTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(addr_de, addr);
 
@@ -4600,7 +4770,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let addr_de: IpAddrTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let addr_de: IpAddrTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(addr_de, addr);
     }
@@ -4627,7 +4797,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let addr_de: MacAddrTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let addr_de: MacAddrTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(addr_de, addr);
 
@@ -4651,7 +4821,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let addr_de: MacAddrTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let addr_de: MacAddrTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(addr_de, addr);
     }
@@ -4702,7 +4872,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let fm_de: FlagsMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let fm_de: FlagsMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(fm_de, fm);
 
@@ -4747,7 +4917,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let fm_de: FlagsMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let fm_de: FlagsMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(fm_de, fm);
 
@@ -4775,7 +4945,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let svn_de: SvnTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let svn_de: SvnTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(svn_de, svn);
 
@@ -4799,7 +4969,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let svn_de: SvnTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let svn_de: SvnTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(svn_de, svn);
 
@@ -4825,7 +4995,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let svn_de: SvnTypeChoice = ciborium::from_reader(expected.as_slice()).unwrap();
+        let svn_de: SvnTypeChoice = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(svn_de, svn);
 
@@ -4862,7 +5032,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let vm_de: VersionMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let vm_de: VersionMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(vm_de, vm);
 
@@ -4878,6 +5048,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_measurement_values_map_serde() {
         let mvm = MeasurementValuesMap {
             version: Some(VersionMap {
@@ -5006,7 +5177,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let mvm_de: MeasurementValuesMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let mvm_de: MeasurementValuesMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(mvm_de, mvm);
 
@@ -5035,7 +5206,7 @@ mod test {
         assert_eq!(actual, expected);
 
         let metc_de: MeasuredElementTypeChoice =
-            ciborium::from_reader(expected.as_slice()).unwrap();
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(metc_de, metc);
 
@@ -5062,7 +5233,7 @@ mod test {
         assert_eq!(actual, expected);
 
         let metc_de: MeasuredElementTypeChoice =
-            ciborium::from_reader(expected.as_slice()).unwrap();
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(metc_de, metc);
 
@@ -5092,7 +5263,7 @@ mod test {
         assert_eq!(actual, expected);
 
         let metc_de: MeasuredElementTypeChoice =
-            ciborium::from_reader(expected.as_slice()).unwrap();
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(metc_de, metc);
 
@@ -5130,7 +5301,7 @@ mod test {
         assert_eq!(actual, expected);
 
         let metc_de: MeasuredElementTypeChoice =
-            ciborium::from_reader(expected.as_slice()).unwrap();
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(metc_de, metc);
 
@@ -5162,7 +5333,7 @@ mod test {
         assert_eq!(actual, expected);
 
         let metc_de: MeasuredElementTypeChoice =
-            ciborium::from_reader(expected.as_slice()).unwrap();
+            crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(metc_de, metc);
 
@@ -5213,7 +5384,7 @@ mod test {
 
         assert_eq!(actual, expected);
 
-        let mm_de: MeasurementMap = ciborium::from_reader(expected.as_slice()).unwrap();
+        let mm_de: MeasurementMap = crate::cbor::from_slice(expected.as_slice()).unwrap();
 
         assert_eq!(mm_de, mm);
 
@@ -5228,4 +5399,190 @@ mod test {
 
         assert_eq!(mm_de, mm);
     }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn leaf_der() -> Vec<u8> {
+        let pem = include_str!("../testdata/x509/leaf.pem");
+        decode_pkix_base64(pem).unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_domain_type_choice_serde() {
+        let domains = vec![
+            DomainTypeChoice::Uint(Integer(7)),
+            DomainTypeChoice::Text("platform".into()),
+            DomainTypeChoice::Uuid(UuidType::from(FixedBytes([0x11; 16]))),
+            DomainTypeChoice::Oid(OidType::from(ObjectIdentifier::try_from("1.2.3").unwrap())),
+        ];
+
+        let mut actual: Vec<u8> = vec![];
+        ciborium::into_writer(&domains, &mut actual).unwrap();
+
+        let domains_de: Vec<DomainTypeChoice> = crate::cbor::from_slice(actual.as_slice()).unwrap();
+
+        assert_eq!(domains_de, domains);
+
+        let actual = serde_json::to_string(&domains).unwrap();
+
+        let expected = r#"[7,"platform",{"type":"uuid","value":"11111111-1111-1111-1111-111111111111"},{"type":"oid","value":"1.2.3"}]"#;
+
+        assert_eq!(actual, expected);
+
+        let domains_de: Vec<DomainTypeChoice> = serde_json::from_str(expected).unwrap();
+
+        assert_eq!(domains_de, domains);
+
+        assert!(serde_json::from_str::<DomainTypeChoice>("-1").is_err());
+        assert!(
+            serde_json::from_str::<DomainTypeChoice>(r#"{"type":"bytes","value":"AQ"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cose_key_thumbprint() {
+        use crate::core::{CoseEllipticCurve, CoseKey, CoseKty};
+
+        // Example from RFC 9679, section 6
+        let key = CoseKey {
+            kty: CoseKty::Ec2,
+            kid: Some(Bytes::from(
+                b"meriadoc.brandybuck@buckland.example".to_vec(),
+            )),
+            alg: None,
+            key_ops: None,
+            base_iv: None,
+            crv: Some(CoseEllipticCurve::P256),
+            x: Some(Bytes::from(from_hex(
+                "65eda5a12577c2bae829437fe338701a10aaa375e1bb5b5de108de439c08551d",
+            ))),
+            y: Some(Bytes::from(from_hex(
+                "1e52ed75701163f7f9e40ddf9f341b3dc9ba860af7e0ca7ca7e9eecd0084d19c",
+            ))),
+            d: None,
+            k: None,
+        };
+
+        let key = CryptoKeyTypeChoice::CoseKey(CoseKeyType::from(CoseKeySetOrKey::Key(key)));
+
+        let actual = key.thumbprint(&HashAlgorithm::Sha256).unwrap();
+
+        let expected = CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(Digest {
+            alg: HashAlgorithm::Sha256,
+            val: from_hex("496bd8afadf307e5b08c64b0421bf9dc01528a344a43bda88fadd1669da253ec")
+                .into(),
+        }));
+
+        assert_eq!(actual, expected);
+        assert!(key.matches_thumbprint(&expected).unwrap());
+
+        let truncated = key.thumbprint(&HashAlgorithm::Sha256_64).unwrap();
+        let digest = truncated.as_thumbprint_digest().unwrap();
+
+        assert_eq!(digest.alg, HashAlgorithm::Sha256_64);
+        assert_eq!(digest.val.as_ref(), &from_hex("496bd8afadf307e5")[..]);
+    }
+
+    #[test]
+    fn test_cert_thumbprint() {
+        use sha2::Digest as _;
+
+        let der = leaf_der();
+        let expected = CryptoKeyTypeChoice::CertThumbprint(CertThumbprintType::from(Digest {
+            alg: HashAlgorithm::Sha256,
+            val: sha2::Sha256::digest(&der)[..].to_vec().into(),
+        }));
+
+        let pem = CryptoKeyTypeChoice::PkixBase64Cert(PkixBase64CertType::from(Tstr::from(
+            include_str!("../testdata/x509/leaf.pem"),
+        )));
+        assert_eq!(pem.thumbprint(&HashAlgorithm::Sha256).unwrap(), expected);
+
+        let asn1 = CryptoKeyTypeChoice::PkixAsn1DerCert(PkixAsn1DerCertType::from(Bytes::from(
+            der.clone(),
+        )));
+        assert_eq!(asn1.thumbprint(&HashAlgorithm::Sha256).unwrap(), expected);
+        assert!(asn1.matches_thumbprint(&expected).unwrap());
+
+        // A key thumbprint with the same digest is a different kind of thumbprint
+        let key_thumbprint = CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(
+            expected.as_thumbprint_digest().unwrap().clone(),
+        ));
+        assert!(!asn1.matches_thumbprint(&key_thumbprint).unwrap());
+
+        let path =
+            CryptoKeyTypeChoice::PkixBase64CertPath(PkixBase64CertPathType::from(Tstr::from(
+                [
+                    include_str!("../testdata/x509/leaf.pem"),
+                    include_str!("../testdata/x509/intermediate.pem"),
+                ]
+                .concat(),
+            )));
+        let intermediate =
+            decode_pkix_base64(include_str!("../testdata/x509/intermediate.pem")).unwrap();
+        let actual = path.thumbprint(&HashAlgorithm::Sha384).unwrap();
+        assert_eq!(
+            actual,
+            CryptoKeyTypeChoice::CertPathThumbprint(CertPathThumbprintType::from(Digest {
+                alg: HashAlgorithm::Sha384,
+                val: sha2::Sha384::digest([der, intermediate.concat()].concat())[..]
+                    .to_vec()
+                    .into(),
+            }))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_pkix_key_thumbprint() {
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        use sha2::Digest as _;
+        use x509_cert::der::{Decode, Encode};
+
+        let cert = x509_cert::Certificate::from_der(&leaf_der()).unwrap();
+        let spki = cert
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()
+            .unwrap();
+
+        let key = CryptoKeyTypeChoice::PkixBase64Key(PkixBase64KeyType::from(Tstr::from(
+            STANDARD.encode(&spki),
+        )));
+
+        let actual = key.thumbprint(&HashAlgorithm::Sha256).unwrap();
+        let expected = CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(Digest {
+            alg: HashAlgorithm::Sha256,
+            val: sha2::Sha256::digest(&spki)[..].to_vec().into(),
+        }));
+        assert_eq!(actual, expected);
+
+        let other = CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(Digest {
+            alg: HashAlgorithm::Sha256,
+            val: vec![0; 32].into(),
+        }));
+        assert!(!key.matches_thumbprint(&other).unwrap());
+
+        let invalid = CryptoKeyTypeChoice::PkixBase64Key(PkixBase64KeyType::from(Tstr::from(
+            STANDARD.encode(b"not a key"),
+        )));
+        assert!(invalid.thumbprint(&HashAlgorithm::Sha256).is_err());
+    }
+
+    #[test]
+    fn test_thumbprint_unsupported() {
+        let bytes = CryptoKeyTypeChoice::Bytes(TaggedBytes::from(Bytes::from(vec![1, 2, 3])));
+        assert!(bytes.thumbprint(&HashAlgorithm::Sha256).is_err());
+        assert!(bytes.matches_thumbprint(&bytes).is_err());
+
+        let asn1 = CryptoKeyTypeChoice::PkixAsn1DerCert(PkixAsn1DerCertType::from(Bytes::from(
+            leaf_der(),
+        )));
+        assert!(asn1.matches_thumbprint(&asn1).is_err());
+    }
 }
@@ -0,0 +1,374 @@
+// SPDX-License-Identifier: MIT
+
+//! Validation of CoRIMs and tags that were not built with the builders
+//!
+//! The `*Builder` types reject empty environments, measurements and triples, but values
+//! deserialized from JSON or CBOR, or built as struct literals, bypass them. The `validate`
+//! methods of [`CorimMap`], [`ConciseTagTypeChoice`], [`ConciseMidTag`], [`ConciseSwidTag`] and
+//! [`ConciseTlTag`] check the same rules, and the CDDL requirement that arrays are non-empty, and
+//! report every violation found with the path to the offending field.
+//!
+//! # Example
+//!
+//! ```rust
+//! use corim_rs::{corim::CorimMap, error::Error};
+//!
+//! fn check(corim: &CorimMap) {
+//!     if let Err(Error::Validation(err)) = corim.validate() {
+//!         for violation in err.violations() {
+//!             eprintln!("{violation}");
+//!         }
+//!     }
+//! }
+//! ```
+
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use core::fmt;
+
+use crate::{
+    ClassMap, ComidError, ConciseMidTag, ConciseSwidTag, ConciseTagTypeChoice, ConciseTlTag,
+    CorimMap, EnvironmentMap, MeasurementMap, MeasurementValuesMap, Result, TriplesError,
+    TriplesMap, TriplesRecordCondition, ValidationError,
+};
+
+/// A rule broken by a field of a CoRIM or tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Path to the field, e.g. `triples.reference-triples[0].ref-env`, empty for the value itself
+    pub path: String,
+    /// The rule that was broken
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl CorimMap<'_> {
+    /// Validates the CoRIM map, including all of its tags
+    pub fn validate(&self) -> Result<()> {
+        let mut validator = Validator::default();
+        validator.corim(self);
+        validator.finish()
+    }
+}
+
+impl ConciseTagTypeChoice<'_> {
+    /// Validates the tag
+    pub fn validate(&self) -> Result<()> {
+        let mut validator = Validator::default();
+        validator.tag(self);
+        validator.finish()
+    }
+}
+
+impl ConciseMidTag<'_> {
+    /// Validates the CoMID tag
+    pub fn validate(&self) -> Result<()> {
+        let mut validator = Validator::default();
+        validator.comid(self);
+        validator.finish()
+    }
+}
+
+impl ConciseSwidTag<'_> {
+    /// Validates the CoSWID tag
+    pub fn validate(&self) -> Result<()> {
+        let mut validator = Validator::default();
+        validator.coswid(self);
+        validator.finish()
+    }
+}
+
+impl ConciseTlTag<'_> {
+    /// Validates the CoTL tag
+    pub fn validate(&self) -> Result<()> {
+        let mut validator = Validator::default();
+        validator.cotl(self);
+        validator.finish()
+    }
+}
+
+/// Collects every violation found, each with the path to the offending field
+#[derive(Debug, Default)]
+struct Validator {
+    path: Vec<String>,
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn error(&mut self, message: impl fmt::Display) {
+        self.violations.push(Violation {
+            path: self.path.join("."),
+            message: message.to_string(),
+        });
+    }
+
+    fn nested(&mut self, name: impl Into<String>, f: impl FnOnce(&mut Self)) {
+        self.path.push(name.into());
+        f(self);
+        self.path.pop();
+    }
+
+    /// Checks that `items` is non-empty and validates each item
+    fn each<T>(&mut self, name: &str, items: &[T], mut f: impl FnMut(&mut Self, &T)) {
+        if items.is_empty() {
+            self.nested(name, |v| v.error("must contain at least one entry"));
+        }
+
+        for (index, item) in items.iter().enumerate() {
+            self.nested(format!("{name}[{index}]"), |v| f(v, item));
+        }
+    }
+
+    fn each_opt<T>(&mut self, name: &str, items: &Option<Vec<T>>, f: impl FnMut(&mut Self, &T)) {
+        if let Some(items) = items {
+            self.each(name, items, f);
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::Violations(self.violations))?
+        }
+    }
+
+    fn corim(&mut self, corim: &CorimMap) {
+        self.each("tags", &corim.tags, |v, tag| v.tag(tag));
+        self.each_opt("dependent-rims", &corim.dependent_rims, |v, locator| {
+            v.each("href", &locator.href, |_, _| {});
+        });
+        self.each_opt("entities", &corim.entities, |v, entity| {
+            v.each("role", &entity.role, |_, _| {});
+        });
+    }
+
+    fn tag(&mut self, tag: &ConciseTagTypeChoice) {
+        match tag {
+            ConciseTagTypeChoice::Mid(comid) => self.comid(comid),
+            ConciseTagTypeChoice::Swid(coswid) => self.coswid(coswid),
+            ConciseTagTypeChoice::Tl(cotl) => self.cotl(cotl),
+        }
+    }
+
+    fn comid(&mut self, comid: &ConciseMidTag) {
+        self.each_opt("entities", &comid.entities, |v, entity| {
+            v.each("role", &entity.role, |_, _| {});
+        });
+        self.each_opt("linked-tags", &comid.linked_tags, |_, _| {});
+        self.nested("triples", |v| v.triples(&comid.triples));
+    }
+
+    fn coswid(&mut self, coswid: &ConciseSwidTag) {
+        if coswid.entity.is_empty() {
+            self.nested("entity", |v| v.error("must contain at least one entry"));
+        }
+    }
+
+    fn cotl(&mut self, cotl: &ConciseTlTag) {
+        self.each("tags-list", &cotl.tags_list, |_, _| {});
+    }
+
+    fn triples(&mut self, triples: &TriplesMap) {
+        if triples.reference_triples.is_none()
+            && triples.endorsed_triples.is_none()
+            && triples.identity_triples.is_none()
+            && triples.attest_key_triples.is_none()
+            && triples.dependency_triples.is_none()
+            && triples.membership_triples.is_none()
+            && triples.coswid_triples.is_none()
+            && triples.conditional_endorsement_series_triples.is_none()
+            && triples.conditional_endorsement_triples.is_none()
+            && triples.extension.is_none()
+        {
+            self.error(ComidError::EmptyTriplesMap);
+        }
+
+        self.each_opt("reference-triples", &triples.reference_triples, |v, t| {
+            v.nested("ref-env", |v| v.environment(&t.ref_env));
+            v.each("ref-claims", &t.ref_claims, Self::measurement);
+        });
+        self.each_opt("endorsed-triples", &triples.endorsed_triples, |v, t| {
+            v.nested("condition", |v| v.environment(&t.condition));
+            v.each("endorsement", &t.endorsement, Self::measurement);
+        });
+        self.each_opt("identity-triples", &triples.identity_triples, |v, t| {
+            v.nested("environment", |v| v.environment(&t.environment));
+            v.each("key-list", &t.key_list, |_, _| {});
+            v.condition(&t.conditions);
+        });
+        self.each_opt("attest-key-triples", &triples.attest_key_triples, |v, t| {
+            v.nested("environment", |v| v.environment(&t.environment));
+            v.each("key-list", &t.key_list, |_, _| {});
+            v.condition(&t.conditions);
+        });
+        self.each_opt("dependency-triples", &triples.dependency_triples, |v, t| {
+            v.each("environments", &t.environment_map, Self::environment);
+        });
+        self.each_opt("membership-triples", &triples.membership_triples, |v, t| {
+            v.each("environments", &t.environment_map, Self::environment);
+        });
+        self.each_opt("coswid-triples", &triples.coswid_triples, |v, t| {
+            v.nested("environment", |v| v.environment(&t.environment_map));
+            v.each("coswid-tags", &t.coswid_tags, |_, _| {});
+        });
+        self.each_opt(
+            "conditional-endorsement-series-triples",
+            &triples.conditional_endorsement_series_triples,
+            |v, t| {
+                v.nested("condition", |v| {
+                    v.nested("environment", |v| v.environment(&t.condition.environment));
+                    v.each("claims-list", &t.condition.claims_list, Self::measurement);
+                });
+                v.each("series", &t.series, |v, record| {
+                    v.each("selection", &record.selection, Self::measurement);
+                    v.each("addition", &record.addition, Self::measurement);
+                });
+            },
+        );
+        self.each_opt(
+            "conditional-endorsement-triples",
+            &triples.conditional_endorsement_triples,
+            |v, t| {
+                v.each("conditions", &t.conditions, |v, condition| {
+                    v.nested("environment", |v| v.environment(&condition.environment));
+                    v.each("claims-list", &condition.claims_list, Self::measurement);
+                });
+                v.each("endorsements", &t.endorsements, |v, endorsement| {
+                    v.nested("condition", |v| v.environment(&endorsement.condition));
+                    v.each("endorsement", &endorsement.endorsement, Self::measurement);
+                });
+            },
+        );
+    }
+
+    fn environment(&mut self, env: &EnvironmentMap) {
+        if env.class.is_none() && env.instance.is_none() && env.group.is_none() {
+            self.error(TriplesError::EmptyEnvironmentMap);
+        }
+
+        if env.class.as_ref() == Some(&ClassMap::default()) {
+            self.nested("class", |v| v.error(TriplesError::EmptyClassMap));
+        }
+    }
+
+    fn measurement(&mut self, measurement: &MeasurementMap) {
+        if measurement.mval == MeasurementValuesMap::default() {
+            self.nested("mval", |v| v.error(TriplesError::EmptyMeasurementValuesMap));
+        }
+
+        if let Some(digests) = &measurement.mval.digests {
+            if digests.is_empty() {
+                self.nested("mval.digests", |v| {
+                    v.error("must contain at least one entry")
+                });
+            }
+        }
+
+        self.each_opt("authorized-by", &measurement.authorized_by, |_, _| {});
+    }
+
+    fn condition(&mut self, condition: &Option<TriplesRecordCondition>) {
+        if let Some(condition) = condition {
+            self.nested("conditions", |v| {
+                if condition.mkey.is_none() && condition.authorized_by.is_none() {
+                    v.error(TriplesError::EmptyTripleRecordCondition);
+                }
+                v.each_opt("authorized-by", &condition.authorized_by, |_, _| {});
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TagIdentityMap, ValidityMap};
+
+    #[test]
+    fn test_validate_collects_violations() {
+        let comid = ConciseMidTag {
+            language: None,
+            tag_identity: TagIdentityMap {
+                tag_id: "comid-1".into(),
+                tag_version: None,
+            },
+            entities: Some(vec![]),
+            linked_tags: None,
+            triples: TriplesMap {
+                reference_triples: None,
+                endorsed_triples: None,
+                identity_triples: None,
+                attest_key_triples: None,
+                dependency_triples: None,
+                membership_triples: None,
+                coswid_triples: None,
+                conditional_endorsement_series_triples: None,
+                conditional_endorsement_triples: None,
+                extension: None,
+            },
+            extension: None,
+        };
+
+        let Err(crate::Error::Validation(err)) = comid.validate() else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(
+            err.violations(),
+            [
+                Violation {
+                    path: "entities".into(),
+                    message: "must contain at least one entry".into(),
+                },
+                Violation {
+                    path: "triples".into(),
+                    message: ComidError::EmptyTriplesMap.to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "entities: must contain at least one entry\n\
+             triples: a TriplesMap must have at least one non-empty field"
+        );
+
+        let corim = CorimMap {
+            id: "corim-1".into(),
+            tags: vec![comid.into()],
+            dependent_rims: None,
+            profile: None,
+            rim_validity: None,
+            entities: None,
+            extension: None,
+        };
+        assert!(corim
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .starts_with("tags[0].entities: must contain at least one entry\n"));
+
+        let cotl = ConciseTlTag {
+            tag_identity: TagIdentityMap {
+                tag_id: "cotl-1".into(),
+                tag_version: None,
+            },
+            tags_list: vec![],
+            tl_validity: ValidityMap {
+                not_before: None,
+                not_after: 0.into(),
+            },
+        };
+        assert_eq!(
+            cotl.validate().unwrap_err().to_string(),
+            "tags-list: must contain at least one entry"
+        );
+    }
+}
@@ -0,0 +1,2047 @@
+// SPDX-License-Identifier: MIT
+
+//! Veraison compatible JSON templates
+//!
+//! The `cocli` tool of the [Veraison](https://github.com/veraison) project describes CoMIDs and
+//! CoRIMs with JSON templates whose layout differs from this crate's human-readable serde
+//! representation: fields are named after the CDDL (`"tag-identity"`, `"reference-values"`,
+//! `"environment"`), identifiers and keys are written as `{"type": ..., "value": ...}` pairs,
+//! byte strings use the standard base64 alphabet with padding, and times are RFC 3339 strings.
+//!
+//! [`ConciseMidTag::to_veraison_json`] and [`ConciseMidTag::from_veraison_json`] convert between
+//! a CoMID and such a template, writing the fields in the order `cocli` writes them, so that a
+//! template read and written again is unchanged. [`CorimMap`] has the same pair of functions
+//! for the CoRIM template, which holds the CoRIM metadata only: as with `cocli corim create`,
+//! the tags are supplied separately. Veraison has no CoTL template, so [`ConciseTlTag`] uses a
+//! layout that follows the same conventions.
+//!
+//! The PSA class ids (`psa.impl-id`) and measurement keys (`psa.refval-id`) of the PSA and CCA
+//! templates are read into the `Extension` variants of [`ClassIdTypeChoice`] and
+//! [`MeasuredElementTypeChoice`], as the CBOR tag 600 and the `psa-refval-id` map the PSA
+//! endorsements profile defines, and written back from them.
+//!
+//! Values that have no representation in a Veraison template, such as triples other than
+//! reference values, endorsed values, attestation keys and identity keys, or other extension
+//! values, are reported as [`VeraisonError::Unsupported`] rather than dropped. Text tag and
+//! CoRIM identifiers that are UUIDs are read as UUIDs, as Veraison does.
+//!
+//! # Example
+//!
+//! ```rust
+//! use corim_rs::comid::ConciseMidTag;
+//!
+//! let template = r#"{
+//!   "tag-identity": {
+//!     "id": "43bbe37f-2e61-4b33-aed3-53cff1428b16",
+//!     "version": 0
+//!   },
+//!   "triples": {
+//!     "reference-values": [
+//!       {
+//!         "environment": {
+//!           "class": {
+//!             "vendor": "ACME",
+//!             "model": "RoadRunner"
+//!           }
+//!         },
+//!         "measurements": [
+//!           {
+//!             "key": {
+//!               "type": "string",
+//!               "value": "firmware"
+//!             },
+//!             "value": {
+//!               "svn": {
+//!                 "type": "exact-value",
+//!                 "value": 3
+//!               }
+//!             }
+//!           }
+//!         ]
+//!       }
+//!     ]
+//!   }
+//! }"#;
+//!
+//! let comid = ConciseMidTag::from_veraison_json(template).unwrap();
+//! assert!(comid.tag_identity.tag_id.as_uuid().is_some());
+//! assert_eq!(comid.to_veraison_json().unwrap(), template);
+//! ```
+
+use std::{collections::BTreeMap, net::IpAddr};
+
+use base64::{
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    core::{format_rfc3339, parse_rfc3339},
+    AttestKeyTripleRecord, Bytes, CertPathThumbprintType, CertThumbprintType, ClassIdTypeChoice,
+    ClassMap, ComidEntityMap, ComidRoleTypeChoice, ConciseMidTag, ConciseTagTypeChoice,
+    ConciseTlTag, CorimEntityMap, CorimIdTypeChoice, CorimLocatorMap, CorimMap,
+    CorimRoleTypeChoice, CoseKeySetOrKey, CoseKeyType, CryptoKeyTypeChoice, Digest,
+    EndorsedTripleRecord, EnvironmentMap, ExtensionValue, FlagsMap, GroupIdTypeChoice,
+    HashAlgorithm, IdentityTripleRecord, InstanceIdTypeChoice, Integer, IntegrityRegisters,
+    IpAddrTypeChoice, Label, LinkedTagMap, MacAddrTypeChoice, MeasuredElementTypeChoice,
+    MeasurementMap, MeasurementValuesMap, MinSvnType, ObjectIdentifier, OidType,
+    PkixAsn1DerCertType, PkixBase64CertPathType, PkixBase64CertType, PkixBase64KeyType,
+    ProfileTypeChoice, RawValueType, RawValueTypeChoice, ReferenceTripleRecord, Result,
+    SvnTypeChoice, TagIdTypeChoice, TagIdentityMap, TagRelTypeChoice, TaggedBytes, TaggedUeidType,
+    TaggedUuidType, Text, ThumbprintType, TriplesMap, TriplesRecordCondition, UeidType, Ulabel,
+    Uri, UuidType, ValidityMap, VeraisonError, VersionMap, VersionScheme,
+};
+
+impl ConciseMidTag<'_> {
+    /// Writes the CoMID as a Veraison CoMID template
+    pub fn to_veraison_json(&self) -> Result<String> {
+        to_json(&export_comid(self)?)
+    }
+
+    /// Reads a CoMID from a Veraison CoMID template
+    pub fn from_veraison_json(json: &str) -> Result<ConciseMidTag<'static>> {
+        import_comid(from_json(json)?)
+    }
+}
+
+impl ConciseTlTag<'_> {
+    /// Writes the CoTL as JSON in the conventions of the Veraison templates
+    pub fn to_veraison_json(&self) -> Result<String> {
+        to_json(&Cotl {
+            tag_identity: export_tag_identity(&self.tag_identity)?,
+            tags_list: self
+                .tags_list
+                .iter()
+                .map(export_tag_identity)
+                .collect::<Result<_>>()?,
+            validity: export_validity(&self.tl_validity)?,
+        })
+    }
+
+    /// Reads a CoTL from JSON in the conventions of the Veraison templates
+    pub fn from_veraison_json(json: &str) -> Result<ConciseTlTag<'static>> {
+        let cotl: Cotl = from_json(json)?;
+
+        Ok(ConciseTlTag {
+            tag_identity: import_tag_identity(cotl.tag_identity),
+            tags_list: cotl
+                .tags_list
+                .into_iter()
+                .map(import_tag_identity)
+                .collect(),
+            tl_validity: import_validity(cotl.validity)?,
+        })
+    }
+}
+
+impl<'a> CorimMap<'a> {
+    /// Writes the CoRIM metadata as a Veraison CoRIM template. The tags are not part of the
+    /// template.
+    pub fn to_veraison_json(&self) -> Result<String> {
+        if self.extension.is_some() {
+            return Err(VeraisonError::Unsupported("CoRIM extensions".to_string()).into());
+        }
+
+        to_json(&Corim {
+            corim_id: match &self.id {
+                CorimIdTypeChoice::Tstr(id) => id.to_string(),
+                CorimIdTypeChoice::Uuid(uuid) => uuid.to_string(),
+            },
+            dependent_rims: self
+                .dependent_rims
+                .iter()
+                .flatten()
+                .map(|locator| Locator {
+                    href: match locator.href.as_slice() {
+                        [href] => Hrefs::One(href.as_ref().to_string()),
+                        hrefs => {
+                            Hrefs::Many(hrefs.iter().map(|h| h.as_ref().to_string()).collect())
+                        }
+                    },
+                    thumbprint: locator.thumbprint.as_ref().map(export_digest),
+                })
+                .collect(),
+            profile: self.profile.as_ref().map(|profile| match profile {
+                ProfileTypeChoice::Uri(uri) => uri.as_ref().to_string(),
+                ProfileTypeChoice::OidType(oid) => oid.as_ref().to_string(),
+            }),
+            validity: self
+                .rim_validity
+                .as_ref()
+                .map(export_validity)
+                .transpose()?,
+            entities: self
+                .entities
+                .iter()
+                .flatten()
+                .map(|entity| {
+                    if entity.extension.is_some() {
+                        return Err(
+                            VeraisonError::Unsupported("entity extensions".to_string()).into()
+                        );
+                    }
+                    Ok(Entity {
+                        name: entity.entity_name.to_string(),
+                        regid: entity.reg_id.as_ref().map(|uri| uri.as_ref().to_string()),
+                        roles: entity
+                            .role
+                            .iter()
+                            .map(|role| match role {
+                                CorimRoleTypeChoice::ManifestCreator => "manifestCreator",
+                                CorimRoleTypeChoice::ManifestSigner => "manifestSigner",
+                            })
+                            .map(str::to_string)
+                            .collect(),
+                    })
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Reads a CoRIM from a Veraison CoRIM template, holding `tags`
+    pub fn from_veraison_json(
+        json: &str,
+        tags: Vec<ConciseTagTypeChoice<'a>>,
+    ) -> Result<CorimMap<'a>> {
+        let corim: Corim = from_json(json)?;
+
+        let dependent_rims = corim
+            .dependent_rims
+            .into_iter()
+            .map(|locator| -> Result<_> {
+                Ok(CorimLocatorMap {
+                    href: match locator.href {
+                        Hrefs::One(href) => vec![href],
+                        Hrefs::Many(hrefs) => hrefs,
+                    }
+                    .into_iter()
+                    .map(|href| Uri::from(Text::from(href)))
+                    .collect(),
+                    thumbprint: locator
+                        .thumbprint
+                        .as_deref()
+                        .map(|digest| import_digest(digest, "dependent-rims thumbprint"))
+                        .transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let entities = corim
+            .entities
+            .into_iter()
+            .map(|entity| -> Result<_> {
+                Ok(CorimEntityMap {
+                    entity_name: entity.name.into(),
+                    reg_id: entity.regid.map(|regid| Uri::from(Text::from(regid))),
+                    role: entity
+                        .roles
+                        .iter()
+                        .map(|role| match role.as_str() {
+                            "manifestCreator" => Ok(CorimRoleTypeChoice::ManifestCreator),
+                            "manifestSigner" => Ok(CorimRoleTypeChoice::ManifestSigner),
+                            role => Err(invalid("entity role", role)),
+                        })
+                        .collect::<std::result::Result<_, _>>()?,
+                    extension: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(CorimMap {
+            id: match UuidType::try_from(corim.corim_id.as_str()) {
+                Ok(uuid) => CorimIdTypeChoice::Uuid(uuid),
+                Err(_) => CorimIdTypeChoice::Tstr(corim.corim_id.into()),
+            },
+            tags,
+            dependent_rims: (!dependent_rims.is_empty()).then_some(dependent_rims),
+            profile: corim.profile.map(import_profile).transpose()?,
+            rim_validity: corim.validity.map(import_validity).transpose()?,
+            entities: (!entities.is_empty()).then_some(entities),
+            extension: None,
+        })
+    }
+}
+
+/// A CoMID template, as read by `cocli comid create`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Comid {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lang: Option<String>,
+    tag_identity: TagIdentity,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    entities: Vec<Entity>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    linked_tags: Vec<LinkedTag>,
+    triples: Triples,
+}
+
+/// A CoRIM template, as read by `cocli corim create`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Corim {
+    corim_id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependent_rims: Vec<Locator>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    validity: Option<Validity>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    entities: Vec<Entity>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Cotl {
+    tag_identity: TagIdentity,
+    tags_list: Vec<TagIdentity>,
+    validity: Validity,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TagIdentity {
+    id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entity {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    regid: Option<String>,
+    roles: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LinkedTag {
+    target: String,
+    rel: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Locator {
+    href: Hrefs,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thumbprint: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Hrefs {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Validity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    not_before: Option<String>,
+    not_after: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Triples {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    reference_values: Vec<ValueTriple>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    endorsed_values: Vec<ValueTriple>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attester_verification_keys: Vec<KeyTriple>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dev_identity_keys: Vec<KeyTriple>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ValueTriple {
+    environment: Environment,
+    measurements: Vec<Measurement>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct KeyTriple {
+    environment: Environment,
+    verification_keys: Vec<TypedValue>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Environment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    class: Option<Class>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    instance: Option<TypedValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group: Option<TypedValue>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Class {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<TypedValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    vendor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    layer: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Measurement {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key: Option<MeasurementKey>,
+    value: Mval,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    authorized_by: Option<TypedValue>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Mval {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<Version>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    svn: Option<TypedValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    digests: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    flags: Option<Flags>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_value: Option<TypedValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_value_mask: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mac_addr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ip_addr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    serial_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ueid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uuid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cryptokeys: Option<Vec<TypedValue>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    integrity_registers: Option<BTreeMap<String, Register>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Version {
+    value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scheme: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Flags {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_configured: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_secure: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_recovery: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_debug: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_replay_protected: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_integrity_protected: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_runtime_meas: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_immutable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_tcb: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_confidentiality_protected: Option<bool>,
+}
+
+/// A measurement key. PSA reference value ids are read into their own type, so that their
+/// fields are written in the order of the `cocli` templates.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum MeasurementKey {
+    PsaRefvalId {
+        #[serde(rename = "type")]
+        kind: PsaRefvalIdKind,
+        value: PsaRefvalId,
+    },
+    Typed(TypedValue),
+}
+
+#[derive(Serialize, Deserialize)]
+enum PsaRefvalIdKind {
+    #[serde(rename = "psa.refval-id")]
+    PsaRefvalId,
+}
+
+/// The value of a `psa.refval-id` measurement key
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct PsaRefvalId {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    signer_id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Register {
+    key_type: String,
+    value: Vec<String>,
+}
+
+/// An identifier, key or value whose kind is given by `type`
+#[derive(Serialize, Deserialize)]
+struct TypedValue {
+    #[serde(rename = "type")]
+    kind: String,
+    value: JsonValue,
+}
+
+impl TypedValue {
+    fn new(kind: &str, value: impl Into<JsonValue>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            value: value.into(),
+        }
+    }
+
+    fn text(&self, field: &'static str) -> Result<&str> {
+        Ok(self
+            .value
+            .as_str()
+            .ok_or_else(|| invalid(field, "expected a string value"))?)
+    }
+
+    fn bytes(&self, field: &'static str) -> Result<Vec<u8>> {
+        decode_base64(self.text(field)?, field)
+    }
+
+    fn uint(&self, field: &'static str) -> Result<u64> {
+        Ok(self
+            .value
+            .as_u64()
+            .ok_or_else(|| invalid(field, "expected an unsigned integer value"))?)
+    }
+
+    fn unsupported(&self, field: &str) -> VeraisonError {
+        VeraisonError::Unsupported(format!("{field} of type \"{}\"", self.kind))
+    }
+}
+
+/// CBOR tag of a PSA implementation id (`tagged-impl-id-type`)
+const PSA_IMPL_ID_TAG: u64 = 600;
+
+/// Length of a PSA implementation id in bytes
+const PSA_IMPL_ID_LEN: usize = 32;
+
+/// Map keys of a PSA reference value id (`psa-refval-id`)
+const PSA_REFVAL_LABEL: i128 = 1;
+const PSA_REFVAL_VERSION: i128 = 4;
+const PSA_REFVAL_SIGNER_ID: i128 = 5;
+
+fn to_json<T: Serialize>(template: &T) -> Result<String> {
+    Ok(serde_json::to_string_pretty(template).map_err(|e| VeraisonError::Json(e.to_string()))?)
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(json: &str) -> Result<T> {
+    Ok(serde_json::from_str(json).map_err(|e| VeraisonError::Json(e.to_string()))?)
+}
+
+fn invalid(field: &'static str, message: impl std::fmt::Display) -> VeraisonError {
+    VeraisonError::InvalidValue {
+        field,
+        message: message.to_string(),
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// Decodes base64 in the standard or URL-safe alphabet, with or without padding
+fn decode_base64(text: &str, field: &'static str) -> Result<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+
+    Ok([STANDARD_NO_PAD, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(text).ok())
+        .ok_or_else(|| invalid(field, "invalid base64"))?)
+}
+
+fn uint(value: &Integer, field: &'static str) -> Result<u64> {
+    Ok(u64::try_from(value.0).map_err(|_| invalid(field, value.0))?)
+}
+
+fn parse_uuid(text: &str, field: &'static str) -> Result<UuidType> {
+    Ok(UuidType::try_from(text).map_err(|e| invalid(field, e))?)
+}
+
+fn parse_oid(text: &str, field: &'static str) -> Result<ObjectIdentifier<'static>> {
+    Ok(ObjectIdentifier::try_from(text).map_err(|_| invalid(field, text))?)
+}
+
+fn export_comid(comid: &ConciseMidTag) -> Result<Comid> {
+    if comid.extension.is_some() {
+        return Err(VeraisonError::Unsupported("CoMID extensions".to_string()).into());
+    }
+
+    Ok(Comid {
+        lang: comid.language.as_ref().map(|lang| lang.to_string()),
+        tag_identity: export_tag_identity(&comid.tag_identity)?,
+        entities: comid
+            .entities
+            .iter()
+            .flatten()
+            .map(export_entity)
+            .collect::<Result<_>>()?,
+        linked_tags: comid
+            .linked_tags
+            .iter()
+            .flatten()
+            .map(|linked| LinkedTag {
+                target: export_tag_id(&linked.linked_tag_id),
+                rel: match linked.tag_rel {
+                    TagRelTypeChoice::Supplements => "supplements",
+                    TagRelTypeChoice::Replaces => "replaces",
+                }
+                .to_string(),
+            })
+            .collect(),
+        triples: export_triples(&comid.triples)?,
+    })
+}
+
+fn import_comid(comid: Comid) -> Result<ConciseMidTag<'static>> {
+    let entities = comid
+        .entities
+        .into_iter()
+        .map(import_entity)
+        .collect::<Result<Vec<_>>>()?;
+    let linked_tags = comid
+        .linked_tags
+        .into_iter()
+        .map(|linked| -> Result<_> {
+            Ok(LinkedTagMap {
+                linked_tag_id: import_tag_id(linked.target),
+                tag_rel: match linked.rel.as_str() {
+                    "supplements" => TagRelTypeChoice::Supplements,
+                    "replaces" => TagRelTypeChoice::Replaces,
+                    rel => Err(invalid("linked tag rel", rel))?,
+                },
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ConciseMidTag {
+        language: comid.lang.map(Into::into),
+        tag_identity: import_tag_identity(comid.tag_identity),
+        entities: (!entities.is_empty()).then_some(entities),
+        linked_tags: (!linked_tags.is_empty()).then_some(linked_tags),
+        triples: import_triples(comid.triples)?,
+        extension: None,
+    })
+}
+
+fn export_tag_id(id: &TagIdTypeChoice) -> String {
+    match id {
+        TagIdTypeChoice::Tstr(id) => id.to_string(),
+        TagIdTypeChoice::Uuid(uuid) => uuid.to_string(),
+    }
+}
+
+fn import_tag_id(id: String) -> TagIdTypeChoice<'static> {
+    match UuidType::try_from(id.as_str()) {
+        Ok(uuid) => TagIdTypeChoice::Uuid(uuid),
+        Err(_) => TagIdTypeChoice::Tstr(id.into()),
+    }
+}
+
+fn export_tag_identity(identity: &TagIdentityMap) -> Result<TagIdentity> {
+    Ok(TagIdentity {
+        id: export_tag_id(&identity.tag_id),
+        version: identity
+            .tag_version
+            .as_ref()
+            .map(|version| uint(version, "tag version"))
+            .transpose()?,
+    })
+}
+
+fn import_tag_identity(identity: TagIdentity) -> TagIdentityMap<'static> {
+    TagIdentityMap {
+        tag_id: import_tag_id(identity.id),
+        tag_version: identity.version.map(Into::into),
+    }
+}
+
+fn export_entity(entity: &ComidEntityMap) -> Result<Entity> {
+    if entity.extension.is_some() {
+        return Err(VeraisonError::Unsupported("entity extensions".to_string()).into());
+    }
+
+    Ok(Entity {
+        name: entity.entity_name.to_string(),
+        regid: entity.reg_id.as_ref().map(|uri| uri.as_ref().to_string()),
+        roles: entity
+            .role
+            .iter()
+            .map(|role| match role {
+                ComidRoleTypeChoice::TagCreator => "tagCreator",
+                ComidRoleTypeChoice::Creator => "creator",
+                ComidRoleTypeChoice::Maintainer => "maintainer",
+            })
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+fn import_entity(entity: Entity) -> Result<ComidEntityMap<'static>> {
+    Ok(ComidEntityMap {
+        entity_name: entity.name.into(),
+        reg_id: entity.regid.map(|regid| Uri::from(Text::from(regid))),
+        role: entity
+            .roles
+            .iter()
+            .map(|role| match role.as_str() {
+                "tagCreator" => Ok(ComidRoleTypeChoice::TagCreator),
+                "creator" => Ok(ComidRoleTypeChoice::Creator),
+                "maintainer" => Ok(ComidRoleTypeChoice::Maintainer),
+                role => Err(invalid("entity role", role)),
+            })
+            .collect::<std::result::Result<_, _>>()?,
+        extension: None,
+    })
+}
+
+fn import_profile(profile: String) -> Result<ProfileTypeChoice<'static>> {
+    let is_oid = profile.contains('.')
+        && profile
+            .split('.')
+            .all(|arc| !arc.is_empty() && arc.bytes().all(|b| b.is_ascii_digit()));
+
+    Ok(if is_oid {
+        ProfileTypeChoice::OidType(OidType::from(parse_oid(&profile, "profile")?))
+    } else {
+        ProfileTypeChoice::Uri(Uri::from(Text::from(profile)))
+    })
+}
+
+fn export_validity(validity: &ValidityMap) -> Result<Validity> {
+    Ok(Validity {
+        not_before: validity.not_before.map(|time| format_rfc3339(time.0)),
+        not_after: format_rfc3339(validity.not_after.0),
+    })
+}
+
+fn import_validity(validity: Validity) -> Result<ValidityMap> {
+    Ok(ValidityMap {
+        not_before: validity
+            .not_before
+            .map(|time| parse_time(&time, "not-before").map(Integer))
+            .transpose()?,
+        not_after: Integer(parse_time(&validity.not_after, "not-after")?),
+    })
+}
+
+fn export_triples(triples: &TriplesMap) -> Result<Triples> {
+    let unsupported = [
+        (
+            triples.dependency_triples.is_some(),
+            "domain dependency triples",
+        ),
+        (
+            triples.membership_triples.is_some(),
+            "domain membership triples",
+        ),
+        (triples.coswid_triples.is_some(), "CoSWID triples"),
+        (
+            triples.conditional_endorsement_series_triples.is_some(),
+            "conditional endorsement series triples",
+        ),
+        (
+            triples.conditional_endorsement_triples.is_some(),
+            "conditional endorsement triples",
+        ),
+        (triples.extension.is_some(), "triples extensions"),
+    ];
+    if let Some((_, name)) = unsupported.iter().find(|(present, _)| *present) {
+        return Err(VeraisonError::Unsupported(name.to_string()).into());
+    }
+
+    Ok(Triples {
+        reference_values: triples
+            .reference_triples
+            .iter()
+            .flatten()
+            .map(|triple| export_value_triple(&triple.ref_env, &triple.ref_claims))
+            .collect::<Result<_>>()?,
+        endorsed_values: triples
+            .endorsed_triples
+            .iter()
+            .flatten()
+            .map(|triple| export_value_triple(&triple.condition, &triple.endorsement))
+            .collect::<Result<_>>()?,
+        attester_verification_keys: triples
+            .attest_key_triples
+            .iter()
+            .flatten()
+            .map(|triple| {
+                export_key_triple(&triple.environment, &triple.key_list, &triple.conditions)
+            })
+            .collect::<Result<_>>()?,
+        dev_identity_keys: triples
+            .identity_triples
+            .iter()
+            .flatten()
+            .map(|triple| {
+                export_key_triple(&triple.environment, &triple.key_list, &triple.conditions)
+            })
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn import_triples(triples: Triples) -> Result<TriplesMap<'static>> {
+    fn non_empty<T>(items: Vec<T>) -> Option<Vec<T>> {
+        (!items.is_empty()).then_some(items)
+    }
+
+    let reference_triples = triples
+        .reference_values
+        .into_iter()
+        .map(|triple| {
+            let (ref_env, ref_claims) = import_value_triple(triple)?;
+            Ok(ReferenceTripleRecord {
+                ref_env,
+                ref_claims,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let endorsed_triples = triples
+        .endorsed_values
+        .into_iter()
+        .map(|triple| {
+            let (condition, endorsement) = import_value_triple(triple)?;
+            Ok(EndorsedTripleRecord {
+                condition,
+                endorsement,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let attest_key_triples = triples
+        .attester_verification_keys
+        .into_iter()
+        .map(|triple| {
+            let (environment, key_list) = import_key_triple(triple)?;
+            Ok(AttestKeyTripleRecord {
+                environment,
+                key_list,
+                conditions: None,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let identity_triples = triples
+        .dev_identity_keys
+        .into_iter()
+        .map(|triple| {
+            let (environment, key_list) = import_key_triple(triple)?;
+            Ok(IdentityTripleRecord {
+                environment,
+                key_list,
+                conditions: None,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TriplesMap {
+        reference_triples: non_empty(reference_triples),
+        endorsed_triples: non_empty(endorsed_triples),
+        identity_triples: non_empty(identity_triples),
+        attest_key_triples: non_empty(attest_key_triples),
+        dependency_triples: None,
+        membership_triples: None,
+        coswid_triples: None,
+        conditional_endorsement_series_triples: None,
+        conditional_endorsement_triples: None,
+        extension: None,
+    })
+}
+
+fn export_value_triple(
+    environment: &EnvironmentMap,
+    measurements: &[MeasurementMap],
+) -> Result<ValueTriple> {
+    Ok(ValueTriple {
+        environment: export_environment(environment)?,
+        measurements: measurements
+            .iter()
+            .map(export_measurement)
+            .collect::<Result<_>>()?,
+    })
+}
+
+fn import_value_triple(
+    triple: ValueTriple,
+) -> Result<(EnvironmentMap<'static>, Vec<MeasurementMap<'static>>)> {
+    Ok((
+        import_environment(triple.environment)?,
+        triple
+            .measurements
+            .into_iter()
+            .map(import_measurement)
+            .collect::<Result<_>>()?,
+    ))
+}
+
+fn export_key_triple(
+    environment: &EnvironmentMap,
+    keys: &[CryptoKeyTypeChoice],
+    conditions: &Option<TriplesRecordCondition>,
+) -> Result<KeyTriple> {
+    if conditions.is_some() {
+        return Err(VeraisonError::Unsupported("key triple conditions".to_string()).into());
+    }
+
+    Ok(KeyTriple {
+        environment: export_environment(environment)?,
+        verification_keys: keys.iter().map(export_key).collect::<Result<_>>()?,
+    })
+}
+
+fn import_key_triple(
+    triple: KeyTriple,
+) -> Result<(EnvironmentMap<'static>, Vec<CryptoKeyTypeChoice<'static>>)> {
+    Ok((
+        import_environment(triple.environment)?,
+        triple
+            .verification_keys
+            .iter()
+            .map(|key| import_key(key, "verification key"))
+            .collect::<Result<_>>()?,
+    ))
+}
+
+fn export_environment(environment: &EnvironmentMap) -> Result<Environment> {
+    Ok(Environment {
+        class: environment.class.as_ref().map(export_class).transpose()?,
+        instance: environment
+            .instance
+            .as_ref()
+            .map(|instance| -> Result<_> {
+                Ok(match instance {
+                    InstanceIdTypeChoice::Ueid(ueid) => {
+                        TypedValue::new("ueid", encode_base64(ueid.as_ref()))
+                    }
+                    InstanceIdTypeChoice::Uuid(uuid) => {
+                        TypedValue::new("uuid", uuid.as_ref().to_string())
+                    }
+                    InstanceIdTypeChoice::CryptoKey(key) => export_key(key)?,
+                    InstanceIdTypeChoice::Bytes(bytes) => {
+                        TypedValue::new("bytes", encode_base64(bytes.as_ref()))
+                    }
+                })
+            })
+            .transpose()?,
+        group: environment.group.as_ref().map(|group| match group {
+            GroupIdTypeChoice::Uuid(uuid) => TypedValue::new("uuid", uuid.as_ref().to_string()),
+            GroupIdTypeChoice::Bytes(bytes) => {
+                TypedValue::new("bytes", encode_base64(bytes.as_ref()))
+            }
+        }),
+    })
+}
+
+fn import_environment(environment: Environment) -> Result<EnvironmentMap<'static>> {
+    Ok(EnvironmentMap {
+        class: environment.class.map(import_class).transpose()?,
+        instance: environment
+            .instance
+            .map(|instance| -> Result<_> {
+                const FIELD: &str = "instance id";
+                Ok(match instance.kind.as_str() {
+                    "ueid" => InstanceIdTypeChoice::Ueid(TaggedUeidType::from(
+                        UeidType::try_from(instance.bytes(FIELD)?)
+                            .map_err(|e| invalid(FIELD, e))?,
+                    )),
+                    "uuid" => InstanceIdTypeChoice::Uuid(TaggedUuidType::from(parse_uuid(
+                        instance.text(FIELD)?,
+                        FIELD,
+                    )?)),
+                    "bytes" => InstanceIdTypeChoice::Bytes(TaggedBytes::from(Bytes::from(
+                        instance.bytes(FIELD)?,
+                    ))),
+                    _ => InstanceIdTypeChoice::CryptoKey(import_key(&instance, FIELD)?),
+                })
+            })
+            .transpose()?,
+        group: environment
+            .group
+            .map(|group| -> Result<_> {
+                const FIELD: &str = "group id";
+                Ok(match group.kind.as_str() {
+                    "uuid" => GroupIdTypeChoice::Uuid(TaggedUuidType::from(parse_uuid(
+                        group.text(FIELD)?,
+                        FIELD,
+                    )?)),
+                    "bytes" => GroupIdTypeChoice::Bytes(TaggedBytes::from(Bytes::from(
+                        group.bytes(FIELD)?,
+                    ))),
+                    _ => Err(group.unsupported(FIELD))?,
+                })
+            })
+            .transpose()?,
+    })
+}
+
+fn export_class(class: &ClassMap) -> Result<Class> {
+    Ok(Class {
+        id: class
+            .class_id
+            .as_ref()
+            .map(|id| -> Result<_> {
+                Ok(match id {
+                    ClassIdTypeChoice::Oid(oid) => TypedValue::new("oid", oid.as_ref().to_string()),
+                    ClassIdTypeChoice::Uuid(uuid) => {
+                        TypedValue::new("uuid", uuid.as_ref().to_string())
+                    }
+                    ClassIdTypeChoice::Bytes(bytes) => {
+                        TypedValue::new("bytes", encode_base64(bytes.as_ref()))
+                    }
+                    ClassIdTypeChoice::Extension(value) => {
+                        export_psa_impl_id(value).ok_or_else(|| {
+                            VeraisonError::Unsupported(
+                                "class id extensions other than PSA implementation ids".to_string(),
+                            )
+                        })?
+                    }
+                })
+            })
+            .transpose()?,
+        vendor: class.vendor.as_ref().map(|vendor| vendor.to_string()),
+        model: class.model.as_ref().map(|model| model.to_string()),
+        layer: class
+            .layer
+            .as_ref()
+            .map(|layer| uint(layer, "class layer"))
+            .transpose()?,
+        index: class
+            .index
+            .as_ref()
+            .map(|index| uint(index, "class index"))
+            .transpose()?,
+    })
+}
+
+fn import_class(class: Class) -> Result<ClassMap<'static>> {
+    const FIELD: &str = "class id";
+
+    Ok(ClassMap {
+        class_id: class
+            .id
+            .map(|id| -> Result<_> {
+                Ok(match id.kind.as_str() {
+                    "oid" => {
+                        ClassIdTypeChoice::Oid(OidType::from(parse_oid(id.text(FIELD)?, FIELD)?))
+                    }
+                    "uuid" => ClassIdTypeChoice::Uuid(TaggedUuidType::from(parse_uuid(
+                        id.text(FIELD)?,
+                        FIELD,
+                    )?)),
+                    "bytes" => {
+                        ClassIdTypeChoice::Bytes(TaggedBytes::from(Bytes::from(id.bytes(FIELD)?)))
+                    }
+                    "psa.impl-id" => ClassIdTypeChoice::Extension(import_psa_impl_id(&id)?),
+                    _ => Err(id.unsupported(FIELD))?,
+                })
+            })
+            .transpose()?,
+        vendor: class.vendor.map(Into::into),
+        model: class.model.map(Into::into),
+        layer: class.layer.map(Into::into),
+        index: class.index.map(Into::into),
+    })
+}
+
+fn export_measurement(measurement: &MeasurementMap) -> Result<Measurement> {
+    Ok(Measurement {
+        key: measurement
+            .mkey
+            .as_ref()
+            .map(|mkey| -> Result<_> {
+                let key = match mkey {
+                    MeasuredElementTypeChoice::Oid(oid) => {
+                        TypedValue::new("oid", oid.as_ref().to_string())
+                    }
+                    MeasuredElementTypeChoice::Uuid(uuid) => {
+                        TypedValue::new("uuid", uuid.as_ref().to_string())
+                    }
+                    MeasuredElementTypeChoice::UInt(uint_key) => {
+                        TypedValue::new("uint", uint(uint_key, "measurement key")?)
+                    }
+                    MeasuredElementTypeChoice::Tstr(text) => {
+                        TypedValue::new("string", text.to_string())
+                    }
+                    MeasuredElementTypeChoice::Extension(value) => {
+                        let value = export_psa_refval_id(value).ok_or_else(|| {
+                            VeraisonError::Unsupported(
+                                "measurement key extensions other than PSA reference value ids"
+                                    .to_string(),
+                            )
+                        })?;
+                        return Ok(MeasurementKey::PsaRefvalId {
+                            kind: PsaRefvalIdKind::PsaRefvalId,
+                            value,
+                        });
+                    }
+                };
+                Ok(MeasurementKey::Typed(key))
+            })
+            .transpose()?,
+        value: export_mval(&measurement.mval)?,
+        authorized_by: match measurement.authorized_by.as_deref() {
+            None => None,
+            Some([key]) => Some(export_key(key)?),
+            Some(_) => Err(VeraisonError::Unsupported(
+                "more than one authorized-by key".to_string(),
+            ))?,
+        },
+    })
+}
+
+fn import_measurement(measurement: Measurement) -> Result<MeasurementMap<'static>> {
+    const FIELD: &str = "measurement key";
+
+    Ok(MeasurementMap {
+        mkey: measurement
+            .key
+            .map(|key| -> Result<_> {
+                let key = match key {
+                    MeasurementKey::PsaRefvalId { value, .. } => {
+                        return Ok(MeasuredElementTypeChoice::Extension(import_psa_refval_id(
+                            value, FIELD,
+                        )?));
+                    }
+                    MeasurementKey::Typed(key) => key,
+                };
+
+                Ok(match key.kind.as_str() {
+                    "oid" => MeasuredElementTypeChoice::Oid(OidType::from(parse_oid(
+                        key.text(FIELD)?,
+                        FIELD,
+                    )?)),
+                    "uuid" => MeasuredElementTypeChoice::Uuid(TaggedUuidType::from(parse_uuid(
+                        key.text(FIELD)?,
+                        FIELD,
+                    )?)),
+                    "uint" => MeasuredElementTypeChoice::UInt(key.uint(FIELD)?.into()),
+                    "string" => {
+                        MeasuredElementTypeChoice::Tstr(key.text(FIELD)?.to_string().into())
+                    }
+                    // Not read as a PsaRefvalId, so the value is malformed
+                    "psa.refval-id" => {
+                        serde_json::from_value::<PsaRefvalId>(key.value)
+                            .map_err(|e| invalid(FIELD, e))?;
+                        Err(invalid(FIELD, "invalid PSA reference value id"))?
+                    }
+                    _ => Err(key.unsupported(FIELD))?,
+                })
+            })
+            .transpose()?,
+        mval: import_mval(measurement.value)?,
+        authorized_by: measurement
+            .authorized_by
+            .map(|key| import_key(&key, "authorized-by key").map(|key| vec![key]))
+            .transpose()?,
+    })
+}
+
+/// Writes a class id extension holding a PSA implementation id, returning `None` for any
+/// other extension value
+fn export_psa_impl_id(value: &ExtensionValue) -> Option<TypedValue> {
+    match value {
+        ExtensionValue::Tag(PSA_IMPL_ID_TAG, id) => match id.as_ref() {
+            ExtensionValue::Bytes(id) => {
+                Some(TypedValue::new("psa.impl-id", encode_base64(id.as_ref())))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn import_psa_impl_id(id: &TypedValue) -> Result<ExtensionValue<'static>> {
+    const FIELD: &str = "class id";
+
+    let bytes = id.bytes(FIELD)?;
+    if bytes.len() != PSA_IMPL_ID_LEN {
+        return Err(invalid(
+            FIELD,
+            format!(
+                "a PSA implementation id has {PSA_IMPL_ID_LEN} bytes, found {}",
+                bytes.len()
+            ),
+        )
+        .into());
+    }
+
+    Ok(ExtensionValue::Tag(
+        PSA_IMPL_ID_TAG,
+        Box::new(ExtensionValue::Bytes(Bytes::from(bytes))),
+    ))
+}
+
+/// Writes a measurement key extension holding a PSA reference value id, returning `None` for
+/// any other extension value
+fn export_psa_refval_id(value: &ExtensionValue) -> Option<PsaRefvalId> {
+    let ExtensionValue::Map(map) = value else {
+        return None;
+    };
+
+    let (mut label, mut version, mut signer_id) = (None, None, None);
+    for (key, value) in map {
+        match (key, value) {
+            (Label::Int(Integer(PSA_REFVAL_LABEL)), ExtensionValue::Text(text)) => {
+                label = Some(text.to_string())
+            }
+            (Label::Int(Integer(PSA_REFVAL_VERSION)), ExtensionValue::Text(text)) => {
+                version = Some(text.to_string())
+            }
+            (Label::Int(Integer(PSA_REFVAL_SIGNER_ID)), ExtensionValue::Bytes(bytes)) => {
+                signer_id = Some(encode_base64(bytes.as_ref()))
+            }
+            _ => return None,
+        }
+    }
+
+    Some(PsaRefvalId {
+        label,
+        version,
+        signer_id: signer_id?,
+    })
+}
+
+fn import_psa_refval_id(id: PsaRefvalId, field: &'static str) -> Result<ExtensionValue<'static>> {
+    let mut map = BTreeMap::new();
+    if let Some(label) = id.label {
+        map.insert(
+            Label::Int(Integer(PSA_REFVAL_LABEL)),
+            ExtensionValue::Text(label.into()),
+        );
+    }
+    if let Some(version) = id.version {
+        map.insert(
+            Label::Int(Integer(PSA_REFVAL_VERSION)),
+            ExtensionValue::Text(version.into()),
+        );
+    }
+    map.insert(
+        Label::Int(Integer(PSA_REFVAL_SIGNER_ID)),
+        ExtensionValue::Bytes(Bytes::from(decode_base64(&id.signer_id, field)?)),
+    );
+
+    Ok(ExtensionValue::Map(map))
+}
+
+fn export_mval(mval: &MeasurementValuesMap) -> Result<Mval> {
+    if mval.extensions.is_some() {
+        return Err(VeraisonError::Unsupported("measurement value extensions".to_string()).into());
+    }
+
+    Ok(Mval {
+        version: mval.version.as_ref().map(|version| Version {
+            value: version.version.to_string(),
+            scheme: version
+                .version_scheme
+                .as_ref()
+                .map(|scheme| scheme.to_string()),
+        }),
+        svn: mval
+            .svn
+            .as_ref()
+            .map(|svn| -> Result<_> {
+                Ok(match svn {
+                    SvnTypeChoice::Svn(svn) => TypedValue::new("exact-value", uint(svn, "svn")?),
+                    SvnTypeChoice::TaggedSvn(svn) => {
+                        TypedValue::new("exact-value", uint(svn.as_ref(), "svn")?)
+                    }
+                    SvnTypeChoice::TaggedMinSvn(svn) => {
+                        TypedValue::new("min-value", uint(svn.as_ref(), "svn")?)
+                    }
+                })
+            })
+            .transpose()?,
+        digests: mval
+            .digests
+            .as_ref()
+            .map(|digests| digests.iter().map(export_digest).collect()),
+        flags: mval.flags.as_ref().map(export_flags).transpose()?,
+        raw_value: mval
+            .raw
+            .as_ref()
+            .map(|raw| match &raw.raw_value {
+                RawValueTypeChoice::TaggedBytes(bytes) => {
+                    Ok(TypedValue::new("bytes", encode_base64(bytes.as_ref())))
+                }
+                RawValueTypeChoice::TaggedMaskedRawValue(_) => {
+                    Err(VeraisonError::Unsupported("masked raw values".to_string()))
+                }
+            })
+            .transpose()?,
+        raw_value_mask: mval
+            .raw
+            .as_ref()
+            .and_then(|raw| raw.raw_value_mask.as_ref())
+            .map(|mask| encode_base64(mask)),
+        mac_addr: mval.mac_addr.as_ref().map(|mac| {
+            let bytes: &[u8] = match mac {
+                MacAddrTypeChoice::Eui48Addr(addr) => addr,
+                MacAddrTypeChoice::Eui64Addr(addr) => addr,
+            };
+            bytes
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<Vec<_>>()
+                .join(":")
+        }),
+        ip_addr: mval.ip_addr.as_ref().map(|ip| match ip {
+            IpAddrTypeChoice::Ipv4(addr) => IpAddr::from(*addr).to_string(),
+            IpAddrTypeChoice::Ipv6(addr) => IpAddr::from(*addr).to_string(),
+        }),
+        serial_number: mval.serial_number.as_ref().map(|serial| serial.to_string()),
+        ueid: mval.ueid.as_ref().map(|ueid| encode_base64(ueid)),
+        uuid: mval.uuid.as_ref().map(|uuid| uuid.to_string()),
+        name: mval.name.as_ref().map(|name| name.to_string()),
+        cryptokeys: mval
+            .cryptokeys
+            .as_ref()
+            .map(|keys| keys.iter().map(export_key).collect::<Result<_>>())
+            .transpose()?,
+        integrity_registers: mval.integrity_registers.as_ref().map(|registers| {
+            registers
+                .0
+                .iter()
+                .map(|(index, digests)| {
+                    let (key, key_type) = match index {
+                        Ulabel::Uint(index) => (index.0.to_string(), "uint"),
+                        Ulabel::Text(index) => (index.to_string(), "text"),
+                    };
+                    let register = Register {
+                        key_type: key_type.to_string(),
+                        value: digests.iter().map(export_digest).collect(),
+                    };
+                    (key, register)
+                })
+                .collect()
+        }),
+    })
+}
+
+fn import_mval(mval: Mval) -> Result<MeasurementValuesMap<'static>> {
+    let raw_value_mask = mval
+        .raw_value_mask
+        .map(|mask| decode_base64(&mask, "raw-value-mask").map(Bytes::from))
+        .transpose()?;
+
+    Ok(MeasurementValuesMap {
+        version: mval
+            .version
+            .map(|version| -> Result<_> {
+                Ok(VersionMap {
+                    version: version.value.into(),
+                    version_scheme: version
+                        .scheme
+                        .map(|scheme| {
+                            VersionScheme::try_from(scheme)
+                                .map_err(|e| invalid("version scheme", e))
+                        })
+                        .transpose()?,
+                })
+            })
+            .transpose()?,
+        svn: mval
+            .svn
+            .map(|svn| -> Result<_> {
+                Ok(match svn.kind.as_str() {
+                    "exact-value" => SvnTypeChoice::Svn(svn.uint("svn")?.into()),
+                    "min-value" => SvnTypeChoice::TaggedMinSvn(MinSvnType::from(Integer::from(
+                        svn.uint("svn")?,
+                    ))),
+                    _ => Err(svn.unsupported("svn"))?,
+                })
+            })
+            .transpose()?,
+        digests: mval
+            .digests
+            .map(|digests| {
+                digests
+                    .iter()
+                    .map(|digest| import_digest(digest, "digest"))
+                    .collect::<Result<_>>()
+            })
+            .transpose()?,
+        flags: mval.flags.map(import_flags),
+        raw: match mval.raw_value {
+            Some(raw) if raw.kind == "bytes" => Some(RawValueType {
+                raw_value: RawValueTypeChoice::TaggedBytes(TaggedBytes::from(Bytes::from(
+                    raw.bytes("raw-value")?,
+                ))),
+                raw_value_mask,
+            }),
+            Some(raw) => Err(raw.unsupported("raw-value"))?,
+            None if raw_value_mask.is_some() => {
+                Err(invalid("raw-value-mask", "a mask requires a raw-value"))?
+            }
+            None => None,
+        },
+        mac_addr: mval
+            .mac_addr
+            .map(|mac| -> Result<_> {
+                let bytes = mac
+                    .split([':', '-'])
+                    .map(|byte| u8::from_str_radix(byte, 16))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|_| invalid("mac-addr", &mac))?;
+                Ok(match bytes.len() {
+                    6 => MacAddrTypeChoice::Eui48Addr(bytes.try_into().unwrap_or_default()),
+                    8 => MacAddrTypeChoice::Eui64Addr(bytes.try_into().unwrap_or_default()),
+                    _ => Err(invalid("mac-addr", &mac))?,
+                })
+            })
+            .transpose()?,
+        ip_addr: mval
+            .ip_addr
+            .map(|ip| -> Result<_> {
+                Ok(
+                    match ip.parse::<IpAddr>().map_err(|e| invalid("ip-addr", e))? {
+                        IpAddr::V4(addr) => IpAddrTypeChoice::Ipv4(addr.octets()),
+                        IpAddr::V6(addr) => IpAddrTypeChoice::Ipv6(addr.octets()),
+                    },
+                )
+            })
+            .transpose()?,
+        serial_number: mval.serial_number.map(Into::into),
+        ueid: mval
+            .ueid
+            .map(|ueid| -> Result<_> {
+                Ok(UeidType::try_from(decode_base64(&ueid, "ueid")?)
+                    .map_err(|e| invalid("ueid", e))?)
+            })
+            .transpose()?,
+        uuid: mval
+            .uuid
+            .map(|uuid| parse_uuid(&uuid, "uuid"))
+            .transpose()?,
+        name: mval.name.map(Into::into),
+        cryptokeys: mval
+            .cryptokeys
+            .map(|keys| {
+                keys.iter()
+                    .map(|key| import_key(key, "cryptokey"))
+                    .collect::<Result<_>>()
+            })
+            .transpose()?,
+        integrity_registers: mval
+            .integrity_registers
+            .map(|registers| {
+                registers
+                    .into_iter()
+                    .map(|(key, register)| {
+                        let index = match register.key_type.as_str() {
+                            "uint" => Ulabel::Uint(
+                                key.parse::<u64>()
+                                    .map_err(|e| invalid("integrity register index", e))?
+                                    .into(),
+                            ),
+                            "text" => Ulabel::Text(key.into()),
+                            key_type => Err(invalid("integrity register key-type", key_type))?,
+                        };
+                        let digests = register
+                            .value
+                            .iter()
+                            .map(|digest| import_digest(digest, "integrity register"))
+                            .collect::<Result<_>>()?;
+                        Ok((index, digests))
+                    })
+                    .collect::<Result<_>>()
+                    .map(IntegrityRegisters)
+            })
+            .transpose()?,
+        extensions: None,
+    })
+}
+
+fn export_flags(flags: &FlagsMap) -> Result<Flags> {
+    if flags.extensions.is_some() {
+        return Err(VeraisonError::Unsupported("flags extensions".to_string()).into());
+    }
+
+    Ok(Flags {
+        is_configured: flags.is_configured,
+        is_secure: flags.is_secure,
+        is_recovery: flags.is_recovery,
+        is_debug: flags.is_debug,
+        is_replay_protected: flags.is_replay_protected,
+        is_integrity_protected: flags.is_integrity_protected,
+        is_runtime_meas: flags.is_runtime_meas,
+        is_immutable: flags.is_immutable,
+        is_tcb: flags.is_tcb,
+        is_confidentiality_protected: flags.is_confidentiality_protected,
+    })
+}
+
+fn import_flags(flags: Flags) -> FlagsMap<'static> {
+    FlagsMap {
+        is_configured: flags.is_configured,
+        is_secure: flags.is_secure,
+        is_recovery: flags.is_recovery,
+        is_debug: flags.is_debug,
+        is_replay_protected: flags.is_replay_protected,
+        is_integrity_protected: flags.is_integrity_protected,
+        is_runtime_meas: flags.is_runtime_meas,
+        is_immutable: flags.is_immutable,
+        is_tcb: flags.is_tcb,
+        is_confidentiality_protected: flags.is_confidentiality_protected,
+        extensions: None,
+    }
+}
+
+/// `<algorithm>;<base64 value>`, the digest format of the Veraison templates
+fn export_digest(digest: &Digest) -> String {
+    format!("{};{}", digest.alg, encode_base64(&digest.val))
+}
+
+fn import_digest(digest: &str, field: &'static str) -> Result<Digest<'static>> {
+    let (alg, val) = digest
+        .split_once(';')
+        .ok_or_else(|| invalid(field, "expected <algorithm>;<base64 value>"))?;
+
+    Ok(Digest {
+        alg: HashAlgorithm::try_from(alg).map_err(|e| invalid(field, e))?,
+        val: decode_base64(val, field)?.into(),
+    })
+}
+
+fn export_key(key: &CryptoKeyTypeChoice) -> Result<TypedValue> {
+    Ok(match key {
+        CryptoKeyTypeChoice::PkixBase64Key(key) => {
+            TypedValue::new("pkix-base64-key", key.as_ref().to_string())
+        }
+        CryptoKeyTypeChoice::PkixBase64Cert(cert) => {
+            TypedValue::new("pkix-base64-cert", cert.as_ref().to_string())
+        }
+        CryptoKeyTypeChoice::PkixBase64CertPath(path) => {
+            TypedValue::new("pkix-base64-cert-path", path.as_ref().to_string())
+        }
+        CryptoKeyTypeChoice::CoseKey(key) => {
+            let mut cbor = vec![];
+            ciborium::into_writer(key.as_ref(), &mut cbor).map_err(|e| invalid("cose-key", e))?;
+            TypedValue::new("cose-key", encode_base64(&cbor))
+        }
+        CryptoKeyTypeChoice::Thumbprint(digest) => {
+            TypedValue::new("thumbprint", export_digest(digest.as_ref()))
+        }
+        CryptoKeyTypeChoice::CertThumbprint(digest) => {
+            TypedValue::new("cert-thumbprint", export_digest(digest.as_ref()))
+        }
+        CryptoKeyTypeChoice::CertPathThumbprint(digest) => {
+            TypedValue::new("cert-path-thumbprint", export_digest(digest.as_ref()))
+        }
+        CryptoKeyTypeChoice::PkixAsn1DerCert(cert) => {
+            TypedValue::new("pkix-asn1-der-cert", encode_base64(cert.as_ref()))
+        }
+        CryptoKeyTypeChoice::Bytes(bytes) => {
+            TypedValue::new("bytes", encode_base64(bytes.as_ref()))
+        }
+    })
+}
+
+fn import_key(key: &TypedValue, field: &'static str) -> Result<CryptoKeyTypeChoice<'static>> {
+    let text = key.text(field)?;
+
+    Ok(match key.kind.as_str() {
+        "pkix-base64-key" => CryptoKeyTypeChoice::PkixBase64Key(PkixBase64KeyType::from(
+            Text::from(text.to_string()),
+        )),
+        "pkix-base64-cert" => CryptoKeyTypeChoice::PkixBase64Cert(PkixBase64CertType::from(
+            Text::from(text.to_string()),
+        )),
+        "pkix-base64-cert-path" => CryptoKeyTypeChoice::PkixBase64CertPath(
+            PkixBase64CertPathType::from(Text::from(text.to_string())),
+        ),
+        "cose-key" => {
+            let cbor = decode_base64(text, field)?;
+            let key: CoseKeySetOrKey =
+                crate::cbor::from_slice_owned(&cbor).map_err(|e| invalid(field, e))?;
+            CryptoKeyTypeChoice::CoseKey(CoseKeyType::from(key))
+        }
+        "thumbprint" => {
+            CryptoKeyTypeChoice::Thumbprint(ThumbprintType::from(import_digest(text, field)?))
+        }
+        "cert-thumbprint" => CryptoKeyTypeChoice::CertThumbprint(CertThumbprintType::from(
+            import_digest(text, field)?,
+        )),
+        "cert-path-thumbprint" => CryptoKeyTypeChoice::CertPathThumbprint(
+            CertPathThumbprintType::from(import_digest(text, field)?),
+        ),
+        "pkix-asn1-der-cert" => CryptoKeyTypeChoice::PkixAsn1DerCert(PkixAsn1DerCertType::from(
+            Bytes::from(decode_base64(text, field)?),
+        )),
+        "bytes" => {
+            CryptoKeyTypeChoice::Bytes(TaggedBytes::from(Bytes::from(decode_base64(text, field)?)))
+        }
+        _ => Err(key.unsupported(field))?,
+    })
+}
+
+/// Parses an RFC 3339 time into seconds since the Unix epoch, ignoring fractional seconds
+fn parse_time(text: &str, field: &'static str) -> Result<i128> {
+    parse_rfc3339(text)
+        .ok_or_else(|| invalid(field, format!("expected an RFC 3339 time, got \"{text}\"")).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNSIGNED: &[u8] = include_bytes!("../testdata/corim/unsigned.cbor");
+
+    fn fixture() -> CorimMap<'static> {
+        let crate::ConciseRimTypeChoice::TaggedUnsignedCorimMap(corim) =
+            crate::cbor::from_slice(UNSIGNED).unwrap()
+        else {
+            panic!("the fixture is an unsigned CoRIM");
+        };
+        corim.as_ref().clone()
+    }
+
+    #[test]
+    fn test_veraison_comid_template() {
+        let template = r#"{
+  "lang": "en-GB",
+  "tag-identity": {
+    "id": "acme-roadrunner-comid",
+    "version": 1
+  },
+  "entities": [
+    {
+      "name": "ACME Inc.",
+      "regid": "https://acme.example",
+      "roles": [
+        "tagCreator",
+        "creator"
+      ]
+    }
+  ],
+  "linked-tags": [
+    {
+      "target": "43bbe37f-2e61-4b33-aed3-53cff1428b16",
+      "rel": "supplements"
+    }
+  ],
+  "triples": {
+    "reference-values": [
+      {
+        "environment": {
+          "class": {
+            "id": {
+              "type": "oid",
+              "value": "2.16.840.1.113741.1.2.3"
+            },
+            "vendor": "ACME Inc.",
+            "model": "RoadRunner",
+            "layer": 0
+          },
+          "instance": {
+            "type": "ueid",
+            "value": "AgEBAQEBAQEBAQEBAQEBAQ=="
+          }
+        },
+        "measurements": [
+          {
+            "key": {
+              "type": "uint",
+              "value": 1
+            },
+            "value": {
+              "version": {
+                "value": "1.0.0",
+                "scheme": "semver"
+              },
+              "svn": {
+                "type": "min-value",
+                "value": 3
+              },
+              "digests": [
+                "sha-256;h0KPxSKAPTEGXnvOPPA/5HUJZjHl4Hu9eg/eYMTPJcc="
+              ],
+              "flags": {
+                "is-debug": false,
+                "is-secure": true
+              },
+              "raw-value": {
+                "type": "bytes",
+                "value": "3q2+7w=="
+              },
+              "raw-value-mask": "/////w==",
+              "mac-addr": "02:00:5e:10:00:00",
+              "ip-addr": "192.0.2.1",
+              "serial-number": "C02X1234",
+              "integrity-registers": {
+                "0": {
+                  "key-type": "uint",
+                  "value": [
+                    "sha-256;h0KPxSKAPTEGXnvOPPA/5HUJZjHl4Hu9eg/eYMTPJcc="
+                  ]
+                }
+              }
+            }
+          }
+        ]
+      }
+    ],
+    "attester-verification-keys": [
+      {
+        "environment": {
+          "group": {
+            "type": "uuid",
+            "value": "31fb5abf-023e-4992-aa4e-95f9c1503bfa"
+          }
+        },
+        "verification-keys": [
+          {
+            "type": "pkix-base64-key",
+            "value": "-----BEGIN PUBLIC KEY-----\nMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE\n-----END PUBLIC KEY-----"
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+
+        let comid = ConciseMidTag::from_veraison_json(template).unwrap();
+        assert_eq!(
+            comid.tag_identity.tag_id.as_str(),
+            Some("acme-roadrunner-comid")
+        );
+        assert!(comid.linked_tags.as_ref().unwrap()[0]
+            .linked_tag_id
+            .as_uuid()
+            .is_some());
+
+        let reference = &comid.triples.reference_triples.as_ref().unwrap()[0];
+        let mval = &reference.ref_claims[0].mval;
+        assert!(matches!(mval.svn, Some(SvnTypeChoice::TaggedMinSvn(_))));
+        assert_eq!(
+            mval.mac_addr,
+            Some(MacAddrTypeChoice::Eui48Addr([2, 0, 0x5e, 0x10, 0, 0]))
+        );
+        assert_eq!(mval.ip_addr, Some(IpAddrTypeChoice::Ipv4([192, 0, 2, 1])));
+        assert_eq!(mval.digests.as_ref().unwrap()[0].val.len(), 32);
+        assert_eq!(mval.flags.as_ref().unwrap().is_secure, Some(true));
+
+        // Flags are written in the order of the flags map
+        let expected = template.replace(
+            "\"is-debug\": false,\n                \"is-secure\": true",
+            "\"is-secure\": true,\n                \"is-debug\": false",
+        );
+        assert_eq!(comid.to_veraison_json().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_veraison_comid_round_trip() {
+        let corim = fixture();
+
+        for tag in &corim.tags {
+            if let ConciseTagTypeChoice::Mid(comid) = tag {
+                let json = comid.as_ref().to_veraison_json().unwrap();
+                assert!(json.contains("\"reference-values\""), "{json}");
+                assert_eq!(
+                    ConciseMidTag::from_veraison_json(&json).unwrap(),
+                    *comid.as_ref()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_veraison_corim_template() {
+        let template = r#"{
+  "corim-id": "5c57e8f4-46cd-421b-91c9-08cf93e13cfc",
+  "dependent-rims": [
+    {
+      "href": "https://acme.example/rims/base",
+      "thumbprint": "sha-256;h0KPxSKAPTEGXnvOPPA/5HUJZjHl4Hu9eg/eYMTPJcc="
+    }
+  ],
+  "profile": "http://arm.com/psa/iot/1",
+  "validity": {
+    "not-before": "2021-12-31T00:00:00Z",
+    "not-after": "2025-12-31T00:00:00Z"
+  },
+  "entities": [
+    {
+      "name": "ACME Ltd.",
+      "regid": "https://acme.example",
+      "roles": [
+        "manifestCreator"
+      ]
+    }
+  ]
+}"#;
+
+        let tags = fixture().tags;
+        let corim = CorimMap::from_veraison_json(template, tags.clone()).unwrap();
+        assert!(corim.id.as_uuid_bytes().is_some());
+        assert_eq!(corim.tags, tags);
+        assert_eq!(
+            corim.rim_validity,
+            Some(ValidityMap::new(
+                Some(1_640_908_800.into()),
+                1_767_139_200.into()
+            ))
+        );
+        assert_eq!(corim.to_veraison_json().unwrap(), template);
+
+        let oid =
+            CorimMap::from_veraison_json(r#"{"corim-id": "corim-1", "profile": "1.2.3.4"}"#, tags)
+                .unwrap();
+        assert!(matches!(oid.profile, Some(ProfileTypeChoice::OidType(_))));
+        assert_eq!(oid.id.as_str(), Some("corim-1"));
+    }
+
+    #[test]
+    fn test_veraison_cotl_round_trip() {
+        let cotl = ConciseTlTag {
+            tag_identity: TagIdentityMap::new("trust-list".into(), Some(2.into())),
+            tags_list: vec![TagIdentityMap::new("acme-roadrunner-comid".into(), None)],
+            tl_validity: ValidityMap::new(None, 1_893_456_000.into()),
+        };
+
+        let json = cotl.to_veraison_json().unwrap();
+        assert!(
+            json.contains("\"not-after\": \"2030-01-01T00:00:00Z\""),
+            "{json}"
+        );
+        assert_eq!(ConciseTlTag::from_veraison_json(&json).unwrap(), cotl);
+    }
+
+    #[test]
+    fn test_veraison_psa_template() {
+        // The PSA reference value template shipped with cocli
+        let template = r#"{
+  "lang": "en-GB",
+  "tag-identity": {
+    "id": "43BBE37F-2E61-4B33-AED3-53CFF1428B16",
+    "version": 0
+  },
+  "entities": [
+    {
+      "name": "ACME Ltd.",
+      "regid": "https://acme.example",
+      "roles": [
+        "tagCreator",
+        "creator",
+        "maintainer"
+      ]
+    }
+  ],
+  "triples": {
+    "reference-values": [
+      {
+        "environment": {
+          "class": {
+            "id": {
+              "type": "psa.impl-id",
+              "value": "YWNtZS1pbXBsZW1lbnRhdGlvbi1pZC0wMDAwMDAwMDE="
+            },
+            "vendor": "ACME",
+            "model": "RoadRunner"
+          }
+        },
+        "measurements": [
+          {
+            "key": {
+              "type": "psa.refval-id",
+              "value": {
+                "label": "BL",
+                "version": "2.1.0",
+                "signer-id": "rLsRx+TaIXIFUjzkzhokWuGiOa48a/2eeHH35di66Gs="
+              }
+            },
+            "value": {
+              "digests": [
+                "sha-256;h0KPxSKAPTEGXnvOPPA/5HUJZjHl4Hu9eg/eYMTPJcc="
+              ]
+            }
+          },
+          {
+            "key": {
+              "type": "psa.refval-id",
+              "value": {
+                "label": "PRoT",
+                "version": "1.3.5",
+                "signer-id": "rLsRx+TaIXIFUjzkzhokWuGiOa48a/2eeHH35di66Gs="
+              }
+            },
+            "value": {
+              "digests": [
+                "sha-256;AmOCmYm2/ZVPcrqvL8ZLwuLwHWktTecphuqAj26ZgT8="
+              ]
+            }
+          },
+          {
+            "key": {
+              "type": "psa.refval-id",
+              "value": {
+                "label": "ARoT",
+                "version": "0.1.4",
+                "signer-id": "rLsRx+TaIXIFUjzkzhokWuGiOa48a/2eeHH35di66Gs="
+              }
+            },
+            "value": {
+              "digests": [
+                "sha-256;o6XnFfDMV0pnw/ifmnIDb9GGbXeqlNsp+X3qMLVR+6M="
+              ]
+            }
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+
+        let comid = ConciseMidTag::from_veraison_json(template).unwrap();
+        let triple = &comid.triples.reference_triples.as_ref().unwrap()[0];
+
+        // tag(600, h'61636d65...') and {1: "BL", 4: "2.1.0", 5: h'acbb11c7...'}
+        let mut class_id = vec![];
+        let class = triple.ref_env.class.as_ref().unwrap();
+        ciborium::into_writer(class.class_id.as_ref().unwrap(), &mut class_id).unwrap();
+        assert_eq!(&class_id[..5], [0xd9, 0x02, 0x58, 0x58, 0x20]);
+        assert_eq!(&class_id[5..], b"acme-implementation-id-000000001");
+
+        let mut mkey = vec![];
+        ciborium::into_writer(triple.ref_claims[0].mkey.as_ref().unwrap(), &mut mkey).unwrap();
+        assert_eq!(
+            &mkey[..15],
+            [
+                0xa3, 0x01, 0x62, b'B', b'L', 0x04, 0x65, b'2', b'.', b'1', b'.', b'0', 0x05, 0x58,
+                0x20
+            ]
+        );
+        assert_eq!(&mkey[15..19], [0xac, 0xbb, 0x11, 0xc7]);
+
+        let mut bytes = vec![];
+        ciborium::into_writer(&comid, &mut bytes).unwrap();
+        let decoded: ConciseMidTag = crate::cbor::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, comid);
+
+        // UUIDs are written in lower case
+        assert_eq!(
+            comid.to_veraison_json().unwrap(),
+            template.replace(
+                "43BBE37F-2E61-4B33-AED3-53CFF1428B16",
+                "43bbe37f-2e61-4b33-aed3-53cff1428b16"
+            )
+        );
+    }
+
+    #[test]
+    fn test_veraison_errors() {
+        let err = |json: &str| {
+            ConciseMidTag::from_veraison_json(json)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let class = |id: &str| {
+            format!(
+                r#"{{"tag-identity": {{"id": "x"}}, "triples": {{"reference-values": [
+                    {{"environment": {{"class": {{"id": {id}}}}}, "measurements": []}}]}}}}"#
+            )
+        };
+        assert_eq!(
+            err(&class(r#"{"type": "example.class-id", "value": "AA=="}"#)),
+            "class id of type \"example.class-id\" cannot be converted to or from a Veraison template"
+        );
+        assert_eq!(
+            err(&class(r#"{"type": "psa.impl-id", "value": "AA=="}"#)),
+            "invalid class id in Veraison template: a PSA implementation id has 32 bytes, found 1"
+        );
+        assert_eq!(
+            err(&class(r#"{"type": "uuid", "value": "not-a-uuid"}"#)),
+            "invalid class id in Veraison template: invalid character: expected an optional prefix of `urn:uuid:` followed by [0-9a-fA-F-], found `n` at 1"
+        );
+        let mkey = r#"{"tag-identity": {"id": "x"}, "triples": {"reference-values": [
+            {"environment": {}, "measurements": [{"key": {"type": "psa.refval-id",
+            "value": {"label": "BL"}}, "value": {}}]}]}}"#;
+        assert_eq!(
+            err(mkey),
+            "invalid measurement key in Veraison template: missing field `signer-id`"
+        );
+        assert!(err("{}").starts_with("invalid Veraison template JSON: missing field"));
+
+        let mut comid =
+            ConciseMidTag::from_veraison_json(&class(r#"{"type": "oid", "value": "1.2.3"}"#))
+                .unwrap();
+        comid.triples.coswid_triples = Some(vec![]);
+        assert_eq!(
+            comid.to_veraison_json().unwrap_err().to_string(),
+            "CoSWID triples cannot be converted to or from a Veraison template"
+        );
+
+        let mut comid =
+            ConciseMidTag::from_veraison_json(&class(r#"{"type": "oid", "value": "1.2.3"}"#))
+                .unwrap();
+        comid.triples.reference_triples.as_mut().unwrap()[0]
+            .ref_env
+            .class
+            .as_mut()
+            .unwrap()
+            .class_id = Some(ClassIdTypeChoice::Extension(ExtensionValue::Tag(
+            601,
+            Box::new(ExtensionValue::Bytes(Bytes::from(vec![0; 32]))),
+        )));
+        assert_eq!(
+            comid.to_veraison_json().unwrap_err().to_string(),
+            "class id extensions other than PSA implementation ids cannot be converted to or from a Veraison template"
+        );
+    }
+
+    #[test]
+    fn test_rfc3339_times() {
+        for (seconds, text) in [
+            (0, "1970-01-01T00:00:00Z"),
+            (951_782_400, "2000-02-29T00:00:00Z"),
+            (1_704_067_199, "2023-12-31T23:59:59Z"),
+            (-86_400, "1969-12-31T00:00:00Z"),
+        ] {
+            assert_eq!(format_rfc3339(seconds), text);
+            assert_eq!(parse_time(text, "time").unwrap(), seconds);
+        }
+
+        assert_eq!(
+            parse_time("2024-01-01T02:00:00.123+02:00", "time").unwrap(),
+            1_704_067_200
+        );
+        assert!(parse_time("2024-01-01", "time").is_err());
+        assert!(parse_time("2024-13-01T00:00:00Z", "time").is_err());
+    }
+}
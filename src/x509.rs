@@ -0,0 +1,539 @@
+// SPDX-License-Identifier: MIT
+
+//! X.509 certificate chain validation for CoRIM signers
+//!
+//! Signed CoRIMs may carry the signer's certificate chain in the `x5chain` COSE header
+//! parameter (RFC 9360). This module validates such chains against a set of locally
+//! configured trust anchors without any network access.
+//!
+//! # Key Components
+//!
+//! * [`TrustAnchorStore`] - A set of trusted root (or intermediate) certificates
+//! * [`ValidatedChain`] - The result of a successful chain validation
+//!
+//! # Checks Performed
+//!
+//! * Every certificate in the chain is within its validity period
+//! * The leaf certificate permits `digitalSignature` when a key usage extension is present
+//! * No certificate in the chain has a critical extension other than basic constraints and key
+//!   usage
+//! * Every issuing certificate, including the trust anchor, is a CA permitted to sign
+//!   certificates, and its path length constraint is honoured
+//! * Each certificate is issued by, and carries a valid signature from, the next certificate
+//! * The chain terminates at, or is issued by, a trust anchor
+//!
+//! Revocation is not checked.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use corim_rs::x509::TrustAnchorStore;
+//! # fn example(root_pem: &str, chain: &[corim_rs::core::Bytes]) -> corim_rs::result::Result<()> {
+//! let mut anchors = TrustAnchorStore::new();
+//! anchors.add_pem(root_pem)?;
+//!
+//! let validated = anchors.validate(chain)?;
+//! let key = validated.leaf_verifying_key()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use der::{oid::AssociatedOid, Decode, Encode};
+use p256::ecdsa::signature::{hazmat::PrehashVerifier as _, Verifier as _};
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
+use x509_cert::{
+    ext::pkix::{BasicConstraints, KeyUsage},
+    Certificate,
+};
+
+use crate::{cose::CoseVerifyingKey, Result, X509Error};
+
+const ECDSA_WITH_SHA256: der::oid::ObjectIdentifier =
+    der::oid::ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ECDSA_WITH_SHA384: der::oid::ObjectIdentifier =
+    der::oid::ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const ECDSA_WITH_SHA512: der::oid::ObjectIdentifier =
+    der::oid::ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.4");
+const ED25519: der::oid::ObjectIdentifier = der::oid::ObjectIdentifier::new_unwrap("1.3.101.112");
+const SHA256_WITH_RSA: der::oid::ObjectIdentifier =
+    der::oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const SHA384_WITH_RSA: der::oid::ObjectIdentifier =
+    der::oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const SHA512_WITH_RSA: der::oid::ObjectIdentifier =
+    der::oid::ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+
+/// Returns the current system time in seconds since the Unix epoch
+pub(crate) fn unix_time_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// A set of trusted certificates used to anchor signer certificate chains
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TrustAnchorStore {
+    anchors: Vec<Certificate>,
+}
+
+impl TrustAnchorStore {
+    /// Creates an empty trust anchor store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a DER encoded certificate to the store
+    pub fn add_der(&mut self, der: &[u8]) -> Result<()> {
+        self.anchors.push(parse_certificate(der)?);
+        Ok(())
+    }
+
+    /// Adds every certificate found in a PEM document to the store
+    pub fn add_pem(&mut self, pem: &str) -> Result<()> {
+        let certs = Certificate::load_pem_chain(pem.as_bytes())
+            .map_err(|e| X509Error::InvalidCertificate(e.to_string()))?;
+
+        if certs.is_empty() {
+            return Err(X509Error::InvalidCertificate(
+                "no certificates found in PEM document".to_string(),
+            ))?;
+        }
+
+        self.anchors.extend(certs);
+        Ok(())
+    }
+
+    /// Returns the number of trust anchors in the store
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    /// Returns `true` if the store contains no trust anchors
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Returns an iterator over the trust anchors in the store
+    pub fn iter(&self) -> impl Iterator<Item = &Certificate> {
+        self.anchors.iter()
+    }
+
+    /// Validates a chain of DER encoded certificates, leaf first, at the current system time
+    pub fn validate<T: AsRef<[u8]>>(&self, chain: &[T]) -> Result<ValidatedChain> {
+        self.validate_at(chain, unix_time_now())
+    }
+
+    /// Validates a chain of DER encoded certificates, leaf first, at `time` (seconds since the
+    /// Unix epoch)
+    pub fn validate_at<T: AsRef<[u8]>>(&self, chain: &[T], time: u64) -> Result<ValidatedChain> {
+        let certificates = chain
+            .iter()
+            .map(|der| parse_certificate(der.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let Some(last) = certificates.last() else {
+            return Err(X509Error::EmptyChain)?;
+        };
+
+        for (index, cert) in certificates.iter().enumerate() {
+            check_validity(cert, time)?;
+            check_critical_extensions(cert)?;
+
+            if index == 0 {
+                check_leaf(cert)?;
+            } else {
+                check_ca(cert, index - 1)?;
+            }
+        }
+
+        for pair in certificates.windows(2) {
+            let (subject, issuer) = (&pair[0], &pair[1]);
+            if subject.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+                return Err(X509Error::IssuerMismatch {
+                    subject: subject_name(subject),
+                    issuer: subject_name(issuer),
+                })?;
+            }
+            verify_issued_by(subject, issuer)?;
+        }
+
+        let anchor = match self.anchors.iter().find(|anchor| *anchor == last) {
+            Some(anchor) => anchor.clone(),
+            None => {
+                let anchor = self
+                    .anchors
+                    .iter()
+                    .filter(|anchor| anchor.tbs_certificate.subject == last.tbs_certificate.issuer)
+                    .find(|anchor| verify_issued_by(last, anchor).is_ok())
+                    .ok_or_else(|| X509Error::UntrustedRoot {
+                        issuer: last.tbs_certificate.issuer.to_string(),
+                    })?;
+
+                // The constraints of the anchor apply to every CA certificate below it
+                check_validity(anchor, time)?;
+                check_ca(anchor, certificates.len() - 1)?;
+                anchor.clone()
+            }
+        };
+
+        Ok(ValidatedChain {
+            certificates,
+            anchor,
+        })
+    }
+}
+
+/// A certificate chain that has been validated against a [`TrustAnchorStore`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedChain {
+    certificates: Vec<Certificate>,
+    anchor: Certificate,
+}
+
+impl ValidatedChain {
+    /// Returns the leaf (signer) certificate
+    pub fn leaf(&self) -> &Certificate {
+        &self.certificates[0]
+    }
+
+    /// Returns the trust anchor that terminated the chain
+    pub fn anchor(&self) -> &Certificate {
+        &self.anchor
+    }
+
+    /// Returns the certificates of the chain, leaf first
+    pub fn certificates(&self) -> &[Certificate] {
+        &self.certificates
+    }
+
+    /// Returns the public key of the leaf certificate
+    pub fn leaf_verifying_key(&self) -> Result<CoseVerifyingKey> {
+        public_key(self.leaf())
+    }
+}
+
+pub(crate) fn parse_certificate(der: &[u8]) -> Result<Certificate> {
+    Ok(Certificate::from_der(der).map_err(|e| X509Error::InvalidCertificate(e.to_string()))?)
+}
+
+fn subject_name(cert: &Certificate) -> String {
+    cert.tbs_certificate.subject.to_string()
+}
+
+pub(crate) fn public_key(cert: &Certificate) -> Result<CoseVerifyingKey> {
+    let spki = cert
+        .tbs_certificate
+        .subject_public_key_info
+        .to_der()
+        .map_err(|e| X509Error::InvalidCertificate(e.to_string()))?;
+    CoseVerifyingKey::from_public_key_der(&spki)
+}
+
+fn extension<T>(cert: &Certificate) -> Result<Option<T>>
+where
+    T: AssociatedOid + for<'a> Decode<'a>,
+{
+    let Some(extensions) = &cert.tbs_certificate.extensions else {
+        return Ok(None);
+    };
+
+    extensions
+        .iter()
+        .find(|ext| ext.extn_id == T::OID)
+        .map(|ext| {
+            T::from_der(ext.extn_value.as_bytes())
+                .map_err(|e| X509Error::InvalidCertificate(e.to_string()).into())
+        })
+        .transpose()
+}
+
+fn check_validity(cert: &Certificate, time: u64) -> Result<()> {
+    let validity = &cert.tbs_certificate.validity;
+
+    if time < validity.not_before.to_unix_duration().as_secs() {
+        return Err(X509Error::NotYetValid {
+            subject: subject_name(cert),
+        }
+        .into());
+    }
+    if time > validity.not_after.to_unix_duration().as_secs() {
+        return Err(X509Error::Expired {
+            subject: subject_name(cert),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Rejects critical extensions other than those checked here, as required by RFC 5280 §4.2
+fn check_critical_extensions(cert: &Certificate) -> Result<()> {
+    let Some(extensions) = &cert.tbs_certificate.extensions else {
+        return Ok(());
+    };
+
+    match extensions
+        .iter()
+        .filter(|ext| ext.critical)
+        .find(|ext| ext.extn_id != BasicConstraints::OID && ext.extn_id != KeyUsage::OID)
+    {
+        Some(ext) => Err(X509Error::UnrecognizedCriticalExtension {
+            subject: subject_name(cert),
+            oid: ext.extn_id.to_string(),
+        })?,
+        None => Ok(()),
+    }
+}
+
+fn check_leaf(cert: &Certificate) -> Result<()> {
+    if let Some(key_usage) = extension::<KeyUsage>(cert)? {
+        if !key_usage.digital_signature() {
+            return Err(X509Error::MissingKeyUsage {
+                subject: subject_name(cert),
+                usage: "digitalSignature",
+            }
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+fn check_ca(cert: &Certificate, intermediates_below: usize) -> Result<()> {
+    let constraints = extension::<BasicConstraints>(cert)?;
+
+    let Some(constraints) = constraints.filter(|c| c.ca) else {
+        return Err(X509Error::NotACa {
+            subject: subject_name(cert),
+        }
+        .into());
+    };
+
+    if let Some(path_len) = constraints.path_len_constraint {
+        if intermediates_below > path_len as usize {
+            return Err(X509Error::PathLenExceeded {
+                subject: subject_name(cert),
+            }
+            .into());
+        }
+    }
+
+    if let Some(key_usage) = extension::<KeyUsage>(cert)? {
+        if !key_usage.key_cert_sign() {
+            return Err(X509Error::MissingKeyUsage {
+                subject: subject_name(cert),
+                usage: "keyCertSign",
+            }
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_issued_by(cert: &Certificate, issuer: &Certificate) -> Result<()> {
+    let invalid = || X509Error::InvalidSignature {
+        subject: subject_name(cert),
+    };
+
+    let tbs = cert
+        .tbs_certificate
+        .to_der()
+        .map_err(|e| X509Error::InvalidCertificate(e.to_string()))?;
+    let signature = cert.signature.raw_bytes();
+    let oid = cert.signature_algorithm.oid;
+
+    let prehash = match oid {
+        ECDSA_WITH_SHA256 => Some(Sha256::digest(&tbs).to_vec()),
+        ECDSA_WITH_SHA384 => Some(Sha384::digest(&tbs).to_vec()),
+        ECDSA_WITH_SHA512 => Some(Sha512::digest(&tbs).to_vec()),
+        _ => None,
+    };
+
+    match (public_key(issuer)?, prehash) {
+        (CoseVerifyingKey::P256(key), Some(prehash)) => {
+            let signature = p256::ecdsa::Signature::from_der(signature).map_err(|_| invalid())?;
+            key.verify_prehash(&prehash, &signature)
+                .map_err(|_| invalid())?;
+        }
+        (CoseVerifyingKey::P384(key), Some(prehash)) => {
+            let signature = p384::ecdsa::Signature::from_der(signature).map_err(|_| invalid())?;
+            key.verify_prehash(&prehash, &signature)
+                .map_err(|_| invalid())?;
+        }
+        (CoseVerifyingKey::Ed25519(key), None) if oid == ED25519 => {
+            let signature =
+                ed25519_dalek::Signature::from_slice(signature).map_err(|_| invalid())?;
+            key.verify(&tbs, &signature).map_err(|_| invalid())?;
+        }
+        (CoseVerifyingKey::Rsa(key), None) => {
+            let signature = rsa::pkcs1v15::Signature::try_from(signature).map_err(|_| invalid())?;
+            let result = match oid {
+                SHA256_WITH_RSA => {
+                    rsa::pkcs1v15::VerifyingKey::<Sha256>::new(key).verify(&tbs, &signature)
+                }
+                SHA384_WITH_RSA => {
+                    rsa::pkcs1v15::VerifyingKey::<Sha384>::new(key).verify(&tbs, &signature)
+                }
+                SHA512_WITH_RSA => {
+                    rsa::pkcs1v15::VerifyingKey::<Sha512>::new(key).verify(&tbs, &signature)
+                }
+                _ => return Err(X509Error::UnsupportedSignatureAlgorithm(oid.to_string()))?,
+            };
+            result.map_err(|_| invalid())?;
+        }
+        _ => return Err(X509Error::UnsupportedSignatureAlgorithm(oid.to_string()))?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[rustfmt::skip::macros(vec)]
+mod tests {
+    use super::*;
+
+    const ROOT: &str = include_str!("../testdata/x509/root.pem");
+    const OTHER_ROOT: &str = include_str!("../testdata/x509/other-root.pem");
+    const INTERMEDIATE: &str = include_str!("../testdata/x509/intermediate.pem");
+    const LEAF: &str = include_str!("../testdata/x509/leaf.pem");
+    const LEAF_EXPIRED: &str = include_str!("../testdata/x509/leaf-expired.pem");
+    const LEAF_NO_DIGITAL_SIGNATURE: &str =
+        include_str!("../testdata/x509/leaf-no-digital-signature.pem");
+    const LEAF_CRITICAL_EXTENSION: &str =
+        include_str!("../testdata/x509/leaf-critical-extension.pem");
+    const CONSTRAINED_ROOT: &str = include_str!("../testdata/x509/constrained-root.pem");
+    const CONSTRAINED_INTERMEDIATE: &str =
+        include_str!("../testdata/x509/constrained-intermediate.pem");
+    const CONSTRAINED_LEAF: &str = include_str!("../testdata/x509/constrained-leaf.pem");
+
+    // 2025-01-01T00:00:00Z
+    const NOW: u64 = 1_735_689_600;
+
+    fn pem_to_der(pem: &str) -> Vec<u8> {
+        der::pem::decode_vec(pem.as_bytes()).unwrap().1
+    }
+
+    fn anchors() -> TrustAnchorStore {
+        let mut anchors = TrustAnchorStore::new();
+        anchors.add_pem(ROOT).unwrap();
+        anchors
+    }
+
+    #[test]
+    fn test_validate_chain() {
+        let chain = vec![pem_to_der(LEAF), pem_to_der(INTERMEDIATE)];
+
+        let validated = anchors().validate_at(&chain, NOW).unwrap();
+
+        assert_eq!(validated.certificates().len(), 2);
+        assert_eq!(validated.anchor(), anchors().iter().next().unwrap());
+        assert!(matches!(
+            validated.leaf_verifying_key().unwrap(),
+            CoseVerifyingKey::P256(_)
+        ));
+
+        // Including the anchor itself at the end of the chain is also accepted
+        let chain = vec![pem_to_der(LEAF), pem_to_der(INTERMEDIATE), pem_to_der(ROOT)];
+        anchors().validate_at(&chain, NOW).unwrap();
+    }
+
+    #[test]
+    fn test_validate_chain_failures() {
+        let anchors = anchors();
+        let intermediate = pem_to_der(INTERMEDIATE);
+
+        let empty: Vec<Vec<u8>> = vec![];
+        assert!(matches!(
+            anchors.validate_at(&empty, NOW),
+            Err(crate::Error::X509(X509Error::EmptyChain))
+        ));
+
+        assert!(matches!(
+            anchors.validate_at(&[vec![0x30, 0x00]], NOW),
+            Err(crate::Error::X509(X509Error::InvalidCertificate(_)))
+        ));
+
+        assert!(matches!(
+            anchors.validate_at(&[pem_to_der(LEAF_EXPIRED), intermediate.clone()], NOW),
+            Err(crate::Error::X509(X509Error::Expired { .. }))
+        ));
+
+        // 2020-01-01T00:00:00Z, before the leaf was issued
+        assert!(matches!(
+            anchors.validate_at(&[pem_to_der(LEAF), intermediate.clone()], 1_577_836_800),
+            Err(crate::Error::X509(X509Error::NotYetValid { .. }))
+        ));
+
+        assert!(matches!(
+            anchors.validate_at(
+                &[pem_to_der(LEAF_NO_DIGITAL_SIGNATURE), intermediate.clone()],
+                NOW
+            ),
+            Err(crate::Error::X509(X509Error::MissingKeyUsage {
+                usage: "digitalSignature",
+                ..
+            }))
+        ));
+
+        // The leaf is not a CA and cannot issue certificates
+        assert!(matches!(
+            anchors.validate_at(&[pem_to_der(LEAF), pem_to_der(LEAF)], NOW),
+            Err(crate::Error::X509(X509Error::NotACa { .. }))
+        ));
+
+        // The leaf is not issued directly by the root
+        assert!(matches!(
+            anchors.validate_at(&[pem_to_der(LEAF), pem_to_der(ROOT)], NOW),
+            Err(crate::Error::X509(X509Error::IssuerMismatch { .. }))
+        ));
+
+        let mut untrusted = TrustAnchorStore::new();
+        untrusted.add_pem(OTHER_ROOT).unwrap();
+        assert!(matches!(
+            untrusted.validate_at(&[pem_to_der(LEAF), intermediate], NOW),
+            Err(crate::Error::X509(X509Error::UntrustedRoot { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_validate_chain_constraints() {
+        let leaf = pem_to_der(CONSTRAINED_LEAF);
+        let intermediate = pem_to_der(CONSTRAINED_INTERMEDIATE);
+
+        let mut anchors = TrustAnchorStore::new();
+        anchors.add_pem(CONSTRAINED_INTERMEDIATE).unwrap();
+        anchors.validate_at(core::slice::from_ref(&leaf), NOW).unwrap();
+
+        assert!(matches!(
+            anchors.validate_at(&[pem_to_der(LEAF_CRITICAL_EXTENSION)], NOW),
+            Err(crate::Error::X509(X509Error::UnrecognizedCriticalExtension { oid, .. }))
+                if oid == "1.3.6.1.4.1.55555.1"
+        ));
+
+        // The root only allows leaf certificates below it (pathLenConstraint 0), whether or not
+        // it is included in the chain
+        let mut anchors = TrustAnchorStore::new();
+        anchors.add_pem(CONSTRAINED_ROOT).unwrap();
+        assert!(matches!(
+            anchors.validate_at(&[leaf.clone(), intermediate.clone()], NOW),
+            Err(crate::Error::X509(X509Error::PathLenExceeded { .. }))
+        ));
+        assert!(matches!(
+            anchors.validate_at(&[leaf, intermediate, pem_to_der(CONSTRAINED_ROOT)], NOW),
+            Err(crate::Error::X509(X509Error::PathLenExceeded { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_tampered_certificate_signature() {
+        let mut leaf = pem_to_der(LEAF);
+        let last = leaf.len() - 1;
+        leaf[last] ^= 0x01;
+
+        assert!(matches!(
+            anchors().validate_at(&[leaf, pem_to_der(INTERMEDIATE)], NOW),
+            Err(crate::Error::X509(X509Error::InvalidSignature { .. }))
+        ));
+    }
+}